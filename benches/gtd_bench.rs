@@ -0,0 +1,141 @@
+//! Benchmarks for the hot paths on large GTD files
+//!
+//! Covers load, save, list filtering, and batch status changes on synthetic
+//! datasets of 10k and 100k notas (see `tests/common/dataset.rs`, which the
+//! tests share).
+//!
+//! ```bash
+//! cargo bench                                # every benchmark
+//! cargo bench -- list                        # only benchmarks whose name contains "list"
+//! GTD_BENCH_SIZES=1000,50000 cargo bench     # custom dataset sizes
+//! ```
+//!
+//! Measured with criterion; reports and the comparison with the previous run
+//! are written to `target/criterion/`.
+
+#[path = "../tests/common/dataset.rs"]
+mod dataset;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use gtd_mcp::formatting;
+use gtd_mcp::{GtdServerHandler, NotaStatus, Storage, local_date_today};
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Dataset sizes used when `GTD_BENCH_SIZES` is not set
+const DEFAULT_SIZES: [usize; 2] = [10_000, 100_000];
+
+/// Time budget per benchmark after warm-up
+const MEASURE_TIME: Duration = Duration::from_secs(2);
+
+/// Samples per benchmark (criterion's minimum; a 100k save takes a while)
+const SAMPLE_SIZE: usize = 10;
+
+/// Number of IDs per batch status change (a large agent request)
+const BATCH_SIZE: usize = 50;
+
+/// Dataset sizes from `GTD_BENCH_SIZES` (comma-separated) or the defaults
+fn sizes() -> Vec<usize> {
+    match std::env::var("GTD_BENCH_SIZES") {
+        Ok(value) => value
+            .split(',')
+            .map(|size| {
+                size.trim()
+                    .parse()
+                    .expect("GTD_BENCH_SIZES must be numbers")
+            })
+            .collect(),
+        Err(_) => DEFAULT_SIZES.to_vec(),
+    }
+}
+
+fn benchmarks(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for size in sizes() {
+        let data = dataset::generate(size);
+        let content = Storage::serialize(&data).unwrap();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("gtd.toml");
+        let storage = Storage::new(&path, false);
+        storage.save(&data).unwrap();
+
+        // Persistence
+        c.bench_with_input(BenchmarkId::new("load", size), &storage, |b, storage| {
+            b.iter(|| storage.load_local().unwrap())
+        });
+        c.bench_with_input(BenchmarkId::new("serialize", size), &data, |b, data| {
+            b.iter(|| Storage::serialize(data).unwrap())
+        });
+        c.bench_with_input(BenchmarkId::new("save", size), &content, |b, content| {
+            b.iter(|| storage.write_content(content, "Benchmark").unwrap())
+        });
+
+        // List filtering (query plus formatting, as the list tool does)
+        let project = dataset::project_id(1);
+        let context = dataset::context_name(1);
+        let today = local_date_today();
+        let stale = Some(formatting::DEFAULT_STALE_AFTER_DAYS);
+        c.bench_function(&format!("list_all/{}", size), |b| {
+            b.iter(|| {
+                let notas = data.list_filtered(None, None, None);
+                formatting::format_notas(&data, &notas, true, None, stale, today)
+            })
+        });
+        c.bench_function(&format!("list_status/{}", size), |b| {
+            b.iter(|| {
+                let notas = data.list_filtered(Some(NotaStatus::next_action), None, None);
+                formatting::format_notas(&data, &notas, false, None, stale, today)
+            })
+        });
+        c.bench_function(&format!("list_project_context/{}", size), |b| {
+            b.iter(|| {
+                let notas = data.list_filtered(None, Some(&project), Some(&context));
+                formatting::format_notas(&data, &notas, false, None, stale, today)
+            })
+        });
+        c.bench_function(&format!("list_keyword/{}", size), |b| {
+            b.iter(|| {
+                let matches = data.search("invoice budget");
+                let mut notas = data.list_filtered(None, None, None);
+                notas.retain(|nota| matches.contains(nota.id.as_str()));
+                formatting::format_notas(&data, &notas, false, None, stale, today)
+            })
+        });
+
+        // Batch status changes through the handler, including the save
+        let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+        let ids: Vec<String> = (0..size)
+            .filter(|n| n % 10 == 3)
+            .take(BATCH_SIZE)
+            .map(dataset::task_id)
+            .collect();
+        let mut to_done = true;
+        c.bench_function(
+            &format!("change_status_batch{}/{}", BATCH_SIZE, size),
+            |b| {
+                b.iter(|| {
+                    let status = if to_done { "done" } else { "next_action" };
+                    to_done = !to_done;
+                    runtime
+                        .block_on(handler.change_status(
+                            ids.clone(),
+                            status.to_string(),
+                            None,
+                            None,
+                        ))
+                        .unwrap()
+                })
+            },
+        );
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(SAMPLE_SIZE)
+        .measurement_time(MEASURE_TIME);
+    targets = benchmarks
+}
+criterion_main!(benches);
@@ -0,0 +1,231 @@
+//! Availability schedules of contexts
+//!
+//! A context can declare when it can be used, e.g. `@office` only on weekdays
+//! during office hours. A schedule is one or more windows separated by `;`,
+//! each a day set followed by a time range:
+//!
+//! ```text
+//! Mon-Fri 9-18; Sat 10:00-14:00
+//! ```
+//!
+//! Days are English weekday names (`Mon`, `monday`, ...), ranges (`Mon-Fri`,
+//! which may wrap as in `Fri-Mon`), lists (`Mon,Wed`) or `daily`. Either part
+//! may be left out: a window without days applies every day, and one without
+//! times lasts the whole day. Times are `H`, `HH` or `HH:MM` in the configured
+//! timezone, `24` ends at midnight, and a range must not wrap past midnight.
+//! An en dash (`–`) may be used in place of `-`.
+
+use chrono::{Datelike, NaiveDateTime, Timelike, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+/// Minutes in a day, the end of a window lasting until midnight
+const DAY_MINUTES: u16 = 24 * 60;
+
+/// Weekdays in schedule order
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// One window of a schedule: a set of days and a time range on each of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Window {
+    /// Days the window applies to, indexed from Monday
+    days: [bool; 7],
+    /// Start, in minutes after midnight
+    start: u16,
+    /// End (exclusive), in minutes after midnight
+    end: u16,
+}
+
+/// When a context can be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Availability {
+    windows: Vec<Window>,
+}
+
+impl Availability {
+    /// Whether the context is available at `at` (local time)
+    ///
+    /// # Arguments
+    /// * `at` - Date and time in the configured timezone
+    pub fn is_available(&self, at: NaiveDateTime) -> bool {
+        let day = at.weekday().num_days_from_monday() as usize;
+        let minute = (at.hour() * 60 + at.minute()) as u16;
+        self.windows
+            .iter()
+            .any(|window| window.days[day] && window.start <= minute && minute < window.end)
+    }
+}
+
+impl FromStr for Availability {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.replace('–', "-");
+        let windows = s
+            .split(';')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(parse_window)
+            .collect::<Result<Vec<_>, _>>()?;
+        if windows.is_empty() {
+            return Err(
+                "Availability is empty. Use days and hours such as \"Mon-Fri 9-18\"".to_string(),
+            );
+        }
+        Ok(Availability { windows })
+    }
+}
+
+impl fmt::Display for Availability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let windows: Vec<String> = self
+            .windows
+            .iter()
+            .map(|window| {
+                let mut parts = Vec::new();
+                if window.days != [true; 7] {
+                    parts.push(format_days(&window.days));
+                }
+                if (window.start, window.end) != (0, DAY_MINUTES) {
+                    parts.push(format!(
+                        "{}-{}",
+                        format_time(window.start),
+                        format_time(window.end)
+                    ));
+                }
+                if parts.is_empty() {
+                    "Daily".to_string()
+                } else {
+                    parts.join(" ")
+                }
+            })
+            .collect();
+        write!(f, "{}", windows.join("; "))
+    }
+}
+
+fn parse_window(window: &str) -> Result<Window, String> {
+    let mut days = None;
+    let mut times = None;
+    for part in window.split_whitespace() {
+        let is_time = part.starts_with(|c: char| c.is_ascii_digit());
+        let slot = if is_time { &mut times } else { &mut days };
+        if slot.is_some() {
+            return Err(format!(
+                "Invalid availability '{}': give one day set and one time range per window, and separate windows with ';'",
+                window
+            ));
+        }
+        *slot = Some(part);
+    }
+    let days = match days {
+        Some(days) => parse_days(days)?,
+        None => [true; 7],
+    };
+    let (start, end) = match times {
+        Some(times) => parse_times(times)?,
+        None => (0, DAY_MINUTES),
+    };
+    Ok(Window { days, start, end })
+}
+
+fn parse_days(days: &str) -> Result<[bool; 7], String> {
+    if days.eq_ignore_ascii_case("daily") {
+        return Ok([true; 7]);
+    }
+    let mut set = [false; 7];
+    for item in days.split(',').filter(|item| !item.is_empty()) {
+        let (first, last) = match item.split_once('-') {
+            Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+            None => {
+                let day = parse_day(item)?;
+                (day, day)
+            }
+        };
+        let mut day = first;
+        loop {
+            set[day] = true;
+            if day == last {
+                break;
+            }
+            day = (day + 1) % 7;
+        }
+    }
+    Ok(set)
+}
+
+fn parse_day(day: &str) -> Result<usize, String> {
+    day.parse::<Weekday>()
+        .map(|day| day.num_days_from_monday() as usize)
+        .map_err(|_| {
+            format!(
+                "Invalid day '{}' in availability. Use Mon, Tue, Wed, Thu, Fri, Sat, Sun, a range such as Mon-Fri, or daily",
+                day
+            )
+        })
+}
+
+fn parse_times(times: &str) -> Result<(u16, u16), String> {
+    let invalid = || {
+        format!(
+            "Invalid hours '{}' in availability. Use a range such as 9-18 or 09:30-17:00",
+            times
+        )
+    };
+    let (start, end) = times.split_once('-').ok_or_else(invalid)?;
+    let start = parse_time(start).ok_or_else(invalid)?;
+    let end = parse_time(end).ok_or_else(invalid)?;
+    if end <= start {
+        return Err(format!(
+            "Invalid hours '{}' in availability: the end must be after the start (ranges cannot wrap past midnight; split them with ';')",
+            times
+        ));
+    }
+    Ok((start, end))
+}
+
+fn parse_time(time: &str) -> Option<u16> {
+    let (hours, minutes) = time.split_once(':').unwrap_or((time, "0"));
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    if hours > 24 || minutes >= 60 {
+        return None;
+    }
+    let total = hours * 60 + minutes;
+    (total <= DAY_MINUTES).then_some(total)
+}
+
+/// Days as ranges of consecutive weekdays, e.g. "Mon-Fri" or "Mon,Wed-Thu"
+fn format_days(days: &[bool; 7]) -> String {
+    let mut runs = Vec::new();
+    let mut day = 0;
+    while day < 7 {
+        if !days[day] {
+            day += 1;
+            continue;
+        }
+        let first = day;
+        while day + 1 < 7 && days[day + 1] {
+            day += 1;
+        }
+        runs.push(if first == day {
+            WEEKDAYS[first].to_string()
+        } else {
+            format!("{}-{}", WEEKDAYS[first], WEEKDAYS[day])
+        });
+        day += 1;
+    }
+    runs.join(",")
+}
+
+fn format_time(minutes: u16) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
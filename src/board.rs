@@ -0,0 +1,155 @@
+//! Kanban-style board of items per status
+//!
+//! The board has one column per stage of the workflow (inbox, next action,
+//! waiting for, done) and one item per cell, so it can be pasted into a chat or
+//! document during a stand-up. Only recently finished items are shown as done,
+//! judged by the day they were last updated.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::{Duration, NaiveDate};
+use std::str::FromStr;
+
+/// Days of finished items shown in the done column, including today
+pub const DONE_DAYS: i64 = 7;
+
+/// Widest cell in the aligned text layout; longer cells are cut with "..."
+pub const TEXT_CELL_WIDTH: usize = 32;
+
+/// Columns of the board, left to right
+const COLUMNS: [(&str, NotaStatus); 4] = [
+    ("Inbox", NotaStatus::inbox),
+    ("Next", NotaStatus::next_action),
+    ("Waiting", NotaStatus::waiting_for),
+    ("Done", NotaStatus::done),
+];
+
+/// How the board is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoardFormat {
+    /// Markdown table
+    #[default]
+    Markdown,
+    /// Plain text with space-aligned columns
+    Text,
+}
+
+impl FromStr for BoardFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(BoardFormat::Markdown),
+            "text" => Ok(BoardFormat::Text),
+            _ => Err(format!(
+                "Invalid board format '{}'. Valid options are: markdown, text",
+                s
+            )),
+        }
+    }
+}
+
+/// Items of each column, in file order
+fn columns<'a>(data: &'a GtdData, project: Option<&str>, today: NaiveDate) -> Vec<Vec<&'a Nota>> {
+    let done_since = today - Duration::days(DONE_DAYS - 1);
+    COLUMNS
+        .iter()
+        .map(|(_, status)| {
+            data.iter()
+                .filter(|n| n.status == *status)
+                .filter(|n| *status != NotaStatus::done || n.updated_at >= done_since)
+                .filter(|n| project.is_none_or(|p| n.project.as_deref() == Some(p)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Render the board
+///
+/// # Arguments
+/// * `data` - The data to render
+/// * `project` - Only show the items of this project
+/// * `format` - Table layout
+/// * `today` - The current date, for the done column
+pub fn render(
+    data: &GtdData,
+    project: Option<&str>,
+    format: BoardFormat,
+    today: NaiveDate,
+) -> String {
+    let columns = columns(data, project, today);
+    let headers: Vec<String> = COLUMNS
+        .iter()
+        .zip(&columns)
+        .map(|((name, _), items)| format!("{} ({})", name, items.len()))
+        .collect();
+    let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+    let cell = |column: usize, row: usize| {
+        columns[column]
+            .get(row)
+            .map(|n| format!("[{}] {}", n.id, n.title))
+            .unwrap_or_default()
+    };
+
+    let mut out = String::new();
+    match format {
+        BoardFormat::Markdown => {
+            out.push_str(&format!("| {} |\n", headers.join(" | ")));
+            out.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+            for row in 0..rows {
+                let cells: Vec<String> = (0..headers.len())
+                    .map(|column| escape_markdown(&cell(column, row)))
+                    .collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+        }
+        BoardFormat::Text => {
+            let grid: Vec<Vec<String>> = std::iter::once(headers.clone())
+                .chain((0..rows).map(|row| {
+                    (0..headers.len())
+                        .map(|column| truncate(&cell(column, row)))
+                        .collect()
+                }))
+                .collect();
+            let widths: Vec<usize> = (0..headers.len())
+                .map(|column| {
+                    grid.iter()
+                        .map(|line| line[column].chars().count())
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+            for (i, line) in grid.iter().enumerate() {
+                let padded: Vec<String> = line
+                    .iter()
+                    .zip(&widths)
+                    .map(|(text, &width)| format!("{:<width$}", text, width = width))
+                    .collect();
+                out.push_str(padded.join("  ").trim_end());
+                out.push('\n');
+                if i == 0 {
+                    let rules: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+                    out.push_str(&rules.join("  "));
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    if rows == 0 {
+        out.push_str("\nNothing on the board\n");
+    }
+    out
+}
+
+/// Make a cell safe inside a Markdown table
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Cut a cell down to [`TEXT_CELL_WIDTH`] characters
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= TEXT_CELL_WIDTH {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(TEXT_CELL_WIDTH - 3).collect();
+    format!("{}...", kept)
+}
@@ -0,0 +1,285 @@
+//! Building a handler from named options
+//!
+//! [`GtdServerHandler::builder`] collects the options of a handler and opens
+//! it in one go, so embedders and tests name only what they change:
+//!
+//! ```no_run
+//! # use gtd_mcp::GtdServerHandler;
+//! # fn main() -> anyhow::Result<()> {
+//! let handler = GtdServerHandler::builder()
+//!     .path("gtd.toml")
+//!     .sync_git(true)
+//!     .read_only(true)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `with_*` methods of the handler stay available; the builder applies
+//! the same ones when it builds.
+
+use crate::commit_message::CommitTemplate;
+use crate::duplicates::DuplicateCheck;
+use crate::gtd::{Clock, Nota, NotaStatus, SystemClock};
+use crate::hooks::Hooks;
+use crate::id_generator::IdGenerator;
+use crate::id_policy::IdPolicy;
+use crate::storage::Storage;
+use crate::{GtdServerHandler, StorageOpener, caldav, wip};
+use anyhow::{Result, bail};
+use chrono::FixedOffset;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Options of a [`GtdServerHandler`] under construction
+#[derive(Default)]
+pub struct GtdServerHandlerBuilder {
+    path: Option<PathBuf>,
+    sync_git: bool,
+    storage: Option<Storage>,
+    timezone: Option<FixedOffset>,
+    read_only: bool,
+    clock: Option<Arc<dyn Clock>>,
+    roots: Option<StorageOpener>,
+    workspaces: Vec<(String, Storage)>,
+    commit_template: Option<CommitTemplate>,
+    instructions: Option<String>,
+    id_policy: Option<IdPolicy>,
+    id_generator: Option<Arc<dyn IdGenerator>>,
+    duplicate_check: Option<DuplicateCheck>,
+    wip_limits: Option<(Vec<wip::WipLimit>, wip::WipMode)>,
+    escalation: Option<(Option<u32>, bool)>,
+    stale_after_days: Option<u32>,
+    trash_retention_days: Option<u32>,
+    transaction_timeout: Option<Duration>,
+    strict: bool,
+    caldav: Option<caldav::CalDavConfig>,
+    hooks: Hooks,
+}
+
+impl GtdServerHandler {
+    /// Start building a handler (see [`builder`](crate::builder))
+    pub fn builder() -> GtdServerHandlerBuilder {
+        GtdServerHandlerBuilder::default()
+    }
+}
+
+impl GtdServerHandlerBuilder {
+    /// Path of the GTD data file (TOML format)
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Enable automatic Git synchronization of the data file given by [`path`](Self::path)
+    pub fn sync_git(mut self, enabled: bool) -> Self {
+        self.sync_git = enabled;
+        self
+    }
+
+    /// Use a pre-configured storage instead of [`path`](Self::path)
+    ///
+    /// For storage options beyond path and Git sync, such as a debounce
+    /// window, a mirror or a webhook.
+    pub fn storage(mut self, storage: Storage) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// UTC offset used for "today" instead of the system timezone
+    ///
+    /// The handler's [`SystemClock`] runs at this offset; ignored when a
+    /// [`clock`](Self::clock) is given.
+    pub fn timezone(mut self, offset: FixedOffset) -> Self {
+        self.timezone = Some(offset);
+        self
+    }
+
+    /// Only serve the tools that read the data (see [`GtdServerHandler::with_read_only`])
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Where "today" comes from (see [`GtdServerHandler::with_clock`])
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Take the data file from the client's workspace roots (see [`GtdServerHandler::with_roots`])
+    pub fn roots(mut self, open: StorageOpener) -> Self {
+        self.roots = Some(open);
+        self
+    }
+
+    /// Serve another data file as a workspace (see [`GtdServerHandler::with_workspace`])
+    pub fn workspace(mut self, name: impl Into<String>, storage: Storage) -> Self {
+        self.workspaces.push((name.into(), storage));
+        self
+    }
+
+    /// Format of Git commit messages (see [`GtdServerHandler::with_commit_template`])
+    pub fn commit_template(mut self, template: CommitTemplate) -> Self {
+        self.commit_template = Some(template);
+        self
+    }
+
+    /// Instructions sent to clients (see [`GtdServerHandler::with_instructions`])
+    pub fn instructions(mut self, text: impl Into<String>) -> Self {
+        self.instructions = Some(text.into());
+        self
+    }
+
+    /// ID rules for new items (see [`GtdServerHandler::with_id_policy`])
+    pub fn id_policy(mut self, policy: IdPolicy) -> Self {
+        self.id_policy = Some(policy);
+        self
+    }
+
+    /// IDs of captured items and recurring occurrences (see [`GtdServerHandler::with_id_generator`])
+    pub fn id_generator(mut self, generator: Arc<dyn IdGenerator>) -> Self {
+        self.id_generator = Some(generator);
+        self
+    }
+
+    /// Handling of near-duplicate titles (see [`GtdServerHandler::with_duplicate_check`])
+    pub fn duplicate_check(mut self, check: DuplicateCheck) -> Self {
+        self.duplicate_check = Some(check);
+        self
+    }
+
+    /// Items a status may hold (see [`GtdServerHandler::with_wip_limits`])
+    pub fn wip_limits(mut self, limits: Vec<wip::WipLimit>, mode: wip::WipMode) -> Self {
+        self.wip_limits = Some((limits, mode));
+        self
+    }
+
+    /// Escalation of long waiting items (see [`GtdServerHandler::with_escalation`])
+    pub fn escalation(mut self, after_days: Option<u32>, to_next_action: bool) -> Self {
+        self.escalation = Some((after_days, to_next_action));
+        self
+    }
+
+    /// Days before `list` flags a next action (see [`GtdServerHandler::with_stale_after_days`])
+    pub fn stale_after_days(mut self, days: u32) -> Self {
+        self.stale_after_days = Some(days);
+        self
+    }
+
+    /// Days an item stays in the trash (see [`GtdServerHandler::with_trash_retention`])
+    pub fn trash_retention(mut self, days: u32) -> Self {
+        self.trash_retention_days = Some(days);
+        self
+    }
+
+    /// Idle time before another client may abort a transaction (see [`GtdServerHandler::with_transaction_timeout`])
+    pub fn transaction_timeout(mut self, timeout: Duration) -> Self {
+        self.transaction_timeout = Some(timeout);
+        self
+    }
+
+    /// Validate the data before every save (see [`GtdServerHandler::with_strict_saves`])
+    pub fn strict_saves(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// CalDAV task list for `sync_caldav` (see [`GtdServerHandler::with_caldav`])
+    pub fn caldav(mut self, config: caldav::CalDavConfig) -> Self {
+        self.caldav = Some(config);
+        self
+    }
+
+    /// Call `hook` for every item a save creates (see [`crate::hooks`])
+    pub fn on_created(mut self, hook: impl Fn(&Nota) + Send + Sync + 'static) -> Self {
+        self.hooks = self.hooks.on_created(hook);
+        self
+    }
+
+    /// Call `hook` for every status change a save makes, with the previous status
+    pub fn on_status_changed(
+        mut self,
+        hook: impl Fn(&Nota, &NotaStatus) + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks = self.hooks.on_status_changed(hook);
+        self
+    }
+
+    /// Call `hook` for every item a save removes from the file
+    pub fn on_deleted(mut self, hook: impl Fn(&Nota) + Send + Sync + 'static) -> Self {
+        self.hooks = self.hooks.on_deleted(hook);
+        self
+    }
+
+    /// Open the data file and build the handler
+    ///
+    /// # Returns
+    /// The handler, or an error when neither a path nor a storage was given,
+    /// or a data file cannot be loaded
+    pub fn build(self) -> Result<GtdServerHandler> {
+        let storage = match (self.storage, self.path) {
+            (Some(storage), _) => storage,
+            (None, Some(path)) => Storage::new(path, self.sync_git),
+            (None, None) => bail!("No data file: give the builder a path or a storage"),
+        };
+        let mut handler = GtdServerHandler::with_storage(storage)?;
+        if let Some(open) = self.roots {
+            handler = handler.with_roots(open);
+        }
+        for (name, storage) in self.workspaces {
+            handler = handler.with_workspace(&name, storage)?;
+        }
+        if let Some(template) = self.commit_template {
+            handler = handler.with_commit_template(template);
+        }
+        if let Some(text) = self.instructions {
+            handler = handler.with_instructions(text);
+        }
+        if let Some(policy) = self.id_policy {
+            handler = handler.with_id_policy(policy);
+        }
+        if let Some(generator) = self.id_generator {
+            handler = handler.with_id_generator(generator);
+        }
+        if let Some(check) = self.duplicate_check {
+            handler = handler.with_duplicate_check(check);
+        }
+        if let Some((limits, mode)) = self.wip_limits {
+            handler = handler.with_wip_limits(limits, mode);
+        }
+        if let Some((after_days, to_next_action)) = self.escalation {
+            handler = handler.with_escalation(after_days, to_next_action);
+        }
+        if let Some(days) = self.stale_after_days {
+            handler = handler.with_stale_after_days(days);
+        }
+        if let Some(days) = self.trash_retention_days {
+            handler = handler.with_trash_retention(days);
+        }
+        if let Some(timeout) = self.transaction_timeout {
+            handler = handler.with_transaction_timeout(timeout);
+        }
+        if self.strict {
+            handler = handler.with_strict_saves();
+        }
+        if let Some(config) = self.caldav {
+            handler = handler.with_caldav(config);
+        }
+        let clock = self.clock.or_else(|| {
+            self.timezone
+                .map(|offset| Arc::new(SystemClock::with_offset(offset)) as Arc<dyn Clock>)
+        });
+        if let Some(clock) = clock {
+            handler = handler.with_clock(clock);
+        }
+        if !self.hooks.is_empty() {
+            handler = handler.with_hooks(self.hooks);
+        }
+        if self.read_only {
+            handler = handler.with_read_only();
+        }
+        Ok(handler)
+    }
+}
@@ -0,0 +1,243 @@
+//! CalDAV requests over HTTP (see [`crate::http`])
+
+use super::vtodo;
+use super::{CalDavConfig, CalDavServer, RemoteTodo};
+use crate::http::{self, Request, Response};
+use anyhow::{Result, bail};
+
+/// Body of the REPORT listing every VTODO with its ETag
+const CALENDAR_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+  <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter></c:filter>
+</c:calendar-query>
+"#;
+
+/// A CalDAV task list reached over HTTP
+pub struct CurlClient {
+    /// Collection URL, ending with '/'
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl CurlClient {
+    /// Create a client for the collection of `config`
+    pub fn new(config: &CalDavConfig) -> Self {
+        let mut url = config.url.clone();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        Self {
+            url,
+            username: config.username.clone(),
+            password: config.password.clone(),
+        }
+    }
+
+    /// Scheme and host of the collection URL (e.g. `https://cloud.example.com`)
+    fn origin(&self) -> &str {
+        let start = self.url.find("://").map_or(0, |i| i + 3);
+        let end = self.url[start..]
+            .find('/')
+            .map_or(self.url.len(), |i| start + i);
+        &self.url[..end]
+    }
+
+    /// Full URL of an href from a multistatus response
+    fn resolve(&self, href: &str) -> String {
+        if href.contains("://") {
+            href.to_string()
+        } else if href.starts_with('/') {
+            format!("{}{}", self.origin(), href)
+        } else {
+            format!("{}{}", self.url, href)
+        }
+    }
+
+    /// Run one request with the configured credentials
+    fn request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&str>,
+    ) -> Result<Response> {
+        http::send(Request {
+            method,
+            url,
+            credentials: self
+                .username
+                .as_deref()
+                .map(|username| (username, self.password.as_deref().unwrap_or_default())),
+            headers,
+            body,
+        })
+    }
+}
+
+impl CalDavServer for CurlClient {
+    fn list(&mut self) -> Result<Vec<RemoteTodo>> {
+        let response = self.request(
+            "REPORT",
+            &self.url,
+            &[
+                ("Depth", "1"),
+                ("Content-Type", "application/xml; charset=utf-8"),
+            ],
+            Some(CALENDAR_QUERY),
+        )?;
+        if response.status != 207 {
+            bail!(
+                "Listing {} failed with HTTP {} (is it a CalDAV task list?)",
+                self.url,
+                response.status
+            );
+        }
+        Ok(parse_multistatus(&response.body)
+            .into_iter()
+            .filter_map(|(href, etag, data)| {
+                let todo = vtodo::parse(&data)?;
+                Some(RemoteTodo {
+                    href: self.resolve(&href),
+                    etag,
+                    uid: todo.uid,
+                    fields: todo.fields,
+                    last_modified: todo.last_modified,
+                })
+            })
+            .collect())
+    }
+
+    fn new_href(&self, name: &str) -> String {
+        format!("{}{}.ics", self.url, name)
+    }
+
+    fn put(&mut self, href: &str, ics: &str, etag: Option<&str>) -> Result<Option<String>> {
+        let precondition = match etag {
+            Some(etag) => ("If-Match", etag),
+            None => ("If-None-Match", "*"),
+        };
+        let response = self.request(
+            "PUT",
+            href,
+            &[
+                ("Content-Type", "text/calendar; charset=utf-8"),
+                precondition,
+            ],
+            Some(ics),
+        )?;
+        match response.status {
+            200..=299 => Ok(response.header("ETag").map(str::to_string)),
+            412 => bail!("{} changed on the server during the sync", href),
+            status => bail!("PUT {} failed with HTTP {}", href, status),
+        }
+    }
+
+    fn delete(&mut self, href: &str, etag: Option<&str>) -> Result<()> {
+        let headers: Vec<(&str, &str)> = etag.map(|etag| ("If-Match", etag)).into_iter().collect();
+        let response = self.request("DELETE", href, &headers, None)?;
+        match response.status {
+            200..=299 | 404 => Ok(()),
+            412 => bail!("{} changed on the server during the sync", href),
+            status => bail!("DELETE {} failed with HTTP {}", href, status),
+        }
+    }
+}
+
+/// The href, ETag and calendar data of every response in a multistatus body
+pub fn parse_multistatus(xml: &str) -> Vec<(String, Option<String>, String)> {
+    elements(xml, "response")
+        .into_iter()
+        .filter_map(|response| {
+            let href = elements(response, "href").into_iter().next()?;
+            let data = elements(response, "calendar-data").into_iter().next()?;
+            let etag = elements(response, "getetag")
+                .into_iter()
+                .next()
+                .map(xml_text)
+                .filter(|etag| !etag.is_empty());
+            Some((xml_text(href), etag, xml_text(data)))
+        })
+        .collect()
+}
+
+/// Contents of every `<name>` element, whatever its namespace prefix
+///
+/// Elements of the same name are assumed not to nest, which holds for the
+/// parts of a multistatus body read here.
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        let tag_end = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let tag = &rest[..tag_end];
+        let local = tag.rsplit(':').next().unwrap_or(tag);
+        if local != name {
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        if rest[..close].ends_with('/') {
+            found.push("");
+            rest = &rest[close + 1..];
+            continue;
+        }
+        let content = &rest[close + 1..];
+        let end_tag = format!("</{}>", tag);
+        let Some(end) = content.find(&end_tag) else {
+            break;
+        };
+        found.push(&content[..end]);
+        rest = &content[end + end_tag.len()..];
+    }
+    found
+}
+
+/// Text of an element: CDATA sections kept as is, entities decoded
+fn xml_text(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut rest = raw.trim();
+    while !rest.is_empty() {
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            text.push_str(&cdata[..end]);
+            rest = cdata.get(end + 3..).unwrap_or_default();
+        } else if let Some(entity) = rest.strip_prefix('&')
+            && let Some(end) = entity.find(';')
+        {
+            let decoded = match &entity[..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                code => code
+                    .strip_prefix("#x")
+                    .or_else(|| code.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| code.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            match decoded {
+                Some(c) => {
+                    text.push(c);
+                    rest = &entity[end + 1..];
+                }
+                None => {
+                    text.push('&');
+                    rest = entity;
+                }
+            }
+        } else {
+            let c = rest.chars().next().expect("rest is not empty");
+            text.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    text
+}
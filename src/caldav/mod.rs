@@ -0,0 +1,511 @@
+//! Two-way sync with a CalDAV task list
+//!
+//! Mirrors `next_action` and `calendar` items to a VTODO collection (Nextcloud
+//! Tasks, or any CalDAV server), so they can be checked off on a phone without
+//! an MCP client. Each sync:
+//! - sends new and changed items to the server
+//! - adopts tasks created on the server as next actions (calendar items when
+//!   they have a due date)
+//! - applies titles, notes, due dates and completions changed on the server
+//! - marks an item done on the server when it is done here, and removes it
+//!   from the server when it moves to any other status or is deleted here
+//! - trashes an item here when it was deleted on the server and is unchanged
+//!   here
+//!
+//! What was last synced is kept in [`SyncState`] next to the data file
+//! (`gtd.caldav.toml` for `gtd.toml`), so each side's changes can be told
+//! apart; an item changed on both sides is settled by the [`ConflictPolicy`].
+
+mod client;
+pub mod vtodo;
+
+pub use client::{CurlClient, parse_multistatus};
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::progress::Cancellation;
+use crate::slug;
+use anyhow::{Context as _, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use vtodo::TodoFields;
+
+/// How an item changed on both sides since the last sync is settled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the side changed last (the local version on the same day)
+    #[default]
+    Newer,
+    /// Always keep the local version
+    Local,
+    /// Always keep the server version
+    Remote,
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "newer" => Ok(ConflictPolicy::Newer),
+            "local" => Ok(ConflictPolicy::Local),
+            "remote" | "server" => Ok(ConflictPolicy::Remote),
+            _ => Err(format!(
+                "Invalid CalDAV conflict policy '{}'. Valid options are: newer, local, remote",
+                s
+            )),
+        }
+    }
+}
+
+/// Where and how to sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalDavConfig {
+    /// URL of the task list (VTODO collection)
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub conflict: ConflictPolicy,
+}
+
+/// A task on the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTodo {
+    pub href: String,
+    pub etag: Option<String>,
+    pub uid: String,
+    pub fields: TodoFields,
+    pub last_modified: Option<NaiveDate>,
+}
+
+/// Requests the sync needs from a CalDAV server
+pub trait CalDavServer {
+    /// Every task of the collection
+    fn list(&mut self) -> Result<Vec<RemoteTodo>>;
+    /// URL for a new task named `name`
+    fn new_href(&self, name: &str) -> String;
+    /// Create (no `etag`) or replace a task, returning its new ETag if the server sent one
+    fn put(&mut self, href: &str, ics: &str, etag: Option<&str>) -> Result<Option<String>>;
+    /// Delete a task
+    fn delete(&mut self, href: &str, etag: Option<&str>) -> Result<()>;
+}
+
+/// A server that refuses to start changing tasks once a sync was cancelled
+///
+/// Listing and every change up to the first are refused after the
+/// cancellation, so a cancelled sync leaves the task list as it was. Once a
+/// change went through, the rest follow, since stopping half way would leave
+/// the server ahead of the sync state.
+pub struct Cancellable<S> {
+    server: S,
+    cancellation: Cancellation,
+    changed: bool,
+}
+
+impl<S: CalDavServer> Cancellable<S> {
+    /// Wrap `server`, checking `cancellation` until the first change
+    pub fn new(server: S, cancellation: Cancellation) -> Self {
+        Self {
+            server,
+            cancellation,
+            changed: false,
+        }
+    }
+
+    fn check(&mut self) -> Result<()> {
+        if !self.changed {
+            self.cancellation.check()?;
+            self.changed = true;
+        }
+        Ok(())
+    }
+}
+
+impl<S: CalDavServer> CalDavServer for Cancellable<S> {
+    fn list(&mut self) -> Result<Vec<RemoteTodo>> {
+        self.cancellation.check()?;
+        self.server.list()
+    }
+
+    fn new_href(&self, name: &str) -> String {
+        self.server.new_href(name)
+    }
+
+    fn put(&mut self, href: &str, ics: &str, etag: Option<&str>) -> Result<Option<String>> {
+        self.check()?;
+        self.server.put(href, ics, etag)
+    }
+
+    fn delete(&mut self, href: &str, etag: Option<&str>) -> Result<()> {
+        self.check()?;
+        self.server.delete(href, etag)
+    }
+}
+
+/// An item as it was after the last sync
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedItem {
+    pub href: String,
+    pub uid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    pub fields: TodoFields,
+}
+
+/// What was last synced, by item ID
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    #[serde(default)]
+    pub items: BTreeMap<String, SyncedItem>,
+}
+
+impl SyncState {
+    /// Read the state file, or start empty if there is none
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("Invalid CalDAV sync state {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Write the state file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Path of the sync state kept next to a data file (`gtd.toml` → `gtd.caldav.toml`)
+pub fn state_path(data_file: &Path) -> PathBuf {
+    data_file.with_extension("caldav.toml")
+}
+
+/// What a sync changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Items created or changed here from the server, with their status before (None if new)
+    pub received: Vec<(String, Option<NotaStatus>)>,
+    /// Items sent to the server
+    pub sent: Vec<String>,
+    /// Items removed from the server
+    pub removed: Vec<String>,
+    /// Items changed on both sides, and which version was kept
+    pub conflicts: Vec<String>,
+    /// Failed requests; their items are retried on the next sync
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for SyncReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.received.is_empty()
+            && self.sent.is_empty()
+            && self.removed.is_empty()
+            && self.errors.is_empty()
+        {
+            return write!(
+                f,
+                "Already in sync with the CalDAV task list - nothing received or sent."
+            );
+        }
+        write!(
+            f,
+            "CalDAV sync: {} received, {} sent, {} removed from the server.",
+            self.received.len(),
+            self.sent.len(),
+            self.removed.len()
+        )?;
+        let received: Vec<&str> = self.received.iter().map(|(id, _)| id.as_str()).collect();
+        for (label, ids) in [
+            ("Received", received),
+            ("Sent", self.sent.iter().map(String::as_str).collect()),
+            ("Removed", self.removed.iter().map(String::as_str).collect()),
+        ] {
+            if !ids.is_empty() {
+                write!(f, "\n{}: {}", label, ids.join(", "))?;
+            }
+        }
+        for (label, lines) in [
+            ("Conflicts", &self.conflicts),
+            ("Errors (retried on the next sync)", &self.errors),
+        ] {
+            if !lines.is_empty() {
+                write!(f, "\n\n{}:\n- {}", label, lines.join("\n- "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether an item is mirrored to the server (done items only once they were synced)
+fn in_scope(nota: &Nota, synced: bool) -> bool {
+    match nota.status {
+        NotaStatus::next_action | NotaStatus::calendar => true,
+        NotaStatus::done => synced,
+        _ => false,
+    }
+}
+
+/// The synced fields of a local item
+fn local_fields(nota: &Nota) -> TodoFields {
+    TodoFields {
+        summary: nota.title.clone(),
+        description: nota.notes.clone(),
+        due: nota.start_date,
+        completed: nota.status == NotaStatus::done,
+    }
+}
+
+/// Apply the server's fields to a local item
+fn apply_remote(nota: &mut Nota, fields: &TodoFields, today: NaiveDate) {
+    nota.title = fields.summary.clone();
+    nota.notes = fields.description.clone();
+    nota.start_date = fields.due;
+    nota.status = match (&nota.status, fields.completed, fields.due) {
+        (_, true, _) => NotaStatus::done,
+        // Reopened on the server
+        (NotaStatus::done, false, Some(_)) => NotaStatus::calendar,
+        (NotaStatus::done | NotaStatus::calendar, false, None) => NotaStatus::next_action,
+        (status, ..) => status.clone(),
+    };
+    nota.updated_at = today;
+}
+
+/// Name of the server object for a local ID (letters, digits, '-' and '_')
+fn object_name(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Sync `data` with the server, updating `state` to what was synced
+///
+/// Fails only if the task list cannot be read; failed changes of single items
+/// are listed in the report's errors and retried on the next sync.
+///
+/// # Arguments
+/// * `data` - The data to sync
+/// * `state` - What was synced last time
+/// * `server` - The task list
+/// * `policy` - How to settle items changed on both sides
+/// * `today` - Date of local changes
+pub fn sync(
+    data: &mut GtdData,
+    state: &mut SyncState,
+    server: &mut dyn CalDavServer,
+    policy: ConflictPolicy,
+    today: NaiveDate,
+) -> Result<SyncReport> {
+    let remote_todos = server.list()?;
+    let remote: HashMap<&str, &RemoteTodo> = remote_todos
+        .iter()
+        .map(|todo| (todo.href.as_str(), todo))
+        .collect();
+    let mut report = SyncReport::default();
+
+    // Relink items this server already knows when the state was lost
+    let mut known: HashSet<String> = state.items.values().map(|s| s.href.clone()).collect();
+    for nota in data.iter() {
+        if !in_scope(nota, false) || state.items.contains_key(&nota.id) {
+            continue;
+        }
+        let uid = vtodo::uid_for(&nota.id);
+        if let Some(todo) = remote_todos
+            .iter()
+            .find(|todo| todo.uid == uid && !known.contains(&todo.href))
+        {
+            known.insert(todo.href.clone());
+            state.items.insert(
+                nota.id.clone(),
+                SyncedItem {
+                    href: todo.href.clone(),
+                    uid,
+                    etag: todo.etag.clone(),
+                    fields: todo.fields.clone(),
+                },
+            );
+        }
+    }
+
+    let synced_ids: Vec<String> = state.items.keys().cloned().collect();
+    for id in synced_ids {
+        let synced = state.items[&id].clone();
+        let local = data.find_by_id(&id).filter(|nota| in_scope(nota, true));
+        let todo = remote.get(synced.href.as_str()).copied();
+        match (local, todo) {
+            (None, None) => {
+                state.items.remove(&id);
+            }
+            // Moved to another status or deleted here
+            (None, Some(todo)) => match server.delete(&todo.href, todo.etag.as_deref()) {
+                Ok(()) => {
+                    state.items.remove(&id);
+                    report.removed.push(id);
+                }
+                Err(e) => report.errors.push(format!("{}: {}", id, e)),
+            },
+            // Deleted on the server
+            (Some(mut nota), None) => {
+                if local_fields(&nota) == synced.fields {
+                    let old_status = nota.status.clone();
+                    nota.set_status(NotaStatus::trash, today);
+                    nota.updated_at = today;
+                    data.update(&id, nota);
+                    state.items.remove(&id);
+                    report.received.push((id, Some(old_status)));
+                } else {
+                    report.conflicts.push(format!(
+                        "{}: deleted on the server but changed here, sent again",
+                        id
+                    ));
+                    let synced = SyncedItem {
+                        etag: None,
+                        ..synced
+                    };
+                    push(server, state, &mut report, &nota, synced, None);
+                }
+            }
+            (Some(mut nota), Some(todo)) => {
+                let fields = local_fields(&nota);
+                let local_changed = fields != synced.fields;
+                let remote_changed = todo.fields != synced.fields;
+                let keep_local = match (local_changed, remote_changed) {
+                    (_, false) => local_changed,
+                    (false, true) => false,
+                    (true, true) if fields == todo.fields => false,
+                    (true, true) => {
+                        let keep_local = match policy {
+                            ConflictPolicy::Local => true,
+                            ConflictPolicy::Remote => false,
+                            ConflictPolicy::Newer => todo
+                                .last_modified
+                                .is_none_or(|modified| nota.updated_at >= modified),
+                        };
+                        report.conflicts.push(format!(
+                            "{}: changed on both sides, kept the {} version",
+                            id,
+                            if keep_local { "local" } else { "server" }
+                        ));
+                        keep_local
+                    }
+                };
+                if keep_local {
+                    let etag = todo.etag.clone();
+                    push(server, state, &mut report, &nota, synced, etag.as_deref());
+                } else {
+                    if remote_changed && fields != todo.fields {
+                        let old_status = nota.status.clone();
+                        apply_remote(&mut nota, &todo.fields, today);
+                        data.update(&id, nota);
+                        report.received.push((id.clone(), Some(old_status)));
+                    }
+                    state.items.insert(
+                        id,
+                        SyncedItem {
+                            etag: todo.etag.clone(),
+                            fields: todo.fields.clone(),
+                            ..synced
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    // Items new to the sync
+    let new_items: Vec<Nota> = data
+        .iter()
+        .filter(|nota| in_scope(nota, false) && !state.items.contains_key(&nota.id))
+        .cloned()
+        .collect();
+    for nota in new_items {
+        let name = object_name(&nota.id);
+        let mut href = server.new_href(&name);
+        let mut suffix = 2;
+        while known.contains(&href) || remote.contains_key(href.as_str()) {
+            href = server.new_href(&format!("{}-{}", name, suffix));
+            suffix += 1;
+        }
+        known.insert(href.clone());
+        let synced = SyncedItem {
+            href,
+            uid: vtodo::uid_for(&nota.id),
+            etag: None,
+            fields: TodoFields::default(),
+        };
+        push(server, state, &mut report, &nota, synced, None);
+    }
+
+    // Tasks created on the server
+    for todo in &remote_todos {
+        if known.contains(&todo.href) || todo.fields.completed {
+            continue;
+        }
+        if todo.fields.summary.trim().is_empty() {
+            report
+                .errors
+                .push(format!("{}: task has no title, not added", todo.href));
+            continue;
+        }
+        let mut nota = Nota {
+            id: slug::unique_id(data, &todo.fields.summary),
+            status: NotaStatus::next_action,
+            created_at: today,
+            ..Default::default()
+        };
+        apply_remote(&mut nota, &todo.fields, today);
+        if nota.start_date.is_some() {
+            nota.status = NotaStatus::calendar;
+        }
+        state.items.insert(
+            nota.id.clone(),
+            SyncedItem {
+                href: todo.href.clone(),
+                uid: todo.uid.clone(),
+                etag: todo.etag.clone(),
+                fields: todo.fields.clone(),
+            },
+        );
+        report.received.push((nota.id.clone(), None));
+        data.add(nota);
+    }
+
+    Ok(report)
+}
+
+/// Send a local item, recording it as synced on success
+fn push(
+    server: &mut dyn CalDavServer,
+    state: &mut SyncState,
+    report: &mut SyncReport,
+    nota: &Nota,
+    synced: SyncedItem,
+    etag: Option<&str>,
+) {
+    let fields = local_fields(nota);
+    let ics = vtodo::render(&synced.uid, &fields, nota.updated_at);
+    match server.put(&synced.href, &ics, etag) {
+        Ok(etag) => {
+            state.items.insert(
+                nota.id.clone(),
+                SyncedItem {
+                    etag,
+                    fields,
+                    ..synced
+                },
+            );
+            report.sent.push(nota.id.clone());
+        }
+        Err(e) => report.errors.push(format!("{}: {}", nota.id, e)),
+    }
+}
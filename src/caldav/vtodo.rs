@@ -0,0 +1,161 @@
+//! VTODO rendering and parsing
+//!
+//! Only the fields both sides understand are synced: the title (`SUMMARY`),
+//! notes (`DESCRIPTION`), start date (`DUE`, as a date) and completion
+//! (`STATUS:COMPLETED`).
+
+use crate::ical::{UID_DOMAIN, escape_text, fold_line};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The synced fields of a task, as stored locally or on the server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoFields {
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// A VTODO read from the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedTodo {
+    pub uid: String,
+    pub fields: TodoFields,
+    /// Date of `LAST-MODIFIED`, used by the `newer` conflict policy
+    pub last_modified: Option<NaiveDate>,
+}
+
+/// UID of the VTODO created for a local item
+pub fn uid_for(id: &str) -> String {
+    format!("{}@{}", id, UID_DOMAIN)
+}
+
+/// Render a calendar object holding one VTODO
+///
+/// # Arguments
+/// * `uid` - UID of the task (kept from the server for tasks created there)
+/// * `fields` - Synced fields
+/// * `updated` - Date of the last local change (completion date of done items)
+pub fn render(uid: &str, fields: &TodoFields, updated: NaiveDate) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!(
+            "PRODID:-//gtd-mcp//gtd-mcp {}//EN",
+            env!("CARGO_PKG_VERSION")
+        ),
+        "BEGIN:VTODO".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", Utc::now().format("%Y%m%dT%H%M%SZ")),
+        format!("SUMMARY:{}", escape_text(&fields.summary)),
+    ];
+    if let Some(description) = &fields.description {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    if let Some(due) = fields.due {
+        lines.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+    }
+    if fields.completed {
+        lines.push("STATUS:COMPLETED".to_string());
+        lines.push(format!("COMPLETED:{}T000000Z", updated.format("%Y%m%d")));
+    } else {
+        lines.push("STATUS:NEEDS-ACTION".to_string());
+    }
+    lines.push("END:VTODO".to_string());
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in lines {
+        fold_line(&mut ics, &line);
+    }
+    ics
+}
+
+/// Parse the first VTODO of a calendar object
+///
+/// # Returns
+/// The task, or None when the object has no VTODO with a UID
+pub fn parse(ics: &str) -> Option<ParsedTodo> {
+    let mut todo: Option<ParsedTodo> = None;
+    let mut in_todo = false;
+    for line in unfold(ics) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Parameters follow the name after ';' (e.g. DUE;VALUE=DATE)
+        let name = name
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        match (name.as_str(), value) {
+            ("BEGIN", "VTODO") => {
+                in_todo = true;
+                todo = Some(ParsedTodo {
+                    uid: String::new(),
+                    fields: TodoFields::default(),
+                    last_modified: None,
+                });
+            }
+            ("END", "VTODO") => break,
+            _ if !in_todo => {}
+            _ => {
+                let todo = todo.as_mut()?;
+                match name.as_str() {
+                    "UID" => todo.uid = value.to_string(),
+                    "SUMMARY" => todo.fields.summary = unescape_text(value),
+                    "DESCRIPTION" => {
+                        todo.fields.description =
+                            Some(unescape_text(value)).filter(|d| !d.is_empty())
+                    }
+                    "DUE" => todo.fields.due = parse_date(value),
+                    "STATUS" => todo.fields.completed = value.eq_ignore_ascii_case("COMPLETED"),
+                    "COMPLETED" => todo.fields.completed = true,
+                    "LAST-MODIFIED" => todo.last_modified = parse_date(value),
+                    _ => {}
+                }
+            }
+        }
+    }
+    todo.filter(|todo| !todo.uid.is_empty())
+}
+
+/// Date part of a DATE or DATE-TIME value (`20250310` or `20250310T090000Z`)
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+/// Join folded content lines (RFC 5545, section 3.1)
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Undo [`escape_text`]
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
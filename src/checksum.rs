@@ -0,0 +1,105 @@
+//! Content checksum in the data file header
+//!
+//! Every save starts the file with a comment line holding a hash of the rest
+//! of the file:
+//!
+//! ```toml
+//! # gtd-mcp checksum: fnv1a64:5e1c0a1f0d9b4c27
+//! ```
+//!
+//! On load the hash is recomputed; a mismatch means the file was edited
+//! outside the server (by hand, another tool, a bad merge) or got corrupted.
+//! Files without the header (older files, merge results) are accepted as is.
+//!
+//! The hash is 64-bit FNV-1a over the LF-normalized content. It detects
+//! accidental changes; it is not meant to resist deliberate tampering.
+
+use std::fmt;
+
+/// Start of the header line
+pub const HEADER_PREFIX: &str = "# gtd-mcp checksum: ";
+
+/// Name of the hash algorithm, written before the digest
+const ALGORITHM: &str = "fnv1a64";
+
+/// Result of checking a file's header against its content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// The file has no checksum header
+    Missing,
+    /// The content matches the header
+    Valid,
+    /// The content changed since the header was written
+    Mismatch {
+        /// Checksum recorded in the header
+        expected: String,
+        /// Checksum of the current content
+        actual: String,
+    },
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verification::Missing => f.write_str("no checksum"),
+            Verification::Valid => f.write_str("checksum valid"),
+            Verification::Mismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch (header {}, content {})",
+                expected, actual
+            ),
+        }
+    }
+}
+
+/// Checksum of `content`, e.g. "fnv1a64:5e1c0a1f0d9b4c27"
+pub fn digest(content: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{}:{:016x}", ALGORITHM, hash)
+}
+
+/// Prefix `content` with its checksum header
+pub fn with_header(content: &str) -> String {
+    format!("{}{}\n{}", HEADER_PREFIX, digest(content), content)
+}
+
+/// Split off the checksum header, if any
+///
+/// # Returns
+/// The recorded checksum and the content after the header line
+pub fn split_header(content: &str) -> (Option<&str>, &str) {
+    match content.strip_prefix(HEADER_PREFIX) {
+        Some(rest) => {
+            let (recorded, body) = rest.split_once('\n').unwrap_or((rest, ""));
+            (Some(recorded.trim()), body)
+        }
+        None => (None, content),
+    }
+}
+
+/// Check LF-normalized file content against its header
+///
+/// # Returns
+/// The verification result and the content after the header line
+pub fn verify(content: &str) -> (Verification, &str) {
+    let (recorded, body) = split_header(content);
+    let verification = match recorded {
+        None => Verification::Missing,
+        Some(expected) => {
+            let actual = digest(body);
+            if expected == actual {
+                Verification::Valid
+            } else {
+                Verification::Mismatch {
+                    expected: expected.to_string(),
+                    actual,
+                }
+            }
+        }
+    };
+    (verification, body)
+}
@@ -0,0 +1,208 @@
+//! Clarifying inbox items with the client's LLM
+//!
+//! `process_inbox` walks through the inbox oldest first. With `assist`, it
+//! asks the client's model (MCP sampling) to clarify each raw capture: a
+//! clearer title, the context it belongs to and the next physical action.
+//! The proposals are only shown; the user confirms them and the assistant
+//! applies them with `update` and `change_status`, so a bad guess of the
+//! model never reaches the data file.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::NaiveDate;
+use mcp_attr::schema::{
+    CreateMessageRequestParams, CreateMessageResult, CreateMessageResultContent, Role,
+    SamplingMessage, TextContent,
+};
+use serde::Deserialize;
+
+/// Inbox items processed per call unless the caller asks for another number
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// Most inbox items processed per call (each one is a sampling request)
+pub const MAX_LIMIT: usize = 50;
+
+/// Tokens the model may use for one proposal
+const MAX_TOKENS: i64 = 300;
+
+const SYSTEM_PROMPT: &str = "You help clarify the inbox of a GTD (Getting Things Done) system. \
+For the captured item you are given, answer with one JSON object and nothing else: \
+{\"title\": \"<clear, specific title of the outcome>\", \
+\"context\": \"<one of the listed contexts, or null when none fits>\", \
+\"next_action\": \"<the very next physical, visible action, starting with a verb>\"}. \
+Keep the language of the item.";
+
+/// What the model proposes for one inbox item
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Proposal {
+    /// Clarified title
+    pub title: String,
+    /// Context the item belongs to, when one fits
+    #[serde(default)]
+    pub context: Option<String>,
+    /// Next physical action
+    pub next_action: String,
+}
+
+/// An inbox item with the model's proposal, or why there is none
+pub struct Clarified {
+    /// The inbox item
+    pub nota: Nota,
+    /// `None` when the model was not asked
+    pub proposal: Option<Result<Proposal, String>>,
+}
+
+/// Inbox items to process, oldest first
+///
+/// # Arguments
+/// * `data` - The GTD data
+/// * `limit` - Most items to return
+pub fn inbox_items(data: &GtdData, limit: usize) -> Vec<Nota> {
+    let mut inbox: Vec<&Nota> = data
+        .iter()
+        .filter(|n| n.status == NotaStatus::inbox)
+        .collect();
+    inbox.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    inbox.into_iter().take(limit).cloned().collect()
+}
+
+/// Names of the contexts, sorted
+pub fn context_names(data: &GtdData) -> Vec<String> {
+    let mut names: Vec<String> = data.contexts().into_keys().collect();
+    names.sort();
+    names
+}
+
+/// The sampling request that asks the model to clarify one item
+///
+/// # Arguments
+/// * `nota` - The inbox item
+/// * `contexts` - Contexts the model may choose from
+pub fn request(nota: &Nota, contexts: &[String]) -> CreateMessageRequestParams {
+    let mut prompt = format!("Captured item: {}\n", nota.title);
+    if let Some(notes) = nota.notes.as_deref().filter(|notes| !notes.is_empty()) {
+        prompt.push_str(&format!("Notes: {}\n", notes));
+    }
+    if let Some(project) = &nota.project {
+        prompt.push_str(&format!("Project: {}\n", project));
+    }
+    if contexts.is_empty() {
+        prompt.push_str("Contexts: none defined\n");
+    } else {
+        prompt.push_str(&format!("Contexts: {}\n", contexts.join(", ")));
+    }
+    CreateMessageRequestParams {
+        max_tokens: MAX_TOKENS,
+        messages: vec![SamplingMessage {
+            content: TextContent::from(prompt).into(),
+            role: Role::User,
+        }],
+        system_prompt: Some(SYSTEM_PROMPT.to_string()),
+        include_context: None,
+        metadata: None,
+        model_preferences: None,
+        stop_sequences: Vec::new(),
+        temperature: None,
+    }
+}
+
+/// Read the proposal out of the model's answer
+///
+/// The JSON object may be wrapped in prose or a code fence.
+///
+/// # Returns
+/// The proposal, or why the answer could not be used
+pub fn parse(result: &CreateMessageResult) -> Result<Proposal, String> {
+    let CreateMessageResultContent::TextContent(content) = &result.content else {
+        return Err("the model did not answer with text".to_string());
+    };
+    let text = &content.text;
+    let json = text
+        .find('{')
+        .zip(text.rfind('}'))
+        .filter(|(start, end)| start < end)
+        .map(|(start, end)| &text[start..=end])
+        .ok_or_else(|| "the model's answer holds no JSON object".to_string())?;
+    let mut proposal: Proposal =
+        serde_json::from_str(json).map_err(|e| format!("unreadable answer: {}", e))?;
+    proposal.title = proposal.title.trim().to_string();
+    proposal.next_action = proposal.next_action.trim().to_string();
+    proposal.context = proposal
+        .context
+        .map(|context| context.trim().to_string())
+        .filter(|context| !context.is_empty() && context != "null");
+    if proposal.title.is_empty() || proposal.next_action.is_empty() {
+        return Err("the model left the title or the next action empty".to_string());
+    }
+    Ok(proposal)
+}
+
+/// Render the inbox items to process, with their proposals
+///
+/// # Arguments
+/// * `items` - Items oldest first, with the proposals when assisted
+/// * `inbox_size` - Number of items in the whole inbox
+/// * `contexts` - Names of the existing contexts
+/// * `today` - Today's date, for the item ages
+pub fn render(
+    items: &[Clarified],
+    inbox_size: usize,
+    contexts: &[String],
+    today: NaiveDate,
+) -> String {
+    let mut out = format!(
+        "# Process inbox: {} of {} item{}, oldest first\n\n",
+        items.len(),
+        inbox_size,
+        if inbox_size == 1 { "" } else { "s" }
+    );
+    if items.is_empty() {
+        out.push_str("_Inbox zero._\n");
+        return out;
+    }
+    out.push_str(
+        "For each item: What is it? Is it actionable? If so, what is the next physical action and where can it be done?\n",
+    );
+    for item in items {
+        let nota = &item.nota;
+        let age = (today - nota.created_at).num_days().max(0);
+        out.push_str(&format!(
+            "\n## [{}] {}\nCaptured {} ({} day{} ago)\n",
+            nota.id,
+            nota.title,
+            nota.created_at,
+            age,
+            if age == 1 { "" } else { "s" }
+        ));
+        match &item.proposal {
+            None => {}
+            Some(Err(e)) => out.push_str(&format!("No proposal: {}\n", e)),
+            Some(Ok(proposal)) => {
+                out.push_str(
+                    "Proposed by the client's model - confirm with the user before applying:\n",
+                );
+                out.push_str(&format!("- Title: {}\n", proposal.title));
+                match &proposal.context {
+                    Some(context) if contexts.contains(context) => {
+                        out.push_str(&format!("- Context: {}\n", context));
+                    }
+                    Some(context) => out.push_str(&format!(
+                        "- Context: {} (new - create it with inbox(status=\"context\") first)\n",
+                        context
+                    )),
+                    None => out.push_str("- Context: none\n"),
+                }
+                out.push_str(&format!("- Next action: {}\n", proposal.next_action));
+                let context = proposal
+                    .context
+                    .as_ref()
+                    .map(|context| format!(", context={:?}", context))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "- Apply: update(id={:?}, title={:?}{}), then change_status(ids=[{:?}], new_status=\"next_action\")\n",
+                    nota.id, proposal.title, context, nota.id
+                ));
+            }
+        }
+    }
+    out
+}
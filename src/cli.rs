@@ -0,0 +1,302 @@
+//! Command-line subcommands that work on a GTD file without an MCP client
+//!
+//! `main.rs` parses the arguments and calls into this module; each function
+//! returns the text to print. `list`, `add` and `capture` go through the same
+//! handlers as the MCP tools, so filtering, validation, saving and Git commits
+//! behave exactly as they do for a client.
+
+use crate::gtd::{GtdData, NotaStatus, local_date_today};
+use crate::import::{self, ImportFormat};
+use crate::merge_file::{self, MergeStrategy};
+use crate::mirror::{self, MirrorFormat};
+use crate::{GtdServerHandler, Storage};
+use anyhow::{Result, anyhow};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+/// Statuses in GTD workflow order, as shown by `stats`
+const STATUS_ORDER: [NotaStatus; 13] = [
+    NotaStatus::inbox,
+    NotaStatus::next_action,
+    NotaStatus::waiting_for,
+    NotaStatus::later,
+    NotaStatus::calendar,
+    NotaStatus::someday,
+    NotaStatus::goal,
+    NotaStatus::project,
+    NotaStatus::context,
+    NotaStatus::template,
+    NotaStatus::reference,
+    NotaStatus::done,
+    NotaStatus::trash,
+];
+
+/// Filters of the `list` subcommand (same meaning as the `list` tool parameters)
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    pub status: Option<String>,
+    pub date: Option<String>,
+    pub exclude_notes: bool,
+    pub keyword: Option<String>,
+    pub project: Option<String>,
+    pub context: Option<String>,
+}
+
+/// Fields of the `add` subcommand (same meaning as the `inbox` tool parameters)
+#[derive(Debug, Clone, Default)]
+pub struct AddOptions {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub project: Option<String>,
+    pub context: Option<String>,
+    pub notes: Option<String>,
+    pub start_date: Option<String>,
+}
+
+/// Result of the `validate` subcommand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Number of items in the file
+    pub item_count: usize,
+    /// Problems found by [`GtdData::check_integrity`]
+    pub issues: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Whether the file has no problems
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "OK: {} items, no problems found", self.item_count);
+        }
+        write!(
+            f,
+            "{} problem{} found in {} items:",
+            self.issues.len(),
+            if self.issues.len() == 1 { "" } else { "s" },
+            self.item_count
+        )?;
+        for issue in &self.issues {
+            write!(f, "\n- {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Load an existing data file without touching Git
+fn load_existing(file: &str) -> Result<GtdData> {
+    if !Path::new(file).exists() {
+        return Err(anyhow!("{} does not exist", file));
+    }
+    Storage::new(file, false).load_local()
+}
+
+/// Convert a tool error into its public message
+fn tool_error(error: mcp_attr::Error) -> anyhow::Error {
+    anyhow!(error.to_error_object(false).message)
+}
+
+/// List items, as the `list` tool does
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+/// * `options` - Filters to apply
+pub async fn list(file: &str, options: ListOptions) -> Result<String> {
+    load_existing(file)?;
+    let handler = GtdServerHandler::new(file, false)?;
+    handler
+        .list(
+            options.status,
+            options.date,
+            Some(options.exclude_notes),
+            options.keyword,
+            options.project,
+            options.context,
+            None,
+        )
+        .await
+        .map_err(tool_error)
+}
+
+/// Capture an item, as the `inbox` tool does (creating the file if needed)
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+/// * `options` - Fields of the new item
+pub async fn add(file: &str, options: AddOptions) -> Result<String> {
+    let handler = GtdServerHandler::new(file, false)?;
+    handler
+        .inbox(
+            options.id,
+            options.title,
+            options.status,
+            options.project,
+            options.context,
+            options.notes,
+            options.start_date,
+            None,
+            None,
+        )
+        .await
+        .map_err(tool_error)
+}
+
+/// Capture each non-empty line as an inbox item with an ID generated from its title
+///
+/// Each line goes through the `capture` tool with the default
+/// [`crate::IdScheme`], so IDs come from [`crate::slug::unique_id`] and
+/// repeated titles get numbered IDs.
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file (created if needed)
+/// * `lines` - Titles, one per item (blank lines are skipped)
+pub async fn capture(file: &str, lines: impl IntoIterator<Item = String>) -> Result<String> {
+    let handler = GtdServerHandler::new(file, false)?;
+    let mut responses = Vec::new();
+    for line in lines {
+        let title = line.trim();
+        if title.is_empty() {
+            continue;
+        }
+        let response = handler
+            .capture(title.to_string(), None)
+            .await
+            .map_err(tool_error)?;
+        responses.push(response);
+    }
+    if responses.is_empty() {
+        return Err(anyhow!("Nothing to capture: every line was empty"));
+    }
+    Ok(responses.join("\n"))
+}
+
+/// Render the whole file in one of the [`MirrorFormat`]s (see [`mirror::render`])
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+/// * `format` - Output format
+pub fn export(file: &str, format: MirrorFormat) -> Result<String> {
+    mirror::render(&load_existing(file)?, format, local_date_today())
+}
+
+/// Write the whole file in one of the [`MirrorFormat`]s to `out`
+///
+/// Like [`export`], but JSON Lines are streamed one item at a time.
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+/// * `format` - Output format
+/// * `out` - Destination (e.g. locked stdout)
+pub fn export_to(file: &str, format: MirrorFormat, out: &mut impl Write) -> Result<()> {
+    let data = load_existing(file)?;
+    match format {
+        MirrorFormat::JsonLines => mirror::write_json_lines(&data, out)?,
+        format => out.write_all(mirror::render(&data, format, local_date_today())?.as_bytes())?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Merge items from another format into the file (created if needed)
+///
+/// The file is only written when the whole input imports cleanly.
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+/// * `text` - Input to import
+/// * `format` - Input format
+/// * `dry_run` - Report what would change without writing the file
+pub fn import(file: &str, text: &str, format: ImportFormat, dry_run: bool) -> Result<String> {
+    let storage = Storage::new(file, false);
+    let mut data = if Path::new(file).exists() {
+        storage.load_local()?
+    } else {
+        GtdData::new()
+    };
+    let summary = import::import(&mut data, text, format, local_date_today())?;
+    if dry_run {
+        return Ok(format!("Dry run, {} not modified: {}", file, summary));
+    }
+    storage.save(&data)?;
+    Ok(format!("Imported into {}: {}", file, summary))
+}
+
+/// Merge another data file into the file, as the `merge_file` tool does
+///
+/// The file is only written when the whole merge succeeds; `other` is never modified.
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+/// * `other` - Path to the data file to merge in
+/// * `strategy` - How to resolve IDs used by different items in both files
+/// * `dry_run` - Report what would change without writing the file
+pub fn merge(file: &str, other: &str, strategy: MergeStrategy, dry_run: bool) -> Result<String> {
+    let storage = Storage::new(file, false);
+    let mut data = load_existing(file)?;
+    let summary = merge_file::merge(&mut data, &load_existing(other)?, strategy)?;
+    if dry_run {
+        return Ok(format!("Dry run, {} not modified: {}", file, summary));
+    }
+    if !summary.is_empty() {
+        storage.save_with_message(&data, &format!("Merge {}", other))?;
+    }
+    Ok(format!("Merged {} into {}: {}", other, file, summary))
+}
+
+/// Check a file for schema and integrity problems
+///
+/// Schema errors make loading fail and are returned as the error; integrity
+/// problems (dangling links, duplicate IDs, bad dates) are listed in the report.
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+pub fn validate(file: &str) -> Result<ValidationReport> {
+    let data = load_existing(file)?;
+    Ok(ValidationReport {
+        item_count: data.iter().count(),
+        issues: data.check_integrity(),
+    })
+}
+
+/// Summarize the file: items per status, calendar items that are due and project progress
+///
+/// # Arguments
+/// * `file` - Path to the GTD data file
+pub fn stats(file: &str) -> Result<String> {
+    let data = load_existing(file)?;
+    let total = data.iter().count();
+    let mut report = format!("Total: {} items\n", total);
+    for status in STATUS_ORDER {
+        let n = data.iter().filter(|nota| nota.status == status).count();
+        if n > 0 {
+            report.push_str(&format!("{:<13}{}\n", format!("{:?}:", status), n));
+        }
+    }
+
+    let today = local_date_today();
+    let due = data
+        .iter()
+        .filter(|n| n.status == NotaStatus::calendar && n.start_date.is_some_and(|d| d <= today))
+        .count();
+    report.push_str(&format!("Calendar items due today or earlier: {}\n", due));
+
+    let projects: Vec<_> = data.iter().filter(|n| n.is_project()).collect();
+    if !projects.is_empty() {
+        report.push_str("Projects:\n");
+        for project in projects {
+            report.push_str(&format!(
+                "  {}: {}\n",
+                project.id,
+                data.project_progress(&project.id)
+            ));
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,269 @@
+//! Commit message templates
+//!
+//! Builds Git commit messages for saves from a configurable format string, so
+//! the history shows what changed (item title, status transition, project)
+//! instead of only the item ID.
+//!
+//! ## Format
+//!
+//! Placeholders in braces are replaced with values of the changed item:
+//! `{action}`, `{id}`, `{title}`, `{status}` (`old → new` when the status
+//! changed, otherwise the current status), `{old_status}`, `{new_status}`,
+//! `{project}`, and `{context}`.
+//!
+//! Text in square brackets is optional: the whole group is dropped when any
+//! placeholder inside it is empty. For example `[ in {project}]` only appears
+//! for items that belong to a project.
+
+use crate::gtd::{Nota, NotaStatus};
+use std::fmt;
+use std::str::FromStr;
+
+/// Placeholders a template may use
+const PLACEHOLDERS: [&str; 8] = [
+    "action",
+    "id",
+    "title",
+    "status",
+    "old_status",
+    "new_status",
+    "project",
+    "context",
+];
+
+/// An item affected by a save, as described in the commit message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitItem {
+    /// Nota ID
+    pub id: String,
+    /// Nota title
+    pub title: String,
+    /// Status before the change (None for newly added items)
+    pub old_status: Option<NotaStatus>,
+    /// Status after the change
+    pub new_status: NotaStatus,
+    /// Project the nota belongs to
+    pub project: Option<String>,
+    /// Context the nota applies to
+    pub context: Option<String>,
+}
+
+impl CommitItem {
+    /// Describe a nota in its current state
+    ///
+    /// # Arguments
+    /// * `nota` - The nota after the change
+    /// * `old_status` - Its status before the change, if it existed
+    pub fn from_nota(nota: &Nota, old_status: Option<NotaStatus>) -> Self {
+        Self {
+            id: nota.id.clone(),
+            title: nota.title.clone(),
+            old_status,
+            new_status: nota.status.clone(),
+            project: nota.project.clone(),
+            context: nota.context.clone(),
+        }
+    }
+
+    /// Value of a placeholder for this item (empty when not applicable)
+    fn value(&self, action: &str, name: &str) -> String {
+        match name {
+            "action" => action.to_string(),
+            "id" => self.id.clone(),
+            "title" => self.title.clone(),
+            "status" => match &self.old_status {
+                Some(old) if *old != self.new_status => {
+                    format!("{} → {}", status_name(old), status_name(&self.new_status))
+                }
+                _ => status_name(&self.new_status),
+            },
+            "old_status" => self
+                .old_status
+                .as_ref()
+                .map(status_name)
+                .unwrap_or_default(),
+            "new_status" => status_name(&self.new_status),
+            "project" => self.project.clone().unwrap_or_default(),
+            "context" => self.context.clone().unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Status name as written in the TOML file (e.g., "next_action")
+fn status_name(status: &NotaStatus) -> String {
+    format!("{:?}", status)
+}
+
+/// A parsed piece of a template
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Placeholder(String),
+    /// Square-bracket group dropped when any placeholder inside is empty
+    Optional(Vec<Segment>),
+}
+
+/// A commit message format string
+///
+/// A single item renders as one line. Several items (e.g., a batch status
+/// change) render as a `"{action}: N items"` headline followed by one bullet
+/// per item, matching the style of coalesced commits.
+#[derive(Debug, Clone)]
+pub struct CommitTemplate {
+    format: String,
+    segments: Vec<Segment>,
+}
+
+impl CommitTemplate {
+    /// Default format: `Update meeting-prep: Prepare slides (inbox → next_action) in q1-launch`
+    pub const DEFAULT: &'static str = "{action} {id}: {title}[ ({status})][ in {project}]";
+
+    /// Parse a format string
+    ///
+    /// # Arguments
+    /// * `format` - Template using the placeholders described in the module docs
+    ///
+    /// # Returns
+    /// The template, or an error describing an unknown placeholder or unbalanced braces/brackets
+    pub fn new(format: &str) -> Result<Self, String> {
+        let segments = parse(format)?;
+        Ok(Self {
+            format: format.to_string(),
+            segments,
+        })
+    }
+
+    /// The format string this template was created from
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    /// Render the commit message for a save
+    ///
+    /// # Arguments
+    /// * `action` - What happened (e.g., "Add", "Update", "Change status")
+    /// * `items` - Items affected by the save
+    ///
+    /// # Returns
+    /// The commit message (just `action` when no items are affected)
+    pub fn render(&self, action: &str, items: &[CommitItem]) -> String {
+        match items {
+            [] => action.to_string(),
+            [item] => self.render_item(action, item),
+            _ => {
+                let mut message = format!("{}: {} items\n\n", action, items.len());
+                for item in items {
+                    message.push_str(&format!("- {}\n", self.render_item(action, item)));
+                }
+                message.trim_end().to_string()
+            }
+        }
+    }
+
+    fn render_item(&self, action: &str, item: &CommitItem) -> String {
+        let (text, _) = render_segments(&self.segments, action, item);
+        text.trim().to_string()
+    }
+}
+
+impl Default for CommitTemplate {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT).expect("default commit template is valid")
+    }
+}
+
+impl FromStr for CommitTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for CommitTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format)
+    }
+}
+
+/// Render segments, also reporting whether every placeholder had a value
+fn render_segments(segments: &[Segment], action: &str, item: &CommitItem) -> (String, bool) {
+    let mut out = String::new();
+    let mut all_present = true;
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => out.push_str(text),
+            Segment::Placeholder(name) => {
+                let value = item.value(action, name);
+                all_present &= !value.is_empty();
+                out.push_str(&value);
+            }
+            Segment::Optional(inner) => {
+                let (text, complete) = render_segments(inner, action, item);
+                if complete {
+                    out.push_str(&text);
+                }
+            }
+        }
+    }
+    (out, all_present)
+}
+
+/// Parse a format string into segments
+fn parse(format: &str) -> Result<Vec<Segment>, String> {
+    let mut stack: Vec<Vec<Segment>> = vec![Vec::new()];
+    let mut literal = String::new();
+    let mut chars = format.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if !PLACEHOLDERS.contains(&name.as_str()) {
+                    return Err(format!(
+                        "Unknown placeholder '{{{}}}' in commit template. Valid placeholders: {}",
+                        name,
+                        PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+                    ));
+                }
+                let current = stack.last_mut().unwrap();
+                if !literal.is_empty() {
+                    current.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                current.push(Segment::Placeholder(name));
+            }
+            '[' => {
+                if stack.len() > 1 {
+                    return Err("Optional groups '[...]' cannot be nested".to_string());
+                }
+                if !literal.is_empty() {
+                    let text = std::mem::take(&mut literal);
+                    stack.last_mut().unwrap().push(Segment::Literal(text));
+                }
+                stack.push(Vec::new());
+            }
+            ']' => {
+                if stack.len() < 2 {
+                    return Err("Unmatched ']' in commit template".to_string());
+                }
+                let mut group = stack.pop().unwrap();
+                if !literal.is_empty() {
+                    group.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                stack.last_mut().unwrap().push(Segment::Optional(group));
+            }
+            '}' => return Err("Unmatched '}' in commit template".to_string()),
+            _ => literal.push(c),
+        }
+    }
+
+    if stack.len() > 1 {
+        return Err("Unclosed '[' in commit template".to_string());
+    }
+    let mut segments = stack.pop().unwrap();
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
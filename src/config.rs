@@ -0,0 +1,490 @@
+//! Server options and the configuration file
+//!
+//! Every option of `gtd-mcp serve` can also be set in a TOML configuration file,
+//! so MCP client definitions only need `gtd-mcp` (or `gtd-mcp --config <path>`):
+//!
+//! ```toml
+//! file = "~/gtd/gtd.toml"
+//!
+//! [serve]
+//! sync_git = true
+//! git_author_name = "GTD Bot"
+//! timezone = "+09:00"
+//! duplicate_titles = "reject"
+//! wip_limits = ["next_action=10"]
+//! workspaces = ["personal=personal.toml"]
+//! ```
+//!
+//! Keys of the `[serve]` table are the command-line flags with underscores.
+//! Every option can also be set through a `GTD_MCP_*` environment variable
+//! (`GTD_MCP_FILE`, `GTD_MCP_SYNC_GIT=true`, ...) for clients that make
+//! arguments awkward. Precedence is command line, then environment, then the
+//! file; a boolean turned on by the file cannot be turned off by the others.
+//! Relative paths in the file are resolved against the directory of the file,
+//! and `~/` against the home directory.
+
+use crate::caldav::{CalDavConfig, ConflictPolicy};
+use crate::daemon::Schedule;
+use crate::mirror::MirrorFormat;
+use crate::wip::{WipLimit, WipMode};
+use crate::workspace::WorkspaceSpec;
+use crate::{CommitTemplate, DuplicateCheck, IdScheme, Storage};
+use anyhow::{Context as _, Result, anyhow, bail};
+use chrono::FixedOffset;
+use clap::builder::BoolishValueParser;
+use serde::{Deserialize, Deserializer, de};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Options of the MCP server (`gtd-mcp serve` flags and the `[serve]` table)
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServeOptions {
+    /// Configuration file (default: ~/.config/gtd-mcp/config.toml if it exists)
+    #[arg(long, env = "GTD_MCP_CONFIG")]
+    #[serde(skip)]
+    pub config: Option<PathBuf>,
+
+    /// Create the data file with starter contexts if it does not exist (./gtd.toml when no path is found)
+    #[arg(long)]
+    #[serde(skip)]
+    pub init: bool,
+
+    /// Enable git synchronization on save
+    #[arg(long, env = "GTD_MCP_SYNC_GIT", value_parser = BoolishValueParser::new())]
+    pub sync_git: bool,
+
+    /// Remote URL added as 'origin' when --sync-git finds a repository without a remote
+    #[arg(long, env = "GTD_MCP_GIT_REMOTE")]
+    pub git_remote: Option<String>,
+
+    /// Tag the repository as weekly-YYYY-Www when the weekly review is completed (empty_trash)
+    #[arg(long, env = "GTD_MCP_WEEKLY_TAGS", value_parser = BoolishValueParser::new())]
+    pub weekly_tags: bool,
+
+    /// Author name for commits made by the server (overrides Git config user.name)
+    #[arg(long, env = "GTD_MCP_GIT_AUTHOR_NAME")]
+    pub git_author_name: Option<String>,
+
+    /// Author email for commits made by the server (overrides Git config user.email)
+    #[arg(long, env = "GTD_MCP_GIT_AUTHOR_EMAIL")]
+    pub git_author_email: Option<String>,
+
+    /// Push from a background worker at most every N seconds (0 = right after each commit) instead of on every save
+    #[arg(long, env = "GTD_MCP_PUSH_INTERVAL_SECS")]
+    pub push_interval_secs: Option<u64>,
+
+    /// Commit message format, e.g. "{action} {id}: {title}[ ({status})][ in {project}]"
+    #[arg(long, env = "GTD_MCP_COMMIT_TEMPLATE")]
+    #[serde(deserialize_with = "parsed")]
+    pub commit_template: Option<CommitTemplate>,
+
+    /// Instructions sent to clients for the model, e.g. team ID conventions; "{default}" stands for the built-in ones
+    #[arg(long, env = "GTD_MCP_INSTRUCTIONS")]
+    pub instructions: Option<String>,
+
+    /// Write a read-only JSON, JSON Lines, Markdown, iCalendar, CSV or todo.txt mirror of the data to this path on every save
+    #[arg(long, env = "GTD_MCP_MIRROR")]
+    pub mirror: Option<String>,
+
+    /// Mirror format (json, jsonl, markdown, ical, csv or todotxt); inferred from the mirror file extension if omitted
+    #[arg(long, env = "GTD_MCP_MIRROR_FORMAT", requires = "mirror")]
+    #[serde(deserialize_with = "parsed")]
+    pub mirror_format: Option<MirrorFormat>,
+
+    /// Match IDs and context names regardless of case ("office" finds "Office"); stored casing is kept
+    #[arg(long, env = "GTD_MCP_CASE_INSENSITIVE_IDS", value_parser = BoolishValueParser::new())]
+    pub case_insensitive_ids: bool,
+
+    /// Check the whole file for integrity problems when its checksum shows it was changed outside the server
+    #[arg(long, env = "GTD_MCP_VALIDATE_ON_CHECKSUM_MISMATCH", value_parser = BoolishValueParser::new())]
+    pub validate_on_checksum_mismatch: bool,
+
+    /// Require new IDs to be lowercase kebab-case (e.g. "call-john", "@home") of at most 64 characters
+    #[arg(long, env = "GTD_MCP_STRICT_IDS", value_parser = BoolishValueParser::new())]
+    pub strict_ids: bool,
+
+    /// Require new IDs to match this regular expression (replaces the --strict-ids pattern)
+    #[arg(long, env = "GTD_MCP_ID_PATTERN")]
+    pub id_pattern: Option<String>,
+
+    /// Maximum length of new IDs in characters
+    #[arg(long, env = "GTD_MCP_ID_MAX_LENGTH")]
+    pub id_max_length: Option<usize>,
+
+    /// Characters new IDs must not contain, e.g. " /#"
+    #[arg(long, env = "GTD_MCP_ID_FORBIDDEN_CHARS")]
+    pub id_forbidden_chars: Option<String>,
+
+    /// IDs generated for captured items and recurring occurrences: slug (default), ulid or sequential
+    #[arg(long, env = "GTD_MCP_ID_SCHEME")]
+    #[serde(deserialize_with = "parsed")]
+    pub id_scheme: Option<IdScheme>,
+
+    /// What inbox does with a title nearly identical to an open item: off, warn (default) or reject
+    #[arg(long, env = "GTD_MCP_DUPLICATE_TITLES")]
+    #[serde(deserialize_with = "parsed")]
+    pub duplicate_titles: Option<DuplicateCheck>,
+
+    /// Most items a status may hold, given as STATUS=MAX (repeatable, e.g. next_action=10)
+    #[arg(long = "wip-limit", env = "GTD_MCP_WIP_LIMITS", value_delimiter = ',')]
+    #[serde(deserialize_with = "parsed_list")]
+    pub wip_limits: Vec<WipLimit>,
+
+    /// What moving an item into a full status does: warn (default) or enforce (refuse the move)
+    #[arg(long, env = "GTD_MCP_WIP_MODE")]
+    #[serde(deserialize_with = "parsed")]
+    pub wip_mode: Option<WipMode>,
+
+    /// Escalate waiting_for items in suggest after they have waited this many days (per-item values set with set_escalation win)
+    #[arg(long, env = "GTD_MCP_ESCALATE_AFTER_DAYS")]
+    pub escalate_after_days: Option<u32>,
+
+    /// Move waiting_for items due for escalation back to next_action when suggest runs
+    #[arg(long, env = "GTD_MCP_ESCALATE_TO_NEXT_ACTION", value_parser = BoolishValueParser::new())]
+    pub escalate_to_next_action: bool,
+
+    /// Purge trash items older than this many days on every save, listing them in the commit message (default: keep until empty_trash)
+    #[arg(long, env = "GTD_MCP_TRASH_RETENTION_DAYS")]
+    pub trash_retention_days: Option<u32>,
+
+    /// Flag next actions in list after this many days in next_action, e.g. "⚠ 21d" (default 14, 0 = off)
+    #[arg(long, env = "GTD_MCP_STALE_AFTER_DAYS")]
+    pub stale_after_days: Option<u32>,
+
+    /// Validate references, IDs and dates before every save and refuse to write invalid data
+    #[arg(long, env = "GTD_MCP_STRICT", value_parser = BoolishValueParser::new())]
+    pub strict: bool,
+
+    /// Only serve the tools that read the data; every other tool call fails and --daemon jobs do not run
+    #[arg(long, env = "GTD_MCP_READ_ONLY", value_parser = BoolishValueParser::new())]
+    pub read_only: bool,
+
+    /// Coalesce saves: flush to disk (one commit) after this many idle milliseconds (default 0 = write immediately)
+    #[arg(long, env = "GTD_MCP_DEBOUNCE_MS")]
+    pub debounce_ms: Option<u64>,
+
+    /// UTC offset used for "today" and context availability instead of the system timezone, e.g. "+09:00" or "UTC"
+    #[arg(long, env = "GTD_MCP_TIMEZONE", value_parser = parse_utc_offset)]
+    #[serde(deserialize_with = "utc_offset")]
+    pub timezone: Option<FixedOffset>,
+
+    /// Run scheduled maintenance while serving: promote due calendar items daily, plus the jobs enabled below
+    #[arg(long, env = "GTD_MCP_DAEMON", value_parser = BoolishValueParser::new())]
+    pub daemon: bool,
+
+    /// With --daemon: move done items not updated for this many days to <name>.archive.toml
+    #[arg(long, env = "GTD_MCP_ARCHIVE_DONE_AFTER_DAYS")]
+    pub archive_done_after_days: Option<u32>,
+
+    /// With --daemon and --sync-git: sync with the Git remote every N minutes
+    #[arg(long, env = "GTD_MCP_SYNC_INTERVAL_MINS")]
+    pub sync_interval_mins: Option<u64>,
+
+    /// Two-way sync next_action and calendar items with this CalDAV task list (VTODO collection) through the sync_caldav tool
+    #[arg(long, env = "GTD_MCP_CALDAV_URL")]
+    pub caldav_url: Option<String>,
+
+    /// User name for the CalDAV server
+    #[arg(long, env = "GTD_MCP_CALDAV_USERNAME", requires = "caldav_url")]
+    pub caldav_username: Option<String>,
+
+    /// Password (or app password) for the CalDAV server
+    #[arg(
+        long,
+        env = "GTD_MCP_CALDAV_PASSWORD",
+        requires = "caldav_url",
+        hide_env_values = true
+    )]
+    pub caldav_password: Option<String>,
+
+    /// Which version of an item changed on both sides the CalDAV sync keeps: newer (default), local or remote
+    #[arg(long, env = "GTD_MCP_CALDAV_CONFLICT", requires = "caldav_url")]
+    #[serde(deserialize_with = "parsed")]
+    pub caldav_conflict: Option<ConflictPolicy>,
+
+    /// Serve the tools as a REST/JSON API on this address (default 127.0.0.1:7878) instead of MCP over stdio
+    #[arg(long, env = "GTD_MCP_REST", num_args = 0..=1, default_missing_value = crate::rest::DEFAULT_ADDR, value_name = "ADDR")]
+    pub rest: Option<String>,
+
+    /// With --rest: only these tools can be listed and called (comma-separated; default all)
+    #[arg(
+        long,
+        env = "GTD_MCP_REST_TOOLS",
+        value_delimiter = ',',
+        requires = "rest"
+    )]
+    pub rest_tools: Vec<String>,
+
+    /// Serve MCP over HTTP on this address (default 127.0.0.1:7879) so several clients can share the data, instead of stdio
+    #[arg(long, env = "GTD_MCP_HTTP", num_args = 0..=1, default_missing_value = crate::mcp_http::DEFAULT_ADDR, value_name = "ADDR", conflicts_with = "rest")]
+    pub http: Option<String>,
+
+    /// POST a JSON payload for every added, updated, completed or deleted item to this URL after each save
+    #[arg(long, env = "GTD_MCP_WEBHOOK_URL")]
+    pub webhook_url: Option<String>,
+
+    /// Also serve this data file as a workspace, given as NAME=PATH (repeatable; see the switch_workspace tool)
+    #[arg(long = "workspace", env = "GTD_MCP_WORKSPACES", value_delimiter = ',')]
+    #[serde(deserialize_with = "parsed_list")]
+    pub workspaces: Vec<WorkspaceSpec>,
+}
+
+impl ServeOptions {
+    /// Fill options not given on the command line or in the environment from the configuration file
+    ///
+    /// # Arguments
+    /// * `file` - The `[serve]` table of the configuration file
+    pub fn or(self, file: ServeOptions) -> ServeOptions {
+        ServeOptions {
+            config: self.config.or(file.config),
+            init: self.init,
+            sync_git: self.sync_git || file.sync_git,
+            git_remote: self.git_remote.or(file.git_remote),
+            weekly_tags: self.weekly_tags || file.weekly_tags,
+            git_author_name: self.git_author_name.or(file.git_author_name),
+            git_author_email: self.git_author_email.or(file.git_author_email),
+            push_interval_secs: self.push_interval_secs.or(file.push_interval_secs),
+            commit_template: self.commit_template.or(file.commit_template),
+            instructions: self.instructions.or(file.instructions),
+            mirror: self.mirror.or(file.mirror),
+            mirror_format: self.mirror_format.or(file.mirror_format),
+            case_insensitive_ids: self.case_insensitive_ids || file.case_insensitive_ids,
+            validate_on_checksum_mismatch: self.validate_on_checksum_mismatch
+                || file.validate_on_checksum_mismatch,
+            strict_ids: self.strict_ids || file.strict_ids,
+            id_pattern: self.id_pattern.or(file.id_pattern),
+            id_max_length: self.id_max_length.or(file.id_max_length),
+            id_forbidden_chars: self.id_forbidden_chars.or(file.id_forbidden_chars),
+            id_scheme: self.id_scheme.or(file.id_scheme),
+            duplicate_titles: self.duplicate_titles.or(file.duplicate_titles),
+            wip_limits: if self.wip_limits.is_empty() {
+                file.wip_limits
+            } else {
+                self.wip_limits
+            },
+            wip_mode: self.wip_mode.or(file.wip_mode),
+            escalate_after_days: self.escalate_after_days.or(file.escalate_after_days),
+            escalate_to_next_action: self.escalate_to_next_action || file.escalate_to_next_action,
+            stale_after_days: self.stale_after_days.or(file.stale_after_days),
+            trash_retention_days: self.trash_retention_days.or(file.trash_retention_days),
+            strict: self.strict || file.strict,
+            read_only: self.read_only || file.read_only,
+            debounce_ms: self.debounce_ms.or(file.debounce_ms),
+            timezone: self.timezone.or(file.timezone),
+            daemon: self.daemon || file.daemon,
+            archive_done_after_days: self
+                .archive_done_after_days
+                .or(file.archive_done_after_days),
+            sync_interval_mins: self.sync_interval_mins.or(file.sync_interval_mins),
+            caldav_url: self.caldav_url.or(file.caldav_url),
+            caldav_username: self.caldav_username.or(file.caldav_username),
+            caldav_password: self.caldav_password.or(file.caldav_password),
+            caldav_conflict: self.caldav_conflict.or(file.caldav_conflict),
+            rest: self.rest.or(file.rest),
+            rest_tools: if self.rest_tools.is_empty() {
+                file.rest_tools
+            } else {
+                self.rest_tools
+            },
+            http: self.http.or(file.http),
+            webhook_url: self.webhook_url.or(file.webhook_url),
+            workspaces: if self.workspaces.is_empty() {
+                file.workspaces
+            } else {
+                self.workspaces
+            },
+        }
+    }
+
+    /// Maintenance jobs enabled for `--daemon`
+    ///
+    /// # Returns
+    /// The schedule, or an error for a setting out of range (see [`Schedule::validate`])
+    pub fn schedule(&self) -> Result<Schedule> {
+        let schedule = Schedule {
+            archive_done_after_days: self.archive_done_after_days,
+            sync_interval: self
+                .sync_interval_mins
+                .map(|mins| Duration::from_secs(mins * 60)),
+        };
+        schedule.validate()?;
+        Ok(schedule)
+    }
+
+    /// CalDAV sync settings, if a task list URL is set
+    pub fn caldav(&self) -> Option<CalDavConfig> {
+        Some(CalDavConfig {
+            url: self.caldav_url.clone()?,
+            username: self.caldav_username.clone(),
+            password: self.caldav_password.clone(),
+            conflict: self.caldav_conflict.unwrap_or_default(),
+        })
+    }
+
+    /// Open the storage of a data file with these options, setting up Git if needed
+    ///
+    /// # Arguments
+    /// * `file` - Path to the GTD data file
+    /// * `with_mirror` - Whether saves also write the `mirror` file (only one
+    ///   workspace can own it)
+    pub fn open_storage(&self, file: impl AsRef<Path>, with_mirror: bool) -> Result<Storage> {
+        let mut storage = Storage::new(file, self.sync_git)
+            .with_git_author(self.git_author_name.clone(), self.git_author_email.clone());
+        // Stdout carries the MCP protocol, so setup notes go to stderr
+        for action in storage.bootstrap_git(self.git_remote.as_deref())? {
+            eprintln!("Git setup: {}", action);
+        }
+        if with_mirror && let Some(mirror) = &self.mirror {
+            let Some(format) = self
+                .mirror_format
+                .or_else(|| MirrorFormat::from_path(mirror))
+            else {
+                bail!(
+                    "Cannot infer mirror format from '{}'; use --mirror-format json|markdown",
+                    mirror
+                );
+            };
+            storage = storage.with_mirror(mirror, format);
+        }
+        if let Some(url) = &self.webhook_url {
+            storage = storage.with_webhook(url);
+        }
+        if self.weekly_tags {
+            storage = storage.with_weekly_snapshots();
+        }
+        if self.case_insensitive_ids {
+            storage = storage.with_case_insensitive_ids();
+        }
+        if self.validate_on_checksum_mismatch {
+            storage = storage.with_checksum_validation();
+        }
+        if let Some(secs) = self.push_interval_secs {
+            storage = storage.with_background_push(Duration::from_secs(secs));
+        }
+        Ok(storage.with_debounce(Duration::from_millis(self.debounce_ms.unwrap_or(0))))
+    }
+}
+
+/// Contents of a configuration file
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Path to the GTD data file, used when none is given on the command line
+    pub file: Option<String>,
+    /// Defaults for the `serve` options
+    pub serve: ServeOptions,
+}
+
+impl Config {
+    /// Default location: `$XDG_CONFIG_HOME/gtd-mcp/config.toml`, else `~/.config/gtd-mcp/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        let base = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => home_dir()?.join(".config"),
+        };
+        Some(base.join("gtd-mcp").join("config.toml"))
+    }
+
+    /// Load a configuration file, resolving its relative paths
+    ///
+    /// # Arguments
+    /// * `path` - Path to the configuration file
+    pub fn load(path: &Path) -> Result<Config> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let mut config: Config = toml::from_str(&content)
+            .map_err(|e| anyhow!("Invalid config file {}: {}", path.display(), e))?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        config.file = config.file.map(|file| resolve_path(base, &file));
+        config.serve.mirror = config.serve.mirror.map(|file| resolve_path(base, &file));
+        for workspace in &mut config.serve.workspaces {
+            workspace.path = PathBuf::from(resolve_path(base, &workspace.path.to_string_lossy()));
+        }
+        Ok(config)
+    }
+
+    /// Load the configuration given by `--config`, or the default file if it exists
+    ///
+    /// # Arguments
+    /// * `explicit` - Path given on the command line (must exist)
+    ///
+    /// # Returns
+    /// The loaded configuration, or an empty one when no file is used
+    pub fn discover(explicit: Option<&Path>) -> Result<Config> {
+        match explicit {
+            Some(path) => Config::load(path),
+            None => match Config::default_path() {
+                Some(path) if path.is_file() => Config::load(&path),
+                _ => Ok(Config::default()),
+            },
+        }
+    }
+}
+
+/// Parse a UTC offset such as "+09:00", "-05:30" or "UTC"
+pub fn parse_utc_offset(s: &str) -> Result<FixedOffset, String> {
+    if s.eq_ignore_ascii_case("utc") || s == "Z" {
+        return Ok(FixedOffset::east_opt(0).expect("zero offset is valid"));
+    }
+    FixedOffset::from_str(s).map_err(|_| {
+        format!(
+            "Invalid timezone '{}'. Use a UTC offset such as +09:00, -05:30 or UTC",
+            s
+        )
+    })
+}
+
+/// Home directory from `HOME` (or `USERPROFILE` on Windows)
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Expand `~/` and make `path` relative to `base`
+fn resolve_path(base: &Path, path: &str) -> String {
+    let expanded = match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    };
+    base.join(expanded).to_string_lossy().into_owned()
+}
+
+/// Deserialize an optional value through its `FromStr` implementation
+fn parsed<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| s.parse().map_err(de::Error::custom))
+        .transpose()
+}
+
+/// Deserialize a list of values through their `FromStr` implementation
+fn parsed_list<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|s| s.parse().map_err(de::Error::custom))
+        .collect()
+}
+
+/// Deserialize an optional UTC offset (see [`parse_utc_offset`])
+fn utc_offset<'de, D>(deserializer: D) -> Result<Option<FixedOffset>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| parse_utc_offset(&s).map_err(de::Error::custom))
+        .transpose()
+}
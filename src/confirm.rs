@@ -0,0 +1,206 @@
+//! Confirmation before destructive tool calls
+//!
+//! A destructive tool (see [`tool_hints`](crate::tool_hints)) called over MCP
+//! does not run right away. The first call returns a summary of what would be
+//! destroyed and a confirmation code; the assistant shows the summary to the
+//! user and calls again with `confirm` set to the code once they agree.
+//!
+//! MCP elicitation would let the server ask the user directly, but it is not
+//! part of the protocol version mcp-attr speaks (2025-03-26), so the
+//! confirmation goes through the assistant and works with every client.
+//!
+//! The code is a hash of the tool, its arguments and the summary. It is only
+//! valid for exactly what the user saw: when more items reach the trash or the
+//! arguments change, the next call asks again. Calls with nothing to destroy
+//! (an empty trash, a dedupe report) run without confirmation.
+
+use crate::GtdServerHandler;
+use crate::checksum;
+use crate::duplicates::{self, DedupeAction};
+use crate::gtd::NotaStatus;
+use crate::tool_hints::{self, Effect};
+use crate::validation;
+use mcp_attr::schema::ListToolsResult;
+use serde_json::{Map, Value, json};
+
+/// Argument carrying the confirmation code
+pub const CONFIRM_ARGUMENT: &str = "confirm";
+
+/// Most items a summary names before it only counts the rest
+const SUMMARY_ITEMS: usize = 20;
+
+/// Whether calls of a tool need confirmation
+pub fn needs_confirmation(tool: &str) -> bool {
+    tool_hints::tool_hint(tool).is_some_and(|hint| hint.effect == Effect::Destructive)
+}
+
+/// The confirmation code for a call
+///
+/// # Arguments
+/// * `tool` - Tool name
+/// * `arguments` - Arguments of the call, without `confirm`
+/// * `summary` - What the call would destroy
+pub fn code(tool: &str, arguments: &Map<String, Value>, summary: &str) -> String {
+    let digest = checksum::digest(&format!(
+        "{}\n{}\n{}",
+        tool,
+        Value::Object(arguments.clone()),
+        summary
+    ));
+    let hex = digest.rsplit(':').next().unwrap_or(&digest);
+    hex[..8].to_string()
+}
+
+/// The answer to a call that waits for confirmation
+pub fn request(tool: &str, summary: &str, code: &str) -> String {
+    format!(
+        "Confirmation needed - {} was not run.\n\n{}\n\nShow this to the user. Only if they agree, call {} again with the same arguments and {}=\"{}\".",
+        tool, summary, tool, CONFIRM_ARGUMENT, code
+    )
+}
+
+/// Add the `confirm` argument to the destructive tools of a tool list
+pub fn add_argument(result: &mut ListToolsResult) {
+    for tool in &mut result.tools {
+        if !needs_confirmation(&tool.name) {
+            continue;
+        }
+        let mut property = Map::new();
+        property.insert("type".to_string(), json!("string"));
+        property.insert(
+            "description".to_string(),
+            json!("Optional: Confirmation code returned by the first call, once the user agreed to the summary"),
+        );
+        tool.input_schema
+            .properties
+            .insert(CONFIRM_ARGUMENT.to_string(), property);
+        let description = tool.description.get_or_insert_with(String::new);
+        description.push_str(
+            "\n**Confirm**: The first call only returns what would be destroyed and a code. Show it to the user; call again with confirm=<code> only if they agree.",
+        );
+    }
+}
+
+impl GtdServerHandler {
+    /// What a call of a destructive tool would destroy
+    ///
+    /// # Returns
+    /// The summary, or `None` when the call destroys nothing (or would fail
+    /// anyway) and can run without confirmation
+    pub async fn destruction_summary(
+        &self,
+        tool: &str,
+        arguments: &Map<String, Value>,
+    ) -> Option<String> {
+        match tool {
+            "empty_trash" => {
+                let data = self.data.read().await;
+                let trash: Vec<String> = data
+                    .iter()
+                    .filter(|nota| nota.status == NotaStatus::trash)
+                    .map(|nota| format!("[{}] {}", nota.id, nota.title))
+                    .collect();
+                (!trash.is_empty()).then(|| {
+                    format!(
+                        "Permanently deletes {} trashed item(s):\n{}",
+                        trash.len(),
+                        bullet_list(&trash)
+                    )
+                })
+            }
+            "dedupe" => {
+                let action = arguments
+                    .get("action")
+                    .and_then(Value::as_str)
+                    .and_then(|action| action.parse::<DedupeAction>().ok())?;
+                let verb = match action {
+                    DedupeAction::Report => return None,
+                    DedupeAction::Merge => "merge",
+                    DedupeAction::Trash => "trash",
+                };
+                let data = self.data.read().await;
+                let only: Option<Vec<String>> =
+                    arguments.get("ids").and_then(Value::as_array).map(|ids| {
+                        ids.iter()
+                            .filter_map(Value::as_str)
+                            .map(validation::normalize_task_id)
+                            .map(|id| data.resolve_id(&id).map(str::to_string).unwrap_or(id))
+                            .collect()
+                    });
+                let groups: Vec<String> = duplicates::duplicate_groups(&data)
+                    .into_iter()
+                    .filter(|group| {
+                        only.as_ref().is_none_or(|only| {
+                            only.contains(&group.keep)
+                                || group.duplicates.iter().any(|id| only.contains(id))
+                        })
+                    })
+                    .map(|group| {
+                        format!(
+                            "keep {}, {} {}",
+                            group.keep,
+                            verb,
+                            group.duplicates.join(", ")
+                        )
+                    })
+                    .collect();
+                (!groups.is_empty()).then(|| {
+                    format!(
+                        "Folds {} group(s) of duplicates ({} the duplicates):\n{}",
+                        groups.len(),
+                        verb,
+                        bullet_list(&groups)
+                    )
+                })
+            }
+            "abort" => {
+                let transaction = self.transaction.lock().unwrap();
+                let messages = &transaction.as_ref()?.messages;
+                let changes: Vec<String> = messages
+                    .iter()
+                    .map(|message| message.lines().next().unwrap_or_default().to_string())
+                    .collect();
+                (!changes.is_empty()).then(|| {
+                    format!(
+                        "Rolls back {} change(s) of the open transaction:\n{}",
+                        changes.len(),
+                        bullet_list(&changes)
+                    )
+                })
+            }
+            "restore_backup" => {
+                let number = arguments.get("number").and_then(Value::as_u64);
+                let backups = self.backups.lock().unwrap();
+                let (number, snapshot) = match number {
+                    Some(number) => backups.iter().find(|(n, _)| *n as u64 == number)?,
+                    None => backups.last()?,
+                };
+                Some(format!(
+                    "Replaces the data ({}) with backup #{} of {} items taken at {}; everything saved since then is lost.",
+                    self.storage().file_path().display(),
+                    number,
+                    snapshot.item_count(),
+                    snapshot.taken_at().format("%Y-%m-%d %H:%M:%S")
+                ))
+            }
+            "reload" => Some(format!(
+                "Replaces the server's copy of the data with {} on disk; changes that were not saved to the file are dropped.",
+                self.storage().file_path().display()
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Markdown list of the first [`SUMMARY_ITEMS`] lines, counting the rest
+fn bullet_list(lines: &[String]) -> String {
+    let mut list: Vec<String> = lines
+        .iter()
+        .take(SUMMARY_ITEMS)
+        .map(|line| format!("- {}", line))
+        .collect();
+    if lines.len() > SUMMARY_ITEMS {
+        list.push(format!("- ... and {} more", lines.len() - SUMMARY_ITEMS));
+    }
+    list.join("\n")
+}
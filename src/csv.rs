@@ -0,0 +1,327 @@
+//! CSV export and import for spreadsheets
+//!
+//! One row per nota, with a header row naming the columns in [`COLUMNS`]:
+//!
+//! | Column | Content |
+//! |---|---|
+//! | `id` | Item ID (empty on import: generated from the title) |
+//! | `title` | Title (required) |
+//! | `status` | `inbox`, `next_action`, ... (empty on import: `inbox` for new rows) |
+//! | `project` | Parent project ID |
+//! | `context` | Context name |
+//! | `start_date` | `YYYY-MM-DD` |
+//! | `notes` | Markdown notes (may span lines inside quotes) |
+//! | `recurrence_pattern` | `daily`, `weekly`, `monthly` or `yearly` |
+//! | `recurrence_config` | e.g. `Monday,Friday` or `1,15` |
+//! | `link` | URL of the source (e.g. a GitHub issue) |
+//! | `priority` | `high`, `medium` or `low` |
+//! | `due_date` | `YYYY-MM-DD` |
+//! | `estimate` | Minutes |
+//! | `energy` | `low`, `medium` or `high` |
+//! | `created_at` | `YYYY-MM-DD` |
+//! | `updated_at` | `YYYY-MM-DD` |
+//!
+//! Import updates the rows whose ID exists and adds the others. Columns can be
+//! left out or reordered; a missing column keeps the stored value, while an
+//! empty cell clears an optional field.
+
+use crate::gtd::{GtdData, Nota, NotaStatus, RecurrencePattern};
+use crate::import::ImportSummary;
+use crate::slug;
+use anyhow::{Result, anyhow, bail};
+use chrono::NaiveDate;
+use std::str::FromStr;
+
+/// Columns in export order
+pub const COLUMNS: [&str; 16] = [
+    "id",
+    "title",
+    "status",
+    "project",
+    "context",
+    "start_date",
+    "notes",
+    "recurrence_pattern",
+    "recurrence_config",
+    "link",
+    "priority",
+    "due_date",
+    "estimate",
+    "energy",
+    "created_at",
+    "updated_at",
+];
+
+/// Render every nota as one CSV row under a header row
+///
+/// # Arguments
+/// * `data` - The GtdData to render
+pub fn render(data: &GtdData) -> String {
+    let mut csv = COLUMNS.join(",");
+    csv.push('\n');
+    for nota in data.iter() {
+        let fields = [
+            nota.id.clone(),
+            nota.title.clone(),
+            format!("{:?}", nota.status),
+            nota.project.clone().unwrap_or_default(),
+            nota.context.clone().unwrap_or_default(),
+            nota.start_date.map(|d| d.to_string()).unwrap_or_default(),
+            nota.notes.clone().unwrap_or_default(),
+            nota.recurrence_pattern
+                .as_ref()
+                .map(|p| format!("{:?}", p))
+                .unwrap_or_default(),
+            nota.recurrence_config.clone().unwrap_or_default(),
+            nota.link.clone().unwrap_or_default(),
+            nota.priority
+                .map(|p| format!("{:?}", p))
+                .unwrap_or_default(),
+            nota.due_date.map(|d| d.to_string()).unwrap_or_default(),
+            nota.estimate.map(|m| m.to_string()).unwrap_or_default(),
+            nota.energy.map(|e| format!("{:?}", e)).unwrap_or_default(),
+            nota.created_at.to_string(),
+            nota.updated_at.to_string(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| quote(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a field when it contains a delimiter, a quote, a line break or edge spaces
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) || field.trim() != field {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split CSV text into rows of fields (RFC 4180, with `\n` or `\r\n` line ends)
+///
+/// # Returns
+/// Each row with the line number it starts on
+fn parse_rows(text: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                '\r' => {}
+                c => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut row)));
+                line += 1;
+                row_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        bail!("line {}: unterminated quoted field", row_line);
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+    // Spreadsheets often leave blank lines at the end
+    rows.retain(|(_, row)| row.iter().any(|field| !field.trim().is_empty()));
+    Ok(rows)
+}
+
+/// Import CSV rows into `data`: rows with a known ID update that item, others are added
+///
+/// Stops at the first invalid row, leaving the rows before it applied; use
+/// [`crate::import::import`] to import all or nothing.
+///
+/// # Arguments
+/// * `data` - The data to import into
+/// * `text` - CSV text with a header row
+/// * `today` - Date used for `created_at`/`updated_at` when the row has none
+pub fn import(data: &mut GtdData, text: &str, today: NaiveDate) -> Result<ImportSummary> {
+    let mut rows = parse_rows(text)?.into_iter();
+    let Some((_, header)) = rows.next() else {
+        bail!("The CSV file is empty (expected a header row)");
+    };
+    let mut columns = Vec::new();
+    for name in &header {
+        let name = name.trim().to_ascii_lowercase();
+        let Some(&column) = COLUMNS.iter().find(|c| **c == name) else {
+            bail!(
+                "Unknown column '{}'. Valid columns are: {}",
+                name,
+                COLUMNS.join(", ")
+            );
+        };
+        if columns.contains(&column) {
+            bail!("Column '{}' appears twice", column);
+        }
+        columns.push(column);
+    }
+    if !columns.contains(&"title") && !columns.contains(&"id") {
+        bail!("The header must name an 'id' or 'title' column");
+    }
+
+    let mut summary = ImportSummary::default();
+    for (line, fields) in rows {
+        if fields.len() != columns.len() {
+            bail!(
+                "line {}: expected {} fields, found {}",
+                line,
+                columns.len(),
+                fields.len()
+            );
+        }
+        let cells: Vec<(&str, &str)> = columns
+            .iter()
+            .copied()
+            .zip(fields.iter().map(|field| field.trim()))
+            .collect();
+        let cell = |column: &str| {
+            cells
+                .iter()
+                .find(|(name, _)| *name == column)
+                .map(|(_, value)| *value)
+        };
+
+        let existing = cell("id")
+            .filter(|id| !id.is_empty())
+            .and_then(|id| data.resolve_id(id))
+            .and_then(|id| data.find_by_id(id));
+        let mut nota = match &existing {
+            Some(nota) => nota.clone(),
+            None => Nota {
+                id: match cell("id").filter(|id| !id.is_empty()) {
+                    Some(id) => id.to_string(),
+                    None => slug::unique_id(data, cell("title").unwrap_or_default()),
+                },
+                created_at: today,
+                updated_at: today,
+                ..Default::default()
+            },
+        };
+        apply_cells(&mut nota, &cells).map_err(|e| anyhow!("line {}: {}", line, e))?;
+
+        match existing {
+            Some(before) if before == nota => summary.unchanged += 1,
+            Some(_) => {
+                if cell("updated_at").is_none_or(str::is_empty) {
+                    nota.updated_at = today;
+                }
+                summary.updated.push(nota.id.clone());
+                data.update(&nota.id.clone(), nota);
+            }
+            None => {
+                summary.added.push(nota.id.clone());
+                data.add(nota);
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Copy the cells of one row onto `nota` (`id` is already applied)
+fn apply_cells(nota: &mut Nota, cells: &[(&str, &str)]) -> Result<()> {
+    let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+    for &(column, value) in cells {
+        match column {
+            "title" if value.is_empty() => bail!("title must not be empty"),
+            "title" => nota.title = value.to_string(),
+            "status" if value.is_empty() => {}
+            "status" => nota.status = value.parse::<NotaStatus>().map_err(|e| anyhow!(e))?,
+            "project" => nota.project = optional(value),
+            "context" => nota.context = optional(value),
+            "notes" => nota.notes = optional(value),
+            "start_date" => nota.start_date = parse_date(column, value)?,
+            "recurrence_pattern" => nota.recurrence_pattern = parse_pattern(value)?,
+            "recurrence_config" => nota.recurrence_config = optional(value),
+            "link" => nota.link = optional(value),
+            "priority" => nota.priority = parse_optional(value)?,
+            "due_date" => nota.due_date = parse_date(column, value)?,
+            "estimate" if value.is_empty() => nota.estimate = None,
+            "estimate" => {
+                nota.estimate = Some(
+                    value
+                        .parse()
+                        .map_err(|_| anyhow!("estimate '{}' is not a number of minutes", value))?,
+                )
+            }
+            "energy" => nota.energy = parse_optional(value)?,
+            "created_at" => {
+                if let Some(date) = parse_date(column, value)? {
+                    nota.created_at = date;
+                }
+            }
+            "updated_at" => {
+                if let Some(date) = parse_date(column, value)? {
+                    nota.updated_at = date;
+                }
+            }
+            _ => {}
+        }
+    }
+    if nota.title.is_empty() {
+        bail!("title must not be empty");
+    }
+    Ok(())
+}
+
+/// Parse an optional `YYYY-MM-DD` cell
+fn parse_date(column: &str, value: &str) -> Result<Option<NaiveDate>> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| anyhow!("{} '{}' is not a YYYY-MM-DD date", column, value))
+}
+
+/// Parse an optional cell holding a value such as a priority or energy
+fn parse_optional<T: FromStr<Err = String>>(value: &str) -> Result<Option<T>> {
+    match value {
+        "" => Ok(None),
+        value => value.parse::<T>().map(Some).map_err(|e| anyhow!(e)),
+    }
+}
+
+/// Parse an optional recurrence pattern cell
+fn parse_pattern(value: &str) -> Result<Option<RecurrencePattern>> {
+    match value {
+        "" => Ok(None),
+        "daily" => Ok(Some(RecurrencePattern::daily)),
+        "weekly" => Ok(Some(RecurrencePattern::weekly)),
+        "monthly" => Ok(Some(RecurrencePattern::monthly)),
+        "yearly" => Ok(Some(RecurrencePattern::yearly)),
+        _ => bail!(
+            "recurrence_pattern '{}' is invalid. Valid options are: daily, weekly, monthly, yearly",
+            value
+        ),
+    }
+}
@@ -0,0 +1,417 @@
+//! Daemon mode: scheduled maintenance while the server runs
+//!
+//! With `--daemon` the server runs a small scheduler next to the MCP session.
+//! It checks once a minute and runs each job when it is due:
+//! - **Tickler promotion** (when the date changes, and at startup): calendar
+//!   items whose `start_date` has arrived become next actions (paused
+//!   recurring items stay put)
+//! - **Auto-archive** (daily, with `--archive-done-after-days`): done items
+//!   untouched for that many days move to `<name>.archive.toml`
+//! - **Weekly snapshot** (when a new ISO week starts, with `--weekly-tags`):
+//!   the finished week is tagged `weekly-YYYY-Www`
+//! - **Background sync** (every `--sync-interval-mins`, with `--sync-git`):
+//!   the same as the `sync` tool
+//!
+//! The MCP session and the scheduler share the handler through [`SharedServer`].
+//! While a transaction is open the scheduler waits: jobs that are due run on
+//! the first tick after it is committed or aborted.
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::progress::Progress;
+use crate::session::{self, SessionId};
+use anyhow::Result;
+use chrono::{Datelike, Duration as Days, NaiveDate};
+use mcp_attr::Result as McpResult;
+use mcp_attr::schema::{
+    CallToolRequestParams, CallToolResult, CompleteRequestParams, CompleteResult,
+    GetPromptRequestParams, GetPromptResult, Implementation, ListPromptsRequestParams,
+    ListPromptsResult, ListResourceTemplatesRequestParams, ListResourceTemplatesResult,
+    ListResourcesRequestParams, ListResourcesResult, ListToolsRequestParams, ListToolsResult,
+    ReadResourceRequestParams, ReadResourceResult, ServerCapabilities,
+};
+use mcp_attr::server::{McpServer, RequestContext};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+/// How often the scheduler checks whether a job is due
+const TICK: Duration = Duration::from_secs(60);
+
+/// Longest `archive_done_after_days` accepted (about a century)
+pub const MAX_ARCHIVE_DONE_AFTER_DAYS: u32 = 36_500;
+
+/// Which maintenance jobs run and how often
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    /// Archive done items not updated for this many days (None keeps them)
+    pub archive_done_after_days: Option<u32>,
+    /// Sync with the Git remote this often (None leaves syncing to saves)
+    pub sync_interval: Option<Duration>,
+}
+
+impl Schedule {
+    /// Check the settings before the scheduler starts
+    ///
+    /// # Returns
+    /// An error naming the setting that is out of range
+    pub fn validate(&self) -> Result<()> {
+        if let Some(days) = self.archive_done_after_days
+            && days > MAX_ARCHIVE_DONE_AFTER_DAYS
+        {
+            anyhow::bail!(
+                "archive_done_after_days must be at most {} days, got {}",
+                MAX_ARCHIVE_DONE_AFTER_DAYS,
+                days
+            );
+        }
+        Ok(())
+    }
+}
+
+/// What the daily jobs changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    /// IDs of calendar items promoted to next_action
+    pub promoted: Vec<String>,
+    /// IDs of done items moved to the archive file
+    pub archived: Vec<String>,
+    /// Weekly snapshot tag created, if any
+    pub tagged: Option<String>,
+}
+
+impl GtdServerHandler {
+    /// Promote calendar items whose `start_date` is on or before `today` to next_action
+    ///
+    /// Paused recurring items are left alone.
+    ///
+    /// # Returns
+    /// IDs of the promoted items (nothing is saved when the list is empty)
+    pub async fn promote_due_calendar_items(&self, today: NaiveDate) -> Result<Vec<String>> {
+        let mut commit_items = Vec::new();
+        {
+            let mut data = self.data.write().await;
+            let due: Vec<String> = data
+                .iter()
+                .filter(|nota| {
+                    nota.status == NotaStatus::calendar
+                        && !nota.paused
+                        && nota.start_date.is_some_and(|date| date <= today)
+                })
+                .map(|nota| nota.id.clone())
+                .collect();
+            for id in due {
+                data.move_status(&id, NotaStatus::next_action, today);
+                if let Some(nota) = data.find_by_id(&id) {
+                    commit_items.push(CommitItem::from_nota(&nota, Some(NotaStatus::calendar)));
+                }
+            }
+        }
+        if commit_items.is_empty() {
+            return Ok(Vec::new());
+        }
+        let message = self.commit_message("Promote", &commit_items);
+        self.save_data_with_message(&message).await?;
+        Ok(commit_items.into_iter().map(|item| item.id).collect())
+    }
+
+    /// Move done items last updated before `cutoff` to the archive file
+    ///
+    /// The items are removed from the data file first and then added to the
+    /// archive ([`crate::Storage::archive_storage`]); when either write fails
+    /// they are put back, so an item is never lost or left in both files. Done
+    /// projects that items still link to are kept. Nothing is archived while a
+    /// transaction is open.
+    ///
+    /// # Returns
+    /// IDs of the archived items
+    pub async fn archive_done_items(&self, cutoff: NaiveDate) -> Result<Vec<String>> {
+        // Held until the archive is written: no transaction can begin and no
+        // other save can run in between
+        let _queue = self.write_queue.lock().await;
+        if self.in_transaction() {
+            return Ok(Vec::new());
+        }
+        let old: Vec<_> = {
+            let mut data = self.data.write().await;
+            let old: Vec<_> = data
+                .iter()
+                .filter(|nota| {
+                    nota.status == NotaStatus::done
+                        && nota.updated_at < cutoff
+                        && !data.is_referenced(&nota.id)
+                })
+                .cloned()
+                .collect();
+            for nota in &old {
+                data.remove_nota(&nota.id);
+            }
+            old
+        };
+        if old.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let commit_items: Vec<_> = old
+            .iter()
+            .map(|nota| CommitItem::from_nota(nota, Some(NotaStatus::done)))
+            .collect();
+        let message = self.commit_message("Archive", &commit_items);
+        if let Err(e) = self.write_data_queued(&message).await {
+            self.put_back(&old).await;
+            return Err(e);
+        }
+
+        let archive = self.storage().archive_storage();
+        let notas = old.clone();
+        let archived = tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut archived = if archive.file_path().exists() {
+                archive.load_local()?
+            } else {
+                GtdData::new()
+            };
+            for nota in notas {
+                archived.add(nota);
+            }
+            archive.save_with_message(&archived, &message)
+        })
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|archived| archived);
+        if let Err(e) = archived {
+            self.put_back(&old).await;
+            let message = self.commit_message("Restore", &commit_items);
+            if let Err(restore_error) = self.write_data_queued(&message).await {
+                eprintln!(
+                    "Warning: Failed to save the items whose archiving failed: {}",
+                    restore_error
+                );
+            }
+            return Err(e.context("Archiving failed; the items were put back"));
+        }
+        Ok(old.into_iter().map(|nota| nota.id).collect())
+    }
+
+    /// Add the items an archive run removed back to the data, unless they are
+    /// there again (a failed strict save reloads the file)
+    async fn put_back(&self, notas: &[Nota]) {
+        let mut data = self.data.write().await;
+        for nota in notas {
+            if data.find_by_id(&nota.id).is_none() {
+                data.add(nota.clone());
+            }
+        }
+    }
+
+    /// Run the daily jobs for `today`
+    ///
+    /// # Arguments
+    /// * `today` - The current date
+    /// * `previous` - The date of the last run (None at startup); crossing
+    ///   into a new ISO week tags the week of `previous`
+    /// * `schedule` - Which optional jobs are enabled
+    pub async fn run_daily_maintenance(
+        &self,
+        today: NaiveDate,
+        previous: Option<NaiveDate>,
+        schedule: &Schedule,
+    ) -> Result<MaintenanceReport> {
+        let mut report = MaintenanceReport {
+            promoted: self.promote_due_calendar_items(today).await?,
+            ..Default::default()
+        };
+        if let Some(days) = schedule.archive_done_after_days {
+            let Some(cutoff) =
+                Days::try_days(i64::from(days)).and_then(|age| today.checked_sub_signed(age))
+            else {
+                anyhow::bail!(
+                    "archive_done_after_days={} reaches before the first representable date",
+                    days
+                );
+            };
+            report.archived = self.archive_done_items(cutoff).await?;
+        }
+        if let Some(previous) = previous
+            && previous.iso_week() != today.iso_week()
+        {
+            let storage = self.storage();
+            report.tagged =
+                tokio::task::spawn_blocking(move || storage.tag_weekly_snapshot(previous))
+                    .await??;
+        }
+        Ok(report)
+    }
+}
+
+/// Run the scheduler until the handler is dropped
+///
+/// # Arguments
+/// * `handler` - The handler shared with the MCP session
+/// * `schedule` - Which optional jobs are enabled
+pub async fn run(handler: Weak<GtdServerHandler>, schedule: Schedule) {
+    let mut last_day = None;
+    let mut last_sync = Instant::now();
+    let mut ticker = tokio::time::interval(TICK);
+    loop {
+        ticker.tick().await;
+        let Some(handler) = handler.upgrade() else {
+            return;
+        };
+        // Nothing to maintain before the data file is taken from the roots,
+        // and nothing may be written to a read-only server's file
+        if handler.is_read_only() || handler.waits_for_roots().await {
+            continue;
+        }
+        // The jobs write outside any session, which an open transaction
+        // refuses: they run on the first tick after it ends
+        if handler.in_transaction() {
+            continue;
+        }
+
+        let today = handler.clock().today();
+        if last_day != Some(today) {
+            match handler
+                .run_daily_maintenance(today, last_day, &schedule)
+                .await
+            {
+                Ok(report) => log_report(&report),
+                Err(e) => eprintln!("Maintenance: daily jobs failed: {}", e),
+            }
+            last_day = Some(today);
+        }
+
+        if let Some(interval) = schedule.sync_interval
+            && last_sync.elapsed() >= interval
+            && handler.storage().git_sync_enabled()
+        {
+            if let Err(e) = handler.handle_sync(Progress::none()).await {
+                eprintln!(
+                    "Maintenance: background sync failed: {}",
+                    e.to_error_object(false).message
+                );
+            }
+            last_sync = Instant::now();
+        }
+    }
+}
+
+/// Note what the daily jobs changed on stderr (stdout carries the MCP protocol)
+fn log_report(report: &MaintenanceReport) {
+    if !report.promoted.is_empty() {
+        eprintln!(
+            "Maintenance: promoted to next_action: {}",
+            report.promoted.join(", ")
+        );
+    }
+    if !report.archived.is_empty() {
+        eprintln!("Maintenance: archived: {}", report.archived.join(", "));
+    }
+    if let Some(tag) = &report.tagged {
+        eprintln!("Maintenance: tagged weekly snapshot {}", tag);
+    }
+}
+
+/// One MCP session of a handler shared with other sessions and background jobs
+///
+/// Forwards every MCP request to the handler, which adds the safety hints,
+/// the read-only gating and the confirmation of destructive tools itself.
+/// Tool calls run as calls of this session (see [`session`]), so the current
+/// context and a transaction belong to the session that set or began them;
+/// when the session ends, its open transaction is aborted.
+pub struct SharedServer(pub Arc<GtdServerHandler>, SessionId);
+
+impl SharedServer {
+    /// Start a new session on `handler`
+    pub fn new(handler: Arc<GtdServerHandler>) -> Self {
+        Self(handler, session::next())
+    }
+}
+
+impl Drop for SharedServer {
+    fn drop(&mut self) {
+        // Without a runtime nothing can run tool calls any more
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let handler = self.0.clone();
+            let session = self.1;
+            runtime.spawn(async move { handler.end_session(session).await });
+        }
+    }
+}
+
+impl McpServer for SharedServer {
+    fn server_info(&self) -> Implementation {
+        self.0.server_info()
+    }
+
+    fn instructions(&self) -> Option<String> {
+        self.0.instructions()
+    }
+
+    fn capabilities(&self) -> ServerCapabilities {
+        self.0.capabilities()
+    }
+
+    async fn prompts_list(
+        &self,
+        p: ListPromptsRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ListPromptsResult> {
+        self.0.prompts_list(p, cx).await
+    }
+
+    async fn prompts_get(
+        &self,
+        p: GetPromptRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<GetPromptResult> {
+        self.0.prompts_get(p, cx).await
+    }
+
+    async fn resources_list(
+        &self,
+        p: ListResourcesRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ListResourcesResult> {
+        self.0.resources_list(p, cx).await
+    }
+
+    async fn resources_templates_list(
+        &self,
+        p: ListResourceTemplatesRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ListResourceTemplatesResult> {
+        self.0.resources_templates_list(p, cx).await
+    }
+
+    async fn resources_read(
+        &self,
+        p: ReadResourceRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ReadResourceResult> {
+        self.0.resources_read(p, cx).await
+    }
+
+    async fn tools_list(
+        &self,
+        p: ListToolsRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ListToolsResult> {
+        self.0.tools_list(p, cx).await
+    }
+
+    async fn tools_call(
+        &self,
+        p: CallToolRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<CallToolResult> {
+        session::scope(self.1, self.0.tools_call(p, cx)).await
+    }
+
+    async fn completion_complete(
+        &self,
+        p: CompleteRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<CompleteResult> {
+        self.0.completion_complete(p, cx).await
+    }
+}
@@ -0,0 +1,68 @@
+//! Finding and creating the data file when no path is given
+//!
+//! Like Git looks for `.git`, `gtd-mcp` without a path walks up from the
+//! current directory to the first `gtd.toml`. `--init` creates the file with a
+//! starter structure when it does not exist yet.
+
+use crate::gtd::{GtdData, Nota, NotaStatus, local_date_today};
+use crate::storage::Storage;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// File name looked for by [`find_data_file`]
+pub const DATA_FILE_NAME: &str = "gtd.toml";
+
+/// Contexts every new file starts with: (name, description)
+const STARTER_CONTEXTS: [(&str, &str); 5] = [
+    ("@home", "At home"),
+    ("@office", "At the office"),
+    ("@computer", "Anywhere with a computer"),
+    ("@phone", "Calls to make"),
+    ("@errands", "Out and about"),
+];
+
+/// Find `gtd.toml` in `start` or the nearest of its parent directories
+///
+/// # Arguments
+/// * `start` - Directory to start from (usually the current directory)
+pub fn find_data_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(DATA_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Data of a newly initialized file: common GTD contexts and nothing else
+pub fn starter_data() -> GtdData {
+    let today = local_date_today();
+    let mut data = GtdData::new();
+    for (id, title) in STARTER_CONTEXTS {
+        data.add(Nota {
+            id: id.to_string(),
+            title: title.to_string(),
+            status: NotaStatus::context,
+            created_at: today,
+            updated_at: today,
+            ..Default::default()
+        });
+    }
+    data
+}
+
+/// Create the data file with [`starter_data`] unless it already exists
+///
+/// # Arguments
+/// * `path` - Path of the data file
+///
+/// # Returns
+/// Whether the file was created (false when it already existed)
+pub fn init_data_file(path: &Path) -> Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    Storage::new(path, false).save(&starter_data())?;
+    Ok(true)
+}
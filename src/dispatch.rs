@@ -0,0 +1,165 @@
+//! The MCP server of the handler, as every transport serves it
+//!
+//! `#[mcp_server]` generates the tool and resource dispatch of
+//! [`GtdServerHandler`] as an impl of the crate's own [`McpServer`] trait.
+//! The impl of mcp-attr's [`McpServer`](server::McpServer) below forwards to
+//! it and adds what the generated code cannot, so the handler behaves the same
+//! on every transport (stdio, Streamable HTTP, the REST API) and whether or
+//! not it is wrapped in a [`SharedServer`](crate::daemon::SharedServer):
+//! - the tool list carries the safety hints of [`tool_hints`], and a
+//!   read-only handler lists and runs only the tools that read the data,
+//! - before the first tool call of a handler waiting for workspace roots, the
+//!   client is asked for them (see [`roots`](crate::roots)),
+//! - while a transaction of another session is open, tools that change the
+//!   data are refused (see [`GtdServerHandler::begin_transaction`]),
+//! - destructive tools only run with the confirmation code of their summary
+//!   (see [`confirm`]).
+
+use crate::GtdServerHandler;
+use crate::confirm;
+use crate::tool_hints;
+use mcp_attr::Result as McpResult;
+use mcp_attr::bail_public;
+use mcp_attr::schema::{
+    CallToolRequestParams, CallToolResult, ListResourceTemplatesRequestParams,
+    ListResourceTemplatesResult, ListResourcesRequestParams, ListResourcesResult,
+    ListToolsRequestParams, ListToolsResult, ReadResourceRequestParams, ReadResourceResult,
+    ServerCapabilities, TextContent,
+};
+use mcp_attr::server::{self, RequestContext};
+use std::future::Future;
+
+/// The requests `#[mcp_server]` answers for the handler
+///
+/// The macro only accepts a trait named `McpServer`, hence the name.
+pub(crate) trait McpServer {
+    fn instructions(&self) -> Option<String>;
+
+    fn capabilities(&self) -> ServerCapabilities;
+
+    fn resources_list(
+        &self,
+        p: ListResourcesRequestParams,
+        cx: &mut RequestContext,
+    ) -> impl Future<Output = McpResult<ListResourcesResult>> + Send;
+
+    fn resources_templates_list(
+        &self,
+        p: ListResourceTemplatesRequestParams,
+        cx: &mut RequestContext,
+    ) -> impl Future<Output = McpResult<ListResourceTemplatesResult>> + Send;
+
+    fn resources_read(
+        &self,
+        p: ReadResourceRequestParams,
+        cx: &mut RequestContext,
+    ) -> impl Future<Output = McpResult<ReadResourceResult>> + Send;
+
+    fn tools_list(
+        &self,
+        p: ListToolsRequestParams,
+        cx: &mut RequestContext,
+    ) -> impl Future<Output = McpResult<ListToolsResult>> + Send;
+
+    fn tools_call(
+        &self,
+        p: CallToolRequestParams,
+        cx: &mut RequestContext,
+    ) -> impl Future<Output = McpResult<CallToolResult>> + Send;
+}
+
+impl server::McpServer for GtdServerHandler {
+    fn instructions(&self) -> Option<String> {
+        McpServer::instructions(self)
+    }
+
+    fn capabilities(&self) -> ServerCapabilities {
+        McpServer::capabilities(self)
+    }
+
+    async fn resources_list(
+        &self,
+        p: ListResourcesRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ListResourcesResult> {
+        McpServer::resources_list(self, p, cx).await
+    }
+
+    async fn resources_templates_list(
+        &self,
+        p: ListResourceTemplatesRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ListResourceTemplatesResult> {
+        McpServer::resources_templates_list(self, p, cx).await
+    }
+
+    async fn resources_read(
+        &self,
+        p: ReadResourceRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ReadResourceResult> {
+        McpServer::resources_read(self, p, cx).await
+    }
+
+    async fn tools_list(
+        &self,
+        p: ListToolsRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<ListToolsResult> {
+        let mut result = McpServer::tools_list(self, p, cx).await?;
+        if self.is_read_only() {
+            tool_hints::retain_read_only(&mut result, self.escalate_to_next_action);
+        }
+        tool_hints::annotate(&mut result, self.escalate_to_next_action);
+        confirm::add_argument(&mut result);
+        Ok(result)
+    }
+
+    async fn tools_call(
+        &self,
+        mut p: CallToolRequestParams,
+        cx: &mut RequestContext,
+    ) -> McpResult<CallToolResult> {
+        if self.is_read_only() && !tool_hints::is_read_only(&p.name, self.escalate_to_next_action) {
+            bail_public!(
+                _,
+                "The server is read-only: {} may change the data and is not available",
+                p.name
+            );
+        }
+        if self.waits_for_roots().await {
+            if cx.client_capabilities().roots.is_none() {
+                bail_public!(
+                    _,
+                    "No data file: the server was started without one and this client does not share workspace roots. Start the server with the path of gtd.toml, or with --init to create one."
+                );
+            }
+            let roots = match cx.roots_list().await {
+                Ok(roots) => roots,
+                Err(e) => bail_public!(_, "Failed to get the client's workspace roots: {}", e),
+            };
+            match self.adopt_roots(&roots).await {
+                Ok(path) => eprintln!("Using {} in the client's workspace root", path.display()),
+                Err(e) => bail_public!(_, "{}", e),
+            }
+        }
+        if !tool_hints::is_read_only(&p.name, self.escalate_to_next_action)
+            && let Err(e) = self.claim_transaction().await
+        {
+            bail_public!(_, "{}. {} was not run.", e, p.name);
+        }
+        if confirm::needs_confirmation(&p.name) {
+            let mut arguments = p.arguments.take().unwrap_or_default();
+            let given = arguments.remove(confirm::CONFIRM_ARGUMENT);
+            if let Some(summary) = self.destruction_summary(&p.name, &arguments).await {
+                let code = confirm::code(&p.name, &arguments, &summary);
+                if given.as_ref().and_then(|given| given.as_str()) != Some(code.as_str()) {
+                    let request = confirm::request(&p.name, &summary, &code);
+                    return Ok(CallToolResult::from(vec![TextContent::from(request)]));
+                }
+            }
+            p.arguments = Some(arguments);
+        }
+        McpServer::tools_call(self, p, cx).await
+    }
+}
@@ -0,0 +1,324 @@
+//! Duplicate-content detection for captured items
+//!
+//! Agents tend to capture the same thought more than once under different IDs
+//! ("Call John about the invoice" vs "call john about invoice"). Titles are
+//! compared after normalization (case, punctuation and word order are ignored)
+//! using an edit-distance ratio, so near-identical titles are caught as well.
+//!
+//! What `inbox` does with a match is set by [`DuplicateCheck`].
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use std::fmt;
+use std::str::FromStr;
+
+/// Minimum similarity (0.0 - 1.0) for two titles to count as duplicates
+pub const SIMILARITY_THRESHOLD: f64 = 0.85;
+
+/// Most IDs suggested when an unknown ID is given
+pub const MAX_ID_SUGGESTIONS: usize = 3;
+
+/// What `inbox` does when the new title matches an open item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateCheck {
+    /// Do not compare titles
+    Off,
+    /// Create the item and add a warning naming the similar items
+    #[default]
+    Warn,
+    /// Refuse to create the item
+    Reject,
+}
+
+impl FromStr for DuplicateCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(DuplicateCheck::Off),
+            "warn" => Ok(DuplicateCheck::Warn),
+            "reject" => Ok(DuplicateCheck::Reject),
+            _ => Err(format!(
+                "Invalid duplicate check '{}'. Valid options are: off, warn, reject",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DuplicateCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DuplicateCheck::Off => "off",
+            DuplicateCheck::Warn => "warn",
+            DuplicateCheck::Reject => "reject",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Normalize a title for comparison
+///
+/// Lowercases, treats punctuation as word separators, and sorts the words so
+/// that "Invoice: call John" and "call john invoice" compare equal.
+pub fn normalize_title(title: &str) -> String {
+    let mut words: Vec<String> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    words.sort();
+    words.join(" ")
+}
+
+/// Similarity of two titles from 0.0 (unrelated) to 1.0 (same after normalization)
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    similarity(&normalize_title(a), &normalize_title(b))
+}
+
+/// Edit-distance ratio of two normalized titles
+fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    // The distance is at least the length difference; skip hopeless pairs cheaply
+    let bound = 1.0 - (longest - a.len().min(b.len())) as f64 / longest as f64;
+    if bound < SIMILARITY_THRESHOLD {
+        return bound;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+/// Number of single-character edits turning `a` into `b`
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Find existing IDs close to one that does not exist
+///
+/// IDs are compared case-insensitively by edit distance. An ID counts as close
+/// when at most a third of its characters (and at least 2) differ, or when one
+/// ID contains the other, which catches a dropped prefix or suffix.
+///
+/// # Arguments
+/// * `data` - GTD data to search
+/// * `id` - The unknown ID
+///
+/// # Returns
+/// Up to [`MAX_ID_SUGGESTIONS`] IDs, closest first
+pub fn similar_ids<'a>(data: &'a GtdData, id: &str) -> Vec<&'a str> {
+    let wanted: Vec<char> = id.to_lowercase().chars().collect();
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let limit = (wanted.len() / 3).max(2);
+    let mut matches: Vec<(&str, usize)> = data
+        .iter()
+        .filter_map(|nota| {
+            let candidate: Vec<char> = nota.id.to_lowercase().chars().collect();
+            let distance = levenshtein(&wanted, &candidate);
+            let contains = wanted.len() >= 3 && {
+                let (short, long) = if wanted.len() <= candidate.len() {
+                    (&wanted, &candidate)
+                } else {
+                    (&candidate, &wanted)
+                };
+                long.windows(short.len()).any(|w| w == short.as_slice())
+            };
+            (distance <= limit || contains).then_some((nota.id.as_str(), distance))
+        })
+        .collect();
+    matches.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+    matches.truncate(MAX_ID_SUGGESTIONS);
+    matches.into_iter().map(|(id, _)| id).collect()
+}
+
+/// Phrase ID suggestions for an error message
+///
+/// # Arguments
+/// * `suggestions` - IDs returned by [`similar_ids`]
+///
+/// # Returns
+/// e.g. " Did you mean 'call-john'?" (with a leading space), or an empty string
+pub fn did_you_mean(suggestions: &[&str]) -> String {
+    let quoted: Vec<String> = suggestions.iter().map(|id| format!("'{}'", id)).collect();
+    match quoted.split_last() {
+        None => String::new(),
+        Some((last, [])) => format!(" Did you mean {}?", last),
+        Some((last, rest)) => format!(" Did you mean {} or {}?", rest.join(", "), last),
+    }
+}
+
+/// Whether an item still needs attention (and so should not be captured again)
+fn is_open(nota: &Nota) -> bool {
+    !matches!(
+        nota.status,
+        NotaStatus::done
+            | NotaStatus::trash
+            | NotaStatus::reference
+            | NotaStatus::context
+            | NotaStatus::goal
+            | NotaStatus::template
+    )
+}
+
+/// Find open items whose title is nearly identical to `title`
+///
+/// # Arguments
+/// * `data` - GTD data to search
+/// * `title` - Title of the item being captured
+///
+/// # Returns
+/// Matching items, most similar first
+pub fn find_similar_open_items<'a>(data: &'a GtdData, title: &str) -> Vec<&'a Nota> {
+    let normalized = normalize_title(title);
+    if normalized.is_empty() {
+        return Vec::new();
+    }
+    let mut matches: Vec<(&Nota, f64)> = data
+        .iter()
+        .filter(|nota| is_open(nota))
+        .filter_map(|nota| {
+            let score = similarity(&normalized, &normalize_title(&nota.title));
+            (score >= SIMILARITY_THRESHOLD).then_some((nota, score))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+    matches.into_iter().map(|(nota, _)| nota).collect()
+}
+
+/// Describe similar items for a warning or error message
+///
+/// # Arguments
+/// * `matches` - Items returned by [`find_similar_open_items`]
+pub fn describe_matches(matches: &[&Nota]) -> String {
+    matches
+        .iter()
+        .map(|nota| format!("{} ({:?}): \"{}\"", nota.id, nota.status, nota.title))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// What `dedupe` does with the groups it finds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupeAction {
+    /// Only list the groups
+    #[default]
+    Report,
+    /// Fold each duplicate into the kept item, then trash it
+    Merge,
+    /// Trash the duplicates as they are
+    Trash,
+}
+
+impl FromStr for DedupeAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "report" => Ok(DedupeAction::Report),
+            "merge" => Ok(DedupeAction::Merge),
+            "trash" => Ok(DedupeAction::Trash),
+            _ => Err(format!(
+                "Invalid dedupe action '{}'. Valid options are: report, merge, trash",
+                s
+            )),
+        }
+    }
+}
+
+/// Normalized title and project shared by a group of duplicates
+type GroupKey = (String, Option<String>);
+
+/// Open items that share a normalized title and a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// ID of the item to keep
+    pub keep: String,
+    /// IDs of the other items, in file order
+    pub duplicates: Vec<String>,
+}
+
+/// Find groups of likely duplicates among open items
+///
+/// Items match when their titles are equal after [`normalize_title`] and they
+/// belong to the same project (or both to none), whatever their status. The
+/// kept item is the one furthest along: anything clarified beats an inbox
+/// item, then the oldest wins, then the first in the file.
+///
+/// # Arguments
+/// * `data` - GTD data to search
+///
+/// # Returns
+/// Groups in the file order of their first item
+pub fn duplicate_groups(data: &GtdData) -> Vec<DuplicateGroup> {
+    // (normalized title, project) → members, in file order
+    let mut groups: Vec<(GroupKey, Vec<&Nota>)> = Vec::new();
+    for nota in data.iter().filter(|nota| is_open(nota)) {
+        let normalized = normalize_title(&nota.title);
+        if normalized.is_empty() {
+            continue;
+        }
+        let key = (normalized, nota.project.clone());
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, members)) => members.push(nota),
+            None => groups.push((key, vec![nota])),
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(_, members)| {
+            let keep = members
+                .iter()
+                .min_by_key(|nota| (nota.status == NotaStatus::inbox, nota.created_at))
+                .expect("groups are not empty")
+                .id
+                .clone();
+            let duplicates = members
+                .iter()
+                .filter(|nota| nota.id != keep)
+                .map(|nota| nota.id.clone())
+                .collect();
+            DuplicateGroup { keep, duplicates }
+        })
+        .collect()
+}
+
+/// Fold a duplicate's details into the kept item
+///
+/// Fields the kept item lacks are taken from the duplicate, and the
+/// duplicate's notes are appended when they differ.
+pub fn merge_duplicate(keep: &mut Nota, duplicate: &Nota) {
+    match (&mut keep.notes, &duplicate.notes) {
+        (Some(notes), Some(extra)) if !notes.contains(extra.as_str()) => {
+            notes.push_str("\n\n");
+            notes.push_str(extra);
+        }
+        (None, Some(extra)) => keep.notes = Some(extra.clone()),
+        _ => {}
+    }
+    keep.context = keep.context.take().or_else(|| duplicate.context.clone());
+    keep.start_date = keep.start_date.or(duplicate.start_date);
+    keep.link = keep.link.take().or_else(|| duplicate.link.clone());
+    keep.priority = keep.priority.or(duplicate.priority);
+    keep.due_date = keep.due_date.or(duplicate.due_date);
+    keep.estimate = keep.estimate.or(duplicate.estimate);
+    keep.energy = keep.energy.or(duplicate.energy);
+    keep.goal = keep.goal.take().or_else(|| duplicate.goal.clone());
+}
@@ -0,0 +1,258 @@
+//! Parsing emails pasted as text
+//!
+//! The `capture_email` tool turns a forwarded or copied email into an inbox
+//! item: the subject becomes the title, the body the notes, and the sender is
+//! kept as a hint for who the item may be waiting on.
+//!
+//! The text is read the way mail clients render it, not as RFC 5322:
+//!
+//! - The first block of `Name: value` lines with a `From` or `Subject` header
+//!   is the header block (indented lines continue the previous header). This
+//!   skips a "Forwarded message" banner or a comment typed above it
+//! - English header names and the Japanese ones used by Outlook and Thunderbird
+//!   (`差出人`, `件名`, `日付`, ...) are recognized
+//! - `Re:`/`Fwd:`-style prefixes are removed from the subject
+//! - The body ends at a `-- ` signature separator
+
+use anyhow::{Result, bail};
+
+/// Lines that introduce a forwarded email rather than being part of it
+const FORWARD_MARKERS: [&str; 4] = [
+    "forwarded message",
+    "original message",
+    "begin forwarded message",
+    "転送メッセージ",
+];
+
+/// Subject prefixes added by replies and forwards
+const SUBJECT_PREFIXES: [&str; 6] = ["re", "fw", "fwd", "aw", "wg", "転送"];
+
+/// Header fields used by the capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    From,
+    Subject,
+    Date,
+    /// Recognized but unused (To, Cc, ...), so the header block continues
+    Other,
+}
+
+/// Field of a header name, or `None` when `name` is not a header
+fn field(name: &str) -> Option<Field> {
+    match name.trim().to_lowercase().as_str() {
+        "from" | "差出人" | "送信者" => Some(Field::From),
+        "subject" | "件名" => Some(Field::Subject),
+        "date" | "sent" | "日付" | "送信日時" => Some(Field::Date),
+        "to" | "cc" | "bcc" | "reply-to" | "宛先" => Some(Field::Other),
+        _ => None,
+    }
+}
+
+/// Split a header line into its field and value
+fn header(line: &str) -> Option<(Field, &str)> {
+    // Gmail and Outlook sometimes bold the names ("**From:** ...") when copied
+    let line = line.trim_start_matches(['*', '>', ' ']);
+    let (name, value) = line.split_once([':', '：'])?;
+    let value = value.trim_start_matches('*').trim();
+    Some((field(name.trim_end_matches('*'))?, value))
+}
+
+/// The sender of an email
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sender {
+    /// Display name, if the address came with one
+    pub name: Option<String>,
+    /// Email address, if one could be found
+    pub address: Option<String>,
+}
+
+impl Sender {
+    /// Parse `Name <address>`, `"Last, First" <address>`, `address` or `Name`
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        let (name, address) = match (value.rfind('<'), value.rfind('>')) {
+            (Some(open), Some(close)) if open < close => {
+                (&value[..open], Some(&value[open + 1..close]))
+            }
+            _ if value.contains('@') && !value.contains(' ') => ("", Some(value)),
+            _ => (value, None),
+        };
+        let name = name.trim().trim_matches(['"', '\'']).trim();
+        Self {
+            name: (!name.is_empty()).then(|| name.to_string()),
+            address: address
+                .map(str::trim)
+                .filter(|address| !address.is_empty())
+                .map(str::to_string),
+        }
+    }
+
+    /// Name to show, falling back to the address
+    pub fn display_name(&self) -> &str {
+        self.name
+            .as_deref()
+            .or(self.address.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for Sender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.name, &self.address) {
+            (Some(name), Some(address)) => write!(f, "{} <{}>", name, address),
+            (Some(name), None) => write!(f, "{}", name),
+            (None, Some(address)) => write!(f, "{}", address),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// The parts of an email used by the capture
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Email {
+    /// Subject without `Re:`/`Fwd:` prefixes
+    pub subject: String,
+    pub from: Option<Sender>,
+    /// Date header as written
+    pub date: Option<String>,
+    /// Text typed above a forwarded email (e.g. "Can you handle this?")
+    pub comment: Option<String>,
+    /// Body without the signature
+    pub body: String,
+}
+
+impl Email {
+    /// Notes for the captured item: sender, date, comment and body
+    pub fn notes(&self) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(from) = &self.from {
+            lines.push(format!("From: {}", from));
+        }
+        if let Some(date) = &self.date {
+            lines.push(format!("Date: {}", date));
+        }
+        let mut notes = lines.join("\n");
+        for text in [self.comment.as_deref(), Some(self.body.as_str())]
+            .into_iter()
+            .flatten()
+            .filter(|text| !text.is_empty())
+        {
+            if !notes.is_empty() {
+                notes.push_str("\n\n");
+            }
+            notes.push_str(text);
+        }
+        (!notes.is_empty()).then_some(notes)
+    }
+}
+
+/// Remove `Re:`, `Fwd:`-style prefixes from a subject
+pub fn clean_subject(subject: &str) -> String {
+    let mut subject = subject.trim();
+    loop {
+        let stripped = subject
+            .split_once([':', '：'])
+            .filter(|(prefix, _)| {
+                let prefix = prefix.trim().to_lowercase();
+                // "Re[2]:" and "RE: RE:" chains
+                let prefix = prefix.split('[').next().unwrap_or_default().trim();
+                SUBJECT_PREFIXES.contains(&prefix)
+            })
+            .map(|(_, rest)| rest.trim());
+        match stripped {
+            Some(rest) => subject = rest,
+            None => break,
+        }
+    }
+    subject.to_string()
+}
+
+/// Parse pasted email text
+///
+/// # Errors
+/// When neither a subject nor a body with text can be found
+pub fn parse(raw: &str) -> Result<Email> {
+    let raw = raw.replace("\r\n", "\n");
+    let lines: Vec<&str> = raw.lines().collect();
+
+    // Find the first header block naming a sender or subject
+    let mut start = 0;
+    let mut block = None;
+    while start < lines.len() {
+        let mut end = start;
+        let mut fields = Vec::new();
+        while end < lines.len() {
+            if let Some((field, value)) = header(lines[end]) {
+                fields.push((field, value.to_string()));
+            } else if !fields.is_empty()
+                && lines[end].starts_with([' ', '\t'])
+                && !lines[end].trim().is_empty()
+            {
+                let (_, value) = fields.last_mut().expect("a header precedes");
+                value.push(' ');
+                value.push_str(lines[end].trim());
+            } else {
+                break;
+            }
+            end += 1;
+        }
+        if fields
+            .iter()
+            .any(|(field, _)| matches!(field, Field::From | Field::Subject))
+        {
+            block = Some((start, end, fields));
+            break;
+        }
+        start = end.max(start + 1);
+    }
+
+    let mut email = Email::default();
+    let body_lines = match block {
+        Some((start, end, fields)) => {
+            for (field, value) in fields {
+                match field {
+                    Field::From if email.from.is_none() => email.from = Some(Sender::parse(&value)),
+                    Field::Subject if email.subject.is_empty() => {
+                        email.subject = clean_subject(&value)
+                    }
+                    Field::Date if email.date.is_none() => email.date = Some(value),
+                    _ => {}
+                }
+            }
+            let comment = lines[..start]
+                .iter()
+                .filter(|line| {
+                    let line = line.to_lowercase();
+                    !FORWARD_MARKERS.iter().any(|marker| line.contains(marker))
+                })
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n");
+            let comment = comment.trim();
+            email.comment = (!comment.is_empty()).then(|| comment.to_string());
+            &lines[end..]
+        }
+        None => &lines[..],
+    };
+    let body = body_lines
+        .iter()
+        .take_while(|line| **line != "-- " && **line != "--")
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    email.body = body.trim().to_string();
+
+    if email.subject.is_empty() {
+        // No subject: use the first line of the body, as for a quick note
+        match email
+            .body
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+        {
+            Some(line) => email.subject = line.to_string(),
+            None => bail!("Nothing to capture: the email has no subject and no body"),
+        }
+    }
+    Ok(email)
+}
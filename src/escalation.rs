@@ -0,0 +1,71 @@
+//! Escalation of waiting-for items
+//!
+//! Delegated work that waits too long needs a follow-up. A waiting_for item is
+//! due for escalation once it has waited `escalate_after_days`: its own value
+//! (set with `set_escalation`), or else the server default
+//! (`--escalate-after-days`). Like the waiting-for report, an item is taken to
+//! be waiting since its `updated_at` date.
+//!
+//! `suggest` lists items due for escalation among the next actions with an
+//! "escalate" marker; with `--escalate-to-next-action` they are moved back to
+//! next_action instead.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::NaiveDate;
+
+/// A waiting item that has waited past its threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overdue {
+    /// Days waited so far
+    pub days: i64,
+    /// Days the item may wait
+    pub limit: u32,
+}
+
+impl Overdue {
+    /// Reason shown with the item, e.g. "escalate: waiting 12 days (limit 7)"
+    pub fn reason(&self) -> String {
+        format!(
+            "escalate: waiting {} day{} (limit {})",
+            self.days,
+            if self.days == 1 { "" } else { "s" },
+            self.limit
+        )
+    }
+}
+
+/// Days `nota` may wait before it is escalated, if it ever is
+///
+/// # Arguments
+/// * `nota` - The item
+/// * `default` - Server default for items without their own threshold
+pub fn threshold(nota: &Nota, default: Option<u32>) -> Option<u32> {
+    nota.escalate_after_days.or(default)
+}
+
+/// How long `nota` has waited past its threshold, if it is a waiting item due for escalation
+///
+/// # Arguments
+/// * `nota` - The item
+/// * `default` - Server default for items without their own threshold
+/// * `today` - The current date
+pub fn overdue(nota: &Nota, default: Option<u32>, today: NaiveDate) -> Option<Overdue> {
+    if nota.status != NotaStatus::waiting_for {
+        return None;
+    }
+    let limit = threshold(nota, default)?;
+    let days = (today - nota.updated_at).num_days();
+    (days >= i64::from(limit)).then_some(Overdue { days, limit })
+}
+
+/// Waiting items due for escalation, in file order
+///
+/// # Arguments
+/// * `data` - The data to check
+/// * `default` - Server default for items without their own threshold
+/// * `today` - The current date
+pub fn due(data: &GtdData, default: Option<u32>, today: NaiveDate) -> Vec<(String, Overdue)> {
+    data.iter()
+        .filter_map(|nota| Some((nota.id.clone(), overdue(nota, default, today)?)))
+        .collect()
+}
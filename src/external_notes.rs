@@ -0,0 +1,350 @@
+//! Long notes stored as external Markdown files (format version 4)
+//!
+//! A file with `external_notes_min_chars = N` keeps every note of at least N
+//! characters in its own Markdown file under `<stem>.notes/` next to the data
+//! file (`gtd.toml` → `gtd.notes/<id>.md`). The item refers to it by name:
+//!
+//! ```toml
+//! format_version = 4
+//! external_notes_min_chars = 200
+//!
+//! [[project]]
+//! id = "website"
+//! title = "Website redesign"
+//! notes_file = "website.md"
+//! ```
+//!
+//! The TOML file stays small and an edited note shows up in `git diff` as a
+//! plain Markdown change. Only the file on disk uses references: loading reads
+//! the notes back inline, and the serialized data, the journal and the mirror
+//! always hold the full text.
+//!
+//! `gtd-mcp migrate --external-notes [MIN_CHARS] <file>` (default 200) converts
+//! a file to this layout and `gtd-mcp migrate --inline-notes <file>` converts it back;
+//! saves keep whichever layout the file uses.
+
+use crate::gtd::{GtdData, NotesByReference};
+use crate::migration;
+use crate::storage::Storage;
+use anyhow::{Context as _, Result, anyhow};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding the notes of `data_file` (`gtd.toml` → `gtd.notes`)
+pub fn notes_dir(data_file: &Path) -> PathBuf {
+    data_file.with_file_name(notes_dir_name(data_file))
+}
+
+/// File name of [`notes_dir`]
+pub fn notes_dir_name(data_file: &Path) -> String {
+    let stem = data_file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "gtd".to_string());
+    format!("{}.notes", stem)
+}
+
+/// Markdown file names of the notes stored externally, as (item ID, file name)
+///
+/// IDs are made file-name safe (anything but ASCII letters, digits, `-`, `_`
+/// and `.` becomes `_`); names that would collide, ignoring case, get a
+/// numeric suffix. Empty when `data` keeps its notes inline.
+pub fn note_files(data: &GtdData) -> Vec<(String, String)> {
+    let Some(min_chars) = data.external_notes else {
+        return Vec::new();
+    };
+    let mut taken = HashSet::new();
+    let mut files = Vec::new();
+    for nota in data.iter() {
+        let Some(notes) = &nota.notes else {
+            continue;
+        };
+        if notes.chars().count() < min_chars {
+            continue;
+        }
+        let base: String = nota
+            .id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let base = base.trim_start_matches('.').to_string();
+        let mut name = format!("{}.md", base);
+        let mut counter = 2;
+        while !taken.insert(name.to_lowercase()) {
+            name = format!("{}-{}.md", base, counter);
+            counter += 1;
+        }
+        files.push((nota.id.clone(), name));
+    }
+    files
+}
+
+/// Content of the data file and its notes files
+pub struct Rendered {
+    /// TOML written to the data file, with `notes_file` references
+    pub content: String,
+    /// Notes files to write, as (file name, Markdown content)
+    pub notes: Vec<(String, String)>,
+}
+
+/// Render serialized data the way it is written to disk
+///
+/// # Arguments
+/// * `content` - TOML produced by [`Storage::serialize`]
+///
+/// # Returns
+/// The on-disk form, or `None` when the data keeps its notes inline
+pub fn render(content: &str) -> Result<Option<Rendered>> {
+    if !content.contains("external_notes_min_chars") {
+        return Ok(None);
+    }
+    let data: GtdData = toml::from_str(content)?;
+    if data.external_notes.is_none() {
+        return Ok(None);
+    }
+    let files = note_files(&data);
+    let notes = files
+        .iter()
+        .filter_map(|(id, name)| {
+            let nota = data.find_by_id(id)?;
+            Some((name.clone(), nota.notes?))
+        })
+        .collect();
+    let notes_files: HashMap<String, String> = files.into_iter().collect();
+    let content = toml::to_string_pretty(&NotesByReference {
+        data: &data,
+        notes_files: &notes_files,
+    })?;
+    Ok(Some(Rendered { content, notes }))
+}
+
+/// Write the notes files that changed
+///
+/// Run this before writing the data file so its references never point at
+/// files that do not exist yet.
+pub fn write_notes(dir: &Path, notes: &[(String, String)]) -> Result<()> {
+    if notes.is_empty() {
+        return Ok(());
+    }
+    fs::create_dir_all(dir)?;
+    for (name, body) in notes {
+        let path = dir.join(name);
+        if fs::read_to_string(&path).is_ok_and(|existing| existing == *body) {
+            continue;
+        }
+        fs::write(&path, body).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Remove the Markdown files in `dir` that no item refers to any more
+///
+/// The directory itself is removed once it is empty.
+pub fn prune_notes(dir: &Path, notes: &[(String, String)]) -> Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    let keep: HashSet<&str> = notes.iter().map(|(name, _)| name.as_str()).collect();
+    for entry in entries {
+        let path = entry?.path();
+        let stale = path.extension().is_some_and(|ext| ext == "md")
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| !keep.contains(name));
+        if stale {
+            fs::remove_file(&path)?;
+        }
+    }
+    if fs::read_dir(dir)?.next().is_none() {
+        fs::remove_dir(dir)?;
+    }
+    Ok(())
+}
+
+/// Read the notes files referenced by a data file back into the items
+///
+/// # Arguments
+/// * `content` - LF-normalized content of the data file (without checksum header)
+/// * `data_file` - Path of the data file, locating the notes directory
+///
+/// # Returns
+/// The document with every `notes_file` replaced by `notes`, or `None` when
+/// it has no references (parse it directly)
+pub fn inline(content: &str, data_file: &Path) -> Result<Option<toml::Table>> {
+    let dir = notes_dir(data_file);
+    inline_with(content, |name| {
+        let path = dir.join(name);
+        fs::read_to_string(&path).with_context(|| format!("{} could not be read", path.display()))
+    })
+}
+
+/// [`inline`] with the notes files read by `read_note`
+///
+/// Used where the notes are not the files in the working tree, e.g. the two
+/// sides of a Git conflict.
+///
+/// # Arguments
+/// * `content` - LF-normalized content of the data file (without checksum header)
+/// * `read_note` - Content of a notes file, by file name
+pub fn inline_with(
+    content: &str,
+    read_note: impl Fn(&str) -> Result<String>,
+) -> Result<Option<toml::Table>> {
+    if !content.contains("notes_file") {
+        return Ok(None);
+    }
+    let Ok(mut table) = content.parse::<toml::Table>() else {
+        // Let the regular load report the syntax error
+        return Ok(None);
+    };
+    let mut found = false;
+    for (_, value) in table.iter_mut() {
+        let toml::Value::Array(items) = value else {
+            continue;
+        };
+        for item in items.iter_mut().filter_map(toml::Value::as_table_mut) {
+            let Some(reference) = item.remove("notes_file") else {
+                continue;
+            };
+            found = true;
+            let id = item.get("id").and_then(toml::Value::as_str).unwrap_or("?");
+            let name = reference
+                .as_str()
+                .filter(|name| !name.contains(['/', '\\']) && *name != "..")
+                .ok_or_else(|| anyhow!("Item '{}' has an invalid notes_file: {}", id, reference))?;
+            let notes = read_note(name).with_context(|| {
+                format!("Notes file {} of item '{}' could not be read", name, id)
+            })?;
+            let notes = notes.replace("\r\n", "\n").replace('\r', "\n");
+            item.insert("notes".to_string(), toml::Value::String(notes));
+        }
+    }
+    Ok(found.then_some(table))
+}
+
+/// Outcome of [`convert_file`]
+#[derive(Debug, Clone)]
+pub struct ConversionReport {
+    /// Path of the converted file
+    pub file_path: PathBuf,
+    /// Minimum length of external notes after the conversion (`None` = inline)
+    pub min_chars: Option<usize>,
+    /// Number of notes stored in Markdown files after the conversion
+    pub external_count: usize,
+    /// Whether the file already used the requested layout
+    pub unchanged: bool,
+    /// Backup written before rewriting (None for dry runs and no-op conversions)
+    pub backup_path: Option<PathBuf>,
+    /// Whether this was a dry run (nothing written)
+    pub dry_run: bool,
+}
+
+impl fmt::Display for ConversionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dir = notes_dir_name(&self.file_path);
+        if self.unchanged {
+            return match self.min_chars {
+                Some(min_chars) => write!(
+                    f,
+                    "{} already stores notes of {} or more characters in {}/. Nothing to convert.",
+                    self.file_path.display(),
+                    min_chars,
+                    dir
+                ),
+                None => write!(
+                    f,
+                    "{} already stores its notes inline. Nothing to convert.",
+                    self.file_path.display()
+                ),
+            };
+        }
+        let heading = if self.dry_run {
+            "Would convert"
+        } else {
+            "Converted"
+        };
+        match self.min_chars {
+            Some(min_chars) => write!(
+                f,
+                "{} {} to format version {}: {} note(s) of {} or more characters in {}/",
+                heading,
+                self.file_path.display(),
+                migration::EXTERNAL_NOTES_FORMAT_VERSION,
+                self.external_count,
+                min_chars,
+                dir
+            )?,
+            None => write!(
+                f,
+                "{} {} to format version {}: all notes inline",
+                heading,
+                self.file_path.display(),
+                migration::CURRENT_FORMAT_VERSION
+            )?,
+        }
+        if let Some(ref backup) = self.backup_path {
+            write!(f, "\nBackup written to {}", backup.display())?;
+        }
+        if self.dry_run {
+            write!(f, "\nDry run: no files were modified.")?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert a data file between inline and external notes
+///
+/// Unless `dry_run`, copies the original to a timestamped `.bak` file before
+/// rewriting it; converting to inline notes also removes the notes directory.
+///
+/// # Arguments
+/// * `path` - GTD data file to convert
+/// * `min_chars` - Store notes of at least this many characters externally,
+///   or `None` to move every note back inline
+/// * `dry_run` - Report what would change without writing anything
+pub fn convert_file(
+    path: impl AsRef<Path>,
+    min_chars: Option<usize>,
+    dry_run: bool,
+) -> Result<ConversionReport> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Err(anyhow!("{} does not exist", path.display()));
+    }
+    let storage = Storage::new(path, false);
+    let mut data = storage.load_local()?;
+    let mut report = ConversionReport {
+        file_path: path.to_path_buf(),
+        min_chars,
+        external_count: 0,
+        unchanged: data.external_notes == min_chars,
+        backup_path: None,
+        dry_run,
+    };
+    data.external_notes = min_chars;
+    report.external_count = note_files(&data).len();
+    if dry_run || report.unchanged {
+        return Ok(report);
+    }
+
+    let backup = migration::backup_path_for(path);
+    fs::copy(path, &backup)
+        .with_context(|| format!("Failed to write backup {}", backup.display()))?;
+    report.backup_path = Some(backup);
+
+    let message = match min_chars {
+        Some(_) => "Store long notes in Markdown files",
+        None => "Store notes inline",
+    };
+    storage.save_with_message(&data, message)?;
+    Ok(report)
+}
@@ -0,0 +1,141 @@
+//! Daily focus: today's most important tasks (MITs)
+//!
+//! `set_focus` marks a few open items with today's date in their
+//! `focus_date` field. A focus only counts on the day it was set, so it lapses
+//! by itself at local midnight; stale dates are cleared the next time the
+//! focus is set.
+//!
+//! The daily plan returned by `set_focus` and `today` has three parts: the
+//! focus items, the calendar items for today, and other next actions due today
+//! or overdue.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::NaiveDate;
+
+/// Most items that can be in focus on one day
+pub const MAX_FOCUS: usize = 3;
+
+/// Whether `nota` is one of the focus items on `today`
+pub fn is_focused(nota: &Nota, today: NaiveDate) -> bool {
+    nota.focus_date == Some(today)
+}
+
+/// Whether `nota` can be put in focus (an open task)
+pub fn can_focus(nota: &Nota) -> bool {
+    nota.is_task()
+        && !matches!(
+            nota.status,
+            NotaStatus::done | NotaStatus::trash | NotaStatus::reference
+        )
+}
+
+/// One line of the plan: `[id] title` with the context and due date
+fn line(nota: &Nota, today: NaiveDate) -> String {
+    let mut line = format!("[{}] {}", nota.id, nota.title);
+    let mut details = Vec::new();
+    if let Some(context) = &nota.context {
+        details.push(context.clone());
+    }
+    if let Some(due) = nota.due_date {
+        if due < today {
+            details.push(format!("overdue since {}", due));
+        } else if due == today {
+            details.push("due today".to_string());
+        } else {
+            details.push(format!("due {}", due));
+        }
+    }
+    if let Some(minutes) = nota.estimate {
+        details.push(format!("{} min", minutes));
+    }
+    if !details.is_empty() {
+        line.push_str(&format!(" ({})", details.join(", ")));
+    }
+    line
+}
+
+/// The daily plan: what to work on `today`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan<'a> {
+    /// The day planned for
+    pub today: NaiveDate,
+    /// Focus items, in file order
+    pub focus: Vec<&'a Nota>,
+    /// Calendar items starting today
+    pub calendar: Vec<&'a Nota>,
+    /// Other next actions due today or overdue, earliest first
+    pub due: Vec<&'a Nota>,
+}
+
+impl<'a> Plan<'a> {
+    /// Collect the plan for `today`
+    ///
+    /// # Arguments
+    /// * `data` - The data to plan from
+    /// * `today` - The current date
+    pub fn new(data: &'a GtdData, today: NaiveDate) -> Self {
+        let focus = data.iter().filter(|n| is_focused(n, today)).collect();
+        let calendar = data
+            .iter()
+            .filter(|n| n.status == NotaStatus::calendar && n.start_date == Some(today))
+            .collect();
+        let mut due: Vec<&Nota> = data
+            .iter()
+            .filter(|n| {
+                n.status == NotaStatus::next_action
+                    && !is_focused(n, today)
+                    && n.due_date.is_some_and(|d| d <= today)
+            })
+            .collect();
+        due.sort_by_key(|n| n.due_date);
+        Plan {
+            today,
+            focus,
+            calendar,
+            due,
+        }
+    }
+
+    /// Render the compact plan
+    pub fn render(&self) -> String {
+        let today = self.today;
+        let mut plan = format!("Plan for {}\n", today.format("%a %Y-%m-%d"));
+
+        plan.push_str(&format!("\nFocus ({}/{}):\n", self.focus.len(), MAX_FOCUS));
+        if self.focus.is_empty() {
+            plan.push_str("- none yet: pick up to 3 next actions with set_focus\n");
+        }
+        for (i, nota) in self.focus.iter().enumerate() {
+            let mark = if nota.status == NotaStatus::done {
+                "[x] "
+            } else {
+                ""
+            };
+            plan.push_str(&format!("{}. {}{}\n", i + 1, mark, line(nota, today)));
+        }
+
+        if !self.calendar.is_empty() {
+            plan.push_str("\nCalendar:\n");
+            for nota in &self.calendar {
+                plan.push_str(&format!("- {}\n", line(nota, today)));
+            }
+        }
+
+        if !self.due.is_empty() {
+            plan.push_str("\nAlso due:\n");
+            for nota in &self.due {
+                plan.push_str(&format!("- {}\n", line(nota, today)));
+            }
+        }
+        plan
+    }
+}
+
+/// Render the compact plan for `today` (see [`Plan`])
+///
+/// # Arguments
+/// * `data` - The data to plan from
+/// * `today` - The current date
+pub fn plan(data: &GtdData, today: NaiveDate) -> String {
+    Plan::new(data, today).render()
+}
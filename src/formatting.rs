@@ -2,19 +2,19 @@
 //!
 //! This module contains formatting logic for displaying notas and other output.
 
-use crate::gtd::{Nota, NotaStatus};
+use crate::gtd::{GtdData, Nota, NotaStatus};
 use chrono::NaiveDate;
 
 /// Apply date filtering to notas (only affects calendar status items)
 ///
 /// # Arguments
-/// * `notas` - Mutable slice of notas to filter
+/// * `notas` - Mutable list of borrowed notas to filter
 /// * `filter_date` - Date to filter by
 ///
 /// # Description
 /// Filters calendar status items to only show those with start_date <= filter_date.
 /// Non-calendar items are not affected by date filtering.
-pub fn apply_date_filter(notas: &mut Vec<Nota>, filter_date: NaiveDate) {
+pub fn apply_date_filter(notas: &mut Vec<&Nota>, filter_date: NaiveDate) {
     notas.retain(|nota| {
         // Only apply date filtering to calendar status tasks
         if nota.status == NotaStatus::calendar {
@@ -32,9 +32,9 @@ pub fn apply_date_filter(notas: &mut Vec<Nota>, filter_date: NaiveDate) {
 /// Apply keyword filtering (case-insensitive search in id, title, and notes)
 ///
 /// # Arguments
-/// * `notas` - Mutable slice of notas to filter
+/// * `notas` - Mutable list of borrowed notas to filter
 /// * `keyword` - Keyword to search for (case-insensitive)
-pub fn apply_keyword_filter(notas: &mut Vec<Nota>, keyword: &str) {
+pub fn apply_keyword_filter(notas: &mut Vec<&Nota>, keyword: &str) {
     let keyword_lower = keyword.to_lowercase();
     notas.retain(|nota| {
         // Search in id
@@ -57,9 +57,9 @@ pub fn apply_keyword_filter(notas: &mut Vec<Nota>, keyword: &str) {
 /// Apply project filtering
 ///
 /// # Arguments
-/// * `notas` - Mutable slice of notas to filter
+/// * `notas` - Mutable list of borrowed notas to filter
 /// * `project_id` - Project ID to filter by
-pub fn apply_project_filter(notas: &mut Vec<Nota>, project_id: &str) {
+pub fn apply_project_filter(notas: &mut Vec<&Nota>, project_id: &str) {
     notas.retain(|nota| {
         nota.project
             .as_ref()
@@ -71,9 +71,9 @@ pub fn apply_project_filter(notas: &mut Vec<Nota>, project_id: &str) {
 /// Apply context filtering
 ///
 /// # Arguments
-/// * `notas` - Mutable slice of notas to filter
+/// * `notas` - Mutable list of borrowed notas to filter
 /// * `context_name` - Context name to filter by
-pub fn apply_context_filter(notas: &mut Vec<Nota>, context_name: &str) {
+pub fn apply_context_filter(notas: &mut Vec<&Nota>, context_name: &str) {
     notas.retain(|nota| {
         nota.context
             .as_ref()
@@ -82,50 +82,156 @@ pub fn apply_context_filter(notas: &mut Vec<Nota>, context_name: &str) {
     });
 }
 
+/// Cut notes down to `max_chars` characters
+///
+/// Longer notes end with an ellipsis and an indicator that names the number of
+/// characters left out and how to read them in full with `get`.
+///
+/// # Arguments
+/// * `id` - ID of the nota the notes belong to
+/// * `notes` - The notes
+/// * `max_chars` - Most characters to keep
+pub fn truncate_notes(id: &str, notes: &str, max_chars: usize) -> String {
+    let total = notes.chars().count();
+    if total <= max_chars {
+        return notes.to_string();
+    }
+    let kept: String = notes.chars().take(max_chars).collect();
+    format!(
+        "{}… [truncated, {} more chars: get(id=\"{}\") for the full notes]",
+        kept.trim_end(),
+        total - max_chars,
+        id
+    )
+}
+
+/// Days in next_action after which `list` flags an item as stale by default
+pub const DEFAULT_STALE_AFTER_DAYS: u32 = 14;
+
 /// Format notas into a display string
 ///
 /// # Arguments
-/// * `notas` - Vector of notas to format
+/// * `data` - The data the notas belong to, for project progress
+/// * `notas` - Borrowed notas to format (no item is cloned)
 /// * `exclude_notes` - Whether to exclude notes from output
+/// * `max_notes_chars` - Truncate notes longer than this many characters
+/// * `stale_after_days` - Flag next actions in that status for this many days
+///   or more with "⚠ <days>d"
+/// * `today` - Today's date, for the stale flag
 ///
 /// # Returns
 /// Formatted string representation of the notas
-pub fn format_notas(notas: Vec<Nota>, exclude_notes: bool) -> String {
+pub fn format_notas(
+    data: &GtdData,
+    notas: &[&Nota],
+    exclude_notes: bool,
+    max_notes_chars: Option<usize>,
+    stale_after_days: Option<u32>,
+    today: NaiveDate,
+) -> String {
     if notas.is_empty() {
         return "No items found".to_string();
     }
 
+    let mut stale = 0;
     let mut result = format!("Found {} item(s):\n\n", notas.len());
     for nota in notas {
         let nota_type = if nota.is_context() {
             "context"
         } else if nota.is_project() {
             "project"
+        } else if nota.is_goal() {
+            "goal"
+        } else if nota.is_template() {
+            "template"
         } else {
             "task"
         };
 
+        let flag = match stale_after_days {
+            Some(after) if nota.is_stale(today, after) => {
+                stale += 1;
+                format!(" ⚠ {}d", nota.days_in_status(today))
+            }
+            _ => String::new(),
+        };
         result.push_str(&format!(
-            "- [{}] {} (status: {:?}, type: {})\n",
-            nota.id, nota.title, nota.status, nota_type
+            "- [{}] {} (status: {:?}, type: {}){}\n",
+            nota.id, nota.title, nota.status, nota_type, flag
         ));
 
+        if nota.is_project() {
+            result.push_str(&format!(
+                "  Progress: {}\n",
+                data.project_progress(&nota.id)
+            ));
+        }
+        if nota.is_goal() {
+            result.push_str(&format!("  Progress: {}\n", data.goal_progress(&nota.id)));
+        }
+        if let Some(ref goal) = nota.goal {
+            result.push_str(&format!("  Goal: {}\n", goal));
+        }
+        if let Some(ref availability) = nota.availability {
+            result.push_str(&format!("  Available: {}\n", availability));
+        }
         if let Some(ref proj) = nota.project {
             result.push_str(&format!("  Project: {}\n", proj));
         }
         if let Some(ref ctx) = nota.context {
             result.push_str(&format!("  Context: {}\n", ctx));
+        } else if let Some(ctx) = data.effective_context(nota) {
+            result.push_str(&format!("  Context: {} (from project)\n", ctx));
         }
         if !exclude_notes && let Some(ref n) = nota.notes {
-            result.push_str(&format!("  Notes: {}\n", n));
+            match max_notes_chars {
+                Some(max) => {
+                    result.push_str(&format!("  Notes: {}\n", truncate_notes(&nota.id, n, max)))
+                }
+                None => result.push_str(&format!("  Notes: {}\n", n)),
+            }
         }
         if let Some(ref date) = nota.start_date {
             result.push_str(&format!("  Start date: {}\n", date));
         }
+        if let Some(ref date) = nota.due_date {
+            result.push_str(&format!("  Due date: {}\n", date));
+        }
+        if let Some(ref priority) = nota.priority {
+            result.push_str(&format!("  Priority: {:?}\n", priority));
+        }
+        if let Some(days) = nota.escalate_after_days {
+            result.push_str(&format!("  Escalate after: {} days\n", days));
+        }
+        if let Some(minutes) = nota.estimate {
+            result.push_str(&format!("  Estimate: {} min\n", minutes));
+        }
+        if let Some(ref energy) = nota.energy {
+            result.push_str(&format!("  Energy: {:?}\n", energy));
+        }
+        if let Some(ref previous) = nota.previous_status {
+            result.push_str(&format!("  Previous status: {:?}\n", previous));
+        }
+        if nota.paused {
+            result.push_str("  Recurrence: paused\n");
+        }
+        if nota.focus_date == Some(today) {
+            result.push_str("  Focus: today\n");
+        }
+        if let Some(ref link) = nota.link {
+            result.push_str(&format!("  Link: {}\n", link));
+        }
         // Display timestamps
         result.push_str(&format!("  Created: {}\n", nota.created_at));
         result.push_str(&format!("  Updated: {}\n", nota.updated_at));
     }
 
+    if stale > 0 {
+        result.push_str(&format!(
+            "\n⚠ {} next action(s) in next_action for {}+ days: do, renegotiate (later/someday) or drop them.\n",
+            stale,
+            stale_after_days.unwrap_or_default()
+        ));
+    }
     result
 }
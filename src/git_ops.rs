@@ -1,16 +1,69 @@
+use crate::progress::Cancellation;
 use anyhow::{Context, Result};
 use git2::{Repository, Signature, Time};
-use std::path::Path;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+/// Resolves the conflicted files of a rebase step from their two versions
+///
+/// Called with `(local, remote)`: `local` is the version from the local
+/// commit being replayed, `remote` the version already on the remote branch.
+/// Writes the merged files to the working tree in place of conflict markers;
+/// every conflicted file and the companion directories are staged afterwards.
+pub type ConflictResolver =
+    Arc<dyn Fn(&ConflictVersion, &ConflictVersion) -> Result<()> + Send + Sync>;
+
+/// One side of the files in conflict during a rebase step
+///
+/// Paths are relative to the directory of the resolver's file, so the file
+/// itself is its file name and a notes file is e.g. `gtd.notes/<id>.md`.
+pub struct ConflictVersion {
+    /// Directory of the resolver's file, for files that do not conflict
+    dir: PathBuf,
+    /// Conflicted files on this side (`None` where this side deleted it)
+    files: HashMap<PathBuf, Option<String>>,
+}
+
+impl ConflictVersion {
+    /// Content of `path` on this side
+    ///
+    /// A file that does not conflict is the same on both sides and is read
+    /// from the working tree.
+    ///
+    /// # Arguments
+    /// * `path` - Path relative to the directory of the resolver's file
+    pub fn read(&self, path: impl AsRef<Path>) -> Result<String> {
+        let path = path.as_ref();
+        match self.files.get(path) {
+            Some(Some(content)) => Ok(content.clone()),
+            Some(None) => anyhow::bail!("{} was deleted on this side", path.display()),
+            None => std::fs::read_to_string(self.dir.join(path))
+                .with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+}
+
 /// Git operations handler for automatic version control
 ///
 /// Handles Git operations like commit, pull, and push for automatic versioning
 /// of GTD data files. Detects if a file is in a Git repository and provides
 /// operations to synchronize changes with a remote repository.
+#[derive(Clone)]
 pub struct GitOps {
     /// Optional Git repository (None if file is not in a Git repository)
     repo_path: Option<Arc<Mutex<Repository>>>,
+    /// Optional automatic resolver for conflicts in one specific file
+    resolver: Option<(PathBuf, ConflictResolver)>,
+    /// Author name overriding `user.name` from the Git config
+    author_name: Option<String>,
+    /// Author email overriding `user.email` from the Git config
+    author_email: Option<String>,
+    /// Treat a missing `origin` remote as local-only history instead of an error
+    allow_no_remote: bool,
+    /// Directories next to the committed file that are committed along with it
+    companion_dirs: Vec<String>,
 }
 
 impl GitOps {
@@ -31,7 +84,60 @@ impl GitOps {
         };
 
         let repo_path = Self::find_repository(&file_dir).map(|r| Arc::new(Mutex::new(r)));
-        Self { repo_path }
+        Self {
+            repo_path,
+            resolver: None,
+            author_name: None,
+            author_email: None,
+            allow_no_remote: false,
+            companion_dirs: Vec::new(),
+        }
+    }
+
+    /// Set the identity used for commits made by the server
+    ///
+    /// Each value overrides the corresponding Git config entry (`user.name` /
+    /// `user.email`); `None` keeps the Git config (or built-in default).
+    ///
+    /// # Arguments
+    /// * `name` - Author name (e.g., "GTD Bot")
+    /// * `email` - Author email (e.g., "gtd@local")
+    pub fn with_author(mut self, name: Option<String>, email: Option<String>) -> Self {
+        self.author_name = name;
+        self.author_email = email;
+        self
+    }
+
+    /// Resolve rebase conflicts in `file_path` automatically with `resolver`
+    ///
+    /// Conflicts in the companion directories (see
+    /// [`GitOps::with_companion_dir`]) are passed to the resolver along with
+    /// the file. Without a resolver (or when other files conflict too, or the
+    /// resolver fails) the rebase is aborted and a [`GitConflict`] error is
+    /// returned.
+    ///
+    /// # Arguments
+    /// * `file_path` - The file whose conflicts the resolver can merge
+    /// * `resolver` - Merge function for the two conflicting versions
+    pub fn with_conflict_resolver(
+        mut self,
+        file_path: impl AsRef<Path>,
+        resolver: ConflictResolver,
+    ) -> Self {
+        self.resolver = Some((file_path.as_ref().to_path_buf(), resolver));
+        self
+    }
+
+    /// Commit the directory `name` next to the committed file along with it
+    ///
+    /// Additions, changes and deletions inside the directory are staged by
+    /// every [`GitOps::commit`]; a missing directory is skipped.
+    ///
+    /// # Arguments
+    /// * `name` - Directory name (e.g., "gtd.notes" for the notes of `gtd.toml`)
+    pub fn with_companion_dir(mut self, name: impl Into<String>) -> Self {
+        self.companion_dirs.push(name.into());
+        self
     }
 
     /// Check if the file is under Git version control
@@ -42,6 +148,163 @@ impl GitOps {
         self.repo_path.is_some()
     }
 
+    /// Prepare a repository for Git sync on a fresh setup
+    ///
+    /// Initializes a repository next to `file_path` when there is none. When the
+    /// repository has no `origin` remote, `remote_url` is added as `origin`;
+    /// without a URL, commits stay local and pull/push are skipped until a remote
+    /// is added. A repository without commits either adopts the remote branch
+    /// (when `origin` already has history) or gets an initial commit, which is
+    /// pushed to an empty remote.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the GTD data file
+    /// * `remote_url` - URL to use for `origin` if the repository has no remote
+    ///
+    /// # Returns
+    /// Descriptions of the steps taken (empty if the repository was ready)
+    pub fn bootstrap(&mut self, file_path: &Path, remote_url: Option<&str>) -> Result<Vec<String>> {
+        let mut actions = Vec::new();
+
+        if self.repo_path.is_none() {
+            let dir = match file_path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            std::fs::create_dir_all(dir).context("Failed to create data directory")?;
+            let repo = Repository::init(dir).context("Failed to initialize Git repository")?;
+            actions.push(format!("Initialized Git repository in {}", dir.display()));
+            self.repo_path = Some(Arc::new(Mutex::new(repo)));
+        }
+        let Some(repo) = self.repo_path.clone() else {
+            return Ok(actions);
+        };
+
+        let push_initial_commit = {
+            let repo = repo.lock().unwrap();
+
+            let has_remote = match (repo.find_remote("origin").is_ok(), remote_url) {
+                (true, _) => true,
+                (false, Some(url)) => {
+                    repo.remote("origin", url)
+                        .context("Failed to add remote 'origin'")?;
+                    actions.push(format!("Added remote origin: {}", url));
+                    true
+                }
+                (false, None) => {
+                    self.allow_no_remote = true;
+                    actions.push(
+                        "No remote 'origin' configured; commits stay local until one is added"
+                            .to_string(),
+                    );
+                    false
+                }
+            };
+
+            if repo.head().is_ok() {
+                false
+            } else if has_remote && let Some(branch) = Self::adopt_remote_branch(&repo)? {
+                actions.push(format!(
+                    "Checked out existing history from origin/{}",
+                    branch
+                ));
+                false
+            } else {
+                self.initial_commit(&repo, file_path)?;
+                actions.push("Created initial commit".to_string());
+                has_remote
+            }
+        };
+
+        if push_initial_commit {
+            self.push()
+                .context("Failed to push initial commit to origin")?;
+            actions.push("Pushed initial commit to origin".to_string());
+        }
+        Ok(actions)
+    }
+
+    /// Point an unborn HEAD at the remote's history, if the remote has any
+    ///
+    /// # Returns
+    /// The adopted branch name, or `None` when the remote is empty
+    fn adopt_remote_branch(repo: &Repository) -> Result<Option<String>> {
+        let mut remote = repo.find_remote("origin")?;
+        remote
+            .fetch::<&str>(&[], None, None)
+            .context("Failed to fetch from origin")?;
+
+        let local_branch = repo
+            .find_reference("HEAD")?
+            .symbolic_target()?
+            .and_then(|target| target.strip_prefix("refs/heads/"))
+            .map(str::to_string);
+        let remote_branches: Vec<String> = repo
+            .references_glob("refs/remotes/origin/*")?
+            .filter_map(|r| r.ok())
+            .filter_map(|r| r.name().ok().map(str::to_string))
+            .filter_map(|name| {
+                name.strip_prefix("refs/remotes/origin/")
+                    .map(str::to_string)
+            })
+            .filter(|name| name != "HEAD")
+            .collect();
+
+        let branch = match (local_branch, remote_branches.as_slice()) {
+            (_, []) => return Ok(None),
+            (Some(local), branches) if branches.contains(&local) => local,
+            (_, [only]) => only.clone(),
+            (_, branches) => anyhow::bail!(
+                "origin has several branches ({}); check out the one holding your GTD data",
+                branches.join(", ")
+            ),
+        };
+
+        let target = repo
+            .find_reference(&format!("refs/remotes/origin/{}", branch))?
+            .target()
+            .context("Remote branch has no target")?;
+        repo.branch(&branch, &repo.find_commit(target)?, false)?;
+        repo.set_head(&format!("refs/heads/{}", branch))?;
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().safe()))
+            .context(
+                "Local files conflict with the history on origin; move them aside and restart",
+            )?;
+        Ok(Some(branch))
+    }
+
+    /// Create the first commit of a new repository (with the data file, if present)
+    fn initial_commit(&self, repo: &Repository, file_path: &Path) -> Result<()> {
+        let mut index = repo.index()?;
+        if file_path.exists() {
+            index.add_path(&Self::relative_path(repo, file_path)?)?;
+            index.write()?;
+        }
+        let tree = repo.find_tree(index.write_tree()?)?;
+        let signature = self.get_signature(repo)?;
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Initialize GTD data repository",
+            &tree,
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Look up the `origin` remote
+    ///
+    /// # Returns
+    /// The remote, or `None` if it is missing and local-only history is allowed
+    fn find_origin<'r>(&self, repo: &'r Repository) -> Result<Option<git2::Remote<'r>>> {
+        match repo.find_remote("origin") {
+            Ok(remote) => Ok(Some(remote)),
+            Err(_) if self.allow_no_remote => Ok(None),
+            Err(e) => Err(e).context("Failed to find remote 'origin'"),
+        }
+    }
+
     /// Find the Git repository containing the given path
     ///
     /// # Arguments
@@ -55,15 +318,26 @@ impl GitOps {
 
     /// Pull changes from remote repository
     ///
-    /// Fetches changes from the origin remote and performs a fast-forward merge
-    /// if possible. Returns an error if a normal merge is required.
+    /// Fetches the current branch from the origin remote and integrates it:
+    /// fast-forwards when there are no local commits, and rebases local commits
+    /// onto the fetched ones when both sides have diverged. If the rebase hits a
+    /// conflict it is aborted (leaving the local branch untouched) and a
+    /// [`GitConflict`] error is returned.
     ///
     /// # Returns
     /// Result indicating success or an error
     pub fn pull(&self) -> Result<()> {
+        self.integrate_remote().map(|_| ())
+    }
+
+    /// Fetch and integrate remote changes (see [`GitOps::pull`])
+    ///
+    /// # Returns
+    /// `true` if remote commits were brought into the local branch
+    pub(crate) fn integrate_remote(&self) -> Result<bool> {
         let repo = match &self.repo_path {
             Some(r) => r.lock().unwrap(),
-            None => return Ok(()), // Not a git repo, skip
+            None => return Ok(false), // Not a git repo, skip
         };
 
         // Get the current branch
@@ -74,9 +348,9 @@ impl GitOps {
             .to_string();
 
         // Fetch from origin
-        let mut remote = repo
-            .find_remote("origin")
-            .context("Failed to find remote 'origin'")?;
+        let Some(mut remote) = self.find_origin(&repo)? else {
+            return Ok(false); // Local-only history, nothing to pull
+        };
 
         remote
             .fetch(&[&branch_name], None, None)
@@ -91,7 +365,7 @@ impl GitOps {
 
         if analysis.is_up_to_date() {
             // Already up to date
-            return Ok(());
+            return Ok(false);
         }
 
         if analysis.is_fast_forward() {
@@ -102,34 +376,166 @@ impl GitOps {
             repo.set_head(&refname)?;
             repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
         } else if analysis.is_normal() {
-            // Normal merge - for simplicity, we'll skip this case
-            // In a real implementation, you might want to handle conflicts
-            return Err(anyhow::anyhow!(
-                "Merge required but automatic merge is not supported. Please resolve manually."
-            ));
+            // Both sides have new commits: replay ours on top of the remote
+            let local_commit = repo.reference_to_annotated_commit(&head)?;
+            self.rebase_onto(&repo, &branch_name, &local_commit, &fetch_commit)?;
         }
 
-        Ok(())
+        Ok(true)
     }
 
-    /// Commit changes to the repository
+    /// Rebase the local branch onto `upstream`
     ///
-    /// Stages the specified file and creates a commit with the given message.
+    /// Conflicts in the resolver's file are merged automatically; any other
+    /// conflict aborts the rebase.
     ///
     /// # Arguments
-    /// * `file_path` - Path to the file to commit
-    /// * `message` - Commit message
+    /// * `repo` - The Git repository
+    /// * `branch_name` - Name of the branch being rebased (for error messages)
+    /// * `local` - Tip of the local branch
+    /// * `upstream` - Commit to replay the local commits onto
     ///
     /// # Returns
-    /// Result indicating success or an error
-    pub fn commit(&self, file_path: &Path, message: &str) -> Result<()> {
-        let repo = match &self.repo_path {
-            Some(r) => r.lock().unwrap(),
-            None => return Ok(()), // Not a git repo, skip
+    /// Result indicating success, or a [`GitConflict`] error
+    fn rebase_onto(
+        &self,
+        repo: &Repository,
+        branch_name: &str,
+        local: &git2::AnnotatedCommit<'_>,
+        upstream: &git2::AnnotatedCommit<'_>,
+    ) -> Result<()> {
+        let signature = self.get_signature(repo)?;
+        let mut rebase = repo
+            .rebase(Some(local), Some(upstream), None, None)
+            .context("Failed to start rebase onto origin")?;
+
+        while let Some(operation) = rebase.next() {
+            operation.context("Failed to apply local commit during rebase")?;
+
+            let mut index = repo.index()?;
+            if index.has_conflicts() && !self.resolve_conflicts(repo, &mut index)? {
+                let paths = index
+                    .conflicts()?
+                    .filter_map(|c| c.ok())
+                    .filter_map(|c| c.our.or(c.their))
+                    .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+                    .collect();
+                rebase.abort().context("Failed to abort rebase")?;
+                return Err(GitConflict {
+                    branch: branch_name.to_string(),
+                    paths,
+                }
+                .into());
+            }
+
+            match rebase.commit(None, &signature, None) {
+                Ok(_) => {}
+                // The change already exists upstream; nothing to replay
+                Err(e) if e.code() == git2::ErrorCode::Applied => {}
+                Err(e) => return Err(e).context("Failed to commit rebased change"),
+            }
+        }
+
+        rebase
+            .finish(Some(&signature))
+            .context("Failed to finish rebase")?;
+        Ok(())
+    }
+
+    /// Try to resolve the index conflicts of a rebase step with the resolver
+    ///
+    /// # Returns
+    /// `true` if every conflict was resolved and staged, `false` if the
+    /// conflicts must be left to the user
+    fn resolve_conflicts(&self, repo: &Repository, index: &mut git2::Index) -> Result<bool> {
+        let Some((ref file_path, ref resolver)) = self.resolver else {
+            return Ok(false);
         };
+        let Ok(relative_path) = Self::relative_path(repo, file_path) else {
+            return Ok(false);
+        };
+        let base = relative_path.parent().unwrap_or(Path::new(""));
+        let dir = file_path.parent().unwrap_or(Path::new("")).to_path_buf();
 
-        // Get the file path relative to the repository
-        // Canonicalize both paths to handle symlinks and platform differences
+        let read_blob = |entry: &Option<git2::IndexEntry>| -> Result<Option<String>> {
+            entry
+                .as_ref()
+                .map(|entry| {
+                    Ok(String::from_utf8(
+                        repo.find_blob(entry.id)?.content().to_vec(),
+                    )?)
+                })
+                .transpose()
+        };
+        // During a rebase "ours" is the upstream side and "theirs" is the
+        // local commit being replayed
+        let mut conflicted = Vec::new();
+        let mut local = ConflictVersion {
+            dir: dir.clone(),
+            files: HashMap::new(),
+        };
+        let mut remote = ConflictVersion {
+            dir,
+            files: HashMap::new(),
+        };
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let Some(entry) = conflict
+                .our
+                .as_ref()
+                .or(conflict.their.as_ref())
+                .or(conflict.ancestor.as_ref())
+            else {
+                continue;
+            };
+            let path = PathBuf::from(&*String::from_utf8_lossy(&entry.path));
+            let Ok(name) = path.strip_prefix(base) else {
+                return Ok(false);
+            };
+            let resolvable = name == relative_path.strip_prefix(base).unwrap_or(&relative_path)
+                || self
+                    .companion_dirs
+                    .iter()
+                    .any(|dir| name.starts_with(dir) && name != Path::new(dir));
+            if !resolvable {
+                return Ok(false);
+            }
+            let (Ok(theirs), Ok(upstream)) = (read_blob(&conflict.their), read_blob(&conflict.our))
+            else {
+                return Ok(false);
+            };
+            local.files.insert(name.to_path_buf(), theirs);
+            remote.files.insert(name.to_path_buf(), upstream);
+            conflicted.push(path);
+        }
+        if conflicted.is_empty() || resolver(&local, &remote).is_err() {
+            return Ok(false);
+        }
+
+        let workdir = repo
+            .workdir()
+            .context("Repository has no working directory")?;
+        for path in &conflicted {
+            if workdir.join(path).exists() {
+                index.add_path(path)?;
+            } else {
+                index.remove_path(path)?;
+            }
+        }
+        index.add_path(&relative_path)?;
+        for name in &self.companion_dirs {
+            let dir = relative_path.with_file_name(name);
+            index.add_all([&dir], git2::IndexAddOption::DEFAULT, None)?;
+            index.update_all([&dir], None)?;
+        }
+        index.write()?;
+        Ok(true)
+    }
+
+    /// Get the path of `file_path` relative to the repository working directory
+    ///
+    /// Canonicalizes both paths to handle symlinks and platform differences.
+    fn relative_path(repo: &Repository, file_path: &Path) -> Result<PathBuf> {
         let repo_workdir = repo
             .workdir()
             .context("Repository has no working directory")?;
@@ -142,10 +548,36 @@ impl GitOps {
         let relative_path = canonical_file
             .strip_prefix(&canonical_workdir)
             .context("File is not in repository")?;
+        Ok(relative_path.to_path_buf())
+    }
+
+    /// Commit changes to the repository
+    ///
+    /// Stages the specified file and creates a commit with the given message.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to commit
+    /// * `message` - Commit message
+    ///
+    /// # Returns
+    /// Result indicating success or an error
+    pub fn commit(&self, file_path: &Path, message: &str) -> Result<()> {
+        let repo = match &self.repo_path {
+            Some(r) => r.lock().unwrap(),
+            None => return Ok(()), // Not a git repo, skip
+        };
+
+        // Get the file path relative to the repository
+        let relative_path = Self::relative_path(&repo, file_path)?;
 
         // Add the file to the index
         let mut index = repo.index()?;
-        index.add_path(relative_path)?;
+        index.add_path(&relative_path)?;
+        for name in &self.companion_dirs {
+            let dir = relative_path.with_file_name(name);
+            index.add_all([&dir], git2::IndexAddOption::DEFAULT, None)?;
+            index.update_all([&dir], None)?;
+        }
         index.write()?;
 
         // Check if there are changes to commit
@@ -162,7 +594,7 @@ impl GitOps {
         };
 
         // Create signature
-        let signature = Self::get_signature(&repo)?;
+        let signature = self.get_signature(&repo)?;
 
         // Create the commit
         let parents: Vec<_> = parent_commit.iter().collect();
@@ -181,14 +613,27 @@ impl GitOps {
 
     /// Push changes to remote repository
     ///
-    /// Pushes the current branch to the origin remote.
+    /// Pushes the current branch to the origin remote. A push the remote
+    /// rejects (e.g., because another machine pushed first) is an error.
     ///
     /// # Returns
     /// Result indicating success or an error
     pub fn push(&self) -> Result<()> {
+        match self.try_push()? {
+            PushOutcome::Accepted => Ok(()),
+            PushOutcome::Rejected { branch, reason } => Err(anyhow::anyhow!(
+                "Push to origin/{} was rejected: {}",
+                branch,
+                reason
+            )),
+        }
+    }
+
+    /// Push the current branch, reporting remote rejections separately from failures
+    pub(crate) fn try_push(&self) -> Result<PushOutcome> {
         let repo = match &self.repo_path {
             Some(r) => r.lock().unwrap(),
-            None => return Ok(()), // Not a git repo, skip
+            None => return Ok(PushOutcome::Accepted), // Not a git repo, skip
         };
 
         // Get the current branch
@@ -199,38 +644,292 @@ impl GitOps {
             .to_string();
 
         // Get remote
-        let mut remote = repo
-            .find_remote("origin")
-            .context("Failed to find remote 'origin'")?;
+        let Some(mut remote) = self.find_origin(&repo)? else {
+            return Ok(PushOutcome::Accepted); // Local-only history, nothing to push
+        };
 
-        // Push to remote
+        // Push to remote. Servers report rejected updates through the callback;
+        // local transports fail the push with NotFastForward instead.
         let refspec = format!("refs/heads/{}", branch_name);
-        remote.push(&[&refspec], None)?;
+        let mut rejection: Option<String> = None;
+        let result = {
+            let mut callbacks = git2::RemoteCallbacks::new();
+            callbacks.push_update_reference(|_refname, status| {
+                rejection = status.map(str::to_string);
+                Ok(())
+            });
+            let mut options = git2::PushOptions::new();
+            options.remote_callbacks(callbacks);
+            remote.push(&[&refspec], Some(&mut options))
+        };
 
+        match result {
+            Ok(()) => match rejection {
+                None => Ok(PushOutcome::Accepted),
+                Some(reason) => Ok(PushOutcome::Rejected {
+                    branch: branch_name,
+                    reason,
+                }),
+            },
+            Err(e) if e.code() == git2::ErrorCode::NotFastForward => Ok(PushOutcome::Rejected {
+                branch: branch_name,
+                reason: e.message().to_string(),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Tag the current HEAD and push the tag to origin
+    ///
+    /// An existing tag with the same name is moved to HEAD, so repeating a
+    /// milestone (e.g., a second review in the same week) keeps the newest state.
+    /// Without an `origin` remote the tag is only created locally.
+    ///
+    /// # Arguments
+    /// * `name` - Tag name (e.g., "weekly-2025-W23")
+    /// * `message` - Annotation message
+    ///
+    /// # Returns
+    /// Result indicating success or an error
+    pub fn tag_head(&self, name: &str, message: &str) -> Result<()> {
+        let repo = match &self.repo_path {
+            Some(r) => r.lock().unwrap(),
+            None => return Ok(()), // Not a git repo, skip
+        };
+
+        let head = repo
+            .head()
+            .context("Failed to get HEAD")?
+            .peel(git2::ObjectType::Commit)?;
+        let signature = self.get_signature(&repo)?;
+        repo.tag(name, &head, &signature, message, true)
+            .with_context(|| format!("Failed to create tag '{}'", name))?;
+
+        let Some(mut remote) = repo.find_remote("origin").ok() else {
+            return Ok(());
+        };
+        let refspec = format!("+refs/tags/{0}:refs/tags/{0}", name);
+        remote
+            .push(&[&refspec], None)
+            .with_context(|| format!("Failed to push tag '{}' to origin", name))?;
         Ok(())
     }
 
+    /// Count local commits that have not been pushed to origin yet
+    ///
+    /// Compares HEAD with the remote-tracking branch as of the last fetch or
+    /// push; no network access is performed.
+    ///
+    /// # Returns
+    /// The number of unpushed commits, or `None` when there is no repository
+    /// or the branch has never been fetched from or pushed to origin
+    pub fn unpushed_commits(&self) -> Result<Option<usize>> {
+        let repo = match &self.repo_path {
+            Some(r) => r.lock().unwrap(),
+            None => return Ok(None),
+        };
+
+        let head = repo.head().context("Failed to get HEAD")?;
+        let branch_name = head.shorthand().context("Failed to get branch name")?;
+        let Ok(tracking) = repo.find_reference(&format!("refs/remotes/origin/{}", branch_name))
+        else {
+            return Ok(None);
+        };
+        let (Some(local), Some(remote)) = (head.target(), tracking.target()) else {
+            return Ok(None);
+        };
+        let (ahead, _behind) = repo.graph_ahead_behind(local, remote)?;
+        Ok(Some(ahead))
+    }
+
+    /// Inspect the working tree and compare the branch with its remote
+    ///
+    /// Works offline: ahead/behind counts use the remote-tracking branch as of
+    /// the last fetch, whose time is reported as well.
+    ///
+    /// # Returns
+    /// The status, or `None` when the file is not in a Git repository
+    pub fn status(&self) -> Result<Option<GitStatus>> {
+        let repo = match &self.repo_path {
+            Some(r) => r.lock().unwrap(),
+            None => return Ok(None),
+        };
+
+        let head = repo.head().context("Failed to get HEAD")?;
+        let branch_name = head
+            .shorthand()
+            .context("Failed to get branch name")?
+            .to_string();
+
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true).include_ignored(false);
+        let changed_files = repo
+            .statuses(Some(&mut options))?
+            .iter()
+            .map(|entry| String::from_utf8_lossy(entry.path_bytes()).into_owned())
+            .collect();
+
+        let tracking = repo
+            .find_reference(&format!("refs/remotes/origin/{}", branch_name))
+            .ok()
+            .and_then(|r| r.target());
+        let (ahead, behind) = match (head.target(), tracking) {
+            (Some(local), Some(remote)) => {
+                let (ahead, behind) = repo.graph_ahead_behind(local, remote)?;
+                (Some(ahead), Some(behind))
+            }
+            _ => (None, None),
+        };
+
+        let last_fetch = std::fs::metadata(repo.path().join("FETCH_HEAD"))
+            .and_then(|m| m.modified())
+            .ok()
+            .map(chrono::DateTime::<chrono::Local>::from);
+
+        Ok(Some(GitStatus {
+            branch: branch_name,
+            changed_files,
+            ahead,
+            behind,
+            last_fetch,
+        }))
+    }
+
+    /// Synchronize with the remote on demand: fetch, rebase, and push
+    ///
+    /// Unlike [`GitOps::sync`] nothing is committed; this brings in commits
+    /// made on other machines and pushes any local commits that have not
+    /// reached the remote yet (e.g., because a save happened offline).
+    ///
+    /// # Returns
+    /// A [`SyncReport`] listing the commits received and sent, or an error
+    /// ([`GitConflict`] when the rebase could not be completed)
+    pub fn sync_remote(&self) -> Result<SyncReport> {
+        self.sync_remote_cancellable(&Cancellation::default())
+    }
+
+    /// [`GitOps::sync_remote`] that stops once `cancellation` is cancelled
+    ///
+    /// Checked before each fetch and before each push. A sync stopped before
+    /// the push leaves the rebased commits for the next push.
+    ///
+    /// # Returns
+    /// [`Cancelled`](crate::progress::Cancelled) when stopped, otherwise as
+    /// [`GitOps::sync_remote`]
+    pub fn sync_remote_cancellable(&self, cancellation: &Cancellation) -> Result<SyncReport> {
+        let mut report = SyncReport::default();
+        let Some(repo) = &self.repo_path else {
+            return Ok(report);
+        };
+        if repo.lock().unwrap().find_remote("origin").is_err() {
+            anyhow::bail!(
+                "The data repository has no remote 'origin'. Add one with `git remote add origin <url>` (or start with --git-remote <url>) to sync."
+            );
+        }
+
+        let mut attempt = 1;
+        loop {
+            cancellation.check()?;
+            let (_, head_before, tracking_before) = self.branch_tips()?;
+            self.integrate_remote()?;
+            let (branch, head, tracking) = self.branch_tips()?;
+            report.branch = branch;
+            report
+                .received
+                .extend(self.commit_summaries(tracking, &[head_before, tracking_before])?);
+            let sent = self.commit_summaries(head, &[tracking])?;
+
+            cancellation.check()?;
+            match self.try_push().context("Failed to push changes")? {
+                PushOutcome::Accepted => {
+                    report.sent = sent;
+                    return Ok(report);
+                }
+                PushOutcome::Rejected { .. } if attempt < MAX_SYNC_ATTEMPTS => attempt += 1,
+                PushOutcome::Rejected { branch, reason } => {
+                    return Err(anyhow::anyhow!(
+                        "Push to origin/{} was rejected {} times (the remote keeps changing): {}",
+                        branch,
+                        MAX_SYNC_ATTEMPTS,
+                        reason
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Current branch name with the commits of HEAD and `origin/<branch>`
+    fn branch_tips(&self) -> Result<(String, Option<git2::Oid>, Option<git2::Oid>)> {
+        let repo = match &self.repo_path {
+            Some(r) => r.lock().unwrap(),
+            None => return Ok((String::new(), None, None)),
+        };
+        let head = repo.head().context("Failed to get HEAD")?;
+        let branch_name = head
+            .shorthand()
+            .context("Failed to get branch name")?
+            .to_string();
+        let tracking = repo
+            .find_reference(&format!("refs/remotes/origin/{}", branch_name))
+            .ok()
+            .and_then(|r| r.target());
+        Ok((branch_name, head.target(), tracking))
+    }
+
+    /// Summaries of commits reachable from `tip` but not from any of `hide`, oldest first
+    fn commit_summaries(
+        &self,
+        tip: Option<git2::Oid>,
+        hide: &[Option<git2::Oid>],
+    ) -> Result<Vec<String>> {
+        let (Some(repo), Some(tip)) = (&self.repo_path, tip) else {
+            return Ok(Vec::new());
+        };
+        let repo = repo.lock().unwrap();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        revwalk.push(tip)?;
+        for oid in hide.iter().flatten() {
+            revwalk.hide(*oid)?;
+        }
+        revwalk
+            .map(|oid| {
+                let commit = repo.find_commit(oid?)?;
+                Ok(
+                    String::from_utf8_lossy(commit.summary_bytes().unwrap_or_default())
+                        .into_owned(),
+                )
+            })
+            .collect()
+    }
+
     /// Get or create a Git signature for commits
     ///
-    /// Uses the configured user.name and user.email from Git config,
-    /// or falls back to default values if not configured.
+    /// Uses the author configured with [`GitOps::with_author`] first, then
+    /// user.name and user.email from Git config, and finally default values.
     ///
     /// # Arguments
     /// * `repo` - The Git repository
     ///
     /// # Returns
     /// Result containing a Signature or an error
-    fn get_signature(repo: &Repository) -> Result<Signature<'_>> {
+    fn get_signature(&self, repo: &Repository) -> Result<Signature<'static>> {
         // Try to use the configured user name and email
         let config = repo.config()?;
 
-        let name = config
-            .get_string("user.name")
-            .unwrap_or_else(|_| "GTD MCP Server".to_string());
+        let name = match &self.author_name {
+            Some(name) => name.clone(),
+            None => config
+                .get_string("user.name")
+                .unwrap_or_else(|_| "GTD MCP Server".to_string()),
+        };
 
-        let email = config
-            .get_string("user.email")
-            .unwrap_or_else(|_| "gtd-mcp@localhost".to_string());
+        let email = match &self.author_email {
+            Some(email) => email.clone(),
+            None => config
+                .get_string("user.email")
+                .unwrap_or_else(|_| "gtd-mcp@localhost".to_string()),
+        };
 
         // Use Signature::now() but with a fallback to a fixed time if it fails
         match Signature::now(&name, &email) {
@@ -244,10 +943,12 @@ impl GitOps {
         }
     }
 
-    /// Perform full Git synchronization: pull, commit, and push
+    /// Perform full Git synchronization: commit, pull (rebase), and push
     ///
-    /// This is the main sync operation that ensures the file is up to date,
-    /// commits changes, and pushes them to the remote repository.
+    /// Commits the local change first so it can be rebased, then integrates any
+    /// commits made on other machines (fast-forward or rebase) before pushing.
+    /// If the remote moves again between the pull and the push, the cycle is
+    /// retried a few times. Conflicts are returned as [`GitConflict`] errors.
     ///
     /// # Arguments
     /// * `file_path` - Path to the file to commit
@@ -256,20 +957,121 @@ impl GitOps {
     /// # Returns
     /// Result indicating success or an error
     pub fn sync(&self, file_path: &Path, commit_message: &str) -> Result<()> {
+        self.sync_with_outcome(file_path, commit_message)
+            .map(|_| ())
+    }
+
+    /// Same as [`GitOps::sync`], reporting whether remote commits were integrated
+    ///
+    /// When this returns `true` the file on disk may contain changes made on
+    /// another machine, so callers holding the data in memory should reload it.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to commit
+    /// * `commit_message` - Commit message to use
+    ///
+    /// # Returns
+    /// Result containing whether remote changes were integrated, or an error
+    pub fn sync_with_outcome(&self, file_path: &Path, commit_message: &str) -> Result<bool> {
         if !self.is_git_managed() {
-            return Ok(());
+            return Ok(false);
         }
 
-        // Pull first to get latest changes
-        self.pull().context("Failed to pull changes")?;
-
         // Commit the changes
         self.commit(file_path, commit_message)
             .context("Failed to commit changes")?;
 
-        // Push to remote
-        self.push().context("Failed to push changes")?;
+        let mut integrated = false;
+        let mut attempt = 1;
+        loop {
+            // Bring in commits made elsewhere; conflicts are returned unwrapped
+            // so their guidance reaches the MCP client as the error message
+            integrated |= self.integrate_remote()?;
 
-        Ok(())
+            match self.try_push().context("Failed to push changes")? {
+                PushOutcome::Accepted => return Ok(integrated),
+                PushOutcome::Rejected { .. } if attempt < MAX_SYNC_ATTEMPTS => attempt += 1,
+                PushOutcome::Rejected { branch, reason } => {
+                    return Err(anyhow::anyhow!(
+                        "Push to origin/{} was rejected {} times (the remote keeps changing): {}",
+                        branch,
+                        MAX_SYNC_ATTEMPTS,
+                        reason
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Snapshot of the data repository's state relative to its remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Current branch
+    pub branch: String,
+    /// Repository-relative paths with uncommitted changes (including untracked files)
+    pub changed_files: Vec<String>,
+    /// Local commits not on `origin/<branch>` (None if the branch was never fetched or pushed)
+    pub ahead: Option<usize>,
+    /// Commits on `origin/<branch>` not yet integrated locally, as of the last fetch
+    pub behind: Option<usize>,
+    /// When the remote was last fetched (every sync starts with a fetch)
+    pub last_fetch: Option<chrono::DateTime<chrono::Local>>,
+}
+
+impl GitStatus {
+    /// Whether the working tree has uncommitted changes
+    pub fn is_dirty(&self) -> bool {
+        !self.changed_files.is_empty()
+    }
+}
+
+/// What an explicit sync exchanged with the remote
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Branch that was synchronized
+    pub branch: String,
+    /// Summaries of commits brought in from origin, oldest first
+    pub received: Vec<String>,
+    /// Summaries of local commits pushed to origin, oldest first
+    pub sent: Vec<String>,
+}
+
+/// Result of a push attempt that reached the remote
+pub(crate) enum PushOutcome {
+    Accepted,
+    Rejected { branch: String, reason: String },
+}
+
+/// Number of pull/push cycles before giving up on a remote that keeps moving
+const MAX_SYNC_ATTEMPTS: usize = 3;
+
+/// A Git sync that could not be completed because of conflicting changes
+///
+/// Returned when local commits cannot be rebased onto the remote branch
+/// without conflicts that the configured [`ConflictResolver`] cannot merge.
+/// The rebase is aborted, so the local branch and working
+/// tree are left exactly as they were before the sync.
+#[derive(Debug, Clone)]
+pub struct GitConflict {
+    /// Branch being synchronized
+    pub branch: String,
+    /// Repository-relative paths that conflicted
+    pub paths: Vec<String>,
+}
+
+impl fmt::Display for GitConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Sync conflict: {} changed both locally and on origin/{}. \
+             Your change is saved in a local commit but was not pushed. \
+             To resolve, run `git pull --rebase` in the data repository, fix the conflict, \
+             and `git push`; then restart the server to reload the data.",
+            self.paths.join(", "),
+            self.branch
+        )
     }
 }
+
+impl std::error::Error for GitConflict {}
@@ -0,0 +1,314 @@
+//! GitHub issue import
+//!
+//! The `import_github` tool turns the open issues of a repository into items
+//! linked to them (the issue URL is stored in `link`):
+//!
+//! - Issues assigned to you become next actions, or waiting_for items when a
+//!   label says they are blocked or waiting
+//! - Issues you follow but someone else handles (`created`, `mentioned`) become
+//!   waiting_for items
+//! - Pull requests are skipped
+//!
+//! Items are matched by their link, so importing again only adds new issues
+//! and picks up renamed ones; how an item was organized since (status,
+//! project, context, notes) is left alone.
+//!
+//! Requests use a token from `GTD_MCP_GITHUB_TOKEN` or `GITHUB_TOKEN`, sent
+//! through [`crate::http`].
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::http::{self, Request};
+use crate::import::ImportSummary;
+use crate::progress::Cancellation;
+use crate::slug;
+use anyhow::{Context as _, Result, anyhow, bail};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Environment variables holding the API token, in order of preference
+pub const TOKEN_VARS: [&str; 2] = ["GTD_MCP_GITHUB_TOKEN", "GITHUB_TOKEN"];
+
+/// Environment variable overriding the API URL (for GitHub Enterprise Server)
+pub const API_URL_VAR: &str = "GTD_MCP_GITHUB_API_URL";
+
+/// API URL of github.com
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Issues fetched per request (the API maximum)
+const PER_PAGE: usize = 100;
+
+/// Pages fetched at most, bounding an import to 1000 issues
+const MAX_PAGES: usize = 10;
+
+/// Words in a label that mark an assigned issue as waiting for someone else
+const WAITING_LABELS: [&str; 3] = ["blocked", "waiting", "on hold"];
+
+/// Which issues of the repository to import
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IssueFilter {
+    /// Issues assigned to you
+    #[default]
+    Assigned,
+    /// Issues you opened
+    Created,
+    /// Issues that mention you
+    Mentioned,
+    /// Every open issue
+    All,
+}
+
+impl FromStr for IssueFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "assigned" => Ok(IssueFilter::Assigned),
+            "created" => Ok(IssueFilter::Created),
+            "mentioned" => Ok(IssueFilter::Mentioned),
+            "all" => Ok(IssueFilter::All),
+            _ => Err(format!(
+                "Invalid filter '{}'. Valid options are: assigned, created, mentioned, all",
+                s
+            )),
+        }
+    }
+}
+
+/// A GitHub account
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+/// A label of an issue
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+/// The fields of an issue used by the import
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub html_url: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub assignees: Vec<User>,
+    /// Set when the "issue" is a pull request
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+impl Issue {
+    /// Status of the item created for this issue
+    ///
+    /// # Arguments
+    /// * `login` - Login of the token's user
+    pub fn status(&self, login: &str) -> NotaStatus {
+        let assigned = self
+            .assignees
+            .iter()
+            .any(|user| user.login.eq_ignore_ascii_case(login));
+        let waiting = self.labels.iter().any(|label| {
+            let name = label.name.to_lowercase();
+            WAITING_LABELS.iter().any(|word| name.contains(word))
+        });
+        if assigned && !waiting {
+            NotaStatus::next_action
+        } else {
+            NotaStatus::waiting_for
+        }
+    }
+}
+
+/// Parse one page of the issues API, leaving out pull requests
+pub fn parse_issues(json: &str) -> Result<Vec<Issue>> {
+    let issues: Vec<Issue> =
+        serde_json::from_str(json).context("Unexpected response from the GitHub issues API")?;
+    Ok(issues
+        .into_iter()
+        .filter(|issue| issue.pull_request.is_none())
+        .collect())
+}
+
+/// Check that `repo` is `owner/name`
+fn check_repo(repo: &str) -> Result<()> {
+    let valid = repo.split_once('/').is_some_and(|(owner, name)| {
+        !owner.is_empty()
+            && !name.is_empty()
+            && repo
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+            && !name.contains('/')
+    });
+    if !valid {
+        bail!("Repository must be given as owner/name (e.g. \"rust-lang/rust\")");
+    }
+    Ok(())
+}
+
+/// Add items for new issues and rename the items of renamed ones
+///
+/// # Arguments
+/// * `data` - The data to import into
+/// * `repo` - Repository as `owner/name`, whose name prefixes new IDs
+/// * `issues` - Open issues (see [`parse_issues`])
+/// * `login` - Login of the token's user
+/// * `today` - Date for new and renamed items
+pub fn import(
+    data: &mut GtdData,
+    repo: &str,
+    issues: &[Issue],
+    login: &str,
+    today: NaiveDate,
+) -> ImportSummary {
+    let name = repo.rsplit('/').next().unwrap_or(repo);
+    let mut summary = ImportSummary::default();
+    for issue in issues {
+        let existing = data
+            .iter()
+            .find(|nota| nota.link.as_deref() == Some(issue.html_url.as_str()))
+            .cloned();
+        match existing {
+            Some(nota) if nota.title == issue.title => summary.unchanged += 1,
+            Some(mut nota) => {
+                nota.title = issue.title.clone();
+                nota.updated_at = today;
+                summary.updated.push(nota.id.clone());
+                data.update(&nota.id.clone(), nota);
+            }
+            None => {
+                let nota = Nota {
+                    id: slug::unique_id(data, &format!("{} {}", name, issue.number)),
+                    title: issue.title.clone(),
+                    status: issue.status(login),
+                    notes: issue
+                        .body
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|body| !body.is_empty())
+                        .map(|body| body.replace("\r\n", "\n")),
+                    link: Some(issue.html_url.clone()),
+                    created_at: today,
+                    updated_at: today,
+                    ..Default::default()
+                };
+                summary.added.push(nota.id.clone());
+                data.add(nota);
+            }
+        }
+    }
+    summary
+}
+
+/// The GitHub REST API, reached through curl
+pub struct GitHubClient {
+    api_url: String,
+    token: String,
+    cancellation: Cancellation,
+}
+
+impl GitHubClient {
+    /// Create a client
+    ///
+    /// # Arguments
+    /// * `api_url` - API root (see [`DEFAULT_API_URL`])
+    /// * `token` - Personal access token that can read the repository's issues
+    pub fn new(api_url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            cancellation: Cancellation::default(),
+        }
+    }
+
+    /// Stop fetching further pages once `cancellation` is cancelled
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Create a client from [`TOKEN_VARS`] and [`API_URL_VAR`]
+    pub fn from_env() -> Result<Self> {
+        let token = TOKEN_VARS
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|token| !token.is_empty()))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No GitHub token: set {} (or {}) in the server's environment to a token that can read the repository's issues",
+                    TOKEN_VARS[1],
+                    TOKEN_VARS[0]
+                )
+            })?;
+        let api_url = std::env::var(API_URL_VAR).unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        Ok(Self::new(api_url, token))
+    }
+
+    /// GET a path of the API and return the body of a 200 response
+    fn get(&self, path: &str) -> Result<String> {
+        let authorization = format!("Bearer {}", self.token);
+        let url = format!("{}{}", self.api_url, path);
+        let response = http::send(Request {
+            method: "GET",
+            url: &url,
+            credentials: None,
+            headers: &[
+                ("Accept", "application/vnd.github+json"),
+                ("Authorization", &authorization),
+                ("X-GitHub-Api-Version", "2022-11-28"),
+            ],
+            body: None,
+        })?;
+        match response.status {
+            200 => Ok(response.body),
+            401 => bail!("GitHub rejected the token (HTTP 401)"),
+            404 => bail!("{} was not found, or the token cannot read it", url),
+            status => bail!("GET {} failed with HTTP {}", url, status),
+        }
+    }
+
+    /// Login of the token's user
+    pub fn login(&self) -> Result<String> {
+        let user: User = serde_json::from_str(&self.get("/user")?)
+            .context("Unexpected response from the GitHub user API")?;
+        Ok(user.login)
+    }
+
+    /// Open issues of `repo` matching `filter`, without pull requests
+    ///
+    /// # Arguments
+    /// * `repo` - Repository as `owner/name`
+    /// * `filter` - Which issues to fetch
+    /// * `login` - Login of the token's user
+    pub fn issues(&self, repo: &str, filter: IssueFilter, login: &str) -> Result<Vec<Issue>> {
+        check_repo(repo)?;
+        let query = match filter {
+            IssueFilter::Assigned => format!("&assignee={}", login),
+            IssueFilter::Created => format!("&creator={}", login),
+            IssueFilter::Mentioned => format!("&mentioned={}", login),
+            IssueFilter::All => String::new(),
+        };
+        let mut issues = Vec::new();
+        for page in 1..=MAX_PAGES {
+            self.cancellation.check()?;
+            let body = self.get(&format!(
+                "/repos/{}/issues?state=open&per_page={}&page={}{}",
+                repo, PER_PAGE, page, query
+            ))?;
+            // Count before pull requests are dropped to tell the last page
+            let count = serde_json::from_str::<Vec<serde_json::Value>>(&body)
+                .map(|page| page.len())
+                .unwrap_or_default();
+            issues.extend(parse_issues(&body)?);
+            if count < PER_PAGE {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+}
@@ -0,0 +1,83 @@
+//! Goals overview (the 1-3 year horizon)
+//!
+//! A goal is a nota with status `goal`; its `due_date` is the target date.
+//! Projects contribute to a goal through their `goal` field, and the progress of
+//! a goal is the progress of those projects added together. Goals are listed by
+//! target date (goals without one last), each with its projects, followed by the
+//! projects that serve no goal yet.
+
+use crate::gtd::{GtdData, Nota};
+use chrono::NaiveDate;
+
+/// Render the overview of every goal
+///
+/// # Arguments
+/// * `data` - The data to render
+/// * `today` - Reference date for the days left until each target date
+pub fn render(data: &GtdData, today: NaiveDate) -> String {
+    let mut goals: Vec<&Nota> = data.iter().filter(|nota| nota.is_goal()).collect();
+    if goals.is_empty() {
+        return "No goals yet. Create one with inbox(status=\"goal\"), set its target date with update(due_date=...) and link projects with update(goal=...)".to_string();
+    }
+    goals.sort_by_key(|goal| goal.due_date.unwrap_or(NaiveDate::MAX));
+
+    let mut out = String::from("Goals:\n");
+    for goal in goals {
+        out.push_str(&format!(
+            "- [{}] {} ({})\n",
+            goal.id,
+            goal.title,
+            target(goal.due_date, today)
+        ));
+        let projects = data.goal_projects(&goal.id);
+        if projects.is_empty() {
+            out.push_str(&format!(
+                "  No projects yet. Link one with update(id=\"<project>\", goal=\"{}\")\n",
+                goal.id
+            ));
+            continue;
+        }
+        out.push_str(&format!(
+            "  Progress: {} across {} project{}\n",
+            data.goal_progress(&goal.id),
+            projects.len(),
+            if projects.len() == 1 { "" } else { "s" }
+        ));
+        for project in projects {
+            out.push_str(&format!(
+                "  - [{}] {} ({})\n",
+                project.id,
+                project.title,
+                data.project_progress(&project.id)
+            ));
+        }
+    }
+
+    let unlinked: Vec<&str> = data
+        .iter()
+        .filter(|nota| nota.is_project() && nota.goal.is_none())
+        .map(|nota| nota.id.as_str())
+        .collect();
+    if !unlinked.is_empty() {
+        out.push_str(&format!(
+            "\nProjects without a goal: {}\n",
+            unlinked.join(", ")
+        ));
+    }
+    out.trim_end().to_string()
+}
+
+/// Target date with the days left (or overdue) relative to `today`
+fn target(due_date: Option<NaiveDate>, today: NaiveDate) -> String {
+    let Some(date) = due_date else {
+        return "no target date".to_string();
+    };
+    let days = (date - today).num_days();
+    match days {
+        0 => format!("target: {}, today", date),
+        1 => format!("target: {}, 1 day left", date),
+        -1 => format!("target: {}, 1 day overdue", date),
+        d if d > 0 => format!("target: {}, {} days left", date, d),
+        d => format!("target: {}, {} days overdue", date, -d),
+    }
+}
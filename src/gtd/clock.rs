@@ -0,0 +1,82 @@
+//! Where "today" comes from
+//!
+//! Everything that depends on the date - recurrence, the tickler, item ages,
+//! stale flags, escalation - asks a [`Clock`] instead of the system time. The
+//! handler holds one ([`SystemClock`] unless configured otherwise), so tests
+//! and simulations run against a [`FixedClock`] they move forward themselves.
+
+use super::nota::local_now;
+use chrono::{Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use std::sync::Mutex;
+
+/// Source of the current date and time
+pub trait Clock: Send + Sync {
+    /// The current local date and time
+    fn now(&self) -> NaiveDateTime;
+
+    /// Today's local date
+    fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+}
+
+/// The system time, in the system timezone or at a fixed UTC offset
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock {
+    /// Offset used instead of the system timezone
+    offset: Option<FixedOffset>,
+}
+
+impl SystemClock {
+    /// The system time at `offset` instead of the system timezone
+    pub fn with_offset(offset: FixedOffset) -> Self {
+        Self {
+            offset: Some(offset),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        match self.offset {
+            Some(offset) => Utc::now().with_timezone(&offset).naive_local(),
+            None => local_now(),
+        }
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<NaiveDateTime>,
+}
+
+impl FixedClock {
+    /// A clock standing at `now`
+    pub fn new(now: NaiveDateTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// A clock standing at the start of `date`
+    pub fn on(date: NaiveDate) -> Self {
+        Self::new(date.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    /// Set the clock to `now`
+    pub fn set(&self, now: NaiveDateTime) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Move the clock forward (or back, for a negative duration)
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> NaiveDateTime {
+        *self.now.lock().unwrap()
+    }
+}
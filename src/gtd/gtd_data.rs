@@ -1,10 +1,45 @@
-use crate::gtd::nota::{Nota, NotaStatus, local_date_today};
-use std::collections::HashMap;
+use crate::gtd::nota::{Nota, NotaStatus};
+use crate::gtd::search::{self, SearchIndex};
+use crate::migration::{EXTERNAL_NOTES_FORMAT_VERSION, MigrationRecord};
+use chrono::NaiveDate;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// Done and total task counts of a project or goal, see [`GtdData::project_progress`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProjectProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl ProjectProgress {
+    /// Done tasks as a whole percentage, or `None` for a project without tasks
+    pub fn percent(&self) -> Option<usize> {
+        (self.total > 0).then(|| self.done * 100 / self.total)
+    }
+}
+
+impl std::fmt::Display for ProjectProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.percent() {
+            Some(percent) => write!(f, "{}/{} done ({}%)", self.done, self.total, percent),
+            None => write!(f, "no tasks yet"),
+        }
+    }
+}
 
+#[derive(Clone)]
 pub struct GtdData {
     /// Format version for the TOML file (current: 3)
     pub format_version: u32,
 
+    /// Minimum length of the notes stored in external Markdown files
+    ///
+    /// `Some` selects format version 4 (see [`crate::external_notes`]); `None`
+    /// keeps every note inline in the TOML file.
+    pub external_notes: Option<usize>,
+
     /// All GTD items stored as Nota objects in a Vec
     ///
     /// Vec is used as the primary storage for several reasons:
@@ -14,48 +49,129 @@ pub struct GtdData {
     /// 4. Simple ownership model - Vec owns all data directly
     pub(crate) notas: Vec<Nota>,
 
-    /// HashMap index for O(1) duplicate ID detection
+    /// Position of each nota in `notas` by ID: the one ID index
     ///
-    /// This map stores ID → Status for fast duplicate checking when adding new notas.
-    /// It does NOT contain references to the actual Nota objects - that would require
-    /// Arc<RefCell<Nota>> and add significant complexity without measurable benefit
-    /// for personal GTD usage scales (100-500 items).
-    ///
-    /// The map is kept in sync with the Vec during all mutating operations:
-    /// - add_nota: inserts into both Vec and HashMap
-    /// - remove_nota: removes from both Vec and HashMap
-    /// - move_status: updates status in both Vec and HashMap
+    /// Answers duplicate checks and lookups in O(1); a nota's status and other
+    /// fields are read from the Vec at that position, so there is no second
+    /// copy to keep in step. Stores indices rather than references so the Vec
+    /// stays the single owner. Positions after a removed nota shift down, so
+    /// removal re-indexes the tail; additions and in-place updates (including
+    /// renames) are O(1).
     ///
     /// This is NOT serialized to TOML - it's rebuilt from notas during deserialization.
-    pub(crate) nota_map: HashMap<String, NotaStatus>,
+    pub(crate) id_index: HashMap<String, usize>,
+
+    /// Reverse index: project ID → IDs of the notas linked to it
+    ///
+    /// Serves project-filtered listings and [`GtdData::is_referenced`] without
+    /// scanning every nota. Entries are removed when their set becomes empty.
+    /// Not serialized.
+    pub(crate) project_index: HashMap<String, HashSet<String>>,
+
+    /// Reverse index: context name → IDs of the notas tagged with it
+    ///
+    /// Same maintenance rules as `project_index`. Not serialized.
+    pub(crate) context_index: HashMap<String, HashSet<String>>,
+
+    /// Reverse index: goal ID → IDs of the projects contributing to it
+    ///
+    /// Same maintenance rules as `project_index`. Not serialized.
+    pub(crate) goal_index: HashMap<String, HashSet<String>>,
+
+    /// Token index over id, title and notes for keyword search
+    ///
+    /// Maintained together with the reverse indexes. Not serialized.
+    pub(crate) search_index: SearchIndex,
+
+    /// Whether IDs and context names match regardless of case
+    ///
+    /// Stored casing is preserved; only lookups and duplicate detection fold
+    /// case. Set from the storage configuration, not serialized.
+    pub(crate) case_insensitive_ids: bool,
+
+    /// Lowercased ID → stored ID, maintained only while `case_insensitive_ids` is set
+    pub(crate) folded_ids: HashMap<String, String>,
+
+    /// Hash of the serialized content last written to (or read from) disk
+    ///
+    /// Lets a save be skipped when a tool call left the state unchanged
+    /// (e.g., a batch where every item failed). `None` until the first save.
+    /// Not serialized.
+    pub(crate) saved_hash: Option<u64>,
 
     /// Counter for generating unique task IDs
     pub task_counter: u32,
 
     /// Counter for generating unique project IDs
     pub project_counter: u32,
+
+    /// Migration steps applied to the file, oldest first (`[[migration_history]]`)
+    pub migration_history: Vec<MigrationRecord>,
 }
 
 impl Default for GtdData {
     fn default() -> Self {
         Self {
             format_version: 3,
+            external_notes: None,
             notas: Vec::new(),
-            nota_map: HashMap::new(),
+            id_index: HashMap::new(),
+            project_index: HashMap::new(),
+            context_index: HashMap::new(),
+            goal_index: HashMap::new(),
+            search_index: SearchIndex::default(),
+            case_insensitive_ids: false,
+            folded_ids: HashMap::new(),
+            saved_hash: None,
             task_counter: 0,
             project_counter: 0,
+            migration_history: Vec::new(),
         }
     }
 }
 
 // Serialize/Deserialize implementations are in serde_impl.rs
 
+/// Hash serialized content for change detection
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
 impl GtdData {
     /// Create a new empty GtdData instance
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Format version written to the file: 4 when notes are stored externally
+    pub fn written_format_version(&self) -> u32 {
+        match self.external_notes {
+            Some(_) => EXTERNAL_NOTES_FORMAT_VERSION,
+            None => self.format_version,
+        }
+    }
+
+    /// Whether `content` differs from what was last saved
+    ///
+    /// # Arguments
+    /// * `content` - The serialized form of this data (see `Storage::serialize`)
+    ///
+    /// # Returns
+    /// `true` if the content must be written, `false` if a save would be a no-op
+    pub fn is_dirty(&self, content: &str) -> bool {
+        self.saved_hash != Some(content_hash(content))
+    }
+
+    /// Record `content` as the state now on disk
+    ///
+    /// # Arguments
+    /// * `content` - The serialized content that was written or read
+    pub fn mark_saved(&mut self, content: &str) {
+        self.saved_hash = Some(content_hash(content));
+    }
+
     /// Generate a new unique task ID
     pub fn generate_task_id(&mut self) -> String {
         self.task_counter += 1;
@@ -67,6 +183,80 @@ impl GtdData {
         self.notas.iter().filter(|n| n.is_task()).count()
     }
 
+    /// Enable or disable case-insensitive ID and context matching
+    ///
+    /// When enabled, "office" finds the context stored as "Office", adding an
+    /// ID that differs from an existing one only in case is a duplicate, and
+    /// project/context references are stored with the casing of their target.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether lookups fold case
+    pub fn set_case_insensitive_ids(&mut self, enabled: bool) {
+        self.case_insensitive_ids = enabled;
+        self.rebuild_indexes();
+    }
+
+    /// Whether IDs and context names match regardless of case
+    pub fn case_insensitive_ids(&self) -> bool {
+        self.case_insensitive_ids
+    }
+
+    /// Resolve an ID as typed to the ID it is stored under
+    ///
+    /// An exact match always wins; with case-insensitive matching enabled, an
+    /// ID differing only in case resolves to the stored one.
+    ///
+    /// # Arguments
+    /// * `id` - The ID to resolve
+    ///
+    /// # Returns
+    /// The stored ID, or `None` if no nota matches
+    pub fn resolve_id(&self, id: &str) -> Option<&str> {
+        if let Some((stored, _)) = self.id_index.get_key_value(id) {
+            return Some(stored);
+        }
+        if self.case_insensitive_ids {
+            return self.folded_ids.get(&id.to_lowercase()).map(String::as_str);
+        }
+        None
+    }
+
+    /// Check whether an ID is taken (honoring case-insensitive matching)
+    ///
+    /// # Arguments
+    /// * `id` - The ID to check
+    pub fn contains_id(&self, id: &str) -> bool {
+        self.resolve_id(id).is_some()
+    }
+
+    /// Status of a nota (honoring case-insensitive matching)
+    ///
+    /// # Arguments
+    /// * `id` - The nota ID
+    pub fn status_of(&self, id: &str) -> Option<&NotaStatus> {
+        self.find_nota_by_id(id).map(|nota| &nota.status)
+    }
+
+    /// Position of a nota in `notas` (honoring case-insensitive matching)
+    fn position(&self, id: &str) -> Option<usize> {
+        self.id_index.get(self.resolve_id(id)?).copied()
+    }
+
+    /// Rewrite a nota's project/context references to the stored casing
+    fn canonicalize_references(&self, nota: &mut Nota) {
+        if !self.case_insensitive_ids {
+            return;
+        }
+        for reference in [&mut nota.project, &mut nota.context] {
+            if let Some(target) = reference
+                && let Some(stored) = self.resolve_id(target)
+                && stored != target
+            {
+                *target = stored.to_string();
+            }
+        }
+    }
+
     /// Find a nota by its ID
     ///
     /// # Arguments
@@ -75,18 +265,21 @@ impl GtdData {
     /// # Returns
     /// An optional reference to the nota if found
     fn find_nota_by_id(&self, id: &str) -> Option<&Nota> {
-        self.notas.iter().find(|n| n.id == id)
+        self.position(id).map(|pos| &self.notas[pos])
     }
 
     /// Find a nota by its ID and return a mutable reference
     ///
+    /// Changing `id`, `project`, or `context` through the reference bypasses the
+    /// indexes; use [`GtdData::update`] for that.
+    ///
     /// # Arguments
     /// * `id` - The nota ID to search for
     ///
     /// # Returns
     /// An optional mutable reference to the nota if found
     fn find_nota_by_id_mut(&mut self, id: &str) -> Option<&mut Nota> {
-        self.notas.iter_mut().find(|n| n.id == id)
+        self.position(id).map(|pos| &mut self.notas[pos])
     }
 
     /// Find a task by its ID (for compatibility)
@@ -97,33 +290,38 @@ impl GtdData {
     /// # Returns
     /// An optional Nota reference if found and it's a task
     pub fn find_task_by_id(&self, id: &str) -> Option<&Nota> {
-        self.notas.iter().find(|n| n.id == id && n.is_task())
+        self.find_nota_by_id(id).filter(|n| n.is_task())
     }
 
     /// Find a task by its ID and return a mutable reference (for compatibility)
     ///
+    /// Changing `id`, `status`, `project`, `context`, `title` or `notes` through
+    /// the reference bypasses the indexes; call [`GtdData::rebuild_indexes`]
+    /// afterwards, or better, use [`GtdData::update`] instead.
+    ///
     /// # Arguments
     /// * `id` - The task ID to search for (e.g., "#1")
     ///
     /// # Returns
     /// An optional mutable Nota reference if found and it's a task
+    #[deprecated(note = "mutate through GtdData::update, which keeps the indexes in sync")]
     pub fn find_task_by_id_mut(&mut self, id: &str) -> Option<&mut Nota> {
-        self.notas.iter_mut().find(|n| n.id == id && n.is_task())
+        self.find_nota_by_id_mut(id).filter(|n| n.is_task())
     }
 
     /// Add a nota to the collection
     ///
     /// # Arguments
     /// * `nota` - The nota to add
-    pub fn add_nota(&mut self, nota: Nota) {
-        let id = nota.id.clone();
-        let status = nota.status.clone();
-
-        // Add to nota_map for duplicate checking
-        self.nota_map.insert(id, status);
+    pub fn add_nota(&mut self, mut nota: Nota) {
+        self.canonicalize_references(&mut nota);
+        // Index position and references
+        self.id_index.insert(nota.id.clone(), self.notas.len());
+        self.add_references(&nota);
 
         // Add to notas vector
         self.notas.push(nota);
+        self.debug_assert_indexes();
     }
 
     /// Remove a nota from the collection and return it
@@ -135,12 +333,171 @@ impl GtdData {
     /// The removed nota if found
     pub fn remove_nota(&mut self, id: &str) -> Option<Nota> {
         // Find and remove nota
-        if let Some(pos) = self.notas.iter().position(|n| n.id == id) {
-            let nota = self.notas.remove(pos);
-            self.nota_map.remove(id);
-            Some(nota)
-        } else {
-            None
+        let pos = self.position(id)?;
+        let nota = self.notas.remove(pos);
+        self.id_index.remove(&nota.id);
+        self.remove_references(&nota);
+
+        // Later notas shifted down by one
+        for (offset, shifted) in self.notas[pos..].iter().enumerate() {
+            self.id_index.insert(shifted.id.clone(), pos + offset);
+        }
+        self.debug_assert_indexes();
+        Some(nota)
+    }
+
+    /// Keep only the notas matching `keep`, preserving order
+    ///
+    /// # Arguments
+    /// * `keep` - Predicate returning `true` for notas to keep
+    ///
+    /// # Returns
+    /// The removed notas, in their original order
+    pub fn retain(&mut self, mut keep: impl FnMut(&Nota) -> bool) -> Vec<Nota> {
+        let (kept, removed) = std::mem::take(&mut self.notas)
+            .into_iter()
+            .partition(|n| keep(n));
+        self.notas = kept;
+        self.rebuild_indexes();
+        removed
+    }
+
+    /// Rebuild every lookup index from the notas
+    ///
+    /// [`GtdData::add`], [`GtdData::remove_nota`], [`GtdData::update`],
+    /// [`GtdData::move_status`] and [`GtdData::retain`] keep the indexes in sync
+    /// and are the supported ways to change data. Call this after changing notas
+    /// any other way (e.g., through the deprecated `find_task_by_id_mut`), before
+    /// the next query or mutation. Debug builds assert after every mutation that
+    /// the indexes match the notas.
+    pub fn rebuild_indexes(&mut self) {
+        self.id_index.clear();
+        self.project_index.clear();
+        self.context_index.clear();
+        self.goal_index.clear();
+        self.search_index.clear();
+        self.folded_ids.clear();
+        let notas = std::mem::take(&mut self.notas);
+        for (pos, nota) in notas.iter().enumerate() {
+            self.id_index.insert(nota.id.clone(), pos);
+            self.add_references(nota);
+        }
+        self.notas = notas;
+    }
+
+    /// Describe the first difference between the indexes and the notas, if any
+    ///
+    /// Checks the ID index and the project/context/goal
+    /// reverse indexes (the search index is derived the same way as the latter). Data
+    /// holding duplicate IDs, which callers of [`GtdData::add`] must prevent, is
+    /// not checked; [`GtdData::check_integrity`] reports those.
+    fn index_drift(&self) -> Option<String> {
+        let distinct: HashSet<&str> = self.notas.iter().map(|n| n.id.as_str()).collect();
+        if distinct.len() != self.notas.len() {
+            return None;
+        }
+        if self.id_index.len() != self.notas.len() {
+            return Some(format!(
+                "{} notas but {} positions",
+                self.notas.len(),
+                self.id_index.len()
+            ));
+        }
+        let mut references = 0;
+        for (pos, nota) in self.notas.iter().enumerate() {
+            if self.id_index.get(&nota.id) != Some(&pos) {
+                return Some(format!("position of '{}' is not {}", nota.id, pos));
+            }
+            for (index, key) in [
+                (&self.project_index, &nota.project),
+                (&self.context_index, &nota.context),
+                (&self.goal_index, &nota.goal),
+            ] {
+                let Some(key) = key else { continue };
+                references += 1;
+                if !index.get(key).is_some_and(|ids| ids.contains(&nota.id)) {
+                    return Some(format!("link of '{}' to '{}' is not indexed", nota.id, key));
+                }
+            }
+        }
+        let indexed: usize = self
+            .project_index
+            .values()
+            .chain(self.context_index.values())
+            .chain(self.goal_index.values())
+            .map(HashSet::len)
+            .sum();
+        if indexed != references {
+            return Some(format!(
+                "{} links indexed but the notas have {}",
+                indexed, references
+            ));
+        }
+        None
+    }
+
+    /// Panic in debug builds when the indexes no longer match the notas
+    ///
+    /// This happens when notas were changed outside the supported mutation
+    /// methods without a [`GtdData::rebuild_indexes`] call.
+    fn debug_assert_indexes(&self) {
+        if cfg!(debug_assertions)
+            && let Some(drift) = self.index_drift()
+        {
+            panic!(
+                "GtdData indexes are out of sync with the notas ({}); call rebuild_indexes() after changing notas directly",
+                drift
+            );
+        }
+    }
+
+    /// Record a nota in the project/context/goal reverse indexes and the search index
+    fn add_references(&mut self, nota: &Nota) {
+        self.search_index.insert(nota);
+        if self.case_insensitive_ids {
+            self.folded_ids
+                .entry(nota.id.to_lowercase())
+                .or_insert_with(|| nota.id.clone());
+        }
+        if let Some(project) = &nota.project {
+            self.project_index
+                .entry(project.clone())
+                .or_default()
+                .insert(nota.id.clone());
+        }
+        if let Some(context) = &nota.context {
+            self.context_index
+                .entry(context.clone())
+                .or_default()
+                .insert(nota.id.clone());
+        }
+        if let Some(goal) = &nota.goal {
+            self.goal_index
+                .entry(goal.clone())
+                .or_default()
+                .insert(nota.id.clone());
+        }
+    }
+
+    /// Remove a nota from the project/context/goal reverse indexes and the search index
+    fn remove_references(&mut self, nota: &Nota) {
+        self.search_index.remove(nota);
+        let folded = nota.id.to_lowercase();
+        if self.folded_ids.get(&folded) == Some(&nota.id) {
+            self.folded_ids.remove(&folded);
+        }
+        for (index, key) in [
+            (&mut self.project_index, &nota.project),
+            (&mut self.context_index, &nota.context),
+            (&mut self.goal_index, &nota.goal),
+        ] {
+            let Some(key) = key else { continue };
+            if let Some(ids) = index.get_mut(key) {
+                ids.remove(&nota.id);
+                if ids.is_empty() {
+                    index.remove(key);
+                }
+            }
         }
     }
 
@@ -151,18 +508,20 @@ impl GtdData {
     /// # Arguments
     /// * `id` - The nota ID to move
     /// * `new_status` - The target status
+    /// * `today` - The current date (recorded as `updated_at` and `status_since`)
     ///
     /// # Returns
     /// `Some(())` if the nota was found and moved, `None` otherwise
-    pub fn move_status(&mut self, id: &str, new_status: NotaStatus) -> Option<()> {
-        if let Some(nota) = self.find_nota_by_id_mut(id) {
-            nota.status = new_status.clone();
-            nota.updated_at = local_date_today();
-            self.nota_map.insert(id.to_string(), new_status);
-            Some(())
-        } else {
-            None
-        }
+    pub fn move_status(
+        &mut self,
+        id: &str,
+        new_status: NotaStatus,
+        today: NaiveDate,
+    ) -> Option<()> {
+        let nota = self.find_nota_by_id_mut(id)?;
+        nota.set_status(new_status, today);
+        nota.updated_at = today;
+        Some(())
     }
 
     /// Find a project by its ID (for compatibility)
@@ -173,9 +532,8 @@ impl GtdData {
     /// # Returns
     /// An optional reference to the nota if found and it's a project
     pub fn find_project_by_id(&self, id: &str) -> Option<&Nota> {
-        self.notas
-            .iter()
-            .find(|n| n.id == id && n.status == NotaStatus::project)
+        self.find_nota_by_id(id)
+            .filter(|n| n.status == NotaStatus::project)
     }
 
     /// Find a context by its name (for compatibility)
@@ -186,9 +544,40 @@ impl GtdData {
     /// # Returns
     /// An optional reference to the nota if found and it's a context
     pub fn find_context_by_name(&self, name: &str) -> Option<&Nota> {
-        self.notas
-            .iter()
-            .find(|n| n.id == name && n.status == NotaStatus::context)
+        self.find_nota_by_id(name)
+            .filter(|n| n.status == NotaStatus::context)
+    }
+
+    /// Find a goal by its ID
+    ///
+    /// # Arguments
+    /// * `id` - The goal ID to search for (e.g., "run-marathon")
+    ///
+    /// # Returns
+    /// An optional reference to the nota if found and it's a goal
+    pub fn find_goal_by_id(&self, id: &str) -> Option<&Nota> {
+        self.find_nota_by_id(id)
+            .filter(|n| n.status == NotaStatus::goal)
+    }
+
+    /// Context that applies to a nota: its own, or else its project's
+    ///
+    /// A task without a context inherits the context of its project at query
+    /// time, so tagging a project `@lab` puts all of its untagged tasks in
+    /// `@lab` without storing it on each of them.
+    ///
+    /// # Arguments
+    /// * `nota` - The nota to look up
+    pub fn effective_context<'a>(&'a self, nota: &'a Nota) -> Option<&'a str> {
+        if let Some(context) = &nota.context {
+            return Some(context);
+        }
+        if !nota.is_task() {
+            return None;
+        }
+        self.find_project_by_id(nota.project.as_deref()?)?
+            .context
+            .as_deref()
     }
 
     /// Validate that a nota's project reference exists (if specified)
@@ -225,6 +614,7 @@ impl GtdData {
                 nota.project = Some(new_id.to_string());
             }
         }
+        self.rebuild_indexes();
     }
 
     /// Add a nota (unified task/project/context)
@@ -250,45 +640,83 @@ impl GtdData {
 
     /// Update a nota by its ID
     ///
+    /// The nota is replaced in place, keeping its position in the file.
+    ///
     /// # Arguments
     /// * `id` - The nota ID to update
     /// * `nota` - The new nota data
     ///
     /// # Returns
     /// The old nota if found and replaced
-    pub fn update(&mut self, id: &str, nota: Nota) -> Option<Nota> {
-        if let Some(pos) = self.notas.iter().position(|n| n.id == id) {
-            let old_nota = self.notas.remove(pos);
-            self.notas.push(nota.clone());
-            self.nota_map.insert(nota.id.clone(), nota.status.clone());
-            Some(old_nota)
-        } else {
-            None
+    pub fn update(&mut self, id: &str, mut nota: Nota) -> Option<Nota> {
+        let pos = self.position(id)?;
+        let old = self.notas[pos].clone();
+        self.remove_references(&old);
+        self.canonicalize_references(&mut nota);
+        self.add_references(&nota);
+        if nota.id != old.id {
+            self.id_index.remove(&old.id);
+            self.id_index.insert(nota.id.clone(), pos);
         }
+        let replaced = std::mem::replace(&mut self.notas[pos], nota);
+        self.debug_assert_indexes();
+        Some(replaced)
+    }
+
+    /// Find the notas whose id, title or notes contain `keyword`
+    ///
+    /// The search index narrows the notas down to those owning matching
+    /// tokens, so only their fields are lowercased and scanned.
+    ///
+    /// # Arguments
+    /// * `keyword` - Substring to search for (case-insensitive)
+    ///
+    /// # Returns
+    /// IDs of the matching notas
+    pub fn search(&self, keyword: &str) -> HashSet<&str> {
+        let keyword = keyword.to_lowercase();
+        let matching = |nota: &&Nota| search::matches(nota, &keyword);
+        match self.search_index.candidates(&keyword) {
+            Some(candidates) => candidates
+                .into_iter()
+                .filter_map(|id| self.find_nota_by_id(id))
+                .filter(matching)
+                .map(|nota| nota.id.as_str())
+                .collect(),
+            None => self
+                .notas
+                .iter()
+                .filter(matching)
+                .map(|nota| nota.id.as_str())
+                .collect(),
+        }
+    }
+
+    /// Iterate over all notas in file order without cloning
+    pub fn iter(&self) -> impl Iterator<Item = &Nota> {
+        self.notas.iter()
     }
 
     /// List all notas with optional status filter
     ///
+    /// Returns owned copies; prefer [`GtdData::list_filtered`] (borrowed) when
+    /// the result is only read, e.g. to format a listing.
+    ///
     /// # Arguments
     /// * `status_filter` - Optional status to filter by
     ///
     /// # Returns
     /// Vector of Nota objects matching the filter
     pub fn list_all(&self, status_filter: Option<NotaStatus>) -> Vec<Nota> {
-        if let Some(status) = status_filter {
-            self.notas
-                .iter()
-                .filter(|n| n.status == status)
-                .cloned()
-                .collect()
-        } else {
-            self.notas.clone()
-        }
+        self.list_filtered(status_filter, None, None)
+            .into_iter()
+            .cloned()
+            .collect()
     }
 
     /// Check if a nota ID is referenced by other notas
     ///
-    /// Returns true if the ID is used in any nota's project or context fields.
+    /// Returns true if the ID is used in any nota's project, context or goal fields.
     ///
     /// # Arguments
     /// * `id` - The nota ID to check
@@ -296,15 +724,186 @@ impl GtdData {
     /// # Returns
     /// True if the ID is referenced by other notas
     pub fn is_referenced(&self, id: &str) -> bool {
-        self.notas
-            .iter()
-            .any(|nota| nota.project.as_deref() == Some(id) || nota.context.as_deref() == Some(id))
+        let id = self.resolve_id(id).unwrap_or(id);
+        self.project_index.contains_key(id)
+            || self.context_index.contains_key(id)
+            || self.goal_index.contains_key(id)
+    }
+
+    /// IDs of the notas whose project, context or goal is `id`, in file order
+    ///
+    /// # Arguments
+    /// * `id` - The project, context or goal ID
+    ///
+    /// # Returns
+    /// IDs of the referencing notas
+    pub fn referrers(&self, id: &str) -> Vec<String> {
+        let id = self.resolve_id(id).unwrap_or(id);
+        let mut positions: Vec<usize> =
+            [&self.project_index, &self.context_index, &self.goal_index]
+                .into_iter()
+                .filter_map(|index| index.get(id))
+                .flatten()
+                .filter_map(|referrer| self.id_index.get(referrer).copied())
+                .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+            .into_iter()
+            .map(|pos| self.notas[pos].id.clone())
+            .collect()
+    }
+
+    /// Completion of a project: its done tasks out of all tasks linked to it
+    ///
+    /// Trashed and reference items are not counted, and neither are nested
+    /// projects. Answered from the project reverse index.
+    ///
+    /// # Arguments
+    /// * `id` - The project ID
+    pub fn project_progress(&self, id: &str) -> ProjectProgress {
+        let id = self.resolve_id(id).unwrap_or(id);
+        let mut progress = ProjectProgress::default();
+        for nota in self
+            .project_index
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|referrer| self.id_index.get(referrer))
+            .map(|&pos| &self.notas[pos])
+        {
+            match nota.status {
+                NotaStatus::trash
+                | NotaStatus::reference
+                | NotaStatus::project
+                | NotaStatus::context
+                | NotaStatus::goal
+                | NotaStatus::template => {}
+                NotaStatus::done => {
+                    progress.done += 1;
+                    progress.total += 1;
+                }
+                _ => progress.total += 1,
+            }
+        }
+        progress
+    }
+
+    /// Completion of a goal: the progress of its projects added together
+    ///
+    /// Trashed projects are not counted. Answered from the goal and project
+    /// reverse indexes.
+    ///
+    /// # Arguments
+    /// * `id` - The goal ID
+    pub fn goal_progress(&self, id: &str) -> ProjectProgress {
+        let mut progress = ProjectProgress::default();
+        for project in self.goal_projects(id) {
+            let project = self.project_progress(&project.id);
+            progress.done += project.done;
+            progress.total += project.total;
+        }
+        progress
+    }
+
+    /// Projects contributing to a goal, in file order (trashed ones excluded)
+    ///
+    /// # Arguments
+    /// * `id` - The goal ID
+    pub fn goal_projects(&self, id: &str) -> Vec<&Nota> {
+        let id = self.resolve_id(id).unwrap_or(id);
+        let mut positions: Vec<usize> = self
+            .goal_index
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|referrer| self.id_index.get(referrer).copied())
+            .collect();
+        positions.sort_unstable();
+        positions
+            .into_iter()
+            .map(|pos| &self.notas[pos])
+            .filter(|nota| nota.is_project())
+            .collect()
+    }
+
+    /// List notas matching a status, project, and context filter
+    ///
+    /// Project and context filters are answered from the reverse indexes, so
+    /// only matching notas are visited. Notas are borrowed, not cloned, and
+    /// keep file order.
+    ///
+    /// # Arguments
+    /// * `status_filter` - Optional status to filter by
+    /// * `project` - Optional project ID the notas must belong to
+    /// * `context` - Optional context the notas must be tagged with (tasks
+    ///   without one match their project's, see [`Self::effective_context`])
+    ///
+    /// # Returns
+    /// References to the notas matching every given filter
+    pub fn list_filtered(
+        &self,
+        status_filter: Option<NotaStatus>,
+        project: Option<&str>,
+        context: Option<&str>,
+    ) -> Vec<&Nota> {
+        let project = project.map(|p| self.resolve_id(p).unwrap_or(p));
+        let context = context.map(|c| self.resolve_id(c).unwrap_or(c));
+        let empty = HashSet::new();
+        let by_project = project.map(|p| self.project_index.get(p).unwrap_or(&empty));
+        let by_context = context.map(|c| {
+            let tagged = self.context_index.get(c).unwrap_or(&empty);
+            // Untagged tasks of a project tagged with the context inherit it
+            let mut ids: HashSet<&String> = tagged.iter().collect();
+            for id in tagged {
+                if self.status_of(id) == Some(&NotaStatus::project)
+                    && let Some(tasks) = self.project_index.get(id)
+                {
+                    ids.extend(tasks);
+                }
+            }
+            ids
+        });
+
+        // Walk the smaller candidate set; the other filter is checked per nota
+        let candidates: Vec<&String> = match (by_project, by_context) {
+            (None, None) => {
+                return self
+                    .notas
+                    .iter()
+                    .filter(|n| status_filter.as_ref().is_none_or(|s| n.status == *s))
+                    .collect();
+            }
+            (Some(ids), None) => ids.iter().collect(),
+            (None, Some(ids)) => ids.into_iter().collect(),
+            (Some(p), Some(c)) => {
+                if p.len() <= c.len() {
+                    p.iter().collect()
+                } else {
+                    c.into_iter().collect()
+                }
+            }
+        };
+
+        let mut positions: Vec<usize> = candidates
+            .into_iter()
+            .filter_map(|id| self.id_index.get(id).copied())
+            .collect();
+        positions.sort_unstable();
+        positions
+            .into_iter()
+            .map(|pos| &self.notas[pos])
+            .filter(|n| status_filter.as_ref().is_none_or(|s| n.status == *s))
+            .filter(|n| project.is_none_or(|p| n.project.as_deref() == Some(p)))
+            .filter(|n| context.is_none_or(|c| self.effective_context(n) == Some(c)))
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::gtd::nota::local_date_today;
     use crate::migration::Task;
     use chrono::NaiveDate;
 
@@ -324,12 +923,26 @@ mod tests {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task1);
 
         // Verify task is in map
-        assert!(data.nota_map.contains_key("test-task"));
-        assert_eq!(data.nota_map.get("test-task"), Some(&NotaStatus::inbox));
+        assert!(data.id_index.contains_key("test-task"));
+        assert_eq!(data.status_of("test-task"), Some(&NotaStatus::inbox));
 
         // Try to add another task with same ID in a different status
         let task2 = Task {
@@ -342,6 +955,20 @@ mod tests {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
 
         // This would add a duplicate - the application layer (lib.rs) should check
@@ -350,10 +977,7 @@ mod tests {
         data.add_task(task2);
 
         // The task_map should now show the new status (last one wins)
-        assert_eq!(
-            data.nota_map.get("test-task"),
-            Some(&NotaStatus::next_action)
-        );
+        assert_eq!(data.status_of("test-task"), Some(&NotaStatus::next_action));
 
         // But there are actually TWO tasks with same ID (one in inbox, one in next_action)
         // This demonstrates why the application layer MUST check task_map before adding
@@ -375,18 +999,32 @@ mod tests {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
 
         // Verify task is in map
-        assert!(data.nota_map.contains_key("remove-test"));
+        assert!(data.id_index.contains_key("remove-test"));
 
         // Remove task
         let removed = data.remove_task("remove-test");
         assert!(removed.is_some());
 
         // Verify task is removed from map
-        assert!(!data.nota_map.contains_key("remove-test"));
+        assert!(!data.id_index.contains_key("remove-test"));
     }
 
     #[test]
@@ -403,18 +1041,32 @@ mod tests {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
 
         // Verify initial status
-        assert_eq!(data.nota_map.get("status-test"), Some(&NotaStatus::inbox));
+        assert_eq!(data.status_of("status-test"), Some(&NotaStatus::inbox));
 
         // Move to next_action
-        data.move_status("status-test", NotaStatus::next_action);
+        data.move_status("status-test", NotaStatus::next_action, local_date_today());
 
         // Verify status updated in map
         assert_eq!(
-            data.nota_map.get("status-test"),
+            data.status_of("status-test"),
             Some(&NotaStatus::next_action)
         );
     }
@@ -441,9 +1093,9 @@ mod tests {
         let data: GtdData = toml::from_str(toml_str).unwrap();
 
         // Verify both tasks are in task_map with correct statuses
-        assert_eq!(data.nota_map.len(), 2);
-        assert_eq!(data.nota_map.get("task-1"), Some(&NotaStatus::inbox));
-        assert_eq!(data.nota_map.get("task-2"), Some(&NotaStatus::next_action));
+        assert_eq!(data.id_index.len(), 2);
+        assert_eq!(data.status_of("task-1"), Some(&NotaStatus::inbox));
+        assert_eq!(data.status_of("task-2"), Some(&NotaStatus::next_action));
     }
 
     // ============================================================================
@@ -453,15 +1105,15 @@ mod tests {
     // These tests validate the design decision to use HashMap<String, NotaStatus>
     // for duplicate checking only, rather than Arc<RefCell<Nota>> for data access.
     //
-    // The current design trades O(n) lookup for simplicity and maintainability,
-    // which is appropriate for personal GTD usage (100-500 items).
+    // Lookups by ID go through id_index (ID → position in the Vec), which keeps
+    // the Vec as the single owner while making lookups O(1).
 
-    /// Test that nota_map correctly tracks all nota IDs and statuses
+    /// Test that the ID index tracks every nota and its status
     ///
     /// This validates that the HashMap is properly synchronized with the Vec
     /// during all operations (add, remove, status change).
     #[test]
-    fn test_nota_map_synchronization() {
+    fn test_id_index_synchronization() {
         let mut data = GtdData::new();
 
         // Add various nota types
@@ -505,30 +1157,30 @@ mod tests {
         });
 
         // Verify HashMap matches Vec
-        assert_eq!(data.nota_map.len(), data.notas.len());
-        assert_eq!(data.nota_map.len(), 3);
+        assert_eq!(data.id_index.len(), data.notas.len());
+        assert_eq!(data.id_index.len(), 3);
 
         // Verify all IDs are in map with correct status
-        assert_eq!(data.nota_map.get("task-1"), Some(&NotaStatus::inbox));
-        assert_eq!(data.nota_map.get("proj-1"), Some(&NotaStatus::project));
-        assert_eq!(data.nota_map.get("Office"), Some(&NotaStatus::context));
+        assert_eq!(data.status_of("task-1"), Some(&NotaStatus::inbox));
+        assert_eq!(data.status_of("proj-1"), Some(&NotaStatus::project));
+        assert_eq!(data.status_of("Office"), Some(&NotaStatus::context));
 
         // Move status and verify map is updated
-        data.move_status("task-1", NotaStatus::next_action);
-        assert_eq!(data.nota_map.get("task-1"), Some(&NotaStatus::next_action));
+        data.move_status("task-1", NotaStatus::next_action, local_date_today());
+        assert_eq!(data.status_of("task-1"), Some(&NotaStatus::next_action));
 
         // Remove nota and verify map is updated
         data.remove_nota("proj-1");
-        assert_eq!(data.nota_map.len(), 2);
-        assert!(!data.nota_map.contains_key("proj-1"));
+        assert_eq!(data.id_index.len(), 2);
+        assert!(!data.id_index.contains_key("proj-1"));
     }
 
     /// Test O(1) duplicate detection performance
     ///
     /// This validates that duplicate checking is fast (O(1)) even with many notas,
-    /// which is the primary purpose of nota_map.
+    /// which is the primary purpose of the ID index.
     #[test]
-    fn test_nota_map_duplicate_detection() {
+    fn test_id_index_duplicate_detection() {
         let mut data = GtdData::new();
 
         // Add 100 notas
@@ -547,15 +1199,15 @@ mod tests {
             });
         }
 
-        assert_eq!(data.nota_map.len(), 100);
+        assert_eq!(data.id_index.len(), 100);
 
         // Test duplicate detection is O(1) - doesn't scan the Vec
-        assert!(data.nota_map.contains_key("nota-50"));
-        assert!(data.nota_map.contains_key("nota-99"));
-        assert!(!data.nota_map.contains_key("nota-100"));
+        assert!(data.id_index.contains_key("nota-50"));
+        assert!(data.id_index.contains_key("nota-99"));
+        assert!(!data.id_index.contains_key("nota-100"));
 
         // Test that status is tracked correctly
-        assert_eq!(data.nota_map.get("nota-50"), Some(&NotaStatus::inbox));
+        assert_eq!(data.status_of("nota-50"), Some(&NotaStatus::inbox));
     }
 
     /// Test that Vec maintains order for Git-friendly TOML output
@@ -592,12 +1244,251 @@ mod tests {
         // This is a key design decision - Vec for ordered storage, HashMap for fast lookups
     }
 
-    /// Test that nota_map is correctly rebuilt from TOML deserialization
+    /// Test that id_index follows position shifts on remove and in-place update
+    #[test]
+    fn test_id_index_tracks_positions() {
+        let mut data = GtdData::new();
+        for id in ["a", "b", "c", "d"] {
+            data.add(Nota {
+                id: id.to_string(),
+                title: id.to_uppercase(),
+                ..Default::default()
+            });
+        }
+
+        data.remove_nota("b");
+        assert_eq!(data.id_index.get("c"), Some(&1));
+        assert_eq!(data.id_index.get("d"), Some(&2));
+        assert_eq!(data.find_by_id("d").unwrap().title, "D");
+
+        // update replaces in place and keeps the order
+        let mut c = data.find_by_id("c").unwrap();
+        c.title = "Changed".to_string();
+        data.update("c", c);
+        let ids: Vec<&str> = data.notas.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c", "d"]);
+        assert_eq!(data.find_by_id("c").unwrap().title, "Changed");
+
+        let removed = data.retain(|n| n.id != "a");
+        assert_eq!(removed.len(), 1);
+        assert_eq!(data.id_index.get("c"), Some(&0));
+        assert!(data.find_by_id("a").is_none());
+        assert!(!data.id_index.contains_key("a"));
+    }
+
+    /// Test that the ID index alone answers lookups after rename, delete and merge
+    #[test]
+    fn test_id_index_consistent_after_rename_delete_merge() {
+        let nota = |id: &str, status: NotaStatus| Nota {
+            id: id.to_string(),
+            title: id.to_string(),
+            status,
+            ..Default::default()
+        };
+        let mut data = GtdData::new();
+        data.add(nota("a", NotaStatus::inbox));
+        data.add(nota("b", NotaStatus::project));
+        data.add(nota("c", NotaStatus::next_action));
+
+        // Rename: the old ID is gone, the new one resolves to the same position
+        let mut renamed = nota("b2", NotaStatus::someday);
+        renamed.title = "Renamed".to_string();
+        data.update("b", renamed);
+        assert_eq!(data.index_drift(), None);
+        assert!(!data.contains_id("b"));
+        assert_eq!(data.id_index.get("b2"), Some(&1));
+        assert_eq!(data.status_of("b2"), Some(&NotaStatus::someday));
+
+        // Delete: later positions shift down
+        data.remove_nota("a");
+        assert_eq!(data.index_drift(), None);
+        assert_eq!(data.status_of("a"), None);
+        assert_eq!(data.id_index.get("c"), Some(&1));
+
+        // Merge: the merged data indexes the items of both sides
+        let mut other = GtdData::new();
+        other.add(nota("c", NotaStatus::done));
+        other.add(nota("d", NotaStatus::inbox));
+        let merged = data.merge(&other);
+        assert_eq!(merged.index_drift(), None);
+        assert_eq!(merged.id_index.len(), 3);
+        for (id, status) in [
+            ("b2", NotaStatus::someday),
+            ("c", NotaStatus::next_action),
+            ("d", NotaStatus::inbox),
+        ] {
+            assert_eq!(merged.status_of(id), Some(&status), "{}", id);
+        }
+    }
+
+    /// Test that reference counts follow add, update, and remove
+    #[test]
+    fn test_reference_counts_track_project_and_context() {
+        let mut data = GtdData::new();
+        for (id, project, context) in [
+            ("t1", Some("proj"), Some("@home")),
+            ("t2", Some("proj"), None),
+        ] {
+            data.add(Nota {
+                id: id.to_string(),
+                project: project.map(str::to_string),
+                context: context.map(str::to_string),
+                ..Default::default()
+            });
+        }
+        assert!(data.is_referenced("proj"));
+        assert!(data.is_referenced("@home"));
+
+        let mut t1 = data.find_by_id("t1").unwrap();
+        t1.context = None;
+        data.update("t1", t1);
+        assert!(!data.is_referenced("@home"));
+
+        data.remove_nota("t1");
+        assert!(data.is_referenced("proj"));
+        data.remove_nota("t2");
+        assert!(!data.is_referenced("proj"));
+        assert!(data.project_index.is_empty());
+        assert!(data.context_index.is_empty());
+    }
+
+    /// Test that filtered listings use the reverse indexes and keep file order
+    #[test]
+    fn test_list_filtered_by_project_and_context() {
+        let mut data = GtdData::new();
+        for (id, status, project, context) in [
+            ("t1", NotaStatus::next_action, Some("proj"), Some("@home")),
+            ("t2", NotaStatus::inbox, Some("proj"), Some("@office")),
+            ("t3", NotaStatus::next_action, None, Some("@home")),
+            ("t4", NotaStatus::next_action, Some("proj"), Some("@home")),
+        ] {
+            data.add(Nota {
+                id: id.to_string(),
+                status,
+                project: project.map(str::to_string),
+                context: context.map(str::to_string),
+                ..Default::default()
+            });
+        }
+
+        let ids = |notas: Vec<&Nota>| notas.iter().map(|n| n.id.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            ids(data.list_filtered(None, Some("proj"), None)),
+            ["t1", "t2", "t4"]
+        );
+        assert_eq!(
+            ids(data.list_filtered(None, None, Some("@home"))),
+            ["t1", "t3", "t4"]
+        );
+        assert_eq!(
+            ids(data.list_filtered(None, Some("proj"), Some("@home"))),
+            ["t1", "t4"]
+        );
+        assert_eq!(
+            ids(data.list_filtered(Some(NotaStatus::inbox), Some("proj"), None)),
+            ["t2"]
+        );
+        assert!(data.list_filtered(None, Some("missing"), None).is_empty());
+
+        // Moving a nota to another project updates the index
+        let mut t4 = data.find_by_id("t4").unwrap();
+        t4.project = Some("other".to_string());
+        data.update("t4", t4);
+        assert_eq!(
+            ids(data.list_filtered(None, Some("proj"), None)),
+            ["t1", "t2"]
+        );
+        assert_eq!(ids(data.list_filtered(None, Some("other"), None)), ["t4"]);
+    }
+
+    /// Test that list_filtered borrows the stored notas instead of cloning them
+    #[test]
+    fn test_list_filtered_borrows_notas() {
+        let mut data = GtdData::new();
+        data.add(Nota {
+            id: "t1".to_string(),
+            notes: Some("long notes".to_string()),
+            ..Default::default()
+        });
+
+        let listed = data.list_filtered(None, None, None);
+        assert_eq!(listed.len(), 1);
+        assert!(std::ptr::eq(listed[0], data.iter().next().unwrap()));
+        assert_eq!(data.list_all(None)[0].notes.as_deref(), Some("long notes"));
+    }
+
+    /// Test that keyword search follows additions, updates and removals
+    #[test]
+    fn test_search_index_tracks_changes() {
+        let mut data = GtdData::new();
+        data.add(Nota {
+            id: "meeting-prep".to_string(),
+            title: "Prepare Meeting".to_string(),
+            notes: Some("Agenda: budget, hiring.\nBring SLIDES".to_string()),
+            ..Default::default()
+        });
+        data.add(Nota {
+            id: "call".to_string(),
+            title: "Call the bank".to_string(),
+            ..Default::default()
+        });
+
+        let sorted = |ids: HashSet<&str>| {
+            let mut ids: Vec<String> = ids.into_iter().map(str::to_string).collect();
+            ids.sort();
+            ids
+        };
+        // Substrings inside tokens, across punctuation, and case-insensitive
+        assert_eq!(sorted(data.search("EET")), ["meeting-prep"]);
+        assert_eq!(sorted(data.search("budget, hir")), ["meeting-prep"]);
+        assert_eq!(sorted(data.search("slides")), ["meeting-prep"]);
+        assert_eq!(sorted(data.search("a")), ["call", "meeting-prep"]);
+        assert_eq!(sorted(data.search("-")), ["meeting-prep"]);
+        assert!(data.search("budget hiring").is_empty());
+
+        let mut call = data.find_by_id("call").unwrap();
+        call.notes = Some("Ask about the budget".to_string());
+        data.update("call", call);
+        assert_eq!(sorted(data.search("budget")), ["call", "meeting-prep"]);
+
+        data.remove_nota("meeting-prep");
+        assert_eq!(sorted(data.search("budget")), ["call"]);
+        assert!(data.search("slides").is_empty());
+
+        // A rename re-tokenizes the old version, so its postings go away
+        let renamed = Nota {
+            id: "phone".to_string(),
+            title: "Phone the bank".to_string(),
+            ..data.find_by_id("call").unwrap()
+        };
+        data.update("call", renamed);
+        assert!(data.search("call").is_empty());
+        assert_eq!(sorted(data.search("PHONE")), ["phone"]);
+        assert_eq!(data.search_index.candidates("call"), Some(HashSet::new()));
+
+        data.remove_nota("phone");
+        for token in ["phone", "bank", "budget", "slides", "meeting"] {
+            assert_eq!(data.search_index.candidates(token), Some(HashSet::new()));
+        }
+    }
+
+    /// Test that is_dirty compares against the last saved content
+    #[test]
+    fn test_is_dirty_tracks_saved_content() {
+        let mut data = GtdData::new();
+        assert!(data.is_dirty("content"));
+
+        data.mark_saved("content");
+        assert!(!data.is_dirty("content"));
+        assert!(data.is_dirty("changed content"));
+    }
+
+    /// Test that the ID index is correctly rebuilt from TOML deserialization
     ///
     /// This validates that the HashMap is properly reconstructed when loading
     /// data from disk, maintaining synchronization with Vec.
     #[test]
-    fn test_nota_map_rebuilt_on_deserialize() {
+    fn test_id_index_rebuilt_on_deserialize() {
         let mut data = GtdData::new();
 
         // Add some notas
@@ -630,18 +1521,19 @@ mod tests {
         // Serialize to TOML
         let toml_str = toml::to_string(&data).unwrap();
 
-        // nota_map should NOT be in TOML (it's not serialized)
-        assert!(!toml_str.contains("nota_map"));
+        // The ID index should NOT be in TOML (it's not serialized)
+        assert!(!toml_str.contains("id_index"));
 
         // Deserialize
         let loaded: GtdData = toml::from_str(&toml_str).unwrap();
 
-        // Verify nota_map was rebuilt correctly
-        assert_eq!(loaded.nota_map.len(), 2);
-        assert_eq!(loaded.nota_map.get("task-1"), Some(&NotaStatus::inbox));
-        assert_eq!(loaded.nota_map.get("proj-1"), Some(&NotaStatus::project));
+        // Verify the ID index was rebuilt correctly
+        assert_eq!(loaded.id_index.len(), 2);
+        assert_eq!(loaded.status_of("task-1"), Some(&NotaStatus::inbox));
+        assert_eq!(loaded.status_of("proj-1"), Some(&NotaStatus::project));
 
         // Verify Vec and HashMap are in sync
-        assert_eq!(loaded.notas.len(), loaded.nota_map.len());
+        assert_eq!(loaded.notas.len(), loaded.id_index.len());
+        assert_eq!(loaded.id_index.get("proj-1"), Some(&1));
     }
 }
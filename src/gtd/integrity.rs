@@ -0,0 +1,101 @@
+//! Whole-file integrity checks for GtdData
+//!
+//! Tools validate the fields they change, but a file edited by hand, merged
+//! from another machine, or changed through the library API can still hold
+//! dangling links or contradictory dates. [`GtdData::check_integrity`] checks
+//! every nota at once; strict mode runs it before each save.
+
+use super::gtd_data::GtdData;
+use super::nota::{NotaStatus, RecurrencePattern};
+use crate::availability::Availability;
+use std::collections::HashSet;
+
+impl GtdData {
+    /// Check references, IDs, dates and recurrence settings of every nota
+    ///
+    /// The following problems are reported:
+    /// - a `project` that is not the ID of a project
+    /// - a `context` that is not the ID of a context
+    /// - a `goal` that is not the ID of a goal
+    /// - an `availability` that is not a valid schedule
+    /// - an empty ID, or an ID used more than once (ignoring case when
+    ///   case-insensitive IDs are enabled)
+    /// - a calendar item without `start_date`
+    /// - `updated_at` earlier than `created_at`
+    /// - a recurrence pattern whose `recurrence_config` is missing or never matches
+    ///
+    /// # Returns
+    /// One message per problem in file order (empty when the data is consistent)
+    pub fn check_integrity(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        let mut seen = HashSet::new();
+
+        for nota in &self.notas {
+            let id = &nota.id;
+            if id.is_empty() {
+                issues.push(format!("Item '{}' has an empty ID", nota.title));
+            } else {
+                let key = if self.case_insensitive_ids() {
+                    id.to_lowercase()
+                } else {
+                    id.clone()
+                };
+                if !seen.insert(key) {
+                    issues.push(format!("{}: ID is used by more than one item", id));
+                }
+            }
+
+            if let Some(project) = &nota.project
+                && !self.validate_nota_project(nota)
+            {
+                issues.push(format!("{}: project '{}' does not exist", id, project));
+            }
+            if let Some(context) = &nota.context
+                && !self.validate_nota_context(nota)
+            {
+                issues.push(format!("{}: context '{}' does not exist", id, context));
+            }
+            if let Some(goal) = &nota.goal
+                && self.find_goal_by_id(goal).is_none()
+            {
+                issues.push(format!("{}: goal '{}' does not exist", id, goal));
+            }
+            if let Some(availability) = &nota.availability
+                && let Err(e) = availability.parse::<Availability>()
+            {
+                issues.push(format!("{}: {}", id, e));
+            }
+
+            if nota.status == NotaStatus::calendar && nota.start_date.is_none() {
+                issues.push(format!("{}: calendar item has no start_date", id));
+            }
+            if nota.updated_at < nota.created_at {
+                issues.push(format!(
+                    "{}: updated_at {} is before created_at {}",
+                    id, nota.updated_at, nota.created_at
+                ));
+            }
+
+            if let Some(pattern) = &nota.recurrence_pattern {
+                let needs_config = !matches!(pattern, RecurrencePattern::daily);
+                match &nota.recurrence_config {
+                    None if needs_config => issues.push(format!(
+                        "{}: recurrence '{:?}' has no recurrence_config",
+                        id, pattern
+                    )),
+                    Some(config)
+                        if needs_config
+                            && nota.calculate_next_occurrence(nota.created_at).is_none() =>
+                    {
+                        issues.push(format!(
+                            "{}: recurrence_config '{}' is not valid for '{:?}'",
+                            id, config, pattern
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        issues
+    }
+}
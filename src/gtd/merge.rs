@@ -0,0 +1,53 @@
+//! Nota-level merging of two versions of the GTD data
+//!
+//! Used to resolve Git conflicts in the data file without conflict markers:
+//! both versions are parsed and combined item by item instead of line by line.
+
+use super::gtd_data::GtdData;
+
+impl GtdData {
+    /// Merge another version of the data into this one at the nota level
+    ///
+    /// - Notas that exist on only one side are kept (union of items)
+    /// - Notas that exist on both sides take every field from the version with
+    ///   the newer `updated_at`; on a tie this version (`self`) wins
+    /// - Counters take the larger value so generated IDs never collide
+    /// - External notes stay enabled if either version enables them
+    /// - Migration history keeps this version's entries, then any only in `other`
+    ///
+    /// Items keep the order of `self`, followed by items only present in `other`.
+    ///
+    /// # Arguments
+    /// * `other` - The other version (e.g., the remote side of a conflict)
+    ///
+    /// # Returns
+    /// A new GtdData containing the merged result
+    pub fn merge(&self, other: &GtdData) -> GtdData {
+        let mut merged = GtdData::new();
+
+        for nota in &self.notas {
+            let theirs = other.id_index.get(&nota.id).map(|&pos| &other.notas[pos]);
+            let newest = match theirs {
+                Some(theirs) if theirs.updated_at > nota.updated_at => theirs,
+                _ => nota,
+            };
+            merged.add_nota(newest.clone());
+        }
+        for nota in &other.notas {
+            if !self.id_index.contains_key(&nota.id) {
+                merged.add_nota(nota.clone());
+            }
+        }
+
+        merged.task_counter = self.task_counter.max(other.task_counter);
+        merged.project_counter = self.project_counter.max(other.project_counter);
+        merged.external_notes = self.external_notes.or(other.external_notes);
+        merged.migration_history = self.migration_history.clone();
+        for record in &other.migration_history {
+            if !merged.migration_history.contains(record) {
+                merged.migration_history.push(record.clone());
+            }
+        }
+        merged
+    }
+}
@@ -2,16 +2,28 @@
 //!
 //! This module contains the core GTD data structures and their implementations.
 //! It is split into submodules for better organization:
+//! - `clock`: Source of the current date (system or fixed, for tests)
 //! - `nota`: Unified nota structure (tasks, projects, contexts)
 //! - `gtd_data`: Main data container with all GTD operations
 //! - `queries`: Query and compatibility methods for GtdData
+//! - `integrity`: Whole-file consistency checks (used by strict saves)
+//! - `merge`: Nota-level merging of two versions of GtdData (Git conflict resolution)
+//! - `search`: Incremental inverted index for keyword search
 //! - `serde_impl`: Serialization/deserialization implementations
 
+mod clock;
 mod gtd_data;
+mod integrity;
+mod merge;
 mod nota;
 mod queries;
+mod search;
 mod serde_impl;
 
 // Re-export all public types
-pub use gtd_data::GtdData;
-pub use nota::{Nota, NotaStatus, RecurrencePattern, local_date_today};
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use gtd_data::{GtdData, ProjectProgress};
+pub use nota::{
+    Energy, Nota, NotaStatus, Priority, RecurrencePattern, local_date_today, local_now,
+};
+pub(crate) use serde_impl::NotesByReference;
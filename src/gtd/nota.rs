@@ -1,12 +1,17 @@
-use chrono::{Local, NaiveDate};
+use chrono::{Local, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-/// Get the current date in local timezone
+/// Get the current date in the system timezone
 pub fn local_date_today() -> NaiveDate {
     Local::now().date_naive()
 }
 
+/// Get the current date and time in the system timezone
+pub fn local_now() -> NaiveDateTime {
+    Local::now().naive_local()
+}
+
 /// Recurrence pattern for recurring tasks
 ///
 /// Defines how a task repeats after completion.
@@ -24,6 +29,66 @@ pub enum RecurrencePattern {
     yearly,
 }
 
+/// How important a task is
+///
+/// Uses snake_case naming to match TOML serialization format.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Priority {
+    /// Do before anything else
+    high,
+    /// Normal importance
+    medium,
+    /// Do when nothing more important fits
+    low,
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "high" => Ok(Priority::high),
+            "medium" => Ok(Priority::medium),
+            "low" => Ok(Priority::low),
+            _ => Err(format!(
+                "Invalid priority '{}'. Valid options are: high, medium, low",
+                s
+            )),
+        }
+    }
+}
+
+/// How much mental energy a task takes
+///
+/// Uses snake_case naming to match TOML serialization format.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Energy {
+    /// Routine work that can be done when tired
+    low,
+    /// Needs normal attention
+    medium,
+    /// Needs full concentration
+    high,
+}
+
+impl FromStr for Energy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Energy::low),
+            "medium" => Ok(Energy::medium),
+            "high" => Ok(Energy::high),
+            _ => Err(format!(
+                "Invalid energy '{}'. Valid options are: low, medium, high",
+                s
+            )),
+        }
+    }
+}
+
 /// Task status in the GTD workflow
 ///
 /// Represents the different states a task can be in according to GTD methodology.
@@ -51,6 +116,10 @@ pub enum NotaStatus {
     context,
     /// Project nota (represents a multi-step outcome)
     project,
+    /// Goal nota (a 1-3 year objective that projects contribute to)
+    goal,
+    /// Template nota (a reusable checklist, stamped out as tasks by `instantiate_template`)
+    template,
     /// Deleted or discarded items
     trash,
 }
@@ -71,8 +140,10 @@ impl FromStr for NotaStatus {
             "trash" => Ok(NotaStatus::trash),
             "context" => Ok(NotaStatus::context),
             "project" => Ok(NotaStatus::project),
+            "goal" => Ok(NotaStatus::goal),
+            "template" => Ok(NotaStatus::template),
             _ => Err(format!(
-                "Invalid status '{}'. Valid options are: inbox, next_action, waiting_for, someday, later, calendar, done, reference, trash, context, project",
+                "Invalid status '{}'. Valid options are: inbox, next_action, waiting_for, someday, later, calendar, done, reference, trash, context, project, goal, template",
                 s
             )),
         }
@@ -85,17 +156,19 @@ impl FromStr for NotaStatus {
 /// The `status` field determines what type of nota it is:
 /// - status = "context": represents a Context
 /// - status = "project": represents a Project
+/// - status = "goal": represents a goal that projects contribute to
+/// - status = "template": represents a reusable checklist
 /// - other statuses (inbox, next_action, etc.): represents a Task
 ///
 /// This design is inspired by TiddlyWiki's tiddler concept.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Nota {
     /// Unique identifier (e.g., "meeting-prep", "website-redesign", "Office")
     pub id: String,
     /// Title describing the nota
     pub title: String,
-    /// Current status (inbox, next_action, waiting_for, later, calendar, someday, done, trash, context, project)
+    /// Current status (inbox, next_action, waiting_for, later, calendar, someday, done, trash, context, project, goal, template)
     pub status: NotaStatus,
     /// Optional parent project ID
     pub project: Option<String>,
@@ -105,9 +178,11 @@ pub struct Nota {
     pub notes: Option<String>,
     /// Optional start date (format: YYYY-MM-DD)
     pub start_date: Option<NaiveDate>,
-    /// Date when the nota was created
+    /// Date when the nota was created (the load date when the file has none)
+    #[serde(default = "local_date_today")]
     pub created_at: NaiveDate,
-    /// Date when the nota was last updated
+    /// Date when the nota was last updated (the load date when the file has none)
+    #[serde(default = "local_date_today")]
     pub updated_at: NaiveDate,
     /// Optional recurrence pattern (daily, weekly, monthly, yearly)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -119,8 +194,52 @@ pub struct Nota {
     /// - yearly: month-day pairs (e.g., "1-1,12-25" for Jan 1 and Dec 25)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recurrence_config: Option<String>,
+    /// Optional URL of the source of the nota (e.g. the GitHub issue it tracks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Optional priority (high, medium, low)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Optional date the nota must be finished by (format: YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    /// Optional estimated time to do the nota, in minutes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    /// Optional energy the nota takes (low, medium, high)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy: Option<Energy>,
+    /// Optional day the nota is one of the most important tasks (MITs) for;
+    /// it only counts on that day, so the focus lapses at local midnight
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_date: Option<NaiveDate>,
+    /// Whether a recurring nota is paused: completing it creates no next
+    /// occurrence and the tickler leaves it alone
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+    /// Status the nota had before it was moved to trash, so `restore` can put
+    /// it back; only set while the nota is in trash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<NotaStatus>,
+    /// Optional ID of the goal a project contributes to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    /// Optional schedule of when a context can be used (e.g. "Mon-Fri 09:00-18:00"),
+    /// see [`crate::availability`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability: Option<String>,
+    /// Optional days a waiting_for item may wait before it needs a follow-up,
+    /// overriding the server default (see [`crate::escalation`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub escalate_after_days: Option<u32>,
+    /// Optional date the nota entered its current status (set by [`Nota::set_status`];
+    /// `updated_at` stands in when missing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_since: Option<NaiveDate>,
 }
 
+/// An empty inbox nota; the dates are `NaiveDate::default()` until the
+/// code creating the item sets them from its clock
 impl Default for Nota {
     fn default() -> Self {
         Self {
@@ -131,10 +250,22 @@ impl Default for Nota {
             context: None,
             notes: None,
             start_date: None,
-            created_at: local_date_today(),
-            updated_at: local_date_today(),
+            created_at: NaiveDate::default(),
+            updated_at: NaiveDate::default(),
             recurrence_pattern: None,
             recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         }
     }
 }
@@ -142,7 +273,10 @@ impl Default for Nota {
 impl Nota {
     /// Check if this nota is a task
     pub fn is_task(&self) -> bool {
-        !matches!(self.status, NotaStatus::context | NotaStatus::project)
+        !matches!(
+            self.status,
+            NotaStatus::context | NotaStatus::project | NotaStatus::goal | NotaStatus::template
+        )
     }
 
     /// Check if this nota is a project
@@ -150,11 +284,62 @@ impl Nota {
         self.status == NotaStatus::project
     }
 
+    /// Check if this nota is a goal
+    pub fn is_goal(&self) -> bool {
+        self.status == NotaStatus::goal
+    }
+
+    /// Check if this nota is a template
+    pub fn is_template(&self) -> bool {
+        self.status == NotaStatus::template
+    }
+
     /// Check if this nota is a context
     pub fn is_context(&self) -> bool {
         self.status == NotaStatus::context
     }
 
+    /// Change the status, remembering the old one while the nota is in trash
+    ///
+    /// Moving into trash records the current status in `previous_status`;
+    /// moving out of trash clears it. A different status records today in
+    /// `status_since`.
+    ///
+    /// # Arguments
+    /// * `status` - The new status
+    /// * `today` - The current date
+    pub fn set_status(&mut self, status: NotaStatus, today: NaiveDate) {
+        if status != self.status {
+            self.status_since = Some(today);
+        }
+        if status == NotaStatus::trash {
+            if self.status != NotaStatus::trash {
+                self.previous_status = Some(self.status.clone());
+            }
+        } else {
+            self.previous_status = None;
+        }
+        self.status = status;
+    }
+
+    /// Days the nota has been in its current status
+    ///
+    /// # Arguments
+    /// * `today` - The current date
+    pub fn days_in_status(&self, today: NaiveDate) -> i64 {
+        (today - self.status_since.unwrap_or(self.updated_at)).num_days()
+    }
+
+    /// Whether the nota is a next action that has waited `after_days` days or more
+    ///
+    /// # Arguments
+    /// * `today` - The current date
+    /// * `after_days` - Days in next_action before an action is stale
+    pub fn is_stale(&self, today: NaiveDate, after_days: u32) -> bool {
+        self.status == NotaStatus::next_action
+            && self.days_in_status(today) >= i64::from(after_days)
+    }
+
     /// Check if this nota has recurrence configured
     pub fn is_recurring(&self) -> bool {
         self.recurrence_pattern.is_some()
@@ -0,0 +1,121 @@
+//! Incremental inverted index for keyword search
+//!
+//! Keyword search matches case-insensitive substrings of a nota's id, title and
+//! notes. Instead of lowercasing every nota on every query, the index keeps a
+//! token → IDs map, updated as notas are added and removed. It holds no copy of
+//! the text: removing a nota tokenizes the removed nota again to find its
+//! postings.
+//!
+//! A query is answered in two steps:
+//! 1. Candidates: every alphanumeric run of the query is a substring of some
+//!    token of a matching nota, so only notas owning such a token are considered.
+//!    This scans the token vocabulary, not the note bodies.
+//! 2. Verification: the candidates' fields are checked for the whole query
+//!    ([`matches`]), which keeps the exact substring semantics of the old
+//!    linear filter.
+
+use super::nota::Nota;
+use std::collections::{HashMap, HashSet};
+
+/// Token index over the searchable text of all notas
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SearchIndex {
+    /// Lowercased token → IDs of the notas containing it
+    postings: HashMap<String, HashSet<String>>,
+}
+
+impl SearchIndex {
+    /// Index a nota's id, title and notes
+    ///
+    /// # Arguments
+    /// * `nota` - The nota to index (a previous version must be removed first)
+    pub(crate) fn insert(&mut self, nota: &Nota) {
+        for token in tokens(nota) {
+            self.postings
+                .entry(token)
+                .or_default()
+                .insert(nota.id.clone());
+        }
+    }
+
+    /// Drop a nota from the index
+    ///
+    /// # Arguments
+    /// * `nota` - The nota as it was indexed
+    pub(crate) fn remove(&mut self, nota: &Nota) {
+        for token in tokens(nota) {
+            if let Some(ids) = self.postings.get_mut(&token) {
+                ids.remove(&nota.id);
+                if ids.is_empty() {
+                    self.postings.remove(&token);
+                }
+            }
+        }
+    }
+
+    /// Remove every entry
+    pub(crate) fn clear(&mut self) {
+        self.postings.clear();
+    }
+
+    /// IDs of the notas that may contain `keyword`
+    ///
+    /// # Arguments
+    /// * `keyword` - Lowercased search keyword
+    ///
+    /// # Returns
+    /// The candidates to verify with [`matches`], or `None` when the keyword
+    /// has no alphanumeric characters and every nota is a candidate
+    pub(crate) fn candidates(&self, keyword: &str) -> Option<HashSet<&str>> {
+        let mut candidates: Option<HashSet<&str>> = None;
+        for query_token in tokenize(keyword) {
+            let owners: HashSet<&str> = self
+                .postings
+                .iter()
+                .filter(|(token, _)| token.contains(query_token))
+                .flat_map(|(_, ids)| ids.iter().map(String::as_str))
+                .collect();
+            candidates = Some(match candidates {
+                Some(previous) => previous.intersection(&owners).copied().collect(),
+                None => owners,
+            });
+        }
+        candidates
+    }
+}
+
+/// Whether a nota's id, title or notes contain `keyword`
+///
+/// # Arguments
+/// * `nota` - The nota to check
+/// * `keyword` - Lowercased search keyword
+pub(crate) fn matches(nota: &Nota, keyword: &str) -> bool {
+    fields(nota).any(|field| field.to_lowercase().contains(keyword))
+}
+
+/// The searchable fields of a nota
+fn fields(nota: &Nota) -> impl Iterator<Item = &str> {
+    [
+        Some(nota.id.as_str()),
+        Some(nota.title.as_str()),
+        nota.notes.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Distinct lowercased tokens of a nota's searchable fields
+fn tokens(nota: &Nota) -> HashSet<String> {
+    fields(nota)
+        .flat_map(|field| {
+            let field = field.to_lowercase();
+            tokenize(&field).map(str::to_string).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Split lowercased text into maximal alphanumeric runs
+fn tokenize(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+}
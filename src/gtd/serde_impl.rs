@@ -48,9 +48,17 @@ impl<'de> Deserialize<'de> for GtdData {
             let mut done = helper.done;
             let mut reference = helper.reference;
             let mut trash = helper.trash;
+            let mut goal = helper.goal;
+            let mut template = helper.template;
             let mut projects = migrate_projects_to_latest(helper.projects);
             let mut contexts = helper.contexts;
 
+            // Remember the file order of Version 3 project/context arrays so that
+            // loading and saving again does not reorder them
+            let project_order: Vec<String> = helper.project.iter().map(|p| p.id.clone()).collect();
+            let context_order: Vec<String> =
+                helper.context.iter().map(|c| c.name.clone()).collect();
+
             // If this is Version 3 format with Vec arrays for projects/contexts, convert to HashMap
             if !helper.project.is_empty() {
                 for project in helper.project {
@@ -77,6 +85,8 @@ impl<'de> Deserialize<'de> for GtdData {
             normalize_task_line_endings(&mut done);
             normalize_task_line_endings(&mut reference);
             normalize_task_line_endings(&mut trash);
+            normalize_task_line_endings(&mut goal);
+            normalize_task_line_endings(&mut template);
             normalize_project_line_endings(&mut projects);
             normalize_context_line_endings(&mut contexts);
 
@@ -108,6 +118,12 @@ impl<'de> Deserialize<'de> for GtdData {
             for task in &mut trash {
                 task.status = NotaStatus::trash;
             }
+            for task in &mut goal {
+                task.status = NotaStatus::goal;
+            }
+            for task in &mut template {
+                task.status = NotaStatus::template;
+            }
 
             // Convert all old structures to Nota
             for task in inbox {
@@ -137,92 +153,185 @@ impl<'de> Deserialize<'de> for GtdData {
             for task in trash {
                 notas.push(nota_from_task(task));
             }
-            for project in projects.into_values() {
+            for task in goal {
+                notas.push(nota_from_task(task));
+            }
+            for task in template {
+                notas.push(nota_from_task(task));
+            }
+            for project in in_file_order(projects, &project_order) {
                 notas.push(nota_from_project(project));
             }
-            for context in contexts.into_values() {
+            for context in in_file_order(contexts, &context_order) {
                 notas.push(nota_from_context(context));
             }
         }
 
-        // Build nota_map from all notas for duplicate checking
-        let mut nota_map = HashMap::new();
-        for nota in &notas {
-            nota_map.insert(nota.id.clone(), nota.status.clone());
-        }
-
-        Ok(GtdData {
+        let mut data = GtdData {
             format_version: 3, // Use version 3 for in-memory representation
             notas,
-            nota_map,
             task_counter: helper.task_counter,
             project_counter: helper.project_counter,
-        })
+            external_notes: helper.external_notes_min_chars,
+            migration_history: helper.migration_history,
+            ..GtdData::default()
+        };
+        // Build the lookup indexes from all notas
+        data.rebuild_indexes();
+        Ok(data)
     }
 }
 
+/// Take the values of `map` in the order of `order`, then any others sorted by key
+///
+/// Keyed tables (`[projects.<id>]`) carry no order of their own; sorting them keeps
+/// the output deterministic.
+fn in_file_order<T>(mut map: HashMap<String, T>, order: &[String]) -> Vec<T> {
+    let mut values: Vec<T> = order.iter().filter_map(|key| map.remove(key)).collect();
+    let mut rest: Vec<(String, T)> = map.into_iter().collect();
+    rest.sort_by(|a, b| a.0.cmp(&b.0));
+    values.extend(rest.into_iter().map(|(_, value)| value));
+    values
+}
+
 impl Serialize for GtdData {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        use serde::ser::SerializeStruct;
-        use std::collections::HashMap;
-
-        let mut state = serializer.serialize_struct("GtdData", 13)?;
-        state.serialize_field("format_version", &self.format_version)?;
-
-        // Separate notas by status in a single pass (Version 3 format)
-        let mut status_map: HashMap<NotaStatus, Vec<&Nota>> = HashMap::new();
-        for nota in &self.notas {
-            status_map
-                .entry(nota.status.clone())
-                .or_default()
-                .push(nota);
-        }
+        serialize_data(self, None, serializer)
+    }
+}
 
-        // Serialize each status array (only if non-empty), in the order they appear in the enum
-        if let Some(inbox) = status_map.get(&NotaStatus::inbox) {
-            state.serialize_field("inbox", inbox)?;
-        }
-        if let Some(next_action) = status_map.get(&NotaStatus::next_action) {
-            state.serialize_field("next_action", next_action)?;
-        }
-        if let Some(waiting_for) = status_map.get(&NotaStatus::waiting_for) {
-            state.serialize_field("waiting_for", waiting_for)?;
-        }
-        if let Some(later) = status_map.get(&NotaStatus::later) {
-            state.serialize_field("later", later)?;
-        }
-        if let Some(calendar) = status_map.get(&NotaStatus::calendar) {
-            state.serialize_field("calendar", calendar)?;
-        }
-        if let Some(someday) = status_map.get(&NotaStatus::someday) {
-            state.serialize_field("someday", someday)?;
-        }
-        if let Some(done) = status_map.get(&NotaStatus::done) {
-            state.serialize_field("done", done)?;
-        }
-        if let Some(reference) = status_map.get(&NotaStatus::reference) {
-            state.serialize_field("reference", reference)?;
-        }
-        if let Some(context) = status_map.get(&NotaStatus::context) {
-            state.serialize_field("context", context)?;
-        }
-        if let Some(project) = status_map.get(&NotaStatus::project) {
-            state.serialize_field("project", project)?;
-        }
-        if let Some(trash) = status_map.get(&NotaStatus::trash) {
-            state.serialize_field("trash", trash)?;
-        }
+/// GtdData rendered with some notes replaced by references to Markdown files
+///
+/// Items listed in `notes_files` (ID → file name) are written with
+/// `notes_file = "<file name>"` instead of their `notes`. Used by
+/// [`crate::external_notes`] for the on-disk form of format version 4.
+pub(crate) struct NotesByReference<'a> {
+    pub(crate) data: &'a GtdData,
+    pub(crate) notes_files: &'a HashMap<String, String>,
+}
+
+impl Serialize for NotesByReference<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_data(self.data, Some(self.notes_files), serializer)
+    }
+}
 
-        if self.task_counter != 0 {
-            state.serialize_field("task_counter", &self.task_counter)?;
+/// A nota as written to a status array, optionally with its notes in a file
+struct StoredNota<'a> {
+    nota: &'a Nota,
+    notes_file: Option<&'a str>,
+}
+
+impl Serialize for StoredNota<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct WithNotesFile<'a> {
+            #[serde(flatten)]
+            nota: Nota,
+            notes_file: &'a str,
         }
-        if self.project_counter != 0 {
-            state.serialize_field("project_counter", &self.project_counter)?;
+
+        match self.notes_file {
+            None => self.nota.serialize(serializer),
+            Some(notes_file) => WithNotesFile {
+                nota: Nota {
+                    notes: None,
+                    ..self.nota.clone()
+                },
+                notes_file,
+            }
+            .serialize(serializer),
         }
+    }
+}
+
+fn serialize_data<S>(
+    data: &GtdData,
+    notes_files: Option<&HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    use serde::ser::SerializeStruct;
+
+    let mut state = serializer.serialize_struct("GtdData", 15)?;
+    state.serialize_field("format_version", &data.written_format_version())?;
+    if let Some(min_chars) = data.external_notes {
+        state.serialize_field("external_notes_min_chars", &min_chars)?;
+    }
+
+    // Separate notas by status in a single pass (Version 3 format)
+    let mut status_map: HashMap<NotaStatus, Vec<StoredNota>> = HashMap::new();
+    for nota in &data.notas {
+        let notes_file = notes_files
+            .and_then(|files| files.get(&nota.id))
+            .map(String::as_str);
+        status_map
+            .entry(nota.status.clone())
+            .or_default()
+            .push(StoredNota { nota, notes_file });
+    }
+
+    // Serialize each status array (only if non-empty), in the order they appear in the enum
+    if let Some(inbox) = status_map.get(&NotaStatus::inbox) {
+        state.serialize_field("inbox", inbox)?;
+    }
+    if let Some(next_action) = status_map.get(&NotaStatus::next_action) {
+        state.serialize_field("next_action", next_action)?;
+    }
+    if let Some(waiting_for) = status_map.get(&NotaStatus::waiting_for) {
+        state.serialize_field("waiting_for", waiting_for)?;
+    }
+    if let Some(later) = status_map.get(&NotaStatus::later) {
+        state.serialize_field("later", later)?;
+    }
+    if let Some(calendar) = status_map.get(&NotaStatus::calendar) {
+        state.serialize_field("calendar", calendar)?;
+    }
+    if let Some(someday) = status_map.get(&NotaStatus::someday) {
+        state.serialize_field("someday", someday)?;
+    }
+    if let Some(done) = status_map.get(&NotaStatus::done) {
+        state.serialize_field("done", done)?;
+    }
+    if let Some(reference) = status_map.get(&NotaStatus::reference) {
+        state.serialize_field("reference", reference)?;
+    }
+    if let Some(context) = status_map.get(&NotaStatus::context) {
+        state.serialize_field("context", context)?;
+    }
+    if let Some(project) = status_map.get(&NotaStatus::project) {
+        state.serialize_field("project", project)?;
+    }
+    if let Some(goal) = status_map.get(&NotaStatus::goal) {
+        state.serialize_field("goal", goal)?;
+    }
+    if let Some(template) = status_map.get(&NotaStatus::template) {
+        state.serialize_field("template", template)?;
+    }
+    if let Some(trash) = status_map.get(&NotaStatus::trash) {
+        state.serialize_field("trash", trash)?;
+    }
 
-        state.end()
+    if data.task_counter != 0 {
+        state.serialize_field("task_counter", &data.task_counter)?;
+    }
+    if data.project_counter != 0 {
+        state.serialize_field("project_counter", &data.project_counter)?;
     }
+    if !data.migration_history.is_empty() {
+        state.serialize_field("migration_history", &data.migration_history)?;
+    }
+
+    state.end()
 }
@@ -0,0 +1,94 @@
+//! Backup handlers for GTD MCP server
+
+use crate::{GtdServerHandler, MAX_BACKUPS};
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Takes a snapshot of the saved data that restore_backup can bring back.
+    pub async fn handle_backup_now(&self) -> McpResult<String> {
+        // Hold the write queue so the snapshot does not catch a save half-way
+        let _queue = self.write_queue.lock().await;
+
+        let storage = self.storage();
+        let snapshot = match tokio::task::spawn_blocking(move || storage.snapshot()).await {
+            Ok(Ok(snapshot)) => snapshot,
+            Ok(Err(e)) => bail_public!(_, "Failed to take a backup: {}", e),
+            Err(e) => bail_public!(_, "Failed to take a backup: {}", e),
+        };
+        let item_count = snapshot.item_count();
+        let taken_at = snapshot.taken_at();
+
+        let number = {
+            let mut backups = self.backups.lock().unwrap();
+            let number = backups.last().map_or(1, |(number, _)| number + 1);
+            backups.push((number, snapshot));
+            if backups.len() > MAX_BACKUPS {
+                backups.remove(0);
+            }
+            number
+        };
+        Ok(format!(
+            "Backup #{} taken: {} items as of {}. Bring it back with restore_backup.",
+            number,
+            item_count,
+            taken_at.format("%Y-%m-%d %H:%M:%S")
+        ))
+    }
+
+    /// Writes a backup back to the data file and adopts it as the current data.
+    pub async fn handle_restore_backup(&self, number: Option<usize>) -> McpResult<String> {
+        if self.transaction.lock().unwrap().is_some() {
+            bail_public!(
+                _,
+                "Cannot restore a backup while a transaction is open. Commit or abort it first."
+            );
+        }
+        let (number, snapshot) = {
+            let backups = self.backups.lock().unwrap();
+            let found = match number {
+                Some(number) => backups.iter().find(|(n, _)| *n == number),
+                None => backups.last(),
+            };
+            match found {
+                Some(backup) => backup.clone(),
+                None if backups.is_empty() => {
+                    bail_public!(_, "No backups yet. Take one with backup_now.")
+                }
+                None => bail_public!(
+                    _,
+                    "Backup #{} does not exist. Available backups: {}",
+                    number.unwrap_or_default(),
+                    backups
+                        .iter()
+                        .map(|(n, _)| format!("#{}", n))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        };
+
+        // Hold the write queue and the data so no tool call sees a half-restored state
+        let _queue = self.write_queue.lock().await;
+        let mut data = self.data.write().await;
+        let storage = self.storage();
+        let restore = snapshot.clone();
+        let restored = match tokio::task::spawn_blocking(move || storage.restore(&restore)).await {
+            Ok(Ok(restored)) => restored,
+            Ok(Err(e)) => bail_public!(_, "Failed to restore backup #{}: {}", number, e),
+            Err(e) => bail_public!(_, "Failed to restore backup #{}: {}", number, e),
+        };
+        // The restore rewrote the file: report what it changed like any save
+        let events = self.saved_events(&restored);
+        *data = restored;
+        drop(data);
+        drop(_queue);
+        self.hooks.fire(&events);
+
+        Ok(format!(
+            "Restored backup #{} ({} items as of {}).",
+            number,
+            snapshot.item_count(),
+            snapshot.taken_at().format("%Y-%m-%d %H:%M:%S")
+        ))
+    }
+}
@@ -0,0 +1,42 @@
+//! Board handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::board::{self, BoardFormat};
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Renders the inbox, next, waiting and recently done items as board columns.
+    pub async fn handle_board(
+        &self,
+        format: Option<String>,
+        project: Option<String>,
+    ) -> McpResult<String> {
+        let format = match format.as_deref() {
+            None => BoardFormat::default(),
+            Some(s) => match s.parse::<BoardFormat>() {
+                Ok(format) => format,
+                Err(e) => bail_public!(_, "{}", e),
+            },
+        };
+
+        let data = self.data.read().await;
+        let project = match project.filter(|p| !p.trim().is_empty()) {
+            None => None,
+            Some(p) => match data.find_project_by_id(p.trim()) {
+                Some(found) => Some(found.id.clone()),
+                None => {
+                    let message = validation::format_invalid_project_error(p.trim(), &data);
+                    drop(data);
+                    bail_public!(_, "{}", message);
+                }
+            },
+        };
+        Ok(board::render(
+            &data,
+            project.as_deref(),
+            format,
+            self.clock.today(),
+        ))
+    }
+}
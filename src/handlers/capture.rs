@@ -0,0 +1,32 @@
+//! Quick capture handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles quick capture - creates an inbox item with a generated ID.
+    pub async fn handle_capture(&self, title: String, notes: Option<String>) -> McpResult<String> {
+        let title = title.trim().to_string();
+        if title.is_empty() {
+            bail_public!(_, "Title is required: describe what needs attention");
+        }
+        let notes = notes.filter(|notes| !notes.trim().is_empty());
+        let id = match self.id_generator.item_id(&*self.data.read().await, &title) {
+            Ok(id) => id,
+            Err(e) => bail_public!(_, "{}", e),
+        };
+
+        self.handle_inbox(
+            id,
+            title,
+            "inbox".to_string(),
+            None,
+            None,
+            notes,
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+}
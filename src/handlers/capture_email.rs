@@ -0,0 +1,25 @@
+//! Email capture handler for GTD MCP server
+
+use crate::{GtdServerHandler, email};
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles email capture - parses the pasted email and creates an inbox item from it.
+    pub async fn handle_capture_email(&self, raw_text: String) -> McpResult<String> {
+        let email = match email::parse(&raw_text) {
+            Ok(email) => email,
+            Err(e) => bail_public!(_, "{}", e),
+        };
+        let mut response = self
+            .handle_capture(email.subject.clone(), email.notes())
+            .await?;
+        response.push_str(&format!("\nTitle: {}", email.subject));
+        if let Some(from) = &email.from {
+            response.push_str(&format!(
+                "\nSender: {} - if this now waits on them, move it to waiting_for",
+                from.display_name()
+            ));
+        }
+        Ok(response)
+    }
+}
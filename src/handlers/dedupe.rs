@@ -0,0 +1,144 @@
+//! Dedupe handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::duplicates::{self, DedupeAction};
+use crate::gtd::NotaStatus;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles dedupe - lists likely duplicates, or merges or trashes them in batch.
+    pub async fn handle_dedupe(
+        &self,
+        action: Option<String>,
+        ids: Option<Vec<String>>,
+    ) -> McpResult<String> {
+        let action = match action.as_deref().map(str::parse::<DedupeAction>) {
+            None => DedupeAction::default(),
+            Some(Ok(action)) => action,
+            Some(Err(e)) => bail_public!(_, "{}", e),
+        };
+        let mut data = self.data.write().await;
+        let only: Option<Vec<String>> = ids.map(|ids| {
+            ids.iter()
+                .map(|id| validation::normalize_task_id(id))
+                .map(|id| data.resolve_id(&id).map(str::to_string).unwrap_or(id))
+                .collect()
+        });
+        let groups: Vec<_> = duplicates::duplicate_groups(&data)
+            .into_iter()
+            .filter(|group| {
+                only.as_ref().is_none_or(|only| {
+                    only.contains(&group.keep)
+                        || group.duplicates.iter().any(|id| only.contains(id))
+                })
+            })
+            .collect();
+        if groups.is_empty() {
+            return Ok(
+                "No likely duplicates found (open items with the same title and project)."
+                    .to_string(),
+            );
+        }
+
+        if action == DedupeAction::Report {
+            let mut result = format!(
+                "Found {} group(s) of likely duplicates (same title and project):\n",
+                groups.len()
+            );
+            for (n, group) in groups.iter().enumerate() {
+                result.push('\n');
+                for (i, id) in std::iter::once(&group.keep)
+                    .chain(&group.duplicates)
+                    .enumerate()
+                {
+                    let Some(nota) = data.find_by_id(id) else {
+                        continue;
+                    };
+                    let label = if i == 0 { "keep" } else { "duplicate" };
+                    let prefix = if i == 0 {
+                        format!("{}.", n + 1)
+                    } else {
+                        " ".repeat(format!("{}.", n + 1).len())
+                    };
+                    result.push_str(&format!(
+                        "{} {} [{}] {} ({:?}, created {})\n",
+                        prefix, label, nota.id, nota.title, nota.status, nota.created_at
+                    ));
+                }
+            }
+            result.push_str(
+                "\nCall dedupe(action=\"merge\") to fold the duplicates' notes and details into the kept item and trash them, or dedupe(action=\"trash\") to trash them as they are. Pass ids to act on some groups only.",
+            );
+            return Ok(result);
+        }
+
+        let today = self.clock.today();
+        let mut commit_items = Vec::new();
+        let mut lines = Vec::new();
+        for group in &groups {
+            let Some(mut keep) = data.find_by_id(&group.keep) else {
+                continue;
+            };
+            let keep_before = keep.clone();
+            for id in &group.duplicates {
+                let Some(mut duplicate) = data.find_by_id(id) else {
+                    continue;
+                };
+                if action == DedupeAction::Merge {
+                    duplicates::merge_duplicate(&mut keep, &duplicate);
+                }
+                // Items filed under the duplicate now belong to the kept item
+                for referrer in data.referrers(id) {
+                    let Some(mut nota) = data.find_by_id(&referrer) else {
+                        continue;
+                    };
+                    if nota.project.as_deref() == Some(id.as_str()) {
+                        nota.project = Some(keep.id.clone());
+                    }
+                    if nota.goal.as_deref() == Some(id.as_str()) {
+                        nota.goal = Some(keep.id.clone());
+                    }
+                    nota.updated_at = today;
+                    commit_items.push(CommitItem::from_nota(&nota, Some(nota.status.clone())));
+                    data.update(&referrer, nota);
+                }
+                let old_status = duplicate.status.clone();
+                duplicate.set_status(NotaStatus::trash, today);
+                duplicate.updated_at = today;
+                commit_items.push(CommitItem::from_nota(&duplicate, Some(old_status)));
+                data.update(id, duplicate);
+            }
+            if keep != keep_before {
+                keep.updated_at = today;
+                commit_items.push(CommitItem::from_nota(&keep, Some(keep.status.clone())));
+                let id = keep.id.clone();
+                data.update(&id, keep);
+            }
+            lines.push(format!(
+                "- {} → kept {}",
+                group.duplicates.join(", "),
+                group.keep
+            ));
+        }
+        drop(data);
+
+        let message = self.commit_message("Dedupe", &commit_items);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+
+        let verb = if action == DedupeAction::Merge {
+            "Merged"
+        } else {
+            "Trashed"
+        };
+        Ok(format!(
+            "{} the duplicates of {} group(s) (moved to trash):\n{}",
+            verb,
+            groups.len(),
+            lines.join("\n")
+        ))
+    }
+}
@@ -0,0 +1,98 @@
+//! Doable handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::gtd::Energy;
+use crate::suggest::{self, Constraints};
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles doable - lists only the next actions whose estimate, energy and context fit the situation.
+    pub async fn handle_doable(
+        &self,
+        minutes: Option<u32>,
+        energy: Option<String>,
+        context: Option<String>,
+    ) -> McpResult<String> {
+        let energy = match energy
+            .filter(|e| !e.is_empty())
+            .map(|e| e.parse::<Energy>())
+        {
+            None => None,
+            Some(Ok(energy)) => Some(energy),
+            Some(Err(e)) => bail_public!(_, "{}", e),
+        };
+        let data = self.data.read().await;
+        let constraints = Constraints {
+            context: self.context_or_current(&data, context),
+            minutes,
+            energy,
+            now: Some(self.clock.now()),
+            escalate_after_days: None,
+        };
+        if let Some(ctx) = &constraints.context
+            && data.find_context_by_name(ctx).is_none()
+        {
+            let error_msg = validation::format_invalid_context_error(ctx, &data);
+            drop(data);
+            bail_public!(_, "{}", error_msg);
+        }
+        let doable = suggest::doable(&data, &constraints, self.clock.today());
+        drop(data);
+
+        let situation = constraints.describe();
+        let situation = if situation.is_empty() {
+            String::new()
+        } else {
+            format!(" for {}", situation)
+        };
+        let mut result = if doable.actions.is_empty() {
+            format!("Nothing is doable{}.\n", situation)
+        } else {
+            format!(
+                "Doable now{}: {} action(s)\n\n",
+                situation,
+                doable.actions.len()
+            )
+        };
+        for action in &doable.actions {
+            let nota = &action.nota;
+            let mut details = Vec::new();
+            if let Some(minutes) = nota.estimate {
+                details.push(format!("{} min", minutes));
+            }
+            if let Some(energy) = nota.energy {
+                details.push(format!("{:?} energy", energy));
+            }
+            if let Some(context) = &nota.context {
+                details.push(context.clone());
+            }
+            if let Some(due) = nota.due_date {
+                details.push(format!("due {}", due));
+            }
+            if details.is_empty() {
+                result.push_str(&format!("- [{}] {}\n", nota.id, nota.title));
+            } else {
+                result.push_str(&format!(
+                    "- [{}] {} ({})\n",
+                    nota.id,
+                    nota.title,
+                    details.join(", ")
+                ));
+            }
+        }
+        if doable.unknown > 0 {
+            result.push_str(&format!(
+                "\n{} more next action(s) have no estimate or energy to check. Add them with update(id, estimate=..., energy=...) so they can show up here.",
+                doable.unknown
+            ));
+        }
+        if doable.skipped > 0 {
+            result.push_str(&format!(
+                "\n{} next action(s) left out because they don't fit.",
+                doable.skipped
+            ));
+        }
+        Ok(result.trim_end().to_string())
+    }
+}
@@ -0,0 +1,33 @@
+//! Done log handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::report::{self, ReportRange};
+use crate::validation;
+use chrono::Duration;
+use mcp_attr::{Result as McpResult, bail_public};
+
+/// Days covered when only one end of the range is given
+const DEFAULT_DAYS: i64 = 7;
+
+impl GtdServerHandler {
+    /// Lists the items completed between `from` and `to` (inclusive), grouped per day.
+    pub async fn handle_done_log(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> McpResult<String> {
+        let end = match to.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(to) => validation::parse_date_filter(to)?,
+            None => self.clock.today(),
+        };
+        let start = match from.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(from) => validation::parse_date_filter(from)?,
+            None => end - Duration::days(DEFAULT_DAYS - 1),
+        };
+        if start > end {
+            bail_public!(_, "Invalid range: from {} is after to {}.", start, end);
+        }
+        let data = self.data.read().await;
+        Ok(report::render_done_log(&data, ReportRange { start, end }))
+    }
+}
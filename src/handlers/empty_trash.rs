@@ -1,32 +1,43 @@
 //! Empty trash handler for GTD MCP server
 
 use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
 use crate::gtd::NotaStatus;
 use mcp_attr::{Result as McpResult, bail_public};
 
 impl GtdServerHandler {
-    /// Removes all notas with status == trash and updates nota_map.
+    /// Removes all notas with status == trash.
     pub async fn handle_empty_trash(&self) -> McpResult<String> {
-        let mut data = self.data.lock().unwrap();
+        let commit_items = {
+            let mut data = self.data.write().await;
 
-        // Count and remove all trash notas
-        let count = data
-            .notas
-            .iter()
-            .filter(|n| n.status == NotaStatus::trash)
-            .count();
-        data.notas.retain(|n| n.status != NotaStatus::trash);
+            // Remove all trash notas (keeps the indexes in sync)
+            data.retain(|n| n.status != NotaStatus::trash)
+                .iter()
+                .map(|n| CommitItem::from_nota(n, Some(NotaStatus::trash)))
+                .collect::<Vec<_>>()
+        };
+        let count = commit_items.len();
 
-        // Update nota_map
-        data.nota_map
-            .retain(|_, status| *status != NotaStatus::trash);
+        let message = self.commit_message("Empty trash", &commit_items);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
 
-        drop(data);
+        let mut response = format!("Deleted {} task(s) from trash", count);
 
-        if let Err(e) = self.save_data_with_message("Empty trash") {
-            bail_public!(_, "Failed to save: {}", e);
+        // Purging completes the weekly review; mark it with a restorable tag
+        let storage = self.storage();
+        let today = self.clock.today();
+        match tokio::task::spawn_blocking(move || storage.tag_weekly_snapshot(today)).await {
+            Ok(Ok(Some(tag))) => response.push_str(&format!("\nTagged weekly snapshot: {}", tag)),
+            Ok(Ok(None)) => {}
+            Ok(Err(e)) => {
+                response.push_str(&format!("\nWarning: weekly snapshot tag failed: {}", e))
+            }
+            Err(e) => response.push_str(&format!("\nWarning: weekly snapshot tag failed: {}", e)),
         }
 
-        Ok(format!("Deleted {} task(s) from trash", count))
+        Ok(response)
     }
 }
@@ -0,0 +1,13 @@
+//! iCalendar export handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::ical;
+use mcp_attr::Result as McpResult;
+
+impl GtdServerHandler {
+    /// Renders calendar items and upcoming recurring occurrences as an .ics document.
+    pub async fn handle_export_ical(&self) -> McpResult<String> {
+        let data = self.data.read().await;
+        Ok(ical::render(&data, self.clock.today()))
+    }
+}
@@ -0,0 +1,33 @@
+//! Get handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::duplicates;
+use crate::formatting;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles get - formats a single nota with its full notes.
+    pub async fn handle_get(&self, id: String) -> McpResult<String> {
+        let id = validation::normalize_task_id(&id);
+        let data = self.data.read().await;
+        let Some(nota) = data.resolve_id(&id).and_then(|id| data.find_by_id(id)) else {
+            let hint = duplicates::did_you_mean(&duplicates::similar_ids(&data, &id));
+            drop(data);
+            bail_public!(
+                _,
+                "Item not found: Item '{}' does not exist.{} Use list() to see available items.",
+                id,
+                hint
+            );
+        };
+        Ok(formatting::format_notas(
+            &data,
+            &[&nota],
+            false,
+            None,
+            self.stale_after_days,
+            self.clock.today(),
+        ))
+    }
+}
@@ -0,0 +1,76 @@
+//! Git status handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Reports working tree state, ahead/behind counts, and last sync time of the data repository.
+    pub async fn handle_git_status(&self) -> McpResult<String> {
+        let storage = self.storage();
+        let git = storage.clone();
+        let status = match tokio::task::spawn_blocking(move || git.git_status()).await {
+            Ok(Ok(Some(status))) => status,
+            Ok(Ok(None)) => {
+                return Ok(format!(
+                    "{} is not in a Git repository - changes are only saved locally.",
+                    storage.file_path().display()
+                ));
+            }
+            Ok(Err(e)) => bail_public!(_, "Failed to read Git status: {}", e),
+            Err(e) => bail_public!(_, "Failed to read Git status: {}", e),
+        };
+
+        let mut response = format!("Branch: {}\n", status.branch);
+        if storage.git_sync_enabled() {
+            response.push_str("Git sync: enabled\n");
+        } else {
+            response
+                .push_str("Git sync: disabled (start with --sync-git to commit and push saves)\n");
+        }
+
+        if status.is_dirty() {
+            let count = status.changed_files.len();
+            response.push_str(&format!(
+                "Working tree: dirty ({} uncommitted file{})\n",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+            for path in &status.changed_files {
+                response.push_str(&format!("- {}\n", path));
+            }
+        } else {
+            response.push_str("Working tree: clean\n");
+        }
+
+        match (status.ahead, status.behind) {
+            (Some(ahead), Some(behind)) => {
+                response.push_str(&format!(
+                    "Ahead of origin/{}: {} (local commits not pushed)\n",
+                    status.branch, ahead
+                ));
+                response.push_str(&format!(
+                    "Behind origin/{}: {} (remote commits not pulled, as of last fetch)\n",
+                    status.branch, behind
+                ));
+            }
+            _ => response.push_str(&format!(
+                "Remote: origin/{} unknown (never fetched or pushed)\n",
+                status.branch
+            )),
+        }
+
+        match status.last_fetch {
+            Some(time) => response.push_str(&format!(
+                "Last sync: {}\n",
+                time.format("%Y-%m-%d %H:%M:%S")
+            )),
+            None => response.push_str("Last sync: never\n"),
+        }
+
+        if status.ahead.unwrap_or(0) > 0 || status.behind.unwrap_or(0) > 0 {
+            response.push_str("\nRun sync to exchange changes with the remote.");
+        }
+
+        Ok(response.trim_end().to_string())
+    }
+}
@@ -0,0 +1,13 @@
+//! Goals overview handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::goals;
+use mcp_attr::Result as McpResult;
+
+impl GtdServerHandler {
+    /// Handles goals_overview - shows each goal with its projects and aggregate progress.
+    pub async fn handle_goals_overview(&self) -> McpResult<String> {
+        let data = self.data.read().await;
+        Ok(goals::render(&data, self.clock.today()))
+    }
+}
@@ -0,0 +1,66 @@
+//! GitHub import handler for GTD MCP server
+
+use crate::github::{self, GitHubClient, IssueFilter};
+use crate::progress::Progress;
+use crate::{CommitItem, GtdServerHandler};
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Imports the open issues of a GitHub repository as linked items.
+    pub async fn handle_import_github(
+        &self,
+        repo: String,
+        filter: Option<String>,
+        progress: Progress<'_>,
+    ) -> McpResult<String> {
+        const STEPS: usize = 2;
+        let filter = match filter.as_deref().map(str::parse::<IssueFilter>) {
+            None => IssueFilter::default(),
+            Some(Ok(filter)) => filter,
+            Some(Err(e)) => bail_public!(_, "{}", e),
+        };
+        let client = match GitHubClient::from_env() {
+            Ok(client) => client.with_cancellation(progress.cancellation()),
+            Err(e) => bail_public!(_, "{}", e),
+        };
+        let fetch_repo = repo.clone();
+        let fetch = tokio::task::spawn_blocking(move || {
+            let login = client.login()?;
+            let issues = client.issues(&fetch_repo, filter, &login)?;
+            anyhow::Ok((login, issues))
+        });
+        let fetched = progress
+            .step(0, STEPS, &format!("Fetching issues of {}", repo), fetch)
+            .await;
+        let (login, issues) = match fetched {
+            Ok(Ok(fetched)) => fetched,
+            Ok(Err(e)) => bail_public!(_, "GitHub import failed: {}", e),
+            Err(e) => bail_public!(_, "GitHub import failed: {}", e),
+        };
+
+        let mut data = self.data.write().await;
+        let summary = github::import(&mut data, &repo, &issues, &login, self.clock.today());
+        let commit_items: Vec<CommitItem> = summary
+            .added
+            .iter()
+            .chain(&summary.updated)
+            .filter_map(|id| data.find_by_id(id))
+            .map(|nota| CommitItem::from_nota(&nota, None))
+            .collect();
+        drop(data);
+
+        if !commit_items.is_empty() {
+            let message = self.commit_message("Import", &commit_items);
+            let save = self.save_data_with_message(&message);
+            if let Err(e) = progress.step(1, STEPS, "Saving imported items", save).await {
+                bail_public!(_, "Failed to save data: {}", e);
+            }
+        }
+        Ok(format!(
+            "Imported {} open issue(s) of {}: {}",
+            issues.len(),
+            repo,
+            summary
+        ))
+    }
+}
@@ -1,13 +1,14 @@
 //! Inbox handler for GTD MCP server
 
 use crate::GtdServerHandler;
-use crate::gtd::{self, NotaStatus};
-use crate::validation;
+use crate::gtd::{NotaStatus, RecurrencePattern};
+use crate::handlers::public_error;
+use crate::service::NewItem;
 use chrono::NaiveDate;
 use mcp_attr::{Result as McpResult, bail_public};
 
 impl GtdServerHandler {
-    /// Handles inbox item creation - validates ID, parses status, and creates new nota.
+    /// Handles inbox item creation - parses status, date and recurrence, and creates the nota through the service.
     #[allow(clippy::too_many_arguments)]
     pub async fn handle_inbox(
         &self,
@@ -21,160 +22,69 @@ impl GtdServerHandler {
         recurrence: Option<String>,
         recurrence_config: Option<String>,
     ) -> McpResult<String> {
-        let mut data = self.data.lock().unwrap();
-
-        // Check for duplicate ID across all notas
-        if data.nota_map.contains_key(&id) {
-            let existing_status = data.nota_map[&id].clone();
-            drop(data);
-            bail_public!(
-                _,
-                "Duplicate ID error: ID '{}' already exists (status: {:?}). Each item must have a unique ID. Please choose a different ID.",
-                id,
-                existing_status
-            );
-        }
-
         // Parse status
-        let nota_status: NotaStatus = match status.parse() {
-            Ok(s) => s,
-            Err(_) => {
-                drop(data);
-                bail_public!(
-                    _,
-                    "Invalid status '{}'. Valid statuses: inbox, next_action, waiting_for, later, calendar, someday, done, reference, trash, project, context",
-                    status
-                );
-            }
-        };
-
-        // Validate calendar status has start_date
-        if nota_status == NotaStatus::calendar && start_date.is_none() {
-            drop(data);
+        let Ok(status) = status.parse::<NotaStatus>() else {
             bail_public!(
                 _,
-                "Calendar status validation failed: status=calendar requires start_date parameter. Please provide a date in YYYY-MM-DD format."
+                "Invalid status '{}'. Valid statuses: inbox, next_action, waiting_for, later, calendar, someday, done, reference, trash, project, context, goal, template",
+                status
             );
-        }
+        };
 
         // Parse start_date if provided
-        let parsed_start_date = if let Some(ref date_str) = start_date {
-            match NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        let start_date = match start_date {
+            Some(date_str) => match NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
                 Ok(d) => Some(d),
-                Err(_) => {
-                    drop(data);
-                    bail_public!(
-                        _,
-                        "Invalid date format '{}'. Use YYYY-MM-DD (e.g., '2025-03-15')",
-                        date_str
-                    );
-                }
-            }
-        } else {
-            None
+                Err(_) => bail_public!(
+                    _,
+                    "Invalid date format '{}'. Use YYYY-MM-DD (e.g., '2025-03-15')",
+                    date_str
+                ),
+            },
+            None => None,
         };
 
-        // Validate project reference if provided
-        if let Some(ref proj_id) = project
-            && data.find_project_by_id(proj_id).is_none()
-        {
-            let error_msg = validation::format_invalid_project_error(proj_id, &data);
-            drop(data);
-            bail_public!(_, "{}", error_msg);
-        }
-
-        // Validate context reference if provided
-        if let Some(ref ctx_name) = context
-            && data.find_context_by_name(ctx_name).is_none()
-        {
-            let error_msg = validation::format_invalid_context_error(ctx_name, &data);
-            drop(data);
-            bail_public!(_, "{}", error_msg);
-        }
-
         // Parse recurrence pattern if provided
-        let recurrence_pattern = if let Some(ref recurrence_str) = recurrence {
-            match recurrence_str.as_str() {
-                "daily" => Some(gtd::RecurrencePattern::daily),
-                "weekly" => Some(gtd::RecurrencePattern::weekly),
-                "monthly" => Some(gtd::RecurrencePattern::monthly),
-                "yearly" => Some(gtd::RecurrencePattern::yearly),
-                _ => {
-                    drop(data);
-                    bail_public!(
-                        _,
-                        "Invalid recurrence pattern '{}'. Valid patterns: daily, weekly, monthly, yearly",
-                        recurrence_str
-                    );
-                }
-            }
-        } else {
-            None
-        };
-
-        // Validate recurrence configuration if recurrence pattern is provided
-        if let Some(ref pattern) = recurrence_pattern
-            && recurrence_config.is_none()
-        {
-            // Only weekly, monthly, and yearly require config
-            match pattern {
-                gtd::RecurrencePattern::weekly => {
-                    drop(data);
-                    bail_public!(
-                        _,
-                        "Recurrence pattern 'weekly' requires recurrence_config with weekday names (e.g., \"Monday,Wednesday,Friday\")"
-                    );
-                }
-                gtd::RecurrencePattern::monthly => {
-                    drop(data);
-                    bail_public!(
-                        _,
-                        "Recurrence pattern 'monthly' requires recurrence_config with day numbers (e.g., \"1,15,25\")"
-                    );
-                }
-                gtd::RecurrencePattern::yearly => {
-                    drop(data);
-                    bail_public!(
-                        _,
-                        "Recurrence pattern 'yearly' requires recurrence_config with month-day pairs (e.g., \"1-1,12-25\")"
-                    );
-                }
-                gtd::RecurrencePattern::daily => {} // Daily doesn't need config
-            }
-        }
-
-        let today = gtd::local_date_today();
-        let nota = gtd::Nota {
-            id: id.clone(),
-            title: title.clone(),
-            status: nota_status.clone(),
-            project,
-            context,
-            notes,
-            start_date: parsed_start_date,
-            created_at: today,
-            updated_at: today,
-            recurrence_pattern,
-            recurrence_config,
+        let recurrence = match recurrence.as_deref() {
+            None => None,
+            Some("daily") => Some(RecurrencePattern::daily),
+            Some("weekly") => Some(RecurrencePattern::weekly),
+            Some("monthly") => Some(RecurrencePattern::monthly),
+            Some("yearly") => Some(RecurrencePattern::yearly),
+            Some(other) => bail_public!(
+                _,
+                "Invalid recurrence pattern '{}'. Valid patterns: daily, weekly, monthly, yearly",
+                other
+            ),
         };
 
-        data.add(nota);
-        drop(data);
+        let created = self
+            .service()
+            .inbox(NewItem {
+                project,
+                context,
+                notes,
+                start_date,
+                recurrence,
+                recurrence_config,
+                ..NewItem::new(id, title, status)
+            })
+            .await
+            .map_err(public_error)?;
 
-        if let Err(e) = self.save_data_with_message(&format!("Add item {}", id)) {
-            bail_public!(_, "Failed to save: {}", e);
-        }
-
-        Ok(format!(
+        let mut response = format!(
             "Item created with ID: {} (type: {})",
-            id,
-            if nota_status == NotaStatus::context {
-                "context"
-            } else if nota_status == NotaStatus::project {
-                "project"
-            } else {
-                "task"
+            created.nota.id,
+            match created.nota.status {
+                NotaStatus::context => "context",
+                NotaStatus::project => "project",
+                _ => "task",
             }
-        ))
+        );
+        for warning in created.warnings {
+            response.push('\n');
+            response.push_str(&warning);
+        }
+        Ok(response)
     }
 }
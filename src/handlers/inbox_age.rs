@@ -0,0 +1,13 @@
+//! Inbox age handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::report;
+use mcp_attr::Result as McpResult;
+
+impl GtdServerHandler {
+    /// Buckets the inbox items by days since capture and calls out the oldest.
+    pub async fn handle_inbox_age(&self) -> McpResult<String> {
+        let data = self.data.read().await;
+        Ok(report::render_inbox_age(&data, self.clock.today()))
+    }
+}
@@ -0,0 +1,78 @@
+//! Instantiate template handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::templates;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles instantiate_template - creates one next action per checklist item.
+    pub async fn handle_instantiate_template(
+        &self,
+        template_id: String,
+        new_id: String,
+    ) -> McpResult<String> {
+        let template_id = validation::normalize_task_id(&template_id);
+        let new_id = validation::normalize_task_id(&new_id);
+        if new_id.is_empty() {
+            bail_public!(
+                _,
+                "new_id is empty. Give a short ID to prefix the new tasks with (e.g. \"trip-paris\")."
+            );
+        }
+
+        let mut data = self.data.write().await;
+        let Some(template) = data
+            .resolve_id(&template_id)
+            .and_then(|id| data.find_by_id(id))
+        else {
+            drop(data);
+            bail_public!(
+                _,
+                "Template '{}' not found. Use list(status=\"template\") to see the templates.",
+                template_id
+            );
+        };
+        if !template.is_template() {
+            drop(data);
+            bail_public!(
+                _,
+                "'{}' is not a template (status: {:?}). Create templates with inbox(status=\"template\") and the checklist as '- item' lines in the notes.",
+                template.id,
+                template.status
+            );
+        }
+        if templates::checklist(template.notes.as_deref().unwrap_or("")).is_empty() {
+            drop(data);
+            bail_public!(
+                _,
+                "Template '{}' has no checklist items. Write them as '- item' lines in its notes.",
+                template.id
+            );
+        }
+
+        let created = templates::instantiate(&mut data, &template, &new_id, self.clock.today());
+        drop(data);
+
+        let commit_items: Vec<CommitItem> = created
+            .iter()
+            .map(|nota| CommitItem::from_nota(nota, None))
+            .collect();
+        let message = self.commit_message("Instantiate template", &commit_items);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+
+        let mut response = format!(
+            "Created {} task{} from template '{}':\n",
+            created.len(),
+            if created.len() == 1 { "" } else { "s" },
+            template.id
+        );
+        for nota in &created {
+            response.push_str(&format!("- [{}] {}\n", nota.id, nota.title));
+        }
+        Ok(response.trim_end().to_string())
+    }
+}
@@ -2,11 +2,13 @@
 
 use crate::GtdServerHandler;
 use crate::formatting;
+use crate::service::ListQuery;
 use crate::validation;
 use mcp_attr::Result as McpResult;
 
 impl GtdServerHandler {
-    /// Handles list/filter operations - applies filters and formats results for display.
+    /// Handles list/filter operations - parses the filters, applies them through the service and formats results for display.
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_list(
         &self,
         status: Option<String>,
@@ -15,45 +17,39 @@ impl GtdServerHandler {
         keyword: Option<String>,
         project: Option<String>,
         context: Option<String>,
+        max_notes_chars: Option<u32>,
     ) -> McpResult<String> {
-        // Parse and validate status filter
-        let status_filter = if let Some(ref status_str) = status {
-            Some(validation::parse_status_filter(status_str)?)
-        } else {
-            None
+        let query = ListQuery {
+            status: status
+                .as_deref()
+                .map(validation::parse_status_filter)
+                .transpose()?,
+            date: date
+                .as_deref()
+                .map(validation::parse_date_filter)
+                .transpose()?,
+            keyword,
+            project,
+            context,
         };
 
-        // Parse and validate date filter
-        let date_filter = if let Some(ref date_str) = date {
-            Some(validation::parse_date_filter(date_str)?)
-        } else {
-            None
-        };
-
-        // Get initial list of notas filtered by status
-        let data = self.data.lock().unwrap();
-        let mut notas = data.list_all(status_filter);
-        drop(data);
-
-        // Apply additional filters in sequence
-        if let Some(filter_date) = date_filter {
-            formatting::apply_date_filter(&mut notas, filter_date);
-        }
-
-        if let Some(ref keyword_filter) = keyword {
-            formatting::apply_keyword_filter(&mut notas, keyword_filter);
-        }
-
-        if let Some(ref project_filter) = project {
-            formatting::apply_project_filter(&mut notas, project_filter);
-        }
-
-        if let Some(ref context_filter) = context {
-            formatting::apply_context_filter(&mut notas, context_filter);
-        }
-
-        // Format and return results
-        let exclude_notes_flag = exclude_notes.unwrap_or(false);
-        Ok(formatting::format_notas(notas, exclude_notes_flag))
+        // Filter and format while borrowing the data, so no nota is cloned
+        let data = self.data.read().await;
+        let (notas, current_context) = self.service().filter(&data, query);
+        let listing = formatting::format_notas(
+            &data,
+            &notas,
+            exclude_notes.unwrap_or(false),
+            max_notes_chars.map(|max| max as usize),
+            self.stale_after_days,
+            self.clock.today(),
+        );
+        Ok(match current_context {
+            Some(context) => format!(
+                "Current context: {} (pass context=\"\" to see every context)\n\n{}",
+                context, listing
+            ),
+            None => listing,
+        })
     }
 }
@@ -0,0 +1,68 @@
+//! Merge file handler for GTD MCP server
+
+use crate::merge_file::{self, MergeStrategy};
+use crate::progress::Progress;
+use crate::{CommitItem, GtdServerHandler, Storage};
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Merges another GTD data file into the active one, resolving ID collisions by the strategy.
+    pub async fn handle_merge_file(
+        &self,
+        path: String,
+        strategy: Option<String>,
+        progress: Progress<'_>,
+    ) -> McpResult<String> {
+        const STEPS: usize = 2;
+        let strategy = match strategy.as_deref().map(str::parse::<MergeStrategy>) {
+            None => MergeStrategy::default(),
+            Some(Ok(strategy)) => strategy,
+            Some(Err(e)) => bail_public!(_, "{}", e),
+        };
+        // Relative paths are resolved next to the active data file
+        let mut other_path = std::path::PathBuf::from(&path);
+        if other_path.is_relative()
+            && let Some(dir) = self.storage().file_path().parent()
+        {
+            other_path = dir.join(other_path);
+        }
+        if !other_path.exists() {
+            bail_public!(_, "Merge failed: {} does not exist", other_path.display());
+        }
+        let load_path = other_path.clone();
+        let load = tokio::task::spawn_blocking(move || Storage::new(load_path, false).load_local());
+        let reading = format!("Reading {}", other_path.display());
+        let other = match progress.step(0, STEPS, &reading, load).await {
+            Ok(Ok(other)) => other,
+            Ok(Err(e)) => bail_public!(_, "Merge failed: {}", e),
+            Err(e) => bail_public!(_, "Merge failed: {}", e),
+        };
+
+        let mut data = self.data.write().await;
+        let summary = match merge_file::merge(&mut data, &other, strategy) {
+            Ok(summary) => summary,
+            Err(e) => {
+                drop(data);
+                bail_public!(_, "{}", e);
+            }
+        };
+        let commit_items: Vec<CommitItem> = summary
+            .added
+            .iter()
+            .chain(summary.renamed.iter().map(|(_, to)| to))
+            .chain(&summary.replaced)
+            .filter_map(|id| data.find_by_id(id))
+            .map(|nota| CommitItem::from_nota(&nota, None))
+            .collect();
+        drop(data);
+
+        if !commit_items.is_empty() {
+            let message = self.commit_message("Merge", &commit_items);
+            let save = self.save_data_with_message(&message);
+            if let Err(e) = progress.step(1, STEPS, "Saving merged items", save).await {
+                bail_public!(_, "Failed to save data: {}", e);
+            }
+        }
+        Ok(format!("Merged {}: {}", other_path.display(), summary))
+    }
+}
@@ -3,8 +3,53 @@
 //! This module contains the implementation of all MCP tool handlers.
 //! Each handler is in a separate file for better organization.
 
+pub mod backup;
+pub mod board;
+pub mod capture;
+pub mod capture_email;
 pub mod change_status;
+pub mod dedupe;
+pub mod doable;
+pub mod done_log;
 pub mod empty_trash;
+pub mod export_ical;
+pub mod get;
+pub mod git_status;
+pub mod goals_overview;
+pub mod import_github;
 pub mod inbox;
+pub mod inbox_age;
+pub mod instantiate_template;
 pub mod list;
+pub mod merge_file;
+pub mod new_project_from_template;
+pub mod postpone;
+pub mod process_inbox;
+pub mod recurrence;
+pub mod reload;
+pub mod report;
+pub mod reschedule;
+pub mod resources;
+pub mod restore;
+pub mod review_someday;
+pub mod set_availability;
+pub mod set_current_context;
+pub mod set_escalation;
+pub mod set_focus;
+pub mod skip_occurrence;
+pub mod suggest;
+pub mod switch_workspace;
+pub mod sync;
+pub mod sync_caldav;
+pub mod sync_status;
+pub mod today;
+pub mod transaction;
+pub mod tree;
+pub mod trends;
 pub mod update;
+pub mod waiting_report;
+
+/// An MCP error whose message reaches the client (what `bail_public!` returns)
+pub(crate) fn public_error(error: impl std::fmt::Display) -> mcp_attr::Error {
+    mcp_attr::Error::new(mcp_attr::ErrorCode::INTERNAL_ERROR).with_message(error, true)
+}
@@ -0,0 +1,129 @@
+//! New project from template handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::templates;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+use std::collections::HashMap;
+
+impl GtdServerHandler {
+    /// Handles new_project_from_template - creates a project and its tasks from a template.
+    pub async fn handle_new_project_from_template(
+        &self,
+        template: String,
+        project_id: String,
+        params: Option<HashMap<String, String>>,
+    ) -> McpResult<String> {
+        let template_id = validation::normalize_task_id(&template);
+        let project_id = validation::normalize_task_id(&project_id);
+        let params = params.unwrap_or_default();
+        if project_id.is_empty() {
+            bail_public!(
+                _,
+                "project_id is empty. Give the new project a short ID (e.g. \"onboarding-acme\")."
+            );
+        }
+        if let Some(policy) = &self.id_policy
+            && let Err(rule) = policy.check(&project_id)
+        {
+            bail_public!(
+                _,
+                "ID policy error: {}. Please choose an ID that follows the rule.",
+                rule
+            );
+        }
+
+        let mut data = self.data.write().await;
+        if let Some(existing_id) = data.resolve_id(&project_id) {
+            let existing_id = existing_id.to_string();
+            drop(data);
+            bail_public!(
+                _,
+                "Duplicate ID error: ID '{}' already exists. Please choose a different project_id.",
+                existing_id
+            );
+        }
+        let Some(template) = data
+            .resolve_id(&template_id)
+            .and_then(|id| data.find_by_id(id))
+        else {
+            drop(data);
+            bail_public!(
+                _,
+                "Template '{}' not found. Use list(status=\"template\") to see the templates.",
+                template_id
+            );
+        };
+        if !template.is_template() {
+            drop(data);
+            bail_public!(
+                _,
+                "'{}' is not a template (status: {:?}). Create templates with inbox(status=\"template\") and the checklist as '- item' lines in the notes.",
+                template.id,
+                template.status
+            );
+        }
+        let notes = template.notes.as_deref().unwrap_or("");
+        if templates::checklist(notes).is_empty() {
+            drop(data);
+            bail_public!(
+                _,
+                "Template '{}' has no checklist items. Write them as '- item' lines in its notes.",
+                template.id
+            );
+        }
+        let missing: Vec<String> =
+            templates::placeholders(&format!("{}\n{}", template.title, notes))
+                .into_iter()
+                .filter(|name| !params.contains_key(name))
+                .collect();
+        if !missing.is_empty() {
+            drop(data);
+            bail_public!(
+                _,
+                "Template '{}' needs a value for {}. Pass them in params (e.g. params={{\"{}\": \"...\"}}).",
+                template.id,
+                missing
+                    .iter()
+                    .map(|name| format!("{{{}}}", name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                missing[0]
+            );
+        }
+
+        let created = templates::new_project(
+            &mut data,
+            &template,
+            &project_id,
+            &params,
+            self.clock.today(),
+        );
+        drop(data);
+
+        let commit_items: Vec<CommitItem> = created
+            .iter()
+            .map(|nota| CommitItem::from_nota(nota, None))
+            .collect();
+        let message = self.commit_message("New project from template", &commit_items);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+
+        // The project comes first, followed by its tasks
+        let (project, tasks) = (&created[0], &created[1..]);
+        let mut response = format!(
+            "Created project [{}] {} from template '{}' with {} task{}:\n",
+            project.id,
+            project.title,
+            template.id,
+            tasks.len(),
+            if tasks.len() == 1 { "" } else { "s" }
+        );
+        for nota in tasks {
+            response.push_str(&format!("- [{}] {}\n", nota.id, nota.title));
+        }
+        Ok(response.trim_end().to_string())
+    }
+}
@@ -0,0 +1,75 @@
+//! Postpone handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::gtd::NotaStatus;
+use crate::validation;
+use chrono::Duration;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles postpone - moves an item's start_date N days later (from today when it has none).
+    pub async fn handle_postpone(&self, id: String, days: u32) -> McpResult<String> {
+        if days == 0 {
+            bail_public!(_, "days must be at least 1.");
+        }
+        let id = validation::normalize_task_id(&id);
+        let today = self.clock.today();
+
+        let mut data = self.data.write().await;
+        let Some(mut nota) = data.resolve_id(&id).and_then(|id| data.find_by_id(id)) else {
+            drop(data);
+            bail_public!(_, "Item '{}' not found.", id);
+        };
+        if matches!(
+            nota.status,
+            NotaStatus::done
+                | NotaStatus::trash
+                | NotaStatus::context
+                | NotaStatus::goal
+                | NotaStatus::template
+        ) {
+            drop(data);
+            bail_public!(
+                _,
+                "Cannot postpone '{}': it is {:?}. Only open items can be postponed.",
+                nota.id,
+                nota.status
+            );
+        }
+        let old_date = nota.start_date;
+        let Some(new_date) = Duration::try_days(i64::from(days))
+            .and_then(|shift| old_date.unwrap_or(today).checked_add_signed(shift))
+        else {
+            drop(data);
+            bail_public!(
+                _,
+                "Cannot postpone '{}' by {} day(s): the new date is out of range.",
+                nota.id,
+                days
+            );
+        };
+        nota.start_date = Some(new_date);
+        nota.updated_at = today;
+        let commit_item = CommitItem::from_nota(&nota, Some(nota.status.clone()));
+        let response = match old_date {
+            Some(old_date) => format!(
+                "Postponed [{}] {} by {} day(s): {} → {}",
+                nota.id, nota.title, days, old_date, new_date
+            ),
+            None => format!(
+                "Postponed [{}] {} by {} day(s): start date set to {}",
+                nota.id, nota.title, days, new_date
+            ),
+        };
+        let nota_id = nota.id.clone();
+        data.update(&nota_id, nota);
+        drop(data);
+
+        let message = self.commit_message("Postpone", &[commit_item]);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+        Ok(response)
+    }
+}
@@ -0,0 +1,67 @@
+//! Process inbox handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::clarify::{self, Clarified};
+use crate::progress::Progress;
+use mcp_attr::server::RequestContext;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Lists the inbox items to clarify, oldest first, with the client's LLM
+    /// proposals when `assist` is set.
+    pub async fn handle_process_inbox(
+        &self,
+        limit: Option<usize>,
+        assist: Option<bool>,
+        sampling: Option<&RequestContext>,
+        progress: Progress<'_>,
+    ) -> McpResult<String> {
+        let limit = limit.unwrap_or(clarify::DEFAULT_LIMIT);
+        if limit == 0 || limit > clarify::MAX_LIMIT {
+            bail_public!(_, "limit must be between 1 and {}", clarify::MAX_LIMIT);
+        }
+        let sampling = match (assist.unwrap_or(false), sampling) {
+            (false, _) => None,
+            (true, Some(cx)) if cx.client_capabilities().sampling.is_some() => Some(cx),
+            (true, _) => bail_public!(
+                _,
+                "This client does not support sampling, so its model cannot propose clarifications. Call process_inbox without assist and clarify the items in the conversation."
+            ),
+        };
+
+        let data = self.data.read().await;
+        let inbox_size = clarify::inbox_items(&data, usize::MAX).len();
+        let notas = clarify::inbox_items(&data, limit);
+        let contexts = clarify::context_names(&data);
+        drop(data);
+
+        let total = notas.len();
+        let mut items = Vec::with_capacity(total);
+        for (done, nota) in notas.into_iter().enumerate() {
+            let proposal = match sampling {
+                None => None,
+                Some(cx) => {
+                    let answer = progress
+                        .step(
+                            done,
+                            total,
+                            &format!("Clarifying {}", nota.id),
+                            cx.sampling_create_message(clarify::request(&nota, &contexts)),
+                        )
+                        .await;
+                    Some(match answer {
+                        Ok(result) => clarify::parse(&result),
+                        Err(e) => Err(format!("sampling failed: {}", e)),
+                    })
+                }
+            };
+            items.push(Clarified { nota, proposal });
+        }
+        Ok(clarify::render(
+            &items,
+            inbox_size,
+            &contexts,
+            self.clock.today(),
+        ))
+    }
+}
@@ -0,0 +1,62 @@
+//! Pause/resume recurrence handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles pause_recurrence/resume_recurrence - sets the paused flag of a recurring item.
+    pub async fn handle_set_recurrence_paused(
+        &self,
+        id: String,
+        paused: bool,
+    ) -> McpResult<String> {
+        let id = validation::normalize_task_id(&id);
+        let mut data = self.data.write().await;
+        let Some(mut nota) = data.resolve_id(&id).and_then(|id| data.find_by_id(id)) else {
+            drop(data);
+            bail_public!(_, "Item '{}' not found.", id);
+        };
+        if !nota.is_recurring() {
+            drop(data);
+            bail_public!(_, "'{}' is not recurring.", nota.id);
+        }
+        if nota.paused == paused {
+            let state = if paused {
+                "already paused"
+            } else {
+                "not paused"
+            };
+            return Ok(format!("[{}] {} is {}.", nota.id, nota.title, state));
+        }
+        nota.paused = paused;
+        nota.updated_at = self.clock.today();
+        let commit_item = CommitItem::from_nota(&nota, Some(nota.status.clone()));
+        let response = if paused {
+            format!(
+                "Paused [{}] {}: completing it creates no next occurrence and the tickler skips it until resume_recurrence.",
+                nota.id, nota.title
+            )
+        } else {
+            let when = nota
+                .start_date
+                .map(|date| format!(" Start date: {}.", date))
+                .unwrap_or_default();
+            format!(
+                "Resumed [{}] {}: the series continues.{}",
+                nota.id, nota.title, when
+            )
+        };
+        let nota_id = nota.id.clone();
+        data.update(&nota_id, nota);
+        drop(data);
+
+        let action = if paused { "Pause" } else { "Resume" };
+        let message = self.commit_message(action, &[commit_item]);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+        Ok(response)
+    }
+}
@@ -0,0 +1,45 @@
+//! Reload handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Replaces the in-memory data with the data file on disk, dropping unsaved changes.
+    pub async fn handle_reload(&self) -> McpResult<String> {
+        // Hold the write queue so no save interleaves with the reload
+        let _queue = self.write_queue.lock().await;
+
+        let storage = self.storage();
+        let path = storage.file_path().display().to_string();
+        let data = match tokio::task::spawn_blocking(move || {
+            storage.discard_pending()?;
+            storage.load_local()
+        })
+        .await
+        {
+            Ok(Ok(data)) => data,
+            Ok(Err(e)) => bail_public!(_, "Failed to reload {}: {}", path, e),
+            Err(e) => bail_public!(_, "Failed to reload {}: {}", path, e),
+        };
+
+        let mut current = self.data.write().await;
+        let content = crate::Storage::serialize(&current).unwrap_or_default();
+        let discarded = current.is_dirty(&content);
+        let count = data.iter().count();
+        // Changes deferred by an open transaction were just dropped with the
+        // data; an abort now returns to the reloaded file
+        if let Some(transaction) = self.transaction.lock().unwrap().as_mut() {
+            transaction.messages.clear();
+            transaction.snapshot = data.clone();
+        }
+        self.adopt_saved(&data);
+        *current = data;
+
+        let mut response = format!("Reloaded {} items from {}.", count, path);
+        if discarded {
+            response
+                .push_str(" Unsaved changes were discarded; redo them if they are still needed.");
+        }
+        Ok(response)
+    }
+}
@@ -0,0 +1,18 @@
+//! Report handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::report::{self, ReportRange};
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Renders a Markdown progress report for the given range (default: the last 7 days).
+    pub async fn handle_report(&self, range: Option<String>) -> McpResult<String> {
+        let range = range.unwrap_or_else(|| "week".to_string());
+        let range = match ReportRange::parse(&range, self.clock.today()) {
+            Ok(range) => range,
+            Err(e) => bail_public!(_, "{}", e),
+        };
+        let data = self.data.read().await;
+        Ok(report::render(&data, range))
+    }
+}
@@ -0,0 +1,100 @@
+//! Reschedule handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::gtd::NotaStatus;
+use crate::validation;
+use chrono::Duration;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles reschedule - moves calendar items to a date or by a number of days, all or nothing.
+    pub async fn handle_reschedule(
+        &self,
+        ids: Vec<String>,
+        to_date: Option<String>,
+        shift_days: Option<i64>,
+    ) -> McpResult<String> {
+        let to_date = to_date.filter(|d| !d.trim().is_empty());
+        let to_date = match (&to_date, shift_days) {
+            (Some(date), None) => Some(validation::parse_date_filter(date.trim())?),
+            (None, Some(0)) => bail_public!(_, "shift_days must not be 0."),
+            (None, Some(_)) => None,
+            _ => bail_public!(
+                _,
+                "Give either to_date (YYYY-MM-DD) or shift_days (e.g. 7 or -2), not both."
+            ),
+        };
+        let mut ids: Vec<String> = ids
+            .iter()
+            .map(|id| validation::normalize_task_id(id))
+            .filter(|id| !id.is_empty())
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        ids.retain(|id| seen.insert(id.clone()));
+        if ids.is_empty() {
+            bail_public!(_, "No IDs given. Pass the calendar items to reschedule.");
+        }
+
+        let today = self.clock.today();
+        let mut data = self.data.write().await;
+        let mut errors = Vec::new();
+        let mut changes = Vec::new();
+        for id in &ids {
+            let Some(mut nota) = data.resolve_id(id).and_then(|id| data.find_by_id(id)) else {
+                errors.push(format!("{}: not found", id));
+                continue;
+            };
+            if nota.status != NotaStatus::calendar {
+                errors.push(format!(
+                    "{}: not a calendar item (status: {:?})",
+                    nota.id, nota.status
+                ));
+                continue;
+            }
+            let Some(old_date) = nota.start_date else {
+                errors.push(format!("{}: has no start_date", nota.id));
+                continue;
+            };
+            let new_date = match to_date {
+                Some(date) => Some(date),
+                None => shift_days.and_then(|days| {
+                    Duration::try_days(days).and_then(|shift| old_date.checked_add_signed(shift))
+                }),
+            };
+            let Some(new_date) = new_date else {
+                errors.push(format!("{}: the new date is out of range", nota.id));
+                continue;
+            };
+            nota.start_date = Some(new_date);
+            nota.updated_at = today;
+            changes.push((old_date, nota));
+        }
+        if !errors.is_empty() {
+            drop(data);
+            bail_public!(_, "Nothing rescheduled:\n- {}", errors.join("\n- "));
+        }
+
+        let mut commit_items = Vec::new();
+        let mut response = format!("Rescheduled {} calendar item(s):\n", changes.len());
+        for (old_date, nota) in changes {
+            response.push_str(&format!(
+                "- [{}] {}: {} → {}\n",
+                nota.id,
+                nota.title,
+                old_date,
+                nota.start_date.unwrap_or(old_date)
+            ));
+            commit_items.push(CommitItem::from_nota(&nota, Some(nota.status.clone())));
+            let id = nota.id.clone();
+            data.update(&id, nota);
+        }
+        drop(data);
+
+        let message = self.commit_message("Reschedule", &commit_items);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+        Ok(response.trim_end().to_string())
+    }
+}
@@ -0,0 +1,86 @@
+//! Resource handlers for GTD MCP server
+//!
+//! Resource-oriented clients read slices of the data instead of calling
+//! `list`: `gtd://list/{status}` for the items of one status and
+//! `gtd://project/{id}/tasks` for the items of one project. Both render the
+//! items the way `list` does, in every context.
+
+use crate::gtd::NotaStatus;
+use crate::{GtdServerHandler, formatting, validation};
+use mcp_attr::schema::{ListResourcesResult, Resource};
+use mcp_attr::{Result as McpResult, bail_public};
+
+/// Statuses listed by `resources/list`, in workflow order
+const STATUSES: [&str; 13] = [
+    "inbox",
+    "next_action",
+    "waiting_for",
+    "later",
+    "calendar",
+    "someday",
+    "done",
+    "reference",
+    "project",
+    "context",
+    "goal",
+    "template",
+    "trash",
+];
+
+impl GtdServerHandler {
+    /// Renders the items of one status.
+    pub async fn handle_status_resource(&self, status: String) -> McpResult<String> {
+        let status = validation::parse_status_filter(&status)?;
+        let data = self.data.read().await;
+        let notas = data.list_filtered(Some(status), None, None);
+        Ok(formatting::format_notas(
+            &data,
+            &notas,
+            false,
+            None,
+            self.stale_after_days,
+            self.clock.today(),
+        ))
+    }
+
+    /// Renders the items of one project.
+    pub async fn handle_project_tasks_resource(&self, id: String) -> McpResult<String> {
+        let data = self.data.read().await;
+        if data.find_project_by_id(&id).is_none() {
+            bail_public!(_, "Project '{}' not found", id);
+        }
+        let notas = data.list_filtered(None, Some(&id), None);
+        Ok(formatting::format_notas(
+            &data,
+            &notas,
+            false,
+            None,
+            self.stale_after_days,
+            self.clock.today(),
+        ))
+    }
+
+    /// Lists a resource per status and per project.
+    pub async fn handle_resources_list(&self) -> McpResult<ListResourcesResult> {
+        let data = self.data.read().await;
+        let mut resources: Vec<Resource> = STATUSES
+            .iter()
+            .map(|status| {
+                Resource::new(&format!("gtd://list/{}", status), status)
+                    .with_description(&format!("Items with status {}", status))
+                    .with_mime_type("text/plain")
+            })
+            .collect();
+        let mut projects: Vec<_> = data
+            .iter()
+            .filter(|nota| nota.status == NotaStatus::project)
+            .collect();
+        projects.sort_by(|a, b| a.id.cmp(&b.id));
+        resources.extend(projects.into_iter().map(|project| {
+            Resource::new(&format!("gtd://project/{}/tasks", project.id), &project.id)
+                .with_description(&format!("Items of project {}", project.title))
+                .with_mime_type("text/plain")
+        }));
+        Ok(resources.into())
+    }
+}
@@ -0,0 +1,111 @@
+//! Restore handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::gtd::NotaStatus;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles restore - moves trashed items back to the status they had before.
+    ///
+    /// Items trashed before the previous status was recorded go back to inbox.
+    pub async fn handle_restore(&self, ids: Vec<String>) -> McpResult<String> {
+        if ids.is_empty() {
+            bail_public!(_, "No IDs provided. Please specify at least one item ID.");
+        }
+
+        let today = self.clock.today();
+        let mut data = self.data.write().await;
+        let mut restored = Vec::new();
+        let mut failures = Vec::new();
+        let mut commit_items = Vec::new();
+        for id in ids.iter().map(|id| validation::normalize_task_id(id)) {
+            let Some(mut nota) = data.resolve_id(&id).and_then(|id| data.find_by_id(id)) else {
+                failures.push(format!("{}: not found", id));
+                continue;
+            };
+            if nota.status != NotaStatus::trash {
+                failures.push(format!(
+                    "{}: not in trash (status: {:?})",
+                    nota.id, nota.status
+                ));
+                continue;
+            }
+            let recorded = nota.previous_status.is_some();
+            let status = nota.previous_status.clone().unwrap_or(NotaStatus::inbox);
+            if status == NotaStatus::calendar && nota.start_date.is_none() {
+                failures.push(format!(
+                    "{}: was a calendar item but has no start_date (use change_status with a start_date)",
+                    nota.id
+                ));
+                continue;
+            }
+
+            // Links to items that are still in trash are kept, but called out
+            let trashed_links: Vec<String> = [&nota.project, &nota.context]
+                .into_iter()
+                .flatten()
+                .filter(|link| {
+                    data.resolve_id(link)
+                        .and_then(|link| data.find_by_id(link))
+                        .is_some_and(|linked| linked.status == NotaStatus::trash)
+                })
+                .cloned()
+                .collect();
+
+            nota.set_status(status.clone(), today);
+            nota.updated_at = today;
+            commit_items.push(CommitItem::from_nota(&nota, Some(NotaStatus::trash)));
+            let id = nota.id.clone();
+            data.update(&id, nota);
+            restored.push((id, status, recorded, trashed_links));
+        }
+        drop(data);
+
+        let mut response = String::new();
+        if !restored.is_empty() {
+            let message = self.commit_message("Restore", &commit_items);
+            if let Err(e) = self.save_data_with_message(&message).await {
+                bail_public!(_, "Failed to save: {}", e);
+            }
+            response.push_str(&format!(
+                "Restored {} item{}:\n",
+                restored.len(),
+                if restored.len() == 1 { "" } else { "s" }
+            ));
+            for (id, status, recorded, trashed_links) in &restored {
+                response.push_str(&format!("- {}: trash → {:?}", id, status));
+                if !recorded {
+                    response.push_str(" (previous status unknown)");
+                }
+                response.push('\n');
+                if !trashed_links.is_empty() {
+                    response.push_str(&format!(
+                        "  Still in trash: {} (restore it too to keep the link useful)\n",
+                        trashed_links.join(", ")
+                    ));
+                }
+            }
+        }
+
+        if !failures.is_empty() {
+            if !response.is_empty() {
+                response.push('\n');
+            }
+            response.push_str(&format!(
+                "Failed to restore {} item{}:\n",
+                failures.len(),
+                if failures.len() == 1 { "" } else { "s" }
+            ));
+            for failure in &failures {
+                response.push_str(&format!("- {}\n", failure));
+            }
+        }
+
+        if restored.is_empty() {
+            bail_public!(_, "{}", response.trim());
+        }
+        Ok(response.trim().to_string())
+    }
+}
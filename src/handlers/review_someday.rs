@@ -0,0 +1,176 @@
+//! Someday/maybe review handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::gtd::NotaStatus;
+use crate::validation;
+use chrono::{Duration, NaiveDate};
+use mcp_attr::{Result as McpResult, bail_public};
+
+/// Days without changes after which a someday item is due for review
+pub const DEFAULT_OLDER_THAN_DAYS: u32 = 30;
+
+/// A decision about a someday item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Decision {
+    /// Make it a next action
+    Promote,
+    /// Leave it in someday, marking it as reviewed today
+    Keep,
+    /// Move it to trash
+    Trash,
+}
+
+impl GtdServerHandler {
+    /// Handles the someday review - applies the decisions given, then lists someday items untouched for a while.
+    pub async fn handle_review_someday(
+        &self,
+        older_than_days: Option<u32>,
+        promote: Option<Vec<String>>,
+        keep: Option<Vec<String>>,
+        trash: Option<Vec<String>>,
+    ) -> McpResult<String> {
+        let older_than_days = older_than_days.unwrap_or(DEFAULT_OLDER_THAN_DAYS);
+        let mut decisions: Vec<(String, Decision)> = Vec::new();
+        for (ids, decision) in [
+            (promote, Decision::Promote),
+            (keep, Decision::Keep),
+            (trash, Decision::Trash),
+        ] {
+            for id in ids.unwrap_or_default() {
+                let id = validation::normalize_task_id(&id);
+                if decisions.iter().any(|(other, _)| *other == id) {
+                    bail_public!(
+                        _,
+                        "'{}' appears in more than one decision list. Promote, keep or trash each item once.",
+                        id
+                    );
+                }
+                decisions.push((id, decision));
+            }
+        }
+
+        let today = self.clock.today();
+        let (applied, failures, commit_items, listing) = {
+            let mut data = self.data.write().await;
+            let mut applied: Vec<(String, Decision)> = Vec::new();
+            let mut failures = Vec::new();
+            let mut commit_items = Vec::new();
+
+            for (id, decision) in decisions {
+                let Some(mut nota) = data.resolve_id(&id).and_then(|id| data.find_by_id(id)) else {
+                    failures.push(format!("{}: not found", id));
+                    continue;
+                };
+                if nota.status != NotaStatus::someday {
+                    failures.push(format!(
+                        "{}: not a someday item (status: {:?})",
+                        nota.id, nota.status
+                    ));
+                    continue;
+                }
+                if decision == Decision::Trash && data.is_referenced(&nota.id) {
+                    failures.push(format!(
+                        "{}: still referenced by other items (trash it with change_status and a cascade)",
+                        nota.id
+                    ));
+                    continue;
+                }
+                match decision {
+                    Decision::Promote => nota.status = NotaStatus::next_action,
+                    Decision::Trash => nota.set_status(NotaStatus::trash, today),
+                    Decision::Keep => {}
+                }
+                nota.updated_at = today;
+                commit_items.push(CommitItem::from_nota(&nota, Some(NotaStatus::someday)));
+                let id = nota.id.clone();
+                data.update(&id, nota);
+                applied.push((id, decision));
+            }
+
+            // No item is older than a threshold reaching before the first representable date
+            let cutoff = Duration::try_days(i64::from(older_than_days))
+                .and_then(|age| today.checked_sub_signed(age))
+                .unwrap_or(NaiveDate::MIN);
+            let mut stale: Vec<_> = data
+                .iter()
+                .filter(|nota| nota.status == NotaStatus::someday && nota.updated_at <= cutoff)
+                .map(|nota| (nota.updated_at, nota.id.clone(), nota.title.clone()))
+                .collect();
+            stale.sort();
+            let total = data
+                .iter()
+                .filter(|nota| nota.status == NotaStatus::someday)
+                .count();
+            drop(data);
+
+            (applied, failures, commit_items, (stale, total))
+        };
+
+        if !commit_items.is_empty() {
+            let message = self.commit_message("Review someday", &commit_items);
+            if let Err(e) = self.save_data_with_message(&message).await {
+                bail_public!(_, "Failed to save: {}", e);
+            }
+        }
+
+        let mut response = String::new();
+        for (decision, label) in [
+            (Decision::Promote, "Promoted to next_action"),
+            (Decision::Keep, "Kept in someday"),
+            (Decision::Trash, "Moved to trash"),
+        ] {
+            let ids: Vec<&str> = applied
+                .iter()
+                .filter(|(_, d)| *d == decision)
+                .map(|(id, _)| id.as_str())
+                .collect();
+            if !ids.is_empty() {
+                response.push_str(&format!("{}: {}\n", label, ids.join(", ")));
+            }
+        }
+        if !failures.is_empty() {
+            response.push_str(&format!(
+                "Failed for {} item{}:\n",
+                failures.len(),
+                if failures.len() == 1 { "" } else { "s" }
+            ));
+            for failure in &failures {
+                response.push_str(&format!("- {}\n", failure));
+            }
+        }
+        if !response.is_empty() {
+            response.push('\n');
+        }
+
+        let (stale, total) = listing;
+        if stale.is_empty() {
+            response.push_str(&format!(
+                "No someday items untouched for {}+ days ({} someday item{} in total).",
+                older_than_days,
+                total,
+                if total == 1 { "" } else { "s" }
+            ));
+            return Ok(response);
+        }
+        response.push_str(&format!(
+            "Someday items untouched for {}+ days ({} of {}), oldest first:\n",
+            older_than_days,
+            stale.len(),
+            total
+        ));
+        for (updated_at, id, title) in &stale {
+            response.push_str(&format!(
+                "- [{}] {} - last touched {} ({} days ago)\n",
+                id,
+                title,
+                updated_at,
+                (today - *updated_at).num_days()
+            ));
+        }
+        response.push_str(
+            "\nDecide with review_someday(promote=[...], keep=[...], trash=[...]) - kept items are skipped until they are due again.",
+        );
+        Ok(response)
+    }
+}
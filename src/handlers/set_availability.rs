@@ -0,0 +1,58 @@
+//! Set availability handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::availability::Availability;
+use crate::commit_message::CommitItem;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles set_availability - stores when a context can be used, or clears it.
+    pub async fn handle_set_availability(
+        &self,
+        context: String,
+        availability: String,
+    ) -> McpResult<String> {
+        let availability = availability.trim();
+        let schedule = if availability.is_empty() {
+            None
+        } else {
+            match availability.parse::<Availability>() {
+                Ok(schedule) => Some(schedule),
+                Err(e) => bail_public!(_, "{}", e),
+            }
+        };
+
+        let mut data = self.data.write().await;
+        let Some(mut nota) = data.find_context_by_name(&context).cloned() else {
+            let error_msg = validation::format_invalid_context_error(&context, &data);
+            drop(data);
+            bail_public!(_, "{}", error_msg);
+        };
+        nota.availability = schedule.as_ref().map(Availability::to_string);
+        nota.updated_at = self.clock.today();
+        let commit_item = CommitItem::from_nota(&nota, Some(nota.status.clone()));
+        let id = nota.id.clone();
+        data.update(&id, nota);
+        drop(data);
+
+        let message = self.commit_message("Set availability", &[commit_item]);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+
+        Ok(match schedule {
+            Some(schedule) => format!(
+                "{} is available {} ({} now). suggest ranks its actions lower while it is not available.",
+                id,
+                schedule,
+                if schedule.is_available(self.clock.now()) {
+                    "available"
+                } else {
+                    "not available"
+                }
+            ),
+            None => format!("Availability of {} cleared: it is always available.", id),
+        })
+    }
+}
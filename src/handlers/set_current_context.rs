@@ -0,0 +1,60 @@
+//! Current context handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::gtd::{GtdData, NotaStatus};
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles set_current_context - remembers where the user is for the rest of the session.
+    pub async fn handle_set_current_context(&self, name: Option<String>) -> McpResult<String> {
+        let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        let Some(name) = name else {
+            let previous = self.set_session_context(None);
+            return Ok(match previous {
+                Some(previous) => format!(
+                    "Current context cleared (was {}). list and suggest show every context.",
+                    previous
+                ),
+                None => "No current context set. list and suggest show every context.".to_string(),
+            });
+        };
+
+        let data = self.data.read().await;
+        let Some(context) = data.find_context_by_name(&name) else {
+            let error_msg = validation::format_invalid_context_error(&name, &data);
+            drop(data);
+            bail_public!(_, "{}", error_msg);
+        };
+        let name = context.id.clone();
+        let (next_actions, without_context) = data
+            .list_filtered(Some(NotaStatus::next_action), None, None)
+            .iter()
+            .fold((0, 0), |(matching, free), nota| {
+                match data.effective_context(nota) {
+                    Some(ctx) if ctx == name => (matching + 1, free),
+                    Some(_) => (matching, free),
+                    None => (matching, free + 1),
+                }
+            });
+        drop(data);
+
+        self.set_session_context(Some(name.clone()));
+        Ok(format!(
+            "Current context: {}. list(status=\"next_action\") and suggest now default to it ({} next action(s) here, {} without a context). Pass context=\"\" to see every context.",
+            name, next_actions, without_context
+        ))
+    }
+
+    /// Context to filter by: the one given, none for `""`, otherwise the current context
+    ///
+    /// A current context that does not exist in `data` (e.g. after switching
+    /// workspaces) is ignored.
+    pub(crate) fn context_or_current(
+        &self,
+        data: &GtdData,
+        context: Option<String>,
+    ) -> Option<String> {
+        crate::service::context_or_current(data, context, self.current_context())
+    }
+}
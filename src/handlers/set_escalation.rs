@@ -0,0 +1,77 @@
+//! Set escalation handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::duplicates;
+use crate::escalation;
+use crate::gtd::NotaStatus;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles set_escalation - stores how long an item may wait, or resets it to the server default.
+    pub async fn handle_set_escalation(
+        &self,
+        id: String,
+        after_days: Option<u32>,
+    ) -> McpResult<String> {
+        let id = validation::normalize_task_id(&id);
+        let mut data = self.data.write().await;
+        let Some(mut nota) = data.resolve_id(&id).and_then(|id| data.find_by_id(id)) else {
+            let hint = duplicates::did_you_mean(&duplicates::similar_ids(&data, &id));
+            drop(data);
+            bail_public!(
+                _,
+                "Item not found: Item '{}' does not exist.{} Use list() to see available items.",
+                id,
+                hint
+            );
+        };
+        if !nota.is_task() {
+            drop(data);
+            bail_public!(
+                _,
+                "Only tasks can be escalated, but '{}' is a {:?}.",
+                nota.id,
+                nota.status
+            );
+        }
+        let today = self.clock.today();
+        nota.escalate_after_days = after_days;
+        nota.updated_at = today;
+        let commit_item = CommitItem::from_nota(&nota, Some(nota.status.clone()));
+        let id = nota.id.clone();
+        let limit = escalation::threshold(&nota, self.escalate_after_days);
+        let waiting = nota.status == NotaStatus::waiting_for;
+        data.update(&id, nota);
+        drop(data);
+
+        let message = self.commit_message("Set escalation", &[commit_item]);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+
+        let when = if waiting {
+            "its clock restarts today"
+        } else {
+            "counted from when it is moved to waiting_for"
+        };
+        Ok(match (after_days, limit) {
+            (Some(days), _) => format!(
+                "{} is escalated after waiting {} day{} ({}).",
+                id,
+                days,
+                if days == 1 { "" } else { "s" },
+                when
+            ),
+            (None, Some(days)) => format!(
+                "{} uses the server default: escalated after waiting {} day{} ({}).",
+                id,
+                days,
+                if days == 1 { "" } else { "s" },
+                when
+            ),
+            (None, None) => format!("{} is never escalated (no server default is set).", id),
+        })
+    }
+}
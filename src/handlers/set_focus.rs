@@ -0,0 +1,74 @@
+//! Set focus handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::focus::{self, MAX_FOCUS};
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles set_focus - replaces today's focus items and returns the daily plan.
+    pub async fn handle_set_focus(&self, ids: Vec<String>) -> McpResult<String> {
+        let mut ids: Vec<String> = ids
+            .iter()
+            .map(|id| validation::normalize_task_id(id))
+            .filter(|id| !id.is_empty())
+            .collect();
+        let mut seen = std::collections::HashSet::new();
+        ids.retain(|id| seen.insert(id.clone()));
+        if ids.len() > MAX_FOCUS {
+            bail_public!(
+                _,
+                "Too many focus items ({}). Pick at most {} most important tasks for today.",
+                ids.len(),
+                MAX_FOCUS
+            );
+        }
+
+        let today = self.clock.today();
+        let mut data = self.data.write().await;
+        let mut errors = Vec::new();
+        let mut focus_ids = Vec::new();
+        for id in &ids {
+            match data.resolve_id(id).and_then(|id| data.find_by_id(id)) {
+                Some(nota) if focus::can_focus(&nota) => focus_ids.push(nota.id),
+                Some(nota) => errors.push(format!(
+                    "{}: not an open task (status: {:?})",
+                    nota.id, nota.status
+                )),
+                None => errors.push(format!("{}: not found", id)),
+            }
+        }
+        if !errors.is_empty() {
+            drop(data);
+            bail_public!(_, "Focus not changed:\n- {}", errors.join("\n- "));
+        }
+
+        // Clear the previous focus (including stale days) and set the new one
+        let changed: Vec<_> = data
+            .iter()
+            .filter(|nota| {
+                let focus_date = focus_ids.contains(&nota.id).then_some(today);
+                nota.focus_date != focus_date
+            })
+            .cloned()
+            .collect();
+        let mut commit_items = Vec::new();
+        for mut nota in changed {
+            nota.focus_date = focus_ids.contains(&nota.id).then_some(today);
+            if nota.focus_date.is_some() {
+                commit_items.push(CommitItem::from_nota(&nota, Some(nota.status.clone())));
+            }
+            let id = nota.id.clone();
+            data.update(&id, nota);
+        }
+        let plan = focus::plan(&data, today);
+        drop(data);
+
+        let message = self.commit_message("Focus", &commit_items);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+        Ok(plan)
+    }
+}
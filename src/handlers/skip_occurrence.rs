@@ -0,0 +1,64 @@
+//! Skip occurrence handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::gtd::NotaStatus;
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles skip_occurrence - moves a recurring item to its next occurrence without completing it.
+    pub async fn handle_skip_occurrence(&self, id: String) -> McpResult<String> {
+        let id = validation::normalize_task_id(&id);
+        let today = self.clock.today();
+
+        let mut data = self.data.write().await;
+        let Some(mut nota) = data.resolve_id(&id).and_then(|id| data.find_by_id(id)) else {
+            drop(data);
+            bail_public!(_, "Item '{}' not found.", id);
+        };
+        if !nota.is_recurring() {
+            drop(data);
+            bail_public!(
+                _,
+                "'{}' is not recurring. Use postpone or update to move its date.",
+                nota.id
+            );
+        }
+        if matches!(nota.status, NotaStatus::done | NotaStatus::trash) {
+            drop(data);
+            bail_public!(
+                _,
+                "'{}' is {:?}. Only open recurring items can skip an occurrence.",
+                nota.id,
+                nota.status
+            );
+        }
+        // Same base date as completing the item (see change_status)
+        let skipped = nota.start_date.unwrap_or(today);
+        let Some(next_date) = nota.calculate_next_occurrence(skipped) else {
+            drop(data);
+            bail_public!(
+                _,
+                "Cannot find the next occurrence of '{}'. Check its recurrence_config.",
+                nota.id
+            );
+        };
+        nota.start_date = Some(next_date);
+        nota.updated_at = today;
+        let commit_item = CommitItem::from_nota(&nota, Some(nota.status.clone()));
+        let response = format!(
+            "Skipped the {} occurrence of [{}] {}. Next occurrence: {}",
+            skipped, nota.id, nota.title, next_date
+        );
+        let nota_id = nota.id.clone();
+        data.update(&nota_id, nota);
+        drop(data);
+
+        let message = self.commit_message("Skip occurrence", &[commit_item]);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+        Ok(response)
+    }
+}
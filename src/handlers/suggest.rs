@@ -0,0 +1,144 @@
+//! Suggest handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::commit_message::CommitItem;
+use crate::escalation;
+use crate::gtd::{Energy, NotaStatus};
+use crate::suggest::{self, Constraints};
+use crate::validation;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Handles next action suggestions - ranks the next actions that fit the given situation.
+    pub async fn handle_suggest(
+        &self,
+        context: Option<String>,
+        minutes: Option<u32>,
+        energy: Option<String>,
+        limit: Option<u32>,
+    ) -> McpResult<String> {
+        let energy = match energy
+            .filter(|e| !e.is_empty())
+            .map(|e| e.parse::<Energy>())
+        {
+            None => None,
+            Some(Ok(energy)) => Some(energy),
+            Some(Err(e)) => bail_public!(_, "{}", e),
+        };
+        let limit = limit.map_or(suggest::DEFAULT_LIMIT, |limit| limit.max(1) as usize);
+        let today = self.clock.today();
+        let escalated = if self.escalate_to_next_action {
+            self.move_escalated(today).await?
+        } else {
+            Vec::new()
+        };
+        let data = self.data.read().await;
+        let constraints = Constraints {
+            context: self.context_or_current(&data, context),
+            minutes,
+            energy,
+            now: Some(self.clock.now()),
+            escalate_after_days: self.escalate_after_days,
+        };
+        if let Some(ctx) = &constraints.context
+            && data.find_context_by_name(ctx).is_none()
+        {
+            let error_msg = validation::format_invalid_context_error(ctx, &data);
+            drop(data);
+            bail_public!(_, "{}", error_msg);
+        }
+        let ranking = suggest::rank(&data, &constraints, today, limit);
+        drop(data);
+
+        let situation = constraints.describe();
+        let situation = if situation.is_empty() {
+            String::new()
+        } else {
+            format!(" for {}", situation)
+        };
+        let mut result = String::new();
+        if !escalated.is_empty() {
+            let moved: Vec<String> = escalated
+                .iter()
+                .map(|(id, overdue)| format!("{} ({} days)", id, overdue.days))
+                .collect();
+            result.push_str(&format!(
+                "Moved back to next_action after waiting too long: {}\n\n",
+                moved.join(", ")
+            ));
+        }
+        if ranking.suggestions.is_empty() {
+            result.push_str(&if ranking.skipped == 0 {
+                "No next actions. Process the inbox or review projects to find the next steps."
+                    .to_string()
+            } else {
+                format!(
+                    "No next actions fit{}: {} left out by context, time, energy or a future start date. Try wider constraints.",
+                    situation, ranking.skipped
+                )
+            });
+            return Ok(result);
+        }
+
+        result.push_str(&format!(
+            "Top {} of {} next action(s){}:\n",
+            ranking.suggestions.len(),
+            ranking.fitting,
+            situation
+        ));
+        for (rank, suggestion) in ranking.suggestions.iter().enumerate() {
+            let nota = &suggestion.nota;
+            let escalate = nota.status == NotaStatus::waiting_for
+                || escalated.iter().any(|(id, _)| *id == nota.id);
+            result.push_str(&format!(
+                "\n{}. [{}] {} (score {}){}\n",
+                rank + 1,
+                nota.id,
+                nota.title,
+                suggestion.score,
+                if escalate { " ESCALATE" } else { "" }
+            ));
+            if !suggestion.reasons.is_empty() {
+                result.push_str(&format!("   Why: {}\n", suggestion.reasons.join("; ")));
+            }
+        }
+        if ranking.skipped > 0 {
+            result.push_str(&format!(
+                "\n{} next action(s) left out because they don't fit.",
+                ranking.skipped
+            ));
+        }
+        Ok(result.trim_end().to_string())
+    }
+
+    /// Move the waiting items due for escalation back to next_action
+    ///
+    /// Returns the moved items with how long they waited.
+    async fn move_escalated(
+        &self,
+        today: chrono::NaiveDate,
+    ) -> McpResult<Vec<(String, escalation::Overdue)>> {
+        let mut data = self.data.write().await;
+        let due = escalation::due(&data, self.escalate_after_days, today);
+        if due.is_empty() {
+            return Ok(due);
+        }
+        let mut commit_items = Vec::new();
+        for (id, _) in &due {
+            let Some(mut nota) = data.find_by_id(id) else {
+                continue;
+            };
+            nota.set_status(NotaStatus::next_action, today);
+            nota.updated_at = today;
+            commit_items.push(CommitItem::from_nota(&nota, Some(NotaStatus::waiting_for)));
+            data.update(id, nota);
+        }
+        drop(data);
+
+        let message = self.commit_message("Escalate", &commit_items);
+        if let Err(e) = self.save_data_with_message(&message).await {
+            bail_public!(_, "Failed to save: {}", e);
+        }
+        Ok(due)
+    }
+}
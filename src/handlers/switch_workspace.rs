@@ -0,0 +1,98 @@
+//! Switch workspace handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Activates another workspace, or lists the workspaces when no name is given.
+    pub async fn handle_switch_workspace(&self, name: Option<String>) -> McpResult<String> {
+        let Some(name) = name else {
+            return Ok(self.describe_workspaces());
+        };
+        let name = name.trim();
+        if name == self.workspace() {
+            return Ok(format!("Workspace '{}' is already active.", name));
+        }
+        if !self.workspaces.lock().unwrap().contains(name) {
+            bail_public!(
+                _,
+                "Unknown workspace '{}'. Available workspaces: {}",
+                name,
+                self.workspaces.lock().unwrap().names().join(", ")
+            );
+        }
+        if self.transaction.lock().unwrap().is_some() {
+            bail_public!(
+                _,
+                "Cannot switch workspaces while a transaction is open. Commit it first."
+            );
+        }
+
+        // Hold the write queue and the data so no tool call sees a half-switched state
+        let _queue = self.write_queue.lock().await;
+        let mut data = self.data.write().await;
+        let content = match crate::Storage::serialize(&data) {
+            Ok(content) => content,
+            Err(e) => bail_public!(_, "Failed to switch workspaces: {}", e),
+        };
+        // A save queued behind this switch (or one that failed) would otherwise
+        // write to the next workspace's file: write the changes here first
+        let mut events = Vec::new();
+        if data.is_dirty(&content) {
+            if let Some(report) = self.integrity_report(&data) {
+                bail_public!(_, "{}", report);
+            }
+            let storage = self.storage();
+            let written = content.clone();
+            match tokio::task::spawn_blocking(move || {
+                storage.write_content(&written, "Update GTD data")
+            })
+            .await
+            {
+                Ok(Ok(())) => {
+                    data.mark_saved(&content);
+                    events = self.saved_events(&data);
+                }
+                Ok(Err(e)) => bail_public!(_, "Failed to save before switching: {}", e),
+                Err(e) => bail_public!(_, "Failed to save before switching: {}", e),
+            }
+        }
+
+        let mut workspaces = self.workspaces.lock().unwrap();
+        let Some((next_data, next_storage)) = workspaces.take(name) else {
+            bail_public!(_, "Unknown workspace '{}'", name);
+        };
+        let path = next_storage.file_path().display().to_string();
+        let previous_data = std::mem::replace(&mut *data, next_data);
+        let previous_storage = std::mem::replace(&mut *self.storage.write().unwrap(), next_storage);
+        workspaces.park_active(name, previous_data, previous_storage);
+        drop(workspaces);
+        self.adopt_saved(&data);
+        let count = data.iter().count();
+        drop(data);
+        self.hooks.fire(&events);
+
+        Ok(format!(
+            "Switched to workspace '{}' ({}, {} items).",
+            name, path, count
+        ))
+    }
+
+    /// List every workspace with its data file, marking the active one
+    fn describe_workspaces(&self) -> String {
+        let workspaces = self.workspaces.lock().unwrap();
+        let mut response = String::from("Workspaces:\n");
+        for name in workspaces.names() {
+            if name == workspaces.active() {
+                response.push_str(&format!(
+                    "* {} (active): {}\n",
+                    name,
+                    self.storage().file_path().display()
+                ));
+            } else if let Some(path) = workspaces.parked_path(name) {
+                response.push_str(&format!("- {}: {}\n", name, path.display()));
+            }
+        }
+        response.trim_end().to_string()
+    }
+}
@@ -0,0 +1,90 @@
+//! Sync handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::progress::Progress;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Fetches, rebases, and pushes on demand - reloads data when remote commits arrive.
+    pub async fn handle_sync(&self, progress: Progress<'_>) -> McpResult<String> {
+        const STEPS: usize = 3;
+        // Received commits replace the data, which would drop the changes of
+        // the transaction
+        if self.in_transaction() {
+            bail_public!(
+                _,
+                "Cannot sync while a transaction is open. Commit or abort it first."
+            );
+        }
+        // Hold the write queue so no save interleaves with the sync. The Git
+        // work keeps it until it ends, even when the call is cancelled meanwhile
+        let queue = self.write_queue.clone().lock_owned();
+        let queue = progress
+            .step(0, STEPS, "Waiting for pending saves", queue)
+            .await;
+
+        let storage = self.storage();
+        let cancellation = progress.cancellation();
+        let sync = tokio::task::spawn_blocking(move || (storage.sync(&cancellation), queue));
+        let (result, _queue) = match progress.step(1, STEPS, "Syncing with origin", sync).await {
+            Ok(synced) => synced,
+            Err(e) => bail_public!(_, "Sync failed: {}", e),
+        };
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => bail_public!(_, "Sync failed: {}", e),
+        };
+
+        // Adopt commits from other machines (including ones a debounced flush pulled in)
+        if self.storage().take_remote_update() || !report.received.is_empty() {
+            let storage = self.storage();
+            let load = tokio::task::spawn_blocking(move || storage.load_local());
+            match progress
+                .step(2, STEPS, "Reloading received changes", load)
+                .await
+            {
+                Ok(Ok(data)) => {
+                    let mut current = self.data.write().await;
+                    if self.in_transaction() {
+                        bail_public!(
+                            _,
+                            "Synced, but a transaction began meanwhile, so the received changes were not loaded. Call reload once it is committed or aborted."
+                        );
+                    }
+                    self.adopt_saved(&data);
+                    *current = data;
+                }
+                Ok(Err(e)) => bail_public!(_, "Synced, but failed to reload data: {}", e),
+                Err(e) => bail_public!(_, "Synced, but failed to reload data: {}", e),
+            }
+        }
+
+        if report.received.is_empty() && report.sent.is_empty() {
+            return Ok(format!(
+                "Already up to date with origin/{} - nothing received or sent.",
+                report.branch
+            ));
+        }
+
+        let mut response = format!("Synced with origin/{}.\n", report.branch);
+        for (label, commits) in [("Received", &report.received), ("Sent", &report.sent)] {
+            if commits.is_empty() {
+                continue;
+            }
+            response.push_str(&format!(
+                "\n{} {} commit{}:\n",
+                label,
+                commits.len(),
+                if commits.len() == 1 { "" } else { "s" }
+            ));
+            for summary in commits {
+                response.push_str(&format!("- {}\n", summary));
+            }
+        }
+        if !report.received.is_empty() {
+            response.push_str("\nData reloaded with the received changes.");
+        }
+
+        Ok(response.trim_end().to_string())
+    }
+}
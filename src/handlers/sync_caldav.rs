@@ -0,0 +1,67 @@
+//! CalDAV sync handler for GTD MCP server
+
+use crate::caldav::{self, Cancellable, CurlClient, SyncState};
+use crate::progress::Progress;
+use crate::{CommitItem, GtdServerHandler};
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Syncs next actions and calendar items with the configured CalDAV task list.
+    pub async fn handle_sync_caldav(&self, progress: Progress<'_>) -> McpResult<String> {
+        const STEPS: usize = 2;
+        let Some(config) = self.caldav.clone() else {
+            bail_public!(
+                _,
+                "CalDAV sync is not configured. Start the server with --caldav-url <task list URL> (plus --caldav-username and --caldav-password if the server needs them)."
+            );
+        };
+        let state_path = caldav::state_path(self.storage().file_path());
+
+        // Hold the data lock so no tool changes items while the server is contacted
+        let mut data = self.data.write().await;
+        let mut synced = data.clone();
+        let cancellation = progress.cancellation();
+        let today = self.clock.today();
+        let sync = tokio::task::spawn_blocking(move || {
+            let mut state = SyncState::load(&state_path)?;
+            let mut server = Cancellable::new(CurlClient::new(&config), cancellation);
+            let report =
+                caldav::sync(&mut synced, &mut state, &mut server, config.conflict, today)?;
+            anyhow::Ok((synced, state, state_path, report))
+        });
+        let result = progress
+            .step(0, STEPS, "Syncing with the CalDAV task list", sync)
+            .await;
+        let (synced, state, state_path, report) = match result {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => bail_public!(_, "CalDAV sync failed: {}", e),
+            Err(e) => bail_public!(_, "CalDAV sync failed: {}", e),
+        };
+        *data = synced;
+        let commit_items: Vec<CommitItem> = report
+            .received
+            .iter()
+            .filter_map(|(id, old_status)| {
+                data.find_by_id(id)
+                    .map(|nota| CommitItem::from_nota(&nota, old_status.clone()))
+            })
+            .collect();
+        drop(data);
+
+        if !commit_items.is_empty() {
+            let message = self.commit_message("CalDAV sync", &commit_items);
+            let save = self.save_data_with_message(&message);
+            if let Err(e) = progress
+                .step(1, STEPS, "Saving received changes", save)
+                .await
+            {
+                bail_public!(_, "CalDAV sync received changes but failed to save: {}", e);
+            }
+        }
+        // Record the sync only once the received changes are saved
+        if let Err(e) = state.save(&state_path) {
+            bail_public!(_, "CalDAV sync done, but {}", e);
+        }
+        Ok(report.to_string())
+    }
+}
@@ -0,0 +1,74 @@
+//! Sync status handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use mcp_attr::{Result as McpResult, bail_public};
+
+/// Timestamp format used in the status report
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+impl GtdServerHandler {
+    /// Reports Git sync state - unpushed commits and background push progress.
+    pub async fn handle_sync_status(&self) -> McpResult<String> {
+        let storage = self.storage();
+        if !storage.git_sync_enabled() {
+            return Ok(
+                "Git sync is disabled. Start the server with --sync-git (in a Git repository) to commit and push changes."
+                    .to_string(),
+            );
+        }
+
+        let git = storage.clone();
+        let unpushed = match tokio::task::spawn_blocking(move || git.unpushed_commits()).await {
+            Ok(Ok(count)) => count,
+            Ok(Err(e)) => bail_public!(_, "Failed to read Git status: {}", e),
+            Err(e) => bail_public!(_, "Failed to read Git status: {}", e),
+        };
+
+        let mut response = String::new();
+        match storage.push_status() {
+            None => response.push_str("Push mode: on every save\n"),
+            Some(status) if status.interval.is_zero() => {
+                response.push_str("Push mode: background, after each commit\n")
+            }
+            Some(status) => response.push_str(&format!(
+                "Push mode: background, at most every {}s\n",
+                status.interval.as_secs()
+            )),
+        }
+        match unpushed {
+            Some(count) => response.push_str(&format!("Unpushed commits: {}\n", count)),
+            None => response.push_str("Unpushed commits: unknown (branch not on origin yet)\n"),
+        }
+
+        if let Some(status) = storage.push_status() {
+            let pending = if status.waiting_for_pull {
+                "waiting - the remote has newer commits; they are pulled on the next save"
+            } else if status.pending {
+                "yes"
+            } else {
+                "no"
+            };
+            response.push_str(&format!("Push pending: {}\n", pending));
+            if let Some(time) = status.last_success {
+                response.push_str(&format!("Last push: {}\n", time.format(TIME_FORMAT)));
+            }
+            if let Some(time) = status.last_attempt {
+                response.push_str(&format!("Last attempt: {}\n", time.format(TIME_FORMAT)));
+            }
+            if let Some(error) = &status.last_error {
+                response.push_str(&format!("Last error: {}\n", error));
+            }
+            if status.consecutive_failures > 0 {
+                response.push_str(&format!(
+                    "Consecutive failures: {}\n",
+                    status.consecutive_failures
+                ));
+            }
+            if let Some(time) = status.next_retry {
+                response.push_str(&format!("Next retry: {}\n", time.format(TIME_FORMAT)));
+            }
+        }
+
+        Ok(response.trim_end().to_string())
+    }
+}
@@ -0,0 +1,13 @@
+//! Today handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::focus;
+use mcp_attr::Result as McpResult;
+
+impl GtdServerHandler {
+    /// Handles today - returns the daily plan (focus items, calendar, due items).
+    pub async fn handle_today(&self) -> McpResult<String> {
+        let data = self.data.read().await;
+        Ok(focus::plan(&data, self.clock.today()))
+    }
+}
@@ -0,0 +1,39 @@
+//! Transaction handlers for GTD MCP server
+
+use crate::GtdServerHandler;
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Starts grouping the following changes into one all-or-nothing save.
+    pub async fn handle_begin_transaction(&self) -> McpResult<String> {
+        if let Err(e) = self.begin_transaction().await {
+            bail_public!(_, "{}. Call commit or abort first.", e);
+        }
+        Ok("Transaction started. Changes stay in memory until commit saves them all in one Git commit, or abort restores the data as it is now.".to_string())
+    }
+
+    /// Saves every change of the open transaction at once, or rolls all of them back.
+    pub async fn handle_commit(&self) -> McpResult<String> {
+        match self.commit_transaction().await {
+            Ok(0) => Ok("Transaction closed without changes - nothing was saved.".to_string()),
+            Ok(count) => Ok(format!(
+                "Transaction committed: {} change{} saved in one commit.",
+                count,
+                if count == 1 { "" } else { "s" }
+            )),
+            Err(e) => bail_public!(_, "Commit failed: {:#}", e),
+        }
+    }
+
+    /// Drops every change of the open transaction.
+    pub async fn handle_abort(&self) -> McpResult<String> {
+        match self.abort_transaction().await {
+            Ok(count) => Ok(format!(
+                "Transaction aborted: {} change{} rolled back.",
+                count,
+                if count == 1 { "" } else { "s" }
+            )),
+            Err(e) => bail_public!(_, "{}", e),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Tree handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::tree;
+use mcp_attr::Result as McpResult;
+
+impl GtdServerHandler {
+    /// Handles tree - renders projects, sub-projects and their tasks as a nested list.
+    pub async fn handle_tree(&self, include_done: Option<bool>) -> McpResult<String> {
+        let data = self.data.read().await;
+        Ok(tree::render(&data, include_done.unwrap_or(false)))
+    }
+}
@@ -0,0 +1,28 @@
+//! Trends handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::gtd::NotaStatus;
+use crate::trends::{self, DEFAULT_WEEKS, MAX_WEEKS};
+use mcp_attr::{Result as McpResult, bail_public};
+
+impl GtdServerHandler {
+    /// Renders weekly capture, clarify and completion counts for the last weeks.
+    pub async fn handle_trends(&self, weeks: Option<u32>) -> McpResult<String> {
+        let weeks = weeks.unwrap_or(DEFAULT_WEEKS);
+        if weeks == 0 || weeks > MAX_WEEKS {
+            bail_public!(
+                _,
+                "Invalid weeks {}. Use a number from 1 to {}.",
+                weeks,
+                MAX_WEEKS
+            );
+        }
+        let data = self.data.read().await;
+        let stats = trends::compute(&data, self.clock.today(), weeks);
+        let inbox = data
+            .iter()
+            .filter(|nota| nota.status == NotaStatus::inbox)
+            .count();
+        Ok(trends::render(&stats, inbox))
+    }
+}
@@ -1,11 +1,37 @@
 //! Update handler for GTD MCP server
 
 use crate::GtdServerHandler;
-use crate::gtd::{self, NotaStatus};
-use crate::validation;
+use crate::gtd::NotaStatus;
+use crate::handlers::public_error;
+use crate::service::ItemUpdate;
 use chrono::NaiveDate;
 use mcp_attr::{Result as McpResult, bail_public};
 
+/// An optional field given as a string: `None` leaves it, "" clears it,
+/// anything else is parsed
+fn clearable<T>(
+    value: Option<String>,
+    parse: impl FnOnce(&str) -> Result<T, String>,
+) -> McpResult<Option<Option<T>>> {
+    match value {
+        None => Ok(None),
+        Some(value) if value.is_empty() => Ok(Some(None)),
+        Some(value) => match parse(&value) {
+            Ok(parsed) => Ok(Some(Some(parsed))),
+            Err(message) => bail_public!(_, "{}", message),
+        },
+    }
+}
+
+fn parse_date(date_str: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
+        format!(
+            "Invalid date format '{}'. Use YYYY-MM-DD (e.g., '2025-03-15')",
+            date_str
+        )
+    })
+}
+
 impl GtdServerHandler {
     /// Updates item fields if provided. Returns error if item not found or status invalid.
     #[allow(clippy::too_many_arguments)]
@@ -18,115 +44,49 @@ impl GtdServerHandler {
         context: Option<String>,
         notes: Option<String>,
         start_date: Option<String>,
+        priority: Option<String>,
+        due_date: Option<String>,
+        estimate: Option<String>,
+        energy: Option<String>,
+        goal: Option<String>,
     ) -> McpResult<String> {
-        let mut data = self.data.lock().unwrap();
-
-        // Find existing nota
-        let mut nota = match data.find_by_id(&id) {
-            Some(n) => n,
-            None => {
-                drop(data);
-                bail_public!(
+        let status = match status {
+            Some(status_str) => match status_str.parse::<NotaStatus>() {
+                Ok(s) => Some(s),
+                Err(_) => bail_public!(
                     _,
-                    "Item not found: Item '{}' does not exist. Use list() to see available items.",
-                    id
-                );
-            }
+                    "Invalid status '{}'. Valid statuses: inbox, next_action, waiting_for, later, calendar, someday, done, reference, trash, project, context, goal, template",
+                    status_str
+                ),
+            },
+            None => None,
+        };
+        // Empty strings clear the optional fields
+        let changes = ItemUpdate {
+            title,
+            status,
+            project: clearable(project, |p| Ok(p.to_string()))?,
+            context: clearable(context, |c| Ok(c.to_string()))?,
+            notes: clearable(notes, |n| Ok(n.to_string()))?,
+            start_date: clearable(start_date, parse_date)?,
+            priority: clearable(priority, str::parse)?,
+            due_date: clearable(due_date, parse_date)?,
+            estimate: clearable(estimate, |minutes| {
+                minutes.parse().map_err(|_| {
+                    format!(
+                        "Invalid estimate '{}'. Use a whole number of minutes (e.g., '15')",
+                        minutes
+                    )
+                })
+            })?,
+            energy: clearable(energy, str::parse)?,
+            goal: clearable(goal, |g| Ok(g.to_string()))?,
         };
 
-        // Update fields if provided
-        if let Some(new_title) = title {
-            nota.title = new_title;
-        }
-
-        if let Some(new_status_str) = status {
-            let new_status: NotaStatus = match new_status_str.parse() {
-                Ok(s) => s,
-                Err(_) => {
-                    drop(data);
-                    bail_public!(
-                        _,
-                        "Invalid status '{}'. Valid statuses: inbox, next_action, waiting_for, later, calendar, someday, done, reference, trash, project, context",
-                        new_status_str
-                    );
-                }
-            };
-            nota.status = new_status;
-        }
-
-        // Handle optional reference fields (empty string means clear)
-        if let Some(proj) = project {
-            nota.project = if proj.is_empty() {
-                None
-            } else {
-                // Validate project exists
-                if data.find_project_by_id(&proj).is_none() {
-                    let error_msg = validation::format_invalid_project_error(&proj, &data);
-                    drop(data);
-                    bail_public!(_, "{}", error_msg);
-                }
-                Some(proj)
-            };
-        }
-
-        if let Some(ctx) = context {
-            nota.context = if ctx.is_empty() {
-                None
-            } else {
-                // Validate context exists
-                if data.find_context_by_name(&ctx).is_none() {
-                    let error_msg = validation::format_invalid_context_error(&ctx, &data);
-                    drop(data);
-                    bail_public!(_, "{}", error_msg);
-                }
-                Some(ctx)
-            };
-        }
-
-        if let Some(n) = notes {
-            nota.notes = if n.is_empty() { None } else { Some(n) };
-        }
-
-        if let Some(date_str) = start_date {
-            nota.start_date = if date_str.is_empty() {
-                None
-            } else {
-                match NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
-                    Ok(d) => Some(d),
-                    Err(_) => {
-                        drop(data);
-                        bail_public!(
-                            _,
-                            "Invalid date format '{}'. Use YYYY-MM-DD (e.g., '2025-03-15')",
-                            date_str
-                        );
-                    }
-                }
-            };
-        }
-
-        // Validate calendar status has start_date
-        if nota.status == NotaStatus::calendar && nota.start_date.is_none() {
-            drop(data);
-            bail_public!(
-                _,
-                "Calendar status validation failed: status=calendar requires start_date. Please provide a start_date or change to a different status."
-            );
-        }
-
-        nota.updated_at = gtd::local_date_today();
-
-        // Update the nota
-        if data.update(&id, nota).is_none() {
-            drop(data);
-            bail_public!(_, "Failed to update item '{}'", id);
-        }
-        drop(data);
-
-        if let Err(e) = self.save_data_with_message(&format!("Update item {}", id)) {
-            bail_public!(_, "Failed to save: {}", e);
-        }
-
+        self.service()
+            .update(&id, changes)
+            .await
+            .map_err(public_error)?;
         Ok(format!("Item {} updated successfully", id))
     }
 }
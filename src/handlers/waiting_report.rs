@@ -0,0 +1,13 @@
+//! Waiting-for report handler for GTD MCP server
+
+use crate::GtdServerHandler;
+use crate::report;
+use mcp_attr::Result as McpResult;
+
+impl GtdServerHandler {
+    /// Lists the waiting-for items with the days each has been waiting, oldest first.
+    pub async fn handle_waiting_report(&self) -> McpResult<String> {
+        let data = self.data.read().await;
+        Ok(report::render_waiting(&data, self.clock.today()))
+    }
+}
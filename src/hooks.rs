@@ -0,0 +1,129 @@
+//! Callbacks for changes to the items
+//!
+//! Embedders register [`Hooks`] to trigger their own side effects
+//! (notifications, syncing another system) when items are created, change
+//! status or are deleted, whichever tool, daemon job or service call made the
+//! change:
+//!
+//! ```no_run
+//! # use gtd_mcp::GtdServerHandler;
+//! # fn main() -> anyhow::Result<()> {
+//! let handler = GtdServerHandler::builder()
+//!     .path("gtd.toml")
+//!     .on_status_changed(|nota, from| println!("{}: {:?} -> {:?}", nota.id, from, nota.status))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Events are found by comparing each save with the previous one, so they fire
+//! once the change is written: a transaction fires them all at commit, and an
+//! aborted transaction or a refused strict save fires none; `restore_backup`
+//! fires what the restore changed. Moving an item to
+//! trash is a status change; an item is deleted when it leaves the file
+//! (`empty_trash`, trash retention, archiving). Callbacks run on the saving
+//! task and should return quickly; spawn slow work.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Callback for a created or deleted item
+pub type NotaHook = Arc<dyn Fn(&Nota) + Send + Sync>;
+
+/// Callback for an item that changed status, with its previous status
+pub type StatusHook = Arc<dyn Fn(&Nota, &NotaStatus) + Send + Sync>;
+
+/// A change to one item found between two saves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// The item is new
+    Created(Nota),
+    /// The item moved from status `from` to its current one
+    StatusChanged {
+        /// The item as saved
+        nota: Nota,
+        /// Status in the previous save
+        from: NotaStatus,
+    },
+    /// The item is gone (as it was in the previous save)
+    Deleted(Nota),
+}
+
+/// Callbacks registered for each kind of [`Event`]
+#[derive(Clone, Default)]
+pub struct Hooks {
+    created: Vec<NotaHook>,
+    status_changed: Vec<StatusHook>,
+    deleted: Vec<NotaHook>,
+}
+
+impl Hooks {
+    /// Call `hook` for every new item
+    pub fn on_created(mut self, hook: impl Fn(&Nota) + Send + Sync + 'static) -> Self {
+        self.created.push(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` for every status change, with the previous status
+    pub fn on_status_changed(
+        mut self,
+        hook: impl Fn(&Nota, &NotaStatus) + Send + Sync + 'static,
+    ) -> Self {
+        self.status_changed.push(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` for every item that leaves the file
+    pub fn on_deleted(mut self, hook: impl Fn(&Nota) + Send + Sync + 'static) -> Self {
+        self.deleted.push(Arc::new(hook));
+        self
+    }
+
+    /// Whether no callback is registered
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.status_changed.is_empty() && self.deleted.is_empty()
+    }
+
+    /// Call the callbacks registered for each event, in order
+    pub fn fire(&self, events: &[Event]) {
+        for event in events {
+            match event {
+                Event::Created(nota) => self.created.iter().for_each(|hook| hook(nota)),
+                Event::StatusChanged { nota, from } => {
+                    self.status_changed.iter().for_each(|hook| hook(nota, from))
+                }
+                Event::Deleted(nota) => self.deleted.iter().for_each(|hook| hook(nota)),
+            }
+        }
+    }
+}
+
+/// Changes from `before` to `after`
+///
+/// New items and status changes come in the order of `after`, followed by
+/// the deleted items in the order of `before`. Changes to other fields are
+/// not events.
+pub fn events(before: &GtdData, after: &GtdData) -> Vec<Event> {
+    let previous: HashMap<&str, &Nota> =
+        before.iter().map(|nota| (nota.id.as_str(), nota)).collect();
+    let mut events: Vec<Event> = after
+        .iter()
+        .filter_map(|nota| match previous.get(nota.id.as_str()) {
+            None => Some(Event::Created(nota.clone())),
+            Some(old) if old.status != nota.status => Some(Event::StatusChanged {
+                nota: nota.clone(),
+                from: old.status.clone(),
+            }),
+            Some(_) => None,
+        })
+        .collect();
+    let current: HashSet<&str> = after.iter().map(|nota| nota.id.as_str()).collect();
+    events.extend(
+        before
+            .iter()
+            .filter(|nota| !current.contains(nota.id.as_str()))
+            .map(|nota| Event::Deleted(nota.clone())),
+    );
+    events
+}
@@ -0,0 +1,149 @@
+//! HTTP requests through the `curl` command
+//!
+//! Used by the CalDAV sync and webhooks. Requests are handed to curl as a
+//! configuration on stdin, so passwords and item contents never show up in the
+//! process list. curl brings HTTPS and the authentication schemes servers ask
+//! for (Basic, Digest). Headers are dumped to a file of their own, so a body
+//! is never mistaken for headers.
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Seconds a request may take before it is abandoned
+pub const TIMEOUT_SECS: u64 = 30;
+
+/// An HTTP request
+#[derive(Debug, Clone, Copy)]
+pub struct Request<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    /// User name and password, if the server needs them
+    pub credentials: Option<(&'a str, &'a str)>,
+    pub headers: &'a [(&'a str, &'a str)],
+    pub body: Option<&'a str>,
+}
+
+/// Status, headers and body of a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// Value of a header, matched case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Run one request
+///
+/// # Returns
+/// The response whatever its status, or an error if no response arrived
+/// (curl missing, connection refused, timeout)
+pub fn send(request: Request) -> Result<Response> {
+    let Request {
+        method,
+        url,
+        credentials,
+        headers,
+        body,
+    } = request;
+    let head_file = tempfile::NamedTempFile::new()?;
+    let mut config = String::from("silent\nshow-error\n");
+    config.push_str(&format!(
+        "dump-header = {}\n",
+        quote(&head_file.path().to_string_lossy())
+    ));
+    config.push_str(&format!("max-time = {}\n", TIMEOUT_SECS));
+    config.push_str(&format!("request = {}\n", quote(method)));
+    config.push_str(&format!("url = {}\n", quote(url)));
+    if let Some((username, password)) = credentials {
+        config.push_str(&format!(
+            "user = {}\n",
+            quote(&format!("{}:{}", username, password))
+        ));
+        config.push_str("anyauth\n");
+    }
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data-raw = {}\n", quote(body)));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("HTTP requests need the curl command")?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "{} {} failed: {}",
+            method,
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let head = std::fs::read_to_string(head_file.path())?;
+    parse_response(&head, String::from_utf8_lossy(&output.stdout).into_owned())
+        .ok_or_else(|| anyhow!("{} {} returned no HTTP response", method, url))
+}
+
+/// Quote a value for a curl configuration
+fn quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The final response from curl's `--dump-header` output and the body
+///
+/// The output holds a header block for every response curl received, so the
+/// blocks of `100 Continue` and authentication challenges come before the
+/// final one.
+fn parse_response(head: &str, body: String) -> Option<Response> {
+    let head = head.replace("\r\n", "\n");
+    let block = head
+        .split("\n\n")
+        .filter(|block| block.starts_with("HTTP/"))
+        .last()?;
+    let mut lines = block.lines();
+    let status: u16 = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    Some(Response {
+        status,
+        headers,
+        body,
+    })
+}
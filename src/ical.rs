@@ -0,0 +1,139 @@
+//! iCalendar (.ics) export of dated items
+//!
+//! Calendar apps can import or subscribe to the feed to show GTD dates next to
+//! meetings. Every item is an all-day `VEVENT`:
+//! - `calendar` items with a `start_date`, whatever their recurrence
+//! - recurring items with a `start_date` that are not done, trashed or paused
+//! - the upcoming occurrences of those recurring items, up to
+//!   [`OCCURRENCE_HORIZON_DAYS`] ahead, computed with the same rules as
+//!   `change_status` uses when a recurring item is done
+//!
+//! An occurrence's UID only names the event in the feed: it is its item's ID
+//! and date (`<id>-YYYYMMDD`) whatever [`IdScheme`](crate::IdScheme) the
+//! server uses, so it is the same on every export and calendar apps update
+//! the event instead of duplicating it. It is not reserved as an item ID. The
+//! default `slug` scheme happens to give the occurrence that ID once it
+//! becomes a real item, so its event carries on in place; with `ulid` or
+//! `sequential` the item gets a new ID and its event replaces the
+//! occurrence's.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::{Duration, NaiveDate};
+
+/// How far ahead recurring occurrences are listed
+pub const OCCURRENCE_HORIZON_DAYS: i64 = 90;
+
+/// Domain part of every UID
+pub(crate) const UID_DOMAIN: &str = "gtd-mcp";
+
+/// Maximum line length in octets before folding (RFC 5545, section 3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Render the dated items of `data` as an iCalendar document
+///
+/// # Arguments
+/// * `data` - The GtdData to render
+/// * `today` - Start of the occurrence horizon
+pub fn render(data: &GtdData, today: NaiveDate) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!(
+            "PRODID:-//gtd-mcp//gtd-mcp {}//EN",
+            env!("CARGO_PKG_VERSION")
+        ),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:GTD".to_string(),
+    ];
+
+    let horizon = today + Duration::days(OCCURRENCE_HORIZON_DAYS);
+    for nota in data.iter() {
+        let Some(start) = nota.start_date else {
+            continue;
+        };
+        let active_recurring = nota.is_recurring()
+            && !nota.paused
+            && !matches!(nota.status, NotaStatus::done | NotaStatus::trash);
+        if nota.status != NotaStatus::calendar && !active_recurring {
+            continue;
+        }
+        push_event(&mut lines, nota, &nota.id, start);
+        if !active_recurring {
+            continue;
+        }
+
+        let mut date = start;
+        while let Some(next) = nota.calculate_next_occurrence(date) {
+            if next > horizon {
+                break;
+            }
+            // Feed-only UID, see the module documentation
+            let uid = format!("{}-{}", nota.id, next.format("%Y%m%d"));
+            // Occurrences that already exist as items (slug IDs) are exported on their own
+            if next >= today && !data.contains_id(&uid) {
+                push_event(&mut lines, nota, &uid, next);
+            }
+            date = next;
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    let mut ics = String::new();
+    for line in lines {
+        fold_line(&mut ics, &line);
+    }
+    ics
+}
+
+/// Append the lines of one all-day event
+fn push_event(lines: &mut Vec<String>, nota: &Nota, uid: &str, date: NaiveDate) {
+    lines.push("BEGIN:VEVENT".to_string());
+    lines.push(format!("UID:{}@{}", escape_text(uid), UID_DOMAIN));
+    lines.push(format!(
+        "DTSTAMP:{}T000000Z",
+        nota.updated_at.format("%Y%m%d")
+    ));
+    lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+    lines.push(format!(
+        "DTEND;VALUE=DATE:{}",
+        (date + Duration::days(1)).format("%Y%m%d")
+    ));
+    lines.push(format!("SUMMARY:{}", escape_text(&nota.title)));
+    if let Some(notes) = &nota.notes {
+        lines.push(format!("DESCRIPTION:{}", escape_text(notes)));
+    }
+    if let Some(context) = &nota.context {
+        lines.push(format!("CATEGORIES:{}", escape_text(context)));
+    }
+    lines.push("END:VEVENT".to_string());
+}
+
+/// Escape a TEXT value (RFC 5545, section 3.3.11)
+pub(crate) fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append `line` with CRLF, folding it into 75-octet chunks without splitting characters
+pub(crate) fn fold_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
@@ -0,0 +1,158 @@
+//! Generating IDs for items the server creates itself
+//!
+//! Quick capture and completed recurring items need an ID nobody typed. The
+//! handler asks its [`IdGenerator`] for one; [`IdScheme`] provides the
+//! built-in schemes:
+//! - `slug` (default): the title as a kebab-case slug (see [`crate::slug`]),
+//!   and `{id}-{YYYYMMDD}` for the next occurrence of a recurring item,
+//! - `ulid`: a lowercase [ULID](https://github.com/ulid/spec), sortable by
+//!   creation time (monotonic within a millisecond, from the `ulid` crate),
+//! - `sequential`: the next free number (`1`, `2`, ...).
+//!
+//! All built-in schemes satisfy [`crate::IdPolicy::kebab_case`].
+
+use crate::gtd::{GtdData, Nota};
+use crate::slug;
+use chrono::NaiveDate;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Source of the IDs of items the server creates
+pub trait IdGenerator: Send + Sync {
+    /// ID for a new item captured with `title`
+    ///
+    /// # Arguments
+    /// * `data` - Items whose IDs are taken
+    /// * `title` - Title of the new item
+    ///
+    /// # Returns
+    /// The ID, or why none could be generated
+    fn item_id(&self, data: &GtdData, title: &str) -> Result<String, String>;
+
+    /// ID for the occurrence of a recurring item on `date`
+    ///
+    /// An ID that is already taken means the occurrence exists, and no
+    /// item is created.
+    ///
+    /// # Arguments
+    /// * `data` - Items whose IDs are taken
+    /// * `series` - The occurrence being completed
+    /// * `date` - Start date of the next occurrence
+    ///
+    /// # Returns
+    /// The ID, or why none could be generated
+    fn occurrence_id(
+        &self,
+        data: &GtdData,
+        series: &Nota,
+        date: NaiveDate,
+    ) -> Result<String, String>;
+}
+
+/// Built-in ID schemes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdScheme {
+    /// Slug of the title; date suffix for occurrences
+    #[default]
+    Slug,
+    /// Lowercase ULID
+    Ulid,
+    /// Next free number
+    Sequential,
+}
+
+impl IdGenerator for IdScheme {
+    fn item_id(&self, data: &GtdData, title: &str) -> Result<String, String> {
+        match self {
+            IdScheme::Slug => Ok(slug::unique_id(data, title)),
+            IdScheme::Ulid => unused_ulid(data),
+            IdScheme::Sequential => next_number(data),
+        }
+    }
+
+    fn occurrence_id(
+        &self,
+        data: &GtdData,
+        series: &Nota,
+        date: NaiveDate,
+    ) -> Result<String, String> {
+        match self {
+            IdScheme::Slug => Ok(format!("{}-{}", series.id, date.format("%Y%m%d"))),
+            IdScheme::Ulid => unused_ulid(data),
+            IdScheme::Sequential => next_number(data),
+        }
+    }
+}
+
+impl FromStr for IdScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "slug" => Ok(IdScheme::Slug),
+            "ulid" => Ok(IdScheme::Ulid),
+            "sequential" => Ok(IdScheme::Sequential),
+            _ => Err(format!(
+                "Invalid ID scheme '{}'. Valid options are: slug, ulid, sequential",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for IdScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IdScheme::Slug => "slug",
+            IdScheme::Ulid => "ulid",
+            IdScheme::Sequential => "sequential",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Generator shared by every handler, so ULIDs of one process keep increasing
+static ULIDS: Mutex<ulid::Generator> = Mutex::new(ulid::Generator::new());
+
+/// A new lowercase ULID, greater than every ULID generated before in this process
+///
+/// # Returns
+/// The ULID, or an error when the random part of the current millisecond
+/// is used up
+pub fn ulid() -> Result<String, String> {
+    ULIDS
+        .lock()
+        .unwrap()
+        .generate()
+        .map(|ulid| ulid.to_string().to_lowercase())
+        .map_err(|e| format!("Cannot generate a ULID: {}", e))
+}
+
+/// A ULID no item uses
+fn unused_ulid(data: &GtdData) -> Result<String, String> {
+    loop {
+        let id = ulid()?;
+        if !data.contains_id(&id) {
+            return Ok(id);
+        }
+    }
+}
+
+/// One more than the highest all-digit ID (`1` for a file without one)
+fn next_number(data: &GtdData) -> Result<String, String> {
+    let highest = data
+        .iter()
+        .filter_map(|nota| nota.id.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+    highest
+        .checked_add(1)
+        .map(|next| next.to_string())
+        .ok_or_else(|| {
+            format!(
+                "Cannot number a new item: the ID {} is the largest number",
+                highest
+            )
+        })
+}
@@ -0,0 +1,165 @@
+//! ID validation rules
+//!
+//! By default any non-empty string is a valid ID. When several agents write to
+//! the same file, an [`IdPolicy`] keeps new IDs consistent: `inbox` rejects IDs
+//! that break the policy with an error explaining the rule.
+//!
+//! A policy combines up to three rules:
+//! - a regular expression the whole ID must match (e.g. lowercase kebab-case),
+//! - a maximum length in characters,
+//! - characters that must not appear anywhere in the ID.
+
+use regex::Regex;
+
+/// Pattern of [`IdPolicy::kebab_case`]: lowercase words joined by hyphens,
+/// optionally prefixed with `@` for contexts (e.g. "call-john", "@home")
+pub const KEBAB_CASE_PATTERN: &str = "^@?[a-z0-9]+(-[a-z0-9]+)*$";
+
+/// Maximum length of [`IdPolicy::kebab_case`]
+pub const DEFAULT_MAX_LENGTH: usize = 64;
+
+/// Rules every newly created ID must follow
+#[derive(Debug, Clone, Default)]
+pub struct IdPolicy {
+    /// Pattern the whole ID must match
+    pattern: Option<Regex>,
+    /// Maximum number of characters
+    max_length: Option<usize>,
+    /// Characters that must not appear in an ID
+    forbidden_chars: Vec<char>,
+}
+
+impl IdPolicy {
+    /// Strict policy: lowercase kebab-case of at most 64 characters
+    pub fn kebab_case() -> Self {
+        Self {
+            pattern: Some(Regex::new(KEBAB_CASE_PATTERN).expect("valid built-in pattern")),
+            max_length: Some(DEFAULT_MAX_LENGTH),
+            forbidden_chars: Vec::new(),
+        }
+    }
+
+    /// Build a policy from command-line style options
+    ///
+    /// # Arguments
+    /// * `strict` - Start from [`IdPolicy::kebab_case`] instead of no rules
+    /// * `pattern` - Optional pattern replacing the starting one
+    /// * `max_length` - Optional maximum length replacing the starting one
+    /// * `forbidden_chars` - Optional characters to forbid
+    ///
+    /// # Returns
+    /// `None` when no option is set, or an error message for an invalid pattern
+    pub fn from_options(
+        strict: bool,
+        pattern: Option<&str>,
+        max_length: Option<usize>,
+        forbidden_chars: Option<&str>,
+    ) -> Result<Option<Self>, String> {
+        if !strict && pattern.is_none() && max_length.is_none() && forbidden_chars.is_none() {
+            return Ok(None);
+        }
+        let mut policy = if strict {
+            Self::kebab_case()
+        } else {
+            Self::default()
+        };
+        if let Some(pattern) = pattern {
+            policy = policy.with_pattern(pattern)?;
+        }
+        if let Some(max_length) = max_length {
+            policy = policy.with_max_length(max_length);
+        }
+        if let Some(chars) = forbidden_chars {
+            policy = policy.with_forbidden_chars(chars);
+        }
+        Ok(Some(policy))
+    }
+
+    /// Require IDs to match a regular expression
+    ///
+    /// The pattern is matched against the whole ID, so `^` and `$` are implied.
+    ///
+    /// # Arguments
+    /// * `pattern` - Regular expression (e.g., `[a-z]+(-[a-z]+)*`)
+    ///
+    /// # Returns
+    /// The policy, or an error message if the pattern does not compile
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self, String> {
+        let anchored = format!(
+            "^(?:{})$",
+            pattern.trim_start_matches('^').trim_end_matches('$')
+        );
+        let regex = Regex::new(&anchored)
+            .map_err(|e| format!("Invalid ID pattern '{}': {}", pattern, e))?;
+        self.pattern = Some(regex);
+        Ok(self)
+    }
+
+    /// Limit IDs to `max_length` characters
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Forbid every character of `chars` in IDs
+    pub fn with_forbidden_chars(mut self, chars: &str) -> Self {
+        self.forbidden_chars = chars.chars().collect();
+        self
+    }
+
+    /// Check an ID against every rule of the policy
+    ///
+    /// # Arguments
+    /// * `id` - The ID to check
+    ///
+    /// # Returns
+    /// `Ok(())` if the ID is valid, otherwise a message naming the broken rule
+    pub fn check(&self, id: &str) -> Result<(), String> {
+        if let Some(max_length) = self.max_length {
+            let length = id.chars().count();
+            if length > max_length {
+                return Err(format!(
+                    "ID '{}' is {} characters long; IDs may have at most {} characters",
+                    id, length, max_length
+                ));
+            }
+        }
+        if let Some(c) = id.chars().find(|c| self.forbidden_chars.contains(c)) {
+            return Err(format!(
+                "ID '{}' contains '{}'; IDs must not contain any of: {}",
+                id,
+                c,
+                self.forbidden_chars.iter().collect::<String>()
+            ));
+        }
+        if let Some(pattern) = &self.pattern
+            && !pattern.is_match(id)
+        {
+            let mut message = format!("ID '{}' does not match the ID pattern {}", id, pattern);
+            if pattern.as_str() == KEBAB_CASE_PATTERN {
+                message.push_str(" (lowercase words joined by hyphens, e.g. 'call-john')");
+                let suggestion = to_kebab_case(id);
+                if !suggestion.is_empty() {
+                    message.push_str(&format!(" - try '{}'", suggestion));
+                }
+            }
+            return Err(message);
+        }
+        Ok(())
+    }
+}
+
+/// Convert an ID to lowercase kebab-case (keeping a leading `@`)
+fn to_kebab_case(id: &str) -> String {
+    let words: Vec<String> = id
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let prefix = if id.starts_with('@') { "@" } else { "" };
+    if words.is_empty() {
+        String::new()
+    } else {
+        format!("{}{}", prefix, words.join("-"))
+    }
+}
@@ -0,0 +1,169 @@
+//! Importing items from other formats
+//!
+//! Each format module turns its input into notas and merges them into the
+//! data; this module picks the format and refuses imports that would leave the
+//! file inconsistent (e.g. a task linking to a project that does not exist).
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::{csv, taskpaper, todotxt};
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Input format of an import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Spreadsheet rows (see [`crate::csv`] for the columns)
+    Csv,
+    /// One task per line (see [`crate::todotxt`] for the mapping)
+    TodoTxt,
+    /// Outline of projects and tasks (see [`crate::taskpaper`])
+    TaskPaper,
+}
+
+impl ImportFormat {
+    /// Infer the format from a file extension (`.csv`, `.taskpaper`) or a todo.txt
+    /// file name (`todo.txt`, `*.todo.txt`)
+    ///
+    /// # Returns
+    /// The matching format, or None for unknown extensions
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        if todotxt::is_todo_txt_path(path.as_ref()) {
+            return Some(ImportFormat::TodoTxt);
+        }
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(ImportFormat::Csv),
+            "taskpaper" => Some(ImportFormat::TaskPaper),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ImportFormat::Csv),
+            "todotxt" | "todo.txt" => Ok(ImportFormat::TodoTxt),
+            "taskpaper" => Ok(ImportFormat::TaskPaper),
+            _ => Err(format!(
+                "Invalid import format '{}'. Valid options are: csv, todotxt, taskpaper",
+                s
+            )),
+        }
+    }
+}
+
+/// What an import changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// IDs of new items
+    pub added: Vec<String>,
+    /// IDs of existing items that changed
+    pub updated: Vec<String>,
+    /// Number of rows identical to the stored item
+    pub unchanged: usize,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} updated, {} unchanged",
+            self.added.len(),
+            self.updated.len(),
+            self.unchanged
+        )?;
+        if !self.added.is_empty() {
+            write!(f, "\nAdded: {}", self.added.join(", "))?;
+        }
+        if !self.updated.is_empty() {
+            write!(f, "\nUpdated: {}", self.updated.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Merge `text` into `data`, leaving `data` untouched on any error
+///
+/// # Arguments
+/// * `data` - The data to import into
+/// * `text` - Input in the given format
+/// * `format` - Input format
+/// * `today` - Date for items whose input carries no dates
+pub fn import(
+    data: &mut GtdData,
+    text: &str,
+    format: ImportFormat,
+    today: NaiveDate,
+) -> Result<ImportSummary> {
+    let mut imported = data.clone();
+    let summary = match format {
+        ImportFormat::Csv => csv::import(&mut imported, text, today)?,
+        ImportFormat::TodoTxt => todotxt::import(&mut imported, text, today)?,
+        ImportFormat::TaskPaper => taskpaper::import(&mut imported, text, today)?,
+    };
+
+    // Only report problems the import introduced, not ones already in the file
+    let before = data.check_integrity();
+    let new_issues: Vec<String> = imported
+        .check_integrity()
+        .into_iter()
+        .filter(|issue| !before.contains(issue))
+        .collect();
+    if !new_issues.is_empty() {
+        bail!(
+            "Import refused, it would introduce {} problem{}:\n- {}",
+            new_issues.len(),
+            if new_issues.len() == 1 { "" } else { "s" },
+            new_issues.join("\n- ")
+        );
+    }
+
+    *data = imported;
+    Ok(summary)
+}
+
+/// Return the stored ID of `id`, creating a project or context with that ID if missing
+pub(crate) fn ensure_item(
+    data: &mut GtdData,
+    id: &str,
+    status: NotaStatus,
+    today: NaiveDate,
+    summary: &mut ImportSummary,
+) -> String {
+    if let Some(stored) = data.resolve_id(id) {
+        return stored.to_string();
+    }
+    data.add(Nota {
+        id: id.to_string(),
+        title: id.trim_start_matches('@').to_string(),
+        status,
+        created_at: today,
+        updated_at: today,
+        ..Default::default()
+    });
+    summary.added.push(id.to_string());
+    id.to_string()
+}
+
+/// Return the stored ID of the context named by `tag` (e.g. `@home`), creating it if missing
+///
+/// `@home` also matches a context stored as `home`.
+pub(crate) fn ensure_context(
+    data: &mut GtdData,
+    tag: &str,
+    today: NaiveDate,
+    summary: &mut ImportSummary,
+) -> String {
+    match data.resolve_id(&tag[1..]) {
+        Some(stored) if data.find_by_id(stored).is_some_and(|n| n.is_context()) => {
+            stored.to_string()
+        }
+        _ => ensure_item(data, tag, NotaStatus::context, today, summary),
+    }
+}
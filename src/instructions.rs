@@ -0,0 +1,31 @@
+//! Instructions sent to MCP clients
+//!
+//! Clients pass the server's `instructions` from the `initialize` response to
+//! the model, usually in its system prompt, so they are where house rules
+//! belong: ID conventions, which contexts exist, how the team runs its weekly
+//! review. The built-in text summarizes the GTD workflow; the `instructions`
+//! option replaces it, and `{default}` in the custom text stands for the
+//! built-in text so a team can add its rules after it.
+
+/// Stands for the built-in instructions in a custom text
+pub const DEFAULT_PLACEHOLDER: &str = "{default}";
+
+/// Built-in instructions
+pub const DEFAULT_INSTRUCTIONS: &str = "\
+GTD task management server implementing David Allen's methodology.
+Workflow: Capture(inbox) → Review(list) → Clarify(update) → Organize(change_status) → Do → Purge(empty_trash)
+
+**Statuses**: inbox(start) | next_action(ready) | waiting_for(blocked) | later(deferred) | calendar(dated) | someday(maybe) | done | reference | trash
+**Types**: task | project(multi-step) | context(@location) | goal(1-3 year horizon)
+**IDs**: Use meaningful strings (e.g., \"call-john\", \"website-redesign\")";
+
+/// The instructions to send
+///
+/// # Arguments
+/// * `custom` - Text configured with `instructions` (None: the built-in text)
+pub fn render(custom: Option<&str>) -> String {
+    match custom {
+        Some(custom) => custom.replace(DEFAULT_PLACEHOLDER, DEFAULT_INSTRUCTIONS),
+        None => DEFAULT_INSTRUCTIONS.to_string(),
+    }
+}
@@ -0,0 +1,136 @@
+//! Write-ahead journal for crash recovery
+//!
+//! Every save is appended to a journal next to the data file (`gtd.toml` →
+//! `gtd.journal.jsonl`) before the data file is rewritten, and dropped from it
+//! once the write has reached the disk. With debouncing, the journal is what
+//! keeps a batch of buffered saves from vanishing when the process dies before
+//! the flush.
+//!
+//! Each line is one JSON [`Entry`] holding the full serialized data, so replay
+//! only needs the newest entry; the older ones contribute their commit
+//! messages. A line torn by a crash during the append is ignored.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One journaled save
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Increasing sequence number, used to drop entries once written
+    pub seq: u64,
+    /// Commit message of the save
+    pub message: String,
+    /// Serialized TOML content (LF line endings)
+    pub content: String,
+}
+
+/// Append-only log of saves not yet written to the data file
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    /// Last sequence number handed out; also serializes all file access
+    last_seq: Mutex<u64>,
+}
+
+impl Journal {
+    /// Journal of the given data file
+    ///
+    /// Sequence numbers continue after any entries left by a previous run.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the GTD data file
+    pub fn for_data_file(file_path: &Path) -> Self {
+        let stem = file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "gtd".to_string());
+        let path = file_path.with_file_name(format!("{}.journal.jsonl", stem));
+        let last_seq = read_entries(&path)
+            .last()
+            .map(|entry| entry.seq)
+            .unwrap_or(0);
+        Self {
+            path,
+            last_seq: Mutex::new(last_seq),
+        }
+    }
+
+    /// Path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Durably record a save before it is written
+    ///
+    /// # Returns
+    /// The entry's sequence number, to pass to [`Journal::discard_through`]
+    pub fn append(&self, content: &str, message: &str) -> Result<u64> {
+        let mut last_seq = self.last_seq.lock().unwrap();
+        let entry = Entry {
+            seq: *last_seq + 1,
+            message: message.to_string(),
+            content: content.to_string(),
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        file.sync_data()?;
+        *last_seq = entry.seq;
+        Ok(entry.seq)
+    }
+
+    /// Saves recorded but not yet written, oldest first
+    pub fn entries(&self) -> Vec<Entry> {
+        let _guard = self.last_seq.lock().unwrap();
+        read_entries(&self.path)
+    }
+
+    /// Drop every entry up to and including `seq` once its write is on disk
+    ///
+    /// Entries appended meanwhile are kept; the file is removed when none remain.
+    pub fn discard_through(&self, seq: u64) -> Result<()> {
+        let _guard = self.last_seq.lock().unwrap();
+        let remaining: Vec<Entry> = read_entries(&self.path)
+            .into_iter()
+            .filter(|entry| entry.seq > seq)
+            .collect();
+        if remaining.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        }
+        let mut content = String::new();
+        for entry in &remaining {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        // Replace the file atomically so a crash here cannot lose the kept entries
+        let temp_path = self.path.with_extension("jsonl.tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Parse the journal file, skipping lines that are not complete entries
+fn read_entries(path: &Path) -> Vec<Entry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
@@ -8,6 +8,7 @@
 //!
 //! The library follows a 3-layer architecture:
 //! - **MCP Layer**: `GtdServerHandler` - Handles MCP protocol communication
+//!   (the core tools are adapters over the typed [`service::GtdService`])
 //! - **Domain Layer**: `gtd` module - Core GTD data models and business logic
 //! - **Persistence Layer**: `storage` module - File-based TOML storage with Git sync
 //!
@@ -25,26 +26,91 @@
 //! }
 //! ```
 
+pub mod availability;
+pub mod board;
+pub mod builder;
+pub mod caldav;
+pub mod checksum;
+pub mod clarify;
+pub mod cli;
+pub mod commit_message;
+pub mod config;
+pub mod confirm;
+pub mod csv;
+pub mod daemon;
+pub mod discovery;
+mod dispatch;
+pub mod duplicates;
+pub mod email;
+pub mod escalation;
+pub mod external_notes;
+pub mod focus;
 pub mod formatting;
 pub mod git_ops;
+pub mod github;
+pub mod goals;
 pub mod gtd;
 pub mod handlers;
+pub mod hooks;
+pub mod http;
+pub mod ical;
+pub mod id_generator;
+pub mod id_policy;
+pub mod import;
+pub mod instructions;
+pub mod journal;
+pub mod mcp_http;
+pub mod merge_file;
 pub mod migration;
+pub mod mirror;
+pub mod progress;
+pub mod push_worker;
+pub mod report;
+pub mod rest;
+pub mod retention;
+pub mod roots;
+pub mod schema;
+pub mod service;
+mod session;
+pub mod slug;
 pub mod storage;
+pub mod suggest;
+pub mod taskpaper;
+pub mod templates;
+pub mod todotxt;
+pub mod tool_hints;
+pub mod tree;
+pub mod trends;
 pub mod validation;
+pub mod webhook;
+pub mod wip;
+pub mod workspace;
 
 use anyhow::Result;
 
+use dispatch::McpServer;
 use mcp_attr::Result as McpResult;
-use mcp_attr::server::{McpServer, mcp_server};
-use std::sync::Mutex;
+use mcp_attr::schema::{ListResourcesRequestParams, ListResourcesResult, Root};
+use mcp_attr::server::{RequestContext, mcp_server};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
 
 // Re-export for integration tests (McpServer trait already in scope above)
 
 // Re-export commonly used types
-pub use git_ops::GitOps;
-pub use gtd::{GtdData, Nota, NotaStatus, local_date_today};
-pub use storage::Storage;
+pub use builder::GtdServerHandlerBuilder;
+pub use commit_message::{CommitItem, CommitTemplate};
+pub use duplicates::DuplicateCheck;
+pub use git_ops::{GitConflict, GitOps};
+pub use gtd::{Clock, FixedClock, GtdData, Nota, NotaStatus, SystemClock, local_date_today};
+pub use hooks::{Event, Hooks};
+pub use id_generator::{IdGenerator, IdScheme};
+pub use id_policy::IdPolicy;
+pub use progress::{Cancellation, Cancelled, Progress};
+pub use service::{GtdService, ServiceError};
+pub use storage::{ExternalChange, Snapshot, Storage};
 
 /// MCP Server handler for GTD task management
 ///
@@ -52,8 +118,95 @@ pub use storage::Storage;
 /// project tracking, and context organization. All changes are automatically
 /// persisted to a TOML file and optionally synchronized with Git.
 pub struct GtdServerHandler {
-    pub data: Mutex<GtdData>,
-    pub storage: Storage,
+    /// In-memory GTD data of the active workspace; read-only tools share the
+    /// lock, mutations take it exclusively
+    pub data: RwLock<GtdData>,
+    /// Storage of the active workspace (see [`Self::storage`])
+    storage: std::sync::RwLock<Storage>,
+    /// Active workspace name and the parked inactive workspaces
+    workspaces: std::sync::Mutex<workspace::Workspaces>,
+    /// Serializes persistence so concurrent tool calls write to disk in order
+    write_queue: Arc<tokio::sync::Mutex<()>>,
+    /// Format of the Git commit messages written for each change
+    commit_template: CommitTemplate,
+    /// Rules new IDs must follow (None accepts any ID)
+    id_policy: Option<IdPolicy>,
+    /// IDs of captured items and recurring occurrences (see [`id_generator`])
+    id_generator: Arc<dyn IdGenerator>,
+    /// What inbox does when a title nearly matches an open item
+    duplicate_check: DuplicateCheck,
+    /// Most items each status may hold
+    wip_limits: Vec<wip::WipLimit>,
+    /// Whether a move over a WIP limit is warned about or refused
+    wip_mode: wip::WipMode,
+    /// Days a waiting item may wait before `suggest` escalates it (None: only
+    /// items with their own threshold)
+    escalate_after_days: Option<u32>,
+    /// Move waiting items due for escalation back to next_action
+    escalate_to_next_action: bool,
+    /// Days in next_action after which `list` flags an item (None: never)
+    stale_after_days: Option<u32>,
+    /// Days trash items are kept before a save purges them (None: until empty_trash)
+    trash_retention_days: Option<u32>,
+    /// Saves deferred by an open transaction (`None` when no transaction is open)
+    transaction: std::sync::Mutex<Option<Transaction>>,
+    /// How long another client waits for an idle transaction before it is
+    /// aborted (see [`with_transaction_timeout`](Self::with_transaction_timeout))
+    transaction_timeout: std::time::Duration,
+    /// Snapshots taken by `backup_now` with their numbers, oldest first (at
+    /// most [`MAX_BACKUPS`])
+    backups: std::sync::Mutex<Vec<(usize, Snapshot)>>,
+    /// Context the user is in, by session (`set_current_context`; see
+    /// [`session`]); `list` of next actions and `suggest` default to it
+    current_context: std::sync::Mutex<HashMap<Option<session::SessionId>, String>>,
+    /// Refuse to save data that fails [`GtdData::check_integrity`]
+    strict: bool,
+    /// CalDAV task list synced by `sync_caldav` (None when not configured)
+    caldav: Option<caldav::CalDavConfig>,
+    /// Commit message of every save, for clients that share this handler
+    changes: tokio::sync::broadcast::Sender<String>,
+    /// Opens the data file found in the client's workspace roots (`None` when
+    /// the server was given a file, or once a root was adopted)
+    roots: tokio::sync::Mutex<Option<StorageOpener>>,
+    /// Instructions sent to clients instead of the built-in ones (see
+    /// [`instructions`])
+    instructions: Option<String>,
+    /// Only the tools that read the data are served (see
+    /// [`with_read_only`](Self::with_read_only))
+    read_only: bool,
+    /// Where "today" comes from (see [`gtd::Clock`])
+    clock: Arc<dyn Clock>,
+    /// Callbacks for created, moved and deleted items (see [`hooks`])
+    hooks: Hooks,
+    /// Data as last saved or loaded, compared with the next save to find the
+    /// events for the hooks (`None` without hooks)
+    saved: std::sync::Mutex<Option<GtdData>>,
+}
+
+/// Opens the storage of a data file with the server's storage options
+pub type StorageOpener = Arc<dyn Fn(&Path) -> Result<Storage> + Send + Sync>;
+
+/// Most snapshots `backup_now` keeps; taking another drops the oldest
+pub const MAX_BACKUPS: usize = 10;
+
+/// How long a transaction may sit idle before another client's change aborts it
+pub const DEFAULT_TRANSACTION_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(15 * 60);
+
+/// Why a change is refused while another client's transaction is open
+const FOREIGN_TRANSACTION: &str =
+    "Another client has a transaction open; try again once it is committed or aborted";
+
+/// An open transaction (see [`GtdServerHandler::begin_transaction`])
+struct Transaction {
+    /// Commit messages of the deferred saves, oldest first
+    messages: Vec<String>,
+    /// Data as it was when the transaction began, restored on abort or a failed commit
+    snapshot: GtdData,
+    /// Session that began the transaction (see [`session`])
+    owner: Option<session::SessionId>,
+    /// When the owner last changed something in the transaction
+    touched: std::time::Instant,
 }
 
 impl GtdServerHandler {
@@ -76,9 +229,372 @@ impl GtdServerHandler {
     /// # }
     /// ```
     pub fn new(storage_path: &str, sync_git: bool) -> Result<Self> {
-        let storage = Storage::new(storage_path, sync_git);
-        let data = Mutex::new(storage.load()?);
-        Ok(Self { data, storage })
+        Self::with_storage(Storage::new(storage_path, sync_git))
+    }
+
+    /// Create a new GTD server handler from a pre-configured storage
+    ///
+    /// Use this when the storage needs options beyond path and Git sync,
+    /// such as a debounce window.
+    ///
+    /// # Arguments
+    /// * `storage` - Storage used to load and persist GTD data
+    ///
+    /// # Returns
+    /// Result containing the handler or an error
+    pub fn with_storage(storage: Storage) -> Result<Self> {
+        let data = RwLock::new(storage.load()?);
+        let name = workspace::default_workspace_name(storage.file_path());
+        Ok(Self {
+            data,
+            storage: std::sync::RwLock::new(storage),
+            workspaces: std::sync::Mutex::new(workspace::Workspaces::new(name)),
+            write_queue: Arc::new(tokio::sync::Mutex::new(())),
+            commit_template: CommitTemplate::default(),
+            id_policy: None,
+            id_generator: Arc::new(IdScheme::default()),
+            duplicate_check: DuplicateCheck::default(),
+            wip_limits: Vec::new(),
+            wip_mode: wip::WipMode::default(),
+            escalate_after_days: None,
+            escalate_to_next_action: false,
+            stale_after_days: Some(formatting::DEFAULT_STALE_AFTER_DAYS),
+            trash_retention_days: None,
+            transaction: std::sync::Mutex::new(None),
+            transaction_timeout: DEFAULT_TRANSACTION_TIMEOUT,
+            backups: std::sync::Mutex::new(Vec::new()),
+            current_context: std::sync::Mutex::new(HashMap::new()),
+            strict: false,
+            caldav: None,
+            changes: tokio::sync::broadcast::channel(64).0,
+            roots: tokio::sync::Mutex::new(None),
+            instructions: None,
+            read_only: false,
+            clock: Arc::new(SystemClock::default()),
+            hooks: Hooks::default(),
+            saved: std::sync::Mutex::new(None),
+        })
+    }
+
+    /// Replace the data file with one in the client's workspace roots
+    ///
+    /// For a server started without a data file: the first tool call asks
+    /// the client for its roots and adopts `gtd.toml` in the first one (see
+    /// [`roots`]). Until then the handler's own file is only a placeholder.
+    ///
+    /// # Arguments
+    /// * `open` - Opens the storage of the data file found in the root
+    pub fn with_roots(mut self, open: StorageOpener) -> Self {
+        self.roots = tokio::sync::Mutex::new(Some(open));
+        self
+    }
+
+    /// Whether the data file still waits for the client's workspace roots
+    pub async fn waits_for_roots(&self) -> bool {
+        self.roots.lock().await.is_some()
+    }
+
+    /// Adopt `gtd.toml` in the first `file://` root of the client
+    ///
+    /// The file is created with the starter contexts when it does not exist.
+    /// Does nothing once a root was adopted, or when the server was given a
+    /// file.
+    ///
+    /// # Returns
+    /// The path of the data file in use, or an error when no root is a local
+    /// directory or the file cannot be opened
+    pub async fn adopt_roots(&self, roots: &[Root]) -> Result<PathBuf> {
+        let mut pending = self.roots.lock().await;
+        let Some(open) = pending.clone() else {
+            return Ok(self.storage().file_path().to_path_buf());
+        };
+        let Some(path) = roots::data_file(roots) else {
+            anyhow::bail!(
+                "The client shared no local workspace root (file:// URI) to keep {} in. Open a folder in the client, or start the server with the path of the data file.",
+                discovery::DATA_FILE_NAME
+            );
+        };
+        let opened = path.clone();
+        let (storage, data) = tokio::task::spawn_blocking(move || {
+            discovery::init_data_file(&opened)?;
+            let storage = open(&opened)?;
+            let data = storage.load()?;
+            anyhow::Ok((storage, data))
+        })
+        .await??;
+
+        let _queue = self.write_queue.lock().await;
+        self.adopt_saved(&data);
+        *self.data.write().await = data;
+        *self.storage.write().unwrap() = storage.with_clock(self.clock.clone());
+        *pending = None;
+        Ok(path)
+    }
+
+    /// Serve another data file as a workspace that `switch_workspace` can activate
+    ///
+    /// The workspace keeps its own data and storage (and so its own Git
+    /// repository); the data is loaded right away so problems show at startup.
+    /// The handler's own file is the workspace named after its file stem.
+    ///
+    /// # Arguments
+    /// * `name` - Workspace name (letters, digits, '-' and '_')
+    /// * `storage` - Storage of the workspace's data file
+    pub fn with_workspace(self, name: &str, storage: Storage) -> Result<Self> {
+        let data = storage.load()?;
+        let storage = storage.with_clock(self.clock.clone());
+        self.workspaces.lock().unwrap().add(name, data, storage)?;
+        Ok(self)
+    }
+
+    /// Storage of the active workspace
+    pub fn storage(&self) -> Storage {
+        self.storage.read().unwrap().clone()
+    }
+
+    /// Name of the active workspace
+    pub fn workspace(&self) -> String {
+        self.workspaces.lock().unwrap().active().to_string()
+    }
+
+    /// Receive the commit message of every save from now on
+    ///
+    /// Lets clients sharing this handler (see [`mcp_http`]) learn that the data
+    /// changed. Saves deferred by a transaction arrive as one message when it
+    /// is committed.
+    pub fn subscribe_changes(&self) -> tokio::sync::broadcast::Receiver<String> {
+        self.changes.subscribe()
+    }
+
+    /// The `sync` tool without progress notifications
+    pub async fn sync(&self) -> McpResult<String> {
+        self.handle_sync(Progress::none()).await
+    }
+
+    /// The `sync_caldav` tool without progress notifications
+    pub async fn sync_caldav(&self) -> McpResult<String> {
+        self.handle_sync_caldav(Progress::none()).await
+    }
+
+    /// The `import_github` tool without progress notifications
+    pub async fn import_github(&self, repo: String, filter: Option<String>) -> McpResult<String> {
+        self.handle_import_github(repo, filter, Progress::none())
+            .await
+    }
+
+    /// The `merge_file` tool without progress notifications
+    pub async fn merge_file(&self, path: String, strategy: Option<String>) -> McpResult<String> {
+        self.handle_merge_file(path, strategy, Progress::none())
+            .await
+    }
+
+    /// Context set with `set_current_context` in the running session, if any
+    ///
+    /// Each MCP session of a shared handler has its own current context;
+    /// calls outside any session share one.
+    pub fn current_context(&self) -> Option<String> {
+        self.current_context
+            .lock()
+            .unwrap()
+            .get(&session::current())
+            .cloned()
+    }
+
+    /// Set (or clear, with `None`) the current context of the running session
+    pub(crate) fn set_session_context(&self, name: Option<String>) -> Option<String> {
+        let mut contexts = self.current_context.lock().unwrap();
+        match name {
+            Some(name) => contexts.insert(session::current(), name),
+            None => contexts.remove(&session::current()),
+        }
+    }
+
+    /// Use a custom format for Git commit messages
+    ///
+    /// # Arguments
+    /// * `template` - Commit message template (see [`commit_message`])
+    pub fn with_commit_template(mut self, template: CommitTemplate) -> Self {
+        self.commit_template = template;
+        self
+    }
+
+    /// Send custom instructions to clients
+    ///
+    /// # Arguments
+    /// * `text` - Instructions for the model; `{default}` stands for the
+    ///   built-in ones (see [`instructions`])
+    pub fn with_instructions(mut self, text: String) -> Self {
+        self.instructions = Some(text);
+        self
+    }
+
+    /// Enforce ID rules when items are created
+    ///
+    /// # Arguments
+    /// * `policy` - ID validation rules (see [`id_policy`])
+    pub fn with_id_policy(mut self, policy: IdPolicy) -> Self {
+        self.id_policy = Some(policy);
+        self
+    }
+
+    /// Generate the IDs of captured items and recurring occurrences
+    ///
+    /// # Arguments
+    /// * `generator` - An [`IdScheme`] or a custom generator (see [`id_generator`])
+    pub fn with_id_generator(mut self, generator: Arc<dyn IdGenerator>) -> Self {
+        self.id_generator = generator;
+        self
+    }
+
+    /// Choose how inbox handles titles that nearly match an open item
+    ///
+    /// # Arguments
+    /// * `check` - Off, warn (the default) or reject (see [`duplicates`])
+    pub fn with_duplicate_check(mut self, check: DuplicateCheck) -> Self {
+        self.duplicate_check = check;
+        self
+    }
+
+    /// Cap how many items a status may hold
+    ///
+    /// # Arguments
+    /// * `limits` - Maximum per status (see [`wip`])
+    /// * `mode` - Warn (the default) or refuse when a move exceeds a limit
+    pub fn with_wip_limits(mut self, limits: Vec<wip::WipLimit>, mode: wip::WipMode) -> Self {
+        self.wip_limits = limits;
+        self.wip_mode = mode;
+        self
+    }
+
+    /// Escalate waiting items that wait too long
+    ///
+    /// # Arguments
+    /// * `after_days` - Days a waiting item may wait unless it has its own
+    ///   threshold (see [`escalation`])
+    /// * `to_next_action` - Move escalated items back to next_action instead of
+    ///   only marking them in `suggest`
+    pub fn with_escalation(mut self, after_days: Option<u32>, to_next_action: bool) -> Self {
+        self.escalate_after_days = after_days;
+        self.escalate_to_next_action = to_next_action;
+        self
+    }
+
+    /// Flag next actions that have been in next_action for too long in `list`
+    ///
+    /// # Arguments
+    /// * `days` - Days after which an item is flagged (default
+    ///   [`formatting::DEFAULT_STALE_AFTER_DAYS`]); 0 turns the flag off
+    pub fn with_stale_after_days(mut self, days: u32) -> Self {
+        self.stale_after_days = (days > 0).then_some(days);
+        self
+    }
+
+    /// Purge trash items older than `days` on every save
+    ///
+    /// See [`retention`]. The purged IDs are listed in the commit message of
+    /// the save that removed them.
+    ///
+    /// # Arguments
+    /// * `days` - Days an item stays in the trash
+    pub fn with_trash_retention(mut self, days: u32) -> Self {
+        self.trash_retention_days = Some(days);
+        self
+    }
+
+    /// Abort a transaction left idle this long when another client changes the data
+    ///
+    /// A client that began a transaction and went away without ending its
+    /// session would otherwise block every other client's changes. Until the
+    /// timeout passes, those changes are refused.
+    ///
+    /// # Arguments
+    /// * `timeout` - Idle time after which the transaction may be aborted
+    ///   (default [`DEFAULT_TRANSACTION_TIMEOUT`])
+    pub fn with_transaction_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.transaction_timeout = timeout;
+        self
+    }
+
+    /// Validate the whole data set before every save
+    ///
+    /// A save whose data fails [`GtdData::check_integrity`] writes nothing: the
+    /// tool returns the validation report as its error and the in-memory data is
+    /// reloaded from the last saved state.
+    pub fn with_strict_saves(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Sync next actions and calendar items with a CalDAV task list through `sync_caldav`
+    ///
+    /// # Arguments
+    /// * `config` - Task list URL, credentials and conflict policy (see [`caldav`])
+    pub fn with_caldav(mut self, config: caldav::CalDavConfig) -> Self {
+        self.caldav = Some(config);
+        self
+    }
+
+    /// Serve only the tools that read the data
+    ///
+    /// MCP clients (and the REST API) see only the read-only tools of
+    /// [`tool_hints`]; calling any other tool fails, and the daemon's
+    /// maintenance jobs do not run. Methods called on the handler directly are
+    /// not restricted.
+    pub fn with_read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Take the date and time from `clock` instead of the system time
+    ///
+    /// Every tool then sees the clock's "today": recurrence, the tickler,
+    /// item ages, stale flags, escalation and the daemon's daily jobs, as do
+    /// the storages of all workspaces (see [`Storage::with_clock`]).
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        let storage = self.storage.get_mut().unwrap();
+        *storage = storage.clone().with_clock(clock.clone());
+        self.workspaces.get_mut().unwrap().set_clock(&clock);
+        self.clock = clock;
+        self
+    }
+
+    /// Call `hooks` when saved changes create, move or delete items
+    ///
+    /// # Arguments
+    /// * `hooks` - Callbacks for each kind of [`Event`] (see [`hooks`])
+    pub fn with_hooks(mut self, hooks: Hooks) -> Self {
+        let saved = (!hooks.is_empty()).then(|| self.data.get_mut().clone());
+        *self.saved.get_mut().unwrap() = saved;
+        self.hooks = hooks;
+        self
+    }
+
+    /// Where the handler takes the date and time from
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Whether only the tools that read the data are served
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Build the validation report that blocks a strict save, if any
+    fn integrity_report(&self, data: &GtdData) -> Option<String> {
+        if self.strict {
+            integrity_report(data)
+        } else {
+            None
+        }
+    }
+
+    /// Build the commit message for a change using the configured template
+    ///
+    /// # Arguments
+    /// * `action` - What happened (e.g., "Add", "Update")
+    /// * `items` - Items affected by the change
+    pub(crate) fn commit_message(&self, action: &str, items: &[CommitItem]) -> String {
+        self.commit_template.render(action, items)
     }
 
     /// Save GTD data with a default commit message.
@@ -87,50 +603,492 @@ impl GtdServerHandler {
     /// defined in `Storage::save()`, which is "Update GTD data".
     /// This is typically called by handler modules after modifying GTD data,
     /// following the MCP tool implementation pattern.
+    ///
+    /// Being synchronous, this does not wait for the data lock: it fails if a
+    /// tool currently holds it. Tools use `save_data_with_message` instead.
     pub fn save_data(&self) -> Result<()> {
-        let data = self.data.lock().unwrap();
-        self.storage.save(&data)?;
+        let mut data = self
+            .data
+            .try_write()
+            .map_err(|_| anyhow::anyhow!("GTD data is locked by a running tool"))?;
+        let content = Storage::serialize(&data)?;
+        if !data.is_dirty(&content) {
+            return Ok(());
+        }
+        let storage = self.storage();
+        if let Some(report) = self.integrity_report(&data) {
+            storage.flush()?;
+            *data = storage.load_local()?;
+            self.adopt_saved(&data);
+            anyhow::bail!(report);
+        }
+        storage.write_content(&content, "Update GTD data")?;
+        data.mark_saved(&content);
+        let events = self.saved_events(&data);
+        drop(data);
+        // No subscribers is fine
+        let _ = self.changes.send("Update GTD data".to_string());
+        self.hooks.fire(&events);
         Ok(())
     }
 
     /// Save GTD data with a custom commit message.
     ///
     /// Persists the current GTD data to disk and creates a Git commit using the provided message.
+    /// The data is serialized while holding the lock, then file and Git I/O run on a
+    /// blocking worker thread so the async executor is never blocked. Saves are queued
+    /// in call order, so a later snapshot can never be overwritten by an earlier one.
+    /// When the state matches what was last saved, nothing is written or committed.
+    ///
+    /// Inside a transaction the save is deferred until [`Self::commit_transaction`].
+    /// A save of another session fails instead: it would write the changes of
+    /// a transaction that is not committed yet.
     ///
     /// # Arguments
     /// * `message` - Commit message to use for the Git version history.
-    pub(crate) fn save_data_with_message(&self, message: &str) -> Result<()> {
-        let data = self.data.lock().unwrap();
-        self.storage.save_with_message(&data, message)?;
+    pub(crate) async fn save_data_with_message(&self, message: &str) -> Result<()> {
+        if let Some(transaction) = self.transaction.lock().unwrap().as_mut() {
+            if transaction.owner != session::current() {
+                anyhow::bail!(FOREIGN_TRANSACTION);
+            }
+            transaction.messages.push(message.to_string());
+            transaction.touched = std::time::Instant::now();
+            return Ok(());
+        }
+        self.write_data(message).await
+    }
+
+    /// The GTD operations with typed arguments and results
+    ///
+    /// The service works on this handler's data under its options and saves
+    /// through it (see [`service::Persist`]).
+    pub fn service(&self) -> GtdService<'_> {
+        let rules = service::Rules {
+            clock: self.clock.clone(),
+            id_policy: self.id_policy.clone(),
+            id_generator: self.id_generator.clone(),
+            duplicate_check: self.duplicate_check,
+            wip_limits: self.wip_limits.clone(),
+            wip_mode: self.wip_mode,
+            commit_template: self.commit_template.clone(),
+            current_context: self.current_context(),
+        };
+        GtdService::new(&self.data, rules, self)
+    }
+
+    /// Begin a transaction that groups the following changes into one save
+    ///
+    /// Until [`Self::commit_transaction`] is called, tools change the in-memory
+    /// data only; their saves are recorded instead of written. The data as it is
+    /// now is kept so [`Self::abort_transaction`] can restore it.
+    ///
+    /// The transaction belongs to the MCP session that began it (see
+    /// [`SharedServer`](daemon::SharedServer)). Only that session can commit or
+    /// abort it; other sessions may read, but their changes are refused until
+    /// it ends. When the session ends, the transaction is aborted.
+    ///
+    /// # Returns
+    /// An error if a transaction is already open
+    pub async fn begin_transaction(&self) -> Result<()> {
+        if self.transaction.lock().unwrap().is_some() {
+            anyhow::bail!("A transaction is already open");
+        }
+        // Let running saves finish, so none of them is taken into the transaction
+        let _queue = self.write_queue.lock().await;
+        let snapshot = self.data.read().await.clone();
+        let mut transaction = self.transaction.lock().unwrap();
+        if transaction.is_some() {
+            anyhow::bail!("A transaction is already open");
+        }
+        *transaction = Some(Transaction {
+            messages: Vec::new(),
+            snapshot,
+            owner: session::current(),
+            touched: std::time::Instant::now(),
+        });
         Ok(())
     }
+
+    /// Commit the open transaction with exactly one file write and Git commit
+    ///
+    /// The commit message lists every deferred change (a single change keeps
+    /// its own message). Nothing is written when no change was made. When the
+    /// save fails, the data is rolled back to the state the transaction began
+    /// with, so either every change persists or none does.
+    ///
+    /// # Returns
+    /// The number of changes committed, or an error if no transaction of this
+    /// session is open or the save failed
+    pub async fn commit_transaction(&self) -> Result<usize> {
+        let transaction = {
+            let mut open = self.transaction.lock().unwrap();
+            match open.as_ref() {
+                None => anyhow::bail!("No transaction is open"),
+                Some(transaction) if transaction.owner != session::current() => {
+                    anyhow::bail!(FOREIGN_TRANSACTION)
+                }
+                Some(_) => open.take().unwrap(),
+            }
+        };
+        if transaction.messages.is_empty() {
+            return Ok(0);
+        }
+        let message = storage::summarize_commit_messages(&transaction.messages);
+        // A save can fail after writing the file (e.g. in Git); keep what is on
+        // disk now so the file can be rolled back as well
+        let storage = self.storage();
+        let backup = tokio::task::spawn_blocking(move || storage.snapshot().ok())
+            .await
+            .ok()
+            .flatten();
+        if let Err(e) = self.write_data(&message).await {
+            // A file changed by someone else was not written and is left alone
+            if let Some(backup) = backup
+                && e.downcast_ref::<ExternalChange>().is_none()
+            {
+                let storage = self.storage();
+                match tokio::task::spawn_blocking(move || storage.restore(&backup)).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(restore_error)) => eprintln!(
+                        "Warning: Failed to restore the data file after a failed commit: {}",
+                        restore_error
+                    ),
+                    Err(restore_error) => eprintln!(
+                        "Warning: Failed to restore the data file after a failed commit: {}",
+                        restore_error
+                    ),
+                }
+            }
+            *self.data.write().await = transaction.snapshot;
+            return Err(e.context("The transaction was rolled back"));
+        }
+        Ok(transaction.messages.len())
+    }
+
+    /// Abort the open transaction, restoring the data it began with
+    ///
+    /// Nothing is written: the changes made since [`Self::begin_transaction`]
+    /// were never saved.
+    ///
+    /// # Returns
+    /// The number of changes rolled back, or an error if no transaction of this
+    /// session is open
+    pub async fn abort_transaction(&self) -> Result<usize> {
+        let session = session::current();
+        match self
+            .roll_back(|transaction| transaction.owner == session)
+            .await
+        {
+            Some(count) => Ok(count),
+            None if self.in_transaction() => anyhow::bail!(FOREIGN_TRANSACTION),
+            None => anyhow::bail!("No transaction is open"),
+        }
+    }
+
+    /// Whether a transaction is open, in any session
+    pub fn in_transaction(&self) -> bool {
+        self.transaction.lock().unwrap().is_some()
+    }
+
+    /// Make sure the running tool call may change the data
+    ///
+    /// Nothing stands in the way without a transaction or inside the session
+    /// that began it. A transaction of another session refuses the change,
+    /// unless it has been idle for longer than the transaction timeout: then it
+    /// is aborted and the change goes ahead.
+    pub(crate) async fn claim_transaction(&self) -> Result<()> {
+        let session = session::current();
+        let timeout = self.transaction_timeout;
+        loop {
+            {
+                let mut open = self.transaction.lock().unwrap();
+                match open.as_mut() {
+                    None => return Ok(()),
+                    Some(transaction) if transaction.owner == session => {
+                        transaction.touched = std::time::Instant::now();
+                        return Ok(());
+                    }
+                    Some(transaction) if transaction.touched.elapsed() < timeout => {
+                        anyhow::bail!(FOREIGN_TRANSACTION)
+                    }
+                    Some(_) => {}
+                }
+            }
+            let idle = |transaction: &Transaction| {
+                transaction.owner != session && transaction.touched.elapsed() >= timeout
+            };
+            if let Some(count) = self.roll_back(idle).await {
+                eprintln!(
+                    "Warning: Aborted a transaction idle for more than {}s: {} change(s) rolled back",
+                    timeout.as_secs(),
+                    count
+                );
+            }
+        }
+    }
+
+    /// Forget the current context of a session that ended and abort its
+    /// transaction, if it is still open
+    pub(crate) async fn end_session(&self, session: session::SessionId) {
+        self.current_context.lock().unwrap().remove(&Some(session));
+        let owned = |transaction: &Transaction| transaction.owner == Some(session);
+        if let Some(count) = self.roll_back(owned).await
+            && count > 0
+        {
+            eprintln!(
+                "Warning: Aborted the transaction of a client that went away: {} change(s) rolled back",
+                count
+            );
+        }
+    }
+
+    /// Close the open transaction and restore the data it began with, if
+    /// `close` picks it
+    ///
+    /// The data stays locked throughout, so no tool call sees the transaction
+    /// closed before its changes are rolled back.
+    ///
+    /// # Returns
+    /// The number of changes rolled back (`None` when nothing was closed)
+    async fn roll_back(&self, close: impl Fn(&Transaction) -> bool) -> Option<usize> {
+        let mut data = self.data.write().await;
+        let transaction = {
+            let mut open = self.transaction.lock().unwrap();
+            if !open.as_ref().is_some_and(&close) {
+                return None;
+            }
+            open.take()?
+        };
+        *data = transaction.snapshot;
+        Some(transaction.messages.len())
+    }
+
+    /// Write the current data to disk and commit it (see `save_data_with_message`)
+    async fn write_data(&self, message: &str) -> Result<()> {
+        let _queue = self.write_queue.lock().await;
+        self.write_data_queued(message).await
+    }
+
+    /// [`Self::write_data`] for a caller that already holds the write queue
+    pub(crate) async fn write_data_queued(&self, message: &str) -> Result<()> {
+        let mut message = message.to_string();
+        if let Some(days) = self.trash_retention_days {
+            let mut data = self.data.write().await;
+            let purged = retention::purge(&mut data, days, self.clock.today());
+            if let Some(note) = retention::commit_note(&purged, days) {
+                message.push_str(&note);
+            }
+        }
+
+        let content = {
+            let data = self.data.read().await;
+            let content = Storage::serialize(&data)?;
+            if !data.is_dirty(&content) {
+                return Ok(());
+            }
+            if let Some(report) = self.integrity_report(&data) {
+                drop(data);
+                self.discard_changes().await?;
+                anyhow::bail!(report);
+            }
+            content
+        };
+
+        let storage = self.storage();
+        let writer = storage.clone();
+        let written = content.clone();
+        let notice = message.clone();
+        tokio::task::spawn_blocking(move || writer.write_content(&written, &message)).await??;
+        let events = {
+            let mut data = self.data.write().await;
+            data.mark_saved(&content);
+            self.saved_events(&data)
+        };
+        // No subscribers is fine
+        let _ = self.changes.send(notice);
+        self.hooks.fire(&events);
+
+        // A rebase onto commits from another machine changed the file under us;
+        // adopt the merged result so the next save does not drop those changes
+        if storage.take_remote_update() {
+            let merged = tokio::task::spawn_blocking(move || storage.load_local()).await??;
+            self.adopt_saved(&merged);
+            *self.data.write().await = merged;
+        }
+        Ok(())
+    }
+
+    /// Replace the in-memory data with the last saved state
+    async fn discard_changes(&self) -> Result<()> {
+        let storage = self.storage();
+        let saved = tokio::task::spawn_blocking(move || {
+            storage.flush()?;
+            storage.load_local()
+        })
+        .await??;
+        self.adopt_saved(&saved);
+        *self.data.write().await = saved;
+        Ok(())
+    }
+
+    /// Take `data` as the saved state later saves are compared with, without
+    /// firing hooks (for data loaded from the file)
+    pub(crate) fn adopt_saved(&self, data: &GtdData) {
+        if !self.hooks.is_empty() {
+            *self.saved.lock().unwrap() = Some(data.clone());
+        }
+    }
+
+    /// Events between the previous save and the just saved `data`, which
+    /// becomes the saved state
+    pub(crate) fn saved_events(&self, data: &GtdData) -> Vec<Event> {
+        if self.hooks.is_empty() {
+            return Vec::new();
+        }
+        let mut saved = self.saved.lock().unwrap();
+        let events = saved
+            .as_ref()
+            .map_or_else(Vec::new, |before| hooks::events(before, data));
+        *saved = Some(data.clone());
+        events
+    }
+}
+
+/// Validation report for data that fails [`GtdData::check_integrity`]
+fn integrity_report(data: &GtdData) -> Option<String> {
+    let issues = data.check_integrity();
+    if issues.is_empty() {
+        return None;
+    }
+    let mut report = format!(
+        "Strict mode: refusing to save invalid data ({} problem{} found):",
+        issues.len(),
+        if issues.len() == 1 { "" } else { "s" }
+    );
+    for issue in &issues {
+        report.push_str(&format!("\n- {}", issue));
+    }
+    report.push_str("\nThe change was discarded.");
+    Some(report)
+}
+
+impl service::Persist for GtdServerHandler {
+    fn save<'a>(&'a self, message: &'a str) -> service::SaveFuture<'a> {
+        Box::pin(self.save_data_with_message(message))
+    }
 }
 
 impl Drop for GtdServerHandler {
     fn drop(&mut self) {
+        let storage = self.storage.get_mut().unwrap();
+        // A transaction that was never committed is rolled back: none of its
+        // changes reach the file
+        if let Some(transaction) = self.transaction.get_mut().unwrap().take()
+            && !transaction.messages.is_empty()
+        {
+            eprintln!(
+                "Warning: Discarded {} change(s) of a transaction that was never committed",
+                transaction.messages.len()
+            );
+        }
+
         // Push to git on shutdown if sync is enabled
-        if let Err(e) = self.storage.shutdown() {
+        if let Err(e) = storage.shutdown() {
             eprintln!("Warning: Shutdown git sync failed: {}", e);
         }
+        for parked in self.workspaces.get_mut().unwrap().parked_storages() {
+            if let Err(e) = parked.shutdown() {
+                eprintln!(
+                    "Warning: Shutdown git sync failed for {}: {}",
+                    parked.file_path().display(),
+                    e
+                );
+            }
+        }
     }
 }
 
 /// GTD task management server implementing David Allen's methodology.
-/// Workflow: Capture(inbox) → Review(list) → Clarify(update) → Organize(change_status) → Do → Purge(empty_trash)
 ///
-/// **Statuses**: inbox(start) | next_action(ready) | waiting_for(blocked) | later(deferred) | calendar(dated) | someday(maybe) | done | reference | trash
-/// **Types**: task | project(multi-step) | context(@location)
-/// **IDs**: Use meaningful strings (e.g., "call-john", "website-redesign")
+/// The workflow summary sent to clients is in [`instructions`].
 #[mcp_server]
 impl McpServer for GtdServerHandler {
+    fn instructions(&self) -> Option<String> {
+        Some(instructions::render(self.instructions.as_deref()))
+    }
+
+    async fn resources_list(
+        &self,
+        _p: ListResourcesRequestParams,
+        _cx: &mut RequestContext,
+    ) -> McpResult<ListResourcesResult> {
+        self.handle_resources_list().await
+    }
+
+    /// Items with one status (inbox | next_action | waiting_for | later | calendar | someday | done | reference | project | context | goal | template | trash), listed as the list tool shows them, in every context.
+    #[resource("gtd://list/{status}", mime_type = "text/plain")]
+    pub async fn status_items(&self, status: String) -> McpResult<String> {
+        self.handle_status_resource(status).await
+    }
+
+    /// Items of one project, listed as list(project=...) shows them.
+    #[resource("gtd://project/{id}/tasks", mime_type = "text/plain")]
+    pub async fn project_tasks(&self, id: String) -> McpResult<String> {
+        self.handle_project_tasks_resource(id).await
+    }
+
     /// **Purge**: Permanently delete all trashed items. Run weekly.
     /// **When**: Part of weekly review - trash items first with change_status, then purge.
     /// **Safety**: Checks references to prevent broken links.
+    /// **Snapshot**: Completes the weekly review - when weekly tags are enabled, the repository is tagged weekly-YYYY-Www.
     #[tool]
     pub async fn empty_trash(&self) -> McpResult<String> {
         self.handle_empty_trash().await
     }
 
+    /// **Undo trash**: Move trashed items back to the status they had before (e.g. next_action, project).
+    /// **When**: Something was trashed by mistake. Works until empty_trash purges the item.
+    /// **Note**: Items trashed before previous statuses were recorded go back to inbox.
+    #[tool]
+    pub async fn restore(
+        &self,
+        /// Trashed item IDs to restore
+        ids: Vec<String>,
+    ) -> McpResult<String> {
+        self.handle_restore(ids).await
+    }
+
+    /// **Checklist**: Stamp out a template's checklist as next actions (e.g. "travel packing" for a new trip).
+    /// **When**: A recurring kind of work starts again. Create templates once with inbox(status="template"), one "- item" line per task in the notes.
+    /// **Result**: Task IDs are the new_id plus the item text (e.g. "trip-paris-book-flights"); tasks inherit the template's project and context.
+    #[tool]
+    pub async fn instantiate_template(
+        &self,
+        /// Template ID (a nota with status "template")
+        template_id: String,
+        /// Prefix for the new task IDs (e.g. "trip-paris")
+        new_id: String,
+    ) -> McpResult<String> {
+        self.handle_instantiate_template(template_id, new_id).await
+    }
+
+    /// **Project scaffolding**: Create a project and its standard tasks from a template in one step (e.g. client onboarding).
+    /// **When**: Starting a project that follows a known pattern. The template's title becomes the project title and its checklist the tasks.
+    /// **Placeholders**: {name} in the template's title, notes and items is replaced from params (e.g. params={"client": "Acme"}).
+    #[tool]
+    pub async fn new_project_from_template(
+        &self,
+        /// Template ID (a nota with status "template")
+        template: String,
+        /// ID of the new project, also the prefix for the task IDs (e.g. "onboarding-acme")
+        project_id: String,
+        /// Optional: Placeholder values by name (e.g. {"client": "Acme"})
+        params: Option<HashMap<String, String>>,
+    ) -> McpResult<String> {
+        self.handle_new_project_from_template(template, project_id, params)
+            .await
+    }
+
     /// **Capture**: Quickly capture anything needing attention. First GTD step - all items start here.
     /// **When**: Something crosses your mind? Capture immediately without thinking.
     /// **Next**: Use list(status="inbox") to review, then update/change_status to organize.
@@ -141,6 +1099,8 @@ impl McpServer for GtdServerHandler {
     /// - Keep concise but meaningful (3-5 words max)
     /// - Use project prefix for clarity: "eci-fix-button", "fft-level-cloud"
     /// - IDs are immutable - choose carefully as they cannot be changed later
+    ///
+    /// **Duplicates**: A title nearly identical to an open item returns a warning naming it (or an error, if the server rejects duplicates). Update the existing item instead of capturing again.
     #[allow(clippy::too_many_arguments)]
     #[tool]
     pub async fn inbox(
@@ -149,7 +1109,7 @@ impl McpServer for GtdServerHandler {
         id: String,
         /// Brief description
         title: String,
-        /// inbox | next_action | waiting_for | later | calendar | someday | done | reference | project | context | trash
+        /// inbox | next_action | waiting_for | later | calendar | someday | done | reference | project | context | goal | template | trash
         status: String,
         /// Optional: Parent project ID
         project: Option<String>,
@@ -184,10 +1144,12 @@ impl McpServer for GtdServerHandler {
     /// **Review**: List/filter all items. Essential for daily/weekly reviews.
     /// **When**: Daily - check next_action. Weekly - review all. Use filters to focus.
     /// **Filters**: No filter=all | status="inbox"=unprocessed | status="next_action"=ready | status="calendar"+date=today's tasks | keyword="text"=search | project="id"=by project | context="name"=by context.
+    /// **Stale**: Next actions flagged "⚠ 21d" have sat in next_action that long - ask whether to do, renegotiate (later/someday) or drop them.
+    #[allow(clippy::too_many_arguments)]
     #[tool]
     pub async fn list(
         &self,
-        /// Optional: Filter by status (inbox | next_action | waiting_for | later | calendar | someday | done | reference | project | context | trash)
+        /// Optional: Filter by status (inbox | next_action | waiting_for | later | calendar | someday | done | reference | project | context | goal | template | trash)
         status: Option<String>,
         /// Optional: Date filter YYYY-MM-DD - For calendar, shows tasks with start_date <= this date
         date: Option<String>,
@@ -197,14 +1159,62 @@ impl McpServer for GtdServerHandler {
         keyword: Option<String>,
         /// Optional: Filter by project ID - use meaningful abbreviation (e.g., "website-redesign", "q1-budget")
         project: Option<String>,
-        /// Optional: Filter by context name
+        /// Optional: Filter by context name (tasks without one use their project's) - next_action listings default to the current context, "" lists every context
         context: Option<String>,
+        /// Optional: Cut notes longer than this many characters (read the full notes with get)
+        max_notes_chars: Option<u32>,
     ) -> McpResult<String> {
-        self.handle_list(status, date, exclude_notes, keyword, project, context)
-            .await
+        self.handle_list(
+            status,
+            date,
+            exclude_notes,
+            keyword,
+            project,
+            context,
+            max_notes_chars,
+        )
+        .await
     }
 
-    /// **Clarify**: Update item details. Add context, notes, project links after capturing.
+    /// **Details**: Show one item with all its fields and its full notes.
+    /// **When**: list cut the notes short (max_notes_chars), or before updating an item.
+    #[tool]
+    pub async fn get(
+        &self,
+        /// Item ID to show
+        id: String,
+    ) -> McpResult<String> {
+        self.handle_get(id).await
+    }
+
+    /// **Quick capture**: Put something in the inbox with just a title - the ID is generated from it (e.g. "Call John re: Q3" → call-john-re-q3).
+    /// **When**: Fast capture while the user is talking; prefer this over inbox unless a status, project or date is already known.
+    /// **Next**: Clarify later with update/change_status using the returned ID.
+    #[tool]
+    pub async fn capture(
+        &self,
+        /// What needs attention
+        title: String,
+        /// Optional: Markdown notes
+        notes: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_capture(title, notes).await
+    }
+
+    /// **Capture email**: Create an inbox item from an email pasted as text (forwarded or copied from a mail client).
+    /// **When**: The user pastes an email or says "add this mail" - pass the whole text as is, headers included.
+    /// **Result**: Subject (without Re:/Fwd:) → title, body → notes with sender and date, ID generated from the subject.
+    /// **Next**: The response names the sender - if the item waits on them, change_status to waiting_for.
+    #[tool]
+    pub async fn capture_email(
+        &self,
+        /// Full email text including the From/Subject header lines
+        raw_text: String,
+    ) -> McpResult<String> {
+        self.handle_capture_email(raw_text).await
+    }
+
+    /// **Clarify**: Update item details. Add context, notes, project links, priority, due date, estimate and energy after capturing.
     /// **When**: After inbox capture, clarify what it is, why it matters, what's needed.
     /// **Tip**: Use ""(empty string) to clear optional fields.
     /// **Note**: Item ID cannot be changed - IDs are immutable. To "rename", create new item and delete old one.
@@ -220,31 +1230,474 @@ impl McpServer for GtdServerHandler {
         status: Option<String>,
         /// Optional: Project link, ""=clear
         project: Option<String>,
-        /// Optional: Context tag, ""=clear
+        /// Optional: Context tag, ""=clear - on a project, the default context of its tasks that have none
         context: Option<String>,
         /// Optional: Markdown notes, ""=clear
         notes: Option<String>,
         /// Optional: Start date YYYY-MM-DD, ""=clear
         start_date: Option<String>,
+        /// Optional: high | medium | low, ""=clear
+        priority: Option<String>,
+        /// Optional: Deadline YYYY-MM-DD, ""=clear
+        due_date: Option<String>,
+        /// Optional: Estimated minutes (e.g., "15"), ""=clear
+        estimate: Option<String>,
+        /// Optional: Energy needed - low | medium | high, ""=clear
+        energy: Option<String>,
+        /// Optional: Goal a project contributes to, ""=clear
+        goal: Option<String>,
     ) -> McpResult<String> {
-        self.handle_update(id, title, status, project, context, notes, start_date)
-            .await
+        self.handle_update(
+            id, title, status, project, context, notes, start_date, priority, due_date, estimate,
+            energy, goal,
+        )
+        .await
     }
 
     /// **Organize/Do**: Move items through workflow stages as you process them.
     /// **When**: inbox→next_action(ready) | →waiting_for(blocked) | →done(complete) | →trash(discard).
-    /// **Tip**: Use change_status to trash before empty_trash to permanently delete.
+    /// **Tip**: Use change_status to trash before empty_trash to permanently delete. Trashing a referenced project/context needs cascade.
     /// **Batch**: Supports multiple IDs for efficient batch operations (e.g., weekly review).
     #[tool]
     pub async fn change_status(
         &self,
         /// Item IDs to change - format: ["#1", "#2", "#3"] for batch operations, or single ID for single item
         ids: Vec<String>,
-        /// New status: inbox | next_action | waiting_for | later | calendar | someday | done | reference | project | context | trash
+        /// New status: inbox | next_action | waiting_for | later | calendar | someday | done | reference | project | context | goal | template | trash
         new_status: String,
         /// Optional: Start date YYYY-MM-DD (required for calendar)
         start_date: Option<String>,
+        /// Optional: When trashing a referenced project/context - trash (also trash linked items) | unlink (clear their link)
+        cascade: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_change_status(ids, new_status, start_date, cascade)
+            .await
+    }
+
+    /// **Dedupe**: Find likely duplicates - open items with the same title (ignoring case, punctuation and word order) in the same project, across statuses.
+    /// **When**: Weekly review, or after capturing from several sources (email, GitHub, imports).
+    /// **Actions**: report (default) lists each group with the item to keep | merge folds the duplicates' notes and missing details into the kept item, then trashes them | trash trashes them as they are.
+    /// **Keep**: A clarified item beats an inbox item, then the oldest wins. Items filed under a duplicate project move to the kept one.
+    #[tool]
+    pub async fn dedupe(
+        &self,
+        /// Optional: report (default) | merge | trash
+        action: Option<String>,
+        /// Optional: Only act on the groups containing these IDs (default: all groups)
+        ids: Option<Vec<String>>,
+    ) -> McpResult<String> {
+        self.handle_dedupe(action, ids).await
+    }
+
+    /// **Do**: Suggest what to work on now - next actions ranked by priority, due date and age, filtered to the user's situation.
+    /// **When**: "What should I do?", "I have 20 minutes at home", "I'm tired - anything easy?".
+    /// **Fit**: Leaves out actions for another context, longer than the minutes given, or needing more energy; each suggestion lists its reasons.
+    /// **Escalate**: Waiting items that waited past their limit (set_escalation) are listed too, marked ESCALATE.
+    /// **Tip**: Set priority, due_date, estimate and energy with update to improve the ranking.
+    #[tool]
+    pub async fn suggest(
+        &self,
+        /// Optional: Context the user is in (e.g., "@home") - defaults to the current context, "" for any
+        context: Option<String>,
+        /// Optional: Minutes available
+        minutes: Option<u32>,
+        /// Optional: Energy available - low | medium | high
+        energy: Option<String>,
+        /// Optional: Number of suggestions (default 5)
+        limit: Option<u32>,
+    ) -> McpResult<String> {
+        self.handle_suggest(context, minutes, energy, limit).await
+    }
+
+    /// **Do**: List only the next actions that certainly fit right now - estimate within the minutes, energy within the energy, and the context.
+    /// **When**: "I have 15 minutes and I'm tired" - when the user wants everything that fits, not a short ranking (use suggest for that).
+    /// **Note**: Actions missing the estimate or energy needed to check are left out and counted; add them with update.
+    #[tool]
+    pub async fn doable(
+        &self,
+        /// Optional: Minutes available
+        minutes: Option<u32>,
+        /// Optional: Energy available - low | medium | high
+        energy: Option<String>,
+        /// Optional: Context the user is in (e.g., "@home") - defaults to the current context, "" for any
+        context: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_doable(minutes, energy, context).await
+    }
+
+    /// **Follow up**: Set how many days a waiting_for item may wait before suggest escalates it.
+    /// **When**: Delegating something with a deadline for the reply ("chase Bob if no answer in 3 days").
+    /// **Note**: Waiting time counts from the item's last change. Omit after_days to use the server default.
+    #[tool]
+    pub async fn set_escalation(
+        &self,
+        /// Task ID (usually a waiting_for item)
+        id: String,
+        /// Optional: Days the item may wait before it is escalated
+        after_days: Option<u32>,
+    ) -> McpResult<String> {
+        self.handle_set_escalation(id, after_days).await
+    }
+
+    /// **Daily focus**: Pick today's most important tasks (MITs), up to 3, replacing any earlier pick.
+    /// **When**: Morning planning, often right after suggest. Pass an empty list to clear the focus.
+    /// **Note**: The focus lapses at local midnight. Returns the daily plan (same as today).
+    #[tool]
+    pub async fn set_focus(
+        &self,
+        /// Open task IDs to focus on today, most important first (at most 3)
+        ids: Vec<String>,
+    ) -> McpResult<String> {
+        self.handle_set_focus(ids).await
+    }
+
+    /// **Daily plan**: Today's focus items, calendar items for today, and other next actions due today or overdue.
+    /// **When**: Start of the day or "what was I doing today?". Set the focus first with set_focus.
+    #[tool]
+    pub async fn today(&self) -> McpResult<String> {
+        self.handle_today().await
+    }
+
+    /// **Where am I**: Remember the context the user is in (e.g. @office, @home) for this session.
+    /// **When**: The user says they arrived somewhere or switched mode. Call without a name to clear.
+    /// **Effect**: list(status="next_action") and suggest default to this context until it is changed.
+    #[tool]
+    pub async fn set_current_context(
+        &self,
+        /// Optional: Context name (e.g., "@office"); omit or "" to clear
+        name: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_set_current_context(name).await
+    }
+
+    /// **Opening hours**: Declare when a context can be used (e.g. @office "Mon-Fri 9-18", @bank "Mon-Fri 9-15; Sat 9-12").
+    /// **When**: A place or person is only reachable at certain times. Times are in the configured timezone.
+    /// **Effect**: suggest ranks actions lower while their context is not available (unless the user is in that context).
+    #[tool]
+    pub async fn set_availability(
+        &self,
+        /// Context name (e.g., "@office")
+        context: String,
+        /// Days and hours, windows separated by ";" (e.g., "Mon-Fri 9-18; Sat 10-14"); "" to clear
+        availability: String,
+    ) -> McpResult<String> {
+        self.handle_set_availability(context, availability).await
+    }
+
+    /// **Snooze**: Push an item's start_date N days later, or set it N days from today when it has none.
+    /// **When**: "Remind me about this next week" - snoozing calendar or tickler items without working out the date.
+    #[tool]
+    pub async fn postpone(
+        &self,
+        /// Item ID
+        id: String,
+        /// Days to postpone by (at least 1)
+        days: u32,
+    ) -> McpResult<String> {
+        self.handle_postpone(id, days).await
+    }
+
+    /// **Pause**: Stop a recurring series for now - completing the item creates no next occurrence and the tickler skips it.
+    /// **When**: A routine is on hold (e.g. gym membership paused, project on ice). Undo with resume_recurrence.
+    #[tool]
+    pub async fn pause_recurrence(
+        &self,
+        /// Recurring item ID
+        id: String,
+    ) -> McpResult<String> {
+        self.handle_set_recurrence_paused(id, true).await
+    }
+
+    /// **Resume**: Restart a recurring series paused with pause_recurrence.
+    /// **When**: The routine is back on. Check the start date afterwards - postpone or skip_occurrence if it is in the past.
+    #[tool]
+    pub async fn resume_recurrence(
+        &self,
+        /// Recurring item ID
+        id: String,
+    ) -> McpResult<String> {
+        self.handle_set_recurrence_paused(id, false).await
+    }
+
+    /// **Skip**: Move a recurring item to its next occurrence without completing it - the series continues, no done item is left behind.
+    /// **When**: Skipping one round of a routine (e.g. the weekly chore while on vacation). Call again to skip more.
+    #[tool]
+    pub async fn skip_occurrence(
+        &self,
+        /// Recurring item ID
+        id: String,
+    ) -> McpResult<String> {
+        self.handle_skip_occurrence(id).await
+    }
+
+    /// **Reschedule**: Move many calendar items at once - to one date, or by a number of days (e.g. push a cancelled trip's appointments a week later).
+    /// **When**: Plans changed for several dated items. Give exactly one of to_date or shift_days.
+    /// **Note**: All or nothing - if any ID is not a calendar item, nothing moves. Saved in one commit.
+    #[tool]
+    pub async fn reschedule(
+        &self,
+        /// Calendar item IDs to move
+        ids: Vec<String>,
+        /// Optional: New start date for every item (YYYY-MM-DD)
+        to_date: Option<String>,
+        /// Optional: Days to move each item by (negative moves earlier)
+        shift_days: Option<i64>,
+    ) -> McpResult<String> {
+        self.handle_reschedule(ids, to_date, shift_days).await
+    }
+
+    /// **Weekly review**: Go through someday/maybe items nobody has looked at for a while, and decide on them in one batch.
+    /// **When**: During the weekly review, or when the someday list feels stale.
+    /// **Flow**: Call without decisions to list items untouched for older_than_days (default 30), ask the user, then call again with promote/keep/trash lists.
+    /// **Decisions**: promote → next_action | keep → stays someday, marked reviewed today | trash → trash. All saved in one commit.
+    #[tool]
+    pub async fn review_someday(
+        &self,
+        /// Optional: List someday items not updated for this many days (default 30)
+        older_than_days: Option<u32>,
+        /// Optional: Someday item IDs to move to next_action
+        promote: Option<Vec<String>>,
+        /// Optional: Someday item IDs to keep (skipped in the next reviews)
+        keep: Option<Vec<String>>,
+        /// Optional: Someday item IDs to move to trash
+        trash: Option<Vec<String>>,
+    ) -> McpResult<String> {
+        self.handle_review_someday(older_than_days, promote, keep, trash)
+            .await
+    }
+
+    /// **Sync**: Pull changes from other machines and push local ones to the Git remote now.
+    /// **When**: Before a daily/weekly review so you see the latest data, or after sync_status shows unpushed commits.
+    /// **Reports**: Commits received and sent; data is reloaded when changes arrive.
+    #[tool("sync")]
+    pub async fn sync_with_progress(&self, cx: &RequestContext) -> McpResult<String> {
+        self.handle_sync(Progress::new(cx)).await
+    }
+
+    /// **Transaction**: Group the following changes so they are saved together or not at all.
+    /// **When**: Before a series of creates/updates that only make sense as a whole (e.g. a project with its first actions).
+    /// **Then**: Call commit to save everything in one Git commit, or abort to roll back. Applies to all clients of this server.
+    #[tool("begin_transaction")]
+    pub async fn begin(&self) -> McpResult<String> {
+        self.handle_begin_transaction().await
+    }
+
+    /// **Commit**: Save every change made since begin_transaction in one write and one Git commit.
+    /// **Atomic**: If the save fails, all changes of the transaction are rolled back.
+    #[tool]
+    pub async fn commit(&self) -> McpResult<String> {
+        self.handle_commit().await
+    }
+
+    /// **Abort**: Roll back every change made since begin_transaction; nothing is saved.
+    /// **When**: A step of the series failed or the plan changed.
+    #[tool]
+    pub async fn abort(&self) -> McpResult<String> {
+        self.handle_abort().await
+    }
+
+    /// **Safety net**: Take a backup of the saved data before a risky change (bulk edits, merges, cleanups).
+    /// **Then**: restore_backup brings it back. Backups are kept in memory until the server stops (latest 10).
+    #[tool]
+    pub async fn backup_now(&self) -> McpResult<String> {
+        self.handle_backup_now().await
+    }
+
+    /// **Undo**: Restore the data saved by backup_now, replacing everything changed since.
+    /// **Note**: The restore is saved and committed like any change, so it can be undone through Git.
+    #[tool]
+    pub async fn restore_backup(
+        &self,
+        /// Optional: Backup number reported by backup_now (default: the latest)
+        number: Option<usize>,
+    ) -> McpResult<String> {
+        self.handle_restore_backup(number).await
+    }
+
+    /// **Reload**: Replace the server's copy of the data with the data file on disk.
+    /// **When**: A save failed because the file was changed outside this server (another machine, an editor, another gtd-mcp); reload, then retry the change.
+    /// **Note**: Unsaved changes are dropped.
+    #[tool]
+    pub async fn reload(&self) -> McpResult<String> {
+        self.handle_reload().await
+    }
+
+    /// **Phone sync**: Two-way sync of next_action and calendar items with the configured CalDAV task list (e.g. Nextcloud Tasks).
+    /// **When**: Before and after working from the phone - completions, edits and new tasks made there come back as changes here.
+    /// **Reports**: Items received, sent and removed from the server; conflicts (changed on both sides) with the version kept.
+    #[tool("sync_caldav")]
+    pub async fn sync_caldav_with_progress(&self, cx: &RequestContext) -> McpResult<String> {
+        self.handle_sync_caldav(Progress::new(cx)).await
+    }
+
+    /// **Capture from GitHub**: Import the open issues of a repository as items linked to their issue URL.
+    /// **When**: Issue trackers hold part of the user's commitments - bring them into the weekly review.
+    /// **Status**: Assigned to you → next_action (waiting_for if labelled blocked/waiting); created/mentioned by you → waiting_for.
+    /// **Tip**: Safe to rerun - issues already imported (matched by link) are only renamed, never re-added.
+    #[tool("import_github")]
+    pub async fn import_github_with_progress(
+        &self,
+        cx: &RequestContext,
+        /// Repository as owner/name (e.g. "rust-lang/rust")
+        repo: String,
+        /// Optional: assigned (default) | created | mentioned | all
+        filter: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_import_github(repo, filter, Progress::new(cx))
+            .await
+    }
+
+    /// **Consolidate**: Merge another GTD data file (e.g. an old laptop's gtd.toml) into the active one.
+    /// **When**: Two files grew apart and should become one. Items only in the other file are added; identical items are skipped.
+    /// **Strategy**: suffix (default) keeps both versions of a colliding ID, adding the incoming one as id-2 (contexts are never suffixed) | newest keeps the version with the newer updated_at.
+    /// **Safe**: The merge is refused as a whole if it would leave dangling references. The other file is not modified.
+    #[tool("merge_file")]
+    pub async fn merge_file_with_progress(
+        &self,
+        cx: &RequestContext,
+        /// Path of the file to merge in (relative paths are resolved next to the active data file)
+        path: String,
+        /// Optional: suffix (default) | newest
+        strategy: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_merge_file(path, strategy, Progress::new(cx))
+            .await
+    }
+
+    /// **Report**: Markdown summary of a period for a team's weekly update - wins, completed items by project, active projects, upcoming calendar.
+    /// **When**: Writing a status update or closing the weekly review. Paste the result as is.
+    /// **Range**: week (default, last 7 days) | last-week (previous Mon-Sun) | month (last 30 days) | YYYY-MM-DD (until today) | YYYY-MM-DD..YYYY-MM-DD.
+    #[tool]
+    pub async fn report(
+        &self,
+        /// Optional: Period to report on (default "week")
+        range: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_report(range).await
+    }
+
+    /// **Done log**: Items completed in a date range, grouped per day with their projects.
+    /// **When**: Retrospectives, performance reviews, or "what did I get done last month?".
+    /// **Range**: from/to are inclusive YYYY-MM-DD dates. Without from, the 7 days up to to (default today).
+    #[tool]
+    pub async fn done_log(
+        &self,
+        /// Optional: First day (YYYY-MM-DD)
+        from: Option<String>,
+        /// Optional: Last day (YYYY-MM-DD, default today)
+        to: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_done_log(from, to).await
+    }
+
+    /// **Trends**: Weekly table of items captured, clarified and completed, with the average days from capture to done.
+    /// **When**: Weekly or monthly review - is the inbox keeping up, is throughput going up or down?
+    /// **Note**: Derived from created_at/updated_at (no status history). The current week is partial.
+    #[tool]
+    pub async fn trends(
+        &self,
+        /// Optional: Number of weeks to show, including the current one (default 8, max 104)
+        weeks: Option<u32>,
+    ) -> McpResult<String> {
+        self.handle_trends(weeks).await
+    }
+
+    /// **Big picture**: Projects and their sub-projects with the tasks nested underneath, each marked with its status ([ ] next, [w] waiting, ...). Tasks outside any project are listed last.
+    /// **When**: Weekly review, or to see the whole system structure in one call before planning.
+    #[tool]
+    pub async fn tree(
+        &self,
+        /// Optional: Also show done tasks (default false)
+        include_done: Option<bool>,
+    ) -> McpResult<String> {
+        self.handle_tree(include_done).await
+    }
+
+    /// **Horizons**: Each goal with its target date, the projects that contribute to it and their combined progress; projects serving no goal are listed last.
+    /// **When**: Quarterly or monthly review - check that active projects still move the 1-3 year goals forward.
+    /// **Setup**: Create goals with inbox(status="goal"), set the target with update(due_date=...) and link projects with update(id=<project>, goal=<goal>).
+    #[tool]
+    pub async fn goals_overview(&self) -> McpResult<String> {
+        self.handle_goals_overview().await
+    }
+
+    /// **Kanban board**: Inbox, next, waiting and recently done items (last 7 days) side by side, one column per status.
+    /// **When**: Stand-ups and status updates - paste the board into chat or a document.
+    #[tool]
+    pub async fn board(
+        &self,
+        /// Optional: "markdown" (table, default) or "text" (aligned columns)
+        format: Option<String>,
+        /// Optional: Only show the items of this project ID
+        project: Option<String>,
+    ) -> McpResult<String> {
+        self.handle_board(format, project).await
+    }
+
+    /// **Follow up**: List waiting_for items with the days each has been waiting, oldest first, so nothing delegated falls through the cracks.
+    /// **When**: Weekly review, or before chasing people up. Follow up on the top items, then update or move them.
+    /// **Note**: Waiting time counts from the item's last update.
+    #[tool]
+    pub async fn waiting_report(&self) -> McpResult<String> {
+        self.handle_waiting_report().await
+    }
+
+    /// **Inbox zero**: Bucket inbox items by days since capture (0-2, 3-7, over 7) and call out the oldest ones.
+    /// **When**: Daily processing, or when the inbox feels out of control. Clarify the "Process first" items right away.
+    #[tool]
+    pub async fn inbox_age(&self) -> McpResult<String> {
+        self.handle_inbox_age().await
+    }
+
+    /// **Clarify**: Walk through the inbox oldest first with the GTD questions - what is it, is it actionable, what is the next physical action, which context?
+    /// **Assist**: With assist=true the client's LLM proposes a clarified title, a context and the next action for each item (MCP sampling - the client may ask the user to approve each request). Nothing is changed.
+    /// **Then**: Show the proposals to the user and apply only the confirmed ones with update and change_status.
+    #[tool("process_inbox")]
+    pub async fn process_inbox_with_sampling(
+        &self,
+        /// Optional: Most items to process, oldest first (default 10, max 50)
+        limit: Option<usize>,
+        /// Optional: Ask the client's LLM for proposals (default false)
+        assist: Option<bool>,
+        cx: &RequestContext,
+    ) -> McpResult<String> {
+        self.handle_process_inbox(limit, assist, Some(cx), Progress::new(cx))
+            .await
+    }
+
+    /// **Calendar feed**: Export calendar items and the upcoming occurrences of recurring items (next 90 days) as an iCalendar (.ics) document.
+    /// **When**: The user wants GTD dates in their calendar app - save the output as a .ics file and import it.
+    /// **Returns**: The .ics text, one all-day event per date.
+    #[tool]
+    pub async fn export_ical(&self) -> McpResult<String> {
+        self.handle_export_ical().await
+    }
+
+    /// **Diagnose**: Show the Git state of the data file - uncommitted changes, commits ahead/behind the remote, last sync time.
+    /// **When**: Changes made here don't show up on another device (or the other way around).
+    /// **Next**: Ahead/behind > 0 → run sync.
+    #[tool]
+    pub async fn git_status(&self) -> McpResult<String> {
+        self.handle_git_status().await
+    }
+
+    /// **Sync check**: Show whether changes have reached the Git remote.
+    /// **When**: Before switching machines, or when a save reported a Git problem.
+    /// **Shows**: Unpushed commit count, background push state, last push time, last error and next retry.
+    #[tool]
+    pub async fn sync_status(&self) -> McpResult<String> {
+        self.handle_sync_status().await
+    }
+
+    /// **Workspaces**: Switch to another data file served by this server (e.g. work, personal), or list them.
+    /// **When**: The user wants to see or change items of a different area of life. All other tools act on the active workspace only.
+    /// **Omit name** to list the workspaces and see which one is active.
+    #[tool]
+    pub async fn switch_workspace(
+        &self,
+        /// Optional: Workspace to activate (omit to list workspaces)
+        name: Option<String>,
     ) -> McpResult<String> {
-        self.handle_change_status(ids, new_status, start_date).await
+        self.handle_switch_workspace(name).await
     }
 }
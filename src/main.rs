@@ -4,35 +4,470 @@
 //! The actual implementation is in the `gtd_mcp` library.
 
 use anyhow::Result;
-use clap::{CommandFactory, Parser};
-use gtd_mcp::GtdServerHandler;
+use clap::{Parser, Subcommand};
+use gtd_mcp::config::{Config, ServeOptions};
+use gtd_mcp::daemon::{self, SharedServer};
+use gtd_mcp::import::ImportFormat;
+use gtd_mcp::merge_file::MergeStrategy;
+use gtd_mcp::mirror::MirrorFormat;
+use gtd_mcp::{
+    GtdServerHandler, IdPolicy, Storage, cli, discovery, external_notes, mcp_http, migration, rest,
+    tool_hints,
+};
 use mcp_attr::server::serve_stdio;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// GTD MCP Server - Getting Things Done task management via Model Context Protocol
+///
+/// Without a subcommand, `gtd-mcp <FILE>` serves FILE over stdio (same as `gtd-mcp serve <FILE>`).
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
-    /// Path to the GTD data file
-    file: String,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// Enable git synchronization on save
-    #[arg(long)]
-    sync_git: bool,
+    /// Path to the GTD data file (default: `file` of the configuration file, else the nearest gtd.toml in this or a parent directory)
+    #[arg(env = "GTD_MCP_FILE")]
+    file: Option<String>,
+
+    #[command(flatten)]
+    serve: ServeOptions,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Serve a GTD data file over stdio as an MCP server (the default), as a REST API with --rest, or to several MCP clients over HTTP with --http
+    Serve {
+        /// Path to the GTD data file (default: `file` of the configuration file, else the nearest gtd.toml in this or a parent directory)
+        #[arg(env = "GTD_MCP_FILE")]
+        file: Option<String>,
+
+        #[command(flatten)]
+        options: Box<ServeOptions>,
+    },
+    /// Print items, filtered like the list tool (e.g. `gtd-mcp list gtd.toml --status next_action`)
+    List {
+        /// Path to the GTD data file
+        file: String,
+
+        /// Filter by status (inbox, next_action, waiting_for, later, calendar, someday, done, reference, project, context, goal, template, trash)
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Date filter YYYY-MM-DD (calendar items with start_date on or before this date)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Search keyword in id, title and notes (case-insensitive)
+        #[arg(long)]
+        keyword: Option<String>,
+
+        /// Filter by project ID
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Filter by context name
+        #[arg(long)]
+        context: Option<String>,
+
+        /// Omit notes from the output
+        #[arg(long)]
+        exclude_notes: bool,
+    },
+    /// Capture an item, like the inbox tool (creates the file if it does not exist)
+    Add {
+        /// Path to the GTD data file
+        file: String,
+
+        /// Unique ID of the new item (e.g. "call-john")
+        id: String,
+
+        /// Brief description
+        title: String,
+
+        /// Status of the new item
+        #[arg(long, default_value = "inbox")]
+        status: String,
+
+        /// Parent project ID
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Context name (e.g. "@home")
+        #[arg(long)]
+        context: Option<String>,
+
+        /// Markdown notes
+        #[arg(long)]
+        notes: Option<String>,
+
+        /// Start date YYYY-MM-DD (required for calendar status)
+        #[arg(long)]
+        start_date: Option<String>,
+    },
+    /// Capture titles as inbox items with IDs generated from the titles (`-` reads one title per line from stdin)
+    Capture {
+        /// Titles to capture, or `-` to read them from stdin
+        #[arg(required = true)]
+        titles: Vec<String>,
+
+        /// Path to the GTD data file (default: the nearest gtd.toml in this or a parent directory)
+        #[arg(long, short, env = "GTD_MCP_FILE")]
+        file: Option<String>,
+    },
+    /// Print the whole file as JSON, JSON Lines, a Markdown report, an iCalendar feed of dated items, CSV or todo.txt
+    Export {
+        /// Path to the GTD data file
+        file: String,
+
+        /// Output format (json, jsonl, markdown, ical, csv or todotxt)
+        #[arg(long, default_value = "json")]
+        format: MirrorFormat,
+    },
+    /// Add or update items from a CSV or todo.txt file (items with an existing ID are updated)
+    Import {
+        /// Path to the GTD data file (created if it does not exist)
+        file: String,
+
+        /// File to import, or `-` to read stdin
+        input: String,
+
+        /// Input format (csv, todotxt or taskpaper); inferred from the input file extension if omitted
+        #[arg(long)]
+        format: Option<ImportFormat>,
+
+        /// Show what would change without modifying the data file
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Merge another GTD data file into this one (e.g. to consolidate an old laptop's file)
+    Merge {
+        /// Path to the GTD data file to merge into
+        file: String,
+
+        /// Path to the GTD data file to merge in (not modified)
+        other: String,
+
+        /// How to resolve an ID used by different items in both files: suffix (add the incoming item as id-2) or newest (keep the newer updated_at)
+        #[arg(long, default_value = "suffix")]
+        strategy: MergeStrategy,
+
+        /// Show what would change without modifying the data file
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check a GTD data file for schema and integrity problems (exit code 1 if any)
+    Validate {
+        /// Path to the GTD data file
+        file: String,
+    },
+    /// Upgrade a GTD data file from a legacy format (writes a backup first)
+    Migrate {
+        /// Path to the GTD data file
+        file: String,
+
+        /// Show what would change without modifying any files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Then store notes of at least MIN_CHARS characters in Markdown files (format version 4)
+        #[arg(
+            long,
+            value_name = "MIN_CHARS",
+            num_args = 0..=1,
+            default_missing_value = "200",
+            conflicts_with = "inline_notes"
+        )]
+        external_notes: Option<usize>,
+
+        /// Then move notes kept in Markdown files back into the data file (format version 3)
+        #[arg(long)]
+        inline_notes: bool,
+    },
+    /// Print item counts per status
+    Stats {
+        /// Path to the GTD data file
+        file: String,
+    },
+}
+
+/// Whether an error comes from writing to a closed pipe
+fn is_broken_pipe(error: &anyhow::Error) -> bool {
+    let kind = match error.downcast_ref::<serde_json::Error>() {
+        Some(e) => e.io_error_kind(),
+        None => error
+            .downcast_ref::<std::io::Error>()
+            .map(std::io::Error::kind),
+    };
+    kind == Some(std::io::ErrorKind::BrokenPipe)
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Check if no arguments were provided (except the program name)
-    if std::env::args().len() == 1 {
-        // No arguments provided, show help and exit with error code
-        let mut cmd = Args::command();
-        cmd.print_help().ok();
-        println!(); // Add a newline after help
-        std::process::exit(2);
+    let args = Args::parse();
+    let Some(command) = args.command else {
+        return serve(args.file, args.serve).await;
+    };
+    match command {
+        Command::Serve { file, options } => serve(file, *options).await?,
+        Command::List {
+            file,
+            status,
+            date,
+            keyword,
+            project,
+            context,
+            exclude_notes,
+        } => {
+            let options = cli::ListOptions {
+                status,
+                date,
+                exclude_notes,
+                keyword,
+                project,
+                context,
+            };
+            print!("{}", cli::list(&file, options).await?);
+        }
+        Command::Add {
+            file,
+            id,
+            title,
+            status,
+            project,
+            context,
+            notes,
+            start_date,
+        } => {
+            let options = cli::AddOptions {
+                id,
+                title,
+                status,
+                project,
+                context,
+                notes,
+                start_date,
+            };
+            println!("{}", cli::add(&file, options).await?);
+        }
+        Command::Capture { titles, file } => {
+            let file = match file {
+                Some(file) => file,
+                None => discovery::find_data_file(&std::env::current_dir()?)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "No {} found; pass --file or set GTD_MCP_FILE",
+                            discovery::DATA_FILE_NAME
+                        )
+                    })?
+                    .to_string_lossy()
+                    .into_owned(),
+            };
+            let titles = if titles == ["-"] {
+                std::io::stdin()
+                    .lines()
+                    .collect::<std::io::Result<Vec<_>>>()?
+            } else {
+                titles
+            };
+            println!("{}", cli::capture(&file, titles).await?);
+        }
+        Command::Export { file, format } => {
+            let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+            match cli::export_to(&file, format, &mut out) {
+                // The reader went away (e.g. `| head`); nothing left to do
+                Err(e) if is_broken_pipe(&e) => {}
+                result => result?,
+            }
+        }
+        Command::Import {
+            file,
+            input,
+            format,
+            dry_run,
+        } => {
+            let Some(format) = format.or_else(|| ImportFormat::from_path(&input)) else {
+                anyhow::bail!("Cannot tell the format of {}; pass --format", input);
+            };
+            let text = if input == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(&input)?
+            };
+            println!("{}", cli::import(&file, &text, format, dry_run)?);
+        }
+        Command::Merge {
+            file,
+            other,
+            strategy,
+            dry_run,
+        } => {
+            println!("{}", cli::merge(&file, &other, strategy, dry_run)?);
+        }
+        Command::Validate { file } => {
+            let report = cli::validate(&file)?;
+            println!("{}", report);
+            if !report.is_valid() {
+                std::process::exit(1);
+            }
+        }
+        Command::Migrate {
+            file,
+            dry_run,
+            external_notes,
+            inline_notes,
+        } => {
+            println!("{}", migration::migrate_file(&file, dry_run)?);
+            if external_notes.is_some() || inline_notes {
+                println!(
+                    "{}",
+                    external_notes::convert_file(&file, external_notes, dry_run)?
+                );
+            }
+        }
+        Command::Stats { file } => print!("{}", cli::stats(&file)?),
     }
+    Ok(())
+}
 
-    let args = Args::parse();
-    let handler = GtdServerHandler::new(&args.file, args.sync_git)?;
-    serve_stdio(handler).await?;
+/// Serve `file` over stdio (or REST with `--rest`, MCP over HTTP with `--http`), filling missing options from the configuration file
+async fn serve(file: Option<String>, options: ServeOptions) -> Result<()> {
+    let config = Config::discover(options.config.as_deref())?;
+    // No data file given or found: take it from the client's workspace roots
+    let mut from_roots = false;
+    let file = match file.or(config.file) {
+        Some(file) => PathBuf::from(file),
+        None => {
+            let cwd = std::env::current_dir()?;
+            match discovery::find_data_file(&cwd) {
+                Some(found) => found,
+                None if options.init => cwd.join(discovery::DATA_FILE_NAME),
+                None => {
+                    from_roots = true;
+                    cwd.join(discovery::DATA_FILE_NAME)
+                }
+            }
+        }
+    };
+    let args = options.or(config.serve);
+    // Reject a bad schedule before anything is served
+    let schedule = if args.daemon {
+        Some(args.schedule()?)
+    } else {
+        None
+    };
+    if args.init && discovery::init_data_file(&file)? {
+        eprintln!("Created {} with starter contexts", file.display());
+    }
+    if from_roots {
+        let cwd = file.parent().unwrap_or(&file).display();
+        if args.rest.is_some() {
+            // REST clients have no workspace roots to take the file from
+            anyhow::bail!(
+                "No {} found in {} or its parents. Pass a path, or use --init to create one.",
+                discovery::DATA_FILE_NAME,
+                cwd
+            );
+        }
+        eprintln!(
+            "No {} found in {} or its parents; using the one in the client's workspace root. Pass a path, or use --init to create one here.",
+            discovery::DATA_FILE_NAME,
+            cwd
+        );
+    }
+    let mut builder = if from_roots {
+        // A placeholder until the root is known: nothing is written to it
+        let roots_args = args.clone();
+        GtdServerHandler::builder()
+            .storage(Storage::new(&file, false))
+            .roots(Arc::new(move |path| roots_args.open_storage(path, true)))
+    } else {
+        GtdServerHandler::builder().storage(args.open_storage(&file, true)?)
+    };
+    for workspace in &args.workspaces {
+        let storage = args.open_storage(&workspace.path, false)?;
+        builder = builder.workspace(&workspace.name, storage);
+    }
+    if let Some(template) = args.commit_template.clone() {
+        builder = builder.commit_template(template);
+    }
+    if let Some(instructions) = args.instructions.clone() {
+        builder = builder.instructions(instructions);
+    }
+    if let Some(policy) = IdPolicy::from_options(
+        args.strict_ids,
+        args.id_pattern.as_deref(),
+        args.id_max_length,
+        args.id_forbidden_chars.as_deref(),
+    )
+    .map_err(anyhow::Error::msg)?
+    {
+        builder = builder.id_policy(policy);
+    }
+    if let Some(scheme) = args.id_scheme {
+        builder = builder.id_generator(Arc::new(scheme));
+    }
+    builder = builder.duplicate_check(args.duplicate_titles.unwrap_or_default());
+    if !args.wip_limits.is_empty() {
+        builder = builder.wip_limits(args.wip_limits.clone(), args.wip_mode.unwrap_or_default());
+    }
+    if args.escalate_after_days.is_some() || args.escalate_to_next_action {
+        builder = builder.escalation(args.escalate_after_days, args.escalate_to_next_action);
+    }
+    if let Some(days) = args.stale_after_days {
+        builder = builder.stale_after_days(days);
+    }
+    if let Some(days) = args.trash_retention_days {
+        builder = builder.trash_retention(days);
+    }
+    if let Some(caldav) = args.caldav() {
+        builder = builder.caldav(caldav);
+    }
+    if let Some(offset) = args.timezone {
+        builder = builder.timezone(offset);
+    }
+    let handler = builder
+        .strict_saves(args.strict)
+        .read_only(args.read_only)
+        .build()?;
+    if let Some(addr) = &args.rest {
+        let handler = Arc::new(handler);
+        if let Some(schedule) = schedule {
+            tokio::spawn(daemon::run(Arc::downgrade(&handler), schedule));
+        }
+        if let Some(unknown) = args
+            .rest_tools
+            .iter()
+            .find(|tool| tool_hints::tool_hint(tool).is_none())
+        {
+            anyhow::bail!("--rest-tools: unknown tool '{}'", unknown);
+        }
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        eprintln!(
+            "Serving the REST API on http://{}/api/",
+            listener.local_addr()?
+        );
+        rest::serve(handler, listener, args.rest_tools, tokio::signal::ctrl_c()).await?;
+    } else if let Some(addr) = &args.http {
+        let handler = Arc::new(handler);
+        if let Some(schedule) = schedule {
+            tokio::spawn(daemon::run(Arc::downgrade(&handler), schedule));
+        }
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        eprintln!(
+            "Serving MCP over HTTP on http://{}{}",
+            listener.local_addr()?,
+            mcp_http::ENDPOINT
+        );
+        mcp_http::serve(handler, listener, tokio::signal::ctrl_c()).await?;
+    } else {
+        let handler = Arc::new(handler);
+        if let Some(schedule) = schedule {
+            tokio::spawn(daemon::run(Arc::downgrade(&handler), schedule));
+        }
+        serve_stdio(SharedServer::new(handler)).await?;
+    }
     Ok(())
 }
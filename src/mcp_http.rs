@@ -0,0 +1,429 @@
+//! MCP over HTTP for several clients at once
+//!
+//! `gtd-mcp serve --http [ADDR]` serves MCP over the Streamable HTTP transport
+//! instead of stdio, so several agents can work on one data file through one
+//! server:
+//!
+//! | Request | Response |
+//! |---------|----------|
+//! | `POST /mcp` | A JSON-RPC message; requests are answered with their JSON response |
+//! | `GET /mcp` | Server-sent events carrying the session's notifications |
+//! | `DELETE /mcp` | Ends the session |
+//!
+//! An `initialize` request starts a session and returns its ID in the
+//! `Mcp-Session-Id` header; every later request of the client carries it.
+//! Batches are not supported.
+//!
+//! All sessions run on the same handler (and the daemon, if enabled): reading
+//! tools share the data lock, changing tools take it exclusively and saves are
+//! written one at a time, so no client sees a half-applied change. After each
+//! save every session gets a `notifications/message` from the `gtd-mcp`
+//! logger with the commit message, telling agents that their view of the data
+//! may be out of date. Each session has its own current context
+//! (`set_current_context`). A transaction belongs to the session that began it:
+//! other sessions can read, but their changes are refused until it is
+//! committed or aborted, and ending the session aborts it (see
+//! [`GtdServerHandler::begin_transaction`]).
+//!
+//! There is no authentication: bind to a loopback address (the default) or
+//! put the server behind a proxy that adds it. Like the REST API, requests
+//! whose `Origin` or `Host` names another machine are refused with 403, so
+//! web pages open in a browser cannot reach the tools.
+
+use crate::GtdServerHandler;
+use crate::daemon::SharedServer;
+use crate::rest::{foreign_request, read_line, reason};
+use anyhow::Result;
+use mcp_attr::jsoncall::{Session, SessionOptions};
+use mcp_attr::server::McpServer;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::io::{
+    AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, DuplexStream, ReadHalf, WriteHalf,
+};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, oneshot};
+
+/// Address `--http` listens on when none is given
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7879";
+
+/// Path the MCP endpoint is served at
+pub const ENDPOINT: &str = "/mcp";
+
+/// Header carrying the session ID
+pub const SESSION_HEADER: &str = "Mcp-Session-Id";
+
+/// Largest request body accepted, in bytes
+const MAX_BODY: usize = 1 << 20;
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    session: Option<String>,
+    body: String,
+}
+
+/// Status, session ID header and JSON body of a response (`None`: empty body)
+type Response = (u16, Option<String>, Option<Value>);
+
+fn error(status: u16, message: impl std::fmt::Display) -> Response {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": { "code": -32600, "message": message.to_string() },
+    });
+    (status, None, Some(body))
+}
+
+/// One connected client
+struct ClientSession {
+    /// MCP session answering the client's messages; dropping it ends the session
+    session: Session,
+    /// Where the client's messages are written to the session
+    input: tokio::sync::Mutex<WriteHalf<DuplexStream>>,
+    /// Requests waiting for their response, by JSON-encoded request ID
+    pending: Mutex<HashMap<String, oneshot::Sender<Value>>>,
+    /// Messages for the client's event stream
+    events: broadcast::Sender<String>,
+}
+
+impl ClientSession {
+    /// Start an MCP session on `handler`
+    fn open(handler: &Arc<GtdServerHandler>) -> Arc<Self> {
+        let (client, server) = tokio::io::duplex(1 << 16);
+        let (server_read, server_write) = tokio::io::split(server);
+        let session = Session::new(
+            SharedServer::new(handler.clone()).into_handler(),
+            BufReader::new(server_read),
+            server_write,
+            &SessionOptions::default(),
+        );
+        let (output, input) = tokio::io::split(client);
+        let client = Arc::new(Self {
+            session,
+            input: tokio::sync::Mutex::new(input),
+            pending: Mutex::new(HashMap::new()),
+            events: broadcast::channel(64).0,
+        });
+        tokio::spawn(route_output(Arc::downgrade(&client), output));
+        tokio::spawn(forward_changes(
+            Arc::downgrade(&client),
+            handler.subscribe_changes(),
+        ));
+        client
+    }
+
+    /// Pass a message to the session
+    ///
+    /// # Returns
+    /// The response when `message` is a request
+    async fn send(&self, message: &Value) -> Result<Option<Value>> {
+        let waiting = match message.get("id") {
+            Some(id) if message.get("method").is_some() => {
+                let (sender, receiver) = oneshot::channel();
+                self.pending.lock().unwrap().insert(id.to_string(), sender);
+                Some(receiver)
+            }
+            _ => None,
+        };
+        let mut line = message.to_string();
+        line.push('\n');
+        self.input.lock().await.write_all(line.as_bytes()).await?;
+        match waiting {
+            Some(receiver) => {
+                Ok(Some(receiver.await.map_err(|_| {
+                    anyhow::anyhow!("the session ended before answering")
+                })?))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Hand the session's responses to the waiting requests and everything else
+/// to the event stream
+async fn route_output(client: Weak<ClientSession>, output: ReadHalf<DuplexStream>) {
+    let mut lines = BufReader::new(output).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Some(client) = client.upgrade() else {
+            return;
+        };
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(_) => continue,
+        };
+        let waiting = match message.get("id") {
+            Some(id) if message.get("method").is_none() => {
+                client.pending.lock().unwrap().remove(&id.to_string())
+            }
+            _ => None,
+        };
+        match waiting {
+            Some(sender) => {
+                let _ = sender.send(message);
+            }
+            None => {
+                let _ = client.events.send(line);
+            }
+        }
+    }
+}
+
+/// Tell the client about every save until its session ends
+async fn forward_changes(client: Weak<ClientSession>, mut changes: broadcast::Receiver<String>) {
+    loop {
+        let message = match changes.recv().await {
+            Ok(message) => message,
+            Err(broadcast::error::RecvError::Lagged(_)) => "GTD data changed".to_string(),
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        let Some(client) = client.upgrade() else {
+            return;
+        };
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/message",
+            "params": {
+                "level": "info",
+                "logger": "gtd-mcp",
+                "data": { "event": "changed", "message": message },
+            },
+        });
+        let _ = client.events.send(notification.to_string());
+    }
+}
+
+/// Connected clients by session ID
+type Sessions = Mutex<HashMap<String, Arc<ClientSession>>>;
+
+/// Serve MCP on `listener` until `shutdown` completes
+///
+/// Pending debounced writes are flushed before returning.
+///
+/// # Arguments
+/// * `handler` - The handler every session runs on, shared with the daemon if any
+/// * `listener` - Bound listener (see [`DEFAULT_ADDR`])
+/// * `shutdown` - Future that stops the server, e.g. `tokio::signal::ctrl_c()`
+pub async fn serve(
+    handler: Arc<GtdServerHandler>,
+    listener: TcpListener,
+    shutdown: impl Future,
+) -> Result<()> {
+    let sessions: Arc<Sessions> = Arc::default();
+    tokio::pin!(shutdown);
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => accepted?.0,
+            _ = &mut shutdown => break,
+        };
+        let handler = handler.clone();
+        let sessions = sessions.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &handler, &sessions).await {
+                eprintln!("Warning: MCP HTTP request failed: {:#}", e);
+            }
+        });
+    }
+    sessions.lock().unwrap().clear();
+    handler.storage().flush()
+}
+
+/// Read one request from `stream`, answer it and close the connection
+async fn handle_connection(
+    stream: TcpStream,
+    handler: &Arc<GtdServerHandler>,
+    sessions: &Sessions,
+) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let request = match read_request(&mut reader).await? {
+        Ok(request) => request,
+        Err(response) => return write_response(reader.get_mut(), response).await,
+    };
+    if request.path != ENDPOINT {
+        let response = error(404, format!("not found; the MCP endpoint is {}", ENDPOINT));
+        return write_response(reader.get_mut(), response).await;
+    }
+    let client = request
+        .session
+        .as_ref()
+        .and_then(|id| sessions.lock().unwrap().get(id).cloned());
+    let response = match (request.method.as_str(), client) {
+        ("POST", client) => post(handler, sessions, &request, client).await,
+        // Hold only a receiver so ending the session also ends the stream
+        ("GET", Some(client)) => {
+            return stream_events(reader.get_mut(), client.events.subscribe()).await;
+        }
+        ("DELETE", Some(client)) => {
+            if let Some(id) = &request.session {
+                sessions.lock().unwrap().remove(id);
+            }
+            client.session.shutdown();
+            (200, None, None)
+        }
+        ("GET" | "DELETE", None) => unknown_session(&request),
+        _ => error(405, format!("{} is not allowed here", request.method)),
+    };
+    write_response(reader.get_mut(), response).await
+}
+
+/// The response for a request without a live session
+fn unknown_session(request: &Request) -> Response {
+    match request.session {
+        Some(_) => error(404, "unknown or ended session; send initialize again"),
+        None => error(400, format!("missing {} header", SESSION_HEADER)),
+    }
+}
+
+/// Answer a JSON-RPC message posted by a client
+async fn post(
+    handler: &Arc<GtdServerHandler>,
+    sessions: &Sessions,
+    request: &Request,
+    client: Option<Arc<ClientSession>>,
+) -> Response {
+    let message: Value = match serde_json::from_str(&request.body) {
+        Ok(Value::Array(_)) => return error(400, "batches are not supported"),
+        Ok(message) => message,
+        Err(e) => return error(400, format!("invalid JSON: {}", e)),
+    };
+    let method = message.get("method").and_then(Value::as_str);
+    let (client, new_session) = match (method, client) {
+        (Some("initialize"), None) if request.session.is_none() => {
+            let client = ClientSession::open(handler);
+            let id = session_id();
+            sessions.lock().unwrap().insert(id.clone(), client.clone());
+            (client, Some(id))
+        }
+        (Some("initialize"), Some(_)) => return error(400, "the session is already initialized"),
+        (_, Some(client)) => (client, None),
+        (_, None) => return unknown_session(request),
+    };
+    // Change notifications are always sent; accept any level the client asks for
+    if method == Some("logging/setLevel")
+        && let Some(id) = message.get("id")
+    {
+        let body = json!({ "jsonrpc": "2.0", "id": id, "result": {} });
+        return (200, None, Some(body));
+    }
+    match client.send(&message).await {
+        Ok(Some(mut response)) => {
+            if new_session.is_some()
+                && let Some(capabilities) = response.pointer_mut("/result/capabilities")
+            {
+                capabilities["logging"] = json!({});
+            }
+            (200, new_session, Some(response))
+        }
+        Ok(None) => (202, None, None),
+        Err(e) => error(500, e),
+    }
+}
+
+/// Random ID for a new session
+fn session_id() -> String {
+    let random = || RandomState::new().hash_one(0u8);
+    format!("{:016x}{:016x}", random(), random())
+}
+
+/// Send the client's events as server-sent events until it disconnects
+async fn stream_events(
+    stream: &mut TcpStream,
+    mut events: broadcast::Receiver<String>,
+) -> Result<()> {
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n";
+    stream.write_all(head.as_bytes()).await?;
+    stream.flush().await?;
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        stream
+            .write_all(format!("event: message\ndata: {}\n\n", event).as_bytes())
+            .await?;
+        stream.flush().await?;
+    }
+    stream.shutdown().await?;
+    Ok(())
+}
+
+async fn write_response(stream: &mut TcpStream, (status, session, body): Response) -> Result<()> {
+    let body = body.map(|body| body.to_string()).unwrap_or_default();
+    let mut head = format!("HTTP/1.1 {} {}\r\n", status, reason(status));
+    if !body.is_empty() {
+        head.push_str("Content-Type: application/json\r\n");
+    }
+    if let Some(session) = session {
+        head.push_str(&format!("{}: {}\r\n", SESSION_HEADER, session));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read the request line, headers and body
+///
+/// # Returns
+/// The request, or the error response for a malformed one; I/O errors are
+/// returned as `Err`
+async fn read_request(
+    reader: &mut BufReader<TcpStream>,
+) -> Result<std::result::Result<Request, Response>> {
+    let request_line = read_line(reader).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(error(400, "malformed request line")));
+    };
+    let mut length = 0;
+    let (mut session, mut host, mut origin) = (None, None, None);
+    loop {
+        let line = read_line(reader).await?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            let Ok(value) = value.trim().parse() else {
+                return Ok(Err(error(400, "invalid Content-Length")));
+            };
+            length = value;
+        } else if name.eq_ignore_ascii_case(SESSION_HEADER) {
+            session = Some(value.trim().to_string());
+        } else if name.eq_ignore_ascii_case("host") {
+            host = Some(value.trim().to_string());
+        } else if name.eq_ignore_ascii_case("origin") {
+            origin = Some(value.trim().to_string());
+        }
+    }
+    if length > MAX_BODY {
+        return Ok(Err(error(413, "request body is too large")));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    if let Some(reason) = foreign_request(host.as_deref(), origin.as_deref()) {
+        return Ok(Err(error(403, reason)));
+    }
+    let Ok(body) = String::from_utf8(body) else {
+        return Ok(Err(error(400, "request body is not UTF-8")));
+    };
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+    Ok(Ok(Request {
+        method: method.to_string(),
+        path: path.trim_end_matches('/').to_string(),
+        session,
+        body,
+    }))
+}
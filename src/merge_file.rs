@@ -0,0 +1,188 @@
+//! Merging another GTD data file into this one
+//!
+//! Used to consolidate files that grew apart (e.g. an old laptop's gtd.toml).
+//! Items only in the other file are added and identical items are skipped.
+//! Items whose ID exists on both sides with different content are resolved by
+//! the [`MergeStrategy`]: the incoming item gets a suffixed ID, or the version
+//! with the newer `updated_at` wins.
+
+use crate::gtd::{GtdData, Nota};
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// How to resolve an ID used by different items in both files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Keep both: the incoming item is added as `id-2` (or `id-3`, ...) and
+    /// references to it in the other file follow the new ID. Contexts are
+    /// never suffixed, since the same name means the same context.
+    #[default]
+    Suffix,
+    /// Keep the version with the newer `updated_at` (this file wins ties)
+    Newest,
+}
+
+impl FromStr for MergeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "suffix" => Ok(MergeStrategy::Suffix),
+            "newest" => Ok(MergeStrategy::Newest),
+            _ => Err(format!(
+                "Invalid merge strategy '{}'. Valid options are: suffix, newest",
+                s
+            )),
+        }
+    }
+}
+
+/// What a merge changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    /// IDs of items added under their own ID
+    pub added: Vec<String>,
+    /// Incoming items added under a new ID, as (original ID, new ID)
+    pub renamed: Vec<(String, String)>,
+    /// IDs of items replaced by the newer incoming version
+    pub replaced: Vec<String>,
+    /// IDs of colliding items whose version in this file was kept
+    pub kept: Vec<String>,
+    /// Number of items identical in both files
+    pub unchanged: usize,
+}
+
+impl MergeSummary {
+    /// Whether the merge changed nothing
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.renamed.is_empty() && self.replaced.is_empty()
+    }
+}
+
+impl fmt::Display for MergeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} renamed, {} replaced, {} kept, {} unchanged",
+            self.added.len(),
+            self.renamed.len(),
+            self.replaced.len(),
+            self.kept.len(),
+            self.unchanged
+        )?;
+        if !self.added.is_empty() {
+            write!(f, "\nAdded: {}", self.added.join(", "))?;
+        }
+        if !self.renamed.is_empty() {
+            let renamed: Vec<String> = self
+                .renamed
+                .iter()
+                .map(|(from, to)| format!("{} → {}", from, to))
+                .collect();
+            write!(f, "\nRenamed: {}", renamed.join(", "))?;
+        }
+        if !self.replaced.is_empty() {
+            write!(f, "\nReplaced: {}", self.replaced.join(", "))?;
+        }
+        if !self.kept.is_empty() {
+            write!(f, "\nKept: {}", self.kept.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Merge `other` into `data`, leaving `data` untouched on any error
+///
+/// # Arguments
+/// * `data` - The data to merge into
+/// * `other` - The data of the other file
+/// * `strategy` - How to resolve IDs used by different items on both sides
+pub fn merge(data: &mut GtdData, other: &GtdData, strategy: MergeStrategy) -> Result<MergeSummary> {
+    let mut summary = MergeSummary::default();
+    let mut merged = data.clone();
+
+    // Pick every new ID up front so references can follow renamed items
+    let mut renames: HashMap<String, String> = HashMap::new();
+    if strategy == MergeStrategy::Suffix {
+        for nota in other.iter().filter(|nota| !nota.is_context()) {
+            let Some(existing) = data.resolve_id(&nota.id).and_then(|id| data.find_by_id(id))
+            else {
+                continue;
+            };
+            if existing != *nota {
+                let new_id = (2..)
+                    .map(|n| format!("{}-{}", nota.id, n))
+                    .find(|candidate| {
+                        !data.contains_id(candidate)
+                            && !other.contains_id(candidate)
+                            && !renames.values().any(|taken| taken == candidate)
+                    })
+                    .expect("an unused suffix exists");
+                renames.insert(nota.id.clone(), new_id);
+            }
+        }
+    }
+    let follow = |id: &Option<String>| -> Option<String> {
+        id.as_ref()
+            .map(|id| renames.get(id).cloned().unwrap_or_else(|| id.clone()))
+    };
+
+    for nota in other.iter() {
+        let incoming = Nota {
+            id: renames.get(&nota.id).cloned().unwrap_or(nota.id.clone()),
+            project: follow(&nota.project),
+            goal: follow(&nota.goal),
+            ..nota.clone()
+        };
+        if incoming.id != nota.id {
+            summary.renamed.push((nota.id.clone(), incoming.id.clone()));
+            merged.add(incoming);
+            continue;
+        }
+        let Some(existing) = merged
+            .resolve_id(&incoming.id)
+            .and_then(|id| merged.find_by_id(id))
+        else {
+            summary.added.push(incoming.id.clone());
+            merged.add(incoming);
+            continue;
+        };
+        if existing == incoming {
+            summary.unchanged += 1;
+        } else if strategy == MergeStrategy::Newest && incoming.updated_at > existing.updated_at {
+            summary.replaced.push(existing.id.clone());
+            merged.update(
+                &existing.id,
+                Nota {
+                    id: existing.id.clone(),
+                    ..incoming
+                },
+            );
+        } else {
+            summary.kept.push(existing.id.clone());
+        }
+    }
+    merged.task_counter = merged.task_counter.max(other.task_counter);
+    merged.project_counter = merged.project_counter.max(other.project_counter);
+
+    // Only report problems the merge introduced, not ones already in the file
+    let before = data.check_integrity();
+    let new_issues: Vec<String> = merged
+        .check_integrity()
+        .into_iter()
+        .filter(|issue| !before.contains(issue))
+        .collect();
+    if !new_issues.is_empty() {
+        bail!(
+            "Merge refused, it would introduce {} problem{}:\n- {}",
+            new_issues.len(),
+            if new_issues.len() == 1 { "" } else { "s" },
+            new_issues.join("\n- ")
+        );
+    }
+
+    *data = merged;
+    Ok(summary)
+}
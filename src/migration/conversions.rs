@@ -21,8 +21,20 @@ pub fn nota_from_task(task: Task) -> Nota {
         start_date: task.start_date,
         created_at: task.created_at,
         updated_at: task.updated_at,
-        recurrence_pattern: None,
-        recurrence_config: None,
+        recurrence_pattern: task.recurrence_pattern,
+        recurrence_config: task.recurrence_config,
+        link: task.link,
+        priority: task.priority,
+        due_date: task.due_date,
+        estimate: task.estimate,
+        energy: task.energy,
+        focus_date: task.focus_date,
+        paused: task.paused,
+        previous_status: task.previous_status,
+        goal: task.goal,
+        availability: task.availability,
+        escalate_after_days: task.escalate_after_days,
+        status_since: task.status_since,
     }
 }
 
@@ -41,8 +53,20 @@ pub fn nota_from_project(project: Project) -> Nota {
         start_date: project.start_date,
         created_at: project.created_at,
         updated_at: project.updated_at,
-        recurrence_pattern: None,
-        recurrence_config: None,
+        recurrence_pattern: project.recurrence_pattern,
+        recurrence_config: project.recurrence_config,
+        link: project.link,
+        priority: project.priority,
+        due_date: project.due_date,
+        estimate: project.estimate,
+        energy: project.energy,
+        focus_date: project.focus_date,
+        paused: project.paused,
+        previous_status: project.previous_status,
+        goal: project.goal,
+        availability: project.availability,
+        escalate_after_days: project.escalate_after_days,
+        status_since: project.status_since,
     }
 }
 
@@ -61,8 +85,20 @@ pub fn nota_from_context(context: Context) -> Nota {
         start_date: context.start_date,
         created_at: context.created_at.unwrap_or_else(local_date_today),
         updated_at: context.updated_at.unwrap_or_else(local_date_today),
-        recurrence_pattern: None,
-        recurrence_config: None,
+        recurrence_pattern: context.recurrence_pattern,
+        recurrence_config: context.recurrence_config,
+        link: context.link,
+        priority: context.priority,
+        due_date: context.due_date,
+        estimate: context.estimate,
+        energy: context.energy,
+        focus_date: context.focus_date,
+        paused: context.paused,
+        previous_status: context.previous_status,
+        goal: context.goal,
+        availability: context.availability,
+        escalate_after_days: context.escalate_after_days,
+        status_since: context.status_since,
     }
 }
 
@@ -83,6 +119,20 @@ pub fn nota_to_task(nota: &Nota) -> Option<Task> {
             start_date: nota.start_date,
             created_at: nota.created_at,
             updated_at: nota.updated_at,
+            recurrence_pattern: nota.recurrence_pattern.clone(),
+            recurrence_config: nota.recurrence_config.clone(),
+            link: nota.link.clone(),
+            priority: nota.priority,
+            due_date: nota.due_date,
+            estimate: nota.estimate,
+            energy: nota.energy,
+            focus_date: nota.focus_date,
+            paused: nota.paused,
+            previous_status: nota.previous_status.clone(),
+            goal: nota.goal.clone(),
+            availability: nota.availability.clone(),
+            escalate_after_days: nota.escalate_after_days,
+            status_since: nota.status_since,
         }),
     }
 }
@@ -93,16 +143,32 @@ pub fn nota_to_task(nota: &Nota) -> Option<Task> {
 /// for serialization in legacy formats.
 pub fn nota_to_project(nota: &Nota) -> Option<Project> {
     if nota.status == NotaStatus::project {
-        Some(Project::new(
-            nota.id.clone(),
-            nota.title.clone(),
-            nota.notes.clone(),
-            nota.project.clone(),
-            nota.context.clone(),
-            nota.start_date,
-            nota.created_at,
-            nota.updated_at,
-        ))
+        Some(Project {
+            recurrence_pattern: nota.recurrence_pattern.clone(),
+            recurrence_config: nota.recurrence_config.clone(),
+            link: nota.link.clone(),
+            priority: nota.priority,
+            due_date: nota.due_date,
+            estimate: nota.estimate,
+            energy: nota.energy,
+            focus_date: nota.focus_date,
+            paused: nota.paused,
+            previous_status: nota.previous_status.clone(),
+            goal: nota.goal.clone(),
+            availability: nota.availability.clone(),
+            escalate_after_days: nota.escalate_after_days,
+            status_since: nota.status_since,
+            ..Project::new(
+                nota.id.clone(),
+                nota.title.clone(),
+                nota.notes.clone(),
+                nota.project.clone(),
+                nota.context.clone(),
+                nota.start_date,
+                nota.created_at,
+                nota.updated_at,
+            )
+        })
     } else {
         None
     }
@@ -124,6 +190,20 @@ pub fn nota_to_context(nota: &Nota) -> Option<Context> {
             start_date: nota.start_date,
             created_at: Some(nota.created_at),
             updated_at: Some(nota.updated_at),
+            recurrence_pattern: nota.recurrence_pattern.clone(),
+            recurrence_config: nota.recurrence_config.clone(),
+            link: nota.link.clone(),
+            priority: nota.priority,
+            due_date: nota.due_date,
+            estimate: nota.estimate,
+            energy: nota.energy,
+            focus_date: nota.focus_date,
+            paused: nota.paused,
+            previous_status: nota.previous_status.clone(),
+            goal: nota.goal.clone(),
+            availability: nota.availability.clone(),
+            escalate_after_days: nota.escalate_after_days,
+            status_since: nota.status_since,
         })
     } else {
         None
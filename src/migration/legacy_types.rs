@@ -4,7 +4,7 @@
 //! that are used for backward compatibility with old TOML formats.
 //! New code should use the Nota structure from the gtd module.
 
-use crate::gtd::NotaStatus;
+use crate::gtd::{Energy, NotaStatus, Priority, RecurrencePattern};
 use chrono::{Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -55,6 +55,48 @@ pub struct Task {
     pub created_at: NaiveDate,
     /// Date when the task was last updated
     pub updated_at: NaiveDate,
+    /// Optional recurrence pattern (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_pattern: Option<RecurrencePattern>,
+    /// Optional recurrence configuration (weekdays, days, or month-day pairs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_config: Option<String>,
+    /// Optional source URL (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Optional priority (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Optional due date (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    /// Optional estimate in minutes (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    /// Optional energy (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub energy: Option<Energy>,
+    /// Optional focus day (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_date: Option<NaiveDate>,
+    /// Paused recurrence (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+    /// Status before trashing (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<NotaStatus>,
+    /// Goal of a project (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    /// Availability of a context (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<String>,
+    /// Escalation threshold of a waiting item (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalate_after_days: Option<u32>,
+    /// Date the item entered its status (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_since: Option<NaiveDate>,
 }
 
 /// A GTD project (legacy, used for migration only)
@@ -106,6 +148,48 @@ pub struct Project {
     /// Defaults to None if not specified in the TOML file.
     #[serde(skip_serializing, default)]
     pub status: Option<String>,
+    /// Optional recurrence pattern (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_pattern: Option<RecurrencePattern>,
+    /// Optional recurrence configuration (weekdays, days, or month-day pairs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_config: Option<String>,
+    /// Optional source URL (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Optional priority (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Optional due date (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    /// Optional estimate in minutes (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    /// Optional energy (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub energy: Option<Energy>,
+    /// Optional focus day (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_date: Option<NaiveDate>,
+    /// Paused recurrence (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+    /// Status before trashing (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<NotaStatus>,
+    /// Goal of a project (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    /// Availability of a context (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<String>,
+    /// Escalation threshold of a waiting item (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalate_after_days: Option<u32>,
+    /// Date the item entered its status (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_since: Option<NaiveDate>,
 }
 
 impl Project {
@@ -134,6 +218,20 @@ impl Project {
             created_at,
             updated_at,
             status: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         }
     }
 }
@@ -175,6 +273,48 @@ pub struct Context {
     /// Last update date
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub updated_at: Option<NaiveDate>,
+    /// Optional recurrence pattern (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_pattern: Option<RecurrencePattern>,
+    /// Optional recurrence configuration (weekdays, days, or month-day pairs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_config: Option<String>,
+    /// Optional source URL (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Optional priority (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Optional due date (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    /// Optional estimate in minutes (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u32>,
+    /// Optional energy (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub energy: Option<Energy>,
+    /// Optional focus day (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_date: Option<NaiveDate>,
+    /// Paused recurrence (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+    /// Status before trashing (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<NotaStatus>,
+    /// Goal of a project (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    /// Availability of a context (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<String>,
+    /// Escalation threshold of a waiting item (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalate_after_days: Option<u32>,
+    /// Date the item entered its status (kept when the current status-array layout is loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_since: Option<NaiveDate>,
 }
 
 /// Intermediate format for deserializing projects that supports both old and new formats
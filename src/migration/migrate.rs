@@ -36,6 +36,10 @@ pub struct GtdDataMigrationHelper {
     #[serde(default)]
     pub(crate) trash: Vec<Task>,
     #[serde(default)]
+    pub(crate) goal: Vec<Task>,
+    #[serde(default)]
+    pub(crate) template: Vec<Task>,
+    #[serde(default)]
     pub(crate) projects: Option<ProjectsFormat>,
     #[serde(default)]
     pub(crate) contexts: HashMap<String, Context>,
@@ -50,6 +54,10 @@ pub struct GtdDataMigrationHelper {
     pub(crate) task_counter: u32,
     #[serde(default)]
     pub(crate) project_counter: u32,
+    #[serde(default)]
+    pub(crate) external_notes_min_chars: Option<usize>,
+    #[serde(default)]
+    pub(crate) migration_history: Vec<super::steps::MigrationRecord>,
 }
 
 /// Migrate projects from Version 1 format (Vec) to Version 2 format (HashMap)
@@ -214,6 +222,8 @@ pub fn migrate_notas_v3_to_internal(
                     contexts.insert(context.name.clone(), context);
                 }
             }
+            // Goals and templates did not exist in the legacy formats
+            NotaStatus::goal | NotaStatus::template => {}
         }
     }
 }
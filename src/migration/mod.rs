@@ -1,14 +1,15 @@
 //! Migration module for GTD data format versions
 //!
 //! This module handles migrations between different versions of the GTD data format.
-//! Each version migration is implemented as a separate function to maintain clarity
-//! and enable step-by-step migrations from older versions to the latest.
+//! File migrations run through a registry of versioned steps ([`STEPS`]), each
+//! upgrading the raw TOML document by one version, executed in order from the
+//! file's version to the latest.
 //!
 //! ## Migration Strategy
 //!
 //! When a new format version is introduced:
-//! 1. Add a new migration function (e.g., `migrate_v2_to_v3`)
-//! 2. Update the `migrate_to_latest` function to chain migrations
+//! 1. Bump [`CURRENT_FORMAT_VERSION`]
+//! 2. Append a `MigrationStep` from the previous version to [`STEPS`]
 //! 3. Add tests for the new migration path
 //!
 //! ## Current Versions
@@ -16,11 +17,21 @@
 //! - **Version 1**: Projects stored as `Vec<Project>` (TOML: `[[projects]]`)
 //! - **Version 2**: Projects stored as `HashMap<String, Project>` (TOML: `[projects.id]`), separate arrays for each status
 //! - **Version 3**: Internal storage uses `Vec<Nota>`, serializes as separate status arrays (`[[inbox]]`, `[[next_action]]`, etc.)
+//! - **Version 4**: Version 3 with long notes in external Markdown files (`notes_file`), see [`crate::external_notes`]
+//!
+//! ## Explicit Migration
+//!
+//! `Storage::load` refuses files that still contain legacy constructs. They are
+//! upgraded explicitly with `gtd-mcp migrate <file>` (see [`migrate_file`]), which
+//! supports `--dry-run` and writes a backup before rewriting the file. Every step
+//! that ran is recorded in the file's `[[migration_history]]`.
 
 mod conversions;
 mod legacy_types;
 mod migrate;
 mod normalize;
+mod steps;
+mod upgrade;
 
 // Re-export public types and functions
 pub use conversions::{
@@ -36,6 +47,12 @@ pub use normalize::{
     normalize_context_line_endings, normalize_project_line_endings, normalize_string_line_endings,
     normalize_task_line_endings,
 };
+pub use steps::{AppliedStep, MigrationRecord, MigrationStep, STEPS, source_version};
+pub(crate) use upgrade::backup_path_for;
+pub use upgrade::{
+    CURRENT_FORMAT_VERSION, EXTERNAL_NOTES_FORMAT_VERSION, MigrationReport, migrate_file,
+    plan_migration,
+};
 
 // Re-export utility functions
 pub use legacy_types::local_date_today;
@@ -0,0 +1,305 @@
+//! Registry of versioned migration steps
+//!
+//! Each step upgrades a TOML document by exactly one format version, working on
+//! the raw table before it is deserialized. [`run`] starts at the document's
+//! version and applies the steps in order (v1 → v2 → … → current), so adding a
+//! format version means appending one step to [`STEPS`].
+//!
+//! The version a document starts from is the lower of its declared
+//! `format_version` and the oldest legacy construct it still contains; a file
+//! that claims version 3 but carries `[projects.<id>]` tables still goes through
+//! the v2 → v3 step.
+
+use super::upgrade::CURRENT_FORMAT_VERSION;
+use crate::gtd::NotaStatus;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+/// One upgrade from format version `from` to `to`
+pub struct MigrationStep {
+    /// Version the step upgrades from
+    pub from: u32,
+    /// Version the step upgrades to (always `from + 1`)
+    pub to: u32,
+    /// Summary recorded in the migration history
+    pub description: &'static str,
+    /// Describe the changes the step would make to a document
+    detect: fn(&Table) -> Vec<String>,
+    /// Rewrite the document in place
+    apply: fn(&mut Table),
+}
+
+/// All migration steps, oldest first
+pub const STEPS: &[MigrationStep] = &[
+    MigrationStep {
+        from: 1,
+        to: 2,
+        description: "Key projects by ID",
+        detect: detect_v1_to_v2,
+        apply: apply_v1_to_v2,
+    },
+    MigrationStep {
+        from: 2,
+        to: 3,
+        description: "Store items as arrays of tables per status",
+        detect: detect_v2_to_v3,
+        apply: apply_v2_to_v3,
+    },
+];
+
+/// A step that ran, with the changes it found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedStep {
+    /// Version the step upgraded from
+    pub from: u32,
+    /// Version the step upgraded to
+    pub to: u32,
+    /// Summary of the step
+    pub description: &'static str,
+    /// Human-readable descriptions of the changes (may be empty)
+    pub changes: Vec<String>,
+}
+
+/// Entry of the migration history kept in the data file (`[[migration_history]]`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationRecord {
+    /// Version the step upgraded from
+    pub from: u32,
+    /// Version the step upgraded to
+    pub to: u32,
+    /// Date the step ran
+    pub date: NaiveDate,
+    /// Summary of the step
+    pub description: String,
+}
+
+impl AppliedStep {
+    /// History entry for this step, dated `date`
+    pub fn record(&self, date: NaiveDate) -> MigrationRecord {
+        MigrationRecord {
+            from: self.from,
+            to: self.to,
+            date,
+            description: self.description.to_string(),
+        }
+    }
+}
+
+/// Format version a document has to be migrated from
+///
+/// # Returns
+/// The version, or [`CURRENT_FORMAT_VERSION`] when nothing needs migrating
+pub fn source_version(table: &Table) -> u32 {
+    let declared = table
+        .get("format_version")
+        .and_then(Value::as_integer)
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(CURRENT_FORMAT_VERSION);
+    let inferred = if matches!(table.get("projects"), Some(Value::Array(_))) {
+        1
+    } else if !detect_v2_to_v3(table).is_empty() {
+        2
+    } else {
+        CURRENT_FORMAT_VERSION
+    };
+    declared.min(inferred)
+}
+
+/// Upgrade a document to the current format, one step at a time
+///
+/// # Returns
+/// The steps that ran, in order (empty when the document was already current)
+pub fn run(table: &mut Table) -> Vec<AppliedStep> {
+    let mut version = source_version(table);
+    let mut applied = Vec::new();
+    for step in STEPS {
+        if step.from != version {
+            continue;
+        }
+        let changes = (step.detect)(table);
+        (step.apply)(table);
+        table.insert("format_version".to_string(), Value::from(step.to as i64));
+        applied.push(AppliedStep {
+            from: step.from,
+            to: step.to,
+            description: step.description,
+            changes,
+        });
+        version = step.to;
+    }
+    applied
+}
+
+/// v1 stored projects as a `[[projects]]` array with the ID inside each entry
+fn detect_v1_to_v2(table: &Table) -> Vec<String> {
+    match table.get("projects") {
+        Some(Value::Array(projects)) => vec![format!(
+            "[[projects]] array (v1): {} project(s) → [projects.<id>]",
+            projects.len()
+        )],
+        _ => Vec::new(),
+    }
+}
+
+fn apply_v1_to_v2(table: &mut Table) {
+    let Some(projects) = take_array(table, "projects") else {
+        return;
+    };
+    let mut keyed = Table::new();
+    for project in projects {
+        let Value::Table(mut project) = project else {
+            continue;
+        };
+        let id = match project.remove("id") {
+            Some(Value::String(id)) => id,
+            _ => String::new(),
+        };
+        keyed.insert(id, Value::Table(project));
+    }
+    table.insert("projects".to_string(), Value::Table(keyed));
+}
+
+/// v2 keyed projects and contexts by ID and named project fields differently;
+/// some files also kept every item in one `[[notas]]` array
+fn detect_v2_to_v3(table: &Table) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    if let Some(Value::Table(projects)) = table.get("projects") {
+        changes.push(format!(
+            "[projects.<id>] tables (v2): {} project(s) → [[project]]",
+            projects.len()
+        ));
+    }
+
+    if let Some(Value::Table(contexts)) = table.get("contexts") {
+        changes.push(format!(
+            "[contexts.<name>] tables (v2): {} context(s) → [[context]]",
+            contexts.len()
+        ));
+    }
+
+    if let Some(Value::Array(notas)) = table.get("notas") {
+        changes.push(format!(
+            "[[notas]] unified array: {} item(s) → per-status arrays",
+            notas.len()
+        ));
+    }
+
+    if let Some(Value::Array(projects)) = table.get("project") {
+        let count = projects
+            .iter()
+            .filter_map(Value::as_table)
+            .filter(|project| has_legacy_project_fields(project))
+            .count();
+        if count > 0 {
+            changes.push(format!(
+                "[[project]] legacy fields (name/description/status): {} project(s) → title/notes",
+                count
+            ));
+        }
+    }
+
+    changes
+}
+
+fn has_legacy_project_fields(project: &Table) -> bool {
+    project.contains_key("name")
+        || project.contains_key("description")
+        || project
+            .get("status")
+            .is_some_and(|status| status.as_str() != Some("project"))
+}
+
+fn apply_v2_to_v3(table: &mut Table) {
+    if let Some(projects) = take_table(table, "projects") {
+        for (id, project) in projects {
+            let Value::Table(mut project) = project else {
+                continue;
+            };
+            project.insert("id".to_string(), Value::String(id));
+            push_item(table, "project", project);
+        }
+    }
+
+    if let Some(contexts) = take_table(table, "contexts") {
+        for (name, context) in contexts {
+            let Value::Table(mut context) = context else {
+                continue;
+            };
+            context.remove("name");
+            context.insert("id".to_string(), Value::String(name));
+            push_item(table, "context", context);
+        }
+    }
+
+    if let Some(notas) = take_array(table, "notas") {
+        for nota in notas {
+            let Value::Table(nota) = nota else {
+                continue;
+            };
+            // Unknown statuses land in the inbox rather than an ignored section
+            let section = nota
+                .get("status")
+                .and_then(Value::as_str)
+                .filter(|status| status.parse::<NotaStatus>().is_ok())
+                .unwrap_or("inbox")
+                .to_string();
+            push_item(table, &section, nota);
+        }
+    }
+
+    if let Some(Value::Array(projects)) = table.get_mut("project") {
+        for project in projects.iter_mut().filter_map(Value::as_table_mut) {
+            rename_field(project, "name", "title");
+            rename_field(project, "description", "notes");
+            if project
+                .get("status")
+                .is_some_and(|status| status.as_str() != Some("project"))
+            {
+                project.remove("status");
+            }
+        }
+    }
+}
+
+/// Remove `key` from the document if it holds a table
+fn take_table(table: &mut Table, key: &str) -> Option<Table> {
+    match table.get(key) {
+        Some(Value::Table(_)) => match table.remove(key) {
+            Some(Value::Table(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Remove `key` from the document if it holds an array
+fn take_array(table: &mut Table, key: &str) -> Option<Vec<Value>> {
+    match table.get(key) {
+        Some(Value::Array(_)) => match table.remove(key) {
+            Some(Value::Array(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Append an item table to the `[[section]]` array
+fn push_item(table: &mut Table, section: &str, item: Table) {
+    match table
+        .entry(section.to_string())
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(items) => items.push(Value::Table(item)),
+        // Keep a malformed section so deserializing still reports it
+        other => *other = Value::Array(vec![other.clone(), Value::Table(item)]),
+    }
+}
+
+/// Move a legacy field to its current name unless the current one is already set
+fn rename_field(item: &mut Table, legacy: &str, current: &str) {
+    if let Some(value) = item.remove(legacy) {
+        item.entry(current.to_string()).or_insert(value);
+    }
+}
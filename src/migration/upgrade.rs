@@ -0,0 +1,204 @@
+//! Explicit, file-level migration of GTD data files
+//!
+//! Loading a legacy file no longer upgrades it silently. Instead, [`plan_migration`]
+//! detects which legacy constructs a file contains, and [`migrate_file`] runs the
+//! versioned steps of [`super::steps`] and rewrites the file in the current format
+//! after writing a backup copy (`gtd-mcp migrate <file>`).
+
+use super::steps::{self, AppliedStep};
+use crate::external_notes;
+use crate::gtd::{GtdData, local_date_today};
+use crate::storage::Storage;
+use anyhow::{Context as _, Result};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Format version written by the current serializer
+pub const CURRENT_FORMAT_VERSION: u32 = 3;
+
+/// Format version of files that keep long notes in Markdown files
+///
+/// Version 4 is an alternative layout of version 3 rather than its successor:
+/// both load without migration, and `gtd-mcp migrate --external-notes` /
+/// `--inline-notes` convert between them (see [`crate::external_notes`]).
+pub const EXTERNAL_NOTES_FORMAT_VERSION: u32 = 4;
+
+/// Detect the legacy constructs in a GTD TOML document
+///
+/// Runs the migration steps on a copy of the document. Each entry describes one
+/// change the migration would make. An empty list means the document is already
+/// in the current format.
+///
+/// # Arguments
+/// * `content` - Raw TOML content
+///
+/// # Returns
+/// Human-readable descriptions of the pending changes, or a TOML parse error
+pub fn plan_migration(content: &str) -> Result<Vec<String>> {
+    let mut table: toml::Table = toml::from_str(content)?;
+    Ok(upgrade_table(&mut table).0)
+}
+
+/// Run the migration steps on a parsed document
+///
+/// # Returns
+/// The flattened list of changes (led by the `format_version` bump when the
+/// file declares an older version) and the steps that ran
+fn upgrade_table(table: &mut toml::Table) -> (Vec<String>, Vec<AppliedStep>) {
+    let mut changes = Vec::new();
+    if let Some(version) = table.get("format_version").and_then(|v| v.as_integer())
+        && version < CURRENT_FORMAT_VERSION as i64
+    {
+        changes.push(format!(
+            "format_version {} → {}",
+            version, CURRENT_FORMAT_VERSION
+        ));
+    }
+    let applied = steps::run(table);
+    for step in &applied {
+        changes.extend(step.changes.iter().cloned());
+    }
+    (changes, applied)
+}
+
+/// Outcome of [`migrate_file`]
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    /// Path of the migrated file
+    pub file_path: PathBuf,
+    /// Changes detected across all steps (empty when the file was already current)
+    pub changes: Vec<String>,
+    /// Migration steps that ran, oldest first
+    pub steps: Vec<AppliedStep>,
+    /// Number of items after migration
+    pub item_count: usize,
+    /// Backup written before rewriting (None for dry runs and no-op migrations)
+    pub backup_path: Option<PathBuf>,
+    /// Whether this was a dry run (nothing written)
+    pub dry_run: bool,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(
+                f,
+                "{} is already in the current format (version {}). Nothing to migrate.",
+                self.file_path.display(),
+                CURRENT_FORMAT_VERSION
+            );
+        }
+        let heading = if self.dry_run {
+            "Would migrate"
+        } else {
+            "Migrated"
+        };
+        writeln!(
+            f,
+            "{} {} to format version {}:",
+            heading,
+            self.file_path.display(),
+            CURRENT_FORMAT_VERSION
+        )?;
+        for step in &self.steps {
+            writeln!(f, "- v{} → v{}: {}", step.from, step.to, step.description)?;
+            for change in &step.changes {
+                writeln!(f, "  - {}", change)?;
+            }
+        }
+        write!(f, "Items after migration: {}", self.item_count)?;
+        if let Some(ref backup) = self.backup_path {
+            write!(f, "\nBackup written to {}", backup.display())?;
+        }
+        if self.dry_run {
+            write!(f, "\nDry run: no files were modified.")?;
+        }
+        Ok(())
+    }
+}
+
+/// Build the backup path for a file about to be migrated
+///
+/// Uses a timestamp (plus a counter if needed) so repeated migrations never
+/// overwrite an earlier backup.
+pub(crate) fn backup_path_for(path: &Path) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut candidate = path.with_file_name(format!("{}.pre-migrate-{}.bak", file_name, stamp));
+    let mut counter = 1;
+    while candidate.exists() {
+        candidate = path.with_file_name(format!(
+            "{}.pre-migrate-{}-{}.bak",
+            file_name, stamp, counter
+        ));
+        counter += 1;
+    }
+    candidate
+}
+
+/// Migrate a GTD data file to the current format
+///
+/// Reads the file and runs the migration steps from its version up to the
+/// current one. Unless `dry_run`, each step is logged, recorded in the file's
+/// `[[migration_history]]`, and the original is copied to a timestamped `.bak`
+/// file before the file is rewritten in the current format.
+///
+/// # Arguments
+/// * `path` - GTD data file to migrate
+/// * `dry_run` - Report what would change without writing anything
+///
+/// # Returns
+/// A report describing the migration, or an error if the file cannot be read or parsed
+pub fn migrate_file(path: impl AsRef<Path>, dry_run: bool) -> Result<MigrationReport> {
+    let path = path.as_ref();
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let normalized = content.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut table: toml::Table = match external_notes::inline(&normalized, path)? {
+        Some(table) => table,
+        None => toml::from_str(&normalized)
+            .with_context(|| format!("Failed to parse {}", path.display()))?,
+    };
+    let (changes, applied) = upgrade_table(&mut table);
+    let mut data: GtdData = toml::Value::Table(table)
+        .try_into()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let mut report = MigrationReport {
+        file_path: path.to_path_buf(),
+        changes,
+        steps: applied,
+        item_count: data.notas.len(),
+        backup_path: None,
+        dry_run,
+    };
+
+    if dry_run || report.changes.is_empty() {
+        return Ok(report);
+    }
+
+    let backup = backup_path_for(path);
+    fs::copy(path, &backup)
+        .with_context(|| format!("Failed to write backup {}", backup.display()))?;
+    report.backup_path = Some(backup);
+
+    let today = local_date_today();
+    for step in &report.steps {
+        eprintln!(
+            "Migration: {} v{} → v{}: {}",
+            path.display(),
+            step.from,
+            step.to,
+            step.description
+        );
+        data.migration_history.push(step.record(today));
+    }
+
+    Storage::new(path, false).save_with_message(&data, "Migrate GTD data format")?;
+    Ok(report)
+}
@@ -0,0 +1,187 @@
+//! Read-only mirror exports written alongside the GTD data file
+//!
+//! When a mirror is configured, every save also writes a secondary copy of the
+//! data in a format that dashboards and static site generators can consume
+//! directly (JSON or a Markdown report), without talking to the MCP server.
+//! An iCalendar mirror keeps a feed of dated items for calendar apps, and a
+//! CSV mirror opens directly in a spreadsheet. A todo.txt mirror feeds todo.txt
+//! apps. JSON Lines puts one item per line for jq, DuckDB and log pipelines.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::{csv, ical, todotxt};
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Output format of a mirror file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorFormat {
+    /// The full data set as JSON (same structure as the TOML file)
+    Json,
+    /// A human-readable Markdown report grouped by status
+    Markdown,
+    /// An iCalendar feed of dated items (see [`ical::render`])
+    Ical,
+    /// One spreadsheet row per item (see [`csv::COLUMNS`])
+    Csv,
+    /// One todo.txt line per task (see [`todotxt`])
+    TodoTxt,
+    /// One JSON object per item and line (see [`write_json_lines`])
+    JsonLines,
+}
+
+impl MirrorFormat {
+    /// Infer the format from a file extension (`.json`, `.jsonl`, `.ndjson`, `.md`,
+    /// `.markdown`, `.ics`, `.csv`),
+    /// or a todo.txt file name (`todo.txt`, `*.todo.txt`)
+    ///
+    /// # Returns
+    /// The matching format, or None for unknown extensions
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        if todotxt::is_todo_txt_path(path.as_ref()) {
+            return Some(MirrorFormat::TodoTxt);
+        }
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(MirrorFormat::Json),
+            "jsonl" | "ndjson" => Some(MirrorFormat::JsonLines),
+            "md" | "markdown" => Some(MirrorFormat::Markdown),
+            "ics" => Some(MirrorFormat::Ical),
+            "csv" => Some(MirrorFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for MirrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(MirrorFormat::Json),
+            "markdown" | "md" => Ok(MirrorFormat::Markdown),
+            "ical" | "ics" => Ok(MirrorFormat::Ical),
+            "csv" => Ok(MirrorFormat::Csv),
+            "todotxt" | "todo.txt" => Ok(MirrorFormat::TodoTxt),
+            "jsonl" | "ndjson" => Ok(MirrorFormat::JsonLines),
+            _ => Err(format!(
+                "Invalid mirror format '{}'. Valid options are: json, jsonl, markdown, ical, csv, todotxt",
+                s
+            )),
+        }
+    }
+}
+
+/// Status sections of the Markdown report, in GTD workflow order
+const REPORT_SECTIONS: [(NotaStatus, &str); 13] = [
+    (NotaStatus::inbox, "Inbox"),
+    (NotaStatus::next_action, "Next Actions"),
+    (NotaStatus::waiting_for, "Waiting For"),
+    (NotaStatus::calendar, "Calendar"),
+    (NotaStatus::later, "Later"),
+    (NotaStatus::someday, "Someday"),
+    (NotaStatus::goal, "Goals"),
+    (NotaStatus::project, "Projects"),
+    (NotaStatus::context, "Contexts"),
+    (NotaStatus::template, "Templates"),
+    (NotaStatus::reference, "Reference"),
+    (NotaStatus::done, "Done"),
+    (NotaStatus::trash, "Trash"),
+];
+
+/// Render GTD data in the given mirror format
+///
+/// # Arguments
+/// * `data` - The GtdData to render
+/// * `format` - Output format
+/// * `today` - The current date (start of the iCalendar occurrences)
+///
+/// # Returns
+/// Result containing the rendered document or an error
+pub fn render(data: &GtdData, format: MirrorFormat, today: NaiveDate) -> Result<String> {
+    match format {
+        MirrorFormat::Json => Ok(serde_json::to_string_pretty(data)? + "\n"),
+        MirrorFormat::Markdown => Ok(render_markdown(data)),
+        MirrorFormat::Ical => Ok(ical::render(data, today)),
+        MirrorFormat::Csv => Ok(csv::render(data)),
+        MirrorFormat::TodoTxt => Ok(todotxt::render(data)),
+        MirrorFormat::JsonLines => {
+            let mut lines = Vec::new();
+            write_json_lines(data, &mut lines)?;
+            Ok(String::from_utf8(lines)?)
+        }
+    }
+}
+
+/// Write every item as one line of compact JSON, in file order
+///
+/// Each line is written as soon as it is rendered, so large files stream to
+/// `out` without building the whole document in memory.
+///
+/// # Arguments
+/// * `data` - The GtdData to write
+/// * `out` - Destination (e.g. locked stdout)
+pub fn write_json_lines(data: &GtdData, out: &mut impl Write) -> Result<()> {
+    for nota in data.iter() {
+        serde_json::to_writer(&mut *out, nota)?;
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Render a Markdown report with one section per non-empty status
+fn render_markdown(data: &GtdData) -> String {
+    let mut report = String::from("# GTD Report\n\n");
+    report.push_str(
+        "<!-- Generated by gtd-mcp. Do not edit: this file is overwritten on every save. -->\n",
+    );
+
+    for (status, heading) in REPORT_SECTIONS {
+        let notas: Vec<&Nota> = data.notas.iter().filter(|n| n.status == status).collect();
+        if notas.is_empty() {
+            continue;
+        }
+        report.push_str(&format!("\n## {} ({})\n\n", heading, notas.len()));
+        for nota in notas {
+            report.push_str(&format_report_line(nota));
+        }
+    }
+
+    report
+}
+
+/// Format one nota as a Markdown list item
+fn format_report_line(nota: &Nota) -> String {
+    let checkbox = if nota.status == NotaStatus::done {
+        "[x] "
+    } else if nota.is_task() {
+        "[ ] "
+    } else {
+        ""
+    };
+    let mut line = format!("- {}**{}** (`{}`)", checkbox, nota.title, nota.id);
+
+    let mut details = Vec::new();
+    if let Some(ref project) = nota.project {
+        details.push(format!("project: {}", project));
+    }
+    if let Some(ref context) = nota.context {
+        details.push(format!("context: {}", context));
+    }
+    if let Some(date) = nota.start_date {
+        details.push(format!("start: {}", date));
+    }
+    if !details.is_empty() {
+        line.push_str(&format!(" — {}", details.join(", ")));
+    }
+    line.push('\n');
+
+    if let Some(ref notes) = nota.notes {
+        for note_line in notes.lines() {
+            line.push_str(&format!("  > {}\n", note_line));
+        }
+    }
+    line
+}
@@ -0,0 +1,155 @@
+//! Progress notifications for long tool calls
+//!
+//! A Git sync, a CalDAV sync or an import into a big file can take seconds.
+//! The tools that may run that long report their steps as MCP
+//! `notifications/progress`, so the client shows the call moving instead of a
+//! frozen request and does not give up on it.
+//!
+//! While a step runs, [`Progress::step`] keeps reporting once a second with a
+//! value creeping towards the next step, since the protocol wants every
+//! notification to report more progress than the one before.
+//!
+//! When the client cancels a call, mcp-attr drops it at its next `await`. Work
+//! already handed to a blocking thread keeps running, so it checks the call's
+//! [`Cancellation`] between batches (GitHub pages, Git sync attempts, the
+//! first change sent to a CalDAV server) and stops before starting the next
+//! one. Nothing is applied to the data or saved after the check fails.
+
+use mcp_attr::server::RequestContext;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How often a running step reports that it is still going
+const TICK: Duration = Duration::from_secs(1);
+
+/// Error of work stopped because its tool call was cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The request was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Whether the client still waits for a tool call
+///
+/// Clones share the state, so blocking work can hold one while the call
+/// cancels another.
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    /// Mark the call as cancelled
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the call was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Stop here if the call was cancelled
+    ///
+    /// # Returns
+    /// [`Cancelled`] once the call was cancelled
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Where a tool call reports its progress
+///
+/// Dropping it cancels its [`Cancellation`]: the call either finished or was
+/// dropped by the client's cancellation, and in both cases nobody waits for
+/// work it left running.
+#[derive(Default)]
+pub struct Progress<'a> {
+    cx: Option<&'a RequestContext>,
+    cancellation: Cancellation,
+}
+
+impl<'a> Progress<'a> {
+    /// Report to the client of an MCP request
+    pub fn new(cx: &'a RequestContext) -> Self {
+        Self {
+            cx: Some(cx),
+            cancellation: Cancellation::default(),
+        }
+    }
+
+    /// Report nowhere (background jobs and direct calls)
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// The cancellation of this call, to check in blocking work
+    pub fn cancellation(&self) -> Cancellation {
+        self.cancellation.clone()
+    }
+
+    /// Send one notification
+    ///
+    /// # Arguments
+    /// * `progress` - Steps finished so far (may be fractional)
+    /// * `total` - Number of steps of the call
+    /// * `message` - What the call is doing now
+    pub fn report(&self, progress: f64, total: usize, message: &str) {
+        if let Some(cx) = self.cx {
+            cx.progress(progress, Some(total as f64), Some(message.to_string()));
+        }
+    }
+
+    /// Run one step, reporting its start and then once a second until it ends
+    ///
+    /// # Arguments
+    /// * `done` - Steps finished before this one
+    /// * `total` - Number of steps of the call
+    /// * `message` - What the step does
+    /// * `work` - The step itself
+    pub async fn step<T>(
+        &self,
+        done: usize,
+        total: usize,
+        message: &str,
+        work: impl Future<Output = T>,
+    ) -> T {
+        self.report(done as f64, total, message);
+        if self.cx.is_none() {
+            return work.await;
+        }
+        tokio::pin!(work);
+        let mut ticks = 0u32;
+        loop {
+            tokio::select! {
+                output = &mut work => return output,
+                _ = tokio::time::sleep(TICK) => {
+                    ticks += 1;
+                    // done + 1/2, done + 2/3, ... never reaching the next step
+                    let fraction = f64::from(ticks) / f64::from(ticks + 1);
+                    self.report(
+                        done as f64 + fraction,
+                        total,
+                        &format!("{} ({}s)", message, ticks),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Progress<'_> {
+    fn drop(&mut self) {
+        self.cancellation.cancel();
+    }
+}
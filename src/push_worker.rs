@@ -0,0 +1,194 @@
+//! Background Git push worker
+//!
+//! With background pushing enabled, saves only commit (and integrate remote
+//! commits) synchronously; pushing happens on a worker thread. The worker
+//! pushes after each commit, at most once per throttle interval, and retries
+//! with exponential backoff when the network or remote is unavailable.
+
+use crate::git_ops::{GitOps, PushOutcome};
+use chrono::{DateTime, Local};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Delay before the first retry after a failed push
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Upper bound for the retry delay
+const MAX_BACKOFF: Duration = Duration::from_secs(600);
+
+/// Snapshot of the background push state, reported by the `sync_status` tool
+#[derive(Debug, Clone, Default)]
+pub struct PushStatus {
+    /// Minimum time between pushes (zero = push right after each commit)
+    pub interval: Duration,
+    /// Whether there are commits the worker has not pushed yet
+    pub pending: bool,
+    /// When the last push was attempted
+    pub last_attempt: Option<DateTime<Local>>,
+    /// When the last push succeeded
+    pub last_success: Option<DateTime<Local>>,
+    /// Error of the most recent failed push, cleared on success
+    pub last_error: Option<String>,
+    /// Number of failed pushes since the last success
+    pub consecutive_failures: u32,
+    /// When the next retry is scheduled after a failure
+    pub next_retry: Option<DateTime<Local>>,
+    /// The remote has commits we do not; pushing resumes after the next save or sync
+    pub waiting_for_pull: bool,
+}
+
+/// Mutable worker state guarded by a mutex
+#[derive(Default)]
+struct State {
+    status: PushStatus,
+    /// When the next push may run (None = wait for a commit notification)
+    next_attempt: Option<Instant>,
+    /// Monotonic time of the last attempt, for throttling
+    last_attempt_at: Option<Instant>,
+    /// Set on shutdown to stop the worker thread
+    stopped: bool,
+}
+
+/// Pushes commits to the remote on a background thread
+pub(crate) struct PushWorker {
+    interval: Duration,
+    state: Mutex<State>,
+    wakeup: Condvar,
+}
+
+impl PushWorker {
+    /// Start a worker thread pushing through `git_ops`
+    ///
+    /// # Arguments
+    /// * `git_ops` - Git handle of the data repository
+    /// * `interval` - Minimum time between pushes (zero = after each commit)
+    pub(crate) fn spawn(git_ops: GitOps, interval: Duration) -> Arc<Self> {
+        let worker = Arc::new(Self {
+            interval,
+            state: Mutex::new(State {
+                status: PushStatus {
+                    interval,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            wakeup: Condvar::new(),
+        });
+        let runner = worker.clone();
+        std::thread::spawn(move || runner.run(git_ops));
+        worker
+    }
+
+    /// Record a new local commit and schedule a push
+    ///
+    /// The push runs immediately unless the throttle interval or a pending
+    /// backoff delay says otherwise.
+    pub(crate) fn notify_commit(&self) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let throttled_until = state
+            .last_attempt_at
+            .map(|last| last + self.interval)
+            .unwrap_or(now)
+            .max(now);
+        state.next_attempt = match state.next_attempt {
+            // Keep a backoff delay that is already scheduled later
+            Some(retry) if state.status.consecutive_failures > 0 && retry > throttled_until => {
+                Some(retry)
+            }
+            _ => Some(throttled_until),
+        };
+        state.status.pending = true;
+        state.status.waiting_for_pull = false;
+        self.wakeup.notify_all();
+    }
+
+    /// Record a push made outside the worker (e.g., an explicit sync)
+    pub(crate) fn record_push(&self) {
+        let mut state = self.state.lock().unwrap();
+        let now = Local::now();
+        state.next_attempt = None;
+        state.last_attempt_at = Some(Instant::now());
+        state.status.pending = false;
+        state.status.last_attempt = Some(now);
+        state.status.last_success = Some(now);
+        state.status.last_error = None;
+        state.status.consecutive_failures = 0;
+        state.status.next_retry = None;
+        state.status.waiting_for_pull = false;
+    }
+
+    /// Current push state
+    pub(crate) fn status(&self) -> PushStatus {
+        self.state.lock().unwrap().status.clone()
+    }
+
+    /// Stop the worker thread (pending pushes are left to the caller)
+    pub(crate) fn stop(&self) {
+        self.state.lock().unwrap().stopped = true;
+        self.wakeup.notify_all();
+    }
+
+    /// Worker loop: wait until a push is due, push, and record the outcome
+    fn run(self: Arc<Self>, git_ops: GitOps) {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                loop {
+                    if state.stopped {
+                        return;
+                    }
+                    match state.next_attempt {
+                        Some(due) if state.status.pending => {
+                            let now = Instant::now();
+                            if now >= due {
+                                break;
+                            }
+                            state = self.wakeup.wait_timeout(state, due - now).unwrap().0;
+                        }
+                        _ => state = self.wakeup.wait(state).unwrap(),
+                    }
+                }
+                state.next_attempt = None;
+                state.last_attempt_at = Some(Instant::now());
+                state.status.last_attempt = Some(Local::now());
+            }
+
+            let result = git_ops.try_push();
+
+            let mut state = self.state.lock().unwrap();
+            let status = &mut state.status;
+            match result {
+                Ok(PushOutcome::Accepted) => {
+                    status.pending = false;
+                    status.last_success = status.last_attempt;
+                    status.last_error = None;
+                    status.consecutive_failures = 0;
+                    status.next_retry = None;
+                }
+                Ok(PushOutcome::Rejected { reason, .. }) => {
+                    // Retrying cannot help until the remote commits are integrated,
+                    // which happens on the next save (or an explicit sync)
+                    status.waiting_for_pull = true;
+                    status.last_error = Some(format!("Push rejected: {}", reason));
+                    status.next_retry = None;
+                }
+                Err(e) => {
+                    status.consecutive_failures += 1;
+                    let delay = backoff(status.consecutive_failures);
+                    status.last_error = Some(format!("{:#}", e));
+                    status.next_retry = chrono::Duration::from_std(delay)
+                        .ok()
+                        .map(|d| Local::now() + d);
+                    state.next_attempt = Some(Instant::now() + delay);
+                }
+            }
+        }
+    }
+}
+
+/// Exponential backoff delay for the given number of consecutive failures
+fn backoff(failures: u32) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+    INITIAL_BACKOFF.saturating_mul(factor).min(MAX_BACKOFF)
+}
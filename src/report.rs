@@ -0,0 +1,411 @@
+//! Markdown progress reports for a date range
+//!
+//! A report is meant to be pasted into a team's weekly update. It has four
+//! sections:
+//! - **Wins**: projects finished in the range (every task done) or with at
+//!   least [`WIN_THRESHOLD`] items completed in it
+//! - **Completed**: items done in the range, grouped by project
+//! - **Active Projects**: open projects with their open and completed counts
+//! - **Upcoming**: calendar items in the [`UPCOMING_DAYS`] after the range
+//!
+//! An item counts as completed on its `updated_at` date while it is done.
+//!
+//! [`render_waiting`] lists the waiting-for items by how long they have been
+//! waiting, for following up on delegated work, and [`render_done_log`] lists
+//! the items completed in a range day by day, for retrospectives.
+//! [`render_inbox_age`] buckets the inbox by days since capture.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Completed items that make a project's progress a win
+pub const WIN_THRESHOLD: usize = 3;
+
+/// Days after the end of the range covered by the upcoming section
+pub const UPCOMING_DAYS: i64 = 7;
+
+/// Inclusive range of dates a report covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl ReportRange {
+    /// Parse a range relative to `today`
+    ///
+    /// # Arguments
+    /// * `range` - `week` (last 7 days), `last-week` (previous Monday to
+    ///   Sunday), `month` (last 30 days), `YYYY-MM-DD` (from then to today) or
+    ///   `YYYY-MM-DD..YYYY-MM-DD`
+    /// * `today` - The current date
+    pub fn parse(range: &str, today: NaiveDate) -> Result<Self, String> {
+        let days_back = |days: i64| Self {
+            start: today - Duration::days(days - 1),
+            end: today,
+        };
+        let date = |s: &str| {
+            NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date '{}' in range. Use YYYY-MM-DD.", s.trim()))
+        };
+        let range = range.trim();
+        let parsed = match range {
+            "week" => days_back(7),
+            "month" => days_back(30),
+            "last-week" => {
+                let monday =
+                    today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+                Self {
+                    start: monday - Duration::days(7),
+                    end: monday - Duration::days(1),
+                }
+            }
+            _ => match range.split_once("..") {
+                Some((start, end)) => Self {
+                    start: date(start)?,
+                    end: date(end)?,
+                },
+                None if range.starts_with(|c: char| c.is_ascii_digit()) => Self {
+                    start: date(range)?,
+                    end: today,
+                },
+                None => {
+                    return Err(format!(
+                        "Invalid range '{}'. Use week, last-week, month, YYYY-MM-DD or YYYY-MM-DD..YYYY-MM-DD.",
+                        range
+                    ));
+                }
+            },
+        };
+        if parsed.start > parsed.end {
+            return Err(format!(
+                "Invalid range '{}': {} is after {}.",
+                range, parsed.start, parsed.end
+            ));
+        }
+        Ok(parsed)
+    }
+
+    /// Whether `date` is within the range
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// Per-project counts used by the wins and active project sections
+#[derive(Default)]
+struct ProjectProgress {
+    completed: usize,
+    open: usize,
+}
+
+/// Render the report for `range`
+///
+/// # Arguments
+/// * `data` - The GtdData to report on
+/// * `range` - Dates the report covers
+pub fn render(data: &GtdData, range: ReportRange) -> String {
+    let completed: Vec<&Nota> = data
+        .iter()
+        .filter(|n| n.status == NotaStatus::done && range.contains(n.updated_at))
+        .collect();
+
+    let mut progress: BTreeMap<&str, ProjectProgress> = BTreeMap::new();
+    for nota in data.iter() {
+        let Some(project) = nota.project.as_deref() else {
+            continue;
+        };
+        let entry = progress.entry(project).or_default();
+        match nota.status {
+            NotaStatus::done if range.contains(nota.updated_at) => entry.completed += 1,
+            NotaStatus::done
+            | NotaStatus::trash
+            | NotaStatus::reference
+            | NotaStatus::goal
+            | NotaStatus::template => {}
+            _ => entry.open += 1,
+        }
+    }
+    let project_title = |id: &str| {
+        data.find_project_by_id(id)
+            .map(|p| p.title.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    let mut report = format!("# Report: {} to {}\n", range.start, range.end);
+    report.push_str(&format!(
+        "\n{} item{} completed",
+        completed.len(),
+        if completed.len() == 1 { "" } else { "s" }
+    ));
+    let wins: Vec<(&str, &ProjectProgress)> = progress
+        .iter()
+        .filter(|(_, p)| p.completed > 0 && (p.open == 0 || p.completed >= WIN_THRESHOLD))
+        .map(|(id, p)| (*id, p))
+        .collect();
+    if !wins.is_empty() {
+        report.push_str(&format!(
+            ", {} project{} with big progress",
+            wins.len(),
+            if wins.len() == 1 { "" } else { "s" }
+        ));
+    }
+    report.push_str(".\n");
+
+    if !wins.is_empty() {
+        report.push_str("\n## Wins\n\n");
+        for (id, p) in &wins {
+            let outcome = if p.open == 0 {
+                "finished".to_string()
+            } else {
+                format!("{} still open", p.open)
+            };
+            report.push_str(&format!(
+                "- **{}**: {} item{} completed, {}\n",
+                project_title(id),
+                p.completed,
+                if p.completed == 1 { "" } else { "s" },
+                outcome
+            ));
+        }
+    }
+
+    report.push_str("\n## Completed\n\n");
+    if completed.is_empty() {
+        report.push_str("_Nothing completed in this period._\n");
+    } else {
+        let mut by_project: BTreeMap<Option<&str>, Vec<&Nota>> = BTreeMap::new();
+        for nota in &completed {
+            by_project
+                .entry(nota.project.as_deref())
+                .or_default()
+                .push(nota);
+        }
+        // Items without a project come last
+        let mut groups: Vec<_> = by_project.into_iter().collect();
+        groups.sort_by_key(|(project, _)| project.is_none());
+        for (project, notas) in groups {
+            let heading = match project {
+                Some(id) => project_title(id),
+                None => "Other".to_string(),
+            };
+            report.push_str(&format!("### {}\n\n", heading));
+            for nota in notas {
+                report.push_str(&format!("- [x] {} ({})\n", nota.title, nota.updated_at));
+            }
+            report.push('\n');
+        }
+        report.pop();
+    }
+
+    let projects: Vec<&Nota> = data.iter().filter(|n| n.is_project()).collect();
+    report.push_str("\n## Active Projects\n\n");
+    if projects.is_empty() {
+        report.push_str("_No active projects._\n");
+    }
+    for project in projects {
+        let p = progress.get(project.id.as_str());
+        let open = p.map_or(0, |p| p.open);
+        let done = p.map_or(0, |p| p.completed);
+        let mut line = format!("- **{}**: {} open", project.title, open);
+        if done > 0 {
+            line.push_str(&format!(", {} completed this period", done));
+        }
+        if open == 0 {
+            line.push_str(" (no next action)");
+        }
+        report.push_str(&line);
+        report.push('\n');
+    }
+
+    let horizon = range.end + Duration::days(UPCOMING_DAYS);
+    let mut upcoming: Vec<&Nota> = data
+        .iter()
+        .filter(|n| {
+            n.status == NotaStatus::calendar
+                && n.start_date.is_some_and(|d| d > range.end && d <= horizon)
+        })
+        .collect();
+    upcoming.sort_by_key(|n| n.start_date);
+    report.push_str(&format!(
+        "\n## Upcoming ({} to {})\n\n",
+        range.end + Duration::days(1),
+        horizon
+    ));
+    if upcoming.is_empty() {
+        report.push_str("_Nothing scheduled._\n");
+    }
+    for nota in upcoming {
+        if let Some(date) = nota.start_date {
+            report.push_str(&format!(
+                "- {} {}\n",
+                date.format("%a %Y-%m-%d"),
+                nota.title
+            ));
+        }
+    }
+    report
+}
+
+/// Render the waiting-for items with the days since they last changed, oldest first
+///
+/// There is no status history, so an item is taken to be waiting since its
+/// `updated_at` date: editing a waiting item restarts its clock.
+///
+/// # Arguments
+/// * `data` - The GtdData to report on
+/// * `today` - The current date
+pub fn render_waiting(data: &GtdData, today: NaiveDate) -> String {
+    let mut waiting: Vec<&Nota> = data
+        .iter()
+        .filter(|n| n.status == NotaStatus::waiting_for)
+        .collect();
+    waiting.sort_by(|a, b| a.updated_at.cmp(&b.updated_at).then(a.id.cmp(&b.id)));
+
+    let mut report = format!(
+        "# Waiting For: {} item{}\n\n",
+        waiting.len(),
+        if waiting.len() == 1 { "" } else { "s" }
+    );
+    if waiting.is_empty() {
+        report.push_str("_Nothing delegated or pending._\n");
+        return report;
+    }
+    for nota in waiting {
+        let days = (today - nota.updated_at).num_days();
+        let mut line = format!(
+            "- **{} day{}** [{}] {} (since {}",
+            days,
+            if days == 1 { "" } else { "s" },
+            nota.id,
+            nota.title,
+            nota.updated_at
+        );
+        if let Some(project) = &nota.project {
+            let title = data
+                .find_project_by_id(project)
+                .map(|p| p.title.clone())
+                .unwrap_or_else(|| project.clone());
+            line.push_str(&format!(", project {}", title));
+        }
+        line.push_str(")\n");
+        report.push_str(&line);
+    }
+    report
+}
+
+/// Render the items completed in `range`, grouped per day with their projects
+///
+/// # Arguments
+/// * `data` - The GtdData to report on
+/// * `range` - Dates the log covers
+pub fn render_done_log(data: &GtdData, range: ReportRange) -> String {
+    let mut by_day: BTreeMap<NaiveDate, Vec<&Nota>> = BTreeMap::new();
+    for nota in data
+        .iter()
+        .filter(|n| n.status == NotaStatus::done && range.contains(n.updated_at))
+    {
+        by_day.entry(nota.updated_at).or_default().push(nota);
+    }
+    let total: usize = by_day.values().map(Vec::len).sum();
+
+    let mut log = format!("# Done: {} to {}\n\n", range.start, range.end);
+    if total == 0 {
+        log.push_str("_Nothing completed in this period._\n");
+        return log;
+    }
+    log.push_str(&format!(
+        "{} item{} completed on {} day{}.\n",
+        total,
+        if total == 1 { "" } else { "s" },
+        by_day.len(),
+        if by_day.len() == 1 { "" } else { "s" }
+    ));
+    for (day, mut notas) in by_day {
+        notas.sort_by(|a, b| a.project.cmp(&b.project).then(a.id.cmp(&b.id)));
+        log.push_str(&format!("\n## {}\n\n", day.format("%a %Y-%m-%d")));
+        for nota in notas {
+            let mut line = format!("- [x] {} [{}]", nota.title, nota.id);
+            if let Some(project) = &nota.project {
+                let title = data
+                    .find_project_by_id(project)
+                    .map(|p| p.title.clone())
+                    .unwrap_or_else(|| project.clone());
+                line.push_str(&format!(" - {}", title));
+            }
+            log.push_str(&line);
+            log.push('\n');
+        }
+    }
+    log
+}
+
+/// Inbox age buckets: label and the most days an item in it has waited
+pub const INBOX_AGE_BUCKETS: [(&str, i64); 3] =
+    [("0-2 days", 2), ("3-7 days", 7), ("Over 7 days", i64::MAX)];
+
+/// Oldest inbox items called out at the top of the inbox age report
+pub const INBOX_OLDEST_SHOWN: usize = 3;
+
+/// Render the inbox items bucketed by days since capture, oldest first
+///
+/// # Arguments
+/// * `data` - The GtdData to report on
+/// * `today` - The current date
+pub fn render_inbox_age(data: &GtdData, today: NaiveDate) -> String {
+    let mut inbox: Vec<&Nota> = data
+        .iter()
+        .filter(|n| n.status == NotaStatus::inbox)
+        .collect();
+    inbox.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    let age = |nota: &Nota| (today - nota.created_at).num_days().max(0);
+
+    let mut report = format!(
+        "# Inbox: {} item{}\n\n",
+        inbox.len(),
+        if inbox.len() == 1 { "" } else { "s" }
+    );
+    if inbox.is_empty() {
+        report.push_str("_Inbox zero._\n");
+        return report;
+    }
+    let stale: Vec<&Nota> = inbox
+        .iter()
+        .copied()
+        .filter(|n| age(n) > INBOX_AGE_BUCKETS[1].1)
+        .take(INBOX_OLDEST_SHOWN)
+        .collect();
+    if !stale.is_empty() {
+        report.push_str("Process first:\n");
+        for nota in stale {
+            report.push_str(&format!(
+                "- [{}] {} ({} days old)\n",
+                nota.id,
+                nota.title,
+                age(nota)
+            ));
+        }
+        report.push('\n');
+    }
+
+    let mut lower = 0;
+    for (label, max) in INBOX_AGE_BUCKETS {
+        let bucket: Vec<&Nota> = inbox
+            .iter()
+            .copied()
+            .filter(|n| (lower..=max).contains(&age(n)))
+            .collect();
+        lower = max.saturating_add(1);
+        report.push_str(&format!("## {}: {}\n", label, bucket.len()));
+        for nota in bucket {
+            report.push_str(&format!(
+                "- [{}] {} (captured {})\n",
+                nota.id, nota.title, nota.created_at
+            ));
+        }
+        report.push('\n');
+    }
+    report.pop();
+    report
+}
@@ -0,0 +1,410 @@
+//! REST/JSON API
+//!
+//! `gtd-mcp serve --rest [ADDR]` serves the MCP tools over plain HTTP instead
+//! of stdio, for scripts and web front ends that do not speak MCP:
+//!
+//! | Request | Response |
+//! |---------|----------|
+//! | `GET /api/items` | All items as JSON (`?status=next_action` filters) |
+//! | `GET /api/items/{id}` | One item, or 404 |
+//! | `GET /api/tools` | Tool names, descriptions and argument schemas |
+//! | `POST /api/tools/{name}` | Call a tool with a JSON object of arguments |
+//!
+//! Tool calls go through the same handler as MCP sessions (and the daemon, if
+//! enabled), so they share validation, saving and Git commits. All REST
+//! requests are one MCP session, so a transaction begun over REST can be
+//! committed by a later request. A tool answers
+//! `{"result": "<text>"}`; every error is `{"error": "<message>"}` with a 4xx
+//! or 5xx status. Each connection carries one request (`Connection: close`).
+//!
+//! There is no authentication: bind to a loopback address (the default) or
+//! put the server behind a proxy that adds it. So that web pages open in a
+//! browser cannot use the API, requests whose `Origin` or `Host` names another
+//! machine are refused with 403 (see [`foreign_request`]), and tool calls must
+//! be sent as `Content-Type: application/json`, which a page cannot send to
+//! another site without the server's consent. `--rest-tools` limits the tools
+//! that can be called; a read-only server only runs the tools that read.
+
+use crate::GtdServerHandler;
+use crate::daemon::SharedServer;
+use crate::gtd::NotaStatus;
+use anyhow::Result;
+use mcp_attr::client::McpClient;
+use mcp_attr::schema::{CallToolRequestParams, CallToolResultContentItem};
+use serde_json::{Value, json};
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Address `--rest` listens on when none is given
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// Largest request body accepted, in bytes
+const MAX_BODY: usize = 1 << 20;
+
+/// Largest request line or header accepted, in bytes
+const MAX_LINE: usize = 8 << 10;
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    method: String,
+    /// Decoded path segments after `/api/`
+    path: Vec<String>,
+    /// Decoded query parameters
+    query: Vec<(String, String)>,
+    /// Value of the `Content-Type` header
+    content_type: Option<String>,
+    body: String,
+}
+
+/// Status code and JSON body of a response
+type Response = (u16, Value);
+
+fn error(status: u16, message: impl std::fmt::Display) -> Response {
+    (status, json!({ "error": message.to_string() }))
+}
+
+/// Serve the API on `listener` until `shutdown` completes
+///
+/// Pending debounced writes are flushed before returning.
+///
+/// # Arguments
+/// * `handler` - The handler the tools run on, shared with the daemon if any
+/// * `listener` - Bound listener (see [`DEFAULT_ADDR`])
+/// * `tools` - The only tools that may be called and are listed (empty: all)
+/// * `shutdown` - Future that stops the server, e.g. `tokio::signal::ctrl_c()`
+pub async fn serve(
+    handler: Arc<GtdServerHandler>,
+    listener: TcpListener,
+    tools: Vec<String>,
+    shutdown: impl Future,
+) -> Result<()> {
+    let client = Arc::new(McpClient::with_server(SharedServer::new(handler.clone())).await?);
+    let tools: Arc<[String]> = tools.into();
+    tokio::pin!(shutdown);
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => accepted?.0,
+            _ = &mut shutdown => break,
+        };
+        let handler = handler.clone();
+        let client = client.clone();
+        let tools = tools.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &handler, &client, &tools).await {
+                eprintln!("Warning: REST request failed: {:#}", e);
+            }
+        });
+    }
+    handler.storage().flush()
+}
+
+/// Read one request from `stream`, answer it and close the connection
+async fn handle_connection(
+    stream: TcpStream,
+    handler: &GtdServerHandler,
+    client: &McpClient,
+    tools: &[String],
+) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let (status, body) = match read_request(&mut reader).await? {
+        Ok(request) => respond(handler, client, tools, request).await,
+        Err(response) => response,
+    };
+    let body = body.to_string();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason(status),
+        body.len()
+    );
+    let stream = reader.get_mut();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read the request line, headers and body
+///
+/// # Returns
+/// The request, or the error response for a malformed one; I/O errors are
+/// returned as `Err`
+async fn read_request(
+    reader: &mut BufReader<TcpStream>,
+) -> Result<std::result::Result<Request, Response>> {
+    let request_line = read_line(reader).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(error(400, "malformed request line")));
+    };
+    let mut length = 0;
+    let (mut host, mut origin, mut content_type) = (None, None, None);
+    loop {
+        let line = read_line(reader).await?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, value) = (name.trim(), value.trim().to_string());
+        if name.eq_ignore_ascii_case("content-length") {
+            let Ok(value) = value.parse() else {
+                return Ok(Err(error(400, "invalid Content-Length")));
+            };
+            length = value;
+        } else if name.eq_ignore_ascii_case("host") {
+            host = Some(value);
+        } else if name.eq_ignore_ascii_case("origin") {
+            origin = Some(value);
+        } else if name.eq_ignore_ascii_case("content-type") {
+            content_type = Some(value);
+        }
+    }
+    if length > MAX_BODY {
+        return Ok(Err(error(413, "request body is too large")));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    if let Some(reason) = foreign_request(host.as_deref(), origin.as_deref()) {
+        return Ok(Err(error(403, reason)));
+    }
+    let Ok(body) = String::from_utf8(body) else {
+        return Ok(Err(error(400, "request body is not UTF-8")));
+    };
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let Some(path) = path
+        .strip_prefix("/api/")
+        .or((path == "/api").then_some(""))
+    else {
+        return Ok(Err(error(404, "not found; the API is under /api/")));
+    };
+    Ok(Ok(Request {
+        method: method.to_string(),
+        path: path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect(),
+        query: query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, value)| (percent_decode(name), percent_decode(value)))
+            .collect(),
+        content_type,
+        body,
+    }))
+}
+
+/// Why a request has to be refused as coming from outside this machine
+///
+/// Browsers let any web page send requests to a loopback address. A page of
+/// another site gives itself away by its `Origin`, and one that reached the
+/// server by DNS rebinding by its `Host`. Requests without these headers
+/// (scripts, curl) are let through.
+///
+/// # Arguments
+/// * `host` - Value of the `Host` header
+/// * `origin` - Value of the `Origin` header
+///
+/// # Returns
+/// The reason for refusing the request, or None to serve it
+pub(crate) fn foreign_request(host: Option<&str>, origin: Option<&str>) -> Option<String> {
+    if let Some(origin) = origin {
+        let authority = origin
+            .split_once("://")
+            .map(|(_, rest)| rest.split('/').next().unwrap_or(rest));
+        if !authority.is_some_and(is_loopback_host) {
+            return Some(format!("requests from origin '{}' are not allowed", origin));
+        }
+    }
+    match host {
+        Some(host) if !is_loopback_host(host) => {
+            Some(format!("requests for host '{}' are not allowed", host))
+        }
+        _ => None,
+    }
+}
+
+/// Whether `host` (`name[:port]`) is `localhost` or a loopback address
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split_once(']').map_or(rest, |(name, _)| name),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Read one CRLF- or LF-terminated line, without the terminator
+pub(crate) async fn read_line(reader: &mut BufReader<TcpStream>) -> Result<String> {
+    let mut line = String::new();
+    let read = (&mut *reader)
+        .take(MAX_LINE as u64)
+        .read_line(&mut line)
+        .await?;
+    if read == 0 {
+        anyhow::bail!("connection closed before the request was complete");
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Decode `%XX` escapes and `+` (as space) in a path segment or query value
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => match text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+                None => decoded.push(b'%'),
+            },
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Answer a request
+async fn respond(
+    handler: &GtdServerHandler,
+    client: &McpClient,
+    tools: &[String],
+    request: Request,
+) -> Response {
+    let path: Vec<&str> = request.path.iter().map(String::as_str).collect();
+    match (request.method.as_str(), path.as_slice()) {
+        ("GET", ["items"]) => {
+            let status = match request.query.iter().find(|(name, _)| name == "status") {
+                Some((_, value)) => match NotaStatus::from_str(value) {
+                    Ok(status) => Some(status),
+                    Err(e) => return error(400, e),
+                },
+                None => None,
+            };
+            let data = handler.data.read().await;
+            let items: Vec<_> = data
+                .iter()
+                .filter(|nota| status.as_ref().is_none_or(|s| &nota.status == s))
+                .collect();
+            (200, json!(items))
+        }
+        ("GET", ["items", id]) => {
+            let data = handler.data.read().await;
+            match data.resolve_id(id).and_then(|id| data.find_by_id(id)) {
+                Some(nota) => (200, json!(nota)),
+                None => error(404, format!("item '{}' not found", id)),
+            }
+        }
+        ("GET", ["tools"]) => match client.tools_list(None).await {
+            Ok(mut list) => {
+                list.tools.retain(|tool| allowed(tools, &tool.name));
+                (200, json!(list.tools))
+            }
+            Err(e) => error(500, e),
+        },
+        ("POST", ["tools", name]) => {
+            let media_type = request
+                .content_type
+                .as_deref()
+                .and_then(|value| value.split(';').next())
+                .map(str::trim);
+            if !media_type
+                .is_some_and(|media_type| media_type.eq_ignore_ascii_case("application/json"))
+            {
+                return error(
+                    415,
+                    "tool calls must be sent as Content-Type: application/json",
+                );
+            }
+            call_tool(client, tools, name, &request.body).await
+        }
+        (_, ["items"] | ["items", _] | ["tools"] | ["tools", _]) => {
+            error(405, format!("{} is not allowed here", request.method))
+        }
+        _ => error(404, "not found"),
+    }
+}
+
+/// Whether `--rest-tools` lets `name` be listed and called
+fn allowed(tools: &[String], name: &str) -> bool {
+    tools.is_empty() || tools.iter().any(|tool| tool == name)
+}
+
+/// Call a tool with the JSON object in `body` as its arguments
+async fn call_tool(client: &McpClient, tools: &[String], name: &str, body: &str) -> Response {
+    let arguments = if body.trim().is_empty() {
+        serde_json::Map::new()
+    } else {
+        match serde_json::from_str(body) {
+            Ok(Value::Object(arguments)) => arguments,
+            Ok(_) => return error(400, "the request body must be a JSON object of arguments"),
+            Err(e) => return error(400, format!("invalid JSON: {}", e)),
+        }
+    };
+    match client.tools_list(None).await {
+        Ok(tools) if tools.tools.iter().any(|tool| tool.name == name) => {}
+        Ok(_) => return error(404, format!("unknown tool '{}'", name)),
+        Err(e) => return error(500, e),
+    }
+    if !allowed(tools, name) {
+        return error(
+            403,
+            format!("the REST API is not allowed to call '{}'", name),
+        );
+    }
+    let mut params = CallToolRequestParams::new(name);
+    params.arguments = Some(arguments);
+    match client.tools_call(params).await {
+        Ok(result) => {
+            let text = result
+                .content
+                .iter()
+                .filter_map(|item| match item {
+                    CallToolResultContentItem::TextContent(content) => Some(content.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            if result.is_error == Some(true) {
+                error(400, text)
+            } else {
+                (200, json!({ "result": text }))
+            }
+        }
+        Err(e) => match e.error_object() {
+            Some(object) => error(400, &object.message),
+            None => error(500, e),
+        },
+    }
+}
+
+/// Reason phrase of the status codes used here and in [`crate::mcp_http`]
+pub(crate) fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        _ => "Internal Server Error",
+    }
+}
@@ -0,0 +1,74 @@
+//! Retention policy for trashed items
+//!
+//! With `--trash-retention-days N`, every save first purges trash items that
+//! have been in the trash for more than N days, so the file stays compact
+//! without running `empty_trash`. An item counts as trashed since its
+//! `status_since` date, or its `updated_at` for items trashed before that was
+//! recorded. Trash items still referenced by other items are kept, the same as
+//! an explicit purge would leave them for the user to untangle.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::NaiveDate;
+
+/// IDs of the trash items due for purging, in file order
+///
+/// # Arguments
+/// * `data` - GTD data to search
+/// * `days` - Days an item stays in the trash before it is purged
+/// * `today` - Date the age is measured against
+pub fn expired(data: &GtdData, days: u32, today: NaiveDate) -> Vec<String> {
+    let mut expired: Vec<String> = data
+        .iter()
+        .filter(|nota| nota.status == NotaStatus::trash)
+        .filter(|nota| nota.days_in_status(today) > i64::from(days))
+        .map(|nota| nota.id.clone())
+        .collect();
+    // Keep items something that stays still links to; dropping one can pin
+    // another, so repeat until nothing changes
+    loop {
+        let before = expired.len();
+        let kept: Vec<String> = expired
+            .iter()
+            .filter(|id| {
+                data.referrers(id)
+                    .iter()
+                    .any(|referrer| !expired.contains(referrer))
+            })
+            .cloned()
+            .collect();
+        expired.retain(|id| !kept.contains(id));
+        if expired.len() == before {
+            return expired;
+        }
+    }
+}
+
+/// Remove the trash items due for purging
+///
+/// # Returns
+/// The purged items
+pub fn purge(data: &mut GtdData, days: u32, today: NaiveDate) -> Vec<Nota> {
+    let expired = expired(data, days, today);
+    if expired.is_empty() {
+        return Vec::new();
+    }
+    data.retain(|nota| !expired.contains(&nota.id))
+}
+
+/// Paragraph appended to the commit message of the save that purged `purged`
+///
+/// # Returns
+/// e.g. "\n\nPurged 2 trash item(s) older than 30 days: old-idea, typo", or
+/// `None` when nothing was purged
+pub fn commit_note(purged: &[Nota], days: u32) -> Option<String> {
+    if purged.is_empty() {
+        return None;
+    }
+    let ids: Vec<&str> = purged.iter().map(|nota| nota.id.as_str()).collect();
+    Some(format!(
+        "\n\nPurged {} trash item(s) older than {} days: {}",
+        purged.len(),
+        days,
+        ids.join(", ")
+    ))
+}
@@ -0,0 +1,24 @@
+//! Data files inside the client's workspace roots
+//!
+//! Started without a path and with no `gtd.toml` in the current directory or
+//! its parents, the server asks the client for its workspace roots (the MCP
+//! roots capability) on the first tool call. It uses `gtd.toml` in the first
+//! `file://` root, creating it with the starter contexts when it does not
+//! exist yet, so each project opened in the client gets its own GTD file
+//! without any path in the server's configuration.
+//!
+//! The root is adopted once; every client of the server then shares that file.
+
+use crate::discovery::DATA_FILE_NAME;
+use mcp_attr::schema::Root;
+use std::path::PathBuf;
+
+/// The data file of the first local (`file://`) root
+///
+/// Roots with any other scheme, or on another host, are skipped.
+pub fn data_file(roots: &[Root]) -> Option<PathBuf> {
+    roots
+        .iter()
+        .find_map(Root::to_file_path)
+        .map(|dir| dir.join(DATA_FILE_NAME))
+}
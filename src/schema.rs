@@ -0,0 +1,388 @@
+//! Schema validation for GTD data files
+//!
+//! When `gtd.toml` fails to deserialize, serde only reports the first problem and
+//! rarely says which item it belongs to. This module re-parses the raw document with
+//! span information and checks every item against the expected field types, so the
+//! load error can point at the exact array, item ID, field, and line.
+
+use crate::gtd::NotaStatus;
+use chrono::NaiveDate;
+use std::fmt;
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+/// Task status arrays of the current format (each item is a task)
+const TASK_SECTIONS: [&str; 11] = [
+    "inbox",
+    "next_action",
+    "waiting_for",
+    "later",
+    "calendar",
+    "someday",
+    "done",
+    "reference",
+    "trash",
+    "goal",
+    "template",
+];
+
+/// Fields that hold a `YYYY-MM-DD` date
+const DATE_FIELDS: [&str; 6] = [
+    "start_date",
+    "due_date",
+    "focus_date",
+    "created_at",
+    "updated_at",
+    "status_since",
+];
+
+/// Fields that hold free-form text
+const STRING_FIELDS: [&str; 12] = [
+    "id",
+    "title",
+    "name",
+    "project",
+    "context",
+    "notes",
+    "notes_file",
+    "description",
+    "recurrence_config",
+    "link",
+    "goal",
+    "availability",
+];
+
+/// A single problem found in a GTD data file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaIssue {
+    /// Top-level array or table the item belongs to (e.g., "inbox", "project")
+    pub section: String,
+    /// Position of the item within its array (None for table-keyed sections)
+    pub index: Option<usize>,
+    /// ID (or name) of the item, when it could be read
+    pub item_id: Option<String>,
+    /// Offending field, if the problem is field-specific
+    pub field: Option<String>,
+    /// 1-based line number of the offending value
+    pub line: Option<usize>,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}", self.section)?;
+        if let Some(index) = self.index {
+            write!(f, "[{}]", index)?;
+        }
+        if let Some(ref id) = self.item_id {
+            write!(f, " (id '{}')", id)?;
+        }
+        if let Some(ref field) = self.field {
+            write!(f, " field '{}'", field)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Validate a GTD TOML document and report every schema problem found
+///
+/// Syntax errors are not reported here (the TOML parser already reports them with
+/// line numbers); an unparsable document yields an empty list.
+///
+/// # Arguments
+/// * `content` - Raw TOML content (line endings already normalized)
+///
+/// # Returns
+/// All problems found, in document order per section
+pub fn validate_document(content: &str) -> Vec<SchemaIssue> {
+    let Ok(root) = DeTable::parse(content) else {
+        return Vec::new();
+    };
+    let mut checker = Checker {
+        content,
+        issues: Vec::new(),
+    };
+
+    for (key, value) in root.get_ref().iter() {
+        let section = key.get_ref().as_ref();
+        match section {
+            "format_version" | "external_notes_min_chars" | "task_counter" | "project_counter"
+                if !matches!(value.get_ref(), DeValue::Integer(_)) =>
+            {
+                checker.push(section, None, None, None, value, "expected an integer");
+            }
+            s if TASK_SECTIONS.contains(&s) => {
+                checker.check_array(section, value, &["id", "title", "created_at", "updated_at"])
+            }
+            "project" => checker.check_array(section, value, &["title"]),
+            "context" => checker.check_array(section, value, &[]),
+            "notas" => checker.check_array(section, value, &[]),
+            "projects" => match value.get_ref() {
+                DeValue::Array(_) => checker.check_array(section, value, &["title"]),
+                DeValue::Table(table) => checker.check_keyed_table(section, table),
+                _ => checker.push(
+                    section,
+                    None,
+                    None,
+                    None,
+                    value,
+                    "expected an array or table",
+                ),
+            },
+            "contexts" => match value.get_ref() {
+                DeValue::Table(table) => checker.check_keyed_table(section, table),
+                _ => checker.push(section, None, None, None, value, "expected a table"),
+            },
+            _ => {}
+        }
+    }
+
+    checker.issues
+}
+
+/// Accumulates issues while walking the document
+struct Checker<'a> {
+    content: &'a str,
+    issues: Vec<SchemaIssue>,
+}
+
+impl Checker<'_> {
+    /// Convert a byte offset into a 1-based line number
+    fn line_of(&self, offset: usize) -> usize {
+        let end = offset.min(self.content.len());
+        self.content[..end].matches('\n').count() + 1
+    }
+
+    fn push(
+        &mut self,
+        section: &str,
+        index: Option<usize>,
+        item_id: Option<&str>,
+        field: Option<&str>,
+        at: &Spanned<DeValue<'_>>,
+        message: impl Into<String>,
+    ) {
+        let line = Some(self.line_of(at.span().start));
+        self.issues.push(SchemaIssue {
+            section: section.to_string(),
+            index,
+            item_id: item_id.map(str::to_string),
+            field: field.map(str::to_string),
+            line,
+            message: message.into(),
+        });
+    }
+
+    /// Check an array of item tables such as `[[inbox]]`
+    fn check_array(&mut self, section: &str, value: &Spanned<DeValue<'_>>, required: &[&str]) {
+        let DeValue::Array(items) = value.get_ref() else {
+            self.push(
+                section,
+                None,
+                None,
+                None,
+                value,
+                "expected an array of tables",
+            );
+            return;
+        };
+        for (index, item) in items.iter().enumerate() {
+            match item.get_ref() {
+                DeValue::Table(table) => {
+                    self.check_item(section, Some(index), None, item, table, required)
+                }
+                _ => self.push(section, Some(index), None, None, item, "expected a table"),
+            }
+        }
+    }
+
+    /// Check a table whose keys are item IDs, such as `[projects.website]`
+    fn check_keyed_table(&mut self, section: &str, table: &DeTable<'_>) {
+        for (key, item) in table.iter() {
+            let id = key.get_ref().as_ref();
+            match item.get_ref() {
+                DeValue::Table(fields) => {
+                    self.check_item(section, None, Some(id), item, fields, &[])
+                }
+                _ => self.push(section, None, Some(id), None, item, "expected a table"),
+            }
+        }
+    }
+
+    /// Check the fields of a single item
+    fn check_item(
+        &mut self,
+        section: &str,
+        index: Option<usize>,
+        key_id: Option<&str>,
+        item: &Spanned<DeValue<'_>>,
+        table: &DeTable<'_>,
+        required: &[&str],
+    ) {
+        let id = key_id.map(str::to_string).or_else(|| {
+            ["id", "name"].iter().find_map(|k| {
+                table
+                    .get(*k)
+                    .and_then(|v| v.get_ref().as_str().map(str::to_string))
+            })
+        });
+        let id = id.as_deref();
+
+        for field in required {
+            let present =
+                table.get(*field).is_some() || (*field == "title" && table.get("name").is_some());
+            if !present {
+                self.push(
+                    section,
+                    index,
+                    id,
+                    Some(field),
+                    item,
+                    "missing required field",
+                );
+            }
+        }
+
+        for (key, value) in table.iter() {
+            let field = key.get_ref().as_ref();
+            if DATE_FIELDS.contains(&field) {
+                self.check_date(section, index, id, field, value);
+            } else if STRING_FIELDS.contains(&field) {
+                if value.get_ref().as_str().is_none() {
+                    let message =
+                        format!("expected a string, found {}", value.get_ref().type_str());
+                    self.push(section, index, id, Some(field), value, message);
+                }
+            } else if field == "status" {
+                // Legacy projects store free-form status strings that are ignored on load
+                if section != "project" && section != "projects" {
+                    self.check_status(section, index, id, field, value);
+                }
+            } else if field == "previous_status" {
+                self.check_status(section, index, id, field, value);
+            } else if field == "recurrence_pattern" {
+                self.check_recurrence(section, index, id, value);
+            } else if field == "priority" || field == "energy" {
+                self.check_level(section, index, id, field, value);
+            } else if field == "paused" && !matches!(value.get_ref(), DeValue::Boolean(_)) {
+                let message = format!(
+                    "expected true or false, found {}",
+                    value.get_ref().type_str()
+                );
+                self.push(section, index, id, Some(field), value, message);
+            } else if field == "estimate" && !matches!(value.get_ref(), DeValue::Integer(_)) {
+                let message = format!(
+                    "expected a number of minutes, found {}",
+                    value.get_ref().type_str()
+                );
+                self.push(section, index, id, Some(field), value, message);
+            } else if field == "escalate_after_days"
+                && !matches!(value.get_ref(), DeValue::Integer(_))
+            {
+                let message = format!(
+                    "expected a number of days, found {}",
+                    value.get_ref().type_str()
+                );
+                self.push(section, index, id, Some(field), value, message);
+            }
+        }
+    }
+
+    fn check_date(
+        &mut self,
+        section: &str,
+        index: Option<usize>,
+        id: Option<&str>,
+        field: &str,
+        value: &Spanned<DeValue<'_>>,
+    ) {
+        match value.get_ref() {
+            DeValue::String(s) => {
+                if NaiveDate::parse_from_str(s, "%Y-%m-%d").is_err() {
+                    let message = format!("invalid date '{}' (expected YYYY-MM-DD)", s);
+                    self.push(section, index, id, Some(field), value, message);
+                }
+            }
+            DeValue::Datetime(dt) => {
+                let message = format!(
+                    "date must be a quoted string, e.g. \"{}\" instead of {}",
+                    dt, dt
+                );
+                self.push(section, index, id, Some(field), value, message);
+            }
+            other => {
+                let message = format!("expected a date string, found {}", other.type_str());
+                self.push(section, index, id, Some(field), value, message);
+            }
+        }
+    }
+
+    fn check_status(
+        &mut self,
+        section: &str,
+        index: Option<usize>,
+        id: Option<&str>,
+        field: &str,
+        value: &Spanned<DeValue<'_>>,
+    ) {
+        match value.get_ref().as_str() {
+            Some(s) if s.parse::<NotaStatus>().is_ok() => {}
+            Some(s) => {
+                let message = format!(
+                    "unknown status '{}'. Valid statuses: inbox, next_action, waiting_for, later, calendar, someday, done, reference, trash, project, context, goal, template",
+                    s
+                );
+                self.push(section, index, id, Some(field), value, message);
+            }
+            None => {
+                let message = format!("expected a string, found {}", value.get_ref().type_str());
+                self.push(section, index, id, Some(field), value, message);
+            }
+        }
+    }
+
+    fn check_level(
+        &mut self,
+        section: &str,
+        index: Option<usize>,
+        id: Option<&str>,
+        field: &str,
+        value: &Spanned<DeValue<'_>>,
+    ) {
+        let valid = value
+            .get_ref()
+            .as_str()
+            .is_some_and(|s| matches!(s, "high" | "medium" | "low"));
+        if !valid {
+            let message = format!("unknown {}. Valid options are: high, medium, low", field);
+            self.push(section, index, id, Some(field), value, message);
+        }
+    }
+
+    fn check_recurrence(
+        &mut self,
+        section: &str,
+        index: Option<usize>,
+        id: Option<&str>,
+        value: &Spanned<DeValue<'_>>,
+    ) {
+        let valid = value
+            .get_ref()
+            .as_str()
+            .is_some_and(|s| matches!(s, "daily" | "weekly" | "monthly" | "yearly"));
+        if !valid {
+            self.push(
+                section,
+                index,
+                id,
+                Some("recurrence_pattern"),
+                value,
+                "unknown recurrence pattern. Valid patterns: daily, weekly, monthly, yearly",
+            );
+        }
+    }
+}
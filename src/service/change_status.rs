@@ -0,0 +1,335 @@
+//! Moving items between statuses
+
+use super::{GtdService, ServiceError, ServiceResult};
+use crate::commit_message::CommitItem;
+use crate::duplicates;
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::validation;
+use crate::wip::{self, WipMode};
+use chrono::NaiveDate;
+
+/// What happens to the items linked to a project/context that is trashed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cascade {
+    /// Trash the linked items too (and, recursively, items linked to them)
+    Trash,
+    /// Keep the linked items and clear their project/context link
+    Unlink,
+}
+
+/// One item moved by [`GtdService::change_status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChange {
+    /// ID of the item
+    pub id: String,
+    /// Status the item had before
+    pub old_status: NotaStatus,
+    /// Next occurrence created when a recurring item was done
+    pub next_occurrence: Option<Nota>,
+    /// Linked items trashed along with it
+    pub trashed: Vec<String>,
+    /// Linked items whose link to it was cleared
+    pub unlinked: Vec<String>,
+}
+
+/// An item [`GtdService::change_status`] could not move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusFailure {
+    /// The ID as given (normalized)
+    pub id: String,
+    /// Why it was not moved
+    pub reason: String,
+}
+
+/// Outcome of [`GtdService::change_status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChanges {
+    /// The items moved, in the order given
+    pub changed: Vec<StatusChange>,
+    /// The items left as they were
+    pub failed: Vec<StatusFailure>,
+    /// Set when the moves took the new status over its WIP limit
+    pub wip_warning: Option<String>,
+}
+
+/// Apply a cascade to everything linked to `id` before it is trashed
+///
+/// Every changed item is added to `commit_items`; records the trashed and
+/// unlinked IDs in `change`.
+fn apply_cascade(
+    data: &mut GtdData,
+    id: &str,
+    cascade: Cascade,
+    commit_items: &mut Vec<CommitItem>,
+    change: &mut StatusChange,
+    today: NaiveDate,
+) {
+    match cascade {
+        Cascade::Unlink => {
+            for referrer in data.referrers(id) {
+                let Some(mut nota) = data.find_by_id(&referrer) else {
+                    continue;
+                };
+                if nota.project.as_deref() == Some(id) {
+                    nota.project = None;
+                }
+                if nota.context.as_deref() == Some(id) {
+                    nota.context = None;
+                }
+                if nota.goal.as_deref() == Some(id) {
+                    nota.goal = None;
+                }
+                nota.updated_at = today;
+                commit_items.push(CommitItem::from_nota(&nota, Some(nota.status.clone())));
+                data.update(&referrer, nota);
+                change.unlinked.push(referrer);
+            }
+        }
+        Cascade::Trash => {
+            let mut pending = vec![id.to_string()];
+            let mut visited = std::collections::HashSet::from([id.to_string()]);
+            while let Some(parent) = pending.pop() {
+                for referrer in data.referrers(&parent) {
+                    if !visited.insert(referrer.clone()) {
+                        continue;
+                    }
+                    pending.push(referrer.clone());
+                    let Some(mut nota) = data.find_by_id(&referrer) else {
+                        continue;
+                    };
+                    if nota.status == NotaStatus::trash {
+                        continue;
+                    }
+                    let old_status = nota.status.clone();
+                    nota.set_status(NotaStatus::trash, today);
+                    nota.updated_at = today;
+                    commit_items.push(CommitItem::from_nota(&nota, Some(old_status)));
+                    data.update(&referrer, nota);
+                    change.trashed.push(referrer);
+                }
+            }
+        }
+    }
+}
+
+impl GtdService<'_> {
+    /// Move items to another status
+    ///
+    /// Each item is moved on its own: the ones that break a rule are listed
+    /// in [`StatusChanges::failed`] and the others are still moved.
+    ///
+    /// # Arguments
+    /// * `ids` - Items to move
+    /// * `status` - The new status
+    /// * `start_date` - New start date (required for calendar items that have none)
+    /// * `cascade` - What happens to the items linked to a trashed project/context
+    ///
+    /// # Returns
+    /// The moved and the refused items, or an error when the call itself is
+    /// invalid or the changes could not be saved
+    pub async fn change_status(
+        &self,
+        ids: &[String],
+        status: NotaStatus,
+        start_date: Option<NaiveDate>,
+        cascade: Option<Cascade>,
+    ) -> ServiceResult<StatusChanges> {
+        let rules = &self.rules;
+        let today = rules.clock.today();
+        // Validate we have at least one ID
+        if ids.is_empty() {
+            return Err(ServiceError::Invalid(
+                "No IDs provided. Please specify at least one item ID.".to_string(),
+            ));
+        }
+
+        let is_trash = status == NotaStatus::trash;
+        if cascade.is_some() && !is_trash {
+            return Err(ServiceError::Invalid(format!(
+                "cascade only applies when new_status is trash (got '{:?}')",
+                status
+            )));
+        }
+
+        let (changes, commit_items) = {
+            let mut data = self.data.write().await;
+
+            let mut changed = Vec::new();
+            let mut failed = Vec::new();
+            let mut commit_items = Vec::new();
+            let mut fail = |id: &str, reason: String| {
+                failed.push(StatusFailure {
+                    id: id.to_string(),
+                    reason,
+                })
+            };
+
+            // Normalize all IDs upfront for efficiency
+            let normalized_ids: Vec<String> = ids
+                .iter()
+                .map(|id| validation::normalize_task_id(id))
+                .collect();
+
+            // Process each ID
+            for normalized_id in normalized_ids {
+                // Find existing nota
+                let Some(mut nota) = data.find_by_id(&normalized_id) else {
+                    let suggestions = duplicates::similar_ids(&data, &normalized_id);
+                    if suggestions.is_empty() {
+                        fail(&normalized_id, "not found".to_string());
+                    } else {
+                        fail(
+                            &normalized_id,
+                            format!("not found.{}", duplicates::did_you_mean(&suggestions)),
+                        );
+                    }
+                    continue;
+                };
+
+                // Store old status for reporting
+                let old_status = nota.status.clone();
+                let mut change = StatusChange {
+                    id: normalized_id.clone(),
+                    old_status: old_status.clone(),
+                    next_occurrence: None,
+                    trashed: Vec::new(),
+                    unlinked: Vec::new(),
+                };
+
+                // Validate calendar status has start_date
+                if status == NotaStatus::calendar
+                    && start_date.is_none()
+                    && nota.start_date.is_none()
+                {
+                    fail(
+                        &normalized_id,
+                        "calendar status requires a start_date".to_string(),
+                    );
+                    continue;
+                }
+
+                // A referenced nota moves to trash only with a cascade for its referrers
+                let cascade = if is_trash && data.is_referenced(&normalized_id) {
+                    let Some(cascade) = cascade else {
+                        fail(
+                            &normalized_id,
+                            "still referenced by other items (use cascade=\"trash\" to trash them too, or cascade=\"unlink\" to clear their link)".to_string(),
+                        );
+                        continue;
+                    };
+                    Some(cascade)
+                } else {
+                    None
+                };
+
+                // A linked project/context must keep its type (trash is handled above)
+                if !is_trash
+                    && let Err(message) =
+                        validation::check_type_change(&data, &nota.id, &old_status, &status)
+                {
+                    fail(&normalized_id, message);
+                    continue;
+                }
+
+                // A full status refuses more items when the WIP limit is enforced
+                if rules.wip_mode == WipMode::Enforce
+                    && old_status != status
+                    && let Some(max) = wip::limit_for(&rules.wip_limits, &status)
+                    && wip::count(&data, &status) >= max
+                {
+                    fail(&normalized_id, wip::full_error(&status, max));
+                    continue;
+                }
+
+                // The next occurrence when a recurring item is done (unless the series
+                // is paused), with its ID generated before anything is changed
+                let next_occurrence = if status == NotaStatus::done
+                    && nota.is_recurring()
+                    && !nota.paused
+                    && let Some(next_date) = nota
+                        .calculate_next_occurrence(start_date.or(nota.start_date).unwrap_or(today))
+                {
+                    match rules.id_generator.occurrence_id(&data, &nota, next_date) {
+                        Ok(id) => Some((id, next_date)),
+                        Err(e) => {
+                            fail(&normalized_id, e);
+                            continue;
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                // Every check passed: trash or unlink the referrers
+                if let Some(cascade) = cascade {
+                    apply_cascade(
+                        &mut data,
+                        &nota.id,
+                        cascade,
+                        &mut commit_items,
+                        &mut change,
+                        today,
+                    );
+                }
+
+                // Update status
+                nota.set_status(status.clone(), today);
+
+                // Update start_date if provided
+                if let Some(date) = start_date {
+                    nota.start_date = Some(date);
+                }
+
+                nota.updated_at = today;
+
+                // Create a new task for the next occurrence
+                if let Some((next_id, next_date)) = next_occurrence {
+                    let mut next_nota = nota.clone();
+                    next_nota.id = next_id;
+                    next_nota.start_date = Some(next_date);
+                    next_nota.status = old_status.clone(); // Use the original status, not done
+                    next_nota.created_at = today;
+                    next_nota.updated_at = today;
+
+                    // Check if next occurrence ID already exists
+                    if !data.contains_id(&next_nota.id) {
+                        data.add(next_nota.clone());
+                        change.next_occurrence = Some(next_nota);
+                    }
+                }
+
+                // Update the nota
+                let commit_item = CommitItem::from_nota(&nota, Some(old_status));
+                if data.update(&normalized_id, nota).is_none() {
+                    fail(&normalized_id, "failed to update".to_string());
+                    continue;
+                }
+
+                commit_items.push(commit_item);
+                changed.push(change);
+            }
+
+            let wip_warning = if changed.is_empty() {
+                None
+            } else {
+                wip::over_limit_warning(&data, &rules.wip_limits, &status)
+            };
+
+            (
+                StatusChanges {
+                    changed,
+                    failed,
+                    wip_warning,
+                },
+                commit_items,
+            )
+        };
+
+        // Save data if any changes were made
+        if !changes.changed.is_empty() {
+            let action = if is_trash { "Trash" } else { "Change status" };
+            self.save(action, &commit_items).await?;
+        }
+        Ok(changes)
+    }
+}
@@ -0,0 +1,215 @@
+//! Creating items
+
+use super::{GtdService, ServiceError, ServiceResult};
+use crate::commit_message::CommitItem;
+use crate::duplicates::{self, DuplicateCheck};
+use crate::gtd::{Nota, NotaStatus, RecurrencePattern};
+use crate::validation;
+use crate::wip::{self, WipMode};
+use chrono::NaiveDate;
+
+/// A new item for [`GtdService::inbox`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewItem {
+    /// Unique ID
+    pub id: String,
+    /// Brief description
+    pub title: String,
+    /// Status the item starts in
+    pub status: NotaStatus,
+    /// Parent project ID
+    pub project: Option<String>,
+    /// Context name
+    pub context: Option<String>,
+    /// Markdown notes
+    pub notes: Option<String>,
+    /// Start date, required for calendar items
+    pub start_date: Option<NaiveDate>,
+    /// Recurrence pattern
+    pub recurrence: Option<RecurrencePattern>,
+    /// Weekdays, days or month-day pairs of a weekly, monthly or yearly recurrence
+    pub recurrence_config: Option<String>,
+}
+
+impl NewItem {
+    /// An item with only an ID, a title and a status
+    pub fn new(id: impl Into<String>, title: impl Into<String>, status: NotaStatus) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            status,
+            project: None,
+            context: None,
+            notes: None,
+            start_date: None,
+            recurrence: None,
+            recurrence_config: None,
+        }
+    }
+}
+
+/// An item created by [`GtdService::inbox`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Created {
+    /// The item as it was saved
+    pub nota: Nota,
+    /// Possible duplicates and exceeded WIP limits, for the user
+    pub warnings: Vec<String>,
+}
+
+impl GtdService<'_> {
+    /// Create an item
+    ///
+    /// # Returns
+    /// The created item with its warnings, or why it was refused
+    pub async fn inbox(&self, item: NewItem) -> ServiceResult<Created> {
+        let rules = &self.rules;
+        // Enforce the configured ID rules before touching the data
+        if let Some(policy) = &rules.id_policy
+            && let Err(rule) = policy.check(&item.id)
+        {
+            return Err(ServiceError::Invalid(format!(
+                "ID policy error: {}. Please choose an ID that follows the rule.",
+                rule
+            )));
+        }
+
+        let (nota, commit_item, warnings) = {
+            let mut data = self.data.write().await;
+
+            // Check for duplicate ID across all notas (other casings collide
+            // when case-insensitive IDs are enabled)
+            if let Some(existing_id) = data.resolve_id(&item.id)
+                && let Some(status) = data.status_of(existing_id)
+            {
+                return Err(ServiceError::Invalid(format!(
+                    "Duplicate ID error: ID '{}' already exists (status: {:?}). Each item must have a unique ID. Please choose a different ID.",
+                    existing_id, status
+                )));
+            }
+
+            // Validate calendar status has start_date
+            if item.status == NotaStatus::calendar && item.start_date.is_none() {
+                return Err(ServiceError::Invalid(
+                    "Calendar status validation failed: status=calendar requires start_date parameter. Please provide a date in YYYY-MM-DD format.".to_string(),
+                ));
+            }
+
+            // Validate project reference if provided
+            if let Some(ref proj_id) = item.project
+                && data.find_project_by_id(proj_id).is_none()
+            {
+                return Err(ServiceError::Invalid(
+                    validation::format_invalid_project_error(proj_id, &data),
+                ));
+            }
+
+            // Validate context reference if provided
+            if let Some(ref ctx_name) = item.context
+                && data.find_context_by_name(ctx_name).is_none()
+            {
+                return Err(ServiceError::Invalid(
+                    validation::format_invalid_context_error(ctx_name, &data),
+                ));
+            }
+
+            // Validate recurrence configuration if recurrence pattern is provided
+            if let Some(ref pattern) = item.recurrence
+                && item.recurrence_config.is_none()
+            {
+                // Only weekly, monthly, and yearly require config
+                let example = match pattern {
+                    RecurrencePattern::weekly => {
+                        "weekday names (e.g., \"Monday,Wednesday,Friday\")"
+                    }
+                    RecurrencePattern::monthly => "day numbers (e.g., \"1,15,25\")",
+                    RecurrencePattern::yearly => "month-day pairs (e.g., \"1-1,12-25\")",
+                    RecurrencePattern::daily => "", // Daily doesn't need config
+                };
+                if !example.is_empty() {
+                    return Err(ServiceError::Invalid(format!(
+                        "Recurrence pattern '{:?}' requires recurrence_config with {}",
+                        pattern, example
+                    )));
+                }
+            }
+
+            // Catch re-captures of an item that is still open under another ID
+            let mut warning = None;
+            if rules.duplicate_check != DuplicateCheck::Off
+                && !matches!(
+                    item.status,
+                    NotaStatus::done
+                        | NotaStatus::trash
+                        | NotaStatus::reference
+                        | NotaStatus::context
+                        | NotaStatus::goal
+                        | NotaStatus::template
+                )
+            {
+                let similar = duplicates::find_similar_open_items(&data, &item.title);
+                if !similar.is_empty() {
+                    let description = duplicates::describe_matches(&similar);
+                    if rules.duplicate_check == DuplicateCheck::Reject {
+                        return Err(ServiceError::Invalid(format!(
+                            "Duplicate content error: '{}' nearly matches open item(s) {}. Update the existing item instead, or use a more specific title.",
+                            item.title, description
+                        )));
+                    }
+                    warning = Some(format!(
+                        "Warning: possible duplicate of {}. Consider updating the existing item and moving this one to trash.",
+                        description
+                    ));
+                }
+            }
+
+            // A full status refuses new items when the WIP limit is enforced
+            if rules.wip_mode == WipMode::Enforce
+                && let Some(max) = wip::limit_for(&rules.wip_limits, &item.status)
+                && wip::count(&data, &item.status) >= max
+            {
+                return Err(ServiceError::Invalid(format!(
+                    "WIP limit error: {}, or capture it with status inbox.",
+                    wip::full_error(&item.status, max)
+                )));
+            }
+
+            let today = rules.clock.today();
+            let nota = Nota {
+                id: item.id,
+                title: item.title,
+                status: item.status,
+                project: item.project,
+                context: item.context,
+                notes: item.notes,
+                start_date: item.start_date,
+                created_at: today,
+                updated_at: today,
+                recurrence_pattern: item.recurrence,
+                recurrence_config: item.recurrence_config,
+                link: None,
+                priority: None,
+                due_date: None,
+                estimate: None,
+                energy: None,
+                focus_date: None,
+                paused: false,
+                previous_status: None,
+                goal: None,
+                availability: None,
+                escalate_after_days: None,
+                status_since: None,
+            };
+
+            let commit_item = CommitItem::from_nota(&nota, None);
+            data.add(nota.clone());
+            let wip_warning = wip::over_limit_warning(&data, &rules.wip_limits, &nota.status);
+            let warnings: Vec<String> = warning.into_iter().chain(wip_warning).collect();
+
+            (nota, commit_item, warnings)
+        };
+
+        self.save("Add", &[commit_item]).await?;
+        Ok(Created { nota, warnings })
+    }
+}
@@ -0,0 +1,79 @@
+//! Listing and filtering items
+
+use super::GtdService;
+use crate::formatting;
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use chrono::NaiveDate;
+
+/// Filters for [`GtdService::list`]; the default lists every item
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListQuery {
+    /// Only items with this status
+    pub status: Option<NotaStatus>,
+    /// Hide calendar items starting after this date
+    pub date: Option<NaiveDate>,
+    /// Only items whose ID, title or notes contain this (case-insensitive)
+    pub keyword: Option<String>,
+    /// Only items of this project
+    pub project: Option<String>,
+    /// Only items in this context (tasks without one use their project's).
+    /// Next actions default to the current context; `Some("")` lists every
+    /// context.
+    pub context: Option<String>,
+}
+
+/// Items found by [`GtdService::list`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+    /// The matching items
+    pub notas: Vec<Nota>,
+    /// The current context the listing was narrowed to, when the query named
+    /// no context
+    pub current_context: Option<String>,
+}
+
+impl GtdService<'_> {
+    /// List the items matching a query
+    pub async fn list(&self, query: ListQuery) -> Listing {
+        let data = self.data.read().await;
+        let (notas, current_context) = self.filter(&data, query);
+        Listing {
+            notas: notas.into_iter().cloned().collect(),
+            current_context,
+        }
+    }
+
+    /// The items matching a query, borrowed from the data
+    ///
+    /// # Returns
+    /// The items, and the current context they were narrowed to, if any
+    pub(crate) fn filter<'d>(
+        &self,
+        data: &'d GtdData,
+        query: ListQuery,
+    ) -> (Vec<&'d Nota>, Option<String>) {
+        // Next actions default to the context set with set_current_context
+        let next_actions = query.status == Some(NotaStatus::next_action);
+        let default_context = next_actions && query.context.is_none();
+        let context = if next_actions {
+            super::context_or_current(data, query.context, self.rules.current_context.clone())
+        } else {
+            query.context
+        };
+        // project/context come from GtdData's reverse indexes
+        let mut notas =
+            data.list_filtered(query.status, query.project.as_deref(), context.as_deref());
+
+        // Apply additional filters in sequence
+        if let Some(filter_date) = query.date {
+            formatting::apply_date_filter(&mut notas, filter_date);
+        }
+
+        if let Some(ref keyword_filter) = query.keyword {
+            let matches = data.search(keyword_filter);
+            notas.retain(|nota| matches.contains(nota.id.as_str()));
+        }
+
+        (notas, context.filter(|_| default_context))
+    }
+}
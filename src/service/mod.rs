@@ -0,0 +1,209 @@
+//! The GTD engine without the MCP layer
+//!
+//! [`GtdService`] runs the core operations - capture (`inbox`), `list`,
+//! `update` and `change_status` - on a handler's data with typed arguments and
+//! results, for embedders that drive the data from their own UI instead of an
+//! MCP client:
+//!
+//! ```no_run
+//! # use gtd_mcp::GtdServerHandler;
+//! # use gtd_mcp::NotaStatus;
+//! # use gtd_mcp::service::{ListQuery, NewItem};
+//! # async fn example() -> anyhow::Result<()> {
+//! let handler = GtdServerHandler::builder().path("gtd.toml").build()?;
+//! let service = handler.service();
+//! service
+//!     .inbox(NewItem::new("call-bob", "Call Bob", NotaStatus::next_action))
+//!     .await?;
+//! let next = service
+//!     .list(ListQuery {
+//!         status: Some(NotaStatus::next_action),
+//!         ..Default::default()
+//!     })
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The service applies every rule of the tools (ID policy, references, WIP
+//! limits, duplicate checks, recurrence, see [`Rules`]) and saves through a
+//! [`Persist`]. A handler's service saves through the handler, so its changes
+//! are committed and reach MCP clients like any tool call; the tools of the
+//! same names only parse their string arguments, call the service and render
+//! its result. Without a handler, [`GtdService::new`] runs on any data behind
+//! a lock with a save callback:
+//!
+//! ```
+//! # use gtd_mcp::{GtdData, NotaStatus};
+//! # use gtd_mcp::service::{GtdService, NewItem, Rules};
+//! # async fn example() -> Result<(), gtd_mcp::ServiceError> {
+//! let data = tokio::sync::RwLock::new(GtdData::new());
+//! let save = |message: &str| {
+//!     println!("{}", message);
+//!     Ok(())
+//! };
+//! let service = GtdService::new(&data, Rules::default(), &save);
+//! service
+//!     .inbox(NewItem::new("call-bob", "Call Bob", NotaStatus::inbox))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod change_status;
+pub mod inbox;
+pub mod list;
+pub mod update;
+
+pub use change_status::{Cascade, StatusChange, StatusChanges, StatusFailure};
+pub use inbox::{Created, NewItem};
+pub use list::{ListQuery, Listing};
+pub use update::ItemUpdate;
+
+use crate::commit_message::{CommitItem, CommitTemplate};
+use crate::duplicates::DuplicateCheck;
+use crate::gtd::{Clock, GtdData, SystemClock};
+use crate::id_generator::{IdGenerator, IdScheme};
+use crate::id_policy::IdPolicy;
+use crate::wip::{WipLimit, WipMode};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Why a service call failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceError {
+    /// The item does not exist
+    NotFound(String),
+    /// The call breaks a rule of the data (unknown reference, taken ID, full
+    /// WIP limit, ...); the message says how to fix it
+    Invalid(String),
+    /// The change was applied in memory but could not be saved
+    Save(String),
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::NotFound(message) | ServiceError::Invalid(message) => {
+                write!(f, "{}", message)
+            }
+            ServiceError::Save(error) => write!(f, "Failed to save: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+/// Result of a service call
+pub type ServiceResult<T> = Result<T, ServiceError>;
+
+/// Future returned by [`Persist::save`]
+pub type SaveFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>;
+
+/// Where the service writes its changes
+///
+/// The MCP handler saves through its storage (and transactions); a closure
+/// `Fn(&str) -> anyhow::Result<()>` is enough for other embedders.
+pub trait Persist: Send + Sync {
+    /// Save the data after a change
+    ///
+    /// # Arguments
+    /// * `message` - Commit message describing the change
+    fn save<'a>(&'a self, message: &'a str) -> SaveFuture<'a>;
+}
+
+impl<F> Persist for F
+where
+    F: Fn(&str) -> anyhow::Result<()> + Send + Sync,
+{
+    fn save<'a>(&'a self, message: &'a str) -> SaveFuture<'a> {
+        Box::pin(async move { self(message) })
+    }
+}
+
+/// The rules the service applies to every change
+///
+/// The handler fills them in from its options; the default is a server
+/// without any option.
+#[derive(Clone)]
+pub struct Rules {
+    /// Where "today" comes from
+    pub clock: Arc<dyn Clock>,
+    /// Rules new IDs must follow
+    pub id_policy: Option<IdPolicy>,
+    /// Source of the IDs of next occurrences
+    pub id_generator: Arc<dyn IdGenerator>,
+    /// What `inbox` does with a title matching an open item
+    pub duplicate_check: DuplicateCheck,
+    /// Most items a status may hold
+    pub wip_limits: Vec<WipLimit>,
+    /// Whether the limits refuse moves or only warn
+    pub wip_mode: WipMode,
+    /// Template of the commit messages passed to [`Persist::save`]
+    pub commit_template: CommitTemplate,
+    /// Context next actions are listed in when the query names none
+    pub current_context: Option<String>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(SystemClock::default()),
+            id_policy: None,
+            id_generator: Arc::new(IdScheme::default()),
+            duplicate_check: DuplicateCheck::default(),
+            wip_limits: Vec::new(),
+            wip_mode: WipMode::default(),
+            commit_template: CommitTemplate::default(),
+            current_context: None,
+        }
+    }
+}
+
+/// Typed access to GTD data (see the [module docs](self))
+#[derive(Clone)]
+pub struct GtdService<'a> {
+    data: &'a RwLock<GtdData>,
+    rules: Rules,
+    store: &'a dyn Persist,
+}
+
+impl<'a> GtdService<'a> {
+    /// Run the operations on `data`, saving through `store`
+    ///
+    /// # Arguments
+    /// * `data` - The data to read and change
+    /// * `rules` - The rules changes must follow
+    /// * `store` - Where changes are saved
+    pub fn new(data: &'a RwLock<GtdData>, rules: Rules, store: &'a dyn Persist) -> Self {
+        Self { data, rules, store }
+    }
+
+    /// Save the data with a commit message built from the template
+    async fn save(&self, action: &str, items: &[CommitItem]) -> ServiceResult<()> {
+        let message = self.rules.commit_template.render(action, items);
+        self.store
+            .save(&message)
+            .await
+            .map_err(|e| ServiceError::Save(e.to_string()))
+    }
+}
+
+/// Context to filter by: `context`, none for `""`, otherwise `current`
+///
+/// A current context that does not exist in `data` (e.g. after switching
+/// workspaces) is ignored.
+pub(crate) fn context_or_current(
+    data: &GtdData,
+    context: Option<String>,
+    current: Option<String>,
+) -> Option<String> {
+    match context {
+        Some(context) if context.trim().is_empty() => None,
+        Some(context) => Some(context),
+        None => current.filter(|current| data.find_context_by_name(current).is_some()),
+    }
+}
@@ -0,0 +1,161 @@
+//! Changing the fields of an item
+
+use super::{GtdService, ServiceError, ServiceResult};
+use crate::commit_message::CommitItem;
+use crate::duplicates;
+use crate::gtd::{Energy, Nota, NotaStatus, Priority};
+use crate::validation;
+use chrono::NaiveDate;
+
+/// Changes for [`GtdService::update`]
+///
+/// `None` leaves a field as it is; for the optional fields `Some(None)`
+/// clears it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemUpdate {
+    /// New title
+    pub title: Option<String>,
+    /// New status (changes the type of a project or context)
+    pub status: Option<NotaStatus>,
+    /// Project link
+    pub project: Option<Option<String>>,
+    /// Context tag
+    pub context: Option<Option<String>>,
+    /// Markdown notes
+    pub notes: Option<Option<String>>,
+    /// Start date
+    pub start_date: Option<Option<NaiveDate>>,
+    /// Priority
+    pub priority: Option<Option<Priority>>,
+    /// Deadline
+    pub due_date: Option<Option<NaiveDate>>,
+    /// Estimated minutes
+    pub estimate: Option<Option<u32>>,
+    /// Energy needed
+    pub energy: Option<Option<Energy>>,
+    /// Goal a project contributes to
+    pub goal: Option<Option<String>>,
+}
+
+impl GtdService<'_> {
+    /// Change the fields of an item
+    ///
+    /// # Returns
+    /// The item as it was saved, or why the changes were refused (nothing is
+    /// changed then)
+    pub async fn update(&self, id: &str, changes: ItemUpdate) -> ServiceResult<Nota> {
+        let rules = &self.rules;
+        let today = rules.clock.today();
+        let (nota, commit_item) = {
+            let mut data = self.data.write().await;
+
+            // Find existing nota
+            let Some(mut nota) = data.find_by_id(id) else {
+                let hint = duplicates::did_you_mean(&duplicates::similar_ids(&data, id));
+                return Err(ServiceError::NotFound(format!(
+                    "Item not found: Item '{}' does not exist.{} Use list() to see available items.",
+                    id, hint
+                )));
+            };
+
+            let old_status = nota.status.clone();
+
+            // Update fields if provided
+            if let Some(title) = changes.title {
+                nota.title = title;
+            }
+
+            if let Some(new_status) = changes.status {
+                // A linked project/context must keep its type
+                validation::check_type_change(&data, &nota.id, &old_status, &new_status)
+                    .map_err(ServiceError::Invalid)?;
+                nota.set_status(new_status, today);
+            }
+
+            if let Some(project) = changes.project {
+                // Validate project exists
+                if let Some(proj) = &project
+                    && data.find_project_by_id(proj).is_none()
+                {
+                    return Err(ServiceError::Invalid(
+                        validation::format_invalid_project_error(proj, &data),
+                    ));
+                }
+                nota.project = project;
+            }
+
+            if let Some(context) = changes.context {
+                // Validate context exists
+                if let Some(ctx) = &context
+                    && data.find_context_by_name(ctx).is_none()
+                {
+                    return Err(ServiceError::Invalid(
+                        validation::format_invalid_context_error(ctx, &data),
+                    ));
+                }
+                nota.context = context;
+            }
+
+            if let Some(notes) = changes.notes {
+                nota.notes = notes;
+            }
+            if let Some(start_date) = changes.start_date {
+                nota.start_date = start_date;
+            }
+            if let Some(priority) = changes.priority {
+                nota.priority = priority;
+            }
+            if let Some(due_date) = changes.due_date {
+                nota.due_date = due_date;
+            }
+            if let Some(estimate) = changes.estimate {
+                nota.estimate = estimate;
+            }
+            if let Some(energy) = changes.energy {
+                nota.energy = energy;
+            }
+
+            if let Some(goal) = changes.goal {
+                if let Some(g) = &goal
+                    && data.find_goal_by_id(g).is_none()
+                {
+                    return Err(ServiceError::Invalid(
+                        validation::format_invalid_goal_error(g, &data),
+                    ));
+                }
+                nota.goal = goal;
+            }
+
+            // Only projects contribute to goals
+            if nota.goal.is_some() && !nota.is_project() {
+                return Err(ServiceError::Invalid(format!(
+                    "Only projects can have a goal, but '{}' is a {:?} item. Link its project to the goal instead, or clear it with goal=\"\".",
+                    nota.id, nota.status
+                )));
+            }
+
+            // Validate calendar status has start_date
+            if nota.status == NotaStatus::calendar && nota.start_date.is_none() {
+                return Err(ServiceError::Invalid(
+                    "Calendar status validation failed: status=calendar requires start_date. Please provide a start_date or change to a different status.".to_string(),
+                ));
+            }
+
+            nota.updated_at = today;
+            let commit_item = CommitItem::from_nota(&nota, Some(old_status));
+
+            // Update the nota
+            if data.update(id, nota.clone()).is_none() {
+                return Err(ServiceError::NotFound(format!(
+                    "Failed to update item '{}'",
+                    id
+                )));
+            }
+
+            (nota, commit_item)
+        };
+
+        self.save("Update", &[commit_item]).await?;
+        Ok(nota)
+    }
+}
@@ -0,0 +1,34 @@
+//! Which MCP session a tool call comes from
+//!
+//! Several clients can share one handler (see [`mcp_http`](crate::mcp_http)).
+//! Each [`SharedServer`](crate::daemon::SharedServer) is one session: it gets
+//! its own [`SessionId`] and runs every tool call inside [`scope`], so state
+//! that belongs to a client, such as the current context or an open
+//! transaction, can tell its owner from everyone else. Calls made outside any session (the library API, a
+//! handler served on its own, the daemon's jobs) have no session.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Identifies one MCP session of a shared handler
+pub(crate) type SessionId = u64;
+
+tokio::task_local! {
+    static SESSION: SessionId;
+}
+
+/// A session ID no other session of this process has
+pub(crate) fn next() -> SessionId {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Session of the running tool call (`None` outside any session)
+pub(crate) fn current() -> Option<SessionId> {
+    SESSION.try_with(|session| *session).ok()
+}
+
+/// Run `future` as a call of `session`
+pub(crate) fn scope<F: Future>(session: SessionId, future: F) -> impl Future<Output = F::Output> {
+    SESSION.scope(session, future)
+}
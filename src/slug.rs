@@ -0,0 +1,64 @@
+//! Generating IDs from titles
+//!
+//! Capture without an explicit ID turns the title into a lowercase kebab-case
+//! slug ("Call John re: Q3" → `call-john-re-q3`). A numeric suffix keeps the
+//! ID unique (`call-john-re-q3-2`). Slugs always satisfy
+//! [`crate::IdPolicy::kebab_case`].
+
+use crate::gtd::GtdData;
+use crate::id_policy::DEFAULT_MAX_LENGTH;
+
+/// ID used when a title has no ASCII letters or digits (e.g. "買い物")
+const FALLBACK_SLUG: &str = "item";
+
+/// Turn a title into a lowercase kebab-case slug
+///
+/// ASCII letters and digits are kept; every run of other characters becomes
+/// one hyphen. The slug is cut at a word boundary to fit the kebab-case
+/// length limit, leaving room for a collision suffix.
+///
+/// # Arguments
+/// * `title` - Title to convert
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let word = word.to_ascii_lowercase();
+        let needed = if slug.is_empty() { 0 } else { 1 } + word.len();
+        if slug.len() + needed > DEFAULT_MAX_LENGTH - 4 {
+            if slug.is_empty() {
+                slug = word[..DEFAULT_MAX_LENGTH - 4].to_string();
+            }
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word);
+    }
+    if slug.is_empty() {
+        slug.push_str(FALLBACK_SLUG);
+    }
+    slug
+}
+
+/// Slug of `title` that no item in `data` uses yet
+///
+/// Tries the slug itself, then `slug-2`, `slug-3` and so on. IDs are compared
+/// through [`GtdData::resolve_id`], so case-insensitive files are respected.
+///
+/// # Arguments
+/// * `data` - Items whose IDs are taken
+/// * `title` - Title to convert
+pub fn unique_id(data: &GtdData, title: &str) -> String {
+    let base = slugify(title);
+    if data.resolve_id(&base).is_none() {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| data.resolve_id(candidate).is_none())
+        .expect("an unused suffix exists")
+}
@@ -1,9 +1,22 @@
-use crate::git_ops::GitOps;
-#[allow(unused_imports)]
-use crate::gtd::{GtdData, local_date_today};
-use anyhow::Result;
+use crate::checksum::{self, Verification};
+use crate::external_notes;
+use crate::git_ops::{ConflictVersion, GitConflict, GitOps, GitStatus, SyncReport};
+use crate::gtd::{Clock, GtdData, SystemClock};
+use crate::journal::Journal;
+use crate::migration;
+use crate::mirror::{self, MirrorFormat};
+use crate::progress::Cancellation;
+use crate::push_worker::{PushStatus, PushWorker};
+use crate::schema;
+use crate::webhook;
+use anyhow::{Result, anyhow};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Once};
+use std::time::{Duration, Instant};
 
 /// Normalize line endings to LF (\n) for internal use
 /// This ensures consistent behavior when deserializing
@@ -27,12 +40,255 @@ fn to_native_line_endings(content: &str) -> String {
     normalize_line_endings(content)
 }
 
+/// Merge two conflicting versions of the data file at the nota level
+///
+/// Used as the Git conflict resolver for the data file: instead of leaving
+/// conflict markers, both versions are parsed (with the notes files of the
+/// same side) and merged with [`GtdData::merge`] (union of items, newest
+/// `updated_at` wins, local wins ties). The result is written the way a save
+/// writes it, with its checksum header and notes files.
+fn resolve_conflict(
+    data_file: &Path,
+    local: &ConflictVersion,
+    remote: &ConflictVersion,
+) -> Result<()> {
+    let local = parse_version(data_file, local)?;
+    let remote = parse_version(data_file, remote)?;
+    let merged = Storage::serialize(&local.merge(&remote))?;
+    write_files(data_file, &merged)?;
+    Ok(())
+}
+
+/// Parse one side of a conflict, reading its notes files back inline
+fn parse_version(data_file: &Path, version: &ConflictVersion) -> Result<GtdData> {
+    let file_name = data_file
+        .file_name()
+        .ok_or_else(|| anyhow!("Data file has no name"))?;
+    let notes_dir = PathBuf::from(external_notes::notes_dir_name(data_file));
+    let content = normalize_line_endings(&version.read(file_name)?);
+    let body = checksum::split_header(&content).1;
+    Ok(
+        match external_notes::inline_with(body, |name| version.read(notes_dir.join(name)))? {
+            Some(table) => toml::Value::Table(table).try_into()?,
+            None => toml::from_str(body)?,
+        },
+    )
+}
+
+/// Parse data file content, reading notes kept in Markdown files back inline
+fn parse_file_content(data_file: &Path, content: &str) -> Result<GtdData> {
+    let normalized = normalize_line_endings(content);
+    let body = checksum::split_header(&normalized).1;
+    Ok(match external_notes::inline(body, data_file)? {
+        Some(table) => toml::Value::Table(table).try_into()?,
+        None => toml::from_str(body)?,
+    })
+}
+
+/// Write serialized data to the data file and its notes files
+///
+/// Long notes go to their own files (format version 4), the data file gets
+/// its checksum header and OS-native line endings, and notes files no item
+/// refers to any more are removed.
+///
+/// # Returns
+/// The content written to the data file
+fn write_files(data_file: &Path, content: &str) -> Result<String> {
+    let (file_content, notes) = match external_notes::render(content)? {
+        Some(rendered) => (rendered.content, rendered.notes),
+        None => (content.to_string(), Vec::new()),
+    };
+    let native_content = to_native_line_endings(&checksum::with_header(&file_content));
+    let notes_dir = external_notes::notes_dir(data_file);
+    external_notes::write_notes(&notes_dir, &notes)?;
+    fs::write(data_file, &native_content)?;
+    external_notes::prune_notes(&notes_dir, &notes)?;
+    Ok(native_content)
+}
+
+/// Build the commit message for a batch of coalesced writes
+///
+/// Also used for the single commit of a handler transaction.
+///
+/// A single write keeps its original message. Multiple writes are summarized
+/// with a headline followed by one bullet per original message.
+pub(crate) fn summarize_commit_messages(messages: &[String]) -> String {
+    match messages {
+        [] => "Update GTD data".to_string(),
+        [single] => single.clone(),
+        _ => {
+            let mut summary = format!("Batch update: {} changes\n\n", messages.len());
+            for message in messages {
+                summary.push_str(&format!("- {}\n", message));
+            }
+            summary.trim_end().to_string()
+        }
+    }
+}
+
+/// Error of a save refused because the data file changed on disk since this
+/// storage last read or wrote it
+///
+/// Another machine (through a file sync service or a Git pull), an editor or a
+/// second gtd-mcp process wrote the file; writing the in-memory data would
+/// silently drop their changes. Nothing was written: reload the data with
+/// [`Storage::load_local`] (the `reload` tool) and redo the change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalChange {
+    /// Path of the data file
+    pub path: PathBuf,
+}
+
+impl fmt::Display for ExternalChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} was changed outside this server since it was loaded, so the change was not saved. Call reload to load the current file, then retry the change.",
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for ExternalChange {}
+
+/// Saved state of a data file, taken by [`Storage::snapshot`]
+///
+/// Holds the data in memory; [`Storage::restore`] writes it back as a regular
+/// save (journaled and committed).
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Data file the snapshot was taken of
+    file_path: PathBuf,
+    /// Serialized data (see [`Storage::serialize`])
+    content: String,
+    /// Number of items in the snapshot
+    item_count: usize,
+    /// When the snapshot was taken
+    taken_at: NaiveDateTime,
+}
+
+impl Snapshot {
+    /// Data file the snapshot was taken of
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    /// Number of items in the snapshot
+    pub fn item_count(&self) -> usize {
+        self.item_count
+    }
+
+    /// When the snapshot was taken (local time)
+    pub fn taken_at(&self) -> NaiveDateTime {
+        self.taken_at
+    }
+}
+
+/// Writes waiting for the debounce window to elapse
+#[derive(Default)]
+struct PendingWrite {
+    /// Latest serialized content (only the newest snapshot is ever written)
+    content: Option<String>,
+    /// Commit messages of every coalesced write, oldest first
+    messages: Vec<String>,
+    /// Time of the most recent write request
+    last_write: Option<Instant>,
+    /// Journal sequence number of the newest coalesced write
+    journal_seq: u64,
+    /// Set when the owning storage shuts down, stopping the flusher thread
+    stopped: bool,
+}
+
+/// Coalesces rapid writes into a single file write and Git commit
+///
+/// Writes are buffered in memory and flushed by a background thread once no new
+/// write has arrived for `window`, or explicitly via [`Storage::flush`].
+struct Debouncer {
+    window: Duration,
+    pending: Mutex<PendingWrite>,
+    wakeup: Condvar,
+    /// Held while a flush performs I/O so flushes never overlap or reorder
+    io_lock: Mutex<()>,
+    /// Starts the flusher thread on the first buffered write
+    flusher: Once,
+}
+
+impl Debouncer {
+    /// Start the background flusher unless it runs already
+    ///
+    /// The flusher writes through a copy of `storage` as it is at the first
+    /// buffered write, when every `with_*` option has been applied.
+    fn start(self: &Arc<Self>, storage: &Storage) {
+        self.flusher.call_once(|| {
+            let writer = Storage {
+                debouncer: None,
+                ..storage.clone()
+            };
+            let worker = self.clone();
+            std::thread::spawn(move || worker.run(writer));
+        });
+    }
+
+    /// Take the pending write (if any) and write it through `writer`
+    fn flush(&self, writer: &Storage) -> Result<()> {
+        let _io = self.io_lock.lock().unwrap();
+        let (content, messages, seq) = {
+            let mut pending = self.pending.lock().unwrap();
+            let content = pending.content.take();
+            let messages = std::mem::take(&mut pending.messages);
+            pending.last_write = None;
+            (content, messages, pending.journal_seq)
+        };
+        match content {
+            Some(content) => {
+                writer.write_journaled(&content, &summarize_commit_messages(&messages), seq)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Background loop: flush whenever the pending write has been idle for `window`
+    fn run(self: Arc<Self>, writer: Storage) {
+        loop {
+            {
+                let mut pending = self.pending.lock().unwrap();
+                loop {
+                    if pending.stopped {
+                        return;
+                    }
+                    match pending.last_write {
+                        None => pending = self.wakeup.wait(pending).unwrap(),
+                        Some(last) => {
+                            let elapsed = last.elapsed();
+                            if elapsed >= self.window {
+                                break;
+                            }
+                            pending = self
+                                .wakeup
+                                .wait_timeout(pending, self.window - elapsed)
+                                .unwrap()
+                                .0;
+                        }
+                    }
+                }
+            }
+            if let Err(e) = self.flush(&writer) {
+                eprintln!("Warning: Debounced save failed: {}", e);
+            }
+        }
+    }
+}
+
 /// Storage handler for GTD data persistence
 ///
 /// Handles reading and writing GTD data to TOML files with optional Git synchronization.
 /// Automatically manages line endings:
 /// - Normalizes to LF on read for consistent parsing
 /// - Converts to OS-native format on write (CRLF on Windows, LF on Unix)
+///
+/// Cloning is cheap: the Git repository handle is shared, so a clone can be moved
+/// onto a blocking worker thread to perform the actual file and Git I/O.
+#[derive(Clone)]
 pub struct Storage {
     /// Path to the GTD data file
     file_path: PathBuf,
@@ -40,6 +296,29 @@ pub struct Storage {
     git_ops: GitOps,
     /// Whether to enable Git synchronization
     sync_git: bool,
+    /// Optional write coalescing (None writes through immediately)
+    debouncer: Option<Arc<Debouncer>>,
+    /// Optional read-only export rewritten after every save
+    mirror: Option<(PathBuf, MirrorFormat)>,
+    /// Set when a Git sync brought in commits made on another machine
+    remote_updated: Arc<AtomicBool>,
+    /// Whether completed weekly reviews are tagged as `weekly-YYYY-Www`
+    weekly_snapshots: bool,
+    /// Optional background pusher (None pushes inline on every save)
+    push_worker: Option<Arc<PushWorker>>,
+    /// Whether loaded data matches IDs and context names regardless of case
+    case_insensitive_ids: bool,
+    /// Optional URL notified of the changed items after every save
+    webhook: Option<String>,
+    /// Write-ahead journal replayed on load after a crash
+    journal: Arc<Journal>,
+    /// Whether a checksum mismatch on load triggers an integrity check
+    validate_on_checksum_mismatch: bool,
+    /// Digest of the data file as last read or written here (`None` until the
+    /// first load or save); a save finding other content is refused
+    known_digest: Arc<Mutex<Option<String>>>,
+    /// Where the date of mirrors and the time of snapshots come from
+    clock: Arc<dyn Clock>,
 }
 
 impl Storage {
@@ -50,14 +329,177 @@ impl Storage {
     /// * `sync_git` - Whether to enable automatic Git synchronization
     pub fn new(file_path: impl AsRef<Path>, sync_git: bool) -> Self {
         let file_path = file_path.as_ref().to_path_buf();
-        let git_ops = GitOps::new(&file_path);
+        let resolver_path = file_path.clone();
+        let git_ops = GitOps::new(&file_path)
+            .with_conflict_resolver(
+                &file_path,
+                Arc::new(move |local: &ConflictVersion, remote: &ConflictVersion| {
+                    resolve_conflict(&resolver_path, local, remote)
+                }),
+            )
+            .with_companion_dir(external_notes::notes_dir_name(&file_path));
+        let journal = Arc::new(Journal::for_data_file(&file_path));
         Self {
             file_path,
             git_ops,
             sync_git,
+            debouncer: None,
+            mirror: None,
+            remote_updated: Arc::new(AtomicBool::new(false)),
+            weekly_snapshots: false,
+            push_worker: None,
+            case_insensitive_ids: false,
+            webhook: None,
+            journal,
+            validate_on_checksum_mismatch: false,
+            known_digest: Arc::new(Mutex::new(None)),
+            clock: Arc::new(SystemClock::default()),
         }
     }
 
+    /// Take the date and time from `clock` instead of the system time
+    ///
+    /// The handler passes its own clock (see [`crate::GtdServerHandler::with_clock`]),
+    /// so an iCalendar mirror and snapshots agree with the tools.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Match IDs and context names case-insensitively in all loaded data
+    ///
+    /// "office" then finds "Office", and IDs differing only in case are
+    /// duplicates. Stored casing is preserved (see [`GtdData::set_case_insensitive_ids`]).
+    pub fn with_case_insensitive_ids(mut self) -> Self {
+        self.case_insensitive_ids = true;
+        self
+    }
+
+    /// Check the whole file for integrity problems when its checksum does not match
+    ///
+    /// Loading always warns on stderr when the file was changed outside the
+    /// server (see [`crate::checksum`]); with this option the warning also
+    /// lists the problems found by [`GtdData::check_integrity`].
+    pub fn with_checksum_validation(mut self) -> Self {
+        self.validate_on_checksum_mismatch = true;
+        self
+    }
+
+    /// Set the author identity for commits made by Git sync
+    ///
+    /// Each value overrides the Git config (`user.name` / `user.email`);
+    /// `None` keeps it.
+    ///
+    /// # Arguments
+    /// * `name` - Author name (e.g., "GTD Bot")
+    /// * `email` - Author email (e.g., "gtd@local")
+    pub fn with_git_author(mut self, name: Option<String>, email: Option<String>) -> Self {
+        self.git_ops = self.git_ops.with_author(name, email);
+        self
+    }
+
+    /// Prepare the Git repository for sync on a fresh setup
+    ///
+    /// Initializes a repository in the data file's directory if needed, adds
+    /// `remote_url` as `origin` when no remote exists (without one, commits
+    /// stay local), and creates an initial commit or adopts the remote's
+    /// history. Does nothing unless Git sync is enabled. Call this before
+    /// [`Storage::with_background_push`].
+    ///
+    /// # Arguments
+    /// * `remote_url` - URL for `origin` if the repository has no remote
+    ///
+    /// # Returns
+    /// Descriptions of the steps taken, or an error
+    pub fn bootstrap_git(&mut self, remote_url: Option<&str>) -> Result<Vec<String>> {
+        if !self.sync_git {
+            return Ok(Vec::new());
+        }
+        self.git_ops.bootstrap(&self.file_path, remote_url)
+    }
+
+    /// Tag the repository when a weekly review is completed
+    ///
+    /// See [`Storage::tag_weekly_snapshot`]. Has no effect unless Git sync is
+    /// enabled.
+    pub fn with_weekly_snapshots(mut self) -> Self {
+        self.weekly_snapshots = true;
+        self
+    }
+
+    /// Write a read-only mirror of the data alongside the TOML file on every save
+    ///
+    /// The mirror is regenerated from the saved content after each file write
+    /// (including debounced flushes) and marked read-only on disk. Failing to
+    /// write the mirror never fails the save itself; it is logged to stderr.
+    ///
+    /// # Arguments
+    /// * `path` - Mirror file path
+    /// * `format` - Mirror format (JSON or Markdown report)
+    pub fn with_mirror(mut self, path: impl AsRef<Path>, format: MirrorFormat) -> Self {
+        self.mirror = Some((path.as_ref().to_path_buf(), format));
+        self
+    }
+
+    /// POST the items changed by every save to a webhook URL
+    ///
+    /// See [`crate::webhook`] for the payload. Notifications are sent after
+    /// the file write and Git sync succeed, from a background thread; failing
+    /// to deliver them never fails the save and is logged to stderr.
+    ///
+    /// # Arguments
+    /// * `url` - Webhook URL
+    pub fn with_webhook(mut self, url: impl Into<String>) -> Self {
+        self.webhook = Some(url.into());
+        self
+    }
+
+    /// Push to the remote from a background thread instead of on every save
+    ///
+    /// Saves still commit and integrate remote changes synchronously, but the
+    /// push is handed to a worker that runs it at most once per `interval`
+    /// (a zero interval pushes right after each commit). Network failures are
+    /// retried with exponential backoff, so saves keep working offline.
+    /// Has no effect unless Git sync is enabled and the file is in a repository.
+    ///
+    /// # Arguments
+    /// * `interval` - Minimum time between pushes
+    pub fn with_background_push(mut self, interval: Duration) -> Self {
+        if self.sync_git && self.git_ops.is_git_managed() {
+            self.push_worker = Some(PushWorker::spawn(self.git_ops.clone(), interval));
+        }
+        self
+    }
+
+    /// Enable debounced saving
+    ///
+    /// Writes are buffered and flushed once no new write has arrived for `window`
+    /// (or on [`Storage::flush`] / [`Storage::shutdown`]). All writes coalesced into
+    /// one flush produce a single file write and a single summarized Git commit.
+    /// A zero window disables debouncing. The other `with_*` options apply to
+    /// the flushes whichever order they are set in, since the background
+    /// flusher only starts with the first buffered write.
+    ///
+    /// Git errors from a background flush cannot reach the tool call that caused
+    /// them and are logged to stderr instead.
+    ///
+    /// # Arguments
+    /// * `window` - Idle time after the last write before flushing
+    pub fn with_debounce(mut self, window: Duration) -> Self {
+        if window.is_zero() {
+            self.debouncer = None;
+            return self;
+        }
+        self.debouncer = Some(Arc::new(Debouncer {
+            window,
+            pending: Mutex::new(PendingWrite::default()),
+            wakeup: Condvar::new(),
+            io_lock: Mutex::new(()),
+            flusher: Once::new(),
+        }));
+        self
+    }
+
     /// Get the path to the GTD data file
     ///
     /// # Returns
@@ -66,28 +508,234 @@ impl Storage {
         &self.file_path
     }
 
+    /// Storage of the archive file next to the data file (`gtd.toml` → `gtd.archive.toml`)
+    ///
+    /// The archive has the same format as the data file and shares its Git
+    /// settings, but writes immediately and has no mirror, webhook or background push.
+    pub fn archive_storage(&self) -> Storage {
+        let stem = self
+            .file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "gtd".to_string());
+        let file_path = self
+            .file_path
+            .with_file_name(format!("{}.archive.toml", stem));
+        Storage {
+            journal: Arc::new(Journal::for_data_file(&file_path)),
+            file_path,
+            debouncer: None,
+            mirror: None,
+            remote_updated: Arc::new(AtomicBool::new(false)),
+            weekly_snapshots: false,
+            push_worker: None,
+            webhook: None,
+            known_digest: Arc::new(Mutex::new(None)),
+            ..self.clone()
+        }
+    }
+
+    /// Check whether saves are committed and synchronized with Git
+    ///
+    /// # Returns
+    /// `true` if Git sync is enabled and the file is in a Git repository
+    pub fn git_sync_enabled(&self) -> bool {
+        self.sync_git && self.git_ops.is_git_managed()
+    }
+
     /// Load GTD data from the storage file
     ///
-    /// If Git sync is enabled, pulls changes from remote before loading.
-    /// Returns an empty GtdData instance if the file doesn't exist.
+    /// Saves left in the journal by a crash are written first (see
+    /// [`Storage::recover`]). If Git sync is enabled, pulls changes from remote
+    /// before loading. Returns an empty GtdData instance if the file doesn't exist.
     ///
     /// # Returns
     /// Result containing the loaded GtdData or an error
     pub fn load(&self) -> Result<GtdData> {
+        let recovered = self.recover()?;
+        if recovered > 0 {
+            eprintln!(
+                "Recovered {} unsaved change(s) from {}",
+                recovered,
+                self.journal.path().display()
+            );
+        }
+
         // Pull from git before loading if sync is enabled
         if self.sync_git && self.git_ops.is_git_managed() {
             self.git_ops.pull()?;
         }
 
+        self.load_local()
+    }
+
+    /// Load GTD data from the storage file without pulling from Git
+    ///
+    /// Used to re-read the file after a sync has already integrated remote
+    /// changes into the working tree.
+    ///
+    /// # Returns
+    /// Result containing the loaded GtdData or an error
+    pub fn load_local(&self) -> Result<GtdData> {
+        let (data, content) = self.read_data()?;
+        self.remember_content(&content);
+        Ok(data)
+    }
+
+    /// Parse the data file without recording its content as seen
+    ///
+    /// # Returns
+    /// The data and the raw file content (empty when the file does not exist)
+    fn read_data(&self) -> Result<(GtdData, String)> {
         if !self.file_path.exists() {
-            return Ok(GtdData::new());
+            let mut data = GtdData::new();
+            data.set_case_insensitive_ids(self.case_insensitive_ids);
+            return Ok((data, String::new()));
         }
 
         let content = fs::read_to_string(&self.file_path)?;
         // Normalize line endings to LF for consistent parsing
         let normalized_content = normalize_line_endings(&content);
-        let data: GtdData = toml::from_str(&normalized_content)?;
-        Ok(data)
+        let (verification, body) = checksum::verify(&normalized_content);
+        let mismatch = matches!(verification, Verification::Mismatch { .. });
+        if mismatch {
+            eprintln!(
+                "Warning: {} was modified outside gtd-mcp or is corrupted ({})",
+                self.file_path.display(),
+                verification
+            );
+        }
+        self.ensure_current_format(&normalized_content)?;
+        let parsed = match external_notes::inline(body, &self.file_path)? {
+            Some(table) => toml::Value::Table(table).try_into::<GtdData>(),
+            None => toml::from_str::<GtdData>(&normalized_content),
+        };
+        let mut data = match parsed {
+            Ok(data) => data,
+            Err(e) => return Err(self.describe_load_error(&normalized_content, e)),
+        };
+        data.set_case_insensitive_ids(self.case_insensitive_ids);
+        if mismatch && self.validate_on_checksum_mismatch {
+            self.report_integrity(&data);
+        }
+        // Saving unchanged data would rewrite the file byte for byte - skip it.
+        // A file in a non-canonical layout or without a valid checksum stays
+        // dirty so the next save normalizes it.
+        if verification == Verification::Valid {
+            let content = Self::serialize(&data)?;
+            let on_disk = external_notes::render(&content)?.map(|rendered| rendered.content);
+            if on_disk.as_deref().unwrap_or(&content) == body {
+                data.mark_saved(&content);
+            }
+        }
+        Ok((data, content))
+    }
+
+    /// Log the integrity problems of data whose checksum did not match
+    fn report_integrity(&self, data: &GtdData) {
+        let issues = data.check_integrity();
+        if issues.is_empty() {
+            eprintln!(
+                "Validated {} after the checksum mismatch: no integrity problems found",
+                self.file_path.display()
+            );
+            return;
+        }
+        eprintln!(
+            "Validated {} after the checksum mismatch: {} integrity problem(s):",
+            self.file_path.display(),
+            issues.len()
+        );
+        for issue in &issues {
+            eprintln!("- {}", issue);
+        }
+    }
+
+    /// Check the data file's checksum header against its content
+    ///
+    /// # Returns
+    /// The verification result ([`Verification::Missing`] when the file does not exist)
+    pub fn verify_checksum(&self) -> Result<Verification> {
+        if !self.file_path.exists() {
+            return Ok(Verification::Missing);
+        }
+        let content = normalize_line_endings(&fs::read_to_string(&self.file_path)?);
+        Ok(checksum::verify(&content).0)
+    }
+
+    /// Replay saves that were journaled but never written to the data file
+    ///
+    /// A crash between a save and its (possibly debounced) write leaves the
+    /// save in the journal. The newest entry holds the complete data, so it is
+    /// written and committed with the messages of every unflushed save. An
+    /// entry whose write did reach the file before the crash is just dropped.
+    ///
+    /// # Returns
+    /// The number of saves recovered
+    pub fn recover(&self) -> Result<usize> {
+        let entries = self.journal.entries();
+        let Some(latest) = entries.last() else {
+            return Ok(0);
+        };
+        let on_disk = fs::read_to_string(&self.file_path)
+            .map(|content| normalize_line_endings(&content))
+            .unwrap_or_default();
+        if checksum::split_header(&on_disk).1 == latest.content {
+            self.journal.discard_through(latest.seq)?;
+            return Ok(0);
+        }
+        let messages: Vec<String> = entries.iter().map(|entry| entry.message.clone()).collect();
+        self.write_now(&latest.content, &summarize_commit_messages(&messages))?;
+        self.journal.discard_through(latest.seq)?;
+        Ok(entries.len())
+    }
+
+    /// Refuse to load files that still need a format migration
+    ///
+    /// Legacy files are no longer upgraded implicitly; the user runs
+    /// `gtd-mcp migrate <file>` (optionally with `--dry-run`) instead.
+    /// Unparsable documents pass through so the regular load error is reported.
+    fn ensure_current_format(&self, content: &str) -> Result<()> {
+        let Ok(changes) = migration::plan_migration(content) else {
+            return Ok(());
+        };
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut message = format!(
+            "{} uses a legacy data format and must be migrated before use:",
+            self.file_path.display()
+        );
+        for change in &changes {
+            message.push_str(&format!("\n- {}", change));
+        }
+        message.push_str(&format!(
+            "\n\nRun `gtd-mcp migrate {0}` (preview with `gtd-mcp migrate --dry-run {0}`).",
+            self.file_path.display()
+        ));
+        Err(anyhow!(message))
+    }
+
+    /// Build a detailed error for a file that failed to deserialize
+    ///
+    /// Runs schema validation to list every offending array, item ID, field, and
+    /// line. Falls back to the parser's own error (which carries line numbers for
+    /// syntax errors) when validation finds nothing more specific.
+    fn describe_load_error(&self, content: &str, error: toml::de::Error) -> anyhow::Error {
+        let issues = schema::validate_document(content);
+        if issues.is_empty() {
+            return anyhow!("Failed to load {}: {}", self.file_path.display(), error);
+        }
+        let mut message = format!(
+            "Failed to load {}: {} schema error(s) found:",
+            self.file_path.display(),
+            issues.len()
+        );
+        for issue in &issues {
+            message.push_str(&format!("\n- {}", issue));
+        }
+        message.push_str(&format!("\n\nParser error: {}", error));
+        anyhow!(message)
     }
 
     /// Save GTD data to the storage file with a default commit message
@@ -114,35 +762,392 @@ impl Storage {
     /// # Returns
     /// Result indicating success or an error
     pub fn save_with_message(&self, data: &GtdData, commit_message: &str) -> Result<()> {
-        let content = toml::to_string_pretty(data)?;
+        let content = Self::serialize(data)?;
+        self.write_content(&content, commit_message)
+    }
 
-        // Convert to OS-native line endings for file output
-        let native_content = to_native_line_endings(&content);
+    /// Serialize GTD data to the TOML representation written to disk
+    ///
+    /// This is the CPU-only half of a save. It is split out so callers can
+    /// serialize while holding the data lock and perform the I/O elsewhere.
+    ///
+    /// # Arguments
+    /// * `data` - The GtdData to serialize
+    ///
+    /// # Returns
+    /// Result containing the TOML string or an error
+    pub fn serialize(data: &GtdData) -> Result<String> {
+        Ok(toml::to_string_pretty(data)?)
+    }
+
+    /// Write already-serialized content to the storage file and sync with Git
+    ///
+    /// This is the blocking I/O half of a save. It prefixes the content with its
+    /// checksum header (see [`crate::checksum`]), converts line endings to the
+    /// OS-native format, writes the file, and commits if Git sync is enabled.
+    /// When debouncing is enabled the content is buffered instead and written
+    /// by the next flush. Either way the save is journaled first, so a crash
+    /// before the write is recovered by the next [`Storage::load`].
+    ///
+    /// # Arguments
+    /// * `content` - TOML content produced by [`Storage::serialize`]
+    /// * `commit_message` - Git commit message to use
+    ///
+    /// # Returns
+    /// Result indicating success or an error
+    pub fn write_content(&self, content: &str, commit_message: &str) -> Result<()> {
+        if let Some(debouncer) = &self.debouncer {
+            let mut pending = debouncer.pending.lock().unwrap();
+            pending.journal_seq = self.journal.append(content, commit_message)?;
+            pending.content = Some(content.to_string());
+            pending.messages.push(commit_message.to_string());
+            pending.last_write = Some(Instant::now());
+            drop(pending);
+            debouncer.start(self);
+            debouncer.wakeup.notify_all();
+            return Ok(());
+        }
+        let seq = self.journal.append(content, commit_message)?;
+        self.write_journaled(content, commit_message, seq)
+    }
+
+    /// Write a journaled save, then drop it from the journal
+    ///
+    /// A save refused with [`ExternalChange`] is dropped as well, so the next
+    /// load does not replay it over the newer file.
+    fn write_journaled(&self, content: &str, commit_message: &str, seq: u64) -> Result<()> {
+        let written = self.write_now(content, commit_message);
+        if written
+            .as_ref()
+            .is_err_and(|e| e.downcast_ref::<ExternalChange>().is_some())
+        {
+            self.journal.discard_through(seq)?;
+        }
+        written?;
+        self.journal.discard_through(seq)
+    }
+
+    /// Drop buffered (debounced) writes without writing them
+    ///
+    /// Used when the in-memory data is replaced by the file on disk, so a
+    /// pending write cannot overwrite it later.
+    pub fn discard_pending(&self) -> Result<()> {
+        let Some(debouncer) = &self.debouncer else {
+            return Ok(());
+        };
+        let _io = debouncer.io_lock.lock().unwrap();
+        let seq = {
+            let mut pending = debouncer.pending.lock().unwrap();
+            pending.content = None;
+            pending.messages.clear();
+            pending.last_write = None;
+            pending.journal_seq
+        };
+        self.journal.discard_through(seq)
+    }
+
+    /// Record the content of the data file as read or written here
+    fn remember_content(&self, content: &str) {
+        *self.known_digest.lock().unwrap() = Some(checksum::digest(content));
+    }
+
+    /// Refuse to write when the data file no longer holds what was last read
+    /// or written here
+    fn ensure_unchanged_on_disk(&self) -> Result<()> {
+        let Some(known) = self.known_digest.lock().unwrap().clone() else {
+            return Ok(());
+        };
+        let current = fs::read_to_string(&self.file_path).unwrap_or_default();
+        if checksum::digest(&current) != known {
+            return Err(ExternalChange {
+                path: self.file_path.clone(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Write content to disk and commit immediately, bypassing any debouncing
+    fn write_now(&self, content: &str, commit_message: &str) -> Result<()> {
+        self.ensure_unchanged_on_disk()?;
 
         // Ensure parent directory exists
         if let Some(parent) = self.file_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        fs::write(&self.file_path, native_content)?;
+        // Keep the previous data to tell webhooks what changed (read before
+        // the notes files are overwritten)
+        let previous = self.webhook.as_ref().map(|_| self.previous_data());
+
+        let native_content = write_files(&self.file_path, content)?;
+        self.remember_content(&native_content);
+
+        if let Err(e) = self.write_mirror(content) {
+            eprintln!("Warning: Failed to write mirror: {}", e);
+        }
 
         // Perform git operations only if sync_git flag is enabled and in a git repository
         if self.sync_git && self.git_ops.is_git_managed() {
             // Propagate git errors to the caller so they can be returned to MCP client
-            self.git_ops.sync(&self.file_path, commit_message)?;
+            let integrated = match &self.push_worker {
+                Some(worker) => {
+                    let integrated = self.commit_and_pull(commit_message)?;
+                    worker.notify_commit();
+                    integrated
+                }
+                None => self
+                    .git_ops
+                    .sync_with_outcome(&self.file_path, commit_message)?,
+            };
+            if integrated {
+                // The rebase rewrote the file; the handler reloads it
+                self.remember_content(&fs::read_to_string(&self.file_path).unwrap_or_default());
+                self.remote_updated.store(true, Ordering::SeqCst);
+            }
+        }
+
+        if let (Some(url), Some(previous)) = (&self.webhook, previous) {
+            match Self::changed_items(previous, content) {
+                Ok(changes) => webhook::notify(url, changes, commit_message),
+                Err(e) => eprintln!("Warning: Failed to send webhook: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Data currently in the file, before a write replaces it
+    ///
+    /// A missing or empty file counts as no items, so everything saved is new.
+    fn previous_data(&self) -> Result<GtdData> {
+        let previous = fs::read_to_string(&self.file_path).unwrap_or_default();
+        if previous.trim().is_empty() {
+            return Ok(GtdData::new());
+        }
+        parse_file_content(&self.file_path, &previous)
+    }
+
+    /// Items that differ between the previous data and the saved content
+    fn changed_items(previous: Result<GtdData>, content: &str) -> Result<Vec<webhook::Change>> {
+        let before = previous?;
+        let after: GtdData = toml::from_str(content)?;
+        Ok(webhook::changes(&before, &after))
+    }
+
+    /// Commit and integrate remote changes, leaving the push to the worker
+    ///
+    /// Conflicts are still reported to the caller, but an unreachable remote is
+    /// not an error: the commit stays local and the worker retries the push.
+    fn commit_and_pull(&self, commit_message: &str) -> Result<bool> {
+        self.git_ops.commit(&self.file_path, commit_message)?;
+        match self.git_ops.integrate_remote() {
+            Ok(integrated) => Ok(integrated),
+            Err(e) if e.is::<GitConflict>() => Err(e),
+            Err(e) => {
+                eprintln!("Warning: Failed to pull remote changes: {:#}", e);
+                Ok(false)
+            }
         }
+    }
 
+    /// Current state of the background push worker
+    ///
+    /// # Returns
+    /// The push status, or `None` when background pushing is not enabled
+    pub fn push_status(&self) -> Option<PushStatus> {
+        self.push_worker.as_ref().map(|worker| worker.status())
+    }
+
+    /// Count local commits not yet pushed to the remote
+    ///
+    /// # Returns
+    /// The number of unpushed commits, or `None` when Git sync is disabled or
+    /// the remote branch is unknown
+    pub fn unpushed_commits(&self) -> Result<Option<usize>> {
+        if !self.git_sync_enabled() {
+            return Ok(None);
+        }
+        self.git_ops.unpushed_commits()
+    }
+
+    /// Regenerate the mirror file (if configured) from saved TOML content
+    fn write_mirror(&self, content: &str) -> Result<()> {
+        let Some((ref path, format)) = self.mirror else {
+            return Ok(());
+        };
+        let data: GtdData = toml::from_str(content)?;
+        let rendered = to_native_line_endings(&mirror::render(&data, format, self.clock.today())?);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // The previous mirror is read-only; lift that before overwriting
+        if let Ok(metadata) = fs::metadata(path) {
+            let mut permissions = metadata.permissions();
+            if permissions.readonly() {
+                #[allow(clippy::permissions_set_readonly_false)]
+                permissions.set_readonly(false);
+                fs::set_permissions(path, permissions)?;
+            }
+        }
+        fs::write(path, rendered)?;
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(path, permissions)?;
         Ok(())
     }
 
-    /// Push changes to Git on shutdown
+    /// Check (and clear) whether a sync brought in changes from another machine
     ///
-    /// Called when the server is shutting down to ensure all local commits
-    /// are pushed to the remote repository.
+    /// When this returns `true`, the file on disk contains commits made
+    /// elsewhere, so in-memory data should be replaced via [`Storage::load_local`].
+    ///
+    /// # Returns
+    /// `true` if remote changes were integrated since the last call
+    pub fn take_remote_update(&self) -> bool {
+        self.remote_updated.swap(false, Ordering::SeqCst)
+    }
+
+    /// Write any buffered (debounced) changes to disk immediately
+    ///
+    /// Does nothing when debouncing is disabled or nothing is pending.
+    ///
+    /// # Returns
+    /// Result indicating success or an error
+    pub fn flush(&self) -> Result<()> {
+        match &self.debouncer {
+            Some(debouncer) => debouncer.flush(self),
+            None => Ok(()),
+        }
+    }
+
+    /// Take a snapshot of the saved data
+    ///
+    /// Buffered (debounced) writes are flushed first, so the snapshot holds
+    /// everything saved so far.
+    ///
+    /// # Returns
+    /// A handle for [`Storage::restore`]
+    pub fn snapshot(&self) -> Result<Snapshot> {
+        self.flush()?;
+        let (data, _) = self.read_data()?;
+        Ok(Snapshot {
+            file_path: self.file_path.clone(),
+            content: Self::serialize(&data)?,
+            item_count: data.iter().count(),
+            taken_at: self.clock.now(),
+        })
+    }
+
+    /// Write a snapshot back to the data file
+    ///
+    /// Buffered writes are dropped. Nothing is written when the file already
+    /// holds the snapshot's data; otherwise the restore is committed like any
+    /// save, so a restore can itself be undone through Git.
+    ///
+    /// # Arguments
+    /// * `snapshot` - Snapshot taken by [`Storage::snapshot`] of this file
+    ///
+    /// # Returns
+    /// The restored data, or an error if the snapshot belongs to another file
+    pub fn restore(&self, snapshot: &Snapshot) -> Result<GtdData> {
+        if snapshot.file_path != self.file_path {
+            return Err(anyhow!(
+                "The snapshot was taken of {}, not {}",
+                snapshot.file_path.display(),
+                self.file_path.display()
+            ));
+        }
+        self.discard_pending()?;
+        let (current, _) = self.read_data()?;
+        if Self::serialize(&current)? != snapshot.content {
+            let message = format!(
+                "Restore snapshot from {}",
+                snapshot.taken_at.format("%Y-%m-%d %H:%M:%S")
+            );
+            self.write_content(&snapshot.content, &message)?;
+            self.flush()?;
+        }
+        self.load_local()
+    }
+
+    /// Inspect the Git repository holding the data file
+    ///
+    /// Available whether or not Git sync is enabled.
+    ///
+    /// # Returns
+    /// The repository status, or `None` when the file is not in a Git repository
+    pub fn git_status(&self) -> Result<Option<GitStatus>> {
+        self.git_ops.status()
+    }
+
+    /// Tag the saved state as the snapshot of the week containing `date`
+    ///
+    /// Flushes buffered writes so the tag covers every change, then tags HEAD
+    /// as `weekly-YYYY-Www` (ISO week) and pushes the tag. A second snapshot in
+    /// the same week moves the tag forward.
+    ///
+    /// # Arguments
+    /// * `date` - A day in the week being tagged (usually today)
+    ///
+    /// # Returns
+    /// The tag name, or `None` when weekly snapshots or Git sync are disabled
+    pub fn tag_weekly_snapshot(&self, date: NaiveDate) -> Result<Option<String>> {
+        if !self.weekly_snapshots || !self.git_sync_enabled() {
+            return Ok(None);
+        }
+        self.flush()?;
+        let name = format!("weekly-{}", date.format("%G-W%V"));
+        self.git_ops.tag_head(
+            &name,
+            &format!("Weekly review snapshot {}", date.format("%G-W%V")),
+        )?;
+        Ok(Some(name))
+    }
+
+    /// Synchronize with the Git remote on demand
+    ///
+    /// Flushes buffered (debounced) writes, then fetches, rebases onto remote
+    /// commits, and pushes everything not yet on the remote. When the report
+    /// lists received commits, reload the data with [`Storage::load_local`].
+    ///
+    /// # Arguments
+    /// * `cancellation` - Stops the sync before the next fetch or push (see
+    ///   [`GitOps::sync_remote_cancellable`])
+    ///
+    /// # Returns
+    /// The commits received and sent, or an error (also when Git sync is disabled)
+    pub fn sync(&self, cancellation: &Cancellation) -> Result<SyncReport> {
+        if !self.git_sync_enabled() {
+            return Err(anyhow!(
+                "Git sync is disabled. Start the server with --sync-git (in a Git repository with an 'origin' remote)."
+            ));
+        }
+        self.flush()?;
+        let report = self.git_ops.sync_remote_cancellable(cancellation)?;
+        if let Some(worker) = &self.push_worker {
+            worker.record_push();
+        }
+        Ok(report)
+    }
+
+    /// Flush pending writes and push changes to Git on shutdown
+    ///
+    /// Called when the server is shutting down to ensure buffered writes reach
+    /// the disk and all local commits are pushed to the remote repository.
     ///
     /// # Returns
     /// Result indicating success or an error
     pub fn shutdown(&self) -> Result<()> {
+        self.flush()?;
+        if let Some(debouncer) = &self.debouncer {
+            debouncer.pending.lock().unwrap().stopped = true;
+            debouncer.wakeup.notify_all();
+        }
+        if let Some(worker) = &self.push_worker {
+            worker.stop();
+        }
         if self.sync_git && self.git_ops.is_git_managed() {
             self.git_ops.push()?;
         }
@@ -0,0 +1,293 @@
+//! Ranking next actions for the "Do" phase
+//!
+//! The `suggest` tool answers "what should I do now?". Next actions that
+//! cannot be done in the current situation are left out:
+//!
+//! - another context than the one given (items without a context fit anywhere)
+//! - an estimate longer than the minutes available
+//! - more energy than the energy available
+//! - a start date in the future
+//!
+//! The rest are scored, and the reasons behind each score are returned with
+//! it:
+//!
+//! | Signal | Points |
+//! |---|---|
+//! | Priority | high 30, medium 15, none 10, low 0 |
+//! | Due date | overdue 50, today 45, within 3 days 35, within a week 20, later 5 |
+//! | Age | 2 per full week since capture, at most 20 |
+//! | Fit | 10 for the exact context, 5 for a known estimate that fits, 5 for the exact energy |
+//!
+//! When minutes are given, items without an estimate lose 5 points, so items
+//! known to fit come first. Items whose context has an availability schedule
+//! (see [`crate::availability`]) that is closed at the current time lose 40
+//! points, unless the user said they are in that context. Next actions without
+//! a context use the context of their project.
+//!
+//! Waiting-for items that waited past their escalation threshold (see
+//! [`crate::escalation`]) are ranked with the next actions, with 30 extra
+//! points and an "escalate" reason, since following them up is an action too.
+
+use crate::availability::Availability;
+use crate::escalation;
+use crate::gtd::{Energy, GtdData, Nota, NotaStatus, Priority};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::cmp::Reverse;
+
+/// Number of suggestions returned when no limit is given
+pub const DEFAULT_LIMIT: usize = 5;
+
+/// Most points given for the age of an item
+const MAX_AGE_POINTS: i64 = 20;
+
+/// Points taken from items whose context is not available right now
+const UNAVAILABLE_PENALTY: i64 = 40;
+
+/// Points added to waiting items due for escalation
+const ESCALATION_POINTS: i64 = 30;
+
+/// The situation the user is in
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// Context the user is in (e.g. "@home")
+    pub context: Option<String>,
+    /// Minutes available
+    pub minutes: Option<u32>,
+    /// Energy available
+    pub energy: Option<Energy>,
+    /// Local date and time to check context availability at (not checked when `None`)
+    pub now: Option<NaiveDateTime>,
+    /// Days a waiting item may wait before it is escalated, unless it has its own threshold
+    pub escalate_after_days: Option<u32>,
+}
+
+impl Constraints {
+    /// Short description such as "@home, 30 min, low energy"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(context) = &self.context {
+            parts.push(context.clone());
+        }
+        if let Some(minutes) = self.minutes {
+            parts.push(format!("{} min", minutes));
+        }
+        if let Some(energy) = self.energy {
+            parts.push(format!("{:?} energy", energy));
+        }
+        parts.join(", ")
+    }
+}
+
+/// A next action with its score and the reasons for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub nota: Nota,
+    pub score: i64,
+    pub reasons: Vec<String>,
+}
+
+/// Result of ranking the next actions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ranking {
+    /// Best suggestions first, at most the requested number
+    pub suggestions: Vec<Suggestion>,
+    /// Next actions that fit the constraints
+    pub fitting: usize,
+    /// Next actions left out because they do not fit
+    pub skipped: usize,
+}
+
+/// Rank the next actions of `data` (and waiting items due for escalation) that fit `constraints`
+///
+/// # Arguments
+/// * `data` - The data to rank
+/// * `constraints` - The user's situation
+/// * `today` - The current date
+/// * `limit` - Number of suggestions to return
+pub fn rank(data: &GtdData, constraints: &Constraints, today: NaiveDate, limit: usize) -> Ranking {
+    let mut ranking = Ranking::default();
+    let mut suggestions = Vec::new();
+    for nota in data.iter() {
+        let overdue = escalation::overdue(nota, constraints.escalate_after_days, today);
+        if nota.status != NotaStatus::next_action && overdue.is_none() {
+            continue;
+        }
+        let inherited;
+        let nota = match (&nota.context, data.effective_context(nota)) {
+            (None, Some(context)) => {
+                inherited = Nota {
+                    context: Some(context.to_string()),
+                    ..nota.clone()
+                };
+                &inherited
+            }
+            _ => nota,
+        };
+        match score(nota, constraints, today) {
+            Some(mut suggestion) => {
+                if let Some(overdue) = &overdue {
+                    suggestion.score += ESCALATION_POINTS;
+                    suggestion.reasons.insert(0, overdue.reason());
+                }
+                if let Some(closed) = unavailable_context(data, nota, constraints) {
+                    suggestion.score -= UNAVAILABLE_PENALTY;
+                    suggestion.reasons.push(closed);
+                }
+                suggestions.push(suggestion)
+            }
+            None => ranking.skipped += 1,
+        }
+    }
+    ranking.fitting = suggestions.len();
+    suggestions.sort_by_key(|s| {
+        (
+            Reverse(s.score),
+            s.nota.due_date.unwrap_or(NaiveDate::MAX),
+            s.nota.created_at,
+            s.nota.id.clone(),
+        )
+    });
+    suggestions.truncate(limit);
+    ranking.suggestions = suggestions;
+    ranking
+}
+
+/// Next actions that certainly fit a situation, for the `doable` tool
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Doable {
+    /// Actions whose estimate, energy and context all fit, best first
+    pub actions: Vec<Suggestion>,
+    /// Actions left out because they lack the estimate or energy to check
+    pub unknown: usize,
+    /// Actions left out because they do not fit
+    pub skipped: usize,
+}
+
+/// Next actions that certainly fit `constraints`
+///
+/// Unlike [`rank`], which only ranks actions of unknown fit lower, an action is
+/// kept only when the fields checked against the given constraints are set:
+/// with minutes given it needs an estimate, with energy given an energy
+/// level. Actions without a context fit any context. Waiting items are left out.
+///
+/// # Arguments
+/// * `data` - The data to check
+/// * `constraints` - The user's situation
+/// * `today` - The current date
+pub fn doable(data: &GtdData, constraints: &Constraints, today: NaiveDate) -> Doable {
+    let constraints = Constraints {
+        escalate_after_days: None,
+        ..constraints.clone()
+    };
+    let ranking = rank(data, &constraints, today, usize::MAX);
+    let mut doable = Doable {
+        skipped: ranking.skipped,
+        ..Default::default()
+    };
+    for suggestion in ranking.suggestions {
+        let nota = &suggestion.nota;
+        if nota.status != NotaStatus::next_action {
+            continue;
+        }
+        if (constraints.minutes.is_some() && nota.estimate.is_none())
+            || (constraints.energy.is_some() && nota.energy.is_none())
+        {
+            doable.unknown += 1;
+        } else {
+            doable.actions.push(suggestion);
+        }
+    }
+    doable
+}
+
+/// Why the context of `nota` cannot be used now, if its schedule is closed
+///
+/// Not checked for the context the user said they are in, or without a time.
+fn unavailable_context(data: &GtdData, nota: &Nota, constraints: &Constraints) -> Option<String> {
+    let now = constraints.now?;
+    let context = nota.context.as_deref()?;
+    if constraints.context.as_deref() == Some(context) {
+        return None;
+    }
+    let availability: Availability = data
+        .find_context_by_name(context)?
+        .availability
+        .as_deref()?
+        .parse()
+        .ok()?;
+    (!availability.is_available(now))
+        .then(|| format!("{} not available now ({})", context, availability))
+}
+
+/// Score one next action, or `None` if it does not fit the constraints
+pub fn score(nota: &Nota, constraints: &Constraints, today: NaiveDate) -> Option<Suggestion> {
+    if nota.start_date.is_some_and(|start| start > today) {
+        return None;
+    }
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    // Fit to the situation
+    if let Some(context) = &constraints.context {
+        match &nota.context {
+            Some(own) if own == context => {
+                score += 10;
+                reasons.push(format!("at {}", context));
+            }
+            Some(_) => return None,
+            None => {}
+        }
+    }
+    match (constraints.minutes, nota.estimate) {
+        (Some(available), Some(estimate)) if estimate > available => return None,
+        (Some(_), Some(estimate)) => {
+            score += 5;
+            reasons.push(format!("takes {} min", estimate));
+        }
+        (Some(_), None) => score -= 5,
+        (None, _) => {}
+    }
+    if let (Some(available), Some(energy)) = (constraints.energy, nota.energy) {
+        if energy > available {
+            return None;
+        }
+        if energy == available {
+            score += 5;
+            reasons.push(format!("{:?} energy", energy));
+        }
+    }
+
+    // Importance and urgency
+    match nota.priority {
+        Some(Priority::high) => {
+            score += 30;
+            reasons.push("high priority".to_string());
+        }
+        Some(Priority::medium) => score += 15,
+        None => score += 10,
+        Some(Priority::low) => {}
+    }
+    if let Some(due) = nota.due_date {
+        let days = (due - today).num_days();
+        let (points, reason) = match days {
+            ..0 => (50, format!("overdue by {} day(s) ({})", -days, due)),
+            0 => (45, "due today".to_string()),
+            1..=3 => (35, format!("due in {} day(s) ({})", days, due)),
+            4..=7 => (20, format!("due in {} days ({})", days, due)),
+            _ => (5, format!("due {}", due)),
+        };
+        score += points;
+        reasons.push(reason);
+    }
+    let age = (today - nota.created_at).num_days();
+    if age >= 7 {
+        score += (age / 7 * 2).min(MAX_AGE_POINTS);
+        reasons.push(format!("open for {} days", age));
+    }
+
+    Some(Suggestion {
+        nota: nota.clone(),
+        score,
+        reasons,
+    })
+}
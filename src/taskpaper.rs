@@ -0,0 +1,358 @@
+//! TaskPaper import
+//!
+//! Reads [TaskPaper](https://guide.taskpaper.com/getting-started/) outlines,
+//! the plain-text format exported by Things and OmniFocus:
+//!
+//! ```text
+//! Website:
+//!     - Draft the proposal @computer @flagged
+//!         Ask Anna for last year's numbers
+//!     - Launch @due(2025-03-10)
+//!     - Book the domain @done(2025-03-02)
+//! Inbox:
+//!     - Call mom @phone
+//! ```
+//!
+//! - A line ending with `:` is a project; the tasks (`- ` lines) indented
+//!   under it belong to it, subtasks included
+//! - `Inbox:` and `Someday:` headings give their tasks that status instead of
+//!   creating a project
+//! - Other lines are notes of the project or task above them
+//! - `@done` (optionally with a date) marks done items, `@due`, `@defer` or
+//!   `@start` with a date make calendar items, `@today`, `@flagged` and
+//!   `@next` next actions, and `@id(...)` names the item
+//! - Any other plain `@tag` is the context (the last one of a line; earlier
+//!   ones and tags with values stay in the title); unknown contexts are
+//!   created
+//!
+//! Other tasks are next actions inside a project and inbox items outside one.
+//! Items are matched by `@id`, or else by title (and project, for tasks), so
+//! importing an updated export updates the items instead of duplicating them.
+//! Fields the outline does not mention keep their stored values.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::import::{ImportSummary, ensure_context};
+use crate::slug;
+use anyhow::{Result, anyhow, bail};
+use chrono::NaiveDate;
+
+/// Columns a tab counts for when comparing indentation
+const TAB_WIDTH: usize = 4;
+
+/// A word or `@tag` of a line
+#[derive(Debug)]
+enum Token<'a> {
+    Word(&'a str),
+    Tag {
+        text: &'a str,
+        name: &'a str,
+        value: Option<&'a str>,
+    },
+}
+
+impl<'a> Token<'a> {
+    fn text(&self) -> &'a str {
+        match self {
+            Token::Word(text) | Token::Tag { text, .. } => text,
+        }
+    }
+}
+
+/// Split a line into words and tags; a tag value may contain spaces
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let tag = rest.strip_prefix('@').and_then(|after| {
+            let name_len = after
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or(after.len());
+            if name_len == 0 {
+                return None;
+            }
+            let mut len = 1 + name_len;
+            let mut value = None;
+            if let Some(open) = after[name_len..].strip_prefix('(') {
+                let close = open.find(')')?;
+                value = Some(open[..close].trim());
+                len += close + 2;
+            }
+            if !rest[len..].is_empty() && !rest[len..].starts_with(char::is_whitespace) {
+                return None;
+            }
+            Some(Token::Tag {
+                text: &rest[..len],
+                name: &after[..name_len],
+                value,
+            })
+        });
+        let token = tag.unwrap_or_else(|| {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            Token::Word(&rest[..end])
+        });
+        rest = rest[token.text().len()..].trim_start();
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// What a line of the outline is
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Project,
+    /// `Inbox:` or `Someday:`, giving its tasks a status
+    Heading(NotaStatus),
+    Task,
+}
+
+/// One parsed project, heading or task
+#[derive(Debug)]
+struct Entry {
+    kind: Kind,
+    title: String,
+    done: Option<NaiveDate>,
+    start: Option<NaiveDate>,
+    next: bool,
+    id: Option<String>,
+    context: Option<String>,
+    notes: Vec<String>,
+    /// Index of the enclosing project or heading
+    parent: Option<usize>,
+}
+
+/// Status given by a heading title, if it is one
+fn heading_status(title: &str) -> Option<NotaStatus> {
+    match title.to_lowercase().as_str() {
+        "inbox" => Some(NotaStatus::inbox),
+        "someday" | "someday/maybe" | "someday maybe" => Some(NotaStatus::someday),
+        _ => None,
+    }
+}
+
+/// Parse a tag value as a date, ignoring a time after it
+fn tag_date(name: &str, value: Option<&str>) -> Result<NaiveDate> {
+    let value =
+        value.ok_or_else(|| anyhow!("@{} needs a date, e.g. @{}(2025-03-10)", name, name))?;
+    let day = value.split_whitespace().next().unwrap_or_default();
+    NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .map_err(|_| anyhow!("@{}({}) is not a YYYY-MM-DD date", name, value))
+}
+
+/// Parse a project or task line (without the `- ` of a task)
+fn parse_entry(text: &str, task: bool, today: NaiveDate) -> Result<Entry> {
+    let mut entry = Entry {
+        kind: Kind::Task,
+        title: String::new(),
+        done: None,
+        start: None,
+        next: false,
+        id: None,
+        context: None,
+        notes: Vec::new(),
+        parent: None,
+    };
+    let mut words: Vec<&str> = Vec::new();
+    let mut context_index = None;
+    for token in tokenize(text) {
+        let Token::Tag { text, name, value } = token else {
+            words.push(token.text());
+            continue;
+        };
+        match name.to_lowercase().as_str() {
+            "done" => {
+                entry.done = Some(match value {
+                    Some(_) => tag_date(name, value)?,
+                    None => today,
+                })
+            }
+            "due" | "defer" | "start" => entry.start = Some(tag_date(name, value)?),
+            "today" | "flagged" | "next" if value.is_none() => entry.next = true,
+            "id" if value.is_some_and(|v| !v.is_empty()) => entry.id = value.map(str::to_string),
+            _ if value.is_none() => {
+                // The last plain tag is the context; earlier ones stay in the title
+                context_index = Some(words.len());
+                words.push(text);
+            }
+            _ => words.push(text),
+        }
+    }
+    if let Some(index) = context_index {
+        entry.context = Some(words.remove(index).to_string());
+    }
+    entry.title = words.join(" ");
+    if !task {
+        entry.title = entry.title.trim_end_matches(':').trim_end().to_string();
+        entry.kind = match heading_status(&entry.title) {
+            Some(status) => Kind::Heading(status),
+            None => Kind::Project,
+        };
+    }
+    if entry.title.is_empty() {
+        bail!("the {} has no text", if task { "task" } else { "project" });
+    }
+    Ok(entry)
+}
+
+/// Whether a line (without tags) ends with `:`, making it a project
+fn is_project_line(text: &str) -> bool {
+    tokenize(text)
+        .iter()
+        .rev()
+        .find(|token| matches!(token, Token::Word(_)))
+        .is_some_and(|token| token.text().ends_with(':'))
+}
+
+/// Width of the leading whitespace of a line
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Parse an outline into its projects, headings and tasks
+fn parse(text: &str, today: NaiveDate) -> Result<Vec<Entry>> {
+    let mut entries: Vec<Entry> = Vec::new();
+    // Open entries as (indent, index), innermost last
+    let mut outline: Vec<(usize, usize)> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let body = line.trim();
+        if body.is_empty() {
+            continue;
+        }
+        let indent = indent_width(line);
+        while outline.last().is_some_and(|(open, _)| *open >= indent) {
+            outline.pop();
+        }
+        let error = |e: anyhow::Error| anyhow!("line {}: {}", index + 1, e);
+
+        let task = body.strip_prefix("- ").or((body == "-").then_some(""));
+        if task.is_none() && !is_project_line(body) {
+            let &(_, owner) = outline
+                .last()
+                .ok_or_else(|| error(anyhow!("note '{}' is outside any project or task", body)))?;
+            entries[owner].notes.push(body.to_string());
+            continue;
+        }
+        let mut entry = parse_entry(task.unwrap_or(body), task.is_some(), today).map_err(error)?;
+        entry.parent = outline
+            .iter()
+            .rev()
+            .map(|&(_, open)| open)
+            .find(|&open| entries[open].kind != Kind::Task);
+        outline.push((indent, entries.len()));
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Import a TaskPaper outline into `data`
+///
+/// Stops at the first invalid line without changing `data`, but a valid
+/// outline is applied even if it breaks links; use [`crate::import::import`]
+/// to check them.
+///
+/// # Arguments
+/// * `data` - The data to import into
+/// * `text` - TaskPaper outline
+/// * `today` - Date for new items and `@done` without a date
+pub fn import(data: &mut GtdData, text: &str, today: NaiveDate) -> Result<ImportSummary> {
+    let entries = parse(text, today)?;
+    let mut summary = ImportSummary::default();
+    // Stored ID of each imported project, by entry index
+    let mut project_ids: Vec<Option<String>> = vec![None; entries.len()];
+
+    for (index, entry) in entries.iter().enumerate() {
+        let (project, heading) = match entry.parent.map(|p| (p, &entries[p].kind)) {
+            Some((p, Kind::Project)) => (project_ids[p].clone(), None),
+            Some((_, Kind::Heading(status))) => (None, Some(status.clone())),
+            _ => (None, None),
+        };
+        let is_project = match entry.kind {
+            Kind::Heading(_) => continue,
+            Kind::Project => true,
+            Kind::Task => false,
+        };
+
+        // Match by @id, else by title among the items this import did not add
+        let existing = match &entry.id {
+            Some(id) => data.resolve_id(id).and_then(|id| data.find_by_id(id)),
+            None => data
+                .iter()
+                .find(|n| {
+                    n.title == entry.title
+                        && n.is_project() == is_project
+                        && !n.is_context()
+                        && (is_project || n.project == project)
+                        && !summary.added.contains(&n.id)
+                })
+                .cloned(),
+        };
+        let mut nota = match &existing {
+            Some(nota) => nota.clone(),
+            None => Nota {
+                id: match &entry.id {
+                    Some(id) => id.clone(),
+                    None => slug::unique_id(data, &entry.title),
+                },
+                created_at: today,
+                updated_at: today,
+                ..Default::default()
+            },
+        };
+        nota.title = entry.title.clone();
+        if !entry.notes.is_empty() {
+            nota.notes = Some(entry.notes.join("\n"));
+        }
+        if is_project {
+            nota.status = NotaStatus::project;
+            project_ids[index] = Some(nota.id.clone());
+        } else {
+            nota.project = project.clone();
+            if let Some(tag) = &entry.context {
+                nota.context = Some(ensure_context(data, tag, today, &mut summary));
+            }
+            if let Some(start) = entry.start {
+                nota.start_date = Some(start);
+            }
+            nota.status = if entry.done.is_some() {
+                NotaStatus::done
+            } else if entry.start.is_some() {
+                NotaStatus::calendar
+            } else if entry.next {
+                NotaStatus::next_action
+            } else if let Some(before) = &existing {
+                if before.status == NotaStatus::done {
+                    // A task no longer marked @done is open again
+                    heading.unwrap_or(NotaStatus::inbox)
+                } else {
+                    before.status.clone()
+                }
+            } else if let Some(status) = heading {
+                status
+            } else if project.is_some() {
+                NotaStatus::next_action
+            } else {
+                NotaStatus::inbox
+            };
+        }
+
+        match existing {
+            Some(before) if before == nota => summary.unchanged += 1,
+            Some(_) => {
+                nota.updated_at = entry.done.unwrap_or(today);
+                nota.created_at = nota.created_at.min(nota.updated_at);
+                summary.updated.push(nota.id.clone());
+                data.update(&nota.id.clone(), nota);
+            }
+            None => {
+                nota.updated_at = entry.done.unwrap_or(today);
+                // An item finished before today was created no later than that
+                nota.created_at = nota.created_at.min(nota.updated_at);
+                summary.added.push(nota.id.clone());
+                data.add(nota);
+            }
+        }
+    }
+    Ok(summary)
+}
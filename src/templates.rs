@@ -0,0 +1,209 @@
+//! Reusable checklist templates
+//!
+//! A template is a nota with status `template` (e.g. "Travel packing",
+//! "Release process"). Its notes hold the checklist, one item per list line
+//! (`- item`, `- [ ] item`, `* item` or `1. item`); other lines describe the
+//! template and are ignored. `instantiate_template` stamps out one next action
+//! per item, with an ID made from the new ID and the item text, and the
+//! template's project and context.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::slug;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Text of a checklist line (`- item`, `- [ ] item`, `* item` or `1. item`),
+/// or `None` when the line is not a list line
+fn list_item(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| {
+            let digits = line.find(|c: char| !c.is_ascii_digit())?;
+            (digits > 0)
+                .then(|| line[digits..].strip_prefix(". "))
+                .flatten()
+        })?;
+    let rest = rest.trim_start();
+    Some(
+        ["[ ]", "[x]", "[X]"]
+            .iter()
+            .find_map(|box_| rest.strip_prefix(box_))
+            .unwrap_or(rest)
+            .trim(),
+    )
+}
+
+/// Checklist items of a template, in order
+///
+/// # Arguments
+/// * `notes` - Notes of the template
+pub fn checklist(notes: &str) -> Vec<String> {
+    notes
+        .lines()
+        .filter_map(list_item)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The lines of a template's notes that are not checklist items
+///
+/// # Arguments
+/// * `notes` - Notes of the template
+pub fn description(notes: &str) -> String {
+    notes
+        .lines()
+        .filter(|line| list_item(line).is_none())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Placeholder names (`{client}`) used in `text`, in order of first use
+///
+/// # Arguments
+/// * `text` - Text to scan
+pub fn placeholders(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && !names.iter().any(|known| known == name)
+        {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Replace each `{name}` in `text` with its value from `params`
+///
+/// Placeholders without a value are left as they are.
+///
+/// # Arguments
+/// * `text` - Text with placeholders
+/// * `params` - Placeholder values by name
+pub fn fill(text: &str, params: &HashMap<String, String>) -> String {
+    params.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// Add one next action per checklist item of `template` to `data`
+///
+/// IDs are `<new_id>-<item slug>`, with a numeric suffix when taken.
+///
+/// # Arguments
+/// * `data` - Data to add the tasks to
+/// * `template` - The template nota
+/// * `new_id` - Prefix of the new task IDs
+/// * `today` - Creation date of the tasks
+///
+/// # Returns
+/// The tasks added, in checklist order
+pub fn instantiate(
+    data: &mut GtdData,
+    template: &Nota,
+    new_id: &str,
+    today: NaiveDate,
+) -> Vec<Nota> {
+    let items = checklist(template.notes.as_deref().unwrap_or(""));
+    add_tasks(
+        data,
+        items,
+        new_id,
+        template.project.clone(),
+        template.context.clone(),
+        today,
+    )
+}
+
+/// Create a project from `template`, with one next action per checklist item
+///
+/// `{name}` placeholders in the title, description and items are replaced
+/// from `params`. The project takes the template's title and description;
+/// the project and its tasks take the template's context.
+///
+/// # Arguments
+/// * `data` - Data to add the project and tasks to
+/// * `template` - The template nota
+/// * `project_id` - ID of the new project, also the prefix of the task IDs
+/// * `params` - Placeholder values by name
+/// * `today` - Creation date
+///
+/// # Returns
+/// The project followed by its tasks, in checklist order
+pub fn new_project(
+    data: &mut GtdData,
+    template: &Nota,
+    project_id: &str,
+    params: &HashMap<String, String>,
+    today: NaiveDate,
+) -> Vec<Nota> {
+    let notes = template.notes.as_deref().unwrap_or("");
+    let description = fill(&description(notes), params);
+    let project = Nota {
+        id: project_id.to_string(),
+        title: fill(&template.title, params),
+        status: NotaStatus::project,
+        context: template.context.clone(),
+        notes: (!description.is_empty()).then_some(description),
+        created_at: today,
+        updated_at: today,
+        ..Default::default()
+    };
+    data.add(project.clone());
+
+    let items = checklist(notes)
+        .iter()
+        .map(|item| fill(item, params))
+        .collect();
+    let mut created = vec![project];
+    created.extend(add_tasks(
+        data,
+        items,
+        project_id,
+        Some(project_id.to_string()),
+        template.context.clone(),
+        today,
+    ));
+    created
+}
+
+/// Add one next action per item, with IDs made from `prefix` and the item
+fn add_tasks(
+    data: &mut GtdData,
+    items: Vec<String>,
+    prefix: &str,
+    project: Option<String>,
+    context: Option<String>,
+    today: NaiveDate,
+) -> Vec<Nota> {
+    let mut created = Vec::new();
+    for item in items {
+        let nota = Nota {
+            id: slug::unique_id(data, &format!("{} {}", prefix, item)),
+            title: item,
+            status: NotaStatus::next_action,
+            project: project.clone(),
+            context: context.clone(),
+            created_at: today,
+            updated_at: today,
+            ..Default::default()
+        };
+        data.add(nota.clone());
+        created.push(nota);
+    }
+    created
+}
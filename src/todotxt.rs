@@ -0,0 +1,256 @@
+//! todo.txt export and import
+//!
+//! Maps items to the [todo.txt format](https://github.com/todotxt/todo.txt):
+//!
+//! ```text
+//! x 2025-03-02 2025-03-01 Call mom +family @phone id:call-mom
+//! (A) 2025-03-01 Draft the proposal +website @computer id:draft-proposal
+//! 2025-03-01 Dentist due:2025-03-10 status:calendar id:dentist
+//! ```
+//!
+//! - `x <completion date>` marks done items; the completion date is `updated_at`
+//! - Priority marks next actions: they are written as `(A)`, and any priority
+//!   makes an imported line a next action
+//! - The creation date is `created_at`
+//! - `+project` and `@context` are the item's project and context (the last
+//!   of each on a line; earlier ones stay in the title); unknown ones are
+//!   created on import
+//! - `due:` is the start date, `id:` the item ID (generated from the title
+//!   when missing) and `status:` any status without a todo.txt marker
+//!
+//! Only tasks are written: projects and contexts appear as tags, while
+//! reference and trashed items are left out. Notes have no place in todo.txt
+//! and are kept as they are on import.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::import::{ImportSummary, ensure_context, ensure_item};
+use crate::slug;
+use anyhow::{Result, anyhow, bail};
+use chrono::NaiveDate;
+use std::path::Path;
+
+/// Whether a file is named like a todo.txt list (`todo.txt` or `*.todo.txt`)
+pub fn is_todo_txt_path(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.to_ascii_lowercase())
+        .is_some_and(|name| name == "todo.txt" || name.ends_with(".todo.txt"))
+}
+
+/// Render the tasks of `data` as todo.txt lines
+///
+/// # Arguments
+/// * `data` - The GtdData to render
+pub fn render(data: &GtdData) -> String {
+    let mut out = String::new();
+    for nota in data.iter() {
+        if !nota.is_task() || matches!(nota.status, NotaStatus::reference | NotaStatus::trash) {
+            continue;
+        }
+        let mut parts = Vec::new();
+        match nota.status {
+            NotaStatus::done => {
+                parts.push("x".to_string());
+                parts.push(nota.updated_at.to_string());
+            }
+            NotaStatus::next_action => parts.push("(A)".to_string()),
+            _ => {}
+        }
+        parts.push(nota.created_at.to_string());
+        parts.push(nota.title.split_whitespace().collect::<Vec<_>>().join(" "));
+        if let Some(project) = &nota.project {
+            parts.push(format!("+{}", project));
+        }
+        if let Some(context) = &nota.context {
+            parts.push(if context.starts_with('@') {
+                context.clone()
+            } else {
+                format!("@{}", context)
+            });
+        }
+        if let Some(date) = nota.start_date {
+            parts.push(format!("due:{}", date));
+        }
+        if !matches!(
+            nota.status,
+            NotaStatus::inbox | NotaStatus::next_action | NotaStatus::done
+        ) {
+            parts.push(format!("status:{:?}", nota.status));
+        }
+        parts.push(format!("id:{}", nota.id));
+        out.push_str(&parts.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+/// One parsed todo.txt line
+#[derive(Debug, Default)]
+struct Line {
+    done: bool,
+    priority: bool,
+    completed: Option<NaiveDate>,
+    created: Option<NaiveDate>,
+    title: String,
+    project: Option<String>,
+    context: Option<String>,
+    due: Option<NaiveDate>,
+    id: Option<String>,
+    status: Option<NotaStatus>,
+}
+
+/// Parse a `YYYY-MM-DD` token
+fn date_token(token: Option<&&str>) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(token?, "%Y-%m-%d").ok()
+}
+
+/// Parse one non-empty line
+fn parse_line(text: &str) -> Result<Line> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut line = Line::default();
+    let mut pos = 0;
+
+    if tokens.first() == Some(&"x") {
+        line.done = true;
+        pos = 1;
+        if let Some(date) = date_token(tokens.get(pos)) {
+            line.completed = Some(date);
+            pos += 1;
+        }
+    } else if let Some(token) = tokens.first()
+        && token.len() == 3
+        && token.starts_with('(')
+        && token.ends_with(')')
+        && token.as_bytes()[1].is_ascii_uppercase()
+    {
+        line.priority = true;
+        pos = 1;
+    }
+    if let Some(date) = date_token(tokens.get(pos)) {
+        line.created = Some(date);
+        pos += 1;
+    }
+
+    // The last +project and @context are the item's own (export appends them);
+    // earlier ones stay part of the title
+    let rest = &tokens[pos..];
+    let last_project = rest
+        .iter()
+        .rposition(|token| token.len() > 1 && token.starts_with('+'));
+    let last_context = rest
+        .iter()
+        .rposition(|token| token.len() > 1 && token.starts_with('@'));
+    let mut words = Vec::new();
+    for (index, token) in rest.iter().enumerate() {
+        if Some(index) == last_project {
+            line.project = Some(token[1..].to_string());
+        } else if Some(index) == last_context {
+            line.context = Some(token.to_string());
+        } else if let Some(value) = token.strip_prefix("due:") {
+            line.due = Some(
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|_| anyhow!("due '{}' is not a YYYY-MM-DD date", value))?,
+            );
+        } else if let Some(value) = token.strip_prefix("id:").filter(|v| !v.is_empty()) {
+            line.id = Some(value.to_string());
+        } else if let Some(value) = token.strip_prefix("status:") {
+            line.status = Some(value.parse::<NotaStatus>().map_err(|e| anyhow!(e))?);
+        } else {
+            words.push(*token);
+        }
+    }
+    line.title = words.join(" ");
+    if line.title.is_empty() {
+        bail!("the task has no text");
+    }
+    Ok(line)
+}
+
+/// Import todo.txt lines into `data`: lines with a known `id:` update that item, others are added
+///
+/// Projects and contexts named by the lines are created when missing. Stops
+/// at the first invalid line, leaving the lines before it applied; use
+/// [`crate::import::import`] to import all or nothing.
+///
+/// # Arguments
+/// * `data` - The data to import into
+/// * `text` - todo.txt content
+/// * `today` - Date used for dates the line does not carry
+pub fn import(data: &mut GtdData, text: &str, today: NaiveDate) -> Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+    for (index, text) in text.lines().enumerate() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let line = parse_line(text).map_err(|e| anyhow!("line {}: {}", index + 1, e))?;
+
+        let project = line
+            .project
+            .as_deref()
+            .map(|id| ensure_item(data, id, NotaStatus::project, today, &mut summary));
+        let context = line
+            .context
+            .as_deref()
+            .map(|tag| ensure_context(data, tag, today, &mut summary));
+
+        let existing = line
+            .id
+            .as_deref()
+            .and_then(|id| data.resolve_id(id))
+            .and_then(|id| data.find_by_id(id));
+        let mut nota = match &existing {
+            Some(nota) => nota.clone(),
+            None => Nota {
+                id: match &line.id {
+                    Some(id) => id.clone(),
+                    None => slug::unique_id(data, &line.title),
+                },
+                created_at: today,
+                updated_at: today,
+                ..Default::default()
+            },
+        };
+        nota.title = line.title.clone();
+        nota.project = project;
+        nota.context = context;
+        nota.start_date = line.due;
+        if let Some(created) = line.created {
+            nota.created_at = created;
+        }
+        let status = if let Some(status) = line.status {
+            Some(status)
+        } else if line.done {
+            Some(NotaStatus::done)
+        } else if line.priority {
+            Some(NotaStatus::next_action)
+        } else if line.due.is_some() {
+            Some(NotaStatus::calendar)
+        } else if existing
+            .as_ref()
+            .is_some_and(|n| n.status == NotaStatus::done)
+        {
+            // An "x" removed on the phone reopens the item
+            Some(NotaStatus::inbox)
+        } else {
+            None
+        };
+        if let Some(status) = status {
+            nota.set_status(status, today);
+        }
+
+        match existing {
+            Some(before) if before == nota => summary.unchanged += 1,
+            Some(_) => {
+                nota.updated_at = line.completed.unwrap_or(today);
+                summary.updated.push(nota.id.clone());
+                data.update(&nota.id.clone(), nota);
+            }
+            None => {
+                nota.updated_at = line.completed.unwrap_or(today);
+                summary.added.push(nota.id.clone());
+                data.add(nota);
+            }
+        }
+    }
+    Ok(summary)
+}
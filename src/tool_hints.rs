@@ -0,0 +1,163 @@
+//! Safety hints for MCP tools
+//!
+//! `#[mcp_server]` lists every tool without annotations, so [`annotate`] adds
+//! them before the list reaches the client. Clients use the hints to run
+//! read-only tools freely and to ask the user before a destructive call.
+//!
+//! Destructive here means the tool deletes items or drops changes that no
+//! other tool call can bring back (`empty_trash`, `dedupe`, `abort`, `reload`,
+//! `restore_backup`). Moving an item to the trash is not destructive, since
+//! `restore` undoes it.
+
+use mcp_attr::schema::{ListToolsResult, ToolAnnotations};
+
+/// What a tool does to the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Only reads the data
+    ReadOnly,
+    /// Adds items or state without changing existing items
+    Additive,
+    /// Changes existing items in a way another tool call can undo
+    Update,
+    /// Deletes items or drops changes for good
+    Destructive,
+}
+
+/// Hints for one tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolHint {
+    /// What the tool does to the data
+    pub effect: Effect,
+    /// Calling it again with the same arguments changes nothing more
+    pub idempotent: bool,
+    /// It talks to services outside the data file (Git remotes, CalDAV, GitHub)
+    pub open_world: bool,
+}
+
+const fn hint(effect: Effect, idempotent: bool) -> ToolHint {
+    ToolHint {
+        effect,
+        idempotent,
+        open_world: false,
+    }
+}
+
+const fn remote(effect: Effect, idempotent: bool) -> ToolHint {
+    ToolHint {
+        effect,
+        idempotent,
+        open_world: true,
+    }
+}
+
+/// Hints for every tool, by tool name
+pub const TOOL_HINTS: &[(&str, ToolHint)] = &[
+    ("list", hint(Effect::ReadOnly, true)),
+    ("get", hint(Effect::ReadOnly, true)),
+    ("suggest", hint(Effect::ReadOnly, true)),
+    ("doable", hint(Effect::ReadOnly, true)),
+    ("today", hint(Effect::ReadOnly, true)),
+    ("report", hint(Effect::ReadOnly, true)),
+    ("done_log", hint(Effect::ReadOnly, true)),
+    ("trends", hint(Effect::ReadOnly, true)),
+    ("tree", hint(Effect::ReadOnly, true)),
+    ("goals_overview", hint(Effect::ReadOnly, true)),
+    ("board", hint(Effect::ReadOnly, true)),
+    ("waiting_report", hint(Effect::ReadOnly, true)),
+    ("inbox_age", hint(Effect::ReadOnly, true)),
+    ("process_inbox", hint(Effect::ReadOnly, true)),
+    ("export_ical", hint(Effect::ReadOnly, true)),
+    ("git_status", hint(Effect::ReadOnly, true)),
+    ("sync_status", hint(Effect::ReadOnly, true)),
+    ("inbox", hint(Effect::Additive, false)),
+    ("capture", hint(Effect::Additive, false)),
+    ("capture_email", hint(Effect::Additive, false)),
+    ("instantiate_template", hint(Effect::Additive, false)),
+    ("new_project_from_template", hint(Effect::Additive, false)),
+    ("backup_now", hint(Effect::Additive, false)),
+    ("begin_transaction", hint(Effect::Additive, true)),
+    ("import_github", remote(Effect::Additive, true)),
+    ("update", hint(Effect::Update, true)),
+    ("change_status", hint(Effect::Update, true)),
+    ("restore", hint(Effect::Update, true)),
+    ("set_escalation", hint(Effect::Update, true)),
+    ("set_focus", hint(Effect::Update, true)),
+    ("set_current_context", hint(Effect::Update, true)),
+    ("set_availability", hint(Effect::Update, true)),
+    ("pause_recurrence", hint(Effect::Update, true)),
+    ("resume_recurrence", hint(Effect::Update, true)),
+    ("postpone", hint(Effect::Update, false)),
+    ("skip_occurrence", hint(Effect::Update, false)),
+    ("reschedule", hint(Effect::Update, false)),
+    ("review_someday", hint(Effect::Update, false)),
+    ("merge_file", hint(Effect::Update, true)),
+    ("switch_workspace", hint(Effect::Update, true)),
+    ("commit", hint(Effect::Update, false)),
+    ("sync", remote(Effect::Update, true)),
+    ("sync_caldav", remote(Effect::Update, false)),
+    ("empty_trash", hint(Effect::Destructive, true)),
+    ("dedupe", hint(Effect::Destructive, true)),
+    ("abort", hint(Effect::Destructive, false)),
+    ("reload", hint(Effect::Destructive, true)),
+    ("restore_backup", hint(Effect::Destructive, true)),
+];
+
+/// Look up the hints for a tool
+pub fn tool_hint(name: &str) -> Option<ToolHint> {
+    TOOL_HINTS
+        .iter()
+        .find(|(tool, _)| *tool == name)
+        .map(|(_, hint)| *hint)
+}
+
+impl ToolHint {
+    /// The MCP annotations for this hint
+    ///
+    /// The destructive and idempotent hints only mean something for tools
+    /// that write, so read-only tools leave them out.
+    pub fn annotations(&self) -> ToolAnnotations {
+        let writes = self.effect != Effect::ReadOnly;
+        ToolAnnotations {
+            read_only_hint: Some(!writes),
+            destructive_hint: writes.then_some(self.effect == Effect::Destructive),
+            idempotent_hint: writes.then_some(self.idempotent),
+            open_world_hint: Some(self.open_world),
+            ..Default::default()
+        }
+    }
+}
+
+/// Look up the hints for a tool as the server is configured
+///
+/// `suggest` is a writing tool when `escalate_to_next_action` is set, since it
+/// then moves overdue waiting items back to next actions.
+pub fn configured_hint(name: &str, escalate_to_next_action: bool) -> Option<ToolHint> {
+    let mut hint = tool_hint(name)?;
+    if name == "suggest" && escalate_to_next_action {
+        hint.effect = Effect::Update;
+    }
+    Some(hint)
+}
+
+/// Whether a tool only reads the data (unknown tools are assumed to write)
+pub fn is_read_only(name: &str, escalate_to_next_action: bool) -> bool {
+    configured_hint(name, escalate_to_next_action)
+        .is_some_and(|hint| hint.effect == Effect::ReadOnly)
+}
+
+/// Add the hints to a tool list
+pub fn annotate(result: &mut ListToolsResult, escalate_to_next_action: bool) {
+    for tool in &mut result.tools {
+        if let Some(hint) = configured_hint(&tool.name, escalate_to_next_action) {
+            tool.annotations = Some(hint.annotations());
+        }
+    }
+}
+
+/// Drop the tools that write from a tool list (for a read-only server)
+pub fn retain_read_only(result: &mut ListToolsResult, escalate_to_next_action: bool) {
+    result
+        .tools
+        .retain(|tool| is_read_only(&tool.name, escalate_to_next_action));
+}
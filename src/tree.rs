@@ -0,0 +1,145 @@
+//! Tree view of projects and their tasks
+//!
+//! Projects are the roots; a project whose `project` field points at another
+//! project is shown as a sub-project of it. The tasks of each project are nested
+//! underneath with their status as a short marker, and tasks outside any project
+//! are listed last. Items are kept in file order, and a project that is (through
+//! a chain of sub-projects) its own parent is shown once, where it is first met.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use std::collections::{HashMap, HashSet};
+
+/// Marker and meaning for each status shown in the tree, in legend order
+pub const MARKERS: [(NotaStatus, &str); 8] = [
+    (NotaStatus::inbox, "[i]"),
+    (NotaStatus::next_action, "[ ]"),
+    (NotaStatus::waiting_for, "[w]"),
+    (NotaStatus::calendar, "[c]"),
+    (NotaStatus::later, "[l]"),
+    (NotaStatus::someday, "[s]"),
+    (NotaStatus::reference, "[r]"),
+    (NotaStatus::done, "[x]"),
+];
+
+/// Marker for a task status (projects, contexts and trash have none)
+pub fn marker(status: &NotaStatus) -> Option<&'static str> {
+    MARKERS
+        .iter()
+        .find(|(s, _)| s == status)
+        .map(|&(_, marker)| marker)
+}
+
+/// Whether `nota` appears in the tree as a task
+fn shown(nota: &Nota, include_done: bool) -> bool {
+    match nota.status {
+        NotaStatus::project
+        | NotaStatus::context
+        | NotaStatus::goal
+        | NotaStatus::template
+        | NotaStatus::trash => false,
+        NotaStatus::done => include_done,
+        _ => true,
+    }
+}
+
+/// Render the tree
+///
+/// # Arguments
+/// * `data` - The data to render
+/// * `include_done` - Whether to show done tasks as well
+pub fn render(data: &GtdData, include_done: bool) -> String {
+    let is_project = |id: &&str| data.find_project_by_id(id).is_some();
+
+    // Children per project in file order; anything else is a root or unfiled
+    let mut children: HashMap<&str, Vec<&Nota>> = HashMap::new();
+    let mut roots = Vec::new();
+    let mut unfiled = Vec::new();
+    for nota in data.iter() {
+        let parent = nota
+            .project
+            .as_deref()
+            .and_then(|p| data.resolve_id(p))
+            .filter(is_project);
+        if nota.is_project() {
+            match parent {
+                Some(parent) if parent != nota.id => children.entry(parent).or_default().push(nota),
+                _ => roots.push(nota),
+            }
+        } else if shown(nota, include_done) {
+            match parent {
+                Some(parent) => children.entry(parent).or_default().push(nota),
+                None => unfiled.push(nota),
+            }
+        }
+    }
+
+    let mut out = String::new();
+    let mut visited = HashSet::new();
+    let mut projects: Vec<&Nota> = roots;
+    // Projects caught in a parent cycle have no root; list them from the first one met
+    projects.extend(data.iter().filter(|n| n.is_project()));
+    for project in projects {
+        render_project(data, project, &children, 0, &mut visited, &mut out);
+    }
+    if out.is_empty() {
+        out.push_str("No projects yet\n");
+    }
+
+    if !unfiled.is_empty() {
+        out.push_str("\nNo project:\n");
+        for nota in unfiled {
+            out.push_str(&task_line(nota, 0));
+        }
+    }
+
+    let legend: Vec<String> = MARKERS
+        .iter()
+        .filter(|(status, _)| include_done || *status != NotaStatus::done)
+        .map(|(status, marker)| format!("{} {:?}", marker, status))
+        .collect();
+    out.push_str(&format!("\nLegend: {}\n", legend.join(", ")));
+    out
+}
+
+fn render_project<'a>(
+    data: &GtdData,
+    project: &'a Nota,
+    children: &HashMap<&str, Vec<&'a Nota>>,
+    depth: usize,
+    visited: &mut HashSet<&'a str>,
+    out: &mut String,
+) {
+    if !visited.insert(project.id.as_str()) {
+        return;
+    }
+    out.push_str(&format!(
+        "{}- [{}] {} ({})\n",
+        "  ".repeat(depth),
+        project.id,
+        project.title,
+        data.project_progress(&project.id)
+    ));
+    let members = children.get(project.id.as_str()).into_iter().flatten();
+    // Sub-projects first, then the tasks
+    for sub in members.clone().filter(|n| n.is_project()) {
+        render_project(data, sub, children, depth + 1, visited, out);
+    }
+    for task in members.filter(|n| !n.is_project()) {
+        out.push_str(&task_line(task, depth + 1));
+    }
+}
+
+fn task_line(nota: &Nota, depth: usize) -> String {
+    let mut line = format!(
+        "{}- {} [{}] {}",
+        "  ".repeat(depth),
+        marker(&nota.status).unwrap_or("[?]"),
+        nota.id,
+        nota.title
+    );
+    if let Some(context) = &nota.context {
+        line.push_str(&format!(" ({})", context));
+    }
+    line.push('\n');
+    line
+}
@@ -0,0 +1,140 @@
+//! Weekly productivity trends
+//!
+//! The `trends` tool shows how the system is flowing over the last weeks. The
+//! data file keeps no status history, so everything is derived from the two
+//! timestamps of each item:
+//!
+//! - **Captured**: items created in the week (`created_at`)
+//! - **Clarified**: of those, the ones that have left the inbox since
+//! - **Completed**: items done, counted in the week of their `updated_at`
+//! - **Days to done**: average days from `created_at` to `updated_at` of the
+//!   items completed in the week
+//!
+//! Projects and contexts are not counted. Weeks start on Monday; the current
+//! week is included and may be partial.
+
+use crate::gtd::{GtdData, NotaStatus};
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Number of weeks shown when none is given
+pub const DEFAULT_WEEKS: u32 = 8;
+
+/// Most weeks that can be asked for
+pub const MAX_WEEKS: u32 = 104;
+
+/// Counts for one week
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WeekStats {
+    /// Monday of the week
+    pub start: NaiveDate,
+    pub captured: usize,
+    pub clarified: usize,
+    pub completed: usize,
+    /// Average days from capture to done, if anything was completed
+    pub days_to_done: Option<f64>,
+}
+
+/// Monday of the week `date` is in
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// Compute the stats of the last `weeks` weeks, oldest first
+///
+/// # Arguments
+/// * `data` - The data to analyze
+/// * `today` - The current date, in the last week
+/// * `weeks` - Number of weeks
+pub fn compute(data: &GtdData, today: NaiveDate, weeks: u32) -> Vec<WeekStats> {
+    let current = week_start(today);
+    let mut stats: Vec<WeekStats> = (0..i64::from(weeks))
+        .rev()
+        .map(|back| WeekStats {
+            start: current - Duration::weeks(back),
+            ..Default::default()
+        })
+        .collect();
+    let Some(first) = stats.first().map(|w| w.start) else {
+        return stats;
+    };
+    let index = |date: NaiveDate| {
+        (date >= first && date <= today).then(|| ((date - first).num_days() / 7) as usize)
+    };
+
+    let mut total_days = vec![0i64; stats.len()];
+    for nota in data.iter().filter(|nota| nota.is_task()) {
+        if let Some(i) = index(nota.created_at) {
+            stats[i].captured += 1;
+            if nota.status != NotaStatus::inbox {
+                stats[i].clarified += 1;
+            }
+        }
+        if nota.status == NotaStatus::done
+            && let Some(i) = index(nota.updated_at)
+        {
+            stats[i].completed += 1;
+            total_days[i] += (nota.updated_at - nota.created_at).num_days().max(0);
+        }
+    }
+    for (week, days) in stats.iter_mut().zip(total_days) {
+        if week.completed > 0 {
+            week.days_to_done = Some(days as f64 / week.completed as f64);
+        }
+    }
+    stats
+}
+
+/// Render the stats as a Markdown table followed by totals
+///
+/// # Arguments
+/// * `stats` - Weekly stats, oldest first
+/// * `inbox` - Number of items in the inbox now
+pub fn render(stats: &[WeekStats], inbox: usize) -> String {
+    let mut out = format!(
+        "# Trends: last {} week{}\n\n",
+        stats.len(),
+        if stats.len() == 1 { "" } else { "s" }
+    );
+    out.push_str("| Week of | Captured | Clarified | Completed | Days to done |\n");
+    out.push_str("|---|---:|---:|---:|---:|\n");
+    for week in stats {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            week.start,
+            week.captured,
+            week.clarified,
+            week.completed,
+            week.days_to_done
+                .map_or_else(|| "-".to_string(), |days| format!("{:.1}", days))
+        ));
+    }
+
+    let captured: usize = stats.iter().map(|w| w.captured).sum();
+    let clarified: usize = stats.iter().map(|w| w.clarified).sum();
+    let completed: usize = stats.iter().map(|w| w.completed).sum();
+    let total_days: f64 = stats
+        .iter()
+        .filter_map(|w| w.days_to_done.map(|days| days * w.completed as f64))
+        .sum();
+    out.push_str(&format!(
+        "\nCaptured {}, clarified {} of them, completed {}.",
+        captured, clarified, completed
+    ));
+    if completed > 0 {
+        out.push_str(&format!(
+            " Average {:.1} days from capture to done.",
+            total_days / completed as f64
+        ));
+    }
+    if !stats.is_empty() {
+        let weeks = stats.len() as f64;
+        out.push_str(&format!(
+            "\nPer week: {:.1} captured, {:.1} completed. {} item{} in the inbox now.\n",
+            captured as f64 / weeks,
+            completed as f64 / weeks,
+            inbox,
+            if inbox == 1 { "" } else { "s" }
+        ));
+    }
+    out
+}
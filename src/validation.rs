@@ -18,7 +18,7 @@ pub fn parse_status_filter(status_str: &str) -> McpResult<NotaStatus> {
     status_str.parse::<NotaStatus>().map_err(|_| {
         mcp_attr::Error::new(mcp_attr::ErrorCode::INVALID_PARAMS).with_message(
             format!(
-                "Invalid status '{}'. Valid statuses: inbox, next_action, waiting_for, later, calendar, someday, done, reference, trash, project, context",
+                "Invalid status '{}'. Valid statuses: inbox, next_action, waiting_for, later, calendar, someday, done, reference, trash, project, context, goal, template",
                 status_str
             ),
             true,
@@ -70,6 +70,34 @@ pub fn format_invalid_project_error(project_id: &str, data: &GtdData) -> String
     }
 }
 
+/// Format an error message for invalid goal reference with available goals list
+///
+/// # Arguments
+/// * `goal_id` - The invalid goal ID that was provided
+/// * `data` - Reference to GtdData to get available goals
+///
+/// # Returns
+/// A formatted error message including the list of available goals
+pub fn format_invalid_goal_error(goal_id: &str, data: &GtdData) -> String {
+    let goals: Vec<&str> = data
+        .iter()
+        .filter(|nota| nota.is_goal())
+        .map(|nota| nota.id.as_str())
+        .collect();
+    if goals.is_empty() {
+        format!(
+            "Goal '{}' does not exist. No goals have been created yet. Create a goal first using inbox() with status='goal'.",
+            goal_id
+        )
+    } else {
+        format!(
+            "Goal '{}' does not exist.\nAvailable goals: {}",
+            goal_id,
+            goals.join(", ")
+        )
+    }
+}
+
 /// Format an error message for invalid context reference with available contexts list
 ///
 /// # Arguments
@@ -95,6 +123,53 @@ pub fn format_invalid_context_error(context_name: &str, data: &GtdData) -> Strin
     }
 }
 
+/// Check that a status change keeps the links to a project/context valid
+///
+/// A project or context that other items link to must keep its type: turning
+/// it into a task (or the other type) would leave those links dangling.
+///
+/// # Arguments
+/// * `data` - Current GTD data
+/// * `id` - ID of the nota being changed
+/// * `old_status` - Its current status
+/// * `new_status` - The requested status
+///
+/// # Returns
+/// `Ok(())` if the change is safe, otherwise an error message listing the linked
+/// items and how to relink them
+pub fn check_type_change(
+    data: &GtdData,
+    id: &str,
+    old_status: &NotaStatus,
+    new_status: &NotaStatus,
+) -> Result<(), String> {
+    let field = match old_status {
+        NotaStatus::project => "project",
+        NotaStatus::context => "context",
+        NotaStatus::goal => "goal",
+        _ => return Ok(()),
+    };
+    if new_status == old_status {
+        return Ok(());
+    }
+    let referrers = data.referrers(id);
+    if referrers.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "Cannot change {} '{}' to {:?}: {} item{} still link to it ({}). Relink them first with update(id, {}=\"<other {}>\") or clear the link with {}=\"\", or trash it with change_status(cascade=\"trash\"|\"unlink\").",
+        field,
+        id,
+        new_status,
+        referrers.len(),
+        if referrers.len() == 1 { "" } else { "s" },
+        referrers.join(", "),
+        field,
+        field,
+        field
+    ))
+}
+
 /// Normalize task ID by returning it as-is (no transformation)
 ///
 /// This helper function previously added '#' prefix for backwards compatibility,
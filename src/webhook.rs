@@ -0,0 +1,160 @@
+//! Webhook notifications
+//!
+//! After every successful save, each changed item is POSTed as JSON to the
+//! configured URL:
+//!
+//! ```json
+//! {
+//!   "change": "status_changed",
+//!   "id": "call-bob",
+//!   "title": "Call Bob",
+//!   "old_status": "next_action",
+//!   "new_status": "done",
+//!   "item": { "id": "call-bob", "title": "Call Bob", "status": "done", ... },
+//!   "message": "Update call-bob: Call Bob (next_action → done)",
+//!   "text": "Done: Call Bob"
+//! }
+//! ```
+//!
+//! `text` makes the payload usable as a Slack incoming webhook as is; Home
+//! Assistant automations can match on `change` and `new_status`. Requests are
+//! sent from a background thread, so a slow or unreachable endpoint never
+//! delays a save; failures are logged to stderr.
+
+use crate::gtd::{GtdData, Nota, NotaStatus};
+use crate::http::{self, Request};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// How an item changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The item is new
+    Added,
+    /// Fields other than the status changed
+    Updated,
+    /// The status changed (possibly along with other fields)
+    StatusChanged,
+    /// The item was removed from the file
+    Deleted,
+}
+
+/// One changed item
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// The item after the change (before it, for deletions)
+    pub nota: Nota,
+    /// Status before the change, None for added items
+    pub old_status: Option<NotaStatus>,
+}
+
+/// Items that differ between two versions of the data, in file order
+///
+/// Additions, updates and status changes follow the order of `after`;
+/// deletions come last.
+pub fn changes(before: &GtdData, after: &GtdData) -> Vec<Change> {
+    let previous: HashMap<&str, &Nota> = before.iter().map(|n| (n.id.as_str(), n)).collect();
+    let mut changes: Vec<Change> = after
+        .iter()
+        .filter_map(|nota| {
+            let kind = match previous.get(nota.id.as_str()) {
+                None => ChangeKind::Added,
+                Some(old) if *old == nota => return None,
+                Some(old) if old.status != nota.status => ChangeKind::StatusChanged,
+                Some(_) => ChangeKind::Updated,
+            };
+            Some(Change {
+                kind,
+                nota: nota.clone(),
+                old_status: previous.get(nota.id.as_str()).map(|old| old.status.clone()),
+            })
+        })
+        .collect();
+    changes.extend(
+        before
+            .iter()
+            .filter(|nota| !after.contains_id(&nota.id))
+            .map(|nota| Change {
+                kind: ChangeKind::Deleted,
+                nota: nota.clone(),
+                old_status: Some(nota.status.clone()),
+            }),
+    );
+    changes
+}
+
+/// Short human-readable summary of a change (e.g. "Done: Call Bob")
+pub fn summary(change: &Change) -> String {
+    let title = &change.nota.title;
+    match change.kind {
+        ChangeKind::Added => format!("Added to {:?}: {}", change.nota.status, title),
+        ChangeKind::Updated => format!("Updated: {}", title),
+        ChangeKind::StatusChanged if change.nota.status == NotaStatus::done => {
+            format!("Done: {}", title)
+        }
+        ChangeKind::StatusChanged if change.nota.status == NotaStatus::trash => {
+            format!("Trashed: {}", title)
+        }
+        ChangeKind::StatusChanged => format!("Moved to {:?}: {}", change.nota.status, title),
+        ChangeKind::Deleted => format!("Deleted: {}", title),
+    }
+}
+
+/// JSON payload sent for a change
+///
+/// # Arguments
+/// * `change` - The changed item
+/// * `message` - Commit message of the save that made the change
+pub fn payload(change: &Change, message: &str) -> Value {
+    let new_status = (change.kind != ChangeKind::Deleted).then_some(&change.nota.status);
+    json!({
+        "change": change.kind,
+        "id": change.nota.id,
+        "title": change.nota.title,
+        "old_status": change.old_status,
+        "new_status": new_status,
+        "item": change.nota,
+        "message": message,
+        "text": summary(change),
+    })
+}
+
+/// POST one payload per change to `url` from a background thread
+///
+/// # Arguments
+/// * `url` - Webhook URL
+/// * `changes` - Changed items, see [`changes`]
+/// * `message` - Commit message of the save
+pub fn notify(url: &str, changes: Vec<Change>, message: &str) {
+    if changes.is_empty() {
+        return;
+    }
+    let url = url.to_string();
+    let message = message.to_string();
+    std::thread::spawn(move || {
+        for change in &changes {
+            if let Err(e) = send(&url, &payload(change, &message)) {
+                eprintln!("Warning: Failed to send webhook: {:#}", e);
+            }
+        }
+    });
+}
+
+/// POST one payload and check the response status
+fn send(url: &str, payload: &Value) -> anyhow::Result<()> {
+    let body = payload.to_string();
+    let response = http::send(Request {
+        method: "POST",
+        url,
+        credentials: None,
+        headers: &[("Content-Type", "application/json")],
+        body: Some(&body),
+    })?;
+    if !(200..300).contains(&response.status) {
+        anyhow::bail!("POST {} returned HTTP {}", url, response.status);
+    }
+    Ok(())
+}
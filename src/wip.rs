@@ -0,0 +1,138 @@
+//! Work-in-progress limits per status
+//!
+//! A next-action list with 60 entries stops being trusted and turns into a
+//! second inbox. A [`WipLimit`] caps how many items a status may hold (e.g.
+//! `next_action=10`); `change_status` and `inbox` check it whenever items move
+//! into that status.
+//!
+//! What happens at the limit is set by [`WipMode`]: the move is made with a
+//! warning, or refused until something leaves the status.
+
+use crate::gtd::{GtdData, NotaStatus};
+use std::fmt;
+use std::str::FromStr;
+
+/// Most items a status may hold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WipLimit {
+    /// Status the limit applies to
+    pub status: NotaStatus,
+    /// Maximum number of items
+    pub max: usize,
+}
+
+impl FromStr for WipLimit {
+    type Err = String;
+
+    /// Parse `STATUS=MAX`, e.g. "next_action=10"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid WIP limit '{}'. Use STATUS=MAX, e.g. next_action=10",
+                s
+            )
+        };
+        let (status, max) = s.split_once('=').ok_or_else(invalid)?;
+        let status: NotaStatus = status.trim().parse().map_err(|_| invalid())?;
+        if matches!(status, NotaStatus::trash | NotaStatus::done) {
+            return Err(format!(
+                "Invalid WIP limit '{}': {:?} items are finished and cannot be limited",
+                s, status
+            ));
+        }
+        let max = max.trim().parse().map_err(|_| invalid())?;
+        Ok(WipLimit { status, max })
+    }
+}
+
+impl fmt::Display for WipLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}={}", self.status, self.max)
+    }
+}
+
+/// What happens when a move would take a status over its limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WipMode {
+    /// Make the move and add a warning to the response
+    #[default]
+    Warn,
+    /// Refuse the move
+    Enforce,
+}
+
+impl FromStr for WipMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "warn" => Ok(WipMode::Warn),
+            "enforce" => Ok(WipMode::Enforce),
+            _ => Err(format!(
+                "Invalid WIP mode '{}'. Valid options are: warn, enforce",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for WipMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WipMode::Warn => "warn",
+            WipMode::Enforce => "enforce",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Limit configured for `status`, if any (the last one wins when repeated)
+///
+/// # Arguments
+/// * `limits` - Configured limits
+/// * `status` - Status items move into
+pub fn limit_for(limits: &[WipLimit], status: &NotaStatus) -> Option<usize> {
+    limits
+        .iter()
+        .rev()
+        .find(|limit| &limit.status == status)
+        .map(|limit| limit.max)
+}
+
+/// Number of items currently in `status`
+pub fn count(data: &GtdData, status: &NotaStatus) -> usize {
+    data.iter().filter(|nota| &nota.status == status).count()
+}
+
+/// Error for a move refused because `status` is full
+///
+/// # Arguments
+/// * `status` - Status at its limit
+/// * `max` - The limit
+pub fn full_error(status: &NotaStatus, max: usize) -> String {
+    format!(
+        "{:?} is at its WIP limit ({}/{}). Finish, defer or move an item out of {:?} first",
+        status, max, max, status
+    )
+}
+
+/// Warning for a status holding more than its limit, if it does
+///
+/// # Arguments
+/// * `data` - GTD data after the move
+/// * `limits` - Configured limits
+/// * `status` - Status items moved into
+pub fn over_limit_warning(
+    data: &GtdData,
+    limits: &[WipLimit],
+    status: &NotaStatus,
+) -> Option<String> {
+    let max = limit_for(limits, status)?;
+    let held = count(data, status);
+    (held > max).then(|| {
+        format!(
+            "Warning: {:?} holds {} items, over its WIP limit of {}. Consider finishing or deferring some (e.g. to someday or later).",
+            status, held, max
+        )
+    })
+}
@@ -0,0 +1,160 @@
+//! Several GTD data files served by one handler
+//!
+//! Only the active workspace lives in [`GtdServerHandler::data`](crate::GtdServerHandler)
+//! and the handler's storage. The others are parked here with their own data
+//! and [`Storage`] (and so their own Git repository, debouncer and push worker)
+//! until `switch_workspace` swaps them in.
+
+use crate::gtd::{Clock, GtdData};
+use crate::storage::Storage;
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A named data file given as `NAME=PATH` (`--workspace personal=personal.toml`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSpec {
+    /// Name used by `switch_workspace`
+    pub name: String,
+    /// Path to the GTD data file
+    pub path: PathBuf,
+}
+
+impl FromStr for WorkspaceSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((name, path)) = s.split_once('=') else {
+            return Err(format!(
+                "Invalid workspace '{}'. Use NAME=PATH, e.g. personal=personal.toml",
+                s
+            ));
+        };
+        let name = name.trim();
+        validate_name(name)?;
+        if path.trim().is_empty() {
+            return Err(format!("Workspace '{}' has an empty path", name));
+        }
+        Ok(WorkspaceSpec {
+            name: name.to_string(),
+            path: PathBuf::from(path.trim()),
+        })
+    }
+}
+
+impl fmt::Display for WorkspaceSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.path.display())
+    }
+}
+
+/// Check that a workspace name is usable as a tool argument
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Invalid workspace name '{}'. Use letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Name of the workspace of a data file given without a name (its file stem)
+///
+/// # Arguments
+/// * `path` - Path to the GTD data file
+pub fn default_workspace_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .filter(|stem| validate_name(stem).is_ok())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// The workspaces of one handler: which is active, and the parked ones
+pub(crate) struct Workspaces {
+    /// Name of the workspace whose data is in the handler
+    active: String,
+    /// Every workspace name in the order they were added
+    names: Vec<String>,
+    /// Data and storage of the inactive workspaces
+    parked: HashMap<String, (GtdData, Storage)>,
+}
+
+impl Workspaces {
+    /// Start with the handler's own data as the only workspace
+    pub(crate) fn new(active: String) -> Self {
+        Self {
+            names: vec![active.clone()],
+            active,
+            parked: HashMap::new(),
+        }
+    }
+
+    /// Name of the active workspace
+    pub(crate) fn active(&self) -> &str {
+        &self.active
+    }
+
+    /// Every workspace name in the order they were added
+    pub(crate) fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Whether a workspace with this name exists
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+
+    /// Add an inactive workspace
+    pub(crate) fn add(&mut self, name: &str, data: GtdData, storage: Storage) -> Result<()> {
+        validate_name(name).map_err(anyhow::Error::msg)?;
+        if self.contains(name) {
+            bail!("Workspace '{}' is defined more than once", name);
+        }
+        self.names.push(name.to_string());
+        self.parked.insert(name.to_string(), (data, storage));
+        Ok(())
+    }
+
+    /// Path of the data file of an inactive workspace
+    pub(crate) fn parked_path(&self, name: &str) -> Option<&Path> {
+        self.parked
+            .get(name)
+            .map(|(_, storage)| storage.file_path())
+    }
+
+    /// Remove an inactive workspace to make it active (see [`Self::park_active`])
+    pub(crate) fn take(&mut self, name: &str) -> Option<(GtdData, Storage)> {
+        self.parked.remove(name)
+    }
+
+    /// Park the data and storage of the active workspace and mark `next` active
+    ///
+    /// # Arguments
+    /// * `next` - Workspace previously removed with [`Self::take`]
+    /// * `data` - Data of the workspace that was active until now
+    /// * `storage` - Storage of the workspace that was active until now
+    pub(crate) fn park_active(&mut self, next: &str, data: GtdData, storage: Storage) {
+        let previous = std::mem::replace(&mut self.active, next.to_string());
+        self.parked.insert(previous, (data, storage));
+    }
+
+    /// Make every inactive workspace's storage take the date from `clock`
+    pub(crate) fn set_clock(&mut self, clock: &Arc<dyn Clock>) {
+        for (_, storage) in self.parked.values_mut() {
+            *storage = storage.clone().with_clock(clock.clone());
+        }
+    }
+
+    /// Storage of every inactive workspace (for shutdown)
+    pub(crate) fn parked_storages(&self) -> impl Iterator<Item = &Storage> {
+        self.parked.values().map(|(_, storage)| storage)
+    }
+}
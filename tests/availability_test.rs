@@ -0,0 +1,182 @@
+//! Integration tests for context availability schedules
+//!
+//! These tests cover parsing and normalizing schedules, checking a time
+//! against them, ranking actions of closed contexts lower in `suggest`, and
+//! storing schedules with `set_availability`.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use gtd_mcp::availability::Availability;
+use gtd_mcp::suggest::{self, Constraints};
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+/// 2026-10-12 is a Monday
+fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 10, day)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+// 曜日・時刻の書き方が正規化され、時刻が範囲内か判定できることを確認
+#[test]
+fn test_parse_and_check() {
+    let office: Availability = "mon–fri 9-18".parse().unwrap();
+    assert_eq!(office.to_string(), "Mon-Fri 09:00-18:00");
+    assert!(office.is_available(at(12, 9, 0)));
+    assert!(office.is_available(at(16, 17, 59)));
+    assert!(!office.is_available(at(16, 18, 0)));
+    assert!(!office.is_available(at(17, 12, 0)));
+
+    let bank: Availability = "Fri-Mon 9:30-12; Wed,Thursday".parse().unwrap();
+    assert_eq!(bank.to_string(), "Mon,Fri-Sun 09:30-12:00; Wed-Thu");
+    assert!(bank.is_available(at(18, 10, 0)));
+    assert!(!bank.is_available(at(13, 10, 0)));
+    assert!(bank.is_available(at(15, 23, 59)));
+
+    assert_eq!(
+        "daily".parse::<Availability>().unwrap().to_string(),
+        "Daily"
+    );
+    assert_eq!(
+        "18-24".parse::<Availability>().unwrap().to_string(),
+        "18:00-24:00"
+    );
+}
+
+// 不正なスケジュールは理由付きで拒否されることを確認
+#[test]
+fn test_parse_errors() {
+    for (input, expected) in [
+        ("", "Availability is empty"),
+        ("Someday 9-18", "Invalid day 'Someday'"),
+        ("Mon-Fri 9", "Invalid hours '9'"),
+        ("Mon-Fri 25-26", "Invalid hours '25-26'"),
+        ("Mon 22-2", "the end must be after the start"),
+        ("Mon Tue 9-18", "one day set and one time range"),
+    ] {
+        let error = input.parse::<Availability>().unwrap_err();
+        assert!(error.contains(expected), "{}: {}", input, error);
+    }
+}
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    for (context, availability) in [("@office", Some("Mon-Fri 9-18")), ("@home", None)] {
+        data.add(Nota {
+            id: context.to_string(),
+            title: context.to_string(),
+            status: NotaStatus::context,
+            availability: availability.map(str::to_string),
+            ..Default::default()
+        });
+    }
+    for (id, context) in [("print-report", "@office"), ("water-plants", "@home")] {
+        data.add(Nota {
+            id: id.to_string(),
+            title: id.replace('-', " "),
+            status: NotaStatus::next_action,
+            context: Some(context.to_string()),
+            created_at: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            ..Default::default()
+        });
+    }
+    data
+}
+
+// コンテキストが利用できない時間帯はその行動の順位が下がることを確認
+#[test]
+fn test_suggest_ranks_closed_contexts_lower() {
+    let data = sample();
+    let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+    let ids = |constraints: &Constraints| -> Vec<String> {
+        suggest::rank(&data, constraints, today, 10)
+            .suggestions
+            .into_iter()
+            .map(|s| s.nota.id)
+            .collect()
+    };
+
+    // 時刻が分からなければ判定しない（ID 順）
+    let mut constraints = Constraints::default();
+    assert_eq!(ids(&constraints), vec!["print-report", "water-plants"]);
+
+    // 土曜日はオフィスが閉まっている
+    constraints.now = Some(at(17, 10, 0));
+    assert_eq!(ids(&constraints), vec!["water-plants", "print-report"]);
+    let ranking = suggest::rank(&data, &constraints, today, 10);
+    assert!(
+        ranking.suggestions[1]
+            .reasons
+            .contains(&"@office not available now (Mon-Fri 09:00-18:00)".to_string()),
+        "{:?}",
+        ranking.suggestions[1].reasons
+    );
+
+    // その場にいると伝えた場合は下げない
+    constraints.context = Some("@office".to_string());
+    assert_eq!(ids(&constraints), vec!["print-report"]);
+    assert!(
+        suggest::rank(&data, &constraints, today, 10).suggestions[0]
+            .reasons
+            .iter()
+            .all(|reason| !reason.contains("not available"))
+    );
+}
+
+// set_availability で正規化したスケジュールが保存され、空文字列で消えることを確認
+#[tokio::test]
+async fn test_set_availability() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap().to_string();
+    let handler = GtdServerHandler::new(&path, false).unwrap();
+    handler.data.write().await.add(Nota {
+        id: "@bank".to_string(),
+        title: "Bank".to_string(),
+        status: NotaStatus::context,
+        ..Default::default()
+    });
+
+    let response = handler
+        .set_availability("@bank".to_string(), "mon-fri 9-15; sat 9-12".to_string())
+        .await
+        .unwrap();
+    assert!(
+        response.starts_with("@bank is available Mon-Fri 09:00-15:00; Sat 09:00-12:00 ("),
+        "{}",
+        response
+    );
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        content.contains("availability = \"Mon-Fri 09:00-15:00; Sat 09:00-12:00\""),
+        "{}",
+        content
+    );
+
+    let message = handler
+        .set_availability("@bank".to_string(), "weekdays".to_string())
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(message.contains("Invalid day 'weekdays'"), "{}", message);
+    let message = handler
+        .set_availability("@gym".to_string(), "daily".to_string())
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("Context '@gym' does not exist"),
+        "{}",
+        message
+    );
+
+    handler
+        .set_availability("@bank".to_string(), String::new())
+        .await
+        .unwrap();
+    let data = handler.data.read().await;
+    assert_eq!(data.find_by_id("@bank").unwrap().availability, None);
+    assert!(data.check_integrity().is_empty());
+}
@@ -0,0 +1,85 @@
+//! Integration tests for snapshots and the backup tools
+//!
+//! These tests cover taking and restoring a storage snapshot, refusing a
+//! snapshot of another file, and the `backup_now`/`restore_backup` tools.
+
+mod common;
+
+use common::buy_milk;
+use gtd_mcp::{GtdServerHandler, Storage};
+use std::fs;
+
+// スナップショットを書き戻すとその時点のデータに戻り、別ファイルへの書き戻しは拒否されることを確認
+#[test]
+fn test_snapshot_and_restore() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let storage = Storage::new(&path, false);
+    storage.save(&buy_milk()).unwrap();
+
+    let snapshot = storage.snapshot().unwrap();
+    assert_eq!(snapshot.item_count(), 1);
+    assert_eq!(snapshot.file_path(), path);
+
+    let mut data = storage.load().unwrap();
+    data.retain(|nota| nota.id != "buy-milk");
+    storage.save(&data).unwrap();
+    assert!(!fs::read_to_string(&path).unwrap().contains("buy-milk"));
+
+    let restored = storage.restore(&snapshot).unwrap();
+    assert!(restored.find_by_id("buy-milk").is_some());
+    assert!(fs::read_to_string(&path).unwrap().contains("buy-milk"));
+    // 戻した後も続けて保存できる
+    storage.save(&restored).unwrap();
+
+    let other = Storage::new(temp_dir.path().join("other.toml"), false);
+    let error = other.restore(&snapshot).err().unwrap().to_string();
+    assert!(error.contains("gtd.toml"), "{}", error);
+}
+
+// backup_now で取ったバックアップを restore_backup で番号指定・最新で戻せることを確認
+#[tokio::test]
+async fn test_backup_tools() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    Storage::new(&path, false).save(&buy_milk()).unwrap();
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+
+    let error = format!("{:?}", handler.restore_backup(None).await.unwrap_err());
+    assert!(error.contains("No backups yet"), "{}", error);
+
+    let response = handler.backup_now().await.unwrap();
+    assert!(
+        response.starts_with("Backup #1 taken: 1 items"),
+        "{}",
+        response
+    );
+    handler.empty_trash().await.unwrap();
+    handler
+        .change_status(
+            vec!["buy-milk".to_string()],
+            "trash".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    handler.empty_trash().await.unwrap();
+    let response = handler.backup_now().await.unwrap();
+    assert!(
+        response.starts_with("Backup #2 taken: 0 items"),
+        "{}",
+        response
+    );
+
+    let error = format!("{:?}", handler.restore_backup(Some(5)).await.unwrap_err());
+    assert!(error.contains("Available backups: #1, #2"), "{}", error);
+
+    let response = handler.restore_backup(Some(1)).await.unwrap();
+    assert!(response.starts_with("Restored backup #1"), "{}", response);
+    assert!(handler.data.read().await.find_by_id("buy-milk").is_some());
+    assert!(fs::read_to_string(&path).unwrap().contains("buy-milk"));
+
+    handler.restore_backup(None).await.unwrap();
+    assert!(handler.data.read().await.find_by_id("buy-milk").is_none());
+}
@@ -0,0 +1,138 @@
+//! Unit tests for the Kanban-style board
+//!
+//! These tests check the columns and their counts, the window of done items,
+//! both layouts, and the project filter of the `board` tool.
+
+use chrono::{Duration, NaiveDate};
+use gtd_mcp::board::{self, BoardFormat};
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, 10).unwrap()
+}
+
+fn item(id: &str, title: &str, status: NotaStatus) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: title.to_string(),
+        status,
+        created_at: today(),
+        updated_at: today(),
+        ..Default::default()
+    }
+}
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(item("idea", "New idea", NotaStatus::inbox));
+    data.add(item("call-bank", "Call bank", NotaStatus::next_action));
+    data.add(item(
+        "write-docs",
+        "Write docs | API",
+        NotaStatus::next_action,
+    ));
+    data.add(item("quote", "Supplier quote", NotaStatus::waiting_for));
+    data.add(item("ship", "Ship v1", NotaStatus::done));
+    data.add(Nota {
+        updated_at: today() - Duration::days(board::DONE_DAYS),
+        ..item("old-win", "Old win", NotaStatus::done)
+    });
+    data.add(item("trip", "Plan trip", NotaStatus::someday));
+    data
+}
+
+// 状態ごとの列と件数が Markdown の表として出力されることを確認
+#[test]
+fn test_render_markdown() {
+    let output = board::render(&sample(), None, BoardFormat::Markdown, today());
+    assert_eq!(
+        output,
+        "| Inbox (1) | Next (2) | Waiting (1) | Done (1) |\n\
+         |---|---|---|---|\n\
+         | [idea] New idea | [call-bank] Call bank | [quote] Supplier quote | [ship] Ship v1 |\n\
+         |  | [write-docs] Write docs \\| API |  |  |\n"
+    );
+}
+
+// テキスト形式では列が空白でそろえられ、長いセルが切り詰められることを確認
+#[test]
+fn test_render_text() {
+    let mut data = GtdData::new();
+    data.add(item("a", "Short", NotaStatus::inbox));
+    data.add(item("b", &"x".repeat(60), NotaStatus::next_action));
+
+    let output = board::render(&data, None, BoardFormat::Text, today());
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[0],
+        format!("Inbox (1)  {:<32}  Waiting (0)  Done (0)", "Next (1)")
+    );
+    assert_eq!(
+        lines[1],
+        format!("---------  {}  -----------  --------", "-".repeat(32))
+    );
+    assert_eq!(lines[2], format!("[a] Short  [b] {}...", "x".repeat(25)));
+}
+
+// 空のボードではその旨を表示することを確認
+#[test]
+fn test_render_empty() {
+    let output = board::render(&GtdData::new(), None, BoardFormat::Markdown, today());
+    assert!(output.ends_with("\nNothing on the board\n"), "{}", output);
+}
+
+// 形式名の解析を確認
+#[test]
+fn test_board_format_from_str() {
+    assert_eq!("markdown".parse(), Ok(BoardFormat::Markdown));
+    assert_eq!("text".parse(), Ok(BoardFormat::Text));
+    assert!("csv".parse::<BoardFormat>().is_err());
+}
+
+// board ツールがプロジェクトで絞り込み、存在しないプロジェクトを拒否することを確認
+#[tokio::test]
+async fn test_board_tool_project_filter() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    {
+        let mut data = handler.data.write().await;
+        data.add(item("website", "Website", NotaStatus::project));
+        data.add(Nota {
+            project: Some("website".to_string()),
+            ..item("draft-copy", "Draft copy", NotaStatus::next_action)
+        });
+        data.add(item("call-bank", "Call bank", NotaStatus::next_action));
+    }
+
+    let output = handler
+        .handle_board(None, Some("website".to_string()))
+        .await
+        .unwrap();
+    assert!(output.contains("[draft-copy]"), "{}", output);
+    assert!(!output.contains("[call-bank]"), "{}", output);
+
+    let message = handler
+        .handle_board(None, Some("nope".to_string()))
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("Project 'nope' does not exist"),
+        "{}",
+        message
+    );
+
+    let message = handler
+        .handle_board(Some("html".to_string()), None)
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("Invalid board format 'html'"),
+        "{}",
+        message
+    );
+}
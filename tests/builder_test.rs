@@ -0,0 +1,158 @@
+//! Integration tests for the handler builder and the read-only mode
+//!
+//! These tests build handlers from named options and call a read-only one as
+//! an MCP client, both directly (as stdio serves it) and through
+//! [`SharedServer`].
+
+use chrono::{Duration, FixedOffset, NaiveDate};
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::{DuplicateCheck, FixedClock, GtdData, GtdServerHandler, Nota, NotaStatus, Storage};
+use mcp_attr::client::McpClient;
+use mcp_attr::schema::{CallToolRequestParams, CallToolResultContentItem};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn data_file(dir: &TempDir) -> std::path::PathBuf {
+    let path = dir.path().join("gtd.toml");
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "call-bob".to_string(),
+        title: "Call Bob".to_string(),
+        status: NotaStatus::next_action,
+        ..Default::default()
+    });
+    Storage::new(&path, false).save(&data).unwrap();
+    path
+}
+
+// パスだけで組み立てたハンドラがデータファイルを読み込むことを確認
+#[tokio::test]
+async fn test_builder_opens_path() {
+    let dir = TempDir::new().unwrap();
+    let path = data_file(&dir);
+
+    let handler = GtdServerHandler::builder()
+        .path(&path)
+        .duplicate_check(DuplicateCheck::Off)
+        .build()
+        .unwrap();
+
+    assert_eq!(handler.storage().file_path(), path);
+    assert!(!handler.is_read_only());
+    assert!(handler.data.read().await.find_by_id("call-bob").is_some());
+}
+
+// パスもストレージも無いと組み立てに失敗することを確認
+#[test]
+fn test_builder_needs_data_file() {
+    let error = GtdServerHandler::builder().build().err().unwrap();
+    assert!(error.to_string().contains("No data file"), "{}", error);
+}
+
+// タイムゾーンはハンドラーごとの時計に入り、同じプロセスで別々のオフセットを使えることを確認
+#[test]
+fn test_timezone_per_handler() {
+    let (east_dir, west_dir) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+    let east = GtdServerHandler::builder()
+        .path(data_file(&east_dir))
+        .timezone(FixedOffset::east_opt(14 * 3600).unwrap())
+        .build()
+        .unwrap();
+    let west = GtdServerHandler::builder()
+        .path(data_file(&west_dir))
+        .timezone(FixedOffset::west_opt(12 * 3600).unwrap())
+        .build()
+        .unwrap();
+    // 26 時間離れたオフセットでは日付が必ず異なる
+    let (east_now, west_now) = (east.clock().now(), west.clock().now());
+    let apart = east_now - west_now;
+    assert!(
+        (apart - Duration::hours(26)).num_seconds().abs() < 60,
+        "{} {}",
+        east_now,
+        west_now
+    );
+    assert!(east.clock().today() > west.clock().today());
+
+    // 時計を渡すとタイムゾーンより優先される
+    let date = NaiveDate::from_ymd_opt(2030, 1, 10).unwrap();
+    let fixed = GtdServerHandler::builder()
+        .path(data_file(&TempDir::new().unwrap()))
+        .timezone(FixedOffset::east_opt(14 * 3600).unwrap())
+        .clock(Arc::new(FixedClock::on(date)))
+        .build()
+        .unwrap();
+    assert_eq!(fixed.clock().today(), date);
+}
+
+// 読み取り専用のサーバは読み取りツールだけを一覧し、書き込みツールの呼び出しを拒否することを確認
+#[tokio::test]
+async fn test_read_only_server_refuses_writes() {
+    let dir = TempDir::new().unwrap();
+    let path = data_file(&dir);
+    let handler = Arc::new(
+        GtdServerHandler::builder()
+            .storage(Storage::new(&path, false))
+            .read_only(true)
+            .build()
+            .unwrap(),
+    );
+    let client = McpClient::with_server(SharedServer::new(handler.clone()))
+        .await
+        .unwrap();
+
+    let tools = client.tools_list(None).await.unwrap();
+    let names: Vec<&str> = tools.tools.iter().map(|tool| tool.name.as_str()).collect();
+    assert!(names.contains(&"list"), "{:?}", names);
+    assert!(!names.contains(&"inbox"), "{:?}", names);
+    assert!(!names.contains(&"empty_trash"), "{:?}", names);
+
+    let list = client
+        .tools_call(CallToolRequestParams::new("list"))
+        .await
+        .unwrap();
+    match &list.content[0] {
+        CallToolResultContentItem::TextContent(text) => {
+            assert!(text.text.contains("call-bob"), "{}", text.text)
+        }
+        other => panic!("unexpected content: {:?}", other),
+    }
+
+    let inbox = CallToolRequestParams::new("inbox")
+        .with_argument("title", "Buy milk")
+        .unwrap();
+    assert!(client.tools_call(inbox).await.is_err());
+    assert_eq!(handler.data.read().await.iter().count(), 1);
+}
+
+// 素のハンドラ (stdio と同じ経路) でも読み取り専用が書き込みツールを隠して拒否することを確認
+#[tokio::test]
+async fn test_read_only_handler_refuses_writes_over_stdio() {
+    let dir = TempDir::new().unwrap();
+    let path = data_file(&dir);
+    let handler = GtdServerHandler::builder()
+        .storage(Storage::new(&path, false))
+        .read_only(true)
+        .build()
+        .unwrap();
+    let client = McpClient::with_server(handler).await.unwrap();
+
+    let tools = client.tools_list(None).await.unwrap();
+    let names: Vec<&str> = tools.tools.iter().map(|tool| tool.name.as_str()).collect();
+    assert!(names.contains(&"list"), "{:?}", names);
+    assert!(!names.contains(&"inbox"), "{:?}", names);
+
+    let inbox = CallToolRequestParams::new("inbox")
+        .with_argument("title", "Buy milk")
+        .unwrap();
+    let error = format!("{:?}", client.tools_call(inbox).await.unwrap_err());
+    assert!(error.contains("read-only"), "{}", error);
+    assert_eq!(
+        Storage::new(&path, false)
+            .load_local()
+            .unwrap()
+            .iter()
+            .count(),
+        1
+    );
+}
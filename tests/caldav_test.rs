@@ -0,0 +1,405 @@
+//! Unit tests for the CalDAV task sync
+//!
+//! These tests run the sync against an in-memory task list and verify the
+//! VTODO mapping, changes from both sides, conflict policies and the parsing
+//! of server responses.
+
+mod common;
+
+use common::{date, today};
+use gtd_mcp::caldav::{
+    self, CalDavServer, Cancellable, ConflictPolicy, RemoteTodo, SyncState, parse_multistatus,
+    vtodo,
+};
+use gtd_mcp::{Cancellation, Cancelled, GtdData, GtdServerHandler, Nota, NotaStatus};
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+/// A task list kept in memory, storing the VTODOs it receives
+#[derive(Default)]
+struct MemoryServer {
+    todos: BTreeMap<String, (String, String)>,
+    version: u32,
+}
+
+impl MemoryServer {
+    fn store(&mut self, href: &str, ics: &str) -> String {
+        self.version += 1;
+        let etag = format!("\"{}\"", self.version);
+        self.todos
+            .insert(href.to_string(), (etag.clone(), ics.to_string()));
+        etag
+    }
+
+    /// Change a task as a phone app would
+    fn edit(&mut self, href: &str, edit: impl FnOnce(&mut vtodo::TodoFields)) {
+        let todo = vtodo::parse(&self.todos[href].1).unwrap();
+        let mut fields = todo.fields;
+        edit(&mut fields);
+        let ics = vtodo::render(&todo.uid, &fields, today())
+            .replace("END:VTODO", "LAST-MODIFIED:20250402T080000Z\r\nEND:VTODO");
+        self.store(href, &ics);
+    }
+
+    fn fields(&self, href: &str) -> vtodo::TodoFields {
+        vtodo::parse(&self.todos[href].1).unwrap().fields
+    }
+}
+
+impl CalDavServer for MemoryServer {
+    fn list(&mut self) -> anyhow::Result<Vec<RemoteTodo>> {
+        Ok(self
+            .todos
+            .iter()
+            .map(|(href, (etag, ics))| {
+                let todo = vtodo::parse(ics).unwrap();
+                RemoteTodo {
+                    href: href.clone(),
+                    etag: Some(etag.clone()),
+                    uid: todo.uid,
+                    fields: todo.fields,
+                    last_modified: todo.last_modified,
+                }
+            })
+            .collect())
+    }
+
+    fn new_href(&self, name: &str) -> String {
+        format!("/tasks/{}.ics", name)
+    }
+
+    fn put(&mut self, href: &str, ics: &str, etag: Option<&str>) -> anyhow::Result<Option<String>> {
+        let current = self.todos.get(href).map(|(etag, _)| etag.as_str());
+        if current != etag {
+            anyhow::bail!("{} changed on the server during the sync", href);
+        }
+        Ok(Some(self.store(href, ics)))
+    }
+
+    fn delete(&mut self, href: &str, _etag: Option<&str>) -> anyhow::Result<()> {
+        self.todos.remove(href);
+        Ok(())
+    }
+}
+
+fn add(data: &mut GtdData, id: &str, status: NotaStatus, start_date: Option<&str>) {
+    data.add(Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status,
+        start_date: start_date.map(date),
+        created_at: date("2025-03-01"),
+        updated_at: date("2025-03-01"),
+        ..Default::default()
+    });
+}
+
+fn sync(
+    data: &mut GtdData,
+    state: &mut SyncState,
+    server: &mut MemoryServer,
+    policy: ConflictPolicy,
+) -> caldav::SyncReport {
+    caldav::sync(data, state, server, policy, today()).unwrap()
+}
+
+/// Data with two synced items: a next action and a calendar item
+fn synced_setup() -> (GtdData, SyncState, MemoryServer) {
+    let mut data = GtdData::new();
+    add(&mut data, "call-bob", NotaStatus::next_action, None);
+    add(
+        &mut data,
+        "dentist",
+        NotaStatus::calendar,
+        Some("2025-04-10"),
+    );
+    add(&mut data, "idea", NotaStatus::someday, None);
+    let mut state = SyncState::default();
+    let mut server = MemoryServer::default();
+    sync(&mut data, &mut state, &mut server, ConflictPolicy::Newer);
+    (data, state, server)
+}
+
+// 次のアクションとカレンダー項目だけが送信され、二回目は何もしないことを確認
+#[test]
+fn test_first_sync_sends_items() {
+    let (mut data, mut state, mut server) = synced_setup();
+    assert_eq!(
+        server.todos.keys().collect::<Vec<_>>(),
+        vec!["/tasks/call-bob.ics", "/tasks/dentist.ics"]
+    );
+    let ics = &server.todos["/tasks/dentist.ics"].1;
+    assert!(ics.contains("UID:dentist@gtd-mcp\r\n"));
+    assert!(ics.contains("DUE;VALUE=DATE:20250410\r\n"));
+    assert!(ics.contains("STATUS:NEEDS-ACTION\r\n"));
+
+    let report = sync(&mut data, &mut state, &mut server, ConflictPolicy::Newer);
+    assert_eq!(
+        report.to_string(),
+        "Already in sync with the CalDAV task list - nothing received or sent."
+    );
+
+    // 状態ファイルに保存して読み直せる
+    let dir = TempDir::new().unwrap();
+    let path = caldav::state_path(&dir.path().join("gtd.toml"));
+    assert!(path.ends_with("gtd.caldav.toml"));
+    state.save(&path).unwrap();
+    assert_eq!(SyncState::load(&path).unwrap(), state);
+}
+
+// サーバーでの完了・編集・追加・削除が反映されることを確認
+#[test]
+fn test_receives_server_changes() {
+    let (mut data, mut state, mut server) = synced_setup();
+    server.edit("/tasks/call-bob.ics", |todo| todo.completed = true);
+    server.edit("/tasks/dentist.ics", |todo| {
+        todo.summary = "Dentist at 10".to_string();
+        todo.due = Some(date("2025-04-11"));
+    });
+    let phone = vtodo::render(
+        "phone-1",
+        &vtodo::TodoFields {
+            summary: "Buy milk".to_string(),
+            ..Default::default()
+        },
+        today(),
+    );
+    server.store("/tasks/phone-1.ics", &phone);
+
+    let report = sync(&mut data, &mut state, &mut server, ConflictPolicy::Newer);
+    assert_eq!(
+        report.received,
+        vec![
+            ("call-bob".to_string(), Some(NotaStatus::next_action)),
+            ("dentist".to_string(), Some(NotaStatus::calendar)),
+            ("buy-milk".to_string(), None),
+        ]
+    );
+    assert!(report.sent.is_empty());
+    assert_eq!(
+        data.find_by_id("call-bob").unwrap().status,
+        NotaStatus::done
+    );
+    let dentist = data.find_by_id("dentist").unwrap();
+    assert_eq!(dentist.title, "Dentist at 10");
+    assert_eq!(dentist.start_date, Some(date("2025-04-11")));
+    assert_eq!(dentist.updated_at, today());
+    assert_eq!(
+        data.find_by_id("buy-milk").unwrap().status,
+        NotaStatus::next_action
+    );
+
+    // サーバーで削除され、こちらで変更のない項目はゴミ箱へ
+    server.todos.remove("/tasks/dentist.ics");
+    let report = sync(&mut data, &mut state, &mut server, ConflictPolicy::Newer);
+    assert_eq!(
+        report.received,
+        vec![("dentist".to_string(), Some(NotaStatus::calendar))]
+    );
+    assert_eq!(
+        data.find_by_id("dentist").unwrap().status,
+        NotaStatus::trash
+    );
+    assert!(!state.items.contains_key("dentist"));
+}
+
+// こちらでの完了は COMPLETED として送られ、他のステータスへの移動はサーバーから削除されることを確認
+#[test]
+fn test_sends_local_changes() {
+    let (mut data, mut state, mut server) = synced_setup();
+    let mut bob = data.find_by_id("call-bob").unwrap();
+    bob.status = NotaStatus::done;
+    data.update("call-bob", bob);
+    let mut dentist = data.find_by_id("dentist").unwrap();
+    dentist.status = NotaStatus::someday;
+    data.update("dentist", dentist);
+    add(&mut data, "new-task", NotaStatus::next_action, None);
+
+    let report = sync(&mut data, &mut state, &mut server, ConflictPolicy::Newer);
+    assert_eq!(report.sent, vec!["call-bob", "new-task"]);
+    assert_eq!(report.removed, vec!["dentist"]);
+    assert!(server.fields("/tasks/call-bob.ics").completed);
+    assert!(
+        server.todos["/tasks/call-bob.ics"]
+            .1
+            .contains("STATUS:COMPLETED\r\n")
+    );
+    assert!(!server.todos.contains_key("/tasks/dentist.ics"));
+
+    // 完了済みの項目は同期済みのまま、再送されない
+    let report = sync(&mut data, &mut state, &mut server, ConflictPolicy::Newer);
+    assert!(report.sent.is_empty() && report.removed.is_empty());
+}
+
+// 両側で変更された項目が競合ポリシーに従って解決されることを確認
+#[test]
+fn test_conflict_policies() {
+    for (policy, expected_title, kept) in [
+        // サーバーの変更 (04-02) の方が新しい
+        (ConflictPolicy::Newer, "server title", "server"),
+        (ConflictPolicy::Local, "local title", "local"),
+        (ConflictPolicy::Remote, "server title", "server"),
+    ] {
+        let (mut data, mut state, mut server) = synced_setup();
+        let mut bob = data.find_by_id("call-bob").unwrap();
+        bob.title = "local title".to_string();
+        bob.updated_at = today();
+        data.update("call-bob", bob);
+        server.edit("/tasks/call-bob.ics", |todo| {
+            todo.summary = "server title".to_string()
+        });
+
+        let report = sync(&mut data, &mut state, &mut server, policy);
+        assert_eq!(
+            report.conflicts,
+            vec![format!(
+                "call-bob: changed on both sides, kept the {} version",
+                kept
+            )]
+        );
+        assert_eq!(data.find_by_id("call-bob").unwrap().title, expected_title);
+        assert_eq!(server.fields("/tasks/call-bob.ics").summary, expected_title);
+        // 解決後は再び同期済み
+        let report = sync(&mut data, &mut state, &mut server, policy);
+        assert!(report.conflicts.is_empty() && report.sent.is_empty());
+    }
+}
+
+// 状態ファイルを失っても UID で既存のタスクに結び付け直すことを確認
+#[test]
+fn test_relinks_after_lost_state() {
+    let (mut data, _, mut server) = synced_setup();
+    let mut state = SyncState::default();
+    let report = sync(&mut data, &mut state, &mut server, ConflictPolicy::Newer);
+    assert!(report.sent.is_empty() && report.received.is_empty());
+    assert_eq!(server.todos.len(), 2);
+    assert_eq!(state.items["dentist"].href, "/tasks/dentist.ics");
+}
+
+// サーバーの応答 (multistatus と VTODO) を読み取れることを確認
+#[test]
+fn test_parse_server_responses() {
+    let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+ <d:response>
+  <d:href>/remote.php/dav/calendars/me/tasks/a.ics</d:href>
+  <d:propstat><d:prop>
+   <d:getetag>&quot;abc&quot;</d:getetag>
+   <cal:calendar-data>BEGIN:VCALENDAR&#13;
+BEGIN:VTODO&#13;
+UID:a-1&#13;
+SUMMARY:Tom &amp; Jerry\, folded&#13;
+  line&#13;
+DESCRIPTION:first\nsecond&#13;
+DUE:20250310T090000Z&#13;
+STATUS:COMPLETED&#13;
+LAST-MODIFIED:20250309T100000Z&#13;
+END:VTODO&#13;
+END:VCALENDAR&#13;
+</cal:calendar-data>
+  </d:prop></d:propstat>
+ </d:response>
+ <d:response><d:href>/remote.php/dav/calendars/me/tasks/</d:href></d:response>
+</d:multistatus>"#;
+    let responses = parse_multistatus(xml);
+    assert_eq!(responses.len(), 1);
+    let (href, etag, data) = &responses[0];
+    assert_eq!(href, "/remote.php/dav/calendars/me/tasks/a.ics");
+    assert_eq!(etag.as_deref(), Some("\"abc\""));
+
+    let todo = vtodo::parse(data).unwrap();
+    assert_eq!(todo.uid, "a-1");
+    assert_eq!(todo.fields.summary, "Tom & Jerry, folded line");
+    assert_eq!(todo.fields.description.as_deref(), Some("first\nsecond"));
+    assert_eq!(todo.fields.due, Some(date("2025-03-10")));
+    assert!(todo.fields.completed);
+    assert_eq!(todo.last_modified, Some(date("2025-03-09")));
+
+    assert_eq!("server".parse(), Ok(ConflictPolicy::Remote));
+    assert!("mine".parse::<ConflictPolicy>().is_err());
+}
+
+// 設定がない場合の sync_caldav は設定方法を案内することを確認
+#[tokio::test]
+async fn test_sync_caldav_requires_configuration() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    let error = handler.sync_caldav().await.unwrap_err();
+    assert!(
+        error
+            .to_error_object(false)
+            .message
+            .contains("--caldav-url")
+    );
+}
+
+/// A task list whose listing is interrupted by the client's cancellation
+struct CancelOnList<'a> {
+    server: &'a mut MemoryServer,
+    cancellation: Cancellation,
+}
+
+impl CalDavServer for CancelOnList<'_> {
+    fn list(&mut self) -> anyhow::Result<Vec<RemoteTodo>> {
+        self.cancellation.cancel();
+        self.server.list()
+    }
+
+    fn new_href(&self, name: &str) -> String {
+        self.server.new_href(name)
+    }
+
+    fn put(&mut self, href: &str, ics: &str, etag: Option<&str>) -> anyhow::Result<Option<String>> {
+        self.server.put(href, ics, etag)
+    }
+
+    fn delete(&mut self, href: &str, etag: Option<&str>) -> anyhow::Result<()> {
+        self.server.delete(href, etag)
+    }
+}
+
+// 取り消された同期はサーバーのタスクを一つも変更しないことを確認
+#[test]
+fn test_cancelled_sync_leaves_server_unchanged() {
+    let mut data = GtdData::new();
+    add(&mut data, "call-bob", NotaStatus::next_action, None);
+    let mut state = SyncState::default();
+    let mut server = MemoryServer::default();
+
+    // 一覧の取得中に取り消されると、最初の変更の前で止まる
+    let cancellation = Cancellation::default();
+    let mut cancelling = Cancellable::new(
+        CancelOnList {
+            server: &mut server,
+            cancellation: cancellation.clone(),
+        },
+        cancellation.clone(),
+    );
+    let report = caldav::sync(
+        &mut data,
+        &mut state,
+        &mut cancelling,
+        ConflictPolicy::Newer,
+        today(),
+    )
+    .unwrap();
+    assert!(server.todos.is_empty());
+    assert!(
+        report.errors[0].contains("cancelled"),
+        "{:?}",
+        report.errors
+    );
+
+    // 始める前に取り消されていれば一覧も取得しない
+    let mut cancelled = Cancellable::new(MemoryServer::default(), cancellation);
+    let error = caldav::sync(
+        &mut data,
+        &mut state,
+        &mut cancelled,
+        ConflictPolicy::Newer,
+        today(),
+    )
+    .unwrap_err();
+    assert_eq!(error.downcast_ref::<Cancelled>(), Some(&Cancelled));
+}
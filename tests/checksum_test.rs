@@ -0,0 +1,63 @@
+//! Integration tests for the checksum header of the data file
+//!
+//! These tests cover computing and verifying the header, writing it on save,
+//! and detecting a file changed outside the server on load.
+
+mod common;
+
+use common::buy_milk;
+use gtd_mcp::Storage;
+use gtd_mcp::checksum::{self, Verification};
+use std::fs;
+
+// ヘッダー付きの内容が検証を通り、本文を変えると不一致、ヘッダーがなければ Missing になることを確認
+#[test]
+fn test_verify_header() {
+    let content = "[[inbox]]\nid = \"buy-milk\"\n";
+    let digest = checksum::digest(content);
+    assert!(digest.starts_with("fnv1a64:"), "{}", digest);
+    assert_eq!(digest.len(), "fnv1a64:".len() + 16);
+
+    let with_header = checksum::with_header(content);
+    assert_eq!(
+        checksum::verify(&with_header),
+        (Verification::Valid, content)
+    );
+    let edited = with_header.replace("buy-milk", "buy-bread");
+    assert!(matches!(
+        checksum::verify(&edited).0,
+        Verification::Mismatch { .. }
+    ));
+    assert_eq!(checksum::verify(content), (Verification::Missing, content));
+}
+
+// 保存でヘッダーが書かれ、外部で編集されたファイルも読み込めて次の保存でヘッダーが更新されることを確認
+#[test]
+fn test_storage_detects_outside_changes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let storage = Storage::new(&test_path, false);
+    storage.save(&buy_milk()).unwrap();
+
+    let content = fs::read_to_string(&test_path).unwrap();
+    assert!(content.starts_with(checksum::HEADER_PREFIX), "{}", content);
+    assert_eq!(storage.verify_checksum().unwrap(), Verification::Valid);
+    assert!(
+        !storage
+            .load()
+            .unwrap()
+            .is_dirty(&Storage::serialize(&buy_milk()).unwrap())
+    );
+
+    fs::write(&test_path, content.replace("Buy milk", "Buy oat milk")).unwrap();
+    assert!(matches!(
+        storage.verify_checksum().unwrap(),
+        Verification::Mismatch { .. }
+    ));
+    let storage = storage.with_checksum_validation();
+    let data = storage.load().unwrap();
+    assert_eq!(data.find_by_id("buy-milk").unwrap().title, "Buy oat milk");
+
+    storage.save(&data).unwrap();
+    assert_eq!(storage.verify_checksum().unwrap(), Verification::Valid);
+}
@@ -0,0 +1,237 @@
+//! Unit tests for the command-line subcommands
+//!
+//! These tests run the functions behind `list`, `add`, `capture`, `export`,
+//! `validate` and `stats` against temporary data files.
+
+use gtd_mcp::cli::{self, AddOptions, ListOptions};
+use gtd_mcp::mirror::MirrorFormat;
+use gtd_mcp::{GtdData, Nota, NotaStatus, Storage};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn data_file(dir: &TempDir) -> String {
+    dir.path().join("gtd.toml").to_string_lossy().into_owned()
+}
+
+fn add_options(id: &str, title: &str, status: &str) -> AddOptions {
+    AddOptions {
+        id: id.to_string(),
+        title: title.to_string(),
+        status: status.to_string(),
+        ..Default::default()
+    }
+}
+
+// addで作成した項目がファイルに保存され、listで絞り込めることを確認
+#[tokio::test]
+async fn test_add_then_list() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+
+    let message = cli::add(&file, add_options("call-john", "Call John", "inbox"))
+        .await
+        .unwrap();
+    assert!(message.contains("call-john"));
+    cli::add(&file, add_options("buy-milk", "Buy milk", "next_action"))
+        .await
+        .unwrap();
+
+    let options = ListOptions {
+        status: Some("next_action".to_string()),
+        ..Default::default()
+    };
+    let output = cli::list(&file, options).await.unwrap();
+    assert!(output.contains("buy-milk"));
+    assert!(!output.contains("call-john"));
+}
+
+// addの検証エラーがツールと同じメッセージで返されることを確認
+#[tokio::test]
+async fn test_add_reports_validation_errors() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+
+    let error = cli::add(&file, add_options("meeting", "Meeting", "calendar"))
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("start_date"));
+}
+
+// 存在しないファイルを読むサブコマンドはエラーになり、ファイルを作らないことを確認
+#[tokio::test]
+async fn test_read_only_commands_require_existing_file() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+
+    assert!(cli::list(&file, ListOptions::default()).await.is_err());
+    assert!(cli::stats(&file).is_err());
+    assert!(cli::validate(&file).is_err());
+    assert!(cli::export(&file, MirrorFormat::Json).is_err());
+    assert!(!PathBuf::from(&file).exists());
+}
+
+// exportがJSONとMarkdownの両形式で全項目を出力することを確認
+#[tokio::test]
+async fn test_export_formats() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+    cli::add(&file, add_options("call-john", "Call John", "inbox"))
+        .await
+        .unwrap();
+
+    let json = cli::export(&file, MirrorFormat::Json).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value.to_string().contains("call-john"));
+
+    let markdown = cli::export(&file, MirrorFormat::Markdown).unwrap();
+    assert!(markdown.contains("Call John"));
+}
+
+// JSON Lines出力が1行1項目で、各行が単独のJSONとして読めることを確認
+#[tokio::test]
+async fn test_export_json_lines() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+    cli::add(&file, add_options("call-john", "Call John", "inbox"))
+        .await
+        .unwrap();
+    cli::add(&file, add_options("buy-milk", "Buy milk", "next_action"))
+        .await
+        .unwrap();
+
+    let mut out = Vec::new();
+    cli::export_to(&file, MirrorFormat::JsonLines, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let items: Vec<serde_json::Value> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().any(|item| item["id"] == "buy-milk"));
+    assert!(items.iter().all(|item| item["status"].is_string()));
+    // 文字列で返す export も同じ内容になる
+    assert_eq!(cli::export(&file, MirrorFormat::JsonLines).unwrap(), text);
+    assert_eq!("jsonl".parse(), Ok(MirrorFormat::JsonLines));
+    assert_eq!(
+        MirrorFormat::from_path("items.ndjson"),
+        Some(MirrorFormat::JsonLines)
+    );
+}
+
+// validateが整合性の問題を列挙し、問題がなければOKを返すことを確認
+#[tokio::test]
+async fn test_validate_reports_integrity_problems() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+    cli::add(&file, add_options("call-john", "Call John", "inbox"))
+        .await
+        .unwrap();
+
+    let report = cli::validate(&file).unwrap();
+    assert!(report.is_valid());
+    assert_eq!(report.to_string(), "OK: 1 items, no problems found");
+
+    let storage = Storage::new(&file, false);
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "orphan".to_string(),
+        title: "Orphan".to_string(),
+        status: NotaStatus::next_action,
+        project: Some("missing".to_string()),
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+
+    let report = cli::validate(&file).unwrap();
+    assert!(!report.is_valid());
+    assert!(
+        report
+            .to_string()
+            .contains("orphan: project 'missing' does not exist")
+    );
+}
+
+// statsがステータスごとの件数を数えることを確認
+#[tokio::test]
+async fn test_stats_counts_per_status() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+    cli::add(&file, add_options("a", "Alpha", "inbox"))
+        .await
+        .unwrap();
+    cli::add(&file, add_options("b", "Bravo", "next_action"))
+        .await
+        .unwrap();
+    cli::add(&file, add_options("c", "Charlie", "next_action"))
+        .await
+        .unwrap();
+
+    let stats = cli::stats(&file).unwrap();
+    assert!(stats.starts_with("Total: 3 items\n"));
+    assert!(stats.contains("inbox:       1\n"));
+    assert!(stats.contains("next_action: 2\n"));
+    assert!(stats.contains("Calendar items due today or earlier: 0"));
+}
+
+// プロジェクトの進捗がstatsとlist(status="project")に表示されることを確認
+#[tokio::test]
+async fn test_project_progress_in_stats_and_list() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+    cli::add(&file, add_options("launch", "Launch", "project"))
+        .await
+        .unwrap();
+    for (id, status) in [("copy", "done"), ("venue", "next_action")] {
+        let options = AddOptions {
+            project: Some("launch".to_string()),
+            ..add_options(id, id, status)
+        };
+        cli::add(&file, options).await.unwrap();
+    }
+
+    let stats = cli::stats(&file).unwrap();
+    assert!(
+        stats.ends_with("Projects:\n  launch: 1/2 done (50%)\n"),
+        "{}",
+        stats
+    );
+
+    let options = ListOptions {
+        status: Some("project".to_string()),
+        ..Default::default()
+    };
+    let output = cli::list(&file, options).await.unwrap();
+    assert!(
+        output.contains("  Progress: 1/2 done (50%)\n"),
+        "{}",
+        output
+    );
+}
+
+// captureが行ごとにタイトルから生成したIDでinbox項目を作成することを確認
+#[tokio::test]
+async fn test_capture_generates_ids_from_titles() {
+    let dir = TempDir::new().unwrap();
+    let file = data_file(&dir);
+    let lines = ["Buy milk", "", "  Call John re: Q3!  ", "Buy milk"]
+        .map(String::from)
+        .to_vec();
+
+    let output = cli::capture(&file, lines).await.unwrap();
+    assert_eq!(output.matches("Item created").count(), 3);
+
+    let data = Storage::new(&file, false).load_local().unwrap();
+    assert_eq!(
+        data.find_by_id("buy-milk").unwrap().status,
+        NotaStatus::inbox
+    );
+    assert_eq!(
+        data.find_by_id("call-john-re-q3").unwrap().title,
+        "Call John re: Q3!"
+    );
+    assert!(data.find_by_id("buy-milk-2").is_some());
+
+    // 空行だけの入力はエラー
+    let error = cli::capture(&file, vec![String::new()]).await.unwrap_err();
+    assert!(error.to_string().contains("Nothing to capture"));
+}
@@ -0,0 +1,186 @@
+//! Integration tests for the injectable clock
+//!
+//! These tests run a handler on a [`FixedClock`] and move it forward to check
+//! that dates, recurrence, stale flags and the tickler follow the clock
+//! instead of the system time.
+
+mod common;
+
+use chrono::Duration;
+use common::date;
+use gtd_mcp::mirror::MirrorFormat;
+use gtd_mcp::service::{ItemUpdate, ListQuery, NewItem};
+use gtd_mcp::{FixedClock, GtdServerHandler, NotaStatus, Storage, gtd::RecurrencePattern};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn handler(dir: &TempDir, clock: Arc<FixedClock>) -> GtdServerHandler {
+    GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .clock(clock)
+        .build()
+        .unwrap()
+}
+
+// 作成日・状態変更日・繰り返しの次回が時計の日付になることを確認
+#[tokio::test]
+async fn test_dates_follow_the_clock() {
+    let dir = TempDir::new().unwrap();
+    let clock = Arc::new(FixedClock::on(date("2030-01-10")));
+    let handler = handler(&dir, clock.clone());
+    let service = handler.service();
+
+    let created = service
+        .inbox(NewItem {
+            recurrence: Some(RecurrencePattern::daily),
+            ..NewItem::new("stretch", "Stretch", NotaStatus::next_action)
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.nota.created_at, date("2030-01-10"));
+    assert_eq!(handler.clock().today(), date("2030-01-10"));
+
+    clock.advance(Duration::days(5));
+    let changes = service
+        .change_status(&["stretch".to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+    let next = changes.changed[0].next_occurrence.as_ref().unwrap();
+    assert_eq!(next.id, "stretch-20300116");
+    assert_eq!(next.created_at, date("2030-01-15"));
+
+    let done = handler.data.read().await.find_by_id("stretch").unwrap();
+    assert_eq!(done.updated_at, date("2030-01-15"));
+    assert_eq!(done.status_since, Some(date("2030-01-15")));
+}
+
+// 取り込み・更新の日付、iCalendar ミラーとスナップショットも時計に従うことを確認
+#[tokio::test]
+async fn test_storage_and_updates_follow_the_clock() {
+    let dir = TempDir::new().unwrap();
+    let mirror = dir.path().join("gtd.ics");
+    let clock = Arc::new(FixedClock::on(date("2030-01-10")));
+    let handler = GtdServerHandler::builder()
+        .storage(
+            Storage::new(dir.path().join("gtd.toml"), false)
+                .with_mirror(&mirror, MirrorFormat::Ical),
+        )
+        .clock(clock.clone())
+        .build()
+        .unwrap();
+
+    handler.capture("Buy milk".to_string(), None).await.unwrap();
+    handler
+        .service()
+        .inbox(NewItem {
+            start_date: Some(date("2030-01-10")),
+            recurrence: Some(RecurrencePattern::daily),
+            ..NewItem::new("stretch", "Stretch", NotaStatus::calendar)
+        })
+        .await
+        .unwrap();
+    {
+        let data = handler.data.read().await;
+        let milk = data.find_by_id("buy-milk").unwrap();
+        assert_eq!(milk.created_at, date("2030-01-10"));
+        assert_eq!(milk.updated_at, date("2030-01-10"));
+    }
+    // 次回の予定は時計の日付から 90 日先まで書き出される
+    let ics = std::fs::read_to_string(&mirror).unwrap();
+    assert!(ics.contains("DTSTART;VALUE=DATE:20300111"), "{}", ics);
+
+    clock.advance(Duration::days(3));
+    handler
+        .service()
+        .update(
+            "buy-milk",
+            ItemUpdate {
+                title: Some("Buy oat milk".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let milk = handler.data.read().await.find_by_id("buy-milk").unwrap();
+    assert_eq!(milk.created_at, date("2030-01-10"));
+    assert_eq!(milk.updated_at, date("2030-01-13"));
+    let ics = std::fs::read_to_string(&mirror).unwrap();
+    assert!(!ics.contains("DTSTART;VALUE=DATE:20300112"), "{}", ics);
+    assert!(ics.contains("DTSTART;VALUE=DATE:20300113"), "{}", ics);
+
+    let snapshot = handler.storage().snapshot().unwrap();
+    assert_eq!(snapshot.taken_at().date(), date("2030-01-13"));
+}
+
+// 時計を進めると list の停滞表示と期日到来の昇格が変わることを確認
+#[tokio::test]
+async fn test_advancing_the_clock_ages_items() {
+    let dir = TempDir::new().unwrap();
+    let clock = Arc::new(FixedClock::on(date("2030-03-01")));
+    let handler = handler(&dir, clock.clone());
+    let service = handler.service();
+    service
+        .inbox(NewItem::new(
+            "call-bob",
+            "Call Bob",
+            NotaStatus::next_action,
+        ))
+        .await
+        .unwrap();
+    service
+        .inbox(NewItem {
+            start_date: Some(date("2030-03-20")),
+            ..NewItem::new("dentist", "Dentist", NotaStatus::calendar)
+        })
+        .await
+        .unwrap();
+
+    let list = handler
+        .list(
+            Some("next_action".to_string()),
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(!list.contains('⚠'), "{}", list);
+    assert!(
+        handler
+            .promote_due_calendar_items(handler.clock().today())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    clock.advance(Duration::days(21));
+    let list = handler
+        .list(
+            Some("next_action".to_string()),
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(list.contains("⚠ 21d"), "{}", list);
+    let promoted = handler
+        .promote_due_calendar_items(handler.clock().today())
+        .await
+        .unwrap();
+    assert_eq!(promoted, ["dentist"]);
+    let next = service
+        .list(ListQuery {
+            status: Some(NotaStatus::next_action),
+            ..Default::default()
+        })
+        .await;
+    let dentist = next.notas.iter().find(|n| n.id == "dentist").unwrap();
+    assert_eq!(dentist.status_since, Some(date("2030-03-22")));
+}
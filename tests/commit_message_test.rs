@@ -0,0 +1,91 @@
+//! Unit tests for commit message templates
+//!
+//! These tests verify placeholder substitution, optional groups, batch
+//! rendering, and template validation.
+
+use gtd_mcp::{CommitItem, CommitTemplate, NotaStatus};
+
+fn item(old_status: Option<NotaStatus>, project: Option<&str>) -> CommitItem {
+    CommitItem {
+        id: "meeting-prep".to_string(),
+        title: "Prepare slides".to_string(),
+        old_status,
+        new_status: NotaStatus::next_action,
+        project: project.map(str::to_string),
+        context: None,
+    }
+}
+
+// デフォルトテンプレートでタイトル・ステータス遷移・プロジェクトが含まれることを確認
+#[test]
+fn test_default_template_includes_details() {
+    let template = CommitTemplate::default();
+    let message = template.render(
+        "Update",
+        &[item(Some(NotaStatus::inbox), Some("q1-launch"))],
+    );
+    assert_eq!(
+        message,
+        "Update meeting-prep: Prepare slides (inbox → next_action) in q1-launch"
+    );
+}
+
+// 値が空のプレースホルダーを含む [...] グループは省略されることを確認
+#[test]
+fn test_optional_groups_are_dropped_when_empty() {
+    let template = CommitTemplate::default();
+    assert_eq!(
+        template.render("Add", &[item(None, None)]),
+        "Add meeting-prep: Prepare slides (next_action)"
+    );
+
+    // グループ外の空プレースホルダーはメッセージ全体を消さない
+    let template = CommitTemplate::new("{action} {title} {project}").unwrap();
+    assert_eq!(
+        template.render("Add", &[item(None, None)]),
+        "Add Prepare slides"
+    );
+}
+
+#[test]
+fn test_custom_template_placeholders() {
+    let template = CommitTemplate::new("[{project}: ]{title} [{old_status}→]{new_status}").unwrap();
+    assert_eq!(
+        template.render("Update", &[item(Some(NotaStatus::inbox), Some("q1"))]),
+        "q1: Prepare slides inbox→next_action"
+    );
+    assert_eq!(
+        template.render("Add", &[item(None, None)]),
+        "Prepare slides next_action"
+    );
+}
+
+// 複数アイテムは見出し + 箇条書きになることを確認
+#[test]
+fn test_batch_rendering() {
+    let template = CommitTemplate::default();
+    let mut second = item(Some(NotaStatus::inbox), None);
+    second.id = "call-bob".to_string();
+    second.title = "Call Bob".to_string();
+    let message = template.render(
+        "Change status",
+        &[item(Some(NotaStatus::inbox), None), second],
+    );
+    assert_eq!(
+        message,
+        "Change status: 2 items\n\n\
+         - Change status meeting-prep: Prepare slides (inbox → next_action)\n\
+         - Change status call-bob: Call Bob (inbox → next_action)"
+    );
+    assert_eq!(template.render("Empty trash", &[]), "Empty trash");
+}
+
+#[test]
+fn test_invalid_templates_are_rejected() {
+    let error = CommitTemplate::new("{action} {unknown}").unwrap_err();
+    assert!(error.contains("Unknown placeholder '{unknown}'"));
+    assert!("[{title}".parse::<CommitTemplate>().is_err());
+    assert!("{title}]".parse::<CommitTemplate>().is_err());
+    assert!("[[{title}]]".parse::<CommitTemplate>().is_err());
+    assert!("{title}}".parse::<CommitTemplate>().is_err());
+}
@@ -0,0 +1,131 @@
+//! Synthetic GTD datasets for benchmarks
+//!
+//! Generates deterministic data shaped like a long-lived GTD file: mostly tasks
+//! spread over every status, linked to a pool of projects and contexts, with
+//! Markdown notes of varying length. The same size always yields the same data,
+//! so timings are comparable between runs.
+
+#![allow(dead_code)]
+
+use chrono::{Duration, NaiveDate};
+use gtd_mcp::{GtdData, Nota, NotaStatus};
+
+/// Task statuses in the proportions a typical GTD file accumulates them
+const TASK_STATUSES: [NotaStatus; 10] = [
+    NotaStatus::done,
+    NotaStatus::done,
+    NotaStatus::done,
+    NotaStatus::next_action,
+    NotaStatus::next_action,
+    NotaStatus::inbox,
+    NotaStatus::waiting_for,
+    NotaStatus::someday,
+    NotaStatus::calendar,
+    NotaStatus::reference,
+];
+
+/// Words used to build titles and notes
+const WORDS: [&str; 16] = [
+    "review", "budget", "call", "draft", "meeting", "invoice", "plan", "email", "report", "garden",
+    "travel", "fix", "order", "agenda", "backup", "renew",
+];
+
+/// Number of projects for a dataset with `size` notas (one per 100 notas)
+pub fn project_count(size: usize) -> usize {
+    (size / 100).max(1)
+}
+
+/// Number of contexts in every dataset
+pub const CONTEXT_COUNT: usize = 12;
+
+/// ID of the `n`-th generated project
+pub fn project_id(n: usize) -> String {
+    format!("project-{}", n)
+}
+
+/// Name of the `n`-th generated context
+pub fn context_name(n: usize) -> String {
+    format!("@context-{}", n)
+}
+
+/// ID of the `n`-th generated task
+pub fn task_id(n: usize) -> String {
+    format!("task-{}", n)
+}
+
+/// Generate a dataset with `size` notas in total
+///
+/// # Arguments
+/// * `size` - Total number of notas (projects and contexts included)
+pub fn generate(size: usize) -> GtdData {
+    let base = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let projects = project_count(size);
+    let mut data = GtdData::new();
+
+    for n in 0..CONTEXT_COUNT {
+        data.add(Nota {
+            id: context_name(n),
+            title: format!("Context {}", n),
+            status: NotaStatus::context,
+            created_at: base,
+            updated_at: base,
+            ..Default::default()
+        });
+    }
+    for n in 0..projects {
+        data.add(Nota {
+            id: project_id(n),
+            title: format!("Project {} {}", n, WORDS[n % WORDS.len()]),
+            status: NotaStatus::project,
+            notes: Some(notes(n, 3)),
+            created_at: base,
+            updated_at: base,
+            ..Default::default()
+        });
+    }
+
+    let tasks = size.saturating_sub(CONTEXT_COUNT + projects);
+    for n in 0..tasks {
+        let status = TASK_STATUSES[n % TASK_STATUSES.len()].clone();
+        let created = base + Duration::days((n % 700) as i64);
+        data.add(Nota {
+            id: task_id(n),
+            title: format!(
+                "{} {} {}",
+                WORDS[n % WORDS.len()],
+                WORDS[(n / 7) % WORDS.len()],
+                n
+            ),
+            start_date: matches!(status, NotaStatus::calendar)
+                .then(|| created + Duration::days(30)),
+            status,
+            // Two in three tasks belong to a project, three in four have a context
+            project: (n % 3 != 0).then(|| project_id(n % projects)),
+            context: (n % 4 != 0).then(|| context_name(n % CONTEXT_COUNT)),
+            // Note lengths range from none to a few kilobytes
+            notes: (n % 5 != 0).then(|| notes(n, n % 40)),
+            created_at: created,
+            updated_at: created + Duration::days((n % 11) as i64),
+            ..Default::default()
+        });
+    }
+    data
+}
+
+/// Markdown notes with `lines` lines of text derived from `seed`
+fn notes(seed: usize, lines: usize) -> String {
+    (0..=lines)
+        .map(|line| {
+            let word = |k: usize| WORDS[(seed + line * 5 + k) % WORDS.len()];
+            format!(
+                "- {} the {} before {} ({}/{})",
+                word(0),
+                word(1),
+                word(2),
+                seed,
+                line
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
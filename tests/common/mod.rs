@@ -2,6 +2,7 @@
 
 /// Extract task ID from inbox() response message
 /// Response format: "Nota created with ID: <id> (type: task)"
+#[allow(dead_code)]
 pub fn extract_id_from_response(response: &str) -> String {
     // Parse "Nota created with ID: <id> (type: ...)"
     if let Some(start) = response.find("ID: ") {
@@ -18,3 +19,106 @@ pub fn extract_id_from_response(response: &str) -> String {
         .trim_end_matches(')')
         .to_string()
 }
+
+/// Create a Git working repository whose `origin` is a local bare repository
+///
+/// The working repository has one initial commit that is already pushed to
+/// `origin`, so pull/commit/push all succeed without network access.
+/// Returns (working dir, bare remote dir).
+#[allow(dead_code)]
+pub fn setup_repo_with_remote() -> (tempfile::TempDir, tempfile::TempDir) {
+    use git2::{Repository, Signature, Time};
+    use std::path::Path;
+
+    let remote_dir = tempfile::TempDir::new().unwrap();
+    Repository::init_bare(remote_dir.path()).unwrap();
+
+    let work_dir = tempfile::TempDir::new().unwrap();
+    let repo = Repository::init(work_dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+
+    // 初期コミットを作成
+    std::fs::write(work_dir.path().join("dummy.txt"), "dummy").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("dummy.txt")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let time = Time::new(1_700_000_000, 0);
+    let signature = Signature::new("Test User", "test@example.com", &time).unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "Initial commit",
+        &tree,
+        &[],
+    )
+    .unwrap();
+
+    // origin を登録して初期コミットを push
+    let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+    let mut remote = repo
+        .remote("origin", remote_dir.path().to_str().unwrap())
+        .unwrap();
+    remote
+        .push(&[format!("refs/heads/{}", branch).as_str()], None)
+        .unwrap();
+
+    (work_dir, remote_dir)
+}
+
+/// Clone a bare remote into a new working directory, simulating another machine
+///
+/// The clone has its own user identity configured so it can commit.
+#[allow(dead_code)]
+pub fn clone_remote(remote_dir: &std::path::Path) -> tempfile::TempDir {
+    let other_dir = tempfile::TempDir::new().unwrap();
+    let repo = git2::Repository::clone(remote_dir.to_str().unwrap(), other_dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Other Machine").unwrap();
+    config.set_str("user.email", "other@example.com").unwrap();
+    other_dir
+}
+
+/// A next action titled after its ID (`call-bank` is "call bank")
+///
+/// Tests set the fields they need with struct update syntax:
+/// `Nota { estimate: Some(10), ..common::action("call-bank") }`.
+#[allow(dead_code)]
+pub fn action(id: &str) -> gtd_mcp::Nota {
+    gtd_mcp::Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status: gtd_mcp::NotaStatus::next_action,
+        ..Default::default()
+    }
+}
+
+/// A date written as `YYYY-MM-DD`
+#[allow(dead_code)]
+pub fn date(s: &str) -> chrono::NaiveDate {
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+/// The fixed "today" of the import, export and sync tests
+#[allow(dead_code)]
+pub fn today() -> chrono::NaiveDate {
+    date("2025-04-01")
+}
+
+/// Data with one inbox item, `buy-milk`
+#[allow(dead_code)]
+pub fn buy_milk() -> gtd_mcp::GtdData {
+    let mut data = gtd_mcp::GtdData::new();
+    data.add(gtd_mcp::Nota {
+        id: "buy-milk".to_string(),
+        title: "Buy milk".to_string(),
+        ..Default::default()
+    });
+    data
+}
+
+/// Synthetic datasets shared with the benchmarks
+pub mod dataset;
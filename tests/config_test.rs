@@ -0,0 +1,204 @@
+//! Unit tests for the configuration file
+//!
+//! These tests verify loading the file, resolving paths, and how command-line
+//! options, environment variables and file values are combined.
+
+use chrono::FixedOffset;
+use clap::Parser;
+use gtd_mcp::DuplicateCheck;
+use gtd_mcp::config::{Config, ServeOptions, parse_utc_offset};
+use gtd_mcp::mirror::MirrorFormat;
+use gtd_mcp::wip::WipMode;
+use gtd_mcp::workspace::{WorkspaceSpec, default_workspace_name};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// `gtd-mcp serve` options parsed the same way as the binary
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    serve: ServeOptions,
+}
+
+fn write_config(dir: &TempDir, content: &str) -> std::path::PathBuf {
+    let path = dir.path().join("config.toml");
+    fs::write(&path, content).unwrap();
+    path
+}
+
+// 設定ファイルの各キーが読み込まれ、相対パスが設定ファイルの場所から解決されることを確認
+#[test]
+fn test_load_config_file() {
+    let dir = TempDir::new().unwrap();
+    let path = write_config(
+        &dir,
+        r#"
+file = "gtd.toml"
+
+[serve]
+sync_git = true
+git_author_name = "GTD Bot"
+mirror = "mirror/gtd.md"
+mirror_format = "markdown"
+duplicate_titles = "reject"
+wip_limits = ["next_action=10"]
+wip_mode = "enforce"
+debounce_ms = 500
+timezone = "+09:00"
+workspaces = ["personal=personal.toml", "shared=/srv/gtd/shared.toml"]
+"#,
+    );
+
+    let config = Config::load(&path).unwrap();
+    assert_eq!(
+        config.file.as_deref().map(Path::new),
+        Some(dir.path().join("gtd.toml").as_path())
+    );
+    let serve = config.serve;
+    assert!(serve.sync_git);
+    assert!(!serve.strict);
+    assert_eq!(serve.git_author_name.as_deref(), Some("GTD Bot"));
+    assert_eq!(
+        serve.mirror.as_deref().map(Path::new),
+        Some(dir.path().join("mirror/gtd.md").as_path())
+    );
+    assert_eq!(serve.mirror_format, Some(MirrorFormat::Markdown));
+    assert_eq!(serve.duplicate_titles, Some(DuplicateCheck::Reject));
+    assert_eq!(serve.wip_limits, vec!["next_action=10".parse().unwrap()]);
+    assert_eq!(serve.wip_mode, Some(WipMode::Enforce));
+    assert_eq!(serve.debounce_ms, Some(500));
+    assert_eq!(serve.timezone, FixedOffset::east_opt(9 * 3600));
+    let workspaces: Vec<_> = serve
+        .workspaces
+        .iter()
+        .map(|w| (w.name.as_str(), w.path.clone()))
+        .collect();
+    assert_eq!(
+        workspaces,
+        vec![
+            ("personal", dir.path().join("personal.toml")),
+            ("shared", Path::new("/srv/gtd/shared.toml").to_path_buf()),
+        ]
+    );
+}
+
+// ワークスペースは NAME=PATH 形式で指定し、名前は英数字・'-'・'_' に限られることを確認
+#[test]
+fn test_parse_workspace_spec() {
+    let spec: WorkspaceSpec = "personal=~/gtd/personal.toml".parse().unwrap();
+    assert_eq!(spec.name, "personal");
+    assert_eq!(spec.path, Path::new("~/gtd/personal.toml"));
+    assert!("personal.toml".parse::<WorkspaceSpec>().is_err());
+    assert!("my space=a.toml".parse::<WorkspaceSpec>().is_err());
+    assert!("personal=".parse::<WorkspaceSpec>().is_err());
+    assert_eq!(
+        default_workspace_name(Path::new("/home/me/work.toml")),
+        "work"
+    );
+}
+
+// 未知のキーや不正な値はエラーになり、ファイル名が示されることを確認
+#[test]
+fn test_invalid_config_is_rejected() {
+    let dir = TempDir::new().unwrap();
+
+    let path = write_config(&dir, "[serve]\nsync_gti = true\n");
+    let error = Config::load(&path).unwrap_err().to_string();
+    assert!(error.contains("config.toml"));
+    assert!(error.contains("sync_gti"));
+
+    let path = write_config(&dir, "[serve]\nduplicate_titles = \"maybe\"\n");
+    let error = Config::load(&path).unwrap_err().to_string();
+    assert!(error.contains("Invalid duplicate check 'maybe'"));
+
+    // 明示的に指定したファイルが存在しない場合もエラー
+    assert!(Config::discover(Some(&dir.path().join("missing.toml"))).is_err());
+}
+
+// コマンドラインの値が設定ファイルより優先され、未指定の値は設定ファイルから補われることを確認
+#[test]
+fn test_command_line_overrides_config() {
+    let command_line = ServeOptions {
+        git_author_name: Some("Alice".to_string()),
+        strict: true,
+        ..Default::default()
+    };
+    let file = ServeOptions {
+        sync_git: true,
+        git_author_name: Some("GTD Bot".to_string()),
+        git_author_email: Some("bot@example.com".to_string()),
+        debounce_ms: Some(250),
+        ..Default::default()
+    };
+
+    let merged = command_line.or(file);
+    assert!(merged.sync_git);
+    assert!(merged.strict);
+    assert_eq!(merged.git_author_name.as_deref(), Some("Alice"));
+    assert_eq!(merged.git_author_email.as_deref(), Some("bot@example.com"));
+    assert_eq!(merged.debounce_ms, Some(250));
+    assert_eq!(merged.duplicate_titles, None);
+}
+
+// タイムゾーンはUTCオフセットまたはUTCで指定できることを確認
+#[test]
+fn test_parse_utc_offset() {
+    assert_eq!(
+        parse_utc_offset("+09:00").unwrap(),
+        FixedOffset::east_opt(9 * 3600).unwrap()
+    );
+    assert_eq!(
+        parse_utc_offset("-05:30").unwrap(),
+        FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap()
+    );
+    assert_eq!(
+        parse_utc_offset("UTC").unwrap(),
+        FixedOffset::east_opt(0).unwrap()
+    );
+    assert!(parse_utc_offset("Asia/Tokyo").is_err());
+}
+
+// 環境変数でオプションを指定でき、コマンドラインの値が環境変数より優先されることを確認
+// (環境変数を変更するテストはこの1つだけにして、並列実行での干渉を避ける)
+#[test]
+fn test_options_from_environment() {
+    // SAFETY: このテストバイナリで環境変数を読み書きするのはこのテストだけ
+    unsafe {
+        std::env::set_var("GTD_MCP_SYNC_GIT", "yes");
+        std::env::set_var("GTD_MCP_STRICT", "0");
+        std::env::set_var("GTD_MCP_GIT_AUTHOR_NAME", "From Env");
+        std::env::set_var("GTD_MCP_DEBOUNCE_MS", "750");
+        std::env::set_var("GTD_MCP_TIMEZONE", "UTC");
+    }
+
+    let options = Cli::try_parse_from(["gtd-mcp"]).unwrap().serve;
+    assert!(options.sync_git);
+    assert!(!options.strict);
+    assert_eq!(options.git_author_name.as_deref(), Some("From Env"));
+    assert_eq!(options.debounce_ms, Some(750));
+    assert_eq!(options.timezone, FixedOffset::east_opt(0));
+
+    let options = Cli::try_parse_from(["gtd-mcp", "--git-author-name", "From Args", "--strict"])
+        .unwrap()
+        .serve;
+    assert_eq!(options.git_author_name.as_deref(), Some("From Args"));
+    assert!(options.strict);
+
+    unsafe {
+        std::env::set_var("GTD_MCP_SYNC_GIT", "maybe");
+    }
+    assert!(Cli::try_parse_from(["gtd-mcp"]).is_err());
+
+    unsafe {
+        for name in [
+            "GTD_MCP_SYNC_GIT",
+            "GTD_MCP_STRICT",
+            "GTD_MCP_GIT_AUTHOR_NAME",
+            "GTD_MCP_DEBOUNCE_MS",
+            "GTD_MCP_TIMEZONE",
+        ] {
+            std::env::remove_var(name);
+        }
+    }
+}
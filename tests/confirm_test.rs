@@ -0,0 +1,124 @@
+//! Integration tests for the confirmation of destructive tools
+//!
+//! These tests call destructive tools as an MCP client, through
+//! [`SharedServer`] and on a plain handler as stdio serves it, and check that
+//! they only run with the code of the summary the user saw.
+
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus, Storage};
+use mcp_attr::client::McpClient;
+use mcp_attr::schema::{CallToolRequestParams, CallToolResultContentItem};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn trashed(id: &str) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: format!("Old {}", id),
+        status: NotaStatus::trash,
+        ..Default::default()
+    }
+}
+
+async fn call(client: &McpClient, params: CallToolRequestParams) -> String {
+    let result = client.tools_call(params).await.unwrap();
+    match &result.content[0] {
+        CallToolResultContentItem::TextContent(text) => text.text.clone(),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+/// The code in a confirmation request
+fn code_of(request: &str) -> String {
+    let (_, rest) = request.split_once("confirm=\"").unwrap();
+    rest.split('"').next().unwrap().to_string()
+}
+
+// empty_trash は要約を返して削除せず、コードを付けた再呼び出しでのみ削除することを確認
+#[tokio::test]
+async fn test_empty_trash_waits_for_confirmation() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gtd.toml");
+    let mut data = GtdData::new();
+    data.add(trashed("flyer"));
+    Storage::new(&path, false).save(&data).unwrap();
+    let handler = Arc::new(GtdServerHandler::new(path.to_str().unwrap(), false).unwrap());
+    let client = McpClient::with_server(SharedServer::new(handler.clone()))
+        .await
+        .unwrap();
+
+    let request = call(&client, CallToolRequestParams::new("empty_trash")).await;
+    assert!(request.contains("Confirmation needed"), "{}", request);
+    assert!(request.contains("- [flyer] Old flyer"), "{}", request);
+    assert!(handler.data.read().await.find_by_id("flyer").is_some());
+
+    // 違うコードでは実行しない
+    let wrong = CallToolRequestParams::new("empty_trash")
+        .with_argument("confirm", "00000000")
+        .unwrap();
+    assert!(call(&client, wrong).await.contains("Confirmation needed"));
+
+    // 要約の後にゴミ箱が増えたら、古いコードでは実行せずに確認し直す
+    let code = code_of(&request);
+    handler.data.write().await.add(trashed("menu"));
+    let stale = CallToolRequestParams::new("empty_trash")
+        .with_argument("confirm", &code)
+        .unwrap();
+    let request = call(&client, stale).await;
+    assert!(request.contains("- [menu] Old menu"), "{}", request);
+    assert_eq!(handler.data.read().await.task_count(), 2);
+
+    let confirmed = CallToolRequestParams::new("empty_trash")
+        .with_argument("confirm", code_of(&request))
+        .unwrap();
+    let result = call(&client, confirmed).await;
+    assert!(result.contains("Deleted 2 task(s)"), "{}", result);
+    assert_eq!(handler.data.read().await.task_count(), 0);
+}
+
+// 消すものがない呼び出しは確認なしで実行し、破壊的なツールの一覧には confirm 引数が付くことを確認
+#[tokio::test]
+async fn test_nothing_to_destroy_runs_directly() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    let client = McpClient::with_server(handler).await.unwrap();
+
+    let result = call(&client, CallToolRequestParams::new("empty_trash")).await;
+    assert!(result.contains("Deleted 0 task(s)"), "{}", result);
+    let result = call(&client, CallToolRequestParams::new("dedupe")).await;
+    assert!(result.contains("No likely duplicates"), "{}", result);
+
+    let tools = client.tools_list(None).await.unwrap().tools;
+    for tool in &tools {
+        let has_confirm = tool.input_schema.properties.contains_key("confirm");
+        let destructive = ["empty_trash", "dedupe", "abort", "reload", "restore_backup"]
+            .contains(&tool.name.as_str());
+        assert_eq!(has_confirm, destructive, "{}", tool.name);
+    }
+}
+
+// stdio と同じく素のハンドラを渡しても、確認コードなしでは削除しないことを確認
+#[tokio::test]
+async fn test_plain_handler_waits_for_confirmation() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gtd.toml");
+    let mut data = GtdData::new();
+    data.add(trashed("flyer"));
+    Storage::new(&path, false).save(&data).unwrap();
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    let client = McpClient::with_server(handler).await.unwrap();
+
+    let request = call(&client, CallToolRequestParams::new("empty_trash")).await;
+    assert!(request.contains("Confirmation needed"), "{}", request);
+    let stored = Storage::new(&path, false).load_local().unwrap();
+    assert!(stored.find_by_id("flyer").is_some());
+
+    let confirmed = CallToolRequestParams::new("empty_trash")
+        .with_argument("confirm", code_of(&request))
+        .unwrap();
+    let result = call(&client, confirmed).await;
+    assert!(result.contains("Deleted 1 task(s)"), "{}", result);
+    let stored = Storage::new(&path, false).load_local().unwrap();
+    assert!(stored.find_by_id("flyer").is_none());
+}
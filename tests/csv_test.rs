@@ -0,0 +1,161 @@
+//! Unit tests for the CSV export and import
+//!
+//! These tests verify the column mapping, quoting, merging by ID and the
+//! all-or-nothing behavior of imports.
+
+mod common;
+
+use common::{date, today};
+use gtd_mcp::cli;
+use gtd_mcp::csv;
+use gtd_mcp::gtd::{Energy, Priority, RecurrencePattern};
+use gtd_mcp::import::{self, ImportFormat};
+use gtd_mcp::{GtdData, Nota, NotaStatus, Storage};
+use tempfile::TempDir;
+
+fn sample_data() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "website".to_string(),
+        title: "Website".to_string(),
+        status: NotaStatus::project,
+        created_at: date("2025-01-01"),
+        updated_at: date("2025-01-01"),
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "@phone".to_string(),
+        title: "Calls".to_string(),
+        status: NotaStatus::context,
+        created_at: date("2025-01-01"),
+        updated_at: date("2025-01-01"),
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "call-john".to_string(),
+        title: "Call John, re: \"Q3\"".to_string(),
+        status: NotaStatus::calendar,
+        project: Some("website".to_string()),
+        context: Some("@phone".to_string()),
+        notes: Some("First line\nSecond line".to_string()),
+        start_date: Some(date("2025-03-10")),
+        recurrence_pattern: Some(RecurrencePattern::weekly),
+        recurrence_config: Some("Monday,Friday".to_string()),
+        link: Some("https://example.com/issues/7".to_string()),
+        priority: Some(Priority::high),
+        due_date: Some(date("2025-03-14")),
+        estimate: Some(30),
+        energy: Some(Energy::low),
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
+        created_at: date("2025-01-02"),
+        updated_at: date("2025-01-03"),
+    });
+    data
+}
+
+// 書き出した CSV を読み込むと同じ項目が復元されることを確認
+#[test]
+fn test_render_and_import_roundtrip() {
+    let data = sample_data();
+    let rendered = csv::render(&data);
+    assert!(rendered.starts_with(&format!("{}\n", csv::COLUMNS.join(","))));
+    assert!(rendered.contains("\"Call John, re: \"\"Q3\"\"\""));
+    assert!(rendered.contains("\"First line\nSecond line\""));
+    assert!(rendered.contains("\"Monday,Friday\""));
+
+    let mut imported = GtdData::new();
+    let summary = import::import(&mut imported, &rendered, ImportFormat::Csv, today()).unwrap();
+    assert_eq!(summary.added.len(), 3);
+    for nota in data.iter() {
+        assert_eq!(imported.find_by_id(&nota.id).as_ref(), Some(nota));
+    }
+
+    // 同じ内容を再度読み込んでも変更はない
+    let summary = import::import(&mut imported, &rendered, ImportFormat::Csv, today()).unwrap();
+    assert_eq!(summary.unchanged, 3);
+    assert!(summary.added.is_empty() && summary.updated.is_empty());
+}
+
+// 既存の ID は更新、それ以外は追加され、省略した列は保存済みの値を保つことを確認
+#[test]
+fn test_import_merges_by_id() {
+    let mut data = sample_data();
+    let text = "ID,Title,Status,notes\r\n\
+                call-john,Call Jane,,\r\n\
+                ,Buy milk,next_action,from the sheet\r\n\
+                \r\n";
+    let summary = import::import(&mut data, text, ImportFormat::Csv, today()).unwrap();
+    assert_eq!(summary.updated, vec!["call-john".to_string()]);
+    assert_eq!(summary.added, vec!["buy-milk".to_string()]);
+
+    let updated = data.find_by_id("call-john").unwrap();
+    assert_eq!(updated.title, "Call Jane");
+    // 空のステータスは変更なし、空のメモは削除、列のない項目はそのまま
+    assert_eq!(updated.status, NotaStatus::calendar);
+    assert_eq!(updated.notes, None);
+    assert_eq!(updated.context.as_deref(), Some("@phone"));
+    assert_eq!(updated.updated_at, today());
+    assert_eq!(updated.created_at, date("2025-01-02"));
+
+    let added = data.find_by_id("buy-milk").unwrap();
+    assert_eq!(added.status, NotaStatus::next_action);
+    assert_eq!(added.created_at, today());
+}
+
+// 不正な入力は行番号付きのエラーになり、データは変更されないことを確認
+#[test]
+fn test_import_errors_leave_data_untouched() {
+    let cases = [
+        ("title,owner\nA,me\n", "Unknown column 'owner'"),
+        ("title\n\"A\n", "unterminated"),
+        (
+            "title,status\nA,inbox\nB,started\n",
+            "line 3: Invalid status 'started'",
+        ),
+        ("title,status\nA\n", "line 2: expected 2 fields, found 1"),
+        ("title,start_date\nA,03/10/2025\n", "not a YYYY-MM-DD date"),
+        (
+            "title,project\nA,missing\n",
+            "project 'missing' does not exist",
+        ),
+        ("", "empty"),
+    ];
+    for (text, expected) in cases {
+        let mut data = sample_data();
+        let error = import::import(&mut data, text, ImportFormat::Csv, today()).unwrap_err();
+        assert!(
+            error.to_string().contains(expected),
+            "{:?}: {}",
+            text,
+            error
+        );
+        assert_eq!(data.iter().count(), 3);
+    }
+}
+
+// CLI の import が dry run ではファイルを書き換えないことを確認
+#[test]
+fn test_cli_import_dry_run() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("gtd.toml").to_string_lossy().into_owned();
+    let text = "title\nBuy milk\n";
+
+    let output = cli::import(&file, text, ImportFormat::Csv, true).unwrap();
+    assert!(output.contains("Dry run"));
+    assert!(!dir.path().join("gtd.toml").exists());
+
+    let output = cli::import(&file, text, ImportFormat::Csv, false).unwrap();
+    assert!(output.contains("1 added"));
+    let data = Storage::new(&file, false).load_local().unwrap();
+    assert!(data.find_by_id("buy-milk").is_some());
+    assert_eq!(
+        ImportFormat::from_path("tasks.CSV"),
+        Some(ImportFormat::Csv)
+    );
+}
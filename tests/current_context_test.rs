@@ -0,0 +1,152 @@
+//! Integration tests for the session's current context
+//!
+//! These tests cover `set_current_context`, how `list` and `suggest`
+//! default to the context it sets, and that each MCP session of a shared
+//! handler keeps its own.
+
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::{GtdServerHandler, Nota, NotaStatus};
+use mcp_attr::client::McpClient;
+use mcp_attr::schema::{CallToolRequestParams, CallToolResultContentItem};
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+fn nota(id: &str, status: NotaStatus, context: Option<&str>) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.to_string(),
+        status,
+        context: context.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+async fn sample_handler(temp_file: &NamedTempFile) -> GtdServerHandler {
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    let mut data = handler.data.write().await;
+    data.add(nota("@office", NotaStatus::context, None));
+    data.add(nota("@home", NotaStatus::context, None));
+    data.add(nota(
+        "print-slides",
+        NotaStatus::next_action,
+        Some("@office"),
+    ));
+    data.add(nota("water-plants", NotaStatus::next_action, Some("@home")));
+    data.add(nota("call-bob", NotaStatus::next_action, None));
+    data.add(nota("fix-printer", NotaStatus::waiting_for, Some("@home")));
+    drop(data);
+    handler
+}
+
+async fn list(handler: &GtdServerHandler, status: &str, context: Option<&str>) -> String {
+    handler
+        .list(
+            Some(status.to_string()),
+            None,
+            None,
+            None,
+            None,
+            context.map(str::to_string),
+            None,
+        )
+        .await
+        .unwrap()
+}
+
+// 現在のコンテキストが次のアクションの一覧と提案の既定になることを確認
+#[tokio::test]
+async fn test_current_context_defaults_list_and_suggest() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    let response = handler
+        .set_current_context(Some("@home".to_string()))
+        .await
+        .unwrap();
+    assert!(response.contains("Current context: @home"));
+    assert!(response.contains("1 next action(s) here, 1 without a context"));
+    assert_eq!(handler.current_context().as_deref(), Some("@home"));
+
+    let listing = list(&handler, "next_action", None).await;
+    assert!(listing.starts_with("Current context: @home"), "{}", listing);
+    assert!(listing.contains("water-plants"));
+    assert!(!listing.contains("print-slides"));
+
+    // 明示したコンテキストや空文字が優先され、次のアクション以外には適用しない
+    let listing = list(&handler, "next_action", Some("@office")).await;
+    assert!(listing.contains("print-slides") && !listing.contains("water-plants"));
+    let listing = list(&handler, "next_action", Some("")).await;
+    assert!(listing.contains("print-slides") && listing.contains("water-plants"));
+    assert!(list(&handler, "context", None).await.contains("@office"));
+
+    let suggestions = handler.suggest(None, None, None, None).await.unwrap();
+    assert!(suggestions.contains("for @home"), "{}", suggestions);
+    assert!(!suggestions.contains("print-slides"));
+
+    let response = handler.set_current_context(None).await.unwrap();
+    assert!(response.contains("cleared (was @home)"));
+    let listing = list(&handler, "next_action", None).await;
+    assert!(listing.starts_with("Found 3 item(s)"), "{}", listing);
+}
+
+// 存在しないコンテキストは設定できないことを確認
+#[tokio::test]
+async fn test_set_current_context_rejects_unknown_context() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    assert!(
+        handler
+            .set_current_context(Some("@gym".to_string()))
+            .await
+            .is_err()
+    );
+    assert_eq!(handler.current_context(), None);
+}
+
+async fn call(client: &McpClient, params: CallToolRequestParams) -> String {
+    let result = client.tools_call(params).await.unwrap();
+    match &result.content[0] {
+        CallToolResultContentItem::TextContent(text) => text.text.clone(),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+// 同じハンドラを共有するセッションが、それぞれ自分の現在のコンテキストを持つことを確認
+#[tokio::test]
+async fn test_each_session_has_its_own_current_context() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = Arc::new(sample_handler(&temp_file).await);
+    let home = McpClient::with_server(SharedServer::new(handler.clone()))
+        .await
+        .unwrap();
+    let office = McpClient::with_server(SharedServer::new(handler.clone()))
+        .await
+        .unwrap();
+    let set = |name: &str| {
+        CallToolRequestParams::new("set_current_context")
+            .with_argument("name", name)
+            .unwrap()
+    };
+    let next_actions = || {
+        CallToolRequestParams::new("list")
+            .with_argument("status", "next_action")
+            .unwrap()
+    };
+
+    call(&home, set("@home")).await;
+    let listing = call(&office, next_actions()).await;
+    assert!(listing.starts_with("Found 3 item(s)"), "{}", listing);
+
+    call(&office, set("@office")).await;
+    let listing = call(&home, next_actions()).await;
+    assert!(listing.starts_with("Current context: @home"), "{}", listing);
+    let listing = call(&office, next_actions()).await;
+    assert!(
+        listing.starts_with("Current context: @office"),
+        "{}",
+        listing
+    );
+    // セッション外の呼び出しには現在のコンテキストがない
+    assert_eq!(handler.current_context(), None);
+}
@@ -0,0 +1,249 @@
+//! Unit tests for the scheduled maintenance jobs of daemon mode
+//!
+//! These tests call the jobs directly with fixed dates instead of waiting for
+//! the scheduler.
+
+mod common;
+
+use chrono::NaiveDate;
+use common::date;
+use gtd_mcp::daemon::{MAX_ARCHIVE_DONE_AFTER_DAYS, Schedule};
+use gtd_mcp::{GtdServerHandler, Nota, NotaStatus, Storage};
+use std::fs;
+use tempfile::TempDir;
+
+fn nota(id: &str, status: NotaStatus, updated_at: &str) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.to_string(),
+        status,
+        created_at: date("2025-01-01"),
+        updated_at: date(updated_at),
+        ..Default::default()
+    }
+}
+
+fn handler_with(dir: &TempDir, notas: Vec<Nota>) -> GtdServerHandler {
+    let path = dir.path().join("gtd.toml");
+    let storage = Storage::new(&path, false);
+    let mut data = gtd_mcp::GtdData::new();
+    for nota in notas {
+        data.add(nota);
+    }
+    storage.save(&data).unwrap();
+    GtdServerHandler::with_storage(storage).unwrap()
+}
+
+// 開始日が到来したカレンダー項目だけが next_action に昇格し、保存されることを確認
+#[tokio::test]
+async fn test_promote_due_calendar_items() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler_with(
+        &dir,
+        vec![
+            Nota {
+                start_date: Some(date("2025-03-01")),
+                ..nota("dentist", NotaStatus::calendar, "2025-01-01")
+            },
+            Nota {
+                start_date: Some(date("2025-03-02")),
+                ..nota("conference", NotaStatus::calendar, "2025-01-01")
+            },
+        ],
+    );
+
+    let promoted = handler
+        .promote_due_calendar_items(date("2025-03-01"))
+        .await
+        .unwrap();
+    assert_eq!(promoted, vec!["dentist".to_string()]);
+
+    let saved = Storage::new(dir.path().join("gtd.toml"), false)
+        .load_local()
+        .unwrap();
+    assert_eq!(
+        saved.find_by_id("dentist").unwrap().status,
+        NotaStatus::next_action
+    );
+    assert_eq!(
+        saved.find_by_id("conference").unwrap().status,
+        NotaStatus::calendar
+    );
+
+    // 昇格対象がなければ何もしない
+    assert!(
+        handler
+            .promote_due_calendar_items(date("2025-03-01"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+// 古い完了項目がアーカイブファイルへ移り、参照されている項目や新しい項目は残ることを確認
+#[tokio::test]
+async fn test_archive_done_items() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler_with(
+        &dir,
+        vec![
+            nota("old-task", NotaStatus::done, "2025-01-10"),
+            nota("recent-task", NotaStatus::done, "2025-03-01"),
+            nota("old-project", NotaStatus::done, "2025-01-10"),
+            Nota {
+                project: Some("old-project".to_string()),
+                ..nota("follow-up", NotaStatus::next_action, "2025-01-10")
+            },
+        ],
+    );
+
+    let archived = handler
+        .archive_done_items(date("2025-02-01"))
+        .await
+        .unwrap();
+    assert_eq!(archived, vec!["old-task".to_string()]);
+
+    let data = handler.data.read().await;
+    assert!(data.find_by_id("old-task").is_none());
+    assert!(data.find_by_id("recent-task").is_some());
+    assert!(data.find_by_id("old-project").is_some());
+    drop(data);
+
+    let archive_path = dir.path().join("gtd.archive.toml");
+    let archive = Storage::new(&archive_path, false).load_local().unwrap();
+    assert!(archive.find_by_id("old-task").is_some());
+
+    // 2回目のアーカイブは既存のアーカイブに追記される
+    handler
+        .archive_done_items(date("2025-04-01"))
+        .await
+        .unwrap();
+    let archive = Storage::new(&archive_path, false).load_local().unwrap();
+    assert!(archive.find_by_id("old-task").is_some());
+    assert!(archive.find_by_id("recent-task").is_some());
+}
+
+// トランザクション中はアーカイブせず、どちらのファイルも変わらないことを確認
+#[tokio::test]
+async fn test_archive_waits_for_transaction() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler_with(&dir, vec![nota("old-task", NotaStatus::done, "2025-01-10")]);
+
+    handler.begin_transaction().await.unwrap();
+    let archived = handler
+        .archive_done_items(date("2025-02-01"))
+        .await
+        .unwrap();
+    assert!(archived.is_empty());
+    assert!(handler.data.read().await.find_by_id("old-task").is_some());
+    assert!(!dir.path().join("gtd.archive.toml").exists());
+    assert_eq!(handler.abort_transaction().await.unwrap(), 0);
+}
+
+// データファイルの保存に失敗したらアーカイブに書かず、項目を戻すことを確認
+#[tokio::test]
+async fn test_archive_keeps_items_when_main_save_fails() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler_with(&dir, vec![nota("old-task", NotaStatus::done, "2025-01-10")]);
+    // 別のプロセスがデータファイルを書き換える
+    let path = dir.path().join("gtd.toml");
+    let changed = fs::read_to_string(&path).unwrap() + "\n";
+    fs::write(&path, &changed).unwrap();
+
+    assert!(
+        handler
+            .archive_done_items(date("2025-02-01"))
+            .await
+            .is_err()
+    );
+    assert!(handler.data.read().await.find_by_id("old-task").is_some());
+    assert!(!dir.path().join("gtd.archive.toml").exists());
+    assert_eq!(fs::read_to_string(&path).unwrap(), changed);
+}
+
+// アーカイブの書き込みに失敗したら項目をデータファイルに戻すことを確認
+#[tokio::test]
+async fn test_archive_puts_items_back_when_archive_fails() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler_with(&dir, vec![nota("old-task", NotaStatus::done, "2025-01-10")]);
+    // アーカイブのパスにディレクトリがあって書き込めない
+    fs::create_dir(dir.path().join("gtd.archive.toml")).unwrap();
+
+    let error = format!(
+        "{:?}",
+        handler
+            .archive_done_items(date("2025-02-01"))
+            .await
+            .unwrap_err()
+    );
+    assert!(error.contains("put back"), "{}", error);
+    assert!(handler.data.read().await.find_by_id("old-task").is_some());
+    let saved = Storage::new(dir.path().join("gtd.toml"), false)
+        .load_local()
+        .unwrap();
+    assert!(saved.find_by_id("old-task").is_some());
+}
+
+// 日次メンテナンスが設定に従ってジョブを実行することを確認
+#[tokio::test]
+async fn test_run_daily_maintenance() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler_with(
+        &dir,
+        vec![
+            Nota {
+                start_date: Some(date("2025-03-03")),
+                ..nota("review", NotaStatus::calendar, "2025-01-01")
+            },
+            nota("old-task", NotaStatus::done, "2025-01-10"),
+        ],
+    );
+
+    // アーカイブ未設定なら完了項目は残る
+    let report = handler
+        .run_daily_maintenance(date("2025-03-03"), None, &Schedule::default())
+        .await
+        .unwrap();
+    assert_eq!(report.promoted, vec!["review".to_string()]);
+    assert!(report.archived.is_empty());
+    assert_eq!(report.tagged, None);
+
+    // 週をまたいでも Git 同期が無効ならタグは作られない
+    let schedule = Schedule {
+        archive_done_after_days: Some(30),
+        ..Default::default()
+    };
+    let report = handler
+        .run_daily_maintenance(date("2025-03-10"), Some(date("2025-03-09")), &schedule)
+        .await
+        .unwrap();
+    assert_eq!(report.archived, vec!["old-task".to_string()]);
+    assert_eq!(report.tagged, None);
+}
+
+// 範囲外のアーカイブ日数は起動時に拒否され、日次処理でもパニックしないことを確認
+#[tokio::test]
+async fn test_archive_after_days_out_of_range() {
+    let schedule = Schedule {
+        archive_done_after_days: Some(u32::MAX),
+        ..Default::default()
+    };
+    let error = schedule.validate().unwrap_err().to_string();
+    assert!(error.contains("archive_done_after_days"), "{}", error);
+    assert!(
+        Schedule {
+            archive_done_after_days: Some(MAX_ARCHIVE_DONE_AFTER_DAYS),
+            ..Default::default()
+        }
+        .validate()
+        .is_ok()
+    );
+
+    let dir = TempDir::new().unwrap();
+    let handler = handler_with(&dir, vec![nota("old-task", NotaStatus::done, "2025-01-01")]);
+    let result = handler
+        .run_daily_maintenance(NaiveDate::MIN, None, &schedule)
+        .await;
+    assert!(result.is_err());
+    assert!(handler.data.read().await.find_by_id("old-task").is_some());
+}
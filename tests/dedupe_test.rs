@@ -0,0 +1,130 @@
+//! Integration tests for the `dedupe` tool
+//!
+//! These tests cover listing likely duplicates, folding them into the kept
+//! item, and trashing them while moving their tasks to the kept project.
+
+use chrono::NaiveDate;
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn nota(id: &str, title: &str, status: NotaStatus, created: u32) -> Nota {
+    let date = NaiveDate::from_ymd_opt(2026, 10, created).unwrap();
+    Nota {
+        id: id.to_string(),
+        title: title.to_string(),
+        status,
+        created_at: date,
+        updated_at: date,
+        ..Default::default()
+    }
+}
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(nota("call-john", "Call John", NotaStatus::next_action, 1));
+    data.add(Nota {
+        notes: Some("About the invoice".to_string()),
+        estimate: Some(10),
+        ..nota("call-john-2", "call john", NotaStatus::inbox, 5)
+    });
+    data.add(nota("website", "Website", NotaStatus::project, 1));
+    data.add(nota("website-2", "Website", NotaStatus::project, 3));
+    data.add(Nota {
+        project: Some("website-2".to_string()),
+        ..nota("fix-css", "Fix CSS", NotaStatus::next_action, 3)
+    });
+    data
+}
+
+// 既定では重複グループを一覧するだけで何も変更しないことを確認
+#[tokio::test]
+async fn test_dedupe_reports_groups() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    *handler.data.write().await = sample();
+
+    let response = handler.dedupe(None, None).await.unwrap();
+    assert!(
+        response.starts_with("Found 2 group(s) of likely duplicates"),
+        "{}",
+        response
+    );
+    assert!(
+        response.contains("1. keep [call-john] Call John (next_action, created 2026-10-01)"),
+        "{}",
+        response
+    );
+    assert!(
+        response.contains("   duplicate [call-john-2] call john (inbox, created 2026-10-05)"),
+        "{}",
+        response
+    );
+    assert_eq!(
+        handler
+            .data
+            .read()
+            .await
+            .find_by_id("call-john-2")
+            .unwrap()
+            .status,
+        NotaStatus::inbox
+    );
+}
+
+// merge で詳細が残す項目に移され、重複はゴミ箱へ、配下のタスクは残すプロジェクトに付け替えられることを確認
+#[tokio::test]
+async fn test_dedupe_merge_and_trash() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    *handler.data.write().await = sample();
+
+    let response = handler
+        .dedupe(
+            Some("merge".to_string()),
+            Some(vec!["call-john-2".to_string()]),
+        )
+        .await
+        .unwrap();
+    assert!(
+        response.contains("- call-john-2 → kept call-john"),
+        "{}",
+        response
+    );
+    assert!(!response.contains("website"), "{}", response);
+    {
+        let data = handler.data.read().await;
+        let kept = data.find_by_id("call-john").unwrap();
+        assert_eq!(kept.notes.as_deref(), Some("About the invoice"));
+        assert_eq!(kept.estimate, Some(10));
+        assert_eq!(
+            data.find_by_id("call-john-2").unwrap().status,
+            NotaStatus::trash
+        );
+        assert_eq!(
+            data.find_by_id("website-2").unwrap().status,
+            NotaStatus::project
+        );
+    }
+
+    handler
+        .dedupe(Some("trash".to_string()), None)
+        .await
+        .unwrap();
+    let data = handler.data.read().await;
+    assert_eq!(
+        data.find_by_id("website-2").unwrap().status,
+        NotaStatus::trash
+    );
+    assert_eq!(
+        data.find_by_id("fix-css").unwrap().project.as_deref(),
+        Some("website")
+    );
+    drop(data);
+    assert!(
+        handler
+            .dedupe(None, None)
+            .await
+            .unwrap()
+            .starts_with("No likely duplicates found")
+    );
+}
@@ -0,0 +1,57 @@
+//! Unit tests for finding and initializing the data file
+//!
+//! These tests verify the upward search for gtd.toml and the starter file
+//! written by `--init`.
+
+use gtd_mcp::discovery::{self, DATA_FILE_NAME};
+use gtd_mcp::{NotaStatus, Storage};
+use std::fs;
+use tempfile::TempDir;
+
+// 親ディレクトリをたどって最も近い gtd.toml が見つかることを確認
+#[test]
+fn test_find_data_file_walks_up() {
+    let dir = TempDir::new().unwrap();
+    let nested = dir.path().join("projects").join("site");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(dir.path().join(DATA_FILE_NAME), "").unwrap();
+
+    assert_eq!(
+        discovery::find_data_file(&nested),
+        Some(dir.path().join(DATA_FILE_NAME))
+    );
+
+    // より近いファイルが優先される
+    fs::write(nested.join(DATA_FILE_NAME), "").unwrap();
+    assert_eq!(
+        discovery::find_data_file(&nested),
+        Some(nested.join(DATA_FILE_NAME))
+    );
+}
+
+// 同名のディレクトリはデータファイルとみなさないことを確認
+#[test]
+fn test_find_data_file_ignores_directories() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join(DATA_FILE_NAME)).unwrap();
+    let found = discovery::find_data_file(dir.path());
+    assert_ne!(found, Some(dir.path().join(DATA_FILE_NAME)));
+}
+
+// --init が標準のコンテキストを含むファイルを作成し、既存のファイルは上書きしないことを確認
+#[test]
+fn test_init_data_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("new").join(DATA_FILE_NAME);
+
+    assert!(discovery::init_data_file(&path).unwrap());
+    let data = Storage::new(&path, false).load_local().unwrap();
+    assert!(data.iter().all(|nota| nota.status == NotaStatus::context));
+    assert!(data.find_by_id("@home").is_some());
+    assert!(data.find_by_id("@computer").is_some());
+    assert!(data.check_integrity().is_empty());
+
+    fs::write(&path, "format_version = 3\n").unwrap();
+    assert!(!discovery::init_data_file(&path).unwrap());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "format_version = 3\n");
+}
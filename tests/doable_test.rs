@@ -0,0 +1,264 @@
+//! Integration tests for the `doable` tool
+//!
+//! These tests cover keeping only next actions whose estimate, energy and
+//! context fit, at the edges of each constraint, and counting actions that
+//! lack the metadata to check.
+
+mod common;
+
+use chrono::{Duration, NaiveDate};
+use common::action;
+use gtd_mcp::gtd::Energy;
+use gtd_mcp::suggest::{self, Constraints, Doable};
+use gtd_mcp::{FixedClock, GtdData, GtdServerHandler, Nota, NotaStatus};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, 15).unwrap()
+}
+
+fn context(name: &str) -> Nota {
+    Nota {
+        id: name.to_string(),
+        title: name.to_string(),
+        status: NotaStatus::context,
+        ..Default::default()
+    }
+}
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(context("@home"));
+    data.add(context("@office"));
+    data.add(Nota {
+        estimate: Some(10),
+        energy: Some(Energy::low),
+        context: Some("@home".to_string()),
+        ..action("water-plants")
+    });
+    data.add(Nota {
+        estimate: Some(15),
+        energy: Some(Energy::low),
+        ..action("reply-email")
+    });
+    data.add(Nota {
+        estimate: Some(90),
+        energy: Some(Energy::high),
+        context: Some("@home".to_string()),
+        ..action("write-essay")
+    });
+    data.add(Nota {
+        energy: Some(Energy::low),
+        context: Some("@home".to_string()),
+        ..action("sort-photos")
+    });
+    data.add(Nota {
+        estimate: Some(5),
+        energy: Some(Energy::low),
+        context: Some("@office".to_string()),
+        ..action("print-slides")
+    });
+    data
+}
+
+fn ids(doable: &Doable) -> Vec<&str> {
+    let mut ids: Vec<&str> = doable.actions.iter().map(|s| s.nota.id.as_str()).collect();
+    ids.sort();
+    ids
+}
+
+fn check(data: &GtdData, constraints: Constraints) -> Doable {
+    suggest::doable(data, &constraints, today())
+}
+
+// 見積もり・気力・コンテキストがすべて合う行動だけが残り、判断できない行動は数えられることを確認
+#[test]
+fn test_doable_intersects_constraints() {
+    let data = sample();
+    let doable = check(
+        &data,
+        Constraints {
+            context: Some("@home".to_string()),
+            minutes: Some(20),
+            energy: Some(Energy::low),
+            ..Default::default()
+        },
+    );
+    assert_eq!(ids(&doable), ["reply-email", "water-plants"]);
+    assert_eq!(doable.unknown, 1);
+    assert_eq!(doable.skipped, 2);
+
+    // 制約がなければすべての次の行動が対象
+    let doable = check(&data, Constraints::default());
+    assert_eq!(doable.actions.len(), 5);
+    assert_eq!((doable.unknown, doable.skipped), (0, 0));
+}
+
+// 見積もりが空き時間ちょうどなら残り、1 分でも超えると外れることを確認
+#[test]
+fn test_doable_time_boundary() {
+    let data = sample();
+    let minutes = |minutes| Constraints {
+        minutes: Some(minutes),
+        ..Default::default()
+    };
+
+    let doable = check(&data, minutes(10));
+    assert_eq!(ids(&doable), ["print-slides", "water-plants"]);
+    let doable = check(&data, minutes(9));
+    assert_eq!(ids(&doable), ["print-slides"]);
+    // 見積もりのない行動は時間が合うか分からない
+    assert_eq!(doable.unknown, 1);
+    assert_eq!(doable.skipped, 3);
+
+    let doable = check(&data, minutes(0));
+    assert!(doable.actions.is_empty());
+    assert_eq!(doable.unknown, 1);
+}
+
+// 気力は必要な水準以下なら合い、上回る行動と気力の未設定な行動は外れることを確認
+#[test]
+fn test_doable_energy_levels() {
+    let mut data = sample();
+    data.add(Nota {
+        energy: Some(Energy::medium),
+        ..action("plan-week")
+    });
+    data.add(action("call-mom"));
+    let energy = |energy| Constraints {
+        energy: Some(energy),
+        ..Default::default()
+    };
+
+    let doable = check(&data, energy(Energy::low));
+    assert_eq!(
+        ids(&doable),
+        ["print-slides", "reply-email", "sort-photos", "water-plants"]
+    );
+    assert_eq!((doable.unknown, doable.skipped), (1, 2));
+
+    let doable = check(&data, energy(Energy::medium));
+    assert!(ids(&doable).contains(&"plan-week"));
+    assert!(!ids(&doable).contains(&"write-essay"));
+
+    // 気力が十分ならどの水準の行動も合う
+    let doable = check(&data, energy(Energy::high));
+    assert_eq!(doable.actions.len(), 6);
+    assert_eq!((doable.unknown, doable.skipped), (1, 0));
+}
+
+// コンテキストのない行動はどこでも合い、プロジェクトのコンテキストを引き継いだ行動は別の場所では外れることを確認
+#[test]
+fn test_doable_context_edges() {
+    let mut data = sample();
+    data.add(Nota {
+        id: "move-office".to_string(),
+        title: "Move office".to_string(),
+        status: NotaStatus::project,
+        context: Some("@office".to_string()),
+        ..Default::default()
+    });
+    data.add(Nota {
+        project: Some("move-office".to_string()),
+        ..action("pack-boxes")
+    });
+    let at = |context: &str| Constraints {
+        context: Some(context.to_string()),
+        ..Default::default()
+    };
+
+    let doable = check(&data, at("@home"));
+    assert_eq!(
+        ids(&doable),
+        ["reply-email", "sort-photos", "water-plants", "write-essay"]
+    );
+    assert_eq!(doable.skipped, 2);
+
+    let doable = check(&data, at("@office"));
+    assert_eq!(ids(&doable), ["pack-boxes", "print-slides", "reply-email"]);
+    // 引き継いだコンテキストが結果の行動に表れる
+    let packed = doable
+        .actions
+        .iter()
+        .find(|s| s.nota.id == "pack-boxes")
+        .unwrap();
+    assert_eq!(packed.nota.context.as_deref(), Some("@office"));
+
+    // 定義されていないコンテキストでは、コンテキストのない行動だけが合う
+    let doable = check(&data, at("@car"));
+    assert_eq!(ids(&doable), ["reply-email"]);
+}
+
+// 開始日が未来の行動、待ち・完了の項目は対象にならないことを確認
+#[test]
+fn test_doable_ignores_other_items() {
+    let mut data = sample();
+    data.add(Nota {
+        start_date: Some(today() + Duration::days(1)),
+        ..action("file-taxes")
+    });
+    data.add(Nota {
+        start_date: Some(today()),
+        ..action("pay-rent")
+    });
+    data.add(Nota {
+        status: NotaStatus::waiting_for,
+        due_date: Some(today() - Duration::days(30)),
+        ..action("await-reply")
+    });
+    data.add(Nota {
+        status: NotaStatus::done,
+        ..action("old-task")
+    });
+
+    let doable = check(&data, Constraints::default());
+    let ids = ids(&doable);
+    assert!(ids.contains(&"pay-rent"));
+    assert!(!ids.contains(&"file-taxes"));
+    assert!(!ids.contains(&"await-reply"));
+    assert!(!ids.contains(&"old-task"));
+    assert_eq!(doable.skipped, 1);
+}
+
+// doable ツールが合う行動だけを一覧し、不正な気力や未定義のコンテキストを拒否することを確認
+#[tokio::test]
+async fn test_doable_tool() {
+    let dir = TempDir::new().unwrap();
+    let handler = GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .clock(Arc::new(FixedClock::on(today())))
+        .build()
+        .unwrap();
+    *handler.data.write().await = sample();
+    let listed = |response: &str| -> Vec<String> {
+        response
+            .lines()
+            .filter_map(|line| line.strip_prefix("- ["))
+            .filter_map(|line| Some(line.split_once(']')?.0.to_string()))
+            .collect()
+    };
+
+    let response = handler
+        .doable(Some(20), Some("low".to_string()), Some("@home".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(listed(&response), ["water-plants", "reply-email"]);
+
+    let response = handler
+        .doable(Some(1), None, Some("@home".to_string()))
+        .await
+        .unwrap();
+    assert!(listed(&response).is_empty());
+
+    for (energy, context) in [(Some("sleepy"), None), (None, Some("@moon"))] {
+        let result = handler
+            .doable(
+                None,
+                energy.map(str::to_string),
+                context.map(str::to_string),
+            )
+            .await;
+        assert!(result.is_err(), "{:?} {:?}", energy, context);
+    }
+}
@@ -0,0 +1,139 @@
+//! Unit tests for duplicate-content detection
+//!
+//! These tests verify title normalization, the similarity measure, and which
+//! items count as duplicates of a new capture or of each other.
+
+use gtd_mcp::duplicates::{self, DuplicateCheck};
+use gtd_mcp::{GtdData, Nota, NotaStatus};
+
+fn nota(id: &str, title: &str, status: NotaStatus) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: title.to_string(),
+        status,
+        created_at: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+        ..Default::default()
+    }
+}
+
+// 大文字小文字・記号・語順を無視して正規化されることを確認
+#[test]
+fn test_normalize_title() {
+    assert_eq!(
+        duplicates::normalize_title("Invoice: call John!"),
+        "call invoice john"
+    );
+    assert_eq!(duplicates::normalize_title("  --  "), "");
+}
+
+// ほぼ同じタイトルは類似度が高く、無関係なタイトルは低いことを確認
+#[test]
+fn test_title_similarity() {
+    assert_eq!(duplicates::title_similarity("Call John", "john, CALL"), 1.0);
+    assert!(
+        duplicates::title_similarity("Call John about invoice", "call john about the invoice")
+            >= duplicates::SIMILARITY_THRESHOLD
+    );
+    assert!(
+        duplicates::title_similarity("Call John", "Buy groceries")
+            < duplicates::SIMILARITY_THRESHOLD
+    );
+}
+
+// 未完了の項目だけが類似候補になり、類似度の高い順に並ぶことを確認
+#[test]
+fn test_find_similar_open_items() {
+    let mut data = GtdData::new();
+    data.add(nota("old", "Renew passport", NotaStatus::done));
+    data.add(nota("trashed", "Renew passport", NotaStatus::trash));
+    data.add(nota("close", "Renew passports", NotaStatus::someday));
+    data.add(nota("exact", "renew PASSPORT", NotaStatus::next_action));
+    data.add(nota("other", "Book flights", NotaStatus::inbox));
+
+    let ids: Vec<&str> = duplicates::find_similar_open_items(&data, "Renew passport")
+        .iter()
+        .map(|nota| nota.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["exact", "close"]);
+    assert!(duplicates::find_similar_open_items(&data, "!!").is_empty());
+}
+
+// 編集距離が近い ID・部分一致する ID が近い順に最大 3 件提案されることを確認
+#[test]
+fn test_similar_ids() {
+    let mut data = GtdData::new();
+    data.add(nota("call-john", "Call John", NotaStatus::inbox));
+    data.add(nota("call-joan", "Call Joan", NotaStatus::done));
+    data.add(nota("email-john-about-invoice", "Email", NotaStatus::inbox));
+    data.add(nota("buy-milk", "Buy milk", NotaStatus::inbox));
+
+    assert_eq!(
+        duplicates::similar_ids(&data, "Cal-John"),
+        vec!["call-john", "call-joan"]
+    );
+    assert_eq!(
+        duplicates::similar_ids(&data, "invoice"),
+        vec!["email-john-about-invoice"]
+    );
+    assert!(duplicates::similar_ids(&data, "renew-passport").is_empty());
+    assert!(duplicates::similar_ids(&data, "").is_empty());
+
+    for i in 0..5 {
+        data.add(nota(&format!("task-{}", i), "Task", NotaStatus::inbox));
+    }
+    assert_eq!(
+        duplicates::similar_ids(&data, "task").len(),
+        duplicates::MAX_ID_SUGGESTIONS
+    );
+}
+
+// 提案の件数に応じて文が組み立てられることを確認
+#[test]
+fn test_did_you_mean() {
+    assert_eq!(duplicates::did_you_mean(&[]), "");
+    assert_eq!(
+        duplicates::did_you_mean(&["call-john"]),
+        " Did you mean 'call-john'?"
+    );
+    assert_eq!(
+        duplicates::did_you_mean(&["a", "b", "c"]),
+        " Did you mean 'a', 'b' or 'c'?"
+    );
+}
+
+// 文字列からの変換と表示が対応していることを確認
+#[test]
+fn test_duplicate_check_from_str() {
+    for check in [
+        DuplicateCheck::Off,
+        DuplicateCheck::Warn,
+        DuplicateCheck::Reject,
+    ] {
+        assert_eq!(check.to_string().parse::<DuplicateCheck>(), Ok(check));
+    }
+    assert_eq!(DuplicateCheck::default(), DuplicateCheck::Warn);
+    assert!("ignore".parse::<DuplicateCheck>().is_err());
+}
+
+// 同じ正規化タイトルかつ同じプロジェクトの未完了項目がまとめられ、整理済みで古い項目が残されることを確認
+#[test]
+fn test_duplicate_groups() {
+    let mut data = GtdData::new();
+    data.add(nota("call-john-2", "call john", NotaStatus::inbox));
+    data.add(Nota {
+        created_at: chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+        ..nota("call-john", "Call John", NotaStatus::next_action)
+    });
+    data.add(nota("john-call", "John, call!", NotaStatus::someday));
+    data.add(nota("call-john-done", "Call John", NotaStatus::done));
+    data.add(Nota {
+        project: Some("website".to_string()),
+        ..nota("call-john-web", "Call John", NotaStatus::next_action)
+    });
+    data.add(nota("buy-milk", "Buy milk", NotaStatus::next_action));
+
+    let groups = duplicates::duplicate_groups(&data);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].keep, "call-john");
+    assert_eq!(groups[0].duplicates, vec!["call-john-2", "john-call"]);
+}
@@ -0,0 +1,98 @@
+//! Unit tests for capturing pasted emails
+//!
+//! These tests cover the header, subject and sender parsing of `email::parse`
+//! and the inbox item created by the `capture_email` tool.
+
+use gtd_mcp::email::{self, Sender};
+use gtd_mcp::{GtdServerHandler, NotaStatus};
+use tempfile::NamedTempFile;
+
+const FORWARDED: &str = "Can you take care of this before Friday?
+
+---------- Forwarded message ---------
+From: \"Smith, Alice\" <alice@example.com>
+Date: Mon, 3 Mar 2025 at 09:12
+Subject: Re: FW: Q3 budget
+ numbers
+To: Bob <bob@example.com>
+
+Hi Bob,
+
+Please send me the Q3 numbers.
+
+--
+Alice Smith
+Finance
+";
+
+// 転送メールのヘッダー・件名・本文・転送時のコメントが読み取られることを確認
+#[test]
+fn test_parse_forwarded_email() {
+    let email = email::parse(FORWARDED).unwrap();
+    assert_eq!(email.subject, "Q3 budget numbers");
+    assert_eq!(
+        email.from,
+        Some(Sender {
+            name: Some("Smith, Alice".to_string()),
+            address: Some("alice@example.com".to_string()),
+        })
+    );
+    assert_eq!(email.date.as_deref(), Some("Mon, 3 Mar 2025 at 09:12"));
+    assert_eq!(
+        email.comment.as_deref(),
+        Some("Can you take care of this before Friday?")
+    );
+    // 署名は本文に含めない
+    assert_eq!(email.body, "Hi Bob,\n\nPlease send me the Q3 numbers.");
+
+    let notes = email.notes().unwrap();
+    assert!(notes.starts_with("From: Smith, Alice <alice@example.com>\nDate: "));
+    assert!(notes.ends_with("Please send me the Q3 numbers."));
+}
+
+// 日本語のヘッダー名・全角コロン、ヘッダーのないテキストを扱えることを確認
+#[test]
+fn test_parse_localized_and_headerless_text() {
+    let email = email::parse(
+        "差出人： 山田 太郎 <yamada@example.jp>\r\n件名： 転送: 見積もりの確認\r\n\r\n見積もりをご確認ください。\r\n",
+    )
+    .unwrap();
+    assert_eq!(email.subject, "見積もりの確認");
+    assert_eq!(email.from.unwrap().display_name(), "山田 太郎");
+    assert_eq!(email.body, "見積もりをご確認ください。");
+
+    // 件名がなければ本文の最初の行がタイトルになる
+    let email = email::parse("\nRenew the passport\nBefore the trip in May\n").unwrap();
+    assert_eq!(email.subject, "Renew the passport");
+    assert_eq!(email.from, None);
+
+    assert!(email::parse(" \n\n").is_err());
+    assert_eq!(email::clean_subject("RE[2]: Fwd: Lunch"), "Lunch");
+    assert_eq!(email::clean_subject("Agenda: Monday"), "Agenda: Monday");
+    assert_eq!(
+        Sender::parse("carol@example.com").address.as_deref(),
+        Some("carol@example.com")
+    );
+}
+
+// capture_emailが件名から生成したIDでinbox項目を作り、送信者を返すことを確認
+#[tokio::test]
+async fn test_capture_email_creates_inbox_item() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+
+    let response = handler.capture_email(FORWARDED.to_string()).await.unwrap();
+    assert!(response.contains("q3-budget-numbers"));
+    assert!(response.contains("Sender: Smith, Alice"));
+
+    let data = handler.data.read().await;
+    let nota = data.find_by_id("q3-budget-numbers").unwrap();
+    assert_eq!(nota.status, NotaStatus::inbox);
+    assert_eq!(nota.title, "Q3 budget numbers");
+    assert!(nota.notes.unwrap().contains("alice@example.com"));
+    drop(data);
+
+    // 同じ件名のメールは別のIDで取り込まれる
+    let response = handler.capture_email(FORWARDED.to_string()).await.unwrap();
+    assert!(response.contains("q3-budget-numbers-2"));
+}
@@ -0,0 +1,187 @@
+//! Integration tests for escalating waiting-for items
+//!
+//! These tests cover per-item and default thresholds, listing overdue waiting
+//! items in `suggest` with an escalate marker, and moving them back to
+//! next_action when the server is configured to.
+
+use chrono::{Duration, NaiveDate};
+use gtd_mcp::escalation;
+use gtd_mcp::suggest::{self, Constraints};
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus, local_date_today};
+use tempfile::NamedTempFile;
+
+fn waiting(id: &str, since: NaiveDate, after_days: Option<u32>) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status: NotaStatus::waiting_for,
+        created_at: since,
+        updated_at: since,
+        escalate_after_days: after_days,
+        ..Default::default()
+    }
+}
+
+fn sample(today: NaiveDate) -> GtdData {
+    let mut data = GtdData::new();
+    data.add(waiting(
+        "quote-from-bob",
+        today - Duration::days(10),
+        Some(3),
+    ));
+    data.add(waiting("invoice-reply", today - Duration::days(10), None));
+    data.add(waiting("parts-order", today - Duration::days(2), None));
+    data.add(Nota {
+        id: "write-report".to_string(),
+        title: "write report".to_string(),
+        status: NotaStatus::next_action,
+        created_at: today,
+        updated_at: today,
+        ..Default::default()
+    });
+    data
+}
+
+// 項目ごとの日数が既定値より優先され、超過した待ち項目だけが対象になることを確認
+#[test]
+fn test_due_for_escalation() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+    let data = sample(today);
+
+    let due: Vec<String> = escalation::due(&data, None, today)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(due, vec!["quote-from-bob"]);
+
+    let due = escalation::due(&data, Some(7), today);
+    assert_eq!(
+        due.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(),
+        vec!["quote-from-bob", "invoice-reply"]
+    );
+    assert_eq!(due[1].1.reason(), "escalate: waiting 10 days (limit 7)");
+}
+
+// suggest で期限を過ぎた待ち項目が加点・理由付きで次の行動と並ぶことを確認
+#[test]
+fn test_suggest_lists_escalated_items() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+    let data = sample(today);
+    let ranking = suggest::rank(&data, &Constraints::default(), today, 10);
+    let ids: Vec<&str> = ranking
+        .suggestions
+        .iter()
+        .map(|s| s.nota.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["quote-from-bob", "write-report"]);
+    assert_eq!(
+        ranking.suggestions[0].reasons[0],
+        "escalate: waiting 10 days (limit 3)"
+    );
+}
+
+// 設定に応じて待ち項目に ESCALATE が付くか next_action に戻されることを確認
+#[tokio::test]
+async fn test_suggest_escalates() {
+    let today = local_date_today();
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false)
+        .unwrap()
+        .with_escalation(Some(7), false);
+    *handler.data.write().await = sample(today);
+
+    let response = handler.suggest(None, None, None, None).await.unwrap();
+    assert!(
+        response.contains("[quote-from-bob] quote from bob (score 42) ESCALATE"),
+        "{}",
+        response
+    );
+    assert!(
+        response.contains("[invoice-reply] invoice reply (score 42) ESCALATE"),
+        "{}",
+        response
+    );
+    assert!(!response.contains("parts-order"), "{}", response);
+    assert_eq!(
+        handler
+            .data
+            .read()
+            .await
+            .find_by_id("invoice-reply")
+            .unwrap()
+            .status,
+        NotaStatus::waiting_for
+    );
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false)
+        .unwrap()
+        .with_escalation(Some(7), true);
+    *handler.data.write().await = sample(today);
+    let response = handler.suggest(None, None, None, None).await.unwrap();
+    assert!(
+        response.starts_with(
+            "Moved back to next_action after waiting too long: quote-from-bob (10 days), invoice-reply (10 days)"
+        ),
+        "{}",
+        response
+    );
+    assert!(response.contains("ESCALATE"), "{}", response);
+    let data = handler.data.read().await;
+    assert_eq!(
+        data.find_by_id("quote-from-bob").unwrap().status,
+        NotaStatus::next_action
+    );
+    assert_eq!(
+        data.find_by_id("parts-order").unwrap().status,
+        NotaStatus::waiting_for
+    );
+}
+
+// set_escalation で日数が保存され、省略すると既定値に戻ることを確認
+#[tokio::test]
+async fn test_set_escalation() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap().to_string();
+    let handler = GtdServerHandler::new(&path, false)
+        .unwrap()
+        .with_escalation(Some(14), false);
+    let today = local_date_today();
+    handler
+        .data
+        .write()
+        .await
+        .add(waiting("quote-from-bob", today, None));
+
+    let response = handler
+        .set_escalation("quote-from-bob".to_string(), Some(3))
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        "quote-from-bob is escalated after waiting 3 days (its clock restarts today)."
+    );
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("escalate_after_days = 3"), "{}", content);
+
+    let response = handler
+        .set_escalation("quote-from-bob".to_string(), None)
+        .await
+        .unwrap();
+    assert!(
+        response.contains("uses the server default: escalated after waiting 14 days"),
+        "{}",
+        response
+    );
+    let message = handler
+        .set_escalation("quote-from-bobb".to_string(), Some(3))
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("Did you mean 'quote-from-bob'?"),
+        "{}",
+        message
+    );
+}
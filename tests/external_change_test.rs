@@ -0,0 +1,102 @@
+//! Integration tests for saves over a file changed outside the server
+//!
+//! These tests cover refusing a save when the data file changed on disk since
+//! it was loaded, keeping the refused save out of the journal, and the
+//! `reload` and retry flow of the handler.
+
+use gtd_mcp::{ExternalChange, GtdData, GtdServerHandler, Nota, NotaStatus, Storage};
+use std::fs;
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "call-bob".to_string(),
+        title: "Call Bob".to_string(),
+        status: NotaStatus::next_action,
+        ..Default::default()
+    });
+    data
+}
+
+fn retitle(data: &mut GtdData, title: &str) {
+    let mut nota = data.find_by_id("call-bob").unwrap();
+    nota.title = title.to_string();
+    data.update("call-bob", nota).unwrap();
+}
+
+// 読み込み後にファイルが外部で変更されると保存が拒否され、ジャーナルにも残らないことを確認
+#[test]
+fn test_save_over_external_change_is_refused() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    Storage::new(&path, false).save(&sample()).unwrap();
+
+    let storage = Storage::new(&path, false);
+    let mut data = storage.load().unwrap();
+    retitle(&mut data, "Call Bob today");
+
+    // 別のマシンが同期したファイル
+    let mut other = sample();
+    other
+        .move_status("call-bob", NotaStatus::done, gtd_mcp::local_date_today())
+        .unwrap();
+    Storage::new(&path, false).save(&other).unwrap();
+    let synced = fs::read_to_string(&path).unwrap();
+
+    let error = storage.save(&data).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<ExternalChange>(),
+        Some(&ExternalChange { path: path.clone() })
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), synced);
+    assert!(!temp_dir.path().join("gtd.journal.jsonl").exists());
+
+    // 読み直した後の保存は通る
+    let mut data = storage.load_local().unwrap();
+    retitle(&mut data, "Call Bob today");
+    storage.save(&data).unwrap();
+    // 自分の保存の後も続けて保存できる
+    retitle(&mut data, "Call Bob tomorrow");
+    storage.save(&data).unwrap();
+    let saved = Storage::new(&path, false).load().unwrap();
+    let nota = saved.find_by_id("call-bob").unwrap();
+    assert_eq!(nota.status, NotaStatus::done);
+    assert_eq!(nota.title, "Call Bob tomorrow");
+}
+
+// ツールの保存が拒否されたら reload して同じ変更をやり直せることを確認
+#[tokio::test]
+async fn test_reload_and_retry() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    Storage::new(&path, false).save(&sample()).unwrap();
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+
+    let mut other = sample();
+    retitle(&mut other, "Call Bob about the invoice");
+    Storage::new(&path, false).save(&other).unwrap();
+
+    let ids = vec!["call-bob".to_string()];
+    let error = handler
+        .change_status(ids.clone(), "done".to_string(), None, None)
+        .await
+        .unwrap_err();
+    assert!(format!("{:?}", error).contains("reload"), "{:?}", error);
+
+    let response = handler.reload().await.unwrap();
+    assert!(response.contains("Reloaded 1 items"), "{}", response);
+    assert!(
+        response.contains("Unsaved changes were discarded"),
+        "{}",
+        response
+    );
+
+    handler
+        .change_status(ids, "done".to_string(), None, None)
+        .await
+        .unwrap();
+    let saved = Storage::new(&path, false).load().unwrap();
+    let nota = saved.find_by_id("call-bob").unwrap();
+    assert_eq!(nota.status, NotaStatus::done);
+    assert_eq!(nota.title, "Call Bob about the invoice");
+}
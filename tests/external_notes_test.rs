@@ -0,0 +1,119 @@
+//! Integration tests for notes stored as external Markdown files
+//!
+//! These tests cover writing long notes to `<stem>.notes/`, reading them back
+//! on load, removing files of deleted notes, and converting a file between
+//! inline and external notes.
+
+use gtd_mcp::external_notes;
+use gtd_mcp::{GtdData, Nota, NotaStatus, Storage};
+use std::fs;
+
+const LONG_NOTES: &str = "# Plan\n\n- Collect feedback\n- Draft the new layout\n";
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "website".to_string(),
+        title: "Website redesign".to_string(),
+        status: NotaStatus::project,
+        notes: Some(LONG_NOTES.to_string()),
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "#13".to_string(),
+        title: "Read chapter 4".to_string(),
+        notes: Some(LONG_NOTES.replace("Plan", "Chapter")),
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "buy-milk".to_string(),
+        title: "Buy milk".to_string(),
+        notes: Some("Oat".to_string()),
+        ..Default::default()
+    });
+    data
+}
+
+// 長いメモだけが Markdown ファイルに書き出され、読み込みで元に戻り、削除した項目のファイルは消えることを確認
+#[test]
+fn test_long_notes_are_stored_in_markdown_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let notes_dir = temp_dir.path().join("gtd.notes");
+    let storage = Storage::new(&path, false);
+    let mut data = sample();
+    data.external_notes = Some(20);
+    storage.save(&data).unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("format_version = 4"), "{}", content);
+    assert!(
+        content.contains("notes_file = \"website.md\""),
+        "{}",
+        content
+    );
+    assert!(content.contains("notes_file = \"_13.md\""), "{}", content);
+    assert!(content.contains("notes = \"Oat\""), "{}", content);
+    assert!(!content.contains("Collect feedback"), "{}", content);
+    assert_eq!(
+        fs::read_to_string(notes_dir.join("website.md")).unwrap(),
+        LONG_NOTES
+    );
+
+    let loaded = storage.load().unwrap();
+    assert_eq!(loaded.external_notes, Some(20));
+    assert_eq!(
+        loaded.find_by_id("website").unwrap().notes.as_deref(),
+        Some(LONG_NOTES)
+    );
+    assert!(!loaded.is_dirty(&Storage::serialize(&loaded).unwrap()));
+
+    // ファイルでメモを編集すると次の読み込みに反映される
+    fs::write(notes_dir.join("website.md"), "Edited\n").unwrap();
+    let mut loaded = storage.load().unwrap();
+    assert_eq!(
+        loaded.find_by_id("website").unwrap().notes.as_deref(),
+        Some("Edited\n")
+    );
+
+    loaded.retain(|nota| nota.id != "#13");
+    storage.save(&loaded).unwrap();
+    assert!(!notes_dir.join("_13.md").exists());
+    assert!(!notes_dir.join("website.md").exists());
+    assert!(!notes_dir.exists());
+}
+
+// migrate のオプションで外部メモ形式と埋め込み形式を相互に変換できることを確認
+#[test]
+fn test_convert_file_both_ways() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let notes_dir = temp_dir.path().join("gtd.notes");
+    Storage::new(&path, false).save(&sample()).unwrap();
+
+    let preview = external_notes::convert_file(&path, Some(20), true).unwrap();
+    assert_eq!(preview.external_count, 2);
+    assert!(preview.to_string().starts_with("Would convert"));
+    assert!(!notes_dir.exists());
+
+    let report = external_notes::convert_file(&path, Some(20), false).unwrap();
+    assert!(report.backup_path.unwrap().exists());
+    assert!(notes_dir.join("website.md").exists());
+    assert!(
+        external_notes::convert_file(&path, Some(20), false)
+            .unwrap()
+            .unchanged
+    );
+
+    external_notes::convert_file(&path, None, false).unwrap();
+    assert!(!notes_dir.exists());
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("format_version = 3"), "{}", content);
+    assert!(content.contains("Collect feedback"), "{}", content);
+    let data = Storage::new(&path, false).load().unwrap();
+    assert_eq!(data.external_notes, None);
+    assert_eq!(
+        data.find_by_id("#13").unwrap().notes,
+        Some(LONG_NOTES.replace("Plan", "Chapter"))
+    );
+}
@@ -0,0 +1,202 @@
+//! Integration tests for the daily focus
+//!
+//! These tests cover choosing today's most important tasks with `set_focus`,
+//! the lapse of yesterday's focus and the plan returned by `today`.
+
+mod common;
+
+use chrono::{Duration, NaiveDate};
+use common::action;
+use gtd_mcp::focus::{self, Plan};
+use gtd_mcp::{FixedClock, GtdData, GtdServerHandler, Nota, NotaStatus};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2030, 1, 10).unwrap()
+}
+
+fn sample() -> GtdData {
+    let today = today();
+    let mut data = GtdData::new();
+    data.add(Nota {
+        context: Some("@office".to_string()),
+        estimate: Some(45),
+        ..action("write-report")
+    });
+    data.add(action("call-bank"));
+    data.add(action("book-flights"));
+    data.add(action("fix-bike"));
+    data.add(Nota {
+        due_date: Some(today - Duration::days(1)),
+        ..action("pay-rent")
+    });
+    data.add(Nota {
+        due_date: Some(today),
+        ..action("file-taxes")
+    });
+    data.add(Nota {
+        due_date: Some(today + Duration::days(1)),
+        ..action("renew-visa")
+    });
+    data.add(Nota {
+        status: NotaStatus::calendar,
+        start_date: Some(today),
+        ..action("dentist")
+    });
+    data.add(Nota {
+        status: NotaStatus::calendar,
+        start_date: Some(today + Duration::days(1)),
+        ..action("haircut")
+    });
+    data.add(Nota {
+        status: NotaStatus::done,
+        ..action("old-task")
+    });
+    // 昨日のフォーカスは今日は数えない
+    data.add(Nota {
+        focus_date: Some(today - Duration::days(1)),
+        ..action("yesterday-focus")
+    });
+    data
+}
+
+async fn sample_handler(dir: &TempDir) -> GtdServerHandler {
+    let handler = GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .clock(Arc::new(FixedClock::on(today())))
+        .build()
+        .unwrap();
+    *handler.data.write().await = sample();
+    handler
+}
+
+fn ids<'a>(notas: &[&'a Nota]) -> Vec<&'a str> {
+    notas.iter().map(|nota| nota.id.as_str()).collect()
+}
+
+/// IDs of the items in focus on `today`
+fn focused(data: &GtdData) -> Vec<String> {
+    data.iter()
+        .filter(|nota| focus::is_focused(nota, today()))
+        .map(|nota| nota.id.clone())
+        .collect()
+}
+
+// 計画は今日開始の予定と今日までが期限の行動を集め、昨日のフォーカスは含めないことを確認
+#[test]
+fn test_plan_sections() {
+    let data = sample();
+    let plan = Plan::new(&data, today());
+    assert!(plan.focus.is_empty());
+    assert_eq!(ids(&plan.calendar), ["dentist"]);
+    // 期限の早い順で、明日が期限のものは入らない
+    assert_eq!(ids(&plan.due), ["pay-rent", "file-taxes"]);
+
+    assert_eq!(
+        plan.render(),
+        "Plan for Thu 2030-01-10\n\
+         \n\
+         Focus (0/3):\n\
+         - none yet: pick up to 3 next actions with set_focus\n\
+         \n\
+         Calendar:\n\
+         - [dentist] dentist\n\
+         \n\
+         Also due:\n\
+         - [pay-rent] pay rent (overdue since 2030-01-09)\n\
+         - [file-taxes] file taxes (due today)\n"
+    );
+}
+
+// フォーカスを設定すると選んだ項目だけが今日の日付になり、昨日のフォーカスは消えることを確認
+#[tokio::test]
+async fn test_set_focus_returns_plan() {
+    let dir = TempDir::new().unwrap();
+    let handler = sample_handler(&dir).await;
+
+    let returned = handler
+        .set_focus(vec!["write-report".to_string(), " call-bank ".to_string()])
+        .await
+        .unwrap();
+    let data = handler.data.read().await;
+    assert_eq!(focused(&data), ["write-report", "call-bank"]);
+    assert_eq!(data.find_by_id("yesterday-focus").unwrap().focus_date, None);
+    let plan = Plan::new(&data, today());
+    assert_eq!(ids(&plan.focus), ["write-report", "call-bank"]);
+    assert_eq!(ids(&plan.due), ["pay-rent", "file-taxes"]);
+    // 返される計画と today の計画は現在のデータから作られる
+    assert_eq!(returned, plan.render());
+    drop(data);
+    assert_eq!(handler.today().await.unwrap(), returned);
+
+    // 期限のある行動をフォーカスすると「ほかの期限」から外れる
+    handler
+        .set_focus(vec!["pay-rent".to_string()])
+        .await
+        .unwrap();
+    let data = handler.data.read().await;
+    assert_eq!(focused(&data), ["pay-rent"]);
+    let plan = Plan::new(&data, today());
+    assert_eq!(ids(&plan.due), ["file-taxes"]);
+    // 選び直すと前のフォーカスは置き換えられる
+    assert_eq!(data.find_by_id("write-report").unwrap().focus_date, None);
+}
+
+// 完了したフォーカス項目も今日の計画に残ることを確認
+#[tokio::test]
+async fn test_done_focus_stays_in_plan() {
+    let dir = TempDir::new().unwrap();
+    let handler = sample_handler(&dir).await;
+    handler
+        .set_focus(vec!["fix-bike".to_string()])
+        .await
+        .unwrap();
+    handler
+        .service()
+        .change_status(&["fix-bike".to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+
+    let data = handler.data.read().await;
+    let plan = Plan::new(&data, today());
+    assert_eq!(ids(&plan.focus), ["fix-bike"]);
+    assert_eq!(plan.focus[0].status, NotaStatus::done);
+    // 翌日にはフォーカスが外れる
+    assert!(
+        Plan::new(&data, today() + Duration::days(1))
+            .focus
+            .is_empty()
+    );
+}
+
+// 件数の上限と、存在しない・完了済みの項目が拒否され、フォーカスが変わらないことを確認
+#[tokio::test]
+async fn test_set_focus_rejects_invalid_choices() {
+    let dir = TempDir::new().unwrap();
+    let handler = sample_handler(&dir).await;
+    handler
+        .set_focus(vec!["call-bank".to_string()])
+        .await
+        .unwrap();
+
+    let ids = ["write-report", "call-bank", "book-flights", "fix-bike"];
+    let result = handler
+        .set_focus(ids.iter().map(|id| id.to_string()).collect())
+        .await;
+    assert!(result.is_err());
+
+    let result = handler
+        .set_focus(vec!["old-task".to_string(), "missing".to_string()])
+        .await;
+    assert!(result.is_err());
+    // 開いたタスクは選べるが、参照資料は選べない
+    assert!(focus::can_focus(&action("x")));
+    assert!(!focus::can_focus(&Nota {
+        status: NotaStatus::reference,
+        ..action("x")
+    }));
+
+    let data = handler.data.read().await;
+    assert_eq!(focused(&data), ["call-bank"]);
+}
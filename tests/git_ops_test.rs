@@ -3,8 +3,11 @@
 //! These tests verify the Git integration functionality,
 //! including commit and sync operations.
 
+mod common;
+
 use git2::{Repository, Signature, Time};
-use gtd_mcp::GitOps;
+use gtd_mcp::git_ops::GitConflict;
+use gtd_mcp::{Cancellation, Cancelled, GitOps};
 use std::fs;
 use std::path::Path;
 use tempfile::TempDir;
@@ -94,6 +97,35 @@ fn test_commit() {
     assert_eq!(commit.message().unwrap(), "Update gtd.toml");
 }
 
+// コミットの作者を設定で上書きできることを確認
+#[test]
+fn test_commit_with_configured_author() {
+    let (temp_dir, repo) = setup_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let file_path = temp_dir.path().join("gtd.toml");
+    fs::write(&file_path, "test content").unwrap();
+
+    let git_ops = GitOps::new(&file_path)
+        .with_author(Some("GTD Bot".to_string()), Some("gtd@local".to_string()));
+    git_ops.commit(&file_path, "Update gtd.toml").unwrap();
+
+    let head = repo.head().unwrap();
+    let commit = repo.find_commit(head.target().unwrap()).unwrap();
+    assert_eq!(commit.author().name().unwrap(), "GTD Bot");
+    assert_eq!(commit.author().email().unwrap(), "gtd@local");
+    assert_eq!(commit.committer().name().unwrap(), "GTD Bot");
+
+    // 片方だけ指定した場合、もう片方は git config の値を使う
+    fs::write(&file_path, "more content").unwrap();
+    let git_ops = GitOps::new(&file_path).with_author(Some("GTD Bot".to_string()), None);
+    git_ops.commit(&file_path, "Update again").unwrap();
+    let head = repo.head().unwrap();
+    let commit = repo.find_commit(head.target().unwrap()).unwrap();
+    assert_eq!(commit.author().name().unwrap(), "GTD Bot");
+    assert_eq!(commit.author().email().unwrap(), "test@example.com");
+}
+
 // git管理されていないファイルでのsync操作テスト
 #[test]
 fn test_sync_non_git_file() {
@@ -106,3 +138,299 @@ fn test_sync_non_git_file() {
     // Should succeed but do nothing
     assert!(result.is_ok());
 }
+
+// 別マシン（同じ remote の別クローン）をシミュレートし、ファイルを書いてコミット・push する
+fn commit_on_other_machine(remote_dir: &Path, file_name: &str, content: &str) {
+    let other_dir = common::clone_remote(remote_dir);
+    let file_path = other_dir.path().join(file_name);
+    fs::write(&file_path, content).unwrap();
+    let git_ops = GitOps::new(&file_path);
+    git_ops
+        .sync(&file_path, &format!("Update {} elsewhere", file_name))
+        .unwrap();
+}
+
+fn remote_head_message(remote_dir: &Path) -> String {
+    let repo = Repository::open_bare(remote_dir).unwrap();
+    let head = repo.head().unwrap();
+    let commit = repo.find_commit(head.target().unwrap()).unwrap();
+    commit.message().unwrap().to_string()
+}
+
+// リモートが先行していても fetch + rebase して push できることを確認
+#[test]
+fn test_sync_rebases_onto_remote_changes() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    commit_on_other_machine(remote_dir.path(), "other.txt", "from elsewhere");
+
+    let file_path = work_dir.path().join("gtd.toml");
+    fs::write(&file_path, "local content").unwrap();
+    let git_ops = GitOps::new(&file_path);
+    let integrated = git_ops
+        .sync_with_outcome(&file_path, "Local change")
+        .unwrap();
+    assert!(integrated);
+
+    // ローカルのコミットがリモートの先頭になり、相手の変更も取り込まれている
+    assert_eq!(remote_head_message(remote_dir.path()), "Local change");
+    assert_eq!(
+        fs::read_to_string(work_dir.path().join("other.txt")).unwrap(),
+        "from elsewhere"
+    );
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "local content");
+}
+
+// リモートの変更のみの場合は fast-forward で取り込まれることを確認
+#[test]
+fn test_pull_fast_forwards() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    commit_on_other_machine(remote_dir.path(), "gtd.toml", "remote content");
+
+    let file_path = work_dir.path().join("gtd.toml");
+    GitOps::new(&file_path).pull().unwrap();
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "remote content");
+}
+
+// 同じファイルを両方で変更した場合は対処方法つきの競合エラーになることを確認
+#[test]
+fn test_sync_conflict_is_reported_and_rebase_aborted() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    commit_on_other_machine(remote_dir.path(), "gtd.toml", "remote content\n");
+
+    let file_path = work_dir.path().join("gtd.toml");
+    fs::write(&file_path, "local content\n").unwrap();
+    let git_ops = GitOps::new(&file_path);
+    let error = git_ops.sync(&file_path, "Local change").unwrap_err();
+
+    let conflict = error
+        .downcast_ref::<GitConflict>()
+        .expect("error should be a GitConflict");
+    assert_eq!(conflict.paths, vec!["gtd.toml".to_string()]);
+    let message = error.to_string();
+    assert!(message.contains("Sync conflict: gtd.toml"));
+    assert!(message.contains("git pull --rebase"));
+
+    // rebase は中断され、ローカルのコミットと作業ツリーはそのまま
+    let repo = Repository::open(work_dir.path()).unwrap();
+    assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    let head = repo.head().unwrap();
+    let commit = repo.find_commit(head.target().unwrap()).unwrap();
+    assert_eq!(commit.message().unwrap(), "Local change");
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "local content\n");
+    assert_eq!(
+        remote_head_message(remote_dir.path()),
+        "Update gtd.toml elsewhere"
+    );
+}
+
+// 未 push のコミット数が remote-tracking ブランチとの差分で数えられることを確認
+#[test]
+fn test_unpushed_commits_counts_local_commits() {
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let file_path = work_dir.path().join("gtd.toml");
+    let git_ops = GitOps::new(&file_path);
+    assert_eq!(git_ops.unpushed_commits().unwrap(), Some(0));
+
+    fs::write(&file_path, "first").unwrap();
+    git_ops.commit(&file_path, "First").unwrap();
+    fs::write(&file_path, "second").unwrap();
+    git_ops.commit(&file_path, "Second").unwrap();
+    assert_eq!(git_ops.unpushed_commits().unwrap(), Some(2));
+
+    git_ops.push().unwrap();
+    assert_eq!(git_ops.unpushed_commits().unwrap(), Some(0));
+}
+
+// 手動同期で受信・送信したコミットが報告されることを確認
+#[test]
+fn test_sync_remote_reports_received_and_sent() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    commit_on_other_machine(remote_dir.path(), "other.txt", "from elsewhere");
+
+    // オフライン中の保存を想定: コミットのみで push していない
+    let file_path = work_dir.path().join("gtd.toml");
+    fs::write(&file_path, "local content").unwrap();
+    let git_ops = GitOps::new(&file_path);
+    git_ops.commit(&file_path, "Local change").unwrap();
+
+    let report = git_ops.sync_remote().unwrap();
+    assert_eq!(
+        report.received,
+        vec!["Update other.txt elsewhere".to_string()]
+    );
+    assert_eq!(report.sent, vec!["Local change".to_string()]);
+    assert_eq!(remote_head_message(remote_dir.path()), "Local change");
+
+    // 2回目は何もやり取りしない
+    let report = git_ops.sync_remote().unwrap();
+    assert!(report.received.is_empty());
+    assert!(report.sent.is_empty());
+}
+
+// 作業ツリーの変更と ahead/behind 件数が報告されることを確認
+#[test]
+fn test_status_reports_dirty_and_ahead_behind() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let file_path = work_dir.path().join("gtd.toml");
+    let git_ops = GitOps::new(&file_path);
+
+    let status = git_ops.status().unwrap().unwrap();
+    assert!(!status.is_dirty());
+    assert_eq!((status.ahead, status.behind), (Some(0), Some(0)));
+
+    fs::write(&file_path, "local content").unwrap();
+    let status = git_ops.status().unwrap().unwrap();
+    assert_eq!(status.changed_files, vec!["gtd.toml".to_string()]);
+
+    git_ops.commit(&file_path, "Local change").unwrap();
+    commit_on_other_machine(remote_dir.path(), "other.txt", "from elsewhere");
+    let repo = Repository::open(work_dir.path()).unwrap();
+    let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+    repo.find_remote("origin")
+        .unwrap()
+        .fetch(&[branch], None, None)
+        .unwrap();
+
+    let status = git_ops.status().unwrap().unwrap();
+    assert!(!status.is_dirty());
+    assert_eq!((status.ahead, status.behind), (Some(1), Some(1)));
+    assert!(status.last_fetch.is_some());
+
+    // Git 管理外のファイルは None
+    let temp_dir = TempDir::new().unwrap();
+    assert!(
+        GitOps::new(&temp_dir.path().join("gtd.toml"))
+            .status()
+            .unwrap()
+            .is_none()
+    );
+}
+
+// リポジトリもリモートもないディレクトリを初期化し、ローカルのみでコミットできることを確認
+#[test]
+fn test_bootstrap_without_remote_commits_locally() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("data").join("gtd.toml");
+    let mut git_ops = GitOps::new(&file_path);
+    assert!(!git_ops.is_git_managed());
+
+    let actions = git_ops.bootstrap(&file_path, None).unwrap();
+    assert_eq!(actions.len(), 3);
+    assert!(git_ops.is_git_managed());
+
+    // remote がなくても commit のみで同期が成功する
+    fs::write(&file_path, "content").unwrap();
+    git_ops.sync(&file_path, "First save").unwrap();
+    let repo = Repository::open(temp_dir.path().join("data")).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.message().unwrap(), "First save");
+    assert_eq!(
+        head.parent(0).unwrap().message().unwrap(),
+        "Initialize GTD data repository"
+    );
+
+    // 2回目は何もしない
+    assert_eq!(git_ops.bootstrap(&file_path, None).unwrap().len(), 1);
+
+    // 明示的な同期は remote の設定を促す
+    let error = git_ops.sync_remote().unwrap_err();
+    assert!(error.to_string().contains("git remote add origin"));
+}
+
+// 空のリモートを指定した場合は初期コミットを push することを確認
+#[test]
+fn test_bootstrap_pushes_initial_commit_to_empty_remote() {
+    let remote_dir = TempDir::new().unwrap();
+    Repository::init_bare(remote_dir.path()).unwrap();
+    let work_dir = TempDir::new().unwrap();
+    let file_path = work_dir.path().join("gtd.toml");
+    fs::write(&file_path, "existing data").unwrap();
+
+    let mut git_ops = GitOps::new(&file_path);
+    let actions = git_ops
+        .bootstrap(&file_path, Some(remote_dir.path().to_str().unwrap()))
+        .unwrap();
+    assert!(actions.iter().any(|a| a.starts_with("Added remote origin")));
+    assert!(
+        actions
+            .iter()
+            .any(|a| a == "Pushed initial commit to origin")
+    );
+    assert_eq!(
+        remote_head_message(remote_dir.path()),
+        "Initialize GTD data repository"
+    );
+    assert_eq!(git_ops.unpushed_commits().unwrap(), Some(0));
+}
+
+// 既に履歴のあるリモートを指定した場合はその履歴を取り込むことを確認
+#[test]
+fn test_bootstrap_adopts_existing_remote_history() {
+    let (_seed_dir, remote_dir) = common::setup_repo_with_remote();
+    commit_on_other_machine(remote_dir.path(), "gtd.toml", "remote data");
+
+    let work_dir = TempDir::new().unwrap();
+    let file_path = work_dir.path().join("gtd.toml");
+    let mut git_ops = GitOps::new(&file_path);
+    let actions = git_ops
+        .bootstrap(&file_path, Some(remote_dir.path().to_str().unwrap()))
+        .unwrap();
+    assert!(
+        actions
+            .iter()
+            .any(|a| a.starts_with("Checked out existing history"))
+    );
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "remote data");
+
+    fs::write(&file_path, "updated data").unwrap();
+    git_ops.sync(&file_path, "Update from new machine").unwrap();
+    assert_eq!(
+        remote_head_message(remote_dir.path()),
+        "Update from new machine"
+    );
+}
+
+// タグが HEAD に作成・移動され、remote に push されることを確認
+#[test]
+fn test_tag_head_creates_moves_and_pushes_tag() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let file_path = work_dir.path().join("gtd.toml");
+    let git_ops = GitOps::new(&file_path);
+
+    git_ops.tag_head("weekly-2025-W23", "Snapshot").unwrap();
+    fs::write(&file_path, "later change").unwrap();
+    git_ops.sync(&file_path, "Later change").unwrap();
+    git_ops
+        .tag_head("weekly-2025-W23", "Snapshot again")
+        .unwrap();
+
+    let remote = Repository::open_bare(remote_dir.path()).unwrap();
+    let tagged = remote
+        .revparse_single("weekly-2025-W23")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(tagged.message().unwrap(), "Later change");
+}
+
+// 取り消された手動同期は取得も push もせずに止まることを確認
+#[test]
+fn test_cancelled_sync_remote_changes_nothing() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    commit_on_other_machine(remote_dir.path(), "other.txt", "from elsewhere");
+    let file_path = work_dir.path().join("gtd.toml");
+    fs::write(&file_path, "local content").unwrap();
+    let git_ops = GitOps::new(&file_path);
+    git_ops.commit(&file_path, "Local change").unwrap();
+
+    let cancellation = Cancellation::default();
+    cancellation.cancel();
+    let error = git_ops.sync_remote_cancellable(&cancellation).unwrap_err();
+    assert_eq!(error.downcast_ref::<Cancelled>(), Some(&Cancelled));
+    assert!(!work_dir.path().join("other.txt").exists());
+    assert_eq!(
+        remote_head_message(remote_dir.path()),
+        "Update other.txt elsewhere"
+    );
+    assert_eq!(git_ops.unpushed_commits().unwrap(), Some(1));
+}
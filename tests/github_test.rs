@@ -0,0 +1,185 @@
+//! Unit tests for the GitHub issue import
+//!
+//! These tests cover parsing of the issues API, the status given to each
+//! issue, matching re-imported issues by their link, and the API client
+//! against a local server.
+
+mod common;
+
+use common::date;
+use gtd_mcp::github::{self, GitHubClient, Issue, IssueFilter};
+use gtd_mcp::{Cancellation, Cancelled, GtdData, NotaStatus};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+const ISSUES: &str = r#"[
+  {
+    "number": 12,
+    "title": "Fix login redirect",
+    "body": "Steps:\r\n1. Log in",
+    "html_url": "https://github.com/acme/web/issues/12",
+    "labels": [{"name": "bug"}],
+    "assignees": [{"login": "alice"}]
+  },
+  {
+    "number": 15,
+    "title": "Upgrade TLS library",
+    "body": null,
+    "html_url": "https://github.com/acme/web/issues/15",
+    "labels": [{"name": "Blocked: upstream"}],
+    "assignees": [{"login": "alice"}]
+  },
+  {
+    "number": 16,
+    "title": "Add dark mode",
+    "html_url": "https://github.com/acme/web/pull/16",
+    "pull_request": {"url": "https://api.github.com/repos/acme/web/pulls/16"}
+  },
+  {
+    "number": 20,
+    "title": "Translate settings page",
+    "body": "",
+    "html_url": "https://github.com/acme/web/issues/20",
+    "labels": [],
+    "assignees": [{"login": "bob"}]
+  }
+]"#;
+
+// プルリクエストが除外され、課題のフィールドが読み込まれることを確認
+#[test]
+fn test_parse_issues_skips_pull_requests() {
+    let issues = github::parse_issues(ISSUES).unwrap();
+    let numbers: Vec<u64> = issues.iter().map(|issue| issue.number).collect();
+    assert_eq!(numbers, vec![12, 15, 20]);
+    assert_eq!(issues[0].labels[0].name, "bug");
+    assert_eq!(issues[1].body, None);
+
+    assert!(github::parse_issues(r#"{"message": "Bad credentials"}"#).is_err());
+    assert_eq!("mentioned".parse(), Ok(IssueFilter::Mentioned));
+    assert!("mine".parse::<IssueFilter>().is_err());
+}
+
+// 自分の担当課題はnext_action、ブロック中や他人の担当はwaiting_forになることを確認
+#[test]
+fn test_status_from_assignee_and_labels() {
+    let issues = github::parse_issues(ISSUES).unwrap();
+    assert_eq!(issues[0].status("alice"), NotaStatus::next_action);
+    assert_eq!(issues[0].status("Alice"), NotaStatus::next_action);
+    assert_eq!(issues[1].status("alice"), NotaStatus::waiting_for);
+    assert_eq!(issues[2].status("alice"), NotaStatus::waiting_for);
+    assert_eq!(Issue::default().status("alice"), NotaStatus::waiting_for);
+}
+
+// 取り込んだ項目にリンクが入り、再取り込みではリンクで照合してタイトルだけ更新することを確認
+#[test]
+fn test_reimport_matches_by_link() {
+    let mut data = GtdData::new();
+    let issues = github::parse_issues(ISSUES).unwrap();
+
+    let summary = github::import(&mut data, "acme/web", &issues, "alice", date("2025-05-01"));
+    assert_eq!(summary.added, vec!["web-12", "web-15", "web-20"]);
+    let nota = data.find_by_id("web-12").unwrap();
+    assert_eq!(nota.title, "Fix login redirect");
+    assert_eq!(nota.status, NotaStatus::next_action);
+    assert_eq!(nota.notes.as_deref(), Some("Steps:\n1. Log in"));
+    assert_eq!(
+        nota.link.as_deref(),
+        Some("https://github.com/acme/web/issues/12")
+    );
+    assert_eq!(data.find_by_id("web-20").unwrap().notes, None);
+
+    // 整理した後の状態は再取り込みで上書きされない
+    let mut organized = data.find_by_id("web-12").unwrap();
+    organized.status = NotaStatus::done;
+    data.update("web-12", organized);
+
+    let mut renamed = issues.clone();
+    renamed[2].title = "Translate the settings page".to_string();
+    let summary = github::import(&mut data, "acme/web", &renamed, "alice", date("2025-05-08"));
+    assert!(summary.added.is_empty());
+    assert_eq!(summary.updated, vec!["web-20"]);
+    assert_eq!(summary.unchanged, 2);
+    assert_eq!(data.find_by_id("web-12").unwrap().status, NotaStatus::done);
+    let nota = data.find_by_id("web-20").unwrap();
+    assert_eq!(nota.title, "Translate the settings page");
+    assert_eq!(nota.updated_at, date("2025-05-08"));
+}
+
+/// Answer each request on a local port with the next of `bodies`, returning
+/// the API URL and the received request lines
+fn serve(bodies: Vec<&'static str>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for body in bodies {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if line.to_lowercase().starts_with("authorization:") {
+                    assert_eq!(line.trim_end(), "Authorization: Bearer secret");
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            requests.push(request_line.trim_end().to_string());
+        }
+        requests
+    });
+    (url, handle)
+}
+
+// クライアントがトークンで利用者を調べ、担当課題を取得することを確認
+#[test]
+fn test_client_fetches_assigned_issues() {
+    let (url, server) = serve(vec![r#"{"login": "alice"}"#, ISSUES]);
+    let client = GitHubClient::new(format!("{}/", url), "secret");
+
+    let login = client.login().unwrap();
+    assert_eq!(login, "alice");
+    let issues = client
+        .issues("acme/web", IssueFilter::Assigned, &login)
+        .unwrap();
+    assert_eq!(issues.len(), 3);
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0], "GET /user HTTP/1.1");
+    assert_eq!(
+        requests[1],
+        "GET /repos/acme/web/issues?state=open&per_page=100&page=1&assignee=alice HTTP/1.1"
+    );
+
+    // owner/name 形式でないリポジトリは送信前にエラー
+    assert!(client.issues("web", IssueFilter::All, "alice").is_err());
+    assert!(
+        client
+            .issues("acme/web/x", IssueFilter::All, "alice")
+            .is_err()
+    );
+}
+
+// 取り消されたクライアントは次のページを取得せずに Cancelled で止まることを確認
+#[test]
+fn test_cancelled_client_stops_before_fetching() {
+    let cancellation = Cancellation::default();
+    // 接続されることのないアドレス
+    let client =
+        GitHubClient::new("http://127.0.0.1:9", "secret").with_cancellation(cancellation.clone());
+    cancellation.cancel();
+
+    let error = client
+        .issues("acme/web", IssueFilter::All, "alice")
+        .unwrap_err();
+    assert_eq!(error.downcast_ref::<Cancelled>(), Some(&Cancelled));
+}
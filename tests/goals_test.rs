@@ -0,0 +1,223 @@
+//! Integration tests for goals
+//!
+//! These tests cover linking projects to goals with `update`, the goals
+//! overview with aggregate progress, and keeping goal links valid when a goal
+//! is trashed or the file is reloaded.
+
+mod common;
+
+use common::date;
+use gtd_mcp::goals;
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn item(id: &str, status: NotaStatus) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status,
+        ..Default::default()
+    }
+}
+
+/// Goal ID only (the other update fields are left unchanged)
+async fn set_goal(handler: &GtdServerHandler, id: &str, goal: &str) -> mcp_attr::Result<String> {
+    handler
+        .update(
+            id.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(goal.to_string()),
+        )
+        .await
+}
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        due_date: Some(date("2027-04-20")),
+        ..item("run-marathon", NotaStatus::goal)
+    });
+    data.add(item("learn-spanish", NotaStatus::goal));
+    data.add(Nota {
+        due_date: Some(date("2026-12-31")),
+        ..item("ship-book", NotaStatus::goal)
+    });
+    for (project, goal) in [
+        ("training-plan", Some("run-marathon")),
+        ("buy-gear", Some("run-marathon")),
+        ("write-chapters", Some("ship-book")),
+        ("taxes", None),
+    ] {
+        data.add(Nota {
+            goal: goal.map(str::to_string),
+            ..item(project, NotaStatus::project)
+        });
+    }
+    for (task, project, status) in [
+        ("long-run", "training-plan", NotaStatus::done),
+        ("interval-run", "training-plan", NotaStatus::next_action),
+        ("buy-shoes", "buy-gear", NotaStatus::done),
+        ("buy-watch", "buy-gear", NotaStatus::someday),
+        ("chapter-one", "write-chapters", NotaStatus::next_action),
+    ] {
+        data.add(Nota {
+            project: Some(project.to_string()),
+            ..item(task, status)
+        });
+    }
+    data
+}
+
+// ゴールが目標日順に並び、プロジェクトの進捗が合算されることを確認
+#[test]
+fn test_goals_overview() {
+    let data = sample();
+    assert_eq!(
+        data.goal_projects("run-marathon")
+            .iter()
+            .map(|n| n.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["training-plan", "buy-gear"]
+    );
+    assert_eq!(data.goal_progress("run-marathon").done, 2);
+    assert_eq!(data.goal_progress("run-marathon").total, 4);
+
+    assert_eq!(
+        goals::render(&data, date("2027-01-01")),
+        "Goals:\n\
+         - [ship-book] ship book (target: 2026-12-31, 1 day overdue)\n\
+         \x20 Progress: 0/1 done (0%) across 1 project\n\
+         \x20 - [write-chapters] write chapters (0/1 done (0%))\n\
+         - [run-marathon] run marathon (target: 2027-04-20, 109 days left)\n\
+         \x20 Progress: 2/4 done (50%) across 2 projects\n\
+         \x20 - [training-plan] training plan (1/2 done (50%))\n\
+         \x20 - [buy-gear] buy gear (1/2 done (50%))\n\
+         - [learn-spanish] learn spanish (no target date)\n\
+         \x20 No projects yet. Link one with update(id=\"<project>\", goal=\"learn-spanish\")\n\
+         \n\
+         Projects without a goal: taxes"
+    );
+    assert!(goals::render(&GtdData::new(), date("2027-01-01")).starts_with("No goals yet"));
+}
+
+// update でプロジェクトだけがゴールに紐付けられ、保存・再読み込み後も保たれることを確認
+#[tokio::test]
+async fn test_update_goal() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap().to_string();
+    let handler = GtdServerHandler::new(&path, false).unwrap();
+    {
+        let mut data = handler.data.write().await;
+        data.add(item("run-marathon", NotaStatus::goal));
+        data.add(item("training-plan", NotaStatus::project));
+        data.add(item("long-run", NotaStatus::next_action));
+    }
+
+    let message = set_goal(&handler, "training-plan", "run-ultra")
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("Goal 'run-ultra' does not exist.\nAvailable goals: run-marathon"),
+        "{}",
+        message
+    );
+    let message = set_goal(&handler, "long-run", "run-marathon")
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("Only projects can have a goal"),
+        "{}",
+        message
+    );
+
+    set_goal(&handler, "training-plan", "run-marathon")
+        .await
+        .unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("[[goal]]"), "{}", content);
+    assert!(content.contains("goal = \"run-marathon\""), "{}", content);
+
+    let reloaded = GtdServerHandler::new(&path, false).unwrap();
+    let response = reloaded.goals_overview().await.unwrap();
+    assert!(
+        response.contains("  - [training-plan] training plan (no tasks yet)"),
+        "{}",
+        response
+    );
+
+    // 空文字列で紐付けを解除できる
+    set_goal(&reloaded, "training-plan", "").await.unwrap();
+    let data = reloaded.data.read().await;
+    assert_eq!(data.find_by_id("training-plan").unwrap().goal, None);
+}
+
+// 紐付けられたゴールはカスケードなしではゴミ箱に入れられず、unlink で紐付けが外れることを確認
+#[tokio::test]
+async fn test_trash_linked_goal() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    {
+        let mut data = handler.data.write().await;
+        data.add(item("run-marathon", NotaStatus::goal));
+        data.add(Nota {
+            goal: Some("run-marathon".to_string()),
+            ..item("training-plan", NotaStatus::project)
+        });
+        assert!(data.is_referenced("run-marathon"));
+        assert!(data.check_integrity().is_empty());
+    }
+
+    let response = handler
+        .change_status(
+            vec!["run-marathon".to_string()],
+            "trash".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(response.contains("still referenced"), "{}", response);
+
+    handler
+        .change_status(
+            vec!["run-marathon".to_string()],
+            "trash".to_string(),
+            None,
+            Some("unlink".to_string()),
+        )
+        .await
+        .unwrap();
+    let data = handler.data.read().await;
+    let project = data.find_by_id("training-plan").unwrap();
+    assert_eq!(project.goal, None);
+    assert_eq!(project.status, NotaStatus::project);
+}
+
+// 存在しないゴールへの紐付けは整合性チェックで報告されることを確認
+#[test]
+fn test_integrity_reports_missing_goal() {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        goal: Some("run-marathon".to_string()),
+        ..item("training-plan", NotaStatus::project)
+    });
+    assert_eq!(
+        data.check_integrity(),
+        vec!["training-plan: goal 'run-marathon' does not exist"]
+    );
+}
@@ -95,7 +95,7 @@ fn test_gtd_data_update_nota_status() {
     data.add(nota);
 
     // Update status
-    data.move_status(&nota_id, NotaStatus::next_action);
+    data.move_status(&nota_id, NotaStatus::next_action, local_date_today());
 
     assert!(matches!(
         data.find_task_by_id(&nota_id).unwrap().status,
@@ -119,6 +119,20 @@ fn test_gtd_data_remove_task() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
@@ -146,6 +160,20 @@ fn test_gtd_data_move_status_inbox_to_trash() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
@@ -153,7 +181,7 @@ fn test_gtd_data_move_status_inbox_to_trash() {
     assert_eq!(data.trash().len(), 0);
 
     // Move task to trash
-    let result = data.move_status(&task_id, NotaStatus::trash);
+    let result = data.move_status(&task_id, NotaStatus::trash, local_date_today());
     assert!(result.is_some());
 
     // Verify task was moved
@@ -182,6 +210,20 @@ fn test_gtd_data_move_status_next_action_to_done() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
@@ -189,7 +231,7 @@ fn test_gtd_data_move_status_next_action_to_done() {
     assert_eq!(data.done().len(), 0);
 
     // Move task to done
-    let result = data.move_status(&task_id, NotaStatus::done);
+    let result = data.move_status(&task_id, NotaStatus::done, local_date_today());
     assert!(result.is_some());
 
     // Verify task was moved
@@ -218,12 +260,26 @@ fn test_gtd_data_move_status_multiple_transitions() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
 
     // inbox -> next_action
-    data.move_status(&task_id, NotaStatus::next_action);
+    data.move_status(&task_id, NotaStatus::next_action, local_date_today());
     assert_eq!(data.inbox().len(), 0);
     assert_eq!(data.next_action().len(), 1);
     assert!(matches!(
@@ -232,7 +288,7 @@ fn test_gtd_data_move_status_multiple_transitions() {
     ));
 
     // next_action -> waiting_for
-    data.move_status(&task_id, NotaStatus::waiting_for);
+    data.move_status(&task_id, NotaStatus::waiting_for, local_date_today());
     assert_eq!(data.next_action().len(), 0);
     assert_eq!(data.waiting_for().len(), 1);
     assert!(matches!(
@@ -241,7 +297,7 @@ fn test_gtd_data_move_status_multiple_transitions() {
     ));
 
     // waiting_for -> done
-    data.move_status(&task_id, NotaStatus::done);
+    data.move_status(&task_id, NotaStatus::done, local_date_today());
     assert_eq!(data.waiting_for().len(), 0);
     assert_eq!(data.done().len(), 1);
     assert!(matches!(
@@ -250,7 +306,7 @@ fn test_gtd_data_move_status_multiple_transitions() {
     ));
 
     // done -> trash
-    data.move_status(&task_id, NotaStatus::trash);
+    data.move_status(&task_id, NotaStatus::trash, local_date_today());
     assert_eq!(data.done().len(), 0);
     assert_eq!(data.trash().len(), 1);
     assert!(matches!(
@@ -276,13 +332,27 @@ fn test_gtd_data_move_status_to_calendar() {
         start_date: Some(date),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
     assert_eq!(data.inbox().len(), 1);
 
     // inbox -> calendar
-    let result = data.move_status(&task_id, NotaStatus::calendar);
+    let result = data.move_status(&task_id, NotaStatus::calendar, local_date_today());
     assert!(result.is_some());
     assert_eq!(data.inbox().len(), 0);
     assert_eq!(data.calendar().len(), 1);
@@ -297,7 +367,7 @@ fn test_gtd_data_move_status_to_calendar() {
 #[test]
 fn test_gtd_data_move_status_nonexistent_task() {
     let mut data = GtdData::new();
-    let result = data.move_status("nonexistent-id", NotaStatus::trash);
+    let result = data.move_status("nonexistent-id", NotaStatus::trash, local_date_today());
     assert!(result.is_none());
 }
 
@@ -317,12 +387,26 @@ fn test_gtd_data_move_status_preserves_properties() {
         start_date: NaiveDate::from_ymd_opt(2024, 12, 25),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
 
     // Move task to next_action
-    data.move_status(&task_id, NotaStatus::next_action);
+    data.move_status(&task_id, NotaStatus::next_action, local_date_today());
 
     // Verify all properties are preserved (except updated_at which should be updated)
     let moved_task = data.find_task_by_id(&task_id).unwrap();
@@ -353,6 +437,20 @@ fn test_task_with_project_and_context() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert_eq!(task.project.as_ref().unwrap(), "project-1");
@@ -375,6 +473,20 @@ fn test_task_with_start_date() {
         start_date: Some(date),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert_eq!(task.start_date.unwrap(), date);
@@ -395,6 +507,20 @@ fn test_calendar_task_with_start_date() {
         start_date: Some(date),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(matches!(task.status, NotaStatus::calendar));
@@ -415,6 +541,20 @@ fn test_reference_task() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(matches!(task.status, NotaStatus::reference));
@@ -441,6 +581,20 @@ fn test_move_to_reference() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
@@ -448,7 +602,7 @@ fn test_move_to_reference() {
     assert_eq!(data.reference().len(), 0);
 
     // Move to reference
-    let result = data.move_status(&task_id, NotaStatus::reference);
+    let result = data.move_status(&task_id, NotaStatus::reference, local_date_today());
     assert!(result.is_some());
 
     // Verify task was moved
@@ -483,6 +637,20 @@ fn test_list_reference_items() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
     }
@@ -523,6 +691,20 @@ fn test_task_status_variants() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
 
         match status {
@@ -535,8 +717,8 @@ fn test_task_status_variants() {
             NotaStatus::reference => assert!(matches!(task.status, NotaStatus::reference)),
             NotaStatus::trash => assert!(matches!(task.status, NotaStatus::trash)),
             NotaStatus::calendar => assert!(matches!(task.status, NotaStatus::calendar)),
-            NotaStatus::context | NotaStatus::project => {
-                panic!("context and project are not task statuses")
+            NotaStatus::context | NotaStatus::project | NotaStatus::goal | NotaStatus::template => {
+                panic!("context, project, goal and template are not task statuses")
             }
         }
     }
@@ -556,6 +738,20 @@ fn test_project_creation() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert_eq!(project.id, "project-1");
@@ -577,6 +773,20 @@ fn test_project_without_description() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(project.notes.is_none());
@@ -599,6 +809,20 @@ fn test_gtd_data_insert_project() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_project(project.clone());
@@ -624,6 +848,20 @@ fn test_context_creation() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert_eq!(context.name, "Office");
@@ -644,6 +882,20 @@ fn test_context_with_description() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert_eq!(context.name, "Office");
@@ -668,6 +920,20 @@ fn test_gtd_data_insert_context() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_context(context.clone());
@@ -693,6 +959,20 @@ fn test_gtd_data_insert_multiple_contexts() {
             start_date: None,
             created_at: None,
             updated_at: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_context(context);
     }
@@ -714,6 +994,20 @@ fn test_task_serialization() {
         start_date: NaiveDate::from_ymd_opt(2024, 12, 25),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     let serialized = toml::to_string(&task).unwrap();
@@ -743,6 +1037,20 @@ fn test_project_serialization() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_project(project.clone());
@@ -774,6 +1082,20 @@ fn test_context_serialization() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     let serialized = toml::to_string(&context).unwrap();
@@ -804,6 +1126,20 @@ fn test_gtd_data_serialization() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_task(task);
 
@@ -817,6 +1153,20 @@ fn test_gtd_data_serialization() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_project(project);
 
@@ -830,6 +1180,20 @@ fn test_gtd_data_serialization() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_context(context);
 
@@ -869,6 +1233,20 @@ fn test_task_filter_by_status() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
     }
@@ -904,6 +1282,20 @@ fn test_task_filter_by_project() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
     }
@@ -937,6 +1329,20 @@ fn test_task_filter_by_context() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
     }
@@ -986,6 +1392,20 @@ fn test_task_clone() {
         start_date: NaiveDate::from_ymd_opt(2024, 12, 25),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     let task2 = task1.clone();
@@ -1011,6 +1431,20 @@ fn test_enum_snake_case_serialization() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let serialized = toml::to_string(&data).unwrap();
@@ -1043,6 +1477,20 @@ fn test_gtd_data_insertion_order() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
     }
@@ -1074,6 +1522,20 @@ fn test_toml_serialization_order() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         });
     }
 
@@ -1088,6 +1550,20 @@ fn test_toml_serialization_order() {
             start_date: None,
             created_at: local_date_today(),
             updated_at: local_date_today(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         });
     }
 
@@ -1125,6 +1601,20 @@ fn test_complete_toml_output() {
         start_date: NaiveDate::from_ymd_opt(2024, 3, 15),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     // 最小限のフィールドを設定したタスクを追加（比較用）
@@ -1138,6 +1628,20 @@ fn test_complete_toml_output() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     // 全フィールドを設定したプロジェクトを追加
@@ -1151,6 +1655,20 @@ fn test_complete_toml_output() {
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     // 説明付きコンテキストを追加
@@ -1164,6 +1682,20 @@ fn test_complete_toml_output() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     // TOML出力を生成
@@ -1332,6 +1864,20 @@ fn test_validate_task_project_valid() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let task = Task {
@@ -1344,6 +1890,20 @@ fn test_validate_task_project_valid() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_task_project(&task));
@@ -1365,6 +1925,20 @@ fn test_validate_task_project_invalid() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(!data.validate_task_project(&task));
@@ -1386,6 +1960,20 @@ fn test_validate_task_project_none() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_task_project(&task));
@@ -1407,6 +1995,20 @@ fn test_validate_task_context_valid() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let task = Task {
@@ -1419,6 +2021,20 @@ fn test_validate_task_context_valid() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_task_context(&task));
@@ -1440,6 +2056,20 @@ fn test_validate_task_context_invalid() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(!data.validate_task_context(&task));
@@ -1461,6 +2091,20 @@ fn test_validate_task_context_none() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_task_context(&task));
@@ -1482,6 +2126,20 @@ fn test_validate_task_references_all_valid() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     data.add_context(Context {
@@ -1494,6 +2152,20 @@ fn test_validate_task_references_all_valid() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let task = Task {
@@ -1506,6 +2178,20 @@ fn test_validate_task_references_all_valid() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_task_references(&task));
@@ -1527,6 +2213,20 @@ fn test_validate_task_references_invalid_project() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let task = Task {
@@ -1539,6 +2239,20 @@ fn test_validate_task_references_invalid_project() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(!data.validate_task_references(&task));
@@ -1560,6 +2274,20 @@ fn test_validate_task_references_invalid_context() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let task = Task {
@@ -1572,6 +2300,20 @@ fn test_validate_task_references_invalid_context() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(!data.validate_task_references(&task));
@@ -1593,6 +2335,20 @@ fn test_validate_task_references_both_invalid() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(!data.validate_task_references(&task));
@@ -1613,6 +2369,20 @@ fn test_task_created_at_and_updated_at() {
         start_date: None,
         created_at: date,
         updated_at: date,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert_eq!(task.created_at, date);
@@ -1637,6 +2407,20 @@ fn test_task_updated_at_changes() {
         start_date: None,
         created_at: created_date,
         updated_at: created_date,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     // タスクを更新
@@ -1666,15 +2450,29 @@ fn test_task_created_at_immutable() {
         start_date: None,
         created_at: created_date,
         updated_at: created_date,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     data.add_task(task);
 
     // タスクのステータスを更新
-    if let Some(task) = data.find_task_by_id_mut(&task_id) {
-        task.status = NotaStatus::next_action;
-        task.updated_at = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
-    }
+    let mut task = data.find_task_by_id(&task_id).unwrap().clone();
+    task.status = NotaStatus::next_action;
+    task.updated_at = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+    data.update(&task_id, task);
 
     // created_atは変更されていないことを確認
     let task = data.find_task_by_id(&task_id).unwrap();
@@ -1696,6 +2494,20 @@ fn test_task_dates_serialization() {
         start_date: None,
         created_at: date,
         updated_at: date,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     let serialized = toml::to_string(&task).unwrap();
@@ -1783,6 +2595,20 @@ fn test_validate_project_context_valid() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let project = Project {
@@ -1795,6 +2621,20 @@ fn test_validate_project_context_valid() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: Some("Office".to_string()),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_project_context(&project));
@@ -1816,6 +2656,20 @@ fn test_validate_project_context_invalid() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: Some("NonExistent".to_string()),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(!data.validate_project_context(&project));
@@ -1837,6 +2691,20 @@ fn test_validate_project_context_none() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_project_context(&project));
@@ -1858,6 +2726,20 @@ fn test_project_and_task_with_same_context() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     });
 
     let project = Project {
@@ -1870,6 +2752,20 @@ fn test_project_and_task_with_same_context() {
         created_at: local_date_today(),
         updated_at: local_date_today(),
         context: Some("Office".to_string()),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_project(project.clone());
 
@@ -1883,6 +2779,20 @@ fn test_project_and_task_with_same_context() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     assert!(data.validate_project_context(&project));
@@ -2144,6 +3054,20 @@ fn test_task_status_order_in_toml_serialization() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         });
     }
 
@@ -2190,6 +3114,20 @@ fn test_hashmap_serialization_order() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         tasks_map.insert(task.id.clone(), task);
     }
@@ -2231,6 +3169,20 @@ fn test_vec_serialization_maintains_order() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         tasks_vec.push(task);
     }
@@ -2268,6 +3220,20 @@ fn test_nota_from_task() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     let nota = nota_from_task(task.clone());
@@ -2295,6 +3261,20 @@ fn test_nota_from_project() {
         start_date: None,
         created_at: local_date_today(),
         updated_at: local_date_today(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     let nota = nota_from_project(project.clone());
@@ -2321,6 +3301,20 @@ fn test_nota_from_context() {
         start_date: None,
         created_at: Some(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
         updated_at: Some(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
 
     let nota = nota_from_context(context.clone());
@@ -2763,16 +3757,17 @@ fn test_status_filtering_at_scale() {
 
 /// Test memory efficiency of current design
 ///
-/// This test documents the memory characteristics of HashMap<String, NotaStatus>
-/// vs a hypothetical Arc<RefCell<Nota>> design.
+/// This test documents the memory characteristics of the one ID index,
+/// HashMap<String, usize> (ID → position in the Vec), vs a hypothetical
+/// Arc<RefCell<Nota>> design.
 #[test]
-fn test_nota_map_memory_footprint() {
+fn test_id_index_memory_footprint() {
     use std::mem::size_of;
 
-    // Current design: HashMap stores ID (String) + Status (enum)
+    // Current design: HashMap stores ID (String) + position (usize)
     let string_size = size_of::<String>(); // 24 bytes (ptr + len + cap)
-    let status_size = size_of::<NotaStatus>(); // 1 byte (enum)
-    let entry_size = string_size + status_size; // ~25 bytes per entry
+    let position_size = size_of::<usize>(); // 8 bytes
+    let entry_size = string_size + position_size; // ~32 bytes per entry
 
     // Hypothetical Arc<RefCell<Nota>> design would need:
     // Arc = 16 bytes (ptr + ref counts)
@@ -2780,8 +3775,8 @@ fn test_nota_map_memory_footprint() {
     // Total per entry = 24 bytes EXTRA overhead (plus the original Nota)
 
     // For 500 notas:
-    // Current: 500 × 25 = 12.5 KB
-    // Arc/RefCell: 500 × (25 + 24) = 24.5 KB (double the memory)
+    // Current: 500 × 32 = 16 KB
+    // Arc/RefCell: 500 × (32 + 24) = 28 KB
 
     // This validates that current design is more memory efficient
     println!("Current HashMap entry size: ~{} bytes", entry_size);
@@ -2789,7 +3784,7 @@ fn test_nota_map_memory_footprint() {
 
     // The test itself just validates the size calculations are reasonable
     assert!(string_size >= 16); // String has pointer + metadata
-    assert!(status_size <= 8); // Enum should be small
+    assert!(position_size <= 8); // A position is one machine word
 }
 
 /// Test that trash appears at the end in TOML serialization
@@ -3076,3 +4071,284 @@ updated_at = "2025-10-31"
     assert!(nota.recurrence_config.is_none());
     assert!(!nota.is_recurring());
 }
+
+// nota 単位のマージ: 和集合になり、updated_at が新しい方が採用されることを確認
+#[test]
+fn test_merge_union_and_newest_wins() {
+    let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+    let nota = |id: &str, title: &str, updated: &str| Nota {
+        id: id.to_string(),
+        title: title.to_string(),
+        created_at: date("2024-01-01"),
+        updated_at: date(updated),
+        ..Default::default()
+    };
+
+    let mut local = GtdData::new();
+    local.add(nota("older-remote", "Local wins", "2024-03-01"));
+    local.add(nota("newer-remote", "Local loses", "2024-03-01"));
+    local.add(nota("tie", "Local tie", "2024-03-01"));
+    local.add(nota("local-only", "Local only", "2024-03-01"));
+    local.task_counter = 5;
+
+    let mut remote = GtdData::new();
+    remote.add(nota("remote-only", "Remote only", "2024-03-01"));
+    remote.add(nota("older-remote", "Remote loses", "2024-02-01"));
+    let mut newer = nota("newer-remote", "Remote wins", "2024-04-01");
+    newer.status = NotaStatus::done;
+    remote.add(newer);
+    remote.add(nota("tie", "Remote tie", "2024-03-01"));
+    remote.task_counter = 7;
+    remote.project_counter = 2;
+
+    let merged = local.merge(&remote);
+    let ids: Vec<String> = merged.list_all(None).into_iter().map(|n| n.id).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "older-remote",
+            "newer-remote",
+            "tie",
+            "local-only",
+            "remote-only"
+        ]
+    );
+    assert_eq!(
+        merged.find_by_id("older-remote").unwrap().title,
+        "Local wins"
+    );
+    let newer = merged.find_by_id("newer-remote").unwrap();
+    assert_eq!(newer.title, "Remote wins");
+    assert_eq!(newer.status, NotaStatus::done);
+    assert_eq!(merged.find_by_id("tie").unwrap().title, "Local tie");
+    assert_eq!(merged.task_counter, 7);
+    assert_eq!(merged.project_counter, 2);
+}
+
+// 大文字小文字を区別しない ID 照合のテスト
+// 保存時の表記を保ったまま検索・参照・重複判定で大文字小文字を無視することを確認
+#[test]
+fn test_case_insensitive_ids() {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "Office".to_string(),
+        title: "Office".to_string(),
+        status: NotaStatus::context,
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "Call-Bank".to_string(),
+        title: "Call the bank".to_string(),
+        ..Default::default()
+    });
+
+    // 既定では大文字小文字を区別する
+    assert!(data.find_context_by_name("office").is_none());
+    assert!(!data.contains_id("call-bank"));
+
+    data.set_case_insensitive_ids(true);
+    assert_eq!(data.find_context_by_name("office").unwrap().id, "Office");
+    assert_eq!(data.find_by_id("CALL-BANK").unwrap().id, "Call-Bank");
+    assert_eq!(data.resolve_id("call-bank"), Some("Call-Bank"));
+    assert!(data.contains_id("OFFICE"));
+
+    // 参照は保存済みの表記に揃えられる
+    data.add(Nota {
+        id: "print".to_string(),
+        title: "Print slides".to_string(),
+        status: NotaStatus::next_action,
+        context: Some("office".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(
+        data.find_by_id("print").unwrap().context.as_deref(),
+        Some("Office")
+    );
+    assert!(data.is_referenced("OFFICE"));
+    assert_eq!(data.list_filtered(None, None, Some("office")).len(), 1);
+
+    // 別の表記でも更新・ステータス変更・削除できる
+    let mut task = data.find_by_id("call-bank").unwrap();
+    task.title = "Call the bank today".to_string();
+    assert!(data.update("call-bank", task).is_some());
+    assert!(
+        data.move_status("CALL-BANK", NotaStatus::done, local_date_today())
+            .is_some()
+    );
+    let task = data.find_by_id("Call-Bank").unwrap();
+    assert_eq!(task.title, "Call the bank today");
+    assert_eq!(task.status, NotaStatus::done);
+    assert_eq!(data.remove_nota("call-BANK").unwrap().id, "Call-Bank");
+    assert!(!data.contains_id("call-bank"));
+}
+
+// 整合性チェックが参照・ID・日付・繰り返し設定の問題をすべて報告することを確認
+#[test]
+fn test_check_integrity_reports_every_problem() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "website".to_string(),
+        title: "Website".to_string(),
+        status: NotaStatus::project,
+        created_at: date,
+        updated_at: date,
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "draft".to_string(),
+        title: "Draft copy".to_string(),
+        status: NotaStatus::next_action,
+        project: Some("website".to_string()),
+        created_at: date,
+        updated_at: date,
+        ..Default::default()
+    });
+    assert!(data.check_integrity().is_empty());
+
+    data.add(Nota {
+        id: "broken".to_string(),
+        title: "Broken".to_string(),
+        status: NotaStatus::calendar,
+        project: Some("missing".to_string()),
+        context: Some("@nowhere".to_string()),
+        created_at: date,
+        updated_at: date.pred_opt().unwrap(),
+        recurrence_pattern: Some(RecurrencePattern::weekly),
+        recurrence_config: Some("Someday".to_string()),
+        ..Default::default()
+    });
+    let issues = data.check_integrity();
+    assert_eq!(issues.len(), 5, "{:?}", issues);
+    assert!(issues[0].contains("project 'missing' does not exist"));
+    assert!(issues[1].contains("context '@nowhere' does not exist"));
+    assert!(issues[2].contains("calendar item has no start_date"));
+    assert!(issues[3].contains("updated_at 2024-02-29 is before created_at 2024-03-01"));
+    assert!(issues[4].contains("recurrence_config 'Someday' is not valid"));
+    assert!(issues.iter().all(|issue| issue.starts_with("broken: ")));
+}
+
+// 同じ ID が複数ある読み込みデータを整合性チェックが検出することを確認
+#[test]
+fn test_check_integrity_reports_duplicate_ids() {
+    let toml = r#"
+format_version = 3
+
+[[notas]]
+id = "task-1"
+title = "First"
+status = "inbox"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+
+[[notas]]
+id = "task-1"
+title = "Second"
+status = "inbox"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+"#;
+    let data: GtdData = toml::from_str(toml).unwrap();
+    assert_eq!(
+        data.check_integrity(),
+        vec!["task-1: ID is used by more than one item".to_string()]
+    );
+}
+
+/// 直接変更でインデックスとずれた状態を作る（project を書き換える）
+#[allow(deprecated)]
+fn relink_directly(data: &mut GtdData, id: &str, project: &str) {
+    data.find_task_by_id_mut(id).unwrap().project = Some(project.to_string());
+}
+
+fn data_with_two_projects() -> GtdData {
+    let mut data = GtdData::new();
+    for id in ["old-project", "new-project"] {
+        data.add(Nota {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: NotaStatus::project,
+            ..Default::default()
+        });
+    }
+    data.add(Nota {
+        id: "task".to_string(),
+        title: "Task".to_string(),
+        status: NotaStatus::next_action,
+        project: Some("old-project".to_string()),
+        ..Default::default()
+    });
+    data
+}
+
+// 直接変更した後に rebuild_indexes を呼ぶとインデックスが notas と一致することを確認
+#[test]
+fn test_rebuild_indexes_after_direct_mutation() {
+    let mut data = data_with_two_projects();
+    relink_directly(&mut data, "task", "new-project");
+
+    // インデックスは古いリンクのまま
+    assert!(data.is_referenced("old-project"));
+    assert!(!data.is_referenced("new-project"));
+
+    data.rebuild_indexes();
+    assert!(!data.is_referenced("old-project"));
+    assert_eq!(data.referrers("new-project"), vec!["task".to_string()]);
+    assert_eq!(data.list_filtered(None, Some("new-project"), None).len(), 1);
+
+    // 以降の変更でもインデックスは整合している
+    data.remove_nota("task");
+    assert!(!data.is_referenced("new-project"));
+}
+
+// デバッグビルドでは、ずれたインデックスのまま変更すると検出されることを確認
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "call rebuild_indexes()")]
+fn test_mutation_with_stale_indexes_panics_in_debug() {
+    let mut data = data_with_two_projects();
+    relink_directly(&mut data, "task", "new-project");
+    data.add(Nota {
+        id: "another".to_string(),
+        title: "Another".to_string(),
+        ..Default::default()
+    });
+}
+
+// プロジェクトの進捗が完了タスク数/タスク総数で計算されることを確認
+#[test]
+fn test_project_progress() {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "launch".to_string(),
+        title: "Launch".to_string(),
+        status: NotaStatus::project,
+        ..Default::default()
+    });
+    for (id, status) in [
+        ("copy", NotaStatus::done),
+        ("venue", NotaStatus::done),
+        ("invites", NotaStatus::next_action),
+        ("quote", NotaStatus::waiting_for),
+        ("dropped", NotaStatus::trash),
+        ("brief", NotaStatus::reference),
+    ] {
+        data.add(Nota {
+            id: id.to_string(),
+            title: id.to_string(),
+            status,
+            project: Some("launch".to_string()),
+            ..Default::default()
+        });
+    }
+
+    // ゴミ箱と資料は数えない
+    let progress = data.project_progress("launch");
+    assert_eq!((progress.done, progress.total), (2, 4));
+    assert_eq!(progress.percent(), Some(50));
+    assert_eq!(progress.to_string(), "2/4 done (50%)");
+
+    let empty = data.project_progress("unknown");
+    assert_eq!(empty.percent(), None);
+    assert_eq!(empty.to_string(), "no tasks yet");
+}
@@ -0,0 +1,164 @@
+//! Tests for the callbacks fired by saved changes
+
+use gtd_mcp::hooks::events;
+use gtd_mcp::service::{ItemUpdate, NewItem};
+use gtd_mcp::{Event, GtdData, GtdServerHandler, Nota, NotaStatus};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// A handler that records every event as a line
+fn recording_handler(dir: &TempDir) -> (GtdServerHandler, Arc<Mutex<Vec<String>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let (created, moved, deleted) = (log.clone(), log.clone(), log.clone());
+    let handler = GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .on_created(move |nota| created.lock().unwrap().push(format!("+{}", nota.id)))
+        .on_status_changed(move |nota, from| {
+            moved
+                .lock()
+                .unwrap()
+                .push(format!("{}:{:?}->{:?}", nota.id, from, nota.status))
+        })
+        .on_deleted(move |nota| deleted.lock().unwrap().push(format!("-{}", nota.id)))
+        .build()
+        .unwrap();
+    (handler, log)
+}
+
+fn take(log: &Mutex<Vec<String>>) -> Vec<String> {
+    std::mem::take(&mut *log.lock().unwrap())
+}
+
+// 作成・状態変更・ゴミ箱・完全削除でそれぞれのフックが呼ばれることを確認
+#[tokio::test]
+async fn test_hooks_follow_saved_changes() {
+    let dir = TempDir::new().unwrap();
+    let (handler, log) = recording_handler(&dir);
+    let service = handler.service();
+
+    service
+        .inbox(NewItem::new("call-bob", "Call Bob", NotaStatus::inbox))
+        .await
+        .unwrap();
+    handler.capture("Buy milk".to_string(), None).await.unwrap();
+    assert_eq!(take(&log), ["+call-bob", "+buy-milk"]);
+
+    service
+        .change_status(
+            &["call-bob".to_string()],
+            NotaStatus::next_action,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    // タイトルだけの変更はイベントにならない
+    service
+        .update(
+            "buy-milk",
+            ItemUpdate {
+                title: Some("Buy oat milk".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(take(&log), ["call-bob:inbox->next_action"]);
+
+    service
+        .change_status(&["buy-milk".to_string()], NotaStatus::trash, None, None)
+        .await
+        .unwrap();
+    handler.empty_trash().await.unwrap();
+    assert_eq!(take(&log), ["buy-milk:inbox->trash", "-buy-milk"]);
+}
+
+// トランザクション中の変更はコミット時にまとめて通知され、中止すると通知されないことを確認
+#[tokio::test]
+async fn test_transactions_fire_on_commit() {
+    let dir = TempDir::new().unwrap();
+    let (handler, log) = recording_handler(&dir);
+    let service = handler.service();
+
+    handler.begin_transaction().await.unwrap();
+    service
+        .inbox(NewItem::new("draft", "Draft", NotaStatus::inbox))
+        .await
+        .unwrap();
+    handler.abort_transaction().await.unwrap();
+    assert!(take(&log).is_empty());
+
+    handler.begin_transaction().await.unwrap();
+    service
+        .inbox(NewItem::new("plan", "Plan", NotaStatus::inbox))
+        .await
+        .unwrap();
+    service
+        .change_status(&["plan".to_string()], NotaStatus::someday, None, None)
+        .await
+        .unwrap();
+    assert!(take(&log).is_empty());
+    handler.commit_transaction().await.unwrap();
+    // 保存の間で比較するため、作成直後の状態変更は作成として一度だけ届く
+    assert_eq!(take(&log), ["+plan"]);
+}
+
+// バックアップの復元で変わった分はその場で通知され、次の保存で繰り返されないことを確認
+#[tokio::test]
+async fn test_restore_backup_fires_its_changes_once() {
+    let dir = TempDir::new().unwrap();
+    let (handler, log) = recording_handler(&dir);
+    let service = handler.service();
+    service
+        .inbox(NewItem::new("keep", "Keep", NotaStatus::inbox))
+        .await
+        .unwrap();
+    handler.backup_now().await.unwrap();
+    service
+        .inbox(NewItem::new("later", "Later", NotaStatus::inbox))
+        .await
+        .unwrap();
+    service
+        .change_status(&["keep".to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+    take(&log);
+
+    handler.restore_backup(None).await.unwrap();
+    assert_eq!(take(&log), ["keep:done->inbox", "-later"]);
+
+    service
+        .inbox(NewItem::new("next", "Next", NotaStatus::inbox))
+        .await
+        .unwrap();
+    assert_eq!(take(&log), ["+next"]);
+}
+
+// events がデータの差分から作成・状態変更・削除を順に返すことを確認
+#[test]
+fn test_events_between_two_states() {
+    let nota = |id: &str, status: NotaStatus| Nota {
+        id: id.to_string(),
+        title: id.to_string(),
+        status,
+        ..Default::default()
+    };
+    let mut before = GtdData::new();
+    before.add(nota("keep", NotaStatus::inbox));
+    before.add(nota("move", NotaStatus::inbox));
+    before.add(nota("gone", NotaStatus::done));
+    let mut after = GtdData::new();
+    after.add(nota("new", NotaStatus::inbox));
+    after.add(nota("keep", NotaStatus::inbox));
+    after.add(nota("move", NotaStatus::done));
+
+    let found = events(&before, &after);
+    assert_eq!(found.len(), 3);
+    assert!(matches!(&found[0], Event::Created(n) if n.id == "new"));
+    assert!(matches!(
+        &found[1],
+        Event::StatusChanged { nota, from: NotaStatus::inbox } if nota.id == "move"
+    ));
+    assert!(matches!(&found[2], Event::Deleted(n) if n.id == "gone"));
+    assert!(events(&after, &after).is_empty());
+}
@@ -0,0 +1,93 @@
+//! Tests for HTTP requests through curl
+//!
+//! These tests answer curl from a local port with scripted responses and
+//! check which of the header blocks it received is taken as the response.
+
+use gtd_mcp::http::{self, Request};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+
+/// Answer one connection per response, in order, and hand the head of each
+/// request to the returned channel
+fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/calendar", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for response in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    length = value.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            sender.send(head).unwrap();
+        }
+    });
+    (url, receiver)
+}
+
+fn get(url: &str, credentials: Option<(&str, &str)>) -> http::Response {
+    http::send(Request {
+        method: "GET",
+        url,
+        credentials,
+        headers: &[],
+        body: None,
+    })
+    .unwrap()
+}
+
+// 100 Continue の後の最終応答を使い、"HTTP/" で始まる本文をヘッダと誤認しないことを確認
+#[test]
+fn test_interim_response_and_body_starting_like_headers() {
+    let body = "HTTP/1.1 200 OK is how this body starts";
+    let (url, _) = serve(vec![format!(
+        "HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )]);
+
+    let response = get(&url, None);
+    assert_eq!(response.status, 200);
+    assert_eq!(response.header("etag"), Some("\"v1\""));
+    assert_eq!(response.body, body);
+}
+
+// 認証要求の 401 の後、資格情報付きで再送した応答が結果になることを確認
+#[test]
+fn test_authentication_challenge() {
+    let (url, requests) = serve(vec![
+        "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"gtd\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        "HTTP/1.1 207 Multi-Status\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok".to_string(),
+    ]);
+
+    let response = get(&url, Some(("alice", "s3cret")));
+    assert_eq!(response.status, 207);
+    assert_eq!(response.header("www-authenticate"), None);
+    assert_eq!(response.body, "ok");
+
+    assert!(!requests.recv().unwrap().contains("Authorization"));
+    // alice:s3cret の Base64
+    assert!(
+        requests
+            .recv()
+            .unwrap()
+            .contains("Authorization: Basic YWxpY2U6czNjcmV0")
+    );
+}
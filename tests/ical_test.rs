@@ -0,0 +1,158 @@
+//! Unit tests for the iCalendar export
+//!
+//! These tests verify which items become events, the expansion of recurring
+//! occurrences, and the RFC 5545 escaping and line folding.
+
+mod common;
+
+use common::date;
+use gtd_mcp::gtd::RecurrencePattern;
+use gtd_mcp::mirror::{self, MirrorFormat};
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus, ical};
+use tempfile::NamedTempFile;
+
+fn nota(id: &str, status: NotaStatus, start_date: Option<&str>) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: format!("Title of {}", id),
+        status,
+        start_date: start_date.map(date),
+        created_at: date("2025-01-01"),
+        updated_at: date("2025-01-02"),
+        ..Default::default()
+    }
+}
+
+fn data_with(notas: Vec<Nota>) -> GtdData {
+    let mut data = GtdData::new();
+    for nota in notas {
+        data.add(nota);
+    }
+    data
+}
+
+fn uids(ics: &str) -> Vec<&str> {
+    ics.lines()
+        .filter_map(|line| line.strip_prefix("UID:"))
+        .collect()
+}
+
+// カレンダー項目だけが終日イベントとして出力されることを確認
+#[test]
+fn test_render_calendar_items() {
+    let data = data_with(vec![
+        Nota {
+            context: Some("@office".to_string()),
+            notes: Some("Bring the report".to_string()),
+            ..nota("dentist", NotaStatus::calendar, Some("2025-03-10"))
+        },
+        nota("someday-trip", NotaStatus::someday, Some("2025-03-11")),
+        nota("undated", NotaStatus::calendar, None),
+    ]);
+
+    let ics = ical::render(&data, date("2025-03-01"));
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(uids(&ics), vec!["dentist@gtd-mcp"]);
+    assert!(ics.contains("DTSTART;VALUE=DATE:20250310\r\n"));
+    assert!(ics.contains("DTEND;VALUE=DATE:20250311\r\n"));
+    assert!(ics.contains("DTSTAMP:20250102T000000Z\r\n"));
+    assert!(ics.contains("SUMMARY:Title of dentist\r\n"));
+    assert!(ics.contains("DESCRIPTION:Bring the report\r\n"));
+    assert!(ics.contains("CATEGORIES:@office\r\n"));
+}
+
+// 繰り返し項目の今後の発生日が期間内で展開され、既存の項目とは重複しないことを確認
+#[test]
+fn test_render_recurring_occurrences() {
+    let weekly = Nota {
+        recurrence_pattern: Some(RecurrencePattern::weekly),
+        recurrence_config: Some("Monday".to_string()),
+        ..nota("standup", NotaStatus::next_action, Some("2025-03-03"))
+    };
+    let existing = Nota {
+        title: "Materialized".to_string(),
+        ..nota("standup-20250310", NotaStatus::calendar, Some("2025-03-10"))
+    };
+    let finished = Nota {
+        recurrence_pattern: Some(RecurrencePattern::daily),
+        ..nota("old-habit", NotaStatus::done, Some("2025-03-01"))
+    };
+    let data = data_with(vec![weekly, existing, finished]);
+
+    let ics = ical::render(&data, date("2025-03-05"));
+    let uids = uids(&ics);
+    // 元の項目・既存の発生分・今日以降で期間内の発生分
+    assert!(uids.contains(&"standup@gtd-mcp"));
+    assert!(uids.contains(&"standup-20250310@gtd-mcp"));
+    assert!(uids.contains(&"standup-20250317@gtd-mcp"));
+    assert!(uids.contains(&"standup-20250602@gtd-mcp"));
+    assert!(!uids.contains(&"standup-20250609@gtd-mcp"));
+    assert_eq!(
+        uids.iter()
+            .filter(|uid| uid.starts_with("standup-20250310"))
+            .count(),
+        1
+    );
+    // 完了済みの繰り返し項目は出力しない
+    assert!(!uids.iter().any(|uid| uid.starts_with("old-habit")));
+}
+
+// テキストのエスケープと75オクテットでの折り返しを確認
+#[test]
+fn test_render_escapes_and_folds() {
+    let long_title = "会議".repeat(30);
+    let data = data_with(vec![
+        Nota {
+            notes: Some("a, b; c\\d\nnext".to_string()),
+            ..nota("escape", NotaStatus::calendar, Some("2025-03-10"))
+        },
+        Nota {
+            title: long_title.clone(),
+            ..nota("long", NotaStatus::calendar, Some("2025-03-10"))
+        },
+    ]);
+
+    let ics = ical::render(&data, date("2025-03-01"));
+    assert!(ics.contains("DESCRIPTION:a\\, b\\; c\\\\d\\nnext\r\n"));
+    for line in ics.split("\r\n") {
+        assert!(line.len() <= 75, "line too long: {}", line);
+    }
+    let unfolded = ics.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!("SUMMARY:{}\r\n", long_title)));
+}
+
+// ミラー形式とツールからも iCalendar を出力できることを確認
+#[tokio::test]
+async fn test_export_ical_tool_and_format() {
+    assert_eq!(MirrorFormat::from_path("gtd.ics"), Some(MirrorFormat::Ical));
+    assert_eq!("ical".parse::<MirrorFormat>(), Ok(MirrorFormat::Ical));
+
+    let data = data_with(vec![nota(
+        "review",
+        NotaStatus::calendar,
+        Some("2025-03-10"),
+    )]);
+    let rendered = mirror::render(&data, MirrorFormat::Ical, date("2025-03-01")).unwrap();
+    assert!(rendered.contains("UID:review@gtd-mcp"));
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    handler
+        .inbox(
+            "dentist".to_string(),
+            "Dentist".to_string(),
+            "calendar".to_string(),
+            None,
+            None,
+            None,
+            Some("2025-03-10".to_string()),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let ics = handler.export_ical().await.unwrap();
+    assert!(ics.contains("UID:dentist@gtd-mcp\r\n"));
+    assert!(ics.contains("SUMMARY:Dentist\r\n"));
+}
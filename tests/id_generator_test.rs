@@ -0,0 +1,179 @@
+//! Tests for the ID schemes of captured items and recurring occurrences
+
+use gtd_mcp::id_generator::ulid;
+use gtd_mcp::service::NewItem;
+use gtd_mcp::{
+    GtdServerHandler, IdGenerator, IdPolicy, IdScheme, NotaStatus, gtd::RecurrencePattern,
+};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn handler(dir: &TempDir, scheme: IdScheme) -> GtdServerHandler {
+    GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .id_generator(Arc::new(scheme))
+        .build()
+        .unwrap()
+}
+
+/// Capture `title` and return the ID it got
+async fn capture(handler: &GtdServerHandler, title: &str) -> String {
+    handler.capture(title.to_string(), None).await.unwrap();
+    let data = handler.data.read().await;
+    data.iter()
+        .find(|nota| nota.title == title)
+        .unwrap()
+        .id
+        .clone()
+}
+
+/// Complete the daily item `id` and return the ID of its next occurrence
+async fn complete(handler: &GtdServerHandler, id: &str) -> String {
+    let changes = handler
+        .service()
+        .change_status(&[id.to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+    changes.changed[0]
+        .next_occurrence
+        .as_ref()
+        .unwrap()
+        .id
+        .clone()
+}
+
+// 既定のスラッグ方式はタイトル由来の ID と日付付きの次回 ID を作ることを確認
+#[tokio::test]
+async fn test_slug_scheme_is_the_default() {
+    let dir = TempDir::new().unwrap();
+    let handler = GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .build()
+        .unwrap();
+    assert_eq!(capture(&handler, "Call John").await, "call-john");
+
+    let service = handler.service();
+    let created = service
+        .inbox(NewItem {
+            start_date: Some(chrono::NaiveDate::from_ymd_opt(2030, 1, 10).unwrap()),
+            recurrence: Some(RecurrencePattern::daily),
+            ..NewItem::new("stretch", "Stretch", NotaStatus::next_action)
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.nota.id, "stretch");
+    assert_eq!(complete(&handler, "stretch").await, "stretch-20300111");
+}
+
+// ULID 方式は 26 文字の小文字 ULID を作り、ID ポリシーを満たすことを確認
+#[tokio::test]
+async fn test_ulid_scheme() {
+    let first = ulid().unwrap();
+    assert_eq!(first.len(), 26);
+    assert!(IdPolicy::kebab_case().check(&first).is_ok());
+    // 同じミリ秒内でも単調に増える
+    let mut previous = first;
+    for _ in 0..100 {
+        let next = ulid().unwrap();
+        assert!(next > previous);
+        previous = next;
+    }
+    assert!(ulid::Ulid::from_string(&previous.to_uppercase()).is_ok());
+
+    let dir = TempDir::new().unwrap();
+    let handler = handler(&dir, IdScheme::Ulid);
+    let a = capture(&handler, "Buy milk").await;
+    let b = capture(&handler, "Buy bread").await;
+    assert_eq!(a.len(), 26);
+    assert_ne!(a, b);
+
+    handler
+        .service()
+        .inbox(NewItem {
+            recurrence: Some(RecurrencePattern::daily),
+            ..NewItem::new("water", "Water plants", NotaStatus::next_action)
+        })
+        .await
+        .unwrap();
+    let next = complete(&handler, "water").await;
+    assert_eq!(next.len(), 26);
+    assert!(next.chars().all(|c| c.is_ascii_alphanumeric()));
+}
+
+// 連番方式は数字の ID の最大値の次を使うことを確認
+#[tokio::test]
+async fn test_sequential_scheme() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler(&dir, IdScheme::Sequential);
+    assert_eq!(capture(&handler, "Buy milk").await, "1");
+    assert_eq!(capture(&handler, "Buy bread").await, "2");
+
+    handler
+        .service()
+        .inbox(NewItem {
+            recurrence: Some(RecurrencePattern::daily),
+            ..NewItem::new("7", "Water plants", NotaStatus::next_action)
+        })
+        .await
+        .unwrap();
+    assert_eq!(complete(&handler, "7").await, "8");
+    assert_eq!(capture(&handler, "Call John").await, "9");
+}
+
+// 連番が u64 の最大値に達するとパニックせずエラーになることを確認
+#[tokio::test]
+async fn test_sequential_scheme_overflow() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler(&dir, IdScheme::Sequential);
+    handler
+        .service()
+        .inbox(NewItem::new(
+            u64::MAX.to_string(),
+            "Last number",
+            NotaStatus::inbox,
+        ))
+        .await
+        .unwrap();
+
+    let error = format!(
+        "{:?}",
+        handler
+            .capture("Buy milk".to_string(), None)
+            .await
+            .unwrap_err()
+    );
+    assert!(error.contains("largest number"), "{}", error);
+    assert_eq!(handler.data.read().await.iter().count(), 1);
+}
+
+// 方式名の解析と表示が対応し、独自の生成器も使えることを確認
+#[tokio::test]
+async fn test_scheme_names_and_custom_generator() {
+    for name in ["slug", "ulid", "sequential"] {
+        assert_eq!(name.parse::<IdScheme>().unwrap().to_string(), name);
+    }
+    assert!("uuid".parse::<IdScheme>().is_err());
+
+    struct Prefixed;
+    impl IdGenerator for Prefixed {
+        fn item_id(&self, data: &gtd_mcp::GtdData, _title: &str) -> Result<String, String> {
+            Ok(format!("task-{}", data.iter().count() + 1))
+        }
+        fn occurrence_id(
+            &self,
+            _data: &gtd_mcp::GtdData,
+            series: &gtd_mcp::Nota,
+            date: chrono::NaiveDate,
+        ) -> Result<String, String> {
+            Ok(format!("{}@{}", series.id, date))
+        }
+    }
+    let dir = TempDir::new().unwrap();
+    let handler = GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .id_generator(Arc::new(Prefixed))
+        .build()
+        .unwrap();
+    assert_eq!(capture(&handler, "Buy milk").await, "task-1");
+    assert_eq!(capture(&handler, "Buy bread").await, "task-2");
+}
@@ -0,0 +1,80 @@
+//! Unit tests for ID validation policies
+//!
+//! These tests verify the built-in kebab-case policy, custom patterns,
+//! length limits, and forbidden characters.
+
+use gtd_mcp::IdPolicy;
+
+// 既定の kebab-case ポリシーで有効・無効な ID を判定できることを確認
+#[test]
+fn test_kebab_case_policy() {
+    let policy = IdPolicy::kebab_case();
+    for id in ["call-john", "q1-budget", "@home", "task1"] {
+        assert!(policy.check(id).is_ok(), "{} should be valid", id);
+    }
+    for id in [
+        "Call-John",
+        "call_john",
+        "call--john",
+        "-call",
+        "call john",
+        "",
+    ] {
+        assert!(policy.check(id).is_err(), "{} should be invalid", id);
+    }
+
+    // エラーメッセージにルールと修正候補が含まれる
+    let error = policy.check("Call John").unwrap_err();
+    assert!(error.contains("lowercase words joined by hyphens"));
+    assert!(error.contains("try 'call-john'"));
+}
+
+// 最大長・禁止文字のルールを確認
+#[test]
+fn test_max_length_and_forbidden_chars() {
+    let policy = IdPolicy::default()
+        .with_max_length(8)
+        .with_forbidden_chars(" /#");
+    assert!(policy.check("Call_Bob").is_ok());
+
+    let error = policy.check("too-long-id").unwrap_err();
+    assert!(error.contains("at most 8 characters"));
+    let error = policy.check("a/b").unwrap_err();
+    assert!(error.contains("contains '/'"));
+
+    // kebab-case の最大長は上書きできる
+    let policy = IdPolicy::kebab_case().with_max_length(5);
+    assert!(policy.check("abcdef").is_err());
+}
+
+// カスタムパターンは ID 全体に一致する必要があることを確認
+#[test]
+fn test_custom_pattern() {
+    let policy = IdPolicy::default().with_pattern("[a-z]+").unwrap();
+    assert!(policy.check("abc").is_ok());
+    assert!(policy.check("abc1").is_err());
+
+    let policy = IdPolicy::default().with_pattern("^T-[0-9]+$").unwrap();
+    assert!(policy.check("T-42").is_ok());
+    assert!(policy.check("xT-42").is_err());
+
+    assert!(IdPolicy::default().with_pattern("[unclosed").is_err());
+}
+
+// CLI オプションからのポリシー構築を確認
+#[test]
+fn test_policy_from_options() {
+    assert!(
+        IdPolicy::from_options(false, None, None, None)
+            .unwrap()
+            .is_none()
+    );
+
+    let policy = IdPolicy::from_options(true, None, Some(10), Some("#"))
+        .unwrap()
+        .unwrap();
+    assert!(policy.check("call-john").is_ok());
+    assert!(policy.check("call-john-now").is_err());
+
+    assert!(IdPolicy::from_options(false, Some("("), None, None).is_err());
+}
@@ -0,0 +1,63 @@
+//! Integration tests for the instructions sent to clients
+//!
+//! These tests connect an MCP client and read the `instructions` of the
+//! `initialize` response, with the built-in text and with a team's own text
+//! from the configuration file.
+
+use gtd_mcp::GtdServerHandler;
+use gtd_mcp::config::Config;
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::instructions::DEFAULT_INSTRUCTIONS;
+use mcp_attr::client::McpClient;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+async fn instructions(handler: GtdServerHandler) -> Option<String> {
+    let client = McpClient::with_server(SharedServer::new(Arc::new(handler)))
+        .await
+        .unwrap();
+    client.instructions().map(str::to_string)
+}
+
+// 設定がなければ GTD のワークフローの説明を送ることを確認
+#[tokio::test]
+async fn test_default_instructions() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+
+    let text = instructions(handler).await.unwrap();
+    assert_eq!(text, DEFAULT_INSTRUCTIONS);
+    assert!(text.contains("Workflow: Capture(inbox)"));
+}
+
+// 設定ファイルの instructions で置き換え、{default} には既定の説明が入ることを確認
+#[tokio::test]
+async fn test_instructions_from_config() {
+    let dir = TempDir::new().unwrap();
+    let config = dir.path().join("config.toml");
+    std::fs::write(
+        &config,
+        "[serve]\ninstructions = \"\"\"\n{default}\n\nTeam rules:\n- IDs start with the ticket key, e.g. \"ops-123-restart-db\"\n\"\"\"\n",
+    )
+    .unwrap();
+    let custom = Config::load(&config).unwrap().serve.instructions.unwrap();
+    let path = dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false)
+        .unwrap()
+        .with_instructions(custom);
+
+    let text = instructions(handler).await.unwrap();
+    assert!(text.starts_with(DEFAULT_INSTRUCTIONS), "{}", text);
+    assert!(text.ends_with("e.g. \"ops-123-restart-db\"\n"), "{}", text);
+    assert!(!text.contains("{default}"));
+
+    // {default} がなければ既定の説明は送らない
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false)
+        .unwrap()
+        .with_instructions("Only capture, never delete.".to_string());
+    assert_eq!(
+        instructions(handler).await.as_deref(),
+        Some("Only capture, never delete.")
+    );
+}
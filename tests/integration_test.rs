@@ -29,10 +29,10 @@ fn test_custom_file_path() {
     let handler = GtdServerHandler::new(custom_path, false).unwrap();
 
     // ストレージのファイルパスが正しく設定されていることを確認
-    assert_eq!(handler.storage.file_path().to_str().unwrap(), custom_path);
+    assert_eq!(handler.storage().file_path().to_str().unwrap(), custom_path);
 
     // データの保存と読み込みが正しく動作することを確認
-    let mut data = handler.data.lock().unwrap();
+    let mut data = handler.data.blocking_write();
     let task = Task {
         id: "test-task".to_string(),
         title: "Test Task".to_string(),
@@ -43,6 +43,20 @@ fn test_custom_file_path() {
         start_date: None,
         created_at: local_date_today(),
         updated_at: local_date_today(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add(nota_from_task(task));
     drop(data);
@@ -56,7 +70,7 @@ fn test_custom_file_path() {
 
     // 新しいハンドラーで読み込み
     let handler2 = GtdServerHandler::new(custom_path, false).unwrap();
-    let loaded_data = handler2.data.lock().unwrap();
+    let loaded_data = handler2.data.blocking_read();
     assert_eq!(loaded_data.task_count(), 1);
     let loaded_task = loaded_data.find_task_by_id("test-task").unwrap();
     assert_eq!(loaded_task.title, "Test Task");
@@ -106,40 +120,45 @@ async fn test_change_task_status_unified_api() {
 
     // Test moving to next_action
     let result = handler
-        .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+        .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert_eq!(task.status, NotaStatus::next_action);
     }
 
     // Test moving to done
     let result = handler
-        .change_status(vec![task_id.clone()], "done".to_string(), None)
+        .change_status(vec![task_id.clone()], "done".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert_eq!(task.status, NotaStatus::done);
     }
 
     // Test moving to trash
     let result = handler
-        .change_status(vec![task_id.clone()], "trash".to_string(), None)
+        .change_status(vec![task_id.clone()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert_eq!(task.status, NotaStatus::trash);
     }
 
     // Test invalid status
     let result = handler
-        .change_status(vec![task_id.clone()], "invalid_status".to_string(), None)
+        .change_status(
+            vec![task_id.clone()],
+            "invalid_status".to_string(),
+            None,
+            None,
+        )
         .await;
     assert!(result.is_err());
 }
@@ -171,10 +190,11 @@ async fn test_change_task_status_calendar_with_date() {
             vec![task_id.clone()],
             "calendar".to_string(),
             Some("2024-12-25".to_string()),
+            None,
         )
         .await;
     assert!(result.is_ok());
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert_eq!(task.status, NotaStatus::calendar);
     assert_eq!(
@@ -211,13 +231,13 @@ async fn test_change_nota_status_batch_operation() {
     // Test batch move to next_action
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+            .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
             .await;
         assert!(result.is_ok());
     }
 
     // Verify all tasks moved
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.next_action().len(), 3);
     for task_id in &task_ids {
         let task = data.find_task_by_id(task_id).unwrap();
@@ -252,7 +272,7 @@ async fn test_batch_change_status_multiple_ids() {
 
     // Batch change status to done
     let result = handler
-        .change_status(task_ids.clone(), "done".to_string(), None)
+        .change_status(task_ids.clone(), "done".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     let response = result.unwrap();
@@ -260,7 +280,7 @@ async fn test_batch_change_status_multiple_ids() {
     assert!(response.contains("→ done"));
 
     // Verify all tasks moved to done
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.done().len(), 3);
     for task_id in &task_ids {
         let task = data.find_task_by_id(task_id).unwrap();
@@ -298,6 +318,7 @@ async fn test_batch_change_status_partial_failure() {
             ],
             "done".to_string(),
             None,
+            None,
         )
         .await;
 
@@ -310,7 +331,7 @@ async fn test_batch_change_status_partial_failure() {
     assert!(response.contains("invalid-id-2: not found"));
 
     // Verify the valid task was moved
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id("valid-task").unwrap();
     assert_eq!(task.status, NotaStatus::done);
 }
@@ -329,6 +350,7 @@ async fn test_batch_change_status_all_failures() {
             ],
             "done".to_string(),
             None,
+            None,
         )
         .await;
 
@@ -342,7 +364,7 @@ async fn test_batch_change_status_empty_array() {
 
     // Try to change status with empty array
     let result = handler
-        .change_status(vec![], "done".to_string(), None)
+        .change_status(vec![], "done".to_string(), None, None)
         .await;
 
     // Should fail
@@ -376,7 +398,7 @@ async fn test_batch_change_status_to_trash() {
 
     // Batch move to trash
     let result = handler
-        .change_status(task_ids.clone(), "trash".to_string(), None)
+        .change_status(task_ids.clone(), "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     let response = result.unwrap();
@@ -384,7 +406,7 @@ async fn test_batch_change_status_to_trash() {
     assert!(response.contains("moved to trash"));
 
     // Verify all tasks moved to trash
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.trash().len(), 3);
     for task_id in &task_ids {
         let task = data.find_task_by_id(task_id).unwrap();
@@ -431,12 +453,12 @@ async fn test_batch_change_status_with_id_normalization() {
 
     // Change status using both IDs
     let result = handler
-        .change_status(vec![task_id1, task_id2], "done".to_string(), None)
+        .change_status(vec![task_id1, task_id2], "done".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
     // Verify both tasks were updated
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.done().len(), 2);
 }
 
@@ -500,6 +522,7 @@ async fn test_batch_change_status_different_initial_statuses() {
             ],
             "done".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_ok());
@@ -509,7 +532,7 @@ async fn test_batch_change_status_different_initial_statuses() {
     assert!(response.contains("waiting_for → done"));
 
     // Verify all tasks are now done
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.done().len(), 3);
     assert_eq!(data.inbox().len(), 0);
     assert_eq!(data.next_action().len(), 0);
@@ -548,16 +571,94 @@ async fn test_update_task_with_arbitrary_id() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify the update worked
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id("meeting-prep").unwrap();
     assert_eq!(task.title, "Updated meeting preparation");
 }
 
+// 存在しない ID で update すると近い ID が提案されることを確認
+#[tokio::test]
+async fn test_update_unknown_id_suggests_similar() {
+    let (handler, _temp_file) = get_test_handler();
+    handler
+        .inbox(
+            "call-john".to_string(),
+            "Call John".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let message = handler
+        .update(
+            "cal-jon".to_string(),
+            Some("Call John back".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(message.contains("Did you mean 'call-john'?"), "{}", message);
+
+    // 似た ID がなければ提案は付かない
+    let response = handler
+        .change_status(
+            vec!["call-john".to_string(), "zzz-unrelated".to_string()],
+            "next_action".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(
+        response.contains("zzz-unrelated: not found"),
+        "{}",
+        response
+    );
+    assert!(!response.contains("Did you mean"), "{}", response);
+
+    let response = handler
+        .change_status(
+            vec!["call-john".to_string(), "calljohn".to_string()],
+            "done".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(
+        response.contains("calljohn: not found. Did you mean 'call-john'?"),
+        "{}",
+        response
+    );
+}
+
 // ==================== CHANGE STATUS TESTS ====================
 
 #[tokio::test]
@@ -586,12 +687,13 @@ async fn test_status_movement_with_arbitrary_id() {
             vec!["call-sarah".to_string()],
             "next_action".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify the task moved
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id("call-sarah").unwrap();
     assert_eq!(task.status, NotaStatus::next_action);
 }
@@ -631,12 +733,17 @@ async fn test_update_task_title() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify update
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert_eq!(task.title, "Updated Title");
 }
@@ -666,7 +773,7 @@ async fn test_update_task_status_using_next_action_task() {
 
     // Verify initial status is inbox
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert!(matches!(task.status, NotaStatus::inbox));
         assert_eq!(data.inbox().len(), 1);
@@ -675,13 +782,13 @@ async fn test_update_task_status_using_next_action_task() {
 
     // Update status to next_action using new method
     let result = handler
-        .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+        .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
     // Verify status changed and task moved
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert!(matches!(task.status, NotaStatus::next_action));
         assert_eq!(data.inbox().len(), 0);
@@ -713,7 +820,7 @@ async fn test_update_task_project_and_context() {
     let project_id = common::extract_id_from_response(&project_result.unwrap());
 
     {
-        let mut data = handler.data.lock().unwrap();
+        let mut data = handler.data.write().await;
         data.add(nota_from_context(migration::Context {
             name: "Office".to_string(),
             notes: None,
@@ -724,6 +831,20 @@ async fn test_update_task_project_and_context() {
             start_date: None,
             created_at: None,
             updated_at: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         }));
         drop(data);
         let _ = handler.save_data();
@@ -756,12 +877,17 @@ async fn test_update_task_project_and_context() {
             Some("Office".to_string()),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify update
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert_eq!(task.project, Some(project_id));
     assert_eq!(task.context, Some("Office".to_string()));
@@ -790,7 +916,7 @@ async fn test_update_task_remove_optional_fields() {
 
     // Verify initial state
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert_eq!(task.notes, Some("Some notes".to_string()));
         assert!(task.start_date.is_some());
@@ -806,12 +932,17 @@ async fn test_update_task_remove_optional_fields() {
             Some("".to_string()), // Clear context
             Some("".to_string()), // Clear notes
             Some("".to_string()), // Clear start_date
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify fields removed
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert_eq!(task.notes, None);
     assert_eq!(task.start_date, None);
@@ -848,6 +979,11 @@ async fn test_update_task_invalid_date() {
             None,
             None,
             Some("invalid-date".to_string()), // start_date is 7th param
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -884,6 +1020,11 @@ async fn test_update_task_invalid_project_reference() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -920,6 +1061,11 @@ async fn test_update_task_invalid_context_reference() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -939,6 +1085,11 @@ async fn test_update_task_not_found() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -967,7 +1118,7 @@ async fn test_update_task_updates_timestamp() {
 
     // Get initial timestamps
     let (created_at, _updated_at) = {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         (task.created_at, task.updated_at)
     };
@@ -982,12 +1133,17 @@ async fn test_update_task_updates_timestamp() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify updated_at changed but created_at didn't
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert_eq!(task.created_at, created_at);
     // Note: In test environment, if executed fast enough, updated_at might be the same
@@ -1025,12 +1181,17 @@ async fn test_update_project_name() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify update
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let project = data.find_project_by_id(&project_id).unwrap();
     assert_eq!(project.title, "Updated Name");
 }
@@ -1066,13 +1227,18 @@ async fn test_update_project_description() {
             None,
             Some("New description".to_string()), // notes is 6th param
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify description added
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let project = data.find_project_by_id(&project_id).unwrap();
         assert_eq!(project.notes, Some("New description".to_string()));
     }
@@ -1087,12 +1253,17 @@ async fn test_update_project_description() {
             None,
             Some("".to_string()), // notes is 6th param
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify description removed
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let project = data.find_project_by_id(&project_id).unwrap();
     assert_eq!(project.notes, None);
 }
@@ -1127,6 +1298,11 @@ async fn test_update_project_invalid_status() {
             Some("invalid_status".to_string()),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -1146,6 +1322,11 @@ async fn test_update_project_not_found() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -1179,13 +1360,14 @@ async fn test_delete_project_success() {
             vec!["test-project-1".to_string()],
             "trash".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_ok());
     assert!(result.unwrap().contains("deleted"));
 
     // Verify the project was deleted
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert!(data.find_project_by_id("test-project-1").is_none());
 }
 
@@ -1199,6 +1381,7 @@ async fn test_delete_project_not_found() {
             vec!["non-existent-id".to_string()],
             "trash".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -1246,12 +1429,13 @@ async fn test_delete_project_with_task_reference() {
             vec!["test-project-1".to_string()],
             "trash".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_err());
 
     // Verify the project was NOT deleted
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert!(data.find_project_by_id("test-project-1").is_some());
 }
 
@@ -1301,6 +1485,11 @@ async fn test_delete_project_after_unlinking_tasks() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
@@ -1311,12 +1500,13 @@ async fn test_delete_project_after_unlinking_tasks() {
             vec!["test-project-1".to_string()],
             "trash".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify the project was deleted
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert!(data.find_project_by_id("test-project-1").is_none());
 }
 
@@ -1345,7 +1535,7 @@ async fn test_update_multiple_fields_simultaneously() {
 
     // Add a context
     {
-        let mut data = handler.data.lock().unwrap();
+        let mut data = handler.data.write().await;
         data.add(nota_from_context(migration::Context {
             name: "Office".to_string(),
             notes: None,
@@ -1356,6 +1546,20 @@ async fn test_update_multiple_fields_simultaneously() {
             start_date: None,
             created_at: None,
             updated_at: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         }));
         drop(data);
         let _ = handler.save_data();
@@ -1388,18 +1592,23 @@ async fn test_update_multiple_fields_simultaneously() {
             Some("Office".to_string()),        // context
             Some("Updated notes".to_string()), // notes
             Some("2025-01-15".to_string()),    // start_date
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Change status separately using new method
     let result = handler
-        .change_status(vec![task_id.clone()], "done".to_string(), None)
+        .change_status(vec![task_id.clone()], "done".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
     // Verify all updates
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert_eq!(task.title, "Updated Task");
     assert!(matches!(task.status, NotaStatus::done));
@@ -1439,13 +1648,13 @@ async fn test_inbox_task() {
 
     // Move to next_action first
     let result = handler
-        .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+        .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
     // Verify it's in next_action
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert!(matches!(task.status, NotaStatus::next_action));
         assert_eq!(data.next_action().len(), 1);
@@ -1454,13 +1663,13 @@ async fn test_inbox_task() {
 
     // Move back to inbox
     let result = handler
-        .change_status(vec![task_id.clone()], "inbox".to_string(), None)
+        .change_status(vec![task_id.clone()], "inbox".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
     // Verify it's back in inbox
     {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         assert!(matches!(task.status, NotaStatus::inbox));
         assert_eq!(data.inbox().len(), 1);
@@ -1491,11 +1700,11 @@ async fn test_next_action_task() {
     let task_id = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+        .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::next_action));
     assert_eq!(data.next_action().len(), 1);
@@ -1523,11 +1732,11 @@ async fn test_waiting_for_task() {
     let task_id = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id.clone()], "waiting_for".to_string(), None)
+        .change_status(vec![task_id.clone()], "waiting_for".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::waiting_for));
     assert_eq!(data.waiting_for().len(), 1);
@@ -1555,11 +1764,11 @@ async fn test_someday_task() {
     let task_id = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id.clone()], "someday".to_string(), None)
+        .change_status(vec![task_id.clone()], "someday".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::someday));
     assert_eq!(data.someday().len(), 1);
@@ -1587,11 +1796,11 @@ async fn test_later_task() {
     let task_id = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id.clone()], "later".to_string(), None)
+        .change_status(vec![task_id.clone()], "later".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::later));
     assert_eq!(data.later().len(), 1);
@@ -1619,11 +1828,11 @@ async fn test_done_task() {
     let task_id = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id.clone()], "done".to_string(), None)
+        .change_status(vec![task_id.clone()], "done".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::done));
     assert_eq!(data.done().len(), 1);
@@ -1653,11 +1862,11 @@ async fn test_trash_task_from_inbox() {
     let task_id = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id.clone()], "trash".to_string(), None)
+        .change_status(vec![task_id.clone()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok(), "Failed to trash task: {:?}", result.err());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::trash));
     assert_eq!(data.trash().len(), 1);
@@ -1688,7 +1897,7 @@ async fn test_trash_task_workflow_comparison() {
     let task_id_1 = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id_1.clone()], "trash".to_string(), None)
+        .change_status(vec![task_id_1.clone()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok(), "Direct trash failed: {:?}", result.err());
 
@@ -1710,17 +1919,17 @@ async fn test_trash_task_workflow_comparison() {
     let task_id_2 = common::extract_id_from_response(&result.unwrap());
 
     let result = handler
-        .change_status(vec![task_id_2.clone()], "done".to_string(), None)
+        .change_status(vec![task_id_2.clone()], "done".to_string(), None, None)
         .await;
     assert!(result.is_ok(), "Moving to done failed: {:?}", result.err());
 
     let result = handler
-        .change_status(vec![task_id_2.clone()], "trash".to_string(), None)
+        .change_status(vec![task_id_2.clone()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok(), "Trash from done failed: {:?}", result.err());
 
     // Verify both tasks ended up in trash
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.trash().len(), 2);
     assert_eq!(data.inbox().len(), 0);
     assert_eq!(data.done().len(), 0);
@@ -1740,7 +1949,7 @@ async fn test_trash_task_error_messages() {
 
     for task_id in test_cases {
         let result = handler
-            .change_status(vec![task_id.to_string()], "trash".to_string(), None)
+            .change_status(vec![task_id.to_string()], "trash".to_string(), None, None)
             .await;
         assert!(result.is_err(), "Expected error for task_id: {}", task_id);
     }
@@ -1774,7 +1983,7 @@ async fn test_trash_notas_multiple() {
     // 複数のタスクを一度にtrashに移動
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "trash".to_string(), None)
+            .change_status(vec![task_id.clone()], "trash".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -1785,7 +1994,7 @@ async fn test_trash_notas_multiple() {
     }
 
     // すべてのタスクがtrashに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.trash().len(), 5);
     assert_eq!(data.inbox().len(), 0);
 
@@ -1829,7 +2038,7 @@ async fn test_trash_notas_partial_success() {
     let mut fail_count = 0;
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "trash".to_string(), None)
+            .change_status(vec![task_id.clone()], "trash".to_string(), None, None)
             .await;
         if result.is_ok() {
             success_count += 1;
@@ -1843,7 +2052,7 @@ async fn test_trash_notas_partial_success() {
     assert_eq!(fail_count, 2);
 
     // 有効なタスクだけがtrashに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.trash().len(), 2);
     assert_eq!(data.inbox().len(), 0);
 }
@@ -1862,7 +2071,7 @@ async fn test_trash_tasks_all_invalid() {
     // すべて失敗する場合はエラーを返す
     if !task_ids.is_empty() {
         let result = handler
-            .change_status(vec![task_ids[0].clone()], "trash".to_string(), None)
+            .change_status(vec![task_ids[0].clone()], "trash".to_string(), None, None)
             .await;
         assert!(result.is_err(), "Expected error when all tasks are invalid");
     }
@@ -1910,6 +2119,7 @@ async fn test_trash_notas_from_different_statuses() {
             vec![next_action_task_id.clone()],
             "next_action".to_string(),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -1931,7 +2141,7 @@ async fn test_trash_notas_from_different_statuses() {
     assert!(result.is_ok());
     let done_task_id = common::extract_id_from_response(&result.unwrap());
     handler
-        .change_status(vec![done_task_id.clone()], "done".to_string(), None)
+        .change_status(vec![done_task_id.clone()], "done".to_string(), None, None)
         .await
         .unwrap();
 
@@ -1943,14 +2153,14 @@ async fn test_trash_notas_from_different_statuses() {
     ];
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "trash".to_string(), None)
+            .change_status(vec![task_id.clone()], "trash".to_string(), None, None)
             .await;
         assert!(result.is_ok(), "Failed to trash task: {:?}", result.err());
     }
     // All tasks successfully moved to trash
 
     // すべてがtrashに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.trash().len(), 3);
     assert_eq!(data.inbox().len(), 0);
     assert_eq!(data.next_action().len(), 0);
@@ -1991,11 +2201,12 @@ async fn test_calendar_task_with_start_date() {
             vec![task_id.clone()],
             "calendar".to_string(),
             Some("2024-12-25".to_string()),
+            None,
         )
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::calendar));
     assert_eq!(data.calendar().len(), 1);
@@ -2030,7 +2241,7 @@ async fn test_calendar_task_without_start_date_error() {
 
     // start_dateを指定せずにcalendarに移動しようとするとエラー
     let result = handler
-        .change_status(vec![task_id.clone()], "calendar".to_string(), None)
+        .change_status(vec![task_id.clone()], "calendar".to_string(), None, None)
         .await;
     assert!(result.is_err());
 }
@@ -2058,11 +2269,11 @@ async fn test_calendar_task_with_existing_start_date() {
 
     // start_dateパラメータなしでcalendarに移動（既存のstart_dateを使用）
     let result = handler
-        .change_status(vec![task_id.clone()], "calendar".to_string(), None)
+        .change_status(vec![task_id.clone()], "calendar".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::calendar));
     assert_eq!(data.calendar().len(), 1);
@@ -2099,11 +2310,12 @@ async fn test_calendar_task_override_start_date() {
             vec![task_id.clone()],
             "calendar".to_string(),
             Some("2024-12-31".to_string()),
+            None,
         )
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert!(matches!(task.status, NotaStatus::calendar));
     assert_eq!(
@@ -2138,6 +2350,7 @@ async fn test_calendar_task_invalid_date_format() {
             vec![task_id.clone()],
             "calendar".to_string(),
             Some("2024/12/25".to_string()),
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -2164,19 +2377,19 @@ async fn test_status_movement_updates_timestamp() {
     let task_id = common::extract_id_from_response(&result.unwrap());
 
     let created_at = {
-        let data = handler.data.lock().unwrap();
+        let data = handler.data.read().await;
         let task = data.find_task_by_id(&task_id).unwrap();
         task.created_at
     };
 
     // Move to next_action
     let result = handler
-        .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+        .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
         .await;
     assert!(result.is_ok());
 
     // Verify created_at unchanged
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let task = data.find_task_by_id(&task_id).unwrap();
     assert_eq!(task.created_at, created_at);
 }
@@ -2190,12 +2403,18 @@ async fn test_status_movement_nonexistent_task() {
             vec!["nonexistent-id".to_string()],
             "next_action".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_err());
 
     let result = handler
-        .change_status(vec!["nonexistent-id".to_string()], "done".to_string(), None)
+        .change_status(
+            vec!["nonexistent-id".to_string()],
+            "done".to_string(),
+            None,
+            None,
+        )
         .await;
     assert!(result.is_err());
 
@@ -2204,6 +2423,7 @@ async fn test_status_movement_nonexistent_task() {
             vec!["nonexistent-id".to_string()],
             "trash".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -2233,7 +2453,7 @@ async fn test_add_context() {
     assert!(result.is_ok());
     assert!(result.unwrap().contains("Office"));
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 1);
     let context = data.find_context_by_name("Office").unwrap();
     assert_eq!(context.id, "Office");
@@ -2282,7 +2502,7 @@ async fn test_add_context_duplicate() {
 async fn test_list_contexts_empty() {
     let (handler, _temp_file) = get_test_handler();
 
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     assert!(result.unwrap().contains("No items found")); // list() returns generic message
 }
@@ -2320,7 +2540,7 @@ async fn test_list_contexts() {
         .await
         .unwrap();
 
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     let output = result.unwrap();
     assert!(output.contains("Office"));
@@ -2358,11 +2578,16 @@ async fn test_update_context() {
             None,
             Some("New description".to_string()),
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let context = data.find_context_by_name("Office").unwrap();
     assert_eq!(context.notes, Some("New description".to_string()));
 }
@@ -2395,11 +2620,16 @@ async fn test_update_context_remove_description() {
             None,
             Some("".to_string()),
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let context = data.find_context_by_name("Office").unwrap();
     assert_eq!(context.notes, None);
 }
@@ -2417,6 +2647,11 @@ async fn test_update_context_not_found() {
             None,
             Some("Description".to_string()),
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -2444,13 +2679,13 @@ async fn test_delete_context() {
         .unwrap();
 
     let result = handler
-        .change_status(vec!["Office".to_string()], "trash".to_string(), None)
+        .change_status(vec!["Office".to_string()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     let result = handler.empty_trash().await;
     assert!(result.is_ok());
 
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 0);
 }
 
@@ -2459,7 +2694,12 @@ async fn test_delete_context_not_found() {
     let (handler, _temp_file) = get_test_handler();
 
     let result = handler
-        .change_status(vec!["NonExistent".to_string()], "trash".to_string(), None)
+        .change_status(
+            vec!["NonExistent".to_string()],
+            "trash".to_string(),
+            None,
+            None,
+        )
         .await;
     assert!(result.is_err());
 }
@@ -2502,12 +2742,12 @@ async fn test_delete_context_with_task_reference() {
 
     // Try to delete the context - should fail
     let result = handler
-        .change_status(vec!["Office".to_string()], "trash".to_string(), None)
+        .change_status(vec!["Office".to_string()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_err());
 
     // Verify context still exists
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 1);
     assert!(data.contexts().contains_key("Office"));
 }
@@ -2550,12 +2790,12 @@ async fn test_delete_context_with_project_reference() {
 
     // Try to delete the context - should fail
     let result = handler
-        .change_status(vec!["Office".to_string()], "trash".to_string(), None)
+        .change_status(vec!["Office".to_string()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_err());
 
     // Verify context still exists
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 1);
     assert!(data.contexts().contains_key("Office"));
 }
@@ -2614,12 +2854,12 @@ async fn test_delete_context_with_both_task_and_project_references() {
 
     // Try to delete the context - should fail (task check comes first)
     let result = handler
-        .change_status(vec!["Office".to_string()], "trash".to_string(), None)
+        .change_status(vec!["Office".to_string()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_err());
 
     // Verify context still exists
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 1);
     assert!(data.contexts().contains_key("Office"));
 }
@@ -2665,19 +2905,32 @@ async fn test_delete_context_after_removing_task_reference() {
 
     // Remove the context reference from the task
     handler
-        .update(task_id, None, None, None, Some(String::new()), None, None) // Clear context (5th param)
+        .update(
+            task_id,
+            None,
+            None,
+            None,
+            Some(String::new()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ) // Clear context (5th param)
         .await
         .unwrap();
 
     // Now deletion should succeed
     let result = handler
-        .change_status(vec!["Office".to_string()], "trash".to_string(), None)
+        .change_status(vec!["Office".to_string()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     assert!(result.unwrap().contains("deleted"));
 
     // Verify context is gone
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 0);
 }
 
@@ -2727,19 +2980,24 @@ async fn test_delete_context_after_removing_project_reference() {
             Some(String::new()), // Clear context
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         .unwrap();
 
     // Now deletion should succeed
     let result = handler
-        .change_status(vec!["Office".to_string()], "trash".to_string(), None)
+        .change_status(vec!["Office".to_string()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_ok());
     assert!(result.unwrap().contains("deleted"));
 
     // Verify context is gone
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 0);
 }
 
@@ -2796,12 +3054,12 @@ async fn test_delete_context_with_multiple_task_references() {
 
     // Try to delete the context - should fail with the first task found
     let result = handler
-        .change_status(vec!["Office".to_string()], "trash".to_string(), None)
+        .change_status(vec!["Office".to_string()], "trash".to_string(), None, None)
         .await;
     assert!(result.is_err());
 
     // Verify context still exists
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.contexts().len(), 1);
 }
 
@@ -2842,7 +3100,7 @@ async fn test_add_project_with_context() {
     assert!(result.is_ok());
 
     // Verify project has context
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let projects = data.projects();
     let project = projects.values().next().unwrap();
     assert_eq!(project.context, Some("Office".to_string()));
@@ -2918,12 +3176,17 @@ async fn test_update_project_context() {
             Some("Office".to_string()),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify context added
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let project = data.find_project_by_id(&project_id).unwrap();
     assert_eq!(project.context, Some("Office".to_string()));
 }
@@ -2974,12 +3237,17 @@ async fn test_update_project_remove_context() {
             Some("".to_string()),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // Verify context removed
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let project = data.find_project_by_id(&project_id).unwrap();
     assert_eq!(project.context, None);
 }
@@ -3008,7 +3276,7 @@ async fn test_add_project_with_custom_id() {
     assert!(result.unwrap().contains("my-custom-id"));
 
     // Verify project was created with custom ID
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     let project = data.find_project_by_id("my-custom-id").unwrap();
     assert_eq!(project.id, "my-custom-id");
     assert_eq!(project.title, "Custom ID Project");
@@ -3422,7 +3690,7 @@ async fn test_list_tasks_without_date_filter_shows_all_tasks() {
     assert!(result.is_ok());
 
     // 日付フィルタなしで一覧取得
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     let list = result.unwrap();
 
@@ -3452,7 +3720,7 @@ async fn test_list_tasks_with_date_filter_includes_same_date() {
     assert!(result.is_ok());
 
     // 同じ日付でフィルタリング
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     let list = result.unwrap();
 
@@ -3498,7 +3766,7 @@ async fn test_list_tasks_includes_notes_by_default() {
     assert!(result.is_ok());
 
     // デフォルト（exclude_notes=None）で一覧取得
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     let list = result.unwrap();
 
@@ -3537,7 +3805,7 @@ async fn test_list_tasks_includes_notes_when_explicitly_false() {
     assert!(result.is_ok());
 
     // exclude_notes=falseで明示的に一覧取得
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     let list = result.unwrap();
 
@@ -3568,7 +3836,7 @@ async fn test_list_tasks_with_multiline_notes() {
     assert!(result.is_ok());
 
     // デフォルトで一覧取得
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     let list = result.unwrap();
 
@@ -3599,7 +3867,7 @@ async fn test_list_displays_timestamps() {
     assert!(result.is_ok());
 
     // 一覧取得
-    let result = handler.list(None, None, None, None, None, None).await;
+    let result = handler.list(None, None, None, None, None, None, None).await;
     assert!(result.is_ok());
     let list = result.unwrap();
 
@@ -3655,13 +3923,14 @@ async fn test_list_displays_completion_date_for_done_tasks() {
             vec!["task-completion".to_string()],
             "done".to_string(),
             None,
+            None,
         )
         .await;
     assert!(result.is_ok());
 
     // 一覧取得（status=doneでフィルタ）
     let result = handler
-        .list(Some("done".to_string()), None, None, None, None, None)
+        .list(Some("done".to_string()), None, None, None, None, None, None)
         .await;
     assert!(result.is_ok());
     let list = result.unwrap();
@@ -3709,7 +3978,7 @@ async fn test_inbox_tasks_multiple_tasks() {
         let task_id = common::extract_id_from_response(&result.unwrap());
         // Move to next_action first
         let _ = handler
-            .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+            .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
             .await;
         task_ids.push(task_id);
     }
@@ -3717,7 +3986,7 @@ async fn test_inbox_tasks_multiple_tasks() {
     // 複数のタスクを一度にinboxに移動
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "inbox".to_string(), None)
+            .change_status(vec![task_id.clone()], "inbox".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -3728,7 +3997,7 @@ async fn test_inbox_tasks_multiple_tasks() {
     }
 
     // すべてのタスクがinboxに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.inbox().len(), 3);
     assert_eq!(data.next_action().len(), 0);
 
@@ -3924,7 +4193,7 @@ async fn test_next_action_tasks_multiple_tasks() {
     // 複数のタスクを一度にnext_actionに移動
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "next_action".to_string(), None)
+            .change_status(vec![task_id.clone()], "next_action".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -3935,7 +4204,7 @@ async fn test_next_action_tasks_multiple_tasks() {
     }
 
     // すべてのタスクがnext_actionに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.next_action().len(), 4);
     assert_eq!(data.inbox().len(), 0);
 
@@ -3973,7 +4242,7 @@ async fn test_waiting_for_tasks_multiple_tasks() {
     // 複数のタスクを一度にwaiting_forに移動
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "waiting_for".to_string(), None)
+            .change_status(vec![task_id.clone()], "waiting_for".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -3984,7 +4253,7 @@ async fn test_waiting_for_tasks_multiple_tasks() {
     }
 
     // すべてのタスクがwaiting_forに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.waiting_for().len(), 3);
     assert_eq!(data.inbox().len(), 0);
 
@@ -4022,7 +4291,7 @@ async fn test_someday_tasks_multiple_tasks() {
     // 複数のタスクを一度にsomedayに移動
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "someday".to_string(), None)
+            .change_status(vec![task_id.clone()], "someday".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -4033,7 +4302,7 @@ async fn test_someday_tasks_multiple_tasks() {
     }
 
     // すべてのタスクがsomedayに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.someday().len(), 3);
     assert_eq!(data.inbox().len(), 0);
 
@@ -4071,7 +4340,7 @@ async fn test_later_tasks_multiple_tasks() {
     // 複数のタスクを一度にlaterに移動
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "later".to_string(), None)
+            .change_status(vec![task_id.clone()], "later".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -4082,7 +4351,7 @@ async fn test_later_tasks_multiple_tasks() {
     }
 
     // すべてのタスクがlaterに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.later().len(), 3);
     assert_eq!(data.inbox().len(), 0);
 
@@ -4120,7 +4389,7 @@ async fn test_done_tasks_multiple_tasks() {
     // 複数のタスクを一度にdoneに移動
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "done".to_string(), None)
+            .change_status(vec![task_id.clone()], "done".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -4131,7 +4400,7 @@ async fn test_done_tasks_multiple_tasks() {
     }
 
     // すべてのタスクがdoneに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.done().len(), 3);
     assert_eq!(data.inbox().len(), 0);
 
@@ -4166,7 +4435,7 @@ async fn test_change_task_status_invalid_status_error_message() {
 
     // 無効なステータス "in_progress" でエラーをテスト（問題として報告されたもの）
     let result = handler
-        .change_status(vec![task_id.clone()], "in_progress".to_string(), None)
+        .change_status(vec![task_id.clone()], "in_progress".to_string(), None, None)
         .await;
     assert!(result.is_err());
     let err_msg = format!("{:?}", result.unwrap_err());
@@ -4216,7 +4485,12 @@ async fn test_change_task_status_various_invalid_statuses() {
 
     for invalid_status in invalid_statuses {
         let result = handler
-            .change_status(vec![task_id.clone()], invalid_status.to_string(), None)
+            .change_status(
+                vec![task_id.clone()],
+                invalid_status.to_string(),
+                None,
+                None,
+            )
             .await;
         assert!(
             result.is_err(),
@@ -4248,6 +4522,7 @@ async fn test_list_tasks_invalid_status_error_message() {
             None,
             None,
             None,
+            None,
         )
         .await;
     assert!(result.is_err());
@@ -4272,6 +4547,7 @@ async fn test_list_tasks_various_invalid_statuses() {
                 None,
                 None,
                 None,
+                None,
             )
             .await;
         assert!(
@@ -4322,6 +4598,7 @@ async fn test_calendar_tasks_multiple_tasks_with_date() {
                 vec![task_id.clone()],
                 "calendar".to_string(),
                 Some("2025-01-15".to_string()),
+                None,
             )
             .await;
         assert!(
@@ -4332,7 +4609,7 @@ async fn test_calendar_tasks_multiple_tasks_with_date() {
     }
 
     // すべてのタスクがcalendarに移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.calendar().len(), 3);
     assert_eq!(data.inbox().len(), 0);
 
@@ -4374,7 +4651,7 @@ async fn test_calendar_tasks_with_existing_dates() {
     // start_dateを指定せずにcalendarに移動（既存のstart_dateを使用）
     for task_id in &task_ids {
         let result = handler
-            .change_status(vec![task_id.clone()], "calendar".to_string(), None)
+            .change_status(vec![task_id.clone()], "calendar".to_string(), None, None)
             .await;
         assert!(
             result.is_ok(),
@@ -4384,7 +4661,7 @@ async fn test_calendar_tasks_with_existing_dates() {
     }
 
     // すべてのタスクがcalendarに移動され、既存のstart_dateが保持されていることを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.calendar().len(), 2);
     for task_id in &task_ids {
         let task = data.find_task_by_id(task_id).unwrap();
@@ -4440,18 +4717,28 @@ async fn test_calendar_tasks_partial_failure() {
     // start_dateを指定せずに移動を試みる（部分的な失敗）
     // First task has date, should succeed
     let result1 = handler
-        .change_status(vec![task_ids[0].clone()], "calendar".to_string(), None)
+        .change_status(
+            vec![task_ids[0].clone()],
+            "calendar".to_string(),
+            None,
+            None,
+        )
         .await;
     assert!(result1.is_ok(), "Task with date should move to calendar");
 
     // Second task has no date, should fail
     let result2 = handler
-        .change_status(vec![task_ids[1].clone()], "calendar".to_string(), None)
+        .change_status(
+            vec![task_ids[1].clone()],
+            "calendar".to_string(),
+            None,
+            None,
+        )
         .await;
     assert!(result2.is_err(), "Task without date should fail");
 
     // 1つのタスクだけが移動されたことを確認
-    let data = handler.data.lock().unwrap();
+    let data = handler.data.read().await;
     assert_eq!(data.calendar().len(), 1);
     assert_eq!(data.inbox().len(), 1);
 }
@@ -4522,6 +4809,7 @@ async fn test_list_with_date_filter_basic() {
             None,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -4587,7 +4875,15 @@ async fn test_list_with_date_filter_only_applies_to_calendar() {
 
     // 現在の日付でフィルタリング（2024-06-15）
     let result = handler
-        .list(None, Some("2024-06-15".to_string()), None, None, None, None)
+        .list(
+            None,
+            Some("2024-06-15".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
         .await
         .unwrap();
 
@@ -4626,8 +4922,8 @@ async fn test_list_with_date_filter_calendar_without_start_date() {
     // inbox から calendar に手動で移動（start_date なし）
     // change_status は calendar に start_date を要求するため、直接データを操作
     {
-        let mut data = handler.data.lock().unwrap();
-        data.move_status("task-no-date", NotaStatus::calendar);
+        let mut data = handler.data.write().await;
+        data.move_status("task-no-date", NotaStatus::calendar, local_date_today());
     }
 
     // 未来の日付でフィルタリング
@@ -4639,6 +4935,7 @@ async fn test_list_with_date_filter_calendar_without_start_date() {
             None,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -4654,7 +4951,15 @@ async fn test_list_with_invalid_date_format() {
 
     // 無効な日付フォーマット
     let result = handler
-        .list(None, Some("2024/06/15".to_string()), None, None, None, None)
+        .list(
+            None,
+            Some("2024/06/15".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
         .await;
     assert!(result.is_err());
     let err_msg = format!("{:?}", result.unwrap_err());
@@ -4663,7 +4968,15 @@ async fn test_list_with_invalid_date_format() {
 
     // もう一つの無効なフォーマット
     let result = handler
-        .list(None, Some("15-06-2024".to_string()), None, None, None, None)
+        .list(
+            None,
+            Some("15-06-2024".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
         .await;
     assert!(result.is_err());
 }
@@ -4691,14 +5004,14 @@ async fn test_list_with_exclude_notes() {
 
     // ノートを含めてリスト（デフォルト）
     let result_with_notes = handler
-        .list(None, None, None, None, None, None)
+        .list(None, None, None, None, None, None, None)
         .await
         .unwrap();
     assert!(result_with_notes.contains("These are detailed notes"));
 
     // ノートを除外してリスト
     let result_without_notes = handler
-        .list(None, None, Some(true), None, None, None)
+        .list(None, None, Some(true), None, None, None, None)
         .await
         .unwrap();
     assert!(!result_without_notes.contains("These are detailed notes"));
@@ -4706,21 +5019,80 @@ async fn test_list_with_exclude_notes() {
 
     // 明示的に false を指定してノートを含める
     let result_with_notes_explicit = handler
-        .list(None, None, Some(false), None, None, None)
+        .list(None, None, Some(false), None, None, None, None)
         .await
         .unwrap();
     assert!(result_with_notes_explicit.contains("These are detailed notes"));
 }
 
-// テスト: date フィルタと status フィルタの併用
+// テスト: max_notes_chars で長いノートが切り詰められ、get で全文が読めること
 #[tokio::test]
-async fn test_list_with_date_and_status_filter_combined() {
+async fn test_list_max_notes_chars_and_get() {
     let (handler, _temp_file) = get_test_handler();
-
-    // 複数のステータスでタスクを作成
+    let notes = format!("Summary line. {}", "詳細".repeat(100));
     handler
         .inbox(
-            "cal-past".to_string(),
+            "big-reference".to_string(),
+            "Big reference".to_string(),
+            "reference".to_string(),
+            None,
+            None,
+            Some(notes.clone()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let listing = handler
+        .list(None, None, None, None, None, None, Some(13))
+        .await
+        .unwrap();
+    assert!(
+        listing.contains(
+            "  Notes: Summary line.… [truncated, 201 more chars: get(id=\"big-reference\") for the full notes]\n"
+        ),
+        "{}",
+        listing
+    );
+
+    // 上限より短いノートはそのまま
+    let listing = handler
+        .list(None, None, None, None, None, None, Some(1000))
+        .await
+        .unwrap();
+    assert!(listing.contains(&notes), "{}", listing);
+
+    let detail = handler.get(" big-reference ".to_string()).await.unwrap();
+    assert!(
+        detail.contains(&format!("  Notes: {}\n", notes)),
+        "{}",
+        detail
+    );
+
+    let message = handler
+        .get("big-referense".to_string())
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("Did you mean 'big-reference'?"),
+        "{}",
+        message
+    );
+}
+
+// テスト: date フィルタと status フィルタの併用
+#[tokio::test]
+async fn test_list_with_date_and_status_filter_combined() {
+    let (handler, _temp_file) = get_test_handler();
+
+    // 複数のステータスでタスクを作成
+    handler
+        .inbox(
+            "cal-past".to_string(),
             "Calendar past".to_string(),
             "calendar".to_string(),
             None,
@@ -4772,6 +5144,7 @@ async fn test_list_with_date_and_status_filter_combined() {
             None,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -4827,6 +5200,7 @@ async fn test_list_with_date_filter_and_exclude_notes() {
             None,
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -5127,7 +5501,12 @@ async fn test_mcp_comprehensive_duplicate_scenarios() {
         .unwrap();
 
     handler
-        .change_status(vec!["dup2".to_string()], "next_action".to_string(), None)
+        .change_status(
+            vec!["dup2".to_string()],
+            "next_action".to_string(),
+            None,
+            None,
+        )
         .await
         .unwrap();
 
@@ -5433,7 +5812,15 @@ async fn test_list_with_keyword_filter_in_title() {
 
     // "TRITON"で検索
     let result = handler
-        .list(None, None, None, Some("TRITON".to_string()), None, None)
+        .list(
+            None,
+            None,
+            None,
+            Some("TRITON".to_string()),
+            None,
+            None,
+            None,
+        )
         .await
         .unwrap();
 
@@ -5480,7 +5867,7 @@ async fn test_list_with_keyword_filter_in_notes() {
 
     // "FFT"で検索（ノート内を検索）
     let result = handler
-        .list(None, None, None, Some("FFT".to_string()), None, None)
+        .list(None, None, None, Some("FFT".to_string()), None, None, None)
         .await
         .unwrap();
 
@@ -5511,7 +5898,15 @@ async fn test_list_with_keyword_filter_case_insensitive() {
 
     // 小文字で検索
     let result = handler
-        .list(None, None, None, Some("triton".to_string()), None, None)
+        .list(
+            None,
+            None,
+            None,
+            Some("triton".to_string()),
+            None,
+            None,
+            None,
+        )
         .await
         .unwrap();
 
@@ -5569,7 +5964,15 @@ async fn test_list_with_keyword_filter_checks_both_title_and_notes() {
 
     // "keyword"で検索
     let result = handler
-        .list(None, None, None, Some("keyword".to_string()), None, None)
+        .list(
+            None,
+            None,
+            None,
+            Some("keyword".to_string()),
+            None,
+            None,
+            None,
+        )
         .await
         .unwrap();
 
@@ -5629,7 +6032,7 @@ async fn test_list_with_keyword_filter_in_id() {
 
     // "fft"で検索（ID内を検索）
     let result = handler
-        .list(None, None, None, Some("fft".to_string()), None, None)
+        .list(None, None, None, Some("fft".to_string()), None, None, None)
         .await
         .unwrap();
 
@@ -5734,7 +6137,7 @@ async fn test_list_with_project_filter() {
 
     // "FFT"プロジェクトでフィルタ
     let result = handler
-        .list(None, None, None, None, Some("FFT".to_string()), None)
+        .list(None, None, None, None, Some("FFT".to_string()), None, None)
         .await
         .unwrap();
 
@@ -5840,7 +6243,7 @@ async fn test_list_with_context_filter() {
 
     // "仕事"コンテキストでフィルタ
     let result = handler
-        .list(None, None, None, None, None, Some("仕事".to_string()))
+        .list(None, None, None, None, None, Some("仕事".to_string()), None)
         .await
         .unwrap();
 
@@ -5908,6 +6311,7 @@ async fn test_list_with_status_and_keyword_filters() {
             Some("TRITON".to_string()),
             None,
             None,
+            None,
         )
         .await
         .unwrap();
@@ -6020,6 +6424,7 @@ async fn test_list_with_project_and_context_filters() {
             None,
             Some("FFT".to_string()),
             Some("仕事".to_string()),
+            None,
         )
         .await
         .unwrap();
@@ -6118,6 +6523,7 @@ async fn test_list_with_all_filters_combined() {
             Some("TRITON".to_string()),
             Some("FFT".to_string()),
             Some("仕事".to_string()),
+            None,
         )
         .await
         .unwrap();
@@ -6157,9 +6563,998 @@ async fn test_list_with_filters_no_matches() {
             Some("nonexistent".to_string()),
             None,
             None,
+            None,
         )
         .await
         .unwrap();
 
     assert_eq!(result, "No items found");
 }
+
+// ==================== PERSISTENCE TESTS ====================
+
+// 並行したツール呼び出しの保存が順序通りに行われ、最後の状態がファイルに残ることを確認
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_saves_persist_final_state() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap().to_string();
+    let handler = std::sync::Arc::new(GtdServerHandler::new(&path, false).unwrap());
+
+    let mut tasks = Vec::new();
+    for i in 0..20 {
+        let handler = handler.clone();
+        tasks.push(tokio::spawn(async move {
+            handler
+                .inbox(
+                    format!("concurrent-{}", i),
+                    format!("Concurrent {}", i),
+                    "inbox".to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await
+        }));
+    }
+    for task in tasks {
+        assert!(task.await.unwrap().is_ok());
+    }
+
+    // 別のハンドラーで再読み込みして全件が保存されていることを確認
+    let reloaded = GtdServerHandler::new(&path, false).unwrap();
+    let data = reloaded.data.read().await;
+    assert_eq!(data.task_count(), 20);
+    for i in 0..20 {
+        assert!(data.find_by_id(&format!("concurrent-{}", i)).is_some());
+    }
+}
+
+// 別マシンの push 後に保存すると rebase され、取り込んだ変更がメモリにも反映されることを確認
+#[tokio::test]
+async fn test_save_rebases_and_adopts_changes_from_other_machine() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true).unwrap();
+    for (id, status) in [("local-seed", "inbox"), ("ref-seed", "reference")] {
+        handler
+            .inbox(
+                id.to_string(),
+                id.to_string(),
+                status.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // 別マシン: 同じ remote をクローンして reference に追加
+    let other_dir = common::clone_remote(remote_dir.path());
+    let other_path = other_dir.path().join("gtd.toml");
+    let other = GtdServerHandler::new(other_path.to_str().unwrap(), true).unwrap();
+    other
+        .inbox(
+            "remote-ref".to_string(),
+            "Remote reference".to_string(),
+            "reference".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // ローカル: inbox に追加（push は rebase 後に成功する）
+    handler
+        .inbox(
+            "local-new".to_string(),
+            "Local new".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    {
+        let data = handler.data.read().await;
+        assert!(data.find_by_id("remote-ref").is_some());
+        assert!(data.find_by_id("local-new").is_some());
+    }
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("remote-ref"));
+    assert!(content.contains("local-new"));
+}
+
+// テンプレートに従ったコミットメッセージで保存されることを確認
+#[tokio::test]
+async fn test_commit_messages_follow_template() {
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true)
+        .unwrap()
+        .with_commit_template(
+            gtd_mcp::CommitTemplate::new("{action}: {title} [({status})]").unwrap(),
+        );
+
+    handler
+        .inbox(
+            "call-bob".to_string(),
+            "Call Bob".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    handler
+        .change_status(
+            vec!["call-bob".to_string()],
+            "next_action".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let repo = git2::Repository::open(work_dir.path()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(
+        head.message().unwrap(),
+        "Change status: Call Bob (inbox → next_action)"
+    );
+    let parent = head.parent(0).unwrap();
+    assert_eq!(parent.message().unwrap(), "Add: Call Bob (inbox)");
+}
+
+// sync_status ツールが未 push コミット数と push 状態を報告することを確認
+#[tokio::test]
+async fn test_sync_status_reports_push_state() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    let response = handler.sync_status().await.unwrap();
+    assert!(response.contains("Git sync is disabled"));
+
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true).unwrap();
+    handler
+        .inbox(
+            "sync-me".to_string(),
+            "Sync me".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let response = handler.sync_status().await.unwrap();
+    assert!(response.contains("Push mode: on every save"));
+    assert!(response.contains("Unpushed commits: 0"));
+
+    let storage = gtd_mcp::Storage::new(&path, true)
+        .with_background_push(std::time::Duration::from_secs(3600));
+    let handler = GtdServerHandler::with_storage(storage).unwrap();
+    let response = handler.sync_status().await.unwrap();
+    assert!(response.contains("Push mode: background, at most every 3600s"));
+    assert!(response.contains("Push pending: no"));
+}
+
+// sync ツールが他マシンの変更を取り込んでデータを再読み込みすることを確認
+#[tokio::test]
+async fn test_sync_tool_pulls_and_reloads() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    let error = handler.sync().await.unwrap_err();
+    assert!(format!("{:?}", error).contains("Git sync is disabled"));
+
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true).unwrap();
+    let response = handler.sync().await.unwrap();
+    assert!(response.contains("Already up to date"));
+
+    // 別マシンで項目を追加
+    let other_dir = common::clone_remote(remote_dir.path());
+    let other_path = other_dir.path().join("gtd.toml");
+    let other = GtdServerHandler::new(other_path.to_str().unwrap(), true).unwrap();
+    other
+        .inbox(
+            "from-laptop".to_string(),
+            "From laptop".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let response = handler.sync().await.unwrap();
+    assert!(response.contains("Received 1 commit:"));
+    assert!(response.contains("From laptop"));
+    assert!(response.contains("Data reloaded"));
+    assert!(
+        handler
+            .data
+            .read()
+            .await
+            .find_by_id("from-laptop")
+            .is_some()
+    );
+}
+
+// git_status ツールが未コミット・ahead/behind・最終同期を報告することを確認
+#[tokio::test]
+async fn test_git_status_tool_reports_repository_state() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    let response = handler.git_status().await.unwrap();
+    assert!(response.contains("not in a Git repository"));
+
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true).unwrap();
+    handler
+        .inbox(
+            "status-check".to_string(),
+            "Status check".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let response = handler.git_status().await.unwrap();
+    assert!(response.contains("Git sync: enabled"));
+    assert!(response.contains("Working tree: clean"));
+    assert!(response.contains(": 0 (local commits not pushed)"));
+    assert!(!response.contains("Last sync: never"));
+}
+
+// 週次レビュー完了（empty_trash）で weekly タグが付くことを確認
+#[tokio::test]
+async fn test_empty_trash_tags_weekly_snapshot() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let storage = gtd_mcp::Storage::new(&path, true).with_weekly_snapshots();
+    let handler = GtdServerHandler::with_storage(storage).unwrap();
+    handler
+        .inbox(
+            "old-idea".to_string(),
+            "Old idea".to_string(),
+            "trash".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let response = handler.empty_trash().await.unwrap();
+    let expected = format!("weekly-{}", gtd_mcp::local_date_today().format("%G-W%V"));
+    assert!(response.contains(&format!("Tagged weekly snapshot: {}", expected)));
+    let remote = git2::Repository::open_bare(remote_dir.path()).unwrap();
+    assert!(remote.revparse_single(&expected).is_ok());
+
+    // オプションなしではタグを付けない
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    let response = handler.empty_trash().await.unwrap();
+    assert!(!response.contains("Tagged"));
+}
+
+// 状態が変わらないツール呼び出しでは書き込み・コミットしないことを確認
+#[tokio::test]
+async fn test_unchanged_state_skips_save_and_commit() {
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true).unwrap();
+    handler
+        .inbox(
+            "keep-me".to_string(),
+            "Keep me".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let repo = git2::Repository::open(work_dir.path()).unwrap();
+    let head = repo.head().unwrap().target().unwrap();
+
+    // ゴミ箱が空なので何も変わらない
+    handler.empty_trash().await.unwrap();
+    assert_eq!(repo.head().unwrap().target().unwrap(), head);
+
+    // 再起動直後の無変更保存もスキップされる
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true).unwrap();
+    handler.empty_trash().await.unwrap();
+    assert_eq!(repo.head().unwrap().target().unwrap(), head);
+}
+
+// 読み取りロック保持中でも list が待たされないことを確認（RwLock で読み取りは並行）
+#[tokio::test]
+async fn test_list_runs_while_data_is_read_locked() {
+    let (handler, _temp_file) = get_test_handler();
+    handler
+        .inbox(
+            "shared-read".to_string(),
+            "Shared read".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let _reader = handler.data.read().await;
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        handler.list(None, None, None, None, None, None, None),
+    )
+    .await
+    .expect("list must not wait for other readers");
+    assert!(result.unwrap().contains("shared-read"));
+}
+
+// トランザクション内の複数操作が 1 回の書き込み・1 コミットになることを確認
+#[tokio::test]
+async fn test_transaction_produces_single_commit() {
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let path = work_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), true).unwrap();
+    let repo = git2::Repository::open(work_dir.path()).unwrap();
+    let head_before = repo.head().unwrap().target().unwrap();
+
+    assert!(handler.commit_transaction().await.is_err());
+    handler.begin_transaction().await.unwrap();
+    assert!(handler.begin_transaction().await.is_err());
+
+    for id in ["tx-1", "tx-2", "tx-3"] {
+        handler
+            .inbox(
+                id.to_string(),
+                format!("Transaction item {}", id),
+                "inbox".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    handler
+        .change_status(
+            vec!["tx-1".to_string()],
+            "next_action".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // コミット前はディスクにもGitにも反映されない
+    assert_eq!(repo.head().unwrap().target().unwrap(), head_before);
+    assert!(
+        !std::fs::read_to_string(&path)
+            .unwrap_or_default()
+            .contains("tx-1")
+    );
+
+    assert_eq!(handler.commit_transaction().await.unwrap(), 4);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.parent_id(0).unwrap(), head_before);
+    assert!(
+        head.message()
+            .unwrap()
+            .starts_with("Batch update: 4 changes")
+    );
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("tx-1") && content.contains("tx-3"));
+
+    // 変更のないトランザクションは何も書き込まない
+    handler.begin_transaction().await.unwrap();
+    assert_eq!(handler.commit_transaction().await.unwrap(), 0);
+    assert_eq!(repo.head().unwrap().target().unwrap(), head.id());
+}
+
+// --case-insensitive-ids 相当の設定で、表記違いの ID が重複として扱われることを確認
+#[tokio::test]
+async fn test_case_insensitive_ids_detect_duplicates() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let storage = gtd_mcp::Storage::new(temp_file.path(), false).with_case_insensitive_ids();
+    let handler = GtdServerHandler::with_storage(storage).unwrap();
+    handler
+        .inbox(
+            "Office".to_string(),
+            "Office".to_string(),
+            "context".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let result = handler
+        .inbox(
+            "office".to_string(),
+            "Office again".to_string(),
+            "context".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await;
+    assert!(format!("{:?}", result.unwrap_err()).contains("ID 'Office' already exists"));
+
+    // 小文字のコンテキスト参照は保存済みの表記で記録される
+    handler
+        .inbox(
+            "print-slides".to_string(),
+            "Print slides".to_string(),
+            "next_action".to_string(),
+            None,
+            Some("office".to_string()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let response = handler
+        .list(
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("OFFICE".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(response.contains("print-slides"));
+
+    // 再読み込み後も設定が維持される
+    let storage = gtd_mcp::Storage::new(temp_file.path(), false).with_case_insensitive_ids();
+    let handler = GtdServerHandler::with_storage(storage).unwrap();
+    assert!(handler.data.read().await.contains_id("PRINT-SLIDES"));
+}
+
+// ID ポリシー設定時に inbox がルール違反の ID を拒否することを確認
+#[tokio::test]
+async fn test_inbox_enforces_id_policy() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false)
+        .unwrap()
+        .with_id_policy(gtd_mcp::IdPolicy::kebab_case());
+
+    let result = handler
+        .inbox(
+            "Call_John".to_string(),
+            "Call John".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await;
+    let error = format!("{:?}", result.unwrap_err());
+    assert!(error.contains("ID policy error"));
+    assert!(error.contains("try 'call-john'"));
+    assert!(handler.data.read().await.find_by_id("Call_John").is_none());
+
+    handler
+        .inbox(
+            "call-john".to_string(),
+            "Call John".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+// 参照されているプロジェクトを cascade 付きでゴミ箱へ移動できることを確認
+#[tokio::test]
+async fn test_change_status_trash_with_cascade() {
+    let (handler, _temp_file) = get_test_handler();
+    for (id, status, project) in [
+        ("website", "project", None),
+        ("website-copy", "project", Some("website")),
+        ("draft-copy", "next_action", Some("website-copy")),
+        ("pick-colors", "next_action", Some("website")),
+    ] {
+        handler
+            .inbox(
+                id.to_string(),
+                id.to_string(),
+                status.to_string(),
+                project.map(str::to_string),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // cascade なしでは参照されているため失敗する
+    let error = handler
+        .change_status(vec!["website".to_string()], "trash".to_string(), None, None)
+        .await
+        .unwrap_err();
+    assert!(format!("{:?}", error).contains("cascade"));
+
+    // cascade はゴミ箱以外では使えない
+    assert!(
+        handler
+            .change_status(
+                vec!["website".to_string()],
+                "done".to_string(),
+                None,
+                Some("trash".to_string()),
+            )
+            .await
+            .is_err()
+    );
+
+    // trash: 子孫もまとめてゴミ箱へ
+    let response = handler
+        .change_status(
+            vec!["website".to_string()],
+            "trash".to_string(),
+            None,
+            Some("trash".to_string()),
+        )
+        .await
+        .unwrap();
+    assert!(response.contains("- website (moved to trash)"));
+    assert!(response.contains("Also moved to trash:"));
+    {
+        let data = handler.data.read().await;
+        for id in ["website", "website-copy", "draft-copy", "pick-colors"] {
+            assert_eq!(data.find_by_id(id).unwrap().status, NotaStatus::trash);
+        }
+    }
+}
+
+// cascade=unlink で子タスクのリンクを外してからゴミ箱へ移動することを確認
+#[tokio::test]
+async fn test_change_status_trash_with_cascade_unlink() {
+    let (handler, _temp_file) = get_test_handler();
+    for (id, status, context) in [
+        ("Office", "context", None),
+        ("print-slides", "next_action", Some("Office")),
+    ] {
+        handler
+            .inbox(
+                id.to_string(),
+                id.to_string(),
+                status.to_string(),
+                None,
+                context.map(str::to_string),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    let response = handler
+        .change_status(
+            vec!["Office".to_string()],
+            "trash".to_string(),
+            None,
+            Some("unlink".to_string()),
+        )
+        .await
+        .unwrap();
+    assert!(response.contains("Unlinked: print-slides"));
+
+    let data = handler.data.read().await;
+    let task = data.find_by_id("print-slides").unwrap();
+    assert_eq!(task.status, NotaStatus::next_action);
+    assert_eq!(task.context, None);
+    assert_eq!(data.find_by_id("Office").unwrap().status, NotaStatus::trash);
+    assert!(!data.is_referenced("Office"));
+}
+
+// 参照されているプロジェクトの種類変更がブロックされることを確認
+#[tokio::test]
+async fn test_type_change_blocked_while_referenced() {
+    let (handler, _temp_file) = get_test_handler();
+    for (id, status, project) in [
+        ("website", "project", None),
+        ("pick-colors", "next_action", Some("website")),
+    ] {
+        handler
+            .inbox(
+                id.to_string(),
+                id.to_string(),
+                status.to_string(),
+                project.map(str::to_string),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // update: project → next_action はリンク先を示すエラーになる
+    let error = handler
+        .update(
+            "website".to_string(),
+            None,
+            Some("next_action".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+    let message = format!("{:?}", error);
+    assert!(message.contains("Cannot change project 'website'"));
+    assert!(message.contains("pick-colors"));
+
+    // change_status: project → context も失敗する
+    let result = handler
+        .change_status(
+            vec!["website".to_string()],
+            "context".to_string(),
+            None,
+            None,
+        )
+        .await;
+    assert!(result.is_err());
+    assert_eq!(
+        handler
+            .data
+            .read()
+            .await
+            .find_by_id("website")
+            .unwrap()
+            .status,
+        NotaStatus::project
+    );
+
+    // リンクを外せば変更できる
+    handler
+        .update(
+            "pick-colors".to_string(),
+            None,
+            None,
+            Some(String::new()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    handler
+        .update(
+            "website".to_string(),
+            None,
+            Some("next_action".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+// strict モードでは不整合なデータを保存せず、検証レポートを返して変更を破棄することを確認
+#[tokio::test]
+async fn test_strict_mode_refuses_invalid_save() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false)
+        .unwrap()
+        .with_strict_saves();
+    let inbox = |id: &str| {
+        handler.inbox(
+            id.to_string(),
+            "Task".to_string(),
+            "inbox".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    };
+    inbox("valid").await.unwrap();
+    let saved = std::fs::read_to_string(temp_file.path()).unwrap();
+
+    // ライブラリ API 経由で存在しないプロジェクトへの参照を作る
+    handler.data.write().await.add(gtd::Nota {
+        id: "dangling".to_string(),
+        title: "Dangling".to_string(),
+        status: NotaStatus::next_action,
+        project: Some("missing".to_string()),
+        ..Default::default()
+    });
+
+    let error = format!("{:?}", inbox("another").await.unwrap_err());
+    assert!(error.contains("Strict mode: refusing to save invalid data (1 problem found)"));
+    assert!(error.contains("dangling: project 'missing' does not exist"));
+    assert_eq!(std::fs::read_to_string(temp_file.path()).unwrap(), saved);
+
+    // メモリ上のデータも最後に保存した状態へ戻る
+    let data = handler.data.read().await;
+    assert!(data.find_by_id("valid").is_some());
+    assert!(data.find_by_id("dangling").is_none());
+    assert!(data.find_by_id("another").is_none());
+}
+
+// ほぼ同じタイトルの取り込みは既定で警告付きで作成され、reject 設定では拒否されることを確認
+#[tokio::test]
+async fn test_inbox_detects_duplicate_titles() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    async fn inbox(handler: &GtdServerHandler, id: &str, title: &str) -> McpResult<String> {
+        handler
+            .inbox(
+                id.to_string(),
+                title.to_string(),
+                "inbox".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+    }
+
+    let response = inbox(&handler, "call-john", "Call John about the invoice")
+        .await
+        .unwrap();
+    assert!(!response.contains("Warning"));
+
+    let response = inbox(&handler, "phone-john", "call john about invoice")
+        .await
+        .unwrap();
+    assert!(response.contains("Item created with ID: phone-john"));
+    assert!(response.contains(
+        "Warning: possible duplicate of call-john (inbox): \"Call John about the invoice\""
+    ));
+
+    let handler = handler.with_duplicate_check(gtd_mcp::DuplicateCheck::Reject);
+    let error = format!(
+        "{:?}",
+        inbox(&handler, "ring-john", "Call John: about invoice")
+            .await
+            .unwrap_err()
+    );
+    assert!(error.contains("Duplicate content error"));
+    assert!(error.contains("call-john"));
+    assert!(handler.data.read().await.find_by_id("ring-john").is_none());
+
+    // 完了済みの項目と同じタイトルは再取り込みできる
+    handler
+        .change_status(
+            vec!["call-john".to_string(), "phone-john".to_string()],
+            "done".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    inbox(&handler, "ring-john", "Call John about invoice")
+        .await
+        .unwrap();
+}
+
+// switch_workspace でワークスペースを切り替えると、各ツールがそのワークスペースのデータとファイルを使うことを確認
+#[tokio::test]
+async fn test_switch_workspace_keeps_data_per_workspace() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let work_path = dir.path().join("work.toml");
+    let personal_path = dir.path().join("personal.toml");
+    let handler = GtdServerHandler::new(work_path.to_str().unwrap(), false)
+        .unwrap()
+        .with_workspace("personal", gtd_mcp::Storage::new(&personal_path, false))
+        .unwrap();
+    async fn inbox(handler: &GtdServerHandler, id: &str) -> McpResult<String> {
+        handler
+            .inbox(
+                id.to_string(),
+                id.replace('-', " "),
+                "inbox".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+    }
+
+    assert_eq!(handler.workspace(), "work");
+    let listing = handler.switch_workspace(None).await.unwrap();
+    assert!(listing.contains("* work (active)"));
+    assert!(listing.contains("- personal:"));
+
+    inbox(&handler, "write-report").await.unwrap();
+    let response = handler
+        .switch_workspace(Some("personal".to_string()))
+        .await
+        .unwrap();
+    assert!(response.contains("Switched to workspace 'personal'"));
+    assert_eq!(handler.storage().file_path(), personal_path.as_path());
+    assert!(
+        handler
+            .data
+            .read()
+            .await
+            .find_by_id("write-report")
+            .is_none()
+    );
+
+    inbox(&handler, "buy-milk").await.unwrap();
+    let work = std::fs::read_to_string(&work_path).unwrap();
+    let personal = std::fs::read_to_string(&personal_path).unwrap();
+    assert!(work.contains("write-report") && !work.contains("buy-milk"));
+    assert!(personal.contains("buy-milk") && !personal.contains("write-report"));
+
+    // 元のワークスペースに戻ると、そのデータがそのまま使われる
+    handler
+        .switch_workspace(Some("work".to_string()))
+        .await
+        .unwrap();
+    let data = handler.data.read().await;
+    assert!(data.find_by_id("write-report").is_some());
+    assert!(data.find_by_id("buy-milk").is_none());
+    drop(data);
+
+    let error = format!(
+        "{:?}",
+        handler
+            .switch_workspace(Some("hobby".to_string()))
+            .await
+            .unwrap_err()
+    );
+    assert!(error.contains("Unknown workspace 'hobby'. Available workspaces: work, personal"));
+
+    // トランザクション中は切り替えできない
+    handler.begin_transaction().await.unwrap();
+    assert!(
+        handler
+            .switch_workspace(Some("personal".to_string()))
+            .await
+            .is_err()
+    );
+    handler.commit_transaction().await.unwrap();
+
+    // 同じ名前のワークスペースは追加できない
+    assert!(
+        handler
+            .with_workspace(
+                "work",
+                gtd_mcp::Storage::new(dir.path().join("other.toml"), false)
+            )
+            .is_err()
+    );
+}
+
+// captureがタイトルからIDを生成してinbox項目を作り、衝突時は番号を付けることを確認
+#[tokio::test]
+async fn test_capture_generates_unique_ids() {
+    let (handler, _temp_file) = get_test_handler();
+
+    let response = handler
+        .capture(
+            "Call John re: Q3".to_string(),
+            Some("Ask about the budget".to_string()),
+        )
+        .await
+        .unwrap();
+    assert!(response.contains("Item created with ID: call-john-re-q3"));
+    let response = handler
+        .capture("  Call John re Q3  ".to_string(), Some(" ".to_string()))
+        .await
+        .unwrap();
+    assert!(response.contains("Item created with ID: call-john-re-q3-2"));
+
+    let data = handler.data.read().await;
+    let first = data.find_by_id("call-john-re-q3").unwrap();
+    assert_eq!(first.status, NotaStatus::inbox);
+    assert_eq!(first.notes.as_deref(), Some("Ask about the budget"));
+    let second = data.find_by_id("call-john-re-q3-2").unwrap();
+    assert_eq!(second.title, "Call John re Q3");
+    assert_eq!(second.notes, None);
+    drop(data);
+
+    // 空のタイトルはエラー
+    assert!(handler.capture("   ".to_string(), None).await.is_err());
+}
@@ -0,0 +1,90 @@
+//! Integration tests for the write-ahead journal
+//!
+//! These tests cover journaling saves before the data file is written,
+//! clearing the journal once the write is done, and replaying buffered saves
+//! on load after the process died before flushing them.
+
+use gtd_mcp::journal::Journal;
+use gtd_mcp::{GtdData, Nota, Storage};
+use std::fs;
+use std::time::Duration;
+
+fn task(id: &str) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        ..Default::default()
+    }
+}
+
+// 即時保存では書き込み後にジャーナルが消え、デバウンス中は残ることを確認
+#[test]
+fn test_journal_cleared_after_write() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let journal_path = temp_dir.path().join("gtd.journal.jsonl");
+
+    let mut data = GtdData::new();
+    data.add(task("buy-milk"));
+    Storage::new(&test_path, false).save(&data).unwrap();
+    assert!(test_path.exists());
+    assert!(!journal_path.exists());
+
+    let storage = Storage::new(&test_path, false).with_debounce(Duration::from_secs(60));
+    data.add(task("call-bank"));
+    storage.save_with_message(&data, "Add call-bank").unwrap();
+    let entries = Journal::for_data_file(&test_path).entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].message, "Add call-bank");
+
+    storage.flush().unwrap();
+    assert!(!journal_path.exists());
+}
+
+// フラッシュ前に終了しても、次の読み込みでまとめて書き込まれることを確認
+#[test]
+fn test_journal_replays_unflushed_saves() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let storage = Storage::new(&test_path, false).with_debounce(Duration::from_secs(60));
+
+    let mut data = GtdData::new();
+    for id in ["buy-milk", "call-bank", "fix-bike"] {
+        data.add(task(id));
+        storage
+            .save_with_message(&data, &format!("Add {}", id))
+            .unwrap();
+    }
+    assert!(!test_path.exists());
+
+    // クラッシュ後の再起動を想定し、フラッシュせずに別のインスタンスで読み込む
+    let restarted = Storage::new(&test_path, false);
+    assert_eq!(restarted.recover().unwrap(), 3);
+    let loaded = restarted.load().unwrap();
+    assert_eq!(loaded.task_count(), 3);
+    assert!(loaded.find_by_id("fix-bike").is_some());
+    assert!(Journal::for_data_file(&test_path).entries().is_empty());
+}
+
+// 書き込み途中で途切れた行は無視され、ファイルと同じ内容のエントリは再生されないことを確認
+#[test]
+fn test_journal_skips_torn_and_written_entries() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let mut data = GtdData::new();
+    data.add(task("buy-milk"));
+    let content = Storage::serialize(&data).unwrap();
+    fs::write(&test_path, &content).unwrap();
+
+    let journal = Journal::for_data_file(&test_path);
+    journal.append(&content, "Add buy-milk").unwrap();
+    let mut torn = fs::read_to_string(journal.path()).unwrap();
+    torn.push_str("{\"seq\":2,\"message\":\"Add ca");
+    fs::write(journal.path(), torn).unwrap();
+
+    assert_eq!(journal.entries().len(), 1);
+    let storage = Storage::new(&test_path, false);
+    assert_eq!(storage.recover().unwrap(), 0);
+    assert!(!journal.path().exists());
+    assert_eq!(storage.load().unwrap().task_count(), 1);
+}
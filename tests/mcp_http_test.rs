@@ -0,0 +1,237 @@
+//! Integration tests for MCP over HTTP
+//!
+//! These tests run the server on a local port and talk to it as two MCP
+//! clients with raw HTTP requests, verifying sessions, tool calls on the shared
+//! data and the change notifications sent to every session.
+
+use gtd_mcp::{GtdServerHandler, mcp_http};
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+/// Start the server on a free port
+async fn start(dir: &TempDir) -> (Arc<GtdServerHandler>, SocketAddr, oneshot::Sender<()>) {
+    let path = dir.path().join("gtd.toml");
+    let handler = Arc::new(GtdServerHandler::new(path.to_str().unwrap(), false).unwrap());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stop, stopped) = oneshot::channel::<()>();
+    tokio::spawn(mcp_http::serve(handler.clone(), listener, stopped));
+    (handler, addr, stop)
+}
+
+fn request_head(method: &str, session: Option<&str>, body: &str) -> String {
+    let session = session
+        .map(|id| format!("{}: {}\r\n", mcp_http::SESSION_HEADER, id))
+        .unwrap_or_default();
+    format!(
+        "{} /mcp HTTP/1.1\r\nHost: localhost\r\nAccept: application/json, text/event-stream\r\n{}Content-Length: {}\r\n\r\n{}",
+        method,
+        session,
+        body.len(),
+        body
+    )
+}
+
+/// Send one request and return the status, session header and JSON body of the response
+async fn request(
+    addr: SocketAddr,
+    method: &str,
+    session: Option<&str>,
+    body: Value,
+) -> (u16, Option<String>, Value) {
+    let body = if body.is_null() {
+        String::new()
+    } else {
+        body.to_string()
+    };
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(request_head(method, session, &body).as_bytes())
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let session = head.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.eq_ignore_ascii_case(mcp_http::SESSION_HEADER)
+            .then(|| value.trim().to_string())
+    });
+    let body = serde_json::from_str(body).unwrap_or(Value::Null);
+    (status, session, body)
+}
+
+/// Initialize a client and return its session ID
+async fn connect(addr: SocketAddr, name: &str) -> String {
+    let (status, session, body) = request(
+        addr,
+        "POST",
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": name, "version": "1.0" },
+            },
+        }),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert!(
+        body["result"]["capabilities"]["tools"].is_object(),
+        "{}",
+        body
+    );
+    let session = session.unwrap();
+    let (status, _, _) = request(
+        addr,
+        "POST",
+        Some(&session),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    )
+    .await;
+    assert_eq!(status, 202);
+    session
+}
+
+fn call(id: u64, name: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments },
+    })
+}
+
+// 二つのクライアントが同じデータを読み書きし、変更の通知を受け取ることを確認
+#[tokio::test]
+async fn test_two_clients_share_data_and_get_notified() {
+    let dir = TempDir::new().unwrap();
+    let (handler, addr, _stop) = start(&dir).await;
+    let alice = connect(addr, "alice").await;
+    let bob = connect(addr, "bob").await;
+    assert_ne!(alice, bob);
+
+    // bob のイベントストリームを開いておく
+    let mut events = TcpStream::connect(addr).await.unwrap();
+    events
+        .write_all(request_head("GET", Some(&bob), "").as_bytes())
+        .await
+        .unwrap();
+    let mut events = BufReader::new(events);
+    let mut line = String::new();
+    events.read_line(&mut line).await.unwrap();
+    assert!(line.starts_with("HTTP/1.1 200"), "{}", line);
+    while line.trim() != "" {
+        line.clear();
+        events.read_line(&mut line).await.unwrap();
+    }
+
+    let (status, _, body) = request(
+        addr,
+        "POST",
+        Some(&alice),
+        call(
+            2,
+            "inbox",
+            json!({ "id": "call-bob", "title": "Call Bob", "status": "next_action" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(body["id"], 2);
+    assert!(handler.data.read().await.contains_id("call-bob"));
+
+    // alice の変更が bob に通知される
+    let notification = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            line.clear();
+            events.read_line(&mut line).await.unwrap();
+            if let Some(data) = line.strip_prefix("data: ") {
+                break serde_json::from_str::<Value>(data).unwrap();
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(notification["method"], "notifications/message");
+    assert_eq!(notification["params"]["data"]["event"], "changed");
+
+    // bob からも同じ項目が見える
+    let (status, _, body) = request(
+        addr,
+        "POST",
+        Some(&bob),
+        call(7, "list", json!({ "status": "next_action" })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert!(body.to_string().contains("call-bob"), "{}", body);
+}
+
+// セッション ID がない・不明・終了済みのリクエストが拒否されることを確認
+#[tokio::test]
+async fn test_session_errors() {
+    let dir = TempDir::new().unwrap();
+    let (_handler, addr, _stop) = start(&dir).await;
+
+    let (status, _, _) = request(addr, "POST", None, call(1, "list", json!({}))).await;
+    assert_eq!(status, 400);
+    let (status, _, _) = request(addr, "POST", Some("nope"), call(1, "list", json!({}))).await;
+    assert_eq!(status, 404);
+
+    let session = connect(addr, "alice").await;
+    let (status, _, _) = request(addr, "DELETE", Some(&session), Value::Null).await;
+    assert_eq!(status, 200);
+    let (status, _, _) = request(addr, "POST", Some(&session), call(2, "list", json!({}))).await;
+    assert_eq!(status, 404);
+}
+
+// 他サイトの Origin や他ホスト名の Host を持つリクエストが 403 で拒否されることを確認
+#[tokio::test]
+async fn test_foreign_origin_and_host_are_refused() {
+    let dir = TempDir::new().unwrap();
+    let (_handler, addr, _stop) = start(&dir).await;
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "page", "version": "1.0" },
+        },
+    })
+    .to_string();
+    let send = async |extra: &str| {
+        let head =
+            request_head("POST", None, &initialize).replacen("Host: localhost\r\n", extra, 1);
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(head.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let status: u16 = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+        let session = response.to_ascii_lowercase().contains("mcp-session-id");
+        (status, session)
+    };
+
+    assert_eq!(
+        send("Host: localhost\r\nOrigin: https://evil.example\r\n").await,
+        (403, false)
+    );
+    assert_eq!(send("Host: rebind.example:7879\r\n").await, (403, false));
+    assert_eq!(
+        send("Host: 127.0.0.1:7879\r\nOrigin: http://localhost:5173\r\n").await,
+        (200, true)
+    );
+}
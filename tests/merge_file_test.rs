@@ -0,0 +1,134 @@
+//! Integration tests for merging another GTD data file
+//!
+//! These tests cover both collision strategies, following renamed projects
+//! in the incoming tasks, and the `merge_file` tool and `merge` command.
+
+use chrono::NaiveDate;
+use gtd_mcp::cli;
+use gtd_mcp::merge_file::{self, MergeStrategy};
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus, Storage};
+use tempfile::TempDir;
+
+fn item(id: &str, title: &str, status: NotaStatus, updated: u32) -> Nota {
+    let date = NaiveDate::from_ymd_opt(2026, 10, updated).unwrap();
+    Nota {
+        id: id.to_string(),
+        title: title.to_string(),
+        status,
+        created_at: date,
+        updated_at: date,
+        ..Default::default()
+    }
+}
+
+/// This laptop's file
+fn current() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(item("@home", "Home", NotaStatus::context, 1));
+    data.add(item("website", "Website", NotaStatus::project, 5));
+    data.add(item("buy-milk", "Buy milk", NotaStatus::next_action, 1));
+    data
+}
+
+/// The old laptop's file
+fn old_laptop() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(item("@home", "At home", NotaStatus::context, 9));
+    data.add(item("website", "Old website", NotaStatus::project, 9));
+    data.add(Nota {
+        project: Some("website".to_string()),
+        context: Some("@home".to_string()),
+        ..item("fix-css", "Fix CSS", NotaStatus::next_action, 2)
+    });
+    data.add(item("buy-milk", "Buy milk", NotaStatus::next_action, 1));
+    data
+}
+
+// suffix では衝突した項目が -2 付きで追加され、参照も新しい ID に付け替えられることを確認
+#[test]
+fn test_merge_suffix_keeps_both() {
+    let mut data = current();
+    let summary = merge_file::merge(&mut data, &old_laptop(), MergeStrategy::Suffix).unwrap();
+
+    assert_eq!(summary.added, vec!["fix-css"]);
+    assert_eq!(
+        summary.renamed,
+        vec![("website".to_string(), "website-2".to_string())]
+    );
+    assert_eq!(summary.kept, vec!["@home"]);
+    assert_eq!(summary.unchanged, 1);
+    assert_eq!(data.find_by_id("website").unwrap().title, "Website");
+    assert_eq!(data.find_by_id("website-2").unwrap().title, "Old website");
+    let task = data.find_by_id("fix-css").unwrap();
+    assert_eq!(task.project.as_deref(), Some("website-2"));
+    assert_eq!(task.context.as_deref(), Some("@home"));
+    assert!(data.check_integrity().is_empty());
+}
+
+// newest では updated_at が新しい方が残ることを確認
+#[test]
+fn test_merge_newest_wins() {
+    let mut data = current();
+    data.add(item("@office", "Office", NotaStatus::context, 20));
+    let mut other = old_laptop();
+    other.add(item("@office", "Old office", NotaStatus::context, 3));
+
+    let summary = merge_file::merge(&mut data, &other, MergeStrategy::Newest).unwrap();
+    assert_eq!(summary.replaced, vec!["@home", "website"]);
+    assert_eq!(summary.kept, vec!["@office"]);
+    assert!(summary.renamed.is_empty());
+    assert_eq!(data.find_by_id("website").unwrap().title, "Old website");
+    assert_eq!(data.find_by_id("@office").unwrap().title, "Office");
+    assert_eq!(
+        data.find_by_id("fix-css").unwrap().project.as_deref(),
+        Some("website")
+    );
+    assert!("latest".parse::<MergeStrategy>().is_err());
+}
+
+// merge_file ツールが相対パスのファイルを取り込んで保存し、merge コマンドは dry run で書き込まないことを確認
+#[tokio::test]
+async fn test_merge_file_tool_and_command() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gtd.toml");
+    let old_path = dir.path().join("old.toml");
+    Storage::new(&path, false).save(&current()).unwrap();
+    Storage::new(&old_path, false).save(&old_laptop()).unwrap();
+
+    let preview = cli::merge(
+        path.to_str().unwrap(),
+        old_path.to_str().unwrap(),
+        MergeStrategy::Suffix,
+        true,
+    )
+    .unwrap();
+    assert!(preview.contains("1 added, 1 renamed, 0 replaced, 1 kept, 1 unchanged"));
+    assert!(
+        Storage::new(&path, false)
+            .load()
+            .unwrap()
+            .find_by_id("fix-css")
+            .is_none()
+    );
+
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    let response = handler
+        .merge_file("old.toml".to_string(), Some("suffix".to_string()))
+        .await
+        .unwrap();
+    assert!(
+        response.contains("Renamed: website → website-2"),
+        "{}",
+        response
+    );
+    let saved = Storage::new(&path, false).load().unwrap();
+    assert!(saved.find_by_id("website-2").is_some());
+
+    let message = handler
+        .merge_file("missing.toml".to_string(), None)
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(message.contains("does not exist"), "{}", message);
+}
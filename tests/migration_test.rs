@@ -3,9 +3,11 @@
 //! These tests verify the migration functions for upgrading data structures
 //! between different schema versions.
 
+use gtd_mcp::Storage;
 use gtd_mcp::migration::local_date_today;
 use gtd_mcp::migration::{
-    Project, migrate_projects_v1_to_v2, normalize_string_line_endings, populate_project_ids,
+    CURRENT_FORMAT_VERSION, Project, STEPS, migrate_file, migrate_projects_v1_to_v2,
+    normalize_string_line_endings, plan_migration, populate_project_ids, source_version,
 };
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -23,6 +25,20 @@ fn test_migrate_projects_v1_to_v2() {
             updated_at: local_date_today(),
             context: None,
             status: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         },
         Project {
             id: "project-2".to_string(),
@@ -34,6 +50,20 @@ fn test_migrate_projects_v1_to_v2() {
             updated_at: local_date_today(),
             context: Some("Office".to_string()),
             status: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         },
     ];
 
@@ -83,6 +113,20 @@ fn test_populate_project_ids() {
             updated_at: local_date_today(),
             context: None,
             status: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         },
     );
 
@@ -215,3 +259,184 @@ context = "仕事"
     assert_eq!(task.id, "#13");
     assert_eq!(task.title, "4章を攻略する");
 }
+
+// ==================== EXPLICIT MIGRATION TESTS ====================
+
+const LEGACY_V2_TOML: &str = r#"
+format_version = 2
+
+[[inbox]]
+id = "task-1"
+title = "Legacy task"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+
+[projects.website]
+name = "Website Redesign"
+description = "Refresh the site"
+"#;
+
+#[test]
+fn test_plan_migration_detects_legacy_constructs() {
+    let changes = plan_migration(LEGACY_V2_TOML).unwrap();
+    assert_eq!(changes.len(), 2);
+    assert!(changes[0].contains("format_version 2"));
+    assert!(changes[1].contains("[projects.<id>]"));
+}
+
+#[test]
+fn test_plan_migration_current_format_is_empty() {
+    // 現行フォーマット（format_version 省略も含む）は移行不要
+    let current = r#"
+format_version = 3
+
+[[project]]
+id = "website"
+title = "Website"
+status = "project"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+"#;
+    assert!(plan_migration(current).unwrap().is_empty());
+    assert!(
+        plan_migration("[[inbox]]\nid = \"a\"\ntitle = \"A\"\n")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_storage_load_refuses_legacy_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    std::fs::write(&path, LEGACY_V2_TOML).unwrap();
+
+    let message = match Storage::new(&path, false).load() {
+        Ok(_) => panic!("legacy file should not load without migration"),
+        Err(e) => e.to_string(),
+    };
+    assert!(message.contains("gtd-mcp migrate"));
+    assert!(message.contains("format_version 2"));
+}
+
+#[test]
+fn test_migrate_file_dry_run_does_not_modify() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    std::fs::write(&path, LEGACY_V2_TOML).unwrap();
+
+    let report = migrate_file(&path, true).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.changes.len(), 2);
+    assert_eq!(report.item_count, 2);
+    assert!(report.backup_path.is_none());
+    assert!(report.to_string().contains("Would migrate"));
+
+    // ファイルもバックアップも作られない
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), LEGACY_V2_TOML);
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_migrate_file_writes_backup_and_upgrades() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    std::fs::write(&path, LEGACY_V2_TOML).unwrap();
+
+    let report = migrate_file(&path, false).unwrap();
+    let backup = report.backup_path.expect("backup should be written");
+    assert_eq!(std::fs::read_to_string(&backup).unwrap(), LEGACY_V2_TOML);
+
+    // 移行後は通常どおり読み込める
+    let data = Storage::new(&path, false).load().unwrap();
+    assert_eq!(data.inbox().len(), 1);
+    let project = data.find_by_id("website").unwrap();
+    assert_eq!(project.title, "Website Redesign");
+    assert_eq!(project.notes, Some("Refresh the site".to_string()));
+
+    // 2回目は何もしない
+    let again = migrate_file(&path, false).unwrap();
+    assert!(again.changes.is_empty());
+    assert!(again.backup_path.is_none());
+}
+
+// ==================== MIGRATION STEP TESTS ====================
+
+const LEGACY_V1_TOML: &str = r#"
+format_version = 1
+
+[[projects]]
+id = "website"
+name = "Website Redesign"
+status = "active"
+
+[contexts.office]
+notes = "Desk at work"
+"#;
+
+#[test]
+fn test_migration_steps_chain_to_current_version() {
+    // 登録されたステップが 1 から現行バージョンまで 1 つずつ連続していること
+    assert_eq!(STEPS.first().unwrap().from, 1);
+    for pair in STEPS.windows(2) {
+        assert_eq!(pair[0].to, pair[1].from);
+    }
+    for step in STEPS {
+        assert_eq!(step.to, step.from + 1);
+    }
+    assert_eq!(STEPS.last().unwrap().to, CURRENT_FORMAT_VERSION);
+}
+
+#[test]
+fn test_source_version_uses_oldest_construct() {
+    // 宣言より古い構造が残っていれば、その構造のバージョンから移行する
+    let table = |content: &str| content.parse::<toml::Table>().unwrap();
+    assert_eq!(source_version(&table(LEGACY_V1_TOML)), 1);
+    assert_eq!(source_version(&table(LEGACY_V2_TOML)), 2);
+    assert_eq!(
+        source_version(&table("format_version = 3\n[projects.a]\ntitle = \"A\"\n")),
+        2
+    );
+    assert_eq!(
+        source_version(&table("[[inbox]]\nid = \"a\"\ntitle = \"A\"\n")),
+        CURRENT_FORMAT_VERSION
+    );
+}
+
+#[test]
+fn test_migrate_file_runs_steps_in_order_and_records_history() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    std::fs::write(&path, LEGACY_V1_TOML).unwrap();
+
+    let report = migrate_file(&path, false).unwrap();
+    let versions: Vec<(u32, u32)> = report.steps.iter().map(|s| (s.from, s.to)).collect();
+    assert_eq!(versions, vec![(1, 2), (2, 3)]);
+    assert!(report.steps[0].changes[0].contains("[[projects]] array"));
+    assert!(report.steps[1].changes[0].contains("[projects.<id>]"));
+    assert!(report.to_string().contains("- v1 → v2: Key projects by ID"));
+
+    // 移行結果と履歴が保存され、次の保存でも履歴が残る
+    let storage = Storage::new(&path, false);
+    let data = storage.load().unwrap();
+    assert_eq!(
+        data.find_by_id("website").unwrap().title,
+        "Website Redesign"
+    );
+    assert_eq!(
+        data.find_by_id("office").unwrap().notes.as_deref(),
+        Some("Desk at work")
+    );
+    let history: Vec<(u32, u32)> = data
+        .migration_history
+        .iter()
+        .map(|record| (record.from, record.to))
+        .collect();
+    assert_eq!(history, vec![(1, 2), (2, 3)]);
+    assert_eq!(data.migration_history[0].date, local_date_today());
+
+    storage.save(&data).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("[[migration_history]]"), "{}", content);
+    assert_eq!(storage.load().unwrap().migration_history.len(), 2);
+}
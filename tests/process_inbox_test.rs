@@ -0,0 +1,147 @@
+//! Integration tests for processing the inbox with the client's LLM
+//!
+//! These tests call `process_inbox` as an MCP client whose sampling handler
+//! stands in for the client's model.
+
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus, Storage};
+use mcp_attr::Result as McpResult;
+use mcp_attr::client::{McpClient, McpClientBuilder, McpClientHandler};
+use mcp_attr::schema::{
+    CallToolRequestParams, CallToolResult, CallToolResultContentItem, CreateMessageRequestParams,
+    CreateMessageResult, Role, SamplingMessageContent, TextContent,
+};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+/// A model that answers every request with the same text and keeps the prompts
+struct Model {
+    answer: String,
+    prompts: Arc<Mutex<Vec<String>>>,
+}
+
+impl McpClientHandler for Model {
+    async fn create_message(
+        &self,
+        p: CreateMessageRequestParams,
+    ) -> McpResult<CreateMessageResult> {
+        if let SamplingMessageContent::TextContent(text) = &p.messages[0].content {
+            self.prompts.lock().unwrap().push(text.text.clone());
+        }
+        Ok(CreateMessageResult {
+            content: TextContent::from(self.answer.as_str()).into(),
+            meta: Default::default(),
+            model: "test-model".to_string(),
+            role: Role::Assistant,
+            stop_reason: None,
+        })
+    }
+}
+
+/// A handler whose inbox holds one raw capture, and the @phone context
+fn handler(dir: &TempDir) -> SharedServer {
+    let path = dir.path().join("gtd.toml");
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "phone".to_string(),
+        title: "@phone".to_string(),
+        status: NotaStatus::context,
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "dentist".to_string(),
+        title: "dentist??".to_string(),
+        status: NotaStatus::inbox,
+        ..Default::default()
+    });
+    Storage::new(&path, false).save(&data).unwrap();
+    SharedServer::new(Arc::new(
+        GtdServerHandler::new(path.to_str().unwrap(), false).unwrap(),
+    ))
+}
+
+fn text(result: CallToolResult) -> String {
+    match &result.content[0] {
+        CallToolResultContentItem::TextContent(text) => text.text.clone(),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+fn process_inbox(assist: bool) -> CallToolRequestParams {
+    CallToolRequestParams::new("process_inbox")
+        .with_argument("assist", assist)
+        .unwrap()
+}
+
+// クライアントの LLM の提案が確認用に表示され、データは変更されないことを確認
+#[tokio::test]
+async fn test_assist_shows_model_proposals() {
+    let dir = TempDir::new().unwrap();
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let model = Model {
+        answer: "Sure:\n```json\n{\"title\": \"Schedule a dental check-up\", \"context\": \"phone\", \"next_action\": \"Call the dentist to book an appointment\"}\n```".to_string(),
+        prompts: prompts.clone(),
+    };
+    let server = handler(&dir);
+    let handler = server.0.clone();
+    let client = McpClientBuilder::new()
+        .with_handler(model)
+        .build_with_server(server)
+        .await
+        .unwrap();
+
+    let result = text(client.tools_call(process_inbox(true)).await.unwrap());
+
+    assert!(
+        result.contains("- Title: Schedule a dental check-up"),
+        "{}",
+        result
+    );
+    assert!(result.contains("- Context: phone\n"), "{}", result);
+    assert!(
+        result.contains("- Next action: Call the dentist to book an appointment"),
+        "{}",
+        result
+    );
+    assert!(
+        result.contains("change_status(ids=[\"dentist\"]"),
+        "{}",
+        result
+    );
+    let prompts = prompts.lock().unwrap().clone();
+    assert_eq!(prompts.len(), 1);
+    assert!(prompts[0].contains("dentist??"));
+    assert!(prompts[0].contains("Contexts: phone"));
+    // 提案はユーザーが確認するまで適用しない
+    let data = handler.data.read().await;
+    assert_eq!(data.find_by_id("dentist").unwrap().title, "dentist??");
+}
+
+// 読めない回答は提案なしとして表示し、サンプリング非対応のクライアントには assist をエラーにすることを確認
+#[tokio::test]
+async fn test_unusable_answers_and_clients_without_sampling() {
+    let dir = TempDir::new().unwrap();
+    let model = Model {
+        answer: "I am not sure what this is.".to_string(),
+        prompts: Arc::default(),
+    };
+    let client = McpClientBuilder::new()
+        .with_handler(model)
+        .build_with_server(handler(&dir))
+        .await
+        .unwrap();
+    let result = text(client.tools_call(process_inbox(true)).await.unwrap());
+    assert!(result.contains("[dentist] dentist??"), "{}", result);
+    assert!(result.contains("No proposal: "), "{}", result);
+
+    let client = McpClient::with_server(handler(&dir)).await.unwrap();
+    let error = client.tools_call(process_inbox(true)).await.err().unwrap();
+    assert!(
+        error.to_string().contains("does not support sampling"),
+        "{}",
+        error
+    );
+    let result = text(client.tools_call(process_inbox(false)).await.unwrap());
+    assert!(result.contains("1 of 1 item, oldest first"), "{}", result);
+    assert!(!result.contains("Proposed"), "{}", result);
+}
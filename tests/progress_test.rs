@@ -0,0 +1,142 @@
+//! Integration tests for progress notifications
+//!
+//! These tests call a long tool as an MCP client over HTTP and read the
+//! `notifications/progress` sent on the session's event stream.
+
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, Storage, mcp_http};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+fn request_head(method: &str, session: Option<&str>, body: &str) -> String {
+    let session = session
+        .map(|id| format!("{}: {}\r\n", mcp_http::SESSION_HEADER, id))
+        .unwrap_or_default();
+    format!(
+        "{} /mcp HTTP/1.1\r\nHost: localhost\r\nAccept: application/json, text/event-stream\r\n{}Content-Length: {}\r\n\r\n{}",
+        method,
+        session,
+        body.len(),
+        body
+    )
+}
+
+/// Send one request and return the raw response
+async fn send(addr: std::net::SocketAddr, session: Option<&str>, body: Value) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(request_head("POST", session, &body.to_string()).as_bytes())
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+// merge_file の各段階が進捗通知として増えながら届くことを確認
+#[tokio::test]
+async fn test_merge_file_reports_progress() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gtd.toml");
+    let mut old = GtdData::new();
+    old.add(Nota {
+        id: "call-bob".to_string(),
+        title: "Call Bob".to_string(),
+        ..Default::default()
+    });
+    Storage::new(dir.path().join("old.toml"), false)
+        .save(&old)
+        .unwrap();
+
+    let handler = Arc::new(GtdServerHandler::new(path.to_str().unwrap(), false).unwrap());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (_stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    tokio::spawn(mcp_http::serve(handler, listener, stopped));
+
+    let response = send(
+        addr,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "alice", "version": "1.0" },
+            },
+        }),
+    )
+    .await;
+    let session = response
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case(mcp_http::SESSION_HEADER)
+                .then(|| value.trim().to_string())
+        })
+        .unwrap();
+    send(
+        addr,
+        Some(&session),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    )
+    .await;
+
+    let mut events = TcpStream::connect(addr).await.unwrap();
+    events
+        .write_all(request_head("GET", Some(&session), "").as_bytes())
+        .await
+        .unwrap();
+    let mut events = BufReader::new(events);
+    let mut line = String::new();
+    while line.trim() != "" || line.is_empty() {
+        line.clear();
+        events.read_line(&mut line).await.unwrap();
+    }
+
+    let response = send(
+        addr,
+        Some(&session),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "merge_file", "arguments": { "path": "old.toml" } },
+        }),
+    )
+    .await;
+    assert!(response.contains("Merged"), "{}", response);
+
+    let mut progress = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while progress.len() < 2 {
+            line.clear();
+            events.read_line(&mut line).await.unwrap();
+            if let Some(data) = line.strip_prefix("data: ") {
+                let message: Value = serde_json::from_str(data).unwrap();
+                if message["method"] == "notifications/progress" {
+                    progress.push(message["params"].clone());
+                }
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(progress[0]["progress"], 0.0);
+    assert_eq!(progress[0]["total"], 2.0);
+    assert!(
+        progress[0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Reading"),
+        "{}",
+        progress[0]
+    );
+    assert_eq!(progress[1]["progress"], 1.0);
+    assert_eq!(progress[1]["message"], "Saving merged items");
+}
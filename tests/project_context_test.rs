@@ -0,0 +1,125 @@
+//! Integration tests for project default contexts
+//!
+//! These tests cover tasks without a context inheriting the context of their
+//! project in `list`, `suggest` and `set_current_context`, while tasks with
+//! their own context keep it.
+
+use chrono::NaiveDate;
+use gtd_mcp::suggest::{self, Constraints};
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn nota(id: &str, status: NotaStatus, project: Option<&str>, context: Option<&str>) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status,
+        project: project.map(str::to_string),
+        context: context.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(nota("@lab", NotaStatus::context, None, None));
+    data.add(nota("@home", NotaStatus::context, None, None));
+    data.add(nota("assay", NotaStatus::project, None, Some("@lab")));
+    data.add(nota(
+        "run-gel",
+        NotaStatus::next_action,
+        Some("assay"),
+        None,
+    ));
+    data.add(nota(
+        "write-up",
+        NotaStatus::next_action,
+        Some("assay"),
+        Some("@home"),
+    ));
+    data.add(nota(
+        "water-plants",
+        NotaStatus::next_action,
+        None,
+        Some("@home"),
+    ));
+    data
+}
+
+fn ids(notas: &[&Nota]) -> Vec<String> {
+    notas.iter().map(|n| n.id.clone()).collect()
+}
+
+// コンテキストのないタスクがプロジェクトのコンテキストで絞り込まれ、自身のコンテキストが優先されることを確認
+#[test]
+fn test_list_filtered_inherits_project_context() {
+    let data = sample();
+    let run_gel = data.find_by_id("run-gel").unwrap();
+    assert_eq!(data.effective_context(&run_gel), Some("@lab"));
+
+    assert_eq!(
+        ids(&data.list_filtered(None, None, Some("@lab"))),
+        vec!["assay", "run-gel"]
+    );
+    assert_eq!(
+        ids(&data.list_filtered(Some(NotaStatus::next_action), None, Some("@home"))),
+        vec!["write-up", "water-plants"]
+    );
+    assert_eq!(
+        ids(&data.list_filtered(None, Some("assay"), Some("@lab"))),
+        vec!["run-gel"]
+    );
+
+    // suggest もプロジェクトのコンテキストを使う
+    let constraints = Constraints {
+        context: Some("@home".to_string()),
+        ..Default::default()
+    };
+    let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+    let ranking = suggest::rank(&data, &constraints, today, 10);
+    let suggested: Vec<&str> = ranking
+        .suggestions
+        .iter()
+        .map(|s| s.nota.id.as_str())
+        .collect();
+    assert!(!suggested.contains(&"run-gel"), "{:?}", suggested);
+    assert_eq!(ranking.skipped, 1);
+}
+
+// list の出力に継承したコンテキストが示され、現在のコンテキストの件数にも含まれることを確認
+#[tokio::test]
+async fn test_handlers_show_inherited_context() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    *handler.data.write().await = sample();
+
+    let response = handler
+        .set_current_context(Some("@lab".to_string()))
+        .await
+        .unwrap();
+    assert!(
+        response.contains("(1 next action(s) here, 0 without a context)"),
+        "{}",
+        response
+    );
+
+    let listing = handler
+        .list(
+            Some("next_action".to_string()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(listing.contains("- [run-gel] run gel"), "{}", listing);
+    assert!(
+        listing.contains("  Context: @lab (from project)"),
+        "{}",
+        listing
+    );
+    assert!(!listing.contains("write-up"), "{}", listing);
+}
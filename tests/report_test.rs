@@ -0,0 +1,284 @@
+//! Unit tests for the Markdown report
+//!
+//! These tests verify range parsing, the sections of a rendered report, the
+//! waiting-for report, the done log and the inbox age report.
+
+mod common;
+
+use common::date;
+use gtd_mcp::report::{self, ReportRange};
+use gtd_mcp::{GtdData, Nota, NotaStatus};
+
+fn add(data: &mut GtdData, id: &str, status: NotaStatus, project: Option<&str>, updated: &str) {
+    data.add(Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status,
+        project: project.map(str::to_string),
+        created_at: date("2025-01-01"),
+        updated_at: date(updated),
+        ..Default::default()
+    });
+}
+
+// 期間の指定が今日を基準に解釈されることを確認
+#[test]
+fn test_parse_range() {
+    // 2025-03-12 は水曜日
+    let today = date("2025-03-12");
+    let parse = |s: &str| ReportRange::parse(s, today).unwrap();
+    assert_eq!(parse("week").start, date("2025-03-06"));
+    assert_eq!(parse("week").end, today);
+    assert_eq!(parse("month").start, date("2025-02-11"));
+    assert_eq!(
+        parse("last-week"),
+        ReportRange {
+            start: date("2025-03-03"),
+            end: date("2025-03-09")
+        }
+    );
+    assert_eq!(parse("2025-03-01").start, date("2025-03-01"));
+    assert_eq!(parse("2025-03-01").end, today);
+    assert_eq!(parse("2025-02-01..2025-02-28").end, date("2025-02-28"));
+
+    for (range, expected) in [
+        ("fortnight", "Invalid range 'fortnight'"),
+        ("2025-13-01", "Invalid date '2025-13-01'"),
+        ("2025-03-10..2025-03-01", "is after"),
+    ] {
+        let error = ReportRange::parse(range, today).unwrap_err();
+        assert!(error.contains(expected), "{}", error);
+    }
+}
+
+// 完了項目・成果・進行中のプロジェクト・予定がそれぞれの節に出ることを確認
+#[test]
+fn test_render_sections() {
+    let mut data = GtdData::new();
+    add(
+        &mut data,
+        "website",
+        NotaStatus::project,
+        None,
+        "2025-01-01",
+    );
+    add(&mut data, "taxes", NotaStatus::project, None, "2025-01-01");
+    add(&mut data, "garden", NotaStatus::project, None, "2025-01-01");
+    for id in ["design", "build", "launch"] {
+        add(
+            &mut data,
+            id,
+            NotaStatus::done,
+            Some("website"),
+            "2025-03-10",
+        );
+    }
+    add(
+        &mut data,
+        "blog",
+        NotaStatus::next_action,
+        Some("website"),
+        "2025-03-10",
+    );
+    add(
+        &mut data,
+        "file-taxes",
+        NotaStatus::done,
+        Some("taxes"),
+        "2025-03-11",
+    );
+    add(
+        &mut data,
+        "buy-seeds",
+        NotaStatus::next_action,
+        Some("garden"),
+        "2025-03-01",
+    );
+    add(&mut data, "call-mom", NotaStatus::done, None, "2025-03-08");
+    // 期間外の完了は含めない
+    add(&mut data, "old-task", NotaStatus::done, None, "2025-02-01");
+    data.add(Nota {
+        id: "dentist".to_string(),
+        title: "Dentist".to_string(),
+        status: NotaStatus::calendar,
+        start_date: Some(date("2025-03-14")),
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "far-away".to_string(),
+        title: "Far away".to_string(),
+        status: NotaStatus::calendar,
+        start_date: Some(date("2025-04-30")),
+        ..Default::default()
+    });
+
+    let range = ReportRange::parse("week", date("2025-03-12")).unwrap();
+    let report = report::render(&data, range);
+
+    assert!(report.starts_with("# Report: 2025-03-06 to 2025-03-12\n"));
+    assert!(report.contains("5 items completed, 2 projects with big progress."));
+    assert!(report.contains("- **website**: 3 items completed, 1 still open\n"));
+    assert!(report.contains("- **taxes**: 1 item completed, finished\n"));
+    // プロジェクトごとにまとめ、プロジェクトのない項目は最後
+    let website = report.find("### website").unwrap();
+    let other = report.find("### Other").unwrap();
+    assert!(website < other);
+    assert!(report.contains("- [x] call mom (2025-03-08)\n"));
+    assert!(!report.contains("old task"));
+    assert!(report.contains("- **garden**: 1 open\n"));
+    assert!(report.contains("- **taxes**: 0 open, 1 completed this period (no next action)\n"));
+    assert!(report.contains("## Upcoming (2025-03-13 to 2025-03-19)"));
+    assert!(report.contains("- Fri 2025-03-14 Dentist\n"));
+    assert!(!report.contains("Far away"));
+}
+
+// 何もない期間でも各節が空の説明付きで出ることを確認
+#[test]
+fn test_render_empty_period() {
+    let range = ReportRange::parse("2025-03-01..2025-03-07", date("2025-03-12")).unwrap();
+    let report = report::render(&GtdData::new(), range);
+    assert!(report.contains("0 items completed.\n"));
+    assert!(!report.contains("## Wins"));
+    assert!(report.contains("_Nothing completed in this period._"));
+    assert!(report.contains("_No active projects._"));
+    assert!(report.contains("_Nothing scheduled._"));
+}
+
+// 待ち項目が経過日数の長い順に、プロジェクト名付きで列挙されることを確認
+#[test]
+fn test_render_waiting() {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "launch".to_string(),
+        title: "Product launch".to_string(),
+        status: NotaStatus::project,
+        ..Default::default()
+    });
+    add(
+        &mut data,
+        "quote-from-vendor",
+        NotaStatus::waiting_for,
+        Some("launch"),
+        "2025-03-11",
+    );
+    add(
+        &mut data,
+        "reply-from-alice",
+        NotaStatus::waiting_for,
+        None,
+        "2025-02-10",
+    );
+    add(
+        &mut data,
+        "call-bob",
+        NotaStatus::next_action,
+        None,
+        "2025-01-10",
+    );
+
+    let report = report::render_waiting(&data, date("2025-03-12"));
+    assert!(report.starts_with("# Waiting For: 2 items\n"));
+    let alice = report
+        .find("- **30 days** [reply-from-alice] reply from alice (since 2025-02-10)\n")
+        .unwrap();
+    let vendor = report
+        .find("- **1 day** [quote-from-vendor] quote from vendor (since 2025-03-11, project Product launch)\n")
+        .unwrap();
+    assert!(alice < vendor);
+    assert!(!report.contains("call-bob"));
+
+    let report = report::render_waiting(&GtdData::new(), date("2025-03-12"));
+    assert!(report.contains("_Nothing delegated or pending._"));
+}
+
+// 期間内の完了項目が日ごとに、プロジェクト名付きでまとめられることを確認
+#[test]
+fn test_render_done_log() {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "launch".to_string(),
+        title: "Product launch".to_string(),
+        status: NotaStatus::project,
+        ..Default::default()
+    });
+    add(
+        &mut data,
+        "write-copy",
+        NotaStatus::done,
+        Some("launch"),
+        "2025-03-04",
+    );
+    add(
+        &mut data,
+        "book-venue",
+        NotaStatus::done,
+        None,
+        "2025-03-04",
+    );
+    add(
+        &mut data,
+        "send-invites",
+        NotaStatus::done,
+        Some("launch"),
+        "2025-03-06",
+    );
+    add(&mut data, "old-task", NotaStatus::done, None, "2025-02-20");
+    add(
+        &mut data,
+        "open-task",
+        NotaStatus::next_action,
+        None,
+        "2025-03-05",
+    );
+
+    let range = ReportRange::parse("2025-03-01..2025-03-07", date("2025-03-12")).unwrap();
+    let log = report::render_done_log(&data, range);
+    assert!(log.starts_with("# Done: 2025-03-01 to 2025-03-07\n\n3 items completed on 2 days.\n"));
+    assert!(log.contains(
+        "## Tue 2025-03-04\n\n- [x] book venue [book-venue]\n- [x] write copy [write-copy] - Product launch\n"
+    ));
+    assert!(
+        log.contains("## Thu 2025-03-06\n\n- [x] send invites [send-invites] - Product launch\n")
+    );
+    assert!(!log.contains("old-task"));
+    assert!(!log.contains("open-task"));
+
+    let range = ReportRange::parse("2025-01-01..2025-01-31", date("2025-03-12")).unwrap();
+    assert!(report::render_done_log(&data, range).contains("_Nothing completed in this period._"));
+}
+
+// inbox項目が取り込みからの日数で分類され、古いものが先に示されることを確認
+#[test]
+fn test_render_inbox_age() {
+    let mut data = GtdData::new();
+    for (id, created) in [
+        ("fresh", "2025-03-12"),
+        ("two-days", "2025-03-10"),
+        ("week", "2025-03-05"),
+        ("stale", "2025-02-20"),
+        ("ancient", "2025-01-02"),
+    ] {
+        data.add(Nota {
+            id: id.to_string(),
+            title: id.to_string(),
+            created_at: date(created),
+            updated_at: date("2025-03-12"),
+            ..Default::default()
+        });
+    }
+    add(&mut data, "done", NotaStatus::done, None, "2025-03-01");
+
+    let report = report::render_inbox_age(&data, date("2025-03-12"));
+    assert!(report.starts_with(
+        "# Inbox: 5 items\n\nProcess first:\n- [ancient] ancient (69 days old)\n- [stale] stale (20 days old)\n\n"
+    ), "{}", report);
+    assert!(
+        report.contains("## 0-2 days: 2\n- [two-days] two-days (captured 2025-03-10)\n- [fresh]")
+    );
+    assert!(report.contains("## 3-7 days: 1\n- [week] week"));
+    assert!(report.contains("## Over 7 days: 2\n"));
+    assert!(!report.contains("[done]"));
+
+    let report = report::render_inbox_age(&GtdData::new(), date("2025-03-12"));
+    assert!(report.contains("_Inbox zero._"));
+}
@@ -0,0 +1,106 @@
+//! Integration tests for the per-status and per-project resources
+//!
+//! These tests list and read the `gtd://` resources as an MCP client through
+//! [`SharedServer`].
+
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus, Storage};
+use mcp_attr::client::McpClient;
+use mcp_attr::schema::{ReadResourceRequestParams, ReadResourceResultContentsItem};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn nota(id: &str, status: NotaStatus, project: Option<&str>) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: format!("Title of {}", id),
+        status,
+        project: project.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+async fn client(dir: &TempDir) -> McpClient {
+    let path = dir.path().join("gtd.toml");
+    let mut data = GtdData::new();
+    data.add(nota("launch", NotaStatus::project, None));
+    data.add(nota("write-copy", NotaStatus::next_action, Some("launch")));
+    data.add(nota("book-venue", NotaStatus::waiting_for, Some("launch")));
+    data.add(nota("call-bob", NotaStatus::next_action, None));
+    Storage::new(&path, false).save(&data).unwrap();
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    McpClient::with_server(SharedServer::new(Arc::new(handler)))
+        .await
+        .unwrap()
+}
+
+async fn read(client: &McpClient, uri: &str) -> String {
+    let result = client
+        .resources_read(ReadResourceRequestParams::new(uri))
+        .await
+        .unwrap();
+    match &result.contents[0] {
+        ReadResourceResultContentsItem::TextResourceContents(text) => text.text.clone(),
+        other => panic!("unexpected contents: {:?}", other),
+    }
+}
+
+// テンプレートと、ステータスごと・プロジェクトごとのリソースが一覧されることを確認
+#[tokio::test]
+async fn test_resources_are_listed() {
+    let dir = TempDir::new().unwrap();
+    let client = client(&dir).await;
+
+    let templates = client.resources_templates_list(None).await.unwrap();
+    let templates: Vec<&str> = templates
+        .resource_templates
+        .iter()
+        .map(|template| template.uri_template.as_str())
+        .collect();
+    assert!(
+        templates.contains(&"gtd://list/{status}"),
+        "{:?}",
+        templates
+    );
+    assert!(
+        templates.contains(&"gtd://project/{id}/tasks"),
+        "{:?}",
+        templates
+    );
+
+    let resources = client.resources_list(None).await.unwrap().resources;
+    let uris: Vec<&str> = resources.iter().map(|r| r.uri.as_str()).collect();
+    assert!(uris.contains(&"gtd://list/inbox"), "{:?}", uris);
+    assert!(uris.contains(&"gtd://list/trash"), "{:?}", uris);
+    assert!(uris.contains(&"gtd://project/launch/tasks"), "{:?}", uris);
+}
+
+// ステータスやプロジェクトのリソースはその項目だけを返し、存在しないものはエラーになることを確認
+#[tokio::test]
+async fn test_read_slices() {
+    let dir = TempDir::new().unwrap();
+    let client = client(&dir).await;
+
+    let next = read(&client, "gtd://list/next_action").await;
+    assert!(
+        next.contains("write-copy") && next.contains("call-bob"),
+        "{}",
+        next
+    );
+    assert!(!next.contains("book-venue"), "{}", next);
+
+    let launch = read(&client, "gtd://project/launch/tasks").await;
+    assert!(
+        launch.contains("write-copy") && launch.contains("book-venue"),
+        "{}",
+        launch
+    );
+    assert!(!launch.contains("call-bob"), "{}", launch);
+
+    for uri in ["gtd://list/maybe", "gtd://project/call-bob/tasks"] {
+        let result = client
+            .resources_read(ReadResourceRequestParams::new(uri))
+            .await;
+        assert!(result.is_err(), "{}", uri);
+    }
+}
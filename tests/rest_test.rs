@@ -0,0 +1,317 @@
+//! Integration tests for the REST/JSON API
+//!
+//! These tests run the API on a local port and send raw HTTP requests to it,
+//! verifying item reads, tool calls through the shared handler, the error
+//! responses and the requests refused as coming from browsers or other hosts.
+
+use gtd_mcp::{GtdServerHandler, NotaStatus, rest};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+/// Start the API on a free port
+async fn start(dir: &TempDir) -> (Arc<GtdServerHandler>, SocketAddr, oneshot::Sender<()>) {
+    start_with_tools(dir, &[]).await
+}
+
+/// Start the API on a free port, allowing only `tools` (empty: all)
+async fn start_with_tools(
+    dir: &TempDir,
+    tools: &[&str],
+) -> (Arc<GtdServerHandler>, SocketAddr, oneshot::Sender<()>) {
+    let path = dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    serve(handler, tools).await
+}
+
+/// Serve `handler` on a free port, allowing only `tools` (empty: all)
+async fn serve(
+    handler: GtdServerHandler,
+    tools: &[&str],
+) -> (Arc<GtdServerHandler>, SocketAddr, oneshot::Sender<()>) {
+    let handler = Arc::new(handler);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stop, stopped) = oneshot::channel::<()>();
+    let tools = tools.iter().map(|tool| tool.to_string()).collect();
+    tokio::spawn(rest::serve(handler.clone(), listener, tools, stopped));
+    (handler, addr, stop)
+}
+
+/// Send one request and return the status and JSON body of the response
+async fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let headers = "Host: localhost\r\nContent-Type: application/json\r\n";
+    request_with_headers(addr, method, path, headers, body).await
+}
+
+/// Send one request with the given header lines (each ending in CRLF)
+async fn request_with_headers(
+    addr: SocketAddr,
+    method: &str,
+    path: &str,
+    headers: &str,
+    body: &str,
+) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "{} {} HTTP/1.1\r\n{}Content-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        headers,
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+// ツール呼び出しで追加した項目が一覧・個別取得で JSON として返ることを確認
+#[tokio::test]
+async fn test_tool_call_and_item_reads() {
+    let dir = TempDir::new().unwrap();
+    let (handler, addr, _stop) = start(&dir).await;
+
+    let (status, body) = request(
+        addr,
+        "POST",
+        "/api/tools/inbox",
+        r#"{"id": "call-bob", "title": "Call Bob", "status": "next_action"}"#,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert!(body["result"].as_str().unwrap().contains("call-bob"));
+    // MCP セッションと同じハンドラーのデータが更新される
+    assert!(handler.data.read().await.contains_id("call-bob"));
+
+    let (status, body) = request(addr, "GET", "/api/items?status=next_action", "").await;
+    assert_eq!(status, 200);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["title"], "Call Bob");
+
+    let (status, body) = request(addr, "GET", "/api/items/call-bob", "").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "next_action");
+
+    let (status, body) = request(addr, "GET", "/api/items?status=inbox", "").await;
+    assert_eq!(status, 200);
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+// ツール一覧に引数スキーマが含まれ、ステータス変更もファイルに保存されることを確認
+#[tokio::test]
+async fn test_tools_list_and_status_change() {
+    let dir = TempDir::new().unwrap();
+    let (handler, addr, stop) = start(&dir).await;
+
+    let (status, body) = request(addr, "GET", "/api/tools", "").await;
+    assert_eq!(status, 200);
+    let tools = body.as_array().unwrap();
+    assert!(tools.iter().any(|tool| tool["name"] == "change_status"));
+    assert!(tools.iter().all(|tool| tool["inputSchema"].is_object()));
+
+    request(
+        addr,
+        "POST",
+        "/api/tools/inbox",
+        r#"{"id": "buy-milk", "title": "Buy milk", "status": "inbox"}"#,
+    )
+    .await;
+    let (status, _) = request(
+        addr,
+        "POST",
+        "/api/tools/change_status",
+        r#"{"ids": ["buy-milk"], "new_status": "done"}"#,
+    )
+    .await;
+    assert_eq!(status, 200);
+    stop.send(()).unwrap();
+
+    let saved = std::fs::read_to_string(dir.path().join("gtd.toml")).unwrap();
+    assert!(saved.contains("[[done]]"));
+    let data = handler.data.read().await;
+    assert_eq!(
+        data.find_by_id("buy-milk").unwrap().status,
+        NotaStatus::done
+    );
+}
+
+// 不明なパス・ツール、不正な引数、ツールのエラーがエラー応答になることを確認
+#[tokio::test]
+async fn test_error_responses() {
+    let dir = TempDir::new().unwrap();
+    let (_handler, addr, _stop) = start(&dir).await;
+
+    let (status, body) = request(addr, "GET", "/api/items/missing", "").await;
+    assert_eq!(status, 404);
+    assert!(body["error"].as_str().unwrap().contains("missing"));
+
+    let (status, _) = request(addr, "GET", "/elsewhere", "").await;
+    assert_eq!(status, 404);
+
+    let (status, _) = request(addr, "DELETE", "/api/items", "").await;
+    assert_eq!(status, 405);
+
+    let (status, body) = request(addr, "POST", "/api/tools/fly", "{}").await;
+    assert_eq!(status, 404);
+    assert!(body["error"].as_str().unwrap().contains("fly"));
+
+    let (status, _) = request(addr, "POST", "/api/tools/inbox", "[1, 2]").await;
+    assert_eq!(status, 400);
+
+    let (status, _) = request(addr, "GET", "/api/items?status=urgent", "").await;
+    assert_eq!(status, 400);
+
+    // 存在しない ID のステータス変更はツールのエラーとして返る
+    let (status, body) = request(
+        addr,
+        "POST",
+        "/api/tools/change_status",
+        r#"{"ids": ["nothing-here"], "new_status": "done"}"#,
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert!(!body["error"].as_str().unwrap().is_empty());
+}
+
+// 他サイトの Origin や他ホスト名の Host を持つリクエストが 403 で拒否されることを確認
+#[tokio::test]
+async fn test_foreign_origin_and_host_are_refused() {
+    let dir = TempDir::new().unwrap();
+    let (handler, addr, _stop) = start(&dir).await;
+    let body = r#"{"id": "call-bob", "title": "Call Bob", "status": "inbox"}"#;
+
+    let headers = "Host: localhost:7878\r\nOrigin: https://evil.example\r\nContent-Type: application/json\r\n";
+    let (status, body_json) =
+        request_with_headers(addr, "POST", "/api/tools/inbox", headers, body).await;
+    assert_eq!(status, 403);
+    assert!(
+        body_json["error"]
+            .as_str()
+            .unwrap()
+            .contains("evil.example")
+    );
+    let headers = "Host: localhost\r\nOrigin: null\r\n";
+    let (status, _) = request_with_headers(addr, "GET", "/api/items", headers, "").await;
+    assert_eq!(status, 403);
+
+    // DNS リバインディングでは Host が攻撃者のホスト名になる
+    let headers = "Host: rebind.example:7878\r\nContent-Type: application/json\r\n";
+    let (status, _) = request_with_headers(addr, "POST", "/api/tools/inbox", headers, body).await;
+    assert_eq!(status, 403);
+    let (status, _) =
+        request_with_headers(addr, "GET", "/api/items", "Host: 10.0.0.5\r\n", "").await;
+    assert_eq!(status, 403);
+    assert!(!handler.data.read().await.contains_id("call-bob"));
+
+    // ループバックの Origin・Host と、ヘッダーのないスクリプトは通る
+    let headers =
+        "Host: [::1]:7878\r\nOrigin: http://localhost:3000\r\nContent-Type: application/json\r\n";
+    let (status, _) = request_with_headers(addr, "POST", "/api/tools/inbox", headers, body).await;
+    assert_eq!(status, 200);
+    let headers = "Host: 127.0.0.1:7878\r\nOrigin: http://127.0.0.1:7878\r\n";
+    let (status, _) = request_with_headers(addr, "GET", "/api/items", headers, "").await;
+    assert_eq!(status, 200);
+    let (status, _) = request_with_headers(addr, "GET", "/api/items", "", "").await;
+    assert_eq!(status, 200);
+}
+
+// ツール呼び出しは Content-Type が application/json でないと 415 で拒否されることを確認
+#[tokio::test]
+async fn test_tool_calls_require_json_content_type() {
+    let dir = TempDir::new().unwrap();
+    let (handler, addr, _stop) = start(&dir).await;
+    let body = r#"{"id": "call-bob", "title": "Call Bob", "status": "inbox"}"#;
+
+    // フォームやテキストはブラウザーが他サイトへ送れる形式
+    for headers in [
+        "Host: localhost\r\nContent-Type: text/plain\r\n",
+        "Host: localhost\r\nContent-Type: application/x-www-form-urlencoded\r\n",
+        "Host: localhost\r\n",
+    ] {
+        let (status, _) =
+            request_with_headers(addr, "POST", "/api/tools/inbox", headers, body).await;
+        assert_eq!(status, 415, "{}", headers);
+    }
+    assert!(!handler.data.read().await.contains_id("call-bob"));
+
+    let headers = "Host: localhost\r\nContent-Type: Application/JSON; charset=utf-8\r\n";
+    let (status, _) = request_with_headers(addr, "POST", "/api/tools/inbox", headers, body).await;
+    assert_eq!(status, 200);
+    assert!(handler.data.read().await.contains_id("call-bob"));
+}
+
+// 許可したツールだけが一覧に出て呼び出せ、それ以外は 403 になることを確認
+#[tokio::test]
+async fn test_allowed_tools() {
+    let dir = TempDir::new().unwrap();
+    let (handler, addr, _stop) = start_with_tools(&dir, &["list", "get"]).await;
+
+    let (status, body) = request(addr, "GET", "/api/tools", "").await;
+    assert_eq!(status, 200);
+    let mut names: Vec<_> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["get", "list"]);
+
+    let (status, body) = request(
+        addr,
+        "POST",
+        "/api/tools/inbox",
+        r#"{"id": "call-bob", "title": "Call Bob", "status": "inbox"}"#,
+    )
+    .await;
+    assert_eq!(status, 403);
+    assert!(body["error"].as_str().unwrap().contains("inbox"));
+    assert!(!handler.data.read().await.contains_id("call-bob"));
+
+    let (status, _) = request(addr, "POST", "/api/tools/list", "{}").await;
+    assert_eq!(status, 200);
+    // 存在しないツールは許可の有無にかかわらず 404
+    let (status, _) = request(addr, "POST", "/api/tools/fly", "{}").await;
+    assert_eq!(status, 404);
+}
+
+// 読み取り専用のサーバーでは REST でも読み取りのツールだけが一覧に出て呼び出せることを確認
+#[tokio::test]
+async fn test_read_only_server() {
+    let dir = TempDir::new().unwrap();
+    let handler = GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .read_only(true)
+        .build()
+        .unwrap();
+    let (handler, addr, _stop) = serve(handler, &[]).await;
+
+    let (_, body) = request(addr, "GET", "/api/tools", "").await;
+    let names: Vec<_> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"list"));
+    assert!(!names.contains(&"inbox"));
+    assert!(!names.contains(&"empty_trash"));
+
+    let (status, body) = request(
+        addr,
+        "POST",
+        "/api/tools/inbox",
+        r#"{"id": "call-bob", "title": "Call Bob", "status": "inbox"}"#,
+    )
+    .await;
+    assert_eq!(status, 404, "{}", body);
+    assert!(!handler.data.read().await.contains_id("call-bob"));
+}
@@ -0,0 +1,184 @@
+//! Integration tests for restoring trashed items
+//!
+//! These tests check that trashing records the previous status, that `restore`
+//! puts items back where they were (or in inbox when nothing was recorded), and
+//! that the previous status survives a save and reload.
+
+use chrono::NaiveDate;
+use gtd_mcp::{GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn item(id: &str, status: NotaStatus) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status,
+        ..Default::default()
+    }
+}
+
+/// Date the tests move items on
+fn day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2030, 1, 10).unwrap()
+}
+
+fn handler(temp_file: &NamedTempFile) -> GtdServerHandler {
+    GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap()
+}
+
+// ゴミ箱に入れると元のステータスが記録され、出すと消えることを確認
+#[test]
+fn test_set_status_records_previous_status() {
+    let mut nota = item("call-bank", NotaStatus::waiting_for);
+    nota.set_status(NotaStatus::trash, day());
+    assert_eq!(nota.previous_status, Some(NotaStatus::waiting_for));
+
+    // ゴミ箱の中で再度ゴミ箱に入れても元のステータスは保たれる
+    nota.set_status(NotaStatus::trash, day());
+    assert_eq!(nota.previous_status, Some(NotaStatus::waiting_for));
+
+    nota.set_status(NotaStatus::next_action, day());
+    assert_eq!(nota.previous_status, None);
+}
+
+// change_status でゴミ箱に入れた項目（連鎖分を含む）が元のステータスに戻ることを確認
+#[tokio::test]
+async fn test_restore_after_change_status() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = handler(&temp_file);
+    {
+        let mut data = handler.data.write().await;
+        data.add(item("website", NotaStatus::project));
+        data.add(Nota {
+            project: Some("website".to_string()),
+            ..item("draft-copy", NotaStatus::waiting_for)
+        });
+        data.add(item("call-bank", NotaStatus::someday));
+    }
+    handler
+        .change_status(
+            vec!["website".to_string(), "call-bank".to_string()],
+            "trash".to_string(),
+            None,
+            Some("trash".to_string()),
+        )
+        .await
+        .unwrap();
+
+    let response = handler
+        .restore(vec![
+            "website".to_string(),
+            "draft-copy".to_string(),
+            "call-bank".to_string(),
+        ])
+        .await
+        .unwrap();
+    assert!(response.starts_with("Restored 3 items:"), "{}", response);
+
+    let data = handler.data.read().await;
+    for (id, status) in [
+        ("website", NotaStatus::project),
+        ("draft-copy", NotaStatus::waiting_for),
+        ("call-bank", NotaStatus::someday),
+    ] {
+        let nota = data.find_by_id(id).unwrap();
+        assert_eq!(nota.status, status, "{}", id);
+        assert_eq!(nota.previous_status, None, "{}", id);
+    }
+}
+
+// 元のステータスが記録されていない項目は inbox に戻り、その旨が示されることを確認
+#[tokio::test]
+async fn test_restore_without_previous_status() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = handler(&temp_file);
+    handler
+        .data
+        .write()
+        .await
+        .add(item("old-junk", NotaStatus::trash));
+
+    let response = handler.restore(vec!["old-junk".to_string()]).await.unwrap();
+    assert!(
+        response.contains("old-junk: trash → inbox (previous status unknown)"),
+        "{}",
+        response
+    );
+}
+
+// ゴミ箱にない項目・存在しない項目は失敗として報告されることを確認
+#[tokio::test]
+async fn test_restore_failures() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = handler(&temp_file);
+    {
+        let mut data = handler.data.write().await;
+        data.add(item("active", NotaStatus::next_action));
+        let mut trashed = item("trashed", NotaStatus::later);
+        trashed.set_status(NotaStatus::trash, day());
+        data.add(trashed);
+    }
+
+    let response = handler
+        .restore(vec![
+            "trashed".to_string(),
+            "active".to_string(),
+            "missing".to_string(),
+        ])
+        .await
+        .unwrap();
+    assert!(response.contains("trashed: trash → later"), "{}", response);
+    assert!(
+        response.contains("active: not in trash (status: next_action)"),
+        "{}",
+        response
+    );
+    assert!(response.contains("missing: not found"), "{}", response);
+
+    let message = handler
+        .restore(vec!["active".to_string()])
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(message.contains("Failed to restore 1 item"), "{}", message);
+}
+
+// 元のステータスが保存・再読み込み後も保たれることを確認
+#[tokio::test]
+async fn test_previous_status_persists() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap().to_string();
+    let handler = handler(&temp_file);
+    handler.data.write().await.add(Nota {
+        start_date: NaiveDate::from_ymd_opt(2026, 1, 5),
+        ..item("review-q3", NotaStatus::calendar)
+    });
+    handler
+        .change_status(
+            vec!["review-q3".to_string()],
+            "trash".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        content.contains("previous_status = \"calendar\""),
+        "{}",
+        content
+    );
+
+    let reloaded = GtdServerHandler::new(&path, false).unwrap();
+    let response = reloaded
+        .restore(vec!["review-q3".to_string()])
+        .await
+        .unwrap();
+    assert!(
+        response.contains("review-q3: trash → calendar"),
+        "{}",
+        response
+    );
+}
@@ -0,0 +1,104 @@
+//! Integration tests for the trash retention policy
+//!
+//! These tests cover which trash items are old enough to purge, keeping items
+//! that are still linked, and purging them as part of a regular save.
+
+use chrono::{Duration, NaiveDate};
+use gtd_mcp::retention;
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus, local_date_today};
+use tempfile::NamedTempFile;
+
+fn trashed(id: &str, since: NaiveDate) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status: NotaStatus::trash,
+        created_at: since,
+        updated_at: since,
+        ..Default::default()
+    }
+}
+
+fn sample(today: NaiveDate) -> GtdData {
+    let mut data = GtdData::new();
+    data.add(trashed("old-idea", today - Duration::days(40)));
+    data.add(trashed("typo", today - Duration::days(3)));
+    data.add(Nota {
+        status_since: Some(today - Duration::days(2)),
+        ..trashed("reopened", today - Duration::days(90))
+    });
+    data.add(trashed("old-project", today - Duration::days(60)));
+    data.add(Nota {
+        project: Some("old-project".to_string()),
+        ..trashed("old-task", today - Duration::days(60))
+    });
+    data.add(trashed("linked-project", today - Duration::days(60)));
+    data.add(Nota {
+        id: "live-task".to_string(),
+        title: "live task".to_string(),
+        status: NotaStatus::next_action,
+        project: Some("linked-project".to_string()),
+        ..Default::default()
+    });
+    data
+}
+
+// 日数を超えたゴミ箱の項目だけが対象になり、残る項目から参照されている項目は残されることを確認
+#[test]
+fn test_expired_trash() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+    let data = sample(today);
+    assert_eq!(
+        retention::expired(&data, 30, today),
+        vec!["old-idea", "old-project", "old-task"]
+    );
+    assert_eq!(retention::expired(&data, 100, today), Vec::<String>::new());
+
+    let mut data = data;
+    let purged = retention::purge(&mut data, 30, today);
+    assert_eq!(
+        retention::commit_note(&purged, 30).unwrap(),
+        "\n\nPurged 3 trash item(s) older than 30 days: old-idea, old-project, old-task"
+    );
+    assert!(data.find_by_id("old-idea").is_none());
+    assert!(data.find_by_id("linked-project").is_some());
+    assert!(retention::commit_note(&[], 30).is_none());
+}
+
+// 保存のたびに古いゴミ箱の項目が削除され、設定しなければ残ることを確認
+#[tokio::test]
+async fn test_save_purges_old_trash() {
+    let today = local_date_today();
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap().to_string();
+    let handler = GtdServerHandler::new(&path, false).unwrap();
+    *handler.data.write().await = sample(today);
+    handler
+        .change_status(
+            vec!["live-task".to_string()],
+            "someday".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("old-idea"), "{}", content);
+
+    let handler = GtdServerHandler::new(&path, false)
+        .unwrap()
+        .with_trash_retention(30);
+    handler
+        .change_status(
+            vec!["live-task".to_string()],
+            "next_action".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(!content.contains("old-idea"), "{}", content);
+    assert!(content.contains("typo"), "{}", content);
+    assert!(content.contains("linked-project"), "{}", content);
+}
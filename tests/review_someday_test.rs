@@ -0,0 +1,137 @@
+//! Integration tests for the someday/maybe review
+//!
+//! These tests cover the listing of stale someday items and the batch
+//! decisions of the `review_someday` tool.
+
+use chrono::{Duration, NaiveDate};
+use gtd_mcp::gtd::local_date_today;
+use gtd_mcp::{GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn someday(id: &str, updated_at: NaiveDate) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status: NotaStatus::someday,
+        created_at: updated_at,
+        updated_at,
+        ..Default::default()
+    }
+}
+
+async fn sample_handler(temp_file: &NamedTempFile) -> GtdServerHandler {
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    let today = local_date_today();
+    let mut data = handler.data.write().await;
+    data.add(someday("learn-piano", today - Duration::days(90)));
+    data.add(someday("visit-iceland", today - Duration::days(45)));
+    data.add(someday("build-shed", today - Duration::days(40)));
+    data.add(someday("read-proust", today - Duration::days(3)));
+    data.add(Nota {
+        status: NotaStatus::next_action,
+        ..someday("call-bob", today - Duration::days(60))
+    });
+    drop(data);
+    handler
+}
+
+// 一定期間更新のないsomeday項目だけが古い順に列挙されることを確認
+#[tokio::test]
+async fn test_review_someday_lists_stale_items() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    let response = handler
+        .review_someday(None, None, None, None)
+        .await
+        .unwrap();
+    assert!(
+        response.contains("untouched for 30+ days (3 of 4)"),
+        "{}",
+        response
+    );
+    let piano = response.find("[learn-piano]").unwrap();
+    let iceland = response.find("[visit-iceland]").unwrap();
+    let shed = response.find("[build-shed]").unwrap();
+    assert!(piano < iceland && iceland < shed);
+    assert!(response.contains("(90 days ago)"));
+    assert!(!response.contains("read-proust"));
+    assert!(!response.contains("call-bob"));
+
+    let response = handler
+        .review_someday(Some(100), None, None, None)
+        .await
+        .unwrap();
+    assert!(response.starts_with("No someday items untouched for 100+ days (4 someday items"));
+}
+
+// 日付の範囲を超える経過日数でもパニックせず、該当なしとして扱うことを確認
+#[tokio::test]
+async fn test_review_someday_huge_threshold() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    let response = handler
+        .review_someday(Some(u32::MAX), None, None, None)
+        .await
+        .unwrap();
+    assert!(
+        response.starts_with(&format!(
+            "No someday items untouched for {}+ days",
+            u32::MAX
+        )),
+        "{}",
+        response
+    );
+}
+
+// 昇格・保留・削除の判断がまとめて適用され、対象外のIDは失敗として報告されることを確認
+#[tokio::test]
+async fn test_review_someday_applies_decisions() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    let response = handler
+        .review_someday(
+            None,
+            Some(vec!["learn-piano".to_string()]),
+            Some(vec!["visit-iceland".to_string()]),
+            Some(vec!["build-shed".to_string(), "call-bob".to_string()]),
+        )
+        .await
+        .unwrap();
+    assert!(response.contains("Promoted to next_action: learn-piano"));
+    assert!(response.contains("Kept in someday: visit-iceland"));
+    assert!(response.contains("Moved to trash: build-shed"));
+    assert!(response.contains("call-bob: not a someday item"));
+    assert!(response.contains("No someday items untouched for 30+ days (2 someday items"));
+
+    let data = handler.data.read().await;
+    assert_eq!(
+        data.find_by_id("learn-piano").unwrap().status,
+        NotaStatus::next_action
+    );
+    let kept = data.find_by_id("visit-iceland").unwrap();
+    assert_eq!(kept.status, NotaStatus::someday);
+    assert_eq!(kept.updated_at, local_date_today());
+    assert_eq!(
+        data.find_by_id("build-shed").unwrap().status,
+        NotaStatus::trash
+    );
+    assert_eq!(
+        data.find_by_id("call-bob").unwrap().status,
+        NotaStatus::next_action
+    );
+    drop(data);
+
+    // 同じIDを複数の判断に指定するとエラー
+    let result = handler
+        .review_someday(
+            None,
+            Some(vec!["read-proust".to_string()]),
+            None,
+            Some(vec!["read-proust".to_string()]),
+        )
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,74 @@
+//! Integration tests for taking the data file from the client's workspace roots
+//!
+//! These tests serve a handler started without a data file through
+//! [`SharedServer`] and call a tool from clients with and without roots.
+
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::{GtdServerHandler, Storage};
+use mcp_attr::client::{McpClient, McpClientBuilder};
+use mcp_attr::schema::{CallToolRequestParams, Root};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// A handler waiting for the roots, with a placeholder file in `dir`
+fn waiting_handler(dir: &Path) -> GtdServerHandler {
+    GtdServerHandler::with_storage(Storage::new(dir.join("gtd.toml"), false))
+        .unwrap()
+        .with_roots(Arc::new(|path| Ok(Storage::new(path, false))))
+}
+
+fn inbox_call(id: &str) -> CallToolRequestParams {
+    CallToolRequestParams::new("inbox")
+        .with_argument("id", id)
+        .unwrap()
+        .with_argument("title", "Call Bob")
+        .unwrap()
+        .with_argument("status", "inbox")
+        .unwrap()
+}
+
+// ルートの gtd.toml を作成してそこへ保存し、プレースホルダには書き込まないことを確認
+#[tokio::test]
+async fn test_data_file_is_created_in_root() {
+    let cwd = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    let handler = Arc::new(waiting_handler(cwd.path()));
+    let root = Root::from_file_path(workspace.path()).unwrap();
+    let client = McpClientBuilder::new()
+        .with_roots(vec![root])
+        .build_with_server(SharedServer::new(handler.clone()))
+        .await
+        .unwrap();
+
+    client.tools_call(inbox_call("call-bob")).await.unwrap();
+
+    let path = workspace.path().join("gtd.toml");
+    assert_eq!(handler.storage().file_path(), path);
+    assert!(!handler.waits_for_roots().await);
+    let data = Storage::new(&path, false).load().unwrap();
+    assert!(data.find_by_id("call-bob").is_some());
+    // 初期コンテキスト付きで作成される
+    assert!(!data.contexts().is_empty());
+    assert!(!cwd.path().join("gtd.toml").exists());
+}
+
+// ルートを共有しないクライアントにはデータファイルがない旨のエラーを返すことを確認
+#[tokio::test]
+async fn test_client_without_roots_gets_error() {
+    let cwd = TempDir::new().unwrap();
+    let handler = Arc::new(waiting_handler(cwd.path()));
+    let client = McpClient::with_server(SharedServer::new(handler.clone()))
+        .await
+        .unwrap();
+
+    let error = client
+        .tools_call(inbox_call("call-bob"))
+        .await
+        .err()
+        .unwrap();
+
+    assert!(error.to_string().contains("No data file"), "{}", error);
+    assert!(handler.waits_for_roots().await);
+    assert!(!cwd.path().join("gtd.toml").exists());
+}
@@ -0,0 +1,276 @@
+//! Integration tests for moving dated items
+//!
+//! These tests cover rescheduling calendar items in bulk, postponing a
+//! single item, and skipping one occurrence of or pausing a recurring item.
+
+mod common;
+
+use chrono::{Duration, NaiveDate};
+use common::date;
+use gtd_mcp::gtd::{RecurrencePattern, local_date_today};
+use gtd_mcp::{GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn calendar(id: &str, start: &str) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status: NotaStatus::calendar,
+        start_date: Some(date(start)),
+        ..Default::default()
+    }
+}
+
+async fn sample_handler(temp_file: &NamedTempFile) -> GtdServerHandler {
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    let mut data = handler.data.write().await;
+    data.add(calendar("hotel-checkin", "2025-06-02"));
+    data.add(calendar("museum-tour", "2025-06-04"));
+    data.add(calendar("dentist", "2025-06-10"));
+    data.add(Nota {
+        status: NotaStatus::next_action,
+        ..calendar("pack-bags", "2025-06-01")
+    });
+    drop(data);
+    handler
+}
+
+fn ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+fn start_date(handler: &GtdServerHandler, id: &str) -> Option<NaiveDate> {
+    handler
+        .data
+        .try_read()
+        .unwrap()
+        .find_by_id(id)
+        .unwrap()
+        .start_date
+}
+
+// 複数のカレンダー項目を日数でずらす・日付を指定して移動できることを確認
+#[tokio::test]
+async fn test_reschedule_shifts_and_moves_items() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    let response = handler
+        .reschedule(ids(&["hotel-checkin", "museum-tour"]), None, Some(7))
+        .await
+        .unwrap();
+    assert!(response.starts_with("Rescheduled 2 calendar item(s):\n"));
+    assert!(response.contains("- [hotel-checkin] hotel checkin: 2025-06-02 → 2025-06-09"));
+    assert_eq!(
+        start_date(&handler, "museum-tour"),
+        Some(date("2025-06-11"))
+    );
+    assert_eq!(start_date(&handler, "dentist"), Some(date("2025-06-10")));
+
+    handler
+        .reschedule(ids(&["dentist"]), Some("2025-07-01".to_string()), None)
+        .await
+        .unwrap();
+    assert_eq!(start_date(&handler, "dentist"), Some(date("2025-07-01")));
+}
+
+// 不正な指定や対象外の項目が含まれると何も移動しないことを確認
+#[tokio::test]
+async fn test_reschedule_is_all_or_nothing() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    let err = handler
+        .reschedule(
+            ids(&["hotel-checkin", "pack-bags", "missing"]),
+            None,
+            Some(-1),
+        )
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(err.contains("pack-bags: not a calendar item"), "{}", err);
+    assert!(err.contains("missing: not found"), "{}", err);
+    assert_eq!(
+        start_date(&handler, "hotel-checkin"),
+        Some(date("2025-06-02"))
+    );
+
+    // 日付と日数はどちらか一方だけ
+    let both = handler
+        .reschedule(ids(&["dentist"]), Some("2025-07-01".to_string()), Some(1))
+        .await;
+    assert!(both.is_err());
+    assert!(
+        handler
+            .reschedule(ids(&["dentist"]), None, None)
+            .await
+            .is_err()
+    );
+    let bad_date = handler
+        .reschedule(ids(&["dentist"]), Some("July 1".to_string()), None)
+        .await;
+    assert!(bad_date.is_err());
+}
+
+// 開始日を指定日数後ろにずらし、開始日がなければ今日から数えることを確認
+#[tokio::test]
+async fn test_postpone_moves_start_date() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+
+    let response = handler.postpone("dentist".to_string(), 3).await.unwrap();
+    assert_eq!(
+        response,
+        "Postponed [dentist] dentist by 3 day(s): 2025-06-10 → 2025-06-13"
+    );
+    assert_eq!(start_date(&handler, "dentist"), Some(date("2025-06-13")));
+
+    let mut data = handler.data.write().await;
+    data.add(Nota {
+        id: "renew-passport".to_string(),
+        title: "Renew passport".to_string(),
+        status: NotaStatus::someday,
+        ..Default::default()
+    });
+    drop(data);
+    let response = handler
+        .postpone("renew-passport".to_string(), 14)
+        .await
+        .unwrap();
+    let expected = local_date_today() + Duration::days(14);
+    assert!(response.ends_with(&format!("start date set to {}", expected)));
+    assert_eq!(start_date(&handler, "renew-passport"), Some(expected));
+
+    assert!(handler.postpone("dentist".to_string(), 0).await.is_err());
+    assert!(handler.postpone("missing".to_string(), 1).await.is_err());
+}
+
+// 日付の範囲を超える延期はパニックせずエラーになり、項目は変わらないことを確認
+#[tokio::test]
+async fn test_postpone_out_of_range() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+    handler.data.write().await.add(Nota {
+        start_date: Some(NaiveDate::MAX - Duration::days(30)),
+        ..calendar("far-future", "2025-01-01")
+    });
+
+    let error = format!(
+        "{:?}",
+        handler
+            .postpone("far-future".to_string(), 31)
+            .await
+            .unwrap_err()
+    );
+    assert!(error.contains("out of range"), "{}", error);
+    assert_eq!(
+        start_date(&handler, "far-future"),
+        Some(NaiveDate::MAX - Duration::days(30))
+    );
+    assert!(
+        handler
+            .postpone("dentist".to_string(), u32::MAX)
+            .await
+            .is_err()
+    );
+}
+
+// 繰り返し項目の次の発生日に進み、完了項目や新しい項目は作られないことを確認
+#[tokio::test]
+async fn test_skip_occurrence_advances_series() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+    let mut data = handler.data.write().await;
+    data.add(Nota {
+        recurrence_pattern: Some(RecurrencePattern::weekly),
+        recurrence_config: Some("Monday,Thursday".to_string()),
+        ..calendar("take-out-trash", "2025-06-02")
+    });
+    drop(data);
+
+    let response = handler
+        .skip_occurrence("take-out-trash".to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        "Skipped the 2025-06-02 occurrence of [take-out-trash] take out trash. Next occurrence: 2025-06-05"
+    );
+    handler
+        .skip_occurrence("take-out-trash".to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        start_date(&handler, "take-out-trash"),
+        Some(date("2025-06-09"))
+    );
+    let data = handler.data.read().await;
+    assert_eq!(data.iter().count(), 5);
+    assert_eq!(
+        data.find_by_id("take-out-trash").unwrap().status,
+        NotaStatus::calendar
+    );
+    drop(data);
+
+    let err = handler
+        .skip_occurrence("dentist".to_string())
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(err.contains("not recurring"), "{}", err);
+}
+
+// 一時停止中の繰り返し項目は完了しても次の発生分を作らず、ティックラーでも昇格しないことを確認
+#[tokio::test]
+async fn test_paused_recurrence() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = sample_handler(&temp_file).await;
+    let mut data = handler.data.write().await;
+    data.add(Nota {
+        recurrence_pattern: Some(RecurrencePattern::weekly),
+        recurrence_config: Some("Saturday".to_string()),
+        ..calendar("gym", "2025-06-07")
+    });
+    data.add(Nota {
+        recurrence_pattern: Some(RecurrencePattern::daily),
+        ..calendar("stretch", "2025-06-01")
+    });
+    drop(data);
+
+    let response = handler.pause_recurrence("gym".to_string()).await.unwrap();
+    assert!(response.starts_with("Paused [gym]"));
+    handler
+        .pause_recurrence("stretch".to_string())
+        .await
+        .unwrap();
+    assert!(
+        handler
+            .pause_recurrence("dentist".to_string())
+            .await
+            .is_err()
+    );
+
+    let promoted = handler
+        .promote_due_calendar_items(date("2025-06-30"))
+        .await
+        .unwrap();
+    assert!(!promoted.contains(&"gym".to_string()));
+    assert!(promoted.contains(&"dentist".to_string()));
+
+    handler
+        .change_status(vec!["stretch".to_string()], "done".to_string(), None, None)
+        .await
+        .unwrap();
+    let data = handler.data.read().await;
+    assert!(!data.contains_id("stretch-20250602"));
+    assert!(data.find_by_id("gym").unwrap().paused);
+    drop(data);
+
+    let response = handler.resume_recurrence("gym".to_string()).await.unwrap();
+    assert!(response.starts_with("Resumed [gym]"));
+    let data = handler.data.read().await;
+    assert!(!data.find_by_id("gym").unwrap().paused);
+}
@@ -0,0 +1,183 @@
+//! Unit tests for schema validation of GTD data files
+//!
+//! These tests verify that malformed documents are reported with the
+//! offending array, item ID, field, and line number.
+
+use gtd_mcp::Storage;
+use gtd_mcp::schema::validate_document;
+use std::fs;
+
+// 不正な日付が配列名・ID・フィールド・行番号付きで報告されることを確認
+#[test]
+fn test_schema_reports_invalid_date_with_location() {
+    let toml_str = r#"format_version = 3
+
+[[inbox]]
+id = "good-task"
+title = "Good"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+
+[[inbox]]
+id = "bad-task"
+title = "Bad"
+start_date = "2024-13-45"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+"#;
+
+    let issues = validate_document(toml_str);
+    assert_eq!(issues.len(), 1);
+    let issue = &issues[0];
+    assert_eq!(issue.section, "inbox");
+    assert_eq!(issue.index, Some(1));
+    assert_eq!(issue.item_id.as_deref(), Some("bad-task"));
+    assert_eq!(issue.field.as_deref(), Some("start_date"));
+    assert_eq!(issue.line, Some(12));
+    assert!(issue.message.contains("2024-13-45"));
+    assert_eq!(
+        issue.to_string(),
+        "line 12: inbox[1] (id 'bad-task') field 'start_date': invalid date '2024-13-45' (expected YYYY-MM-DD)"
+    );
+}
+
+// 優先度・エネルギー・見積もり・期限・一時停止の不正な値が報告されることを確認
+#[test]
+fn test_schema_reports_invalid_metadata() {
+    let toml_str = r#"
+[[next_action]]
+id = "x"
+title = "X"
+priority = "urgent"
+due_date = "soon"
+estimate = "15m"
+energy = "low"
+paused = "yes"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+"#;
+
+    let issues = validate_document(toml_str);
+    let fields: Vec<_> = issues.iter().map(|i| i.field.as_deref().unwrap()).collect();
+    assert_eq!(fields, vec!["due_date", "estimate", "paused", "priority"]);
+    assert!(issues[1].message.contains("number of minutes"));
+    assert!(issues[2].message.contains("expected true or false"));
+    assert!(issues[3].message.contains("high, medium, low"));
+}
+
+// 未知のステータスが報告されることを確認
+#[test]
+fn test_schema_reports_unknown_status() {
+    let toml_str = r#"
+[[notas]]
+id = "x"
+title = "X"
+status = "doing"
+"#;
+
+    let issues = validate_document(toml_str);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].section, "notas");
+    assert_eq!(issues[0].field.as_deref(), Some("status"));
+    assert!(issues[0].message.contains("unknown status 'doing'"));
+}
+
+// ゴミ箱に入れる前のステータスが不正な場合に報告されることを確認
+#[test]
+fn test_schema_reports_unknown_previous_status() {
+    let toml_str = r#"
+[[trash]]
+id = "x"
+title = "X"
+previous_status = "doing"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+"#;
+
+    let issues = validate_document(toml_str);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].field.as_deref(), Some("previous_status"));
+    assert!(issues[0].message.contains("unknown status 'doing'"));
+}
+
+// 必須フィールドの欠落・クォートなし日付・型違いを一度に報告することを確認
+#[test]
+fn test_schema_reports_multiple_issues() {
+    let toml_str = r#"
+[[next_action]]
+id = "no-title"
+created_at = 2024-01-01
+updated_at = "2024-01-01"
+
+[[context]]
+name = "Office"
+notes = 42
+"#;
+
+    let issues = validate_document(toml_str);
+    let rendered: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+    assert_eq!(issues.len(), 3, "{:?}", rendered);
+    assert!(
+        rendered
+            .iter()
+            .any(|r| r.contains("field 'title': missing required field"))
+    );
+    assert!(
+        rendered
+            .iter()
+            .any(|r| r.contains("field 'created_at': date must be a quoted string"))
+    );
+    assert!(rendered.iter().any(|r| {
+        r.contains("context[0] (id 'Office') field 'notes': expected a string, found integer")
+    }));
+}
+
+// 正常なドキュメントでは問題が報告されないことを確認
+#[test]
+fn test_schema_valid_document_has_no_issues() {
+    let toml_str = r#"format_version = 3
+
+[[inbox]]
+id = "task"
+title = "Task"
+created_at = "2024-01-01"
+updated_at = "2024-01-01"
+recurrence_pattern = "weekly"
+recurrence_config = "Monday"
+
+[[project]]
+id = "proj"
+title = "Project"
+
+[projects.legacy]
+name = "Legacy"
+status = "active"
+"#;
+
+    assert!(validate_document(toml_str).is_empty());
+}
+
+// Storage::load のエラーメッセージに詳細が含まれることを確認
+#[test]
+fn test_storage_load_error_includes_schema_details() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    fs::write(
+        &path,
+        "[[calendar]]\nid = \"dentist\"\ntitle = \"Dentist\"\nstart_date = \"tomorrow\"\ncreated_at = \"2024-01-01\"\nupdated_at = \"2024-01-01\"\n",
+    )
+    .unwrap();
+
+    let err = match Storage::new(&path, false).load() {
+        Ok(_) => panic!("load should fail"),
+        Err(e) => e.to_string(),
+    };
+    assert!(err.contains("1 schema error(s) found"), "{}", err);
+    assert!(
+        err.contains(
+            "line 4: calendar[0] (id 'dentist') field 'start_date': invalid date 'tomorrow'"
+        ),
+        "{}",
+        err
+    );
+}
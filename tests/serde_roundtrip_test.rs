@@ -0,0 +1,212 @@
+//! Property-based round-trip tests for the serde layer
+//!
+//! These tests generate arbitrary `Nota`/`GtdData` values (unicode text, CRLF
+//! notes, empty and missing optionals, recurrence settings) and check that
+//! serializing and loading them again yields the same data.
+
+use chrono::NaiveDate;
+use gtd_mcp::Storage;
+use gtd_mcp::gtd::{Energy, GtdData, Nota, NotaStatus, Priority, RecurrencePattern};
+use gtd_mcp::migration::normalize_string_line_endings;
+use proptest::collection::{btree_set, vec};
+use proptest::prelude::*;
+use tempfile::TempDir;
+
+const STATUSES: [NotaStatus; 13] = [
+    NotaStatus::inbox,
+    NotaStatus::next_action,
+    NotaStatus::waiting_for,
+    NotaStatus::later,
+    NotaStatus::calendar,
+    NotaStatus::someday,
+    NotaStatus::done,
+    NotaStatus::reference,
+    NotaStatus::trash,
+    NotaStatus::project,
+    NotaStatus::context,
+    NotaStatus::goal,
+    NotaStatus::template,
+];
+
+const PATTERNS: [RecurrencePattern; 4] = [
+    RecurrencePattern::daily,
+    RecurrencePattern::weekly,
+    RecurrencePattern::monthly,
+    RecurrencePattern::yearly,
+];
+
+const PRIORITIES: [Priority; 3] = [Priority::high, Priority::medium, Priority::low];
+
+const ENERGIES: [Energy; 3] = [Energy::low, Energy::medium, Energy::high];
+
+/// 任意の Unicode 文字列（制御文字・絵文字・日本語・引用符を含む）
+fn text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        ".*",
+        "[a-zA-Z0-9 ]{0,20}",
+        "[\"'\\\\#=\\[\\]{}]{0,10}",
+        "[あ-ん漢字😀🎉]{0,10}",
+    ]
+}
+
+/// 改行（LF/CRLF）を含む複数行のノート
+fn notes() -> impl Strategy<Value = String> {
+    vec((text(), prop_oneof!["\n", "\r\n", "\r"]), 0..5)
+        .prop_map(|lines| lines.into_iter().map(|(line, eol)| line + &eol).collect())
+}
+
+fn date() -> impl Strategy<Value = NaiveDate> {
+    (0i64..40_000).prop_map(|days| {
+        NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Duration::days(days)
+    })
+}
+
+/// ID 以外のフィールドを任意に持つ nota
+fn nota(id: String) -> impl Strategy<Value = Nota> {
+    (
+        text(),
+        0..STATUSES.len(),
+        proptest::option::of(text()),
+        proptest::option::of(text()),
+        proptest::option::of(notes()),
+        proptest::option::of(date()),
+        (date(), date()),
+        proptest::option::of((0..PATTERNS.len(), proptest::option::of(text()))),
+        proptest::option::of(text()),
+        (
+            proptest::option::of(0..PRIORITIES.len()),
+            proptest::option::of(date()),
+            proptest::option::of(any::<u32>()),
+            proptest::option::of(0..ENERGIES.len()),
+            proptest::option::of(date()),
+            any::<bool>(),
+            proptest::option::of(0..STATUSES.len()),
+            proptest::option::of(text()),
+            proptest::option::of(text()),
+            proptest::option::of(any::<u32>()),
+            proptest::option::of(date()),
+        ),
+    )
+        .prop_map(
+            move |(
+                title,
+                status,
+                project,
+                context,
+                notes,
+                start_date,
+                dates,
+                recurrence,
+                link,
+                (
+                    priority,
+                    due_date,
+                    estimate,
+                    energy,
+                    focus_date,
+                    paused,
+                    previous_status,
+                    goal,
+                    availability,
+                    escalate_after_days,
+                    status_since,
+                ),
+            )| {
+                Nota {
+                    id: id.clone(),
+                    title,
+                    status: STATUSES[status].clone(),
+                    project,
+                    context,
+                    notes,
+                    start_date,
+                    created_at: dates.0,
+                    updated_at: dates.1,
+                    recurrence_pattern: recurrence.as_ref().map(|(p, _)| PATTERNS[*p].clone()),
+                    recurrence_config: recurrence.and_then(|(_, config)| config),
+                    link,
+                    priority: priority.map(|p| PRIORITIES[p]),
+                    due_date,
+                    estimate,
+                    energy: energy.map(|e| ENERGIES[e]),
+                    focus_date,
+                    paused,
+                    previous_status: previous_status.map(|s| STATUSES[s].clone()),
+                    goal,
+                    availability,
+                    escalate_after_days,
+                    status_since,
+                }
+            },
+        )
+}
+
+/// 一意な ID を持つ nota の並び
+fn notas() -> impl Strategy<Value = Vec<Nota>> {
+    btree_set("[a-zA-Z0-9@_-]{1,12}|.{1,8}", 0..12)
+        .prop_flat_map(|ids| ids.into_iter().map(nota).collect::<Vec<_>>())
+}
+
+fn data_from(notas: &[Nota]) -> GtdData {
+    let mut data = GtdData::new();
+    for nota in notas {
+        data.add(nota.clone());
+    }
+    data
+}
+
+/// 読み込み時に行われるノートの改行正規化（CRLF・CR → LF）を適用した期待値
+fn normalized(nota: &Nota) -> Nota {
+    let mut nota = nota.clone();
+    nota.notes = nota.notes.as_deref().map(normalize_string_line_endings);
+    nota
+}
+
+/// 比較用に ID 順に並べた nota（保存時はステータス順に並び替えられる）
+fn sorted(notas: impl IntoIterator<Item = Nota>) -> Vec<Nota> {
+    let mut notas: Vec<Nota> = notas.into_iter().collect();
+    notas.sort_by(|a, b| a.id.cmp(&b.id));
+    notas
+}
+
+proptest! {
+    // 単体の nota がフィールドを失わずに TOML と往復できることを確認
+    #[test]
+    fn prop_nota_round_trip(nota in "[a-z0-9-]{1,12}".prop_flat_map(nota)) {
+        let toml = toml::to_string(&nota).unwrap();
+        let back: Nota = toml::from_str(&toml).unwrap();
+        prop_assert_eq!(back, nota);
+    }
+
+    // GtdData のシリアライズ → デシリアライズで全 nota が復元されることを確認
+    #[test]
+    fn prop_gtd_data_round_trip(notas in notas()) {
+        let data = data_from(&notas);
+        let content = Storage::serialize(&data).unwrap();
+        let back: GtdData = toml::from_str(&content).unwrap();
+
+        prop_assert_eq!(back.format_version, data.format_version);
+        prop_assert_eq!(
+            sorted(back.iter().cloned()),
+            sorted(notas.iter().map(normalized))
+        );
+        // 読み込んだデータを再度往復させても出力は変わらない（安定した出力）
+        let again = Storage::serialize(&back).unwrap();
+        let reloaded: GtdData = toml::from_str(&again).unwrap();
+        prop_assert_eq!(Storage::serialize(&reloaded).unwrap(), again);
+    }
+
+    // ファイル保存（OS ネイティブ改行）→ 読み込みで全 nota が復元されることを確認
+    #[test]
+    fn prop_storage_round_trip(notas in notas()) {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().join("gtd.toml"), false);
+        storage.save(&data_from(&notas)).unwrap();
+
+        let loaded = storage.load().unwrap();
+        prop_assert_eq!(
+            sorted(loaded.iter().cloned()),
+            sorted(notas.iter().map(normalized))
+        );
+    }
+}
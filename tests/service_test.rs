@@ -0,0 +1,268 @@
+//! Integration tests for the typed GTD service
+//!
+//! These tests drive the data through [`GtdService`] as an embedder would,
+//! without MCP strings, and check that the typed results match the data.
+
+use chrono::NaiveDate;
+use gtd_mcp::service::{Cascade, GtdService, ItemUpdate, ListQuery, NewItem, Rules, ServiceError};
+use gtd_mcp::wip::{WipLimit, WipMode};
+use gtd_mcp::{FixedClock, GtdData, GtdServerHandler, NotaStatus, gtd::RecurrencePattern};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+fn handler(dir: &TempDir) -> GtdServerHandler {
+    GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .build()
+        .unwrap()
+}
+
+// 作成・一覧・更新・状態変更を型付きの値で行えることを確認
+#[tokio::test]
+async fn test_service_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler(&dir);
+    let service = handler.service();
+
+    service
+        .inbox(NewItem::new("@phone", "Phone", NotaStatus::context))
+        .await
+        .unwrap();
+    let created = service
+        .inbox(NewItem {
+            context: Some("@phone".to_string()),
+            ..NewItem::new("call-bob", "Call Bob", NotaStatus::next_action)
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.nota.id, "call-bob");
+    assert!(created.warnings.is_empty());
+
+    let listing = service
+        .list(ListQuery {
+            status: Some(NotaStatus::next_action),
+            ..Default::default()
+        })
+        .await;
+    let ids: Vec<&str> = listing.notas.iter().map(|n| n.id.as_str()).collect();
+    assert_eq!(ids, ["call-bob"]);
+    assert_eq!(listing.current_context, None);
+
+    let updated = service
+        .update(
+            "call-bob",
+            ItemUpdate {
+                title: Some("Call Bob about the offer".to_string()),
+                context: Some(None),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Call Bob about the offer");
+    assert_eq!(updated.context, None);
+
+    let changes = service
+        .change_status(&["call-bob".to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+    assert_eq!(changes.changed.len(), 1);
+    assert_eq!(changes.changed[0].old_status, NotaStatus::next_action);
+    assert!(changes.failed.is_empty());
+
+    // 変更はファイルに保存されている
+    let reloaded = handler.storage().load().unwrap();
+    assert_eq!(
+        reloaded.find_by_id("call-bob").unwrap().status,
+        NotaStatus::done
+    );
+}
+
+// 失敗は種類ごとの ServiceError と項目ごとの失敗として返ることを確認
+#[tokio::test]
+async fn test_service_errors_are_typed() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler(&dir);
+    let service = handler.service();
+
+    let error = service
+        .update("missing", ItemUpdate::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ServiceError::NotFound(_)), "{}", error);
+
+    let error = service
+        .inbox(NewItem::new("meeting", "Meeting", NotaStatus::calendar))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ServiceError::Invalid(_)), "{}", error);
+
+    let error = service
+        .change_status(
+            &["missing".to_string()],
+            NotaStatus::done,
+            None,
+            Some(Cascade::Trash),
+        )
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("cascade"), "{}", error);
+
+    let changes = service
+        .change_status(&["missing".to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+    assert!(changes.changed.is_empty());
+    assert_eq!(changes.failed[0].id, "missing");
+    assert_eq!(changes.failed[0].reason, "not found");
+}
+
+// 繰り返し項目を完了すると次の回が型付きで返ることを確認
+#[tokio::test]
+async fn test_service_returns_next_occurrence() {
+    let dir = TempDir::new().unwrap();
+    let handler = handler(&dir);
+    let service = handler.service();
+    let start = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+    service
+        .inbox(NewItem {
+            start_date: Some(start),
+            recurrence: Some(RecurrencePattern::daily),
+            ..NewItem::new("water-plants", "Water plants", NotaStatus::next_action)
+        })
+        .await
+        .unwrap();
+    let changes = service
+        .change_status(&["water-plants".to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+
+    let next = changes.changed[0].next_occurrence.as_ref().unwrap();
+    assert_eq!(next.id, "water-plants-20250302");
+    assert_eq!(next.status, NotaStatus::next_action);
+    assert_eq!(next.start_date, NaiveDate::from_ymd_opt(2025, 3, 2));
+}
+
+// ハンドラなしでもデータ・規則・保存コールバックだけでサービスが動くことを確認
+#[tokio::test]
+async fn test_service_without_a_handler() {
+    let data = RwLock::new(GtdData::new());
+    let today = NaiveDate::from_ymd_opt(2030, 1, 10).unwrap();
+    let rules = Rules {
+        clock: Arc::new(FixedClock::on(today)),
+        wip_limits: vec![WipLimit {
+            status: NotaStatus::next_action,
+            max: 1,
+        }],
+        wip_mode: WipMode::Enforce,
+        ..Default::default()
+    };
+    let messages = Mutex::new(Vec::new());
+    let save = |message: &str| {
+        messages.lock().unwrap().push(message.to_string());
+        Ok(())
+    };
+    let service = GtdService::new(&data, rules.clone(), &save);
+
+    let created = service
+        .inbox(NewItem::new(
+            "call-bob",
+            "Call Bob",
+            NotaStatus::next_action,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(created.nota.created_at, today);
+    let error = service
+        .inbox(NewItem::new(
+            "buy-milk",
+            "Buy milk",
+            NotaStatus::next_action,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ServiceError::Invalid(ref m) if m.contains("WIP limit")));
+    assert_eq!(messages.lock().unwrap().len(), 1);
+    assert!(messages.lock().unwrap()[0].contains("call-bob"));
+    assert_eq!(data.read().await.iter().count(), 1);
+
+    // 保存に失敗すると Save エラーになる
+    let failing = |_: &str| Err(anyhow::anyhow!("disk full"));
+    let service = GtdService::new(&data, rules, &failing);
+    let error = service
+        .inbox(NewItem::new("buy-milk", "Buy milk", NotaStatus::inbox))
+        .await
+        .unwrap_err();
+    assert_eq!(error, ServiceError::Save("disk full".to_string()));
+}
+
+// 検証で拒否された移動は何も変えず、連鎖はすべての検証を通った移動だけに適用されることを確認
+#[tokio::test]
+async fn test_refused_moves_change_nothing() {
+    let data = RwLock::new(GtdData::new());
+    let rules = Rules {
+        id_generator: Arc::new(gtd_mcp::IdScheme::Sequential),
+        ..Default::default()
+    };
+    let saves = Mutex::new(0);
+    let save = |_: &str| {
+        *saves.lock().unwrap() += 1;
+        Ok(())
+    };
+    let service = GtdService::new(&data, rules, &save);
+    service
+        .inbox(NewItem::new(
+            u64::MAX.to_string(),
+            "Last",
+            NotaStatus::inbox,
+        ))
+        .await
+        .unwrap();
+    service
+        .inbox(NewItem::new("garden", "Garden", NotaStatus::project))
+        .await
+        .unwrap();
+    service
+        .inbox(NewItem {
+            project: Some("garden".to_string()),
+            recurrence: Some(RecurrencePattern::daily),
+            ..NewItem::new("water", "Water plants", NotaStatus::next_action)
+        })
+        .await
+        .unwrap();
+    let saved = *saves.lock().unwrap();
+
+    // 次回の ID が作れない完了は失敗し、項目も保存回数も変わらない
+    let changes = service
+        .change_status(&["water".to_string()], NotaStatus::done, None, None)
+        .await
+        .unwrap();
+    assert!(changes.changed.is_empty());
+    assert!(changes.failed[0].reason.contains("largest number"));
+    assert_eq!(*saves.lock().unwrap(), saved);
+    {
+        let data = data.read().await;
+        assert_eq!(data.iter().count(), 3);
+        assert_eq!(
+            data.find_by_id("water").unwrap().status,
+            NotaStatus::next_action
+        );
+    }
+
+    let changes = service
+        .change_status(
+            &["garden".to_string()],
+            NotaStatus::trash,
+            None,
+            Some(Cascade::Trash),
+        )
+        .await
+        .unwrap();
+    assert_eq!(changes.changed[0].trashed, ["water"]);
+    assert_eq!(
+        data.read().await.find_by_id("water").unwrap().status,
+        NotaStatus::trash
+    );
+}
@@ -0,0 +1,48 @@
+//! Unit tests for generating IDs from titles
+
+use gtd_mcp::slug::{slugify, unique_id};
+use gtd_mcp::{GtdData, IdPolicy, Nota};
+
+// タイトルが小文字のケバブケースに変換されることを確認
+#[test]
+fn test_slugify() {
+    assert_eq!(slugify("Call John"), "call-john");
+    assert_eq!(
+        slugify("  Re: Q3 budget -- review!! "),
+        "re-q3-budget-review"
+    );
+    assert_eq!(slugify("Fix bug #42"), "fix-bug-42");
+    // ASCII の英数字がなければ既定の ID
+    assert_eq!(slugify("買い物"), "item");
+    assert_eq!(slugify("  "), "item");
+}
+
+// 長いタイトルは単語の区切りで切り詰められ、厳格な ID ポリシーを満たすことを確認
+#[test]
+fn test_slugify_long_titles_fit_policy() {
+    let policy = IdPolicy::kebab_case();
+    let long = "word ".repeat(40);
+    let slug = slugify(&long);
+    assert!(slug.len() <= 60);
+    assert!(!slug.ends_with('-'));
+    assert!(policy.check(&slug).is_ok());
+
+    let one_word = "a".repeat(100);
+    assert_eq!(slugify(&one_word).len(), 60);
+    assert!(policy.check(&format!("{}-99", slugify(&one_word))).is_ok());
+}
+
+// 重複する ID には番号が付くことを確認
+#[test]
+fn test_unique_id_appends_suffix() {
+    let mut data = GtdData::new();
+    assert_eq!(unique_id(&data, "Buy milk"), "buy-milk");
+    for id in ["buy-milk", "buy-milk-2"] {
+        data.add(Nota {
+            id: id.to_string(),
+            title: "Buy milk".to_string(),
+            ..Default::default()
+        });
+    }
+    assert_eq!(unique_id(&data, "Buy milk"), "buy-milk-3");
+}
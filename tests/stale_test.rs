@@ -0,0 +1,117 @@
+//! Integration tests for flagging stale next actions
+//!
+//! These tests cover recording when an item entered its status, the
+//! threshold that makes a next action stale, and how `list` flags them.
+
+mod common;
+
+use chrono::{Duration, NaiveDate};
+use gtd_mcp::{FixedClock, GtdServerHandler, Nota, NotaStatus};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2030, 1, 10).unwrap()
+}
+
+/// A next action last changed `days` days ago
+fn next_action(id: &str, days: i64) -> Nota {
+    let since = today() - Duration::days(days);
+    Nota {
+        created_at: since,
+        updated_at: since,
+        ..common::action(id)
+    }
+}
+
+/// IDs of the items `list` flags as stale, in listing order
+async fn flagged(handler: &GtdServerHandler) -> Vec<String> {
+    let listing = handler
+        .list(
+            Some("next_action".to_string()),
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    listing
+        .lines()
+        .filter(|line| line.starts_with("- [") && line.contains(" ⚠ "))
+        .filter_map(|line| Some(line[3..].split_once(']')?.0.to_string()))
+        .collect()
+}
+
+// ステータスを変えたときだけ日付が記録され、未記録なら updated_at から数えることを確認
+#[test]
+fn test_status_since() {
+    let mut nota = next_action("call-bank", 30);
+    assert_eq!(nota.days_in_status(today()), 30);
+
+    nota.set_status(NotaStatus::next_action, today());
+    assert_eq!(nota.status_since, None);
+
+    nota.set_status(NotaStatus::someday, today());
+    assert_eq!(nota.status_since, Some(today()));
+    assert_eq!(nota.days_in_status(today() + Duration::days(3)), 3);
+}
+
+// 閾値ちょうどの日数から古いとみなされ、1 日前はまだ古くないことを確認
+#[test]
+fn test_stale_threshold_boundary() {
+    assert!(!next_action("a", 13).is_stale(today(), 14));
+    assert!(next_action("a", 14).is_stale(today(), 14));
+    assert!(next_action("a", 15).is_stale(today(), 14));
+
+    // 状態に入った日があれば updated_at より優先する
+    let moved_back = Nota {
+        status_since: Some(today() - Duration::days(13)),
+        ..next_action("a", 40)
+    };
+    assert!(!moved_back.is_stale(today(), 14));
+    assert!(moved_back.is_stale(today() + Duration::days(1), 14));
+
+    // next_action 以外は何日たっても古いとみなさない
+    let waiting = Nota {
+        status: NotaStatus::waiting_for,
+        ..next_action("a", 100)
+    };
+    assert!(!waiting.is_stale(today(), 14));
+}
+
+// list が閾値以上の次の行動だけに印を付け、閾値 0 で無効になることを確認
+#[tokio::test]
+async fn test_list_flags_stale_next_actions() {
+    let dir = TempDir::new().unwrap();
+    let handler = GtdServerHandler::builder()
+        .path(dir.path().join("gtd.toml"))
+        .clock(Arc::new(FixedClock::on(today())))
+        .build()
+        .unwrap();
+    {
+        let mut data = handler.data.write().await;
+        data.add(next_action("renew-passport", 21));
+        data.add(next_action("book-dentist", 14));
+        data.add(next_action("call-bank", 13));
+        data.add(next_action("buy-milk", 2));
+        data.add(Nota {
+            status_since: Some(today() - Duration::days(1)),
+            ..next_action("fix-bike", 40)
+        });
+    }
+
+    // 既定の閾値は 14 日
+    assert_eq!(flagged(&handler).await, ["renew-passport", "book-dentist"]);
+
+    let handler = handler.with_stale_after_days(2);
+    assert_eq!(
+        flagged(&handler).await,
+        ["renew-passport", "book-dentist", "call-bank", "buy-milk"]
+    );
+
+    let handler = handler.with_stale_after_days(0);
+    assert!(flagged(&handler).await.is_empty());
+}
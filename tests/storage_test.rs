@@ -3,13 +3,18 @@
 //! These tests verify the file-based TOML storage functionality,
 //! including save/load operations and data integrity.
 
+mod common;
+
 use chrono::NaiveDate;
+use gtd_mcp::checksum::Verification;
 use gtd_mcp::gtd::local_date_today;
 use gtd_mcp::migration::{Context, Project, Task};
-use gtd_mcp::{GtdData, NotaStatus, Storage};
+use gtd_mcp::mirror::MirrorFormat;
+use gtd_mcp::{GtdData, Nota, NotaStatus, Storage};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 fn get_test_path(filename: &str) -> PathBuf {
     env::temp_dir().join(filename)
@@ -89,6 +94,20 @@ fn test_storage_save_and_load_with_tasks() {
         start_date: NaiveDate::from_ymd_opt(2024, 12, 25),
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_task(task.clone());
 
@@ -136,6 +155,20 @@ fn test_storage_save_and_load_with_projects() {
         updated_at: local_date_today(),
         context: None,
         status: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_project(project.clone());
 
@@ -177,6 +210,20 @@ fn test_storage_save_and_load_with_contexts() {
         start_date: None,
         created_at: None,
         updated_at: None,
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_context(context.clone());
 
@@ -219,6 +266,20 @@ fn test_storage_save_and_load_comprehensive() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
     }
@@ -235,6 +296,20 @@ fn test_storage_save_and_load_comprehensive() {
             created_at: local_date_today(),
             updated_at: local_date_today(),
             status: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_project(project);
     }
@@ -251,6 +326,20 @@ fn test_storage_save_and_load_comprehensive() {
             start_date: None,
             created_at: None,
             updated_at: None,
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_context(context);
     }
@@ -292,6 +381,20 @@ fn test_storage_overwrite_existing_file() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data1.add_task(task1);
     storage.save(&data1).unwrap();
@@ -308,6 +411,20 @@ fn test_storage_overwrite_existing_file() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data2.add_task(task2);
     storage.save(&data2).unwrap();
@@ -372,6 +489,20 @@ fn test_storage_different_status_values() {
             start_date: None,
             created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
             updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            recurrence_pattern: None,
+            recurrence_config: None,
+            link: None,
+            priority: None,
+            due_date: None,
+            estimate: None,
+            energy: None,
+            focus_date: None,
+            paused: false,
+            previous_status: None,
+            goal: None,
+            availability: None,
+            escalate_after_days: None,
+            status_since: None,
         };
         data.add_task(task);
     }
@@ -482,6 +613,20 @@ fn test_storage_save_creates_file() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_task(task);
 
@@ -601,6 +746,20 @@ fn test_storage_git_sync_on_save() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_task(task);
 
@@ -711,6 +870,20 @@ fn test_storage_multiline_notes_line_endings() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_task(task);
 
@@ -748,6 +921,20 @@ fn test_storage_file_has_native_line_endings() {
         start_date: None,
         created_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
         updated_at: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        recurrence_pattern: None,
+        recurrence_config: None,
+        link: None,
+        priority: None,
+        due_date: None,
+        estimate: None,
+        energy: None,
+        focus_date: None,
+        paused: false,
+        previous_status: None,
+        goal: None,
+        availability: None,
+        escalate_after_days: None,
+        status_since: None,
     };
     data.add_task(task);
 
@@ -927,3 +1114,480 @@ fn test_storage_toml_escaped_cr_normalized() {
     // Clean up
     let _ = fs::remove_file(&test_path);
 }
+
+// デバウンス有効時は複数の保存が1回の書き込みにまとめられることを確認
+#[test]
+fn test_storage_debounce_coalesces_writes_until_flush() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let storage = Storage::new(&test_path, false).with_debounce(Duration::from_secs(60));
+
+    let mut data = GtdData::new();
+    for i in 0..3 {
+        data.add(Nota {
+            id: format!("task-{}", i),
+            title: format!("Task {}", i),
+            ..Default::default()
+        });
+        storage
+            .save_with_message(&data, &format!("Add item task-{}", i))
+            .unwrap();
+    }
+
+    // ウィンドウ内なのでまだファイルは書かれていない
+    assert!(!test_path.exists());
+
+    storage.flush().unwrap();
+    let loaded = Storage::new(&test_path, false).load().unwrap();
+    assert_eq!(loaded.task_count(), 3);
+}
+
+// アイドル時間経過後にバックグラウンドで書き込まれることを確認
+#[test]
+fn test_storage_debounce_flushes_after_idle() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let storage = Storage::new(&test_path, false).with_debounce(Duration::from_millis(50));
+
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "idle-task".to_string(),
+        title: "Idle".to_string(),
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while !test_path.exists() && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(test_path.exists());
+    let loaded = Storage::new(&test_path, false).load().unwrap();
+    assert!(loaded.find_by_id("idle-task").is_some());
+}
+
+// デバウンスの後に設定したミラーもバックグラウンドの書き込みで更新されることを確認
+#[test]
+fn test_storage_debounce_keeps_options_set_after_it() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let mirror_path = temp_dir.path().join("gtd.json");
+    let storage = Storage::new(&test_path, false)
+        .with_debounce(Duration::from_millis(50))
+        .with_mirror(&mirror_path, MirrorFormat::Json);
+
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "idle-task".to_string(),
+        title: "Idle".to_string(),
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while !mirror_path.exists() && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mirror_path).unwrap()).unwrap();
+    assert_eq!(json["inbox"][0]["id"], "idle-task", "{}", json);
+}
+
+// まとめられた保存が1つの要約コミットになることを確認
+#[test]
+fn test_storage_debounce_produces_single_summarized_commit() {
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let test_path = work_dir.path().join("gtd.toml");
+    let storage = Storage::new(&test_path, true).with_debounce(Duration::from_secs(60));
+
+    let mut data = GtdData::new();
+    for i in 0..3 {
+        data.add(Nota {
+            id: format!("task-{}", i),
+            title: format!("Task {}", i),
+            ..Default::default()
+        });
+        storage
+            .save_with_message(&data, &format!("Add item task-{}", i))
+            .unwrap();
+    }
+    storage.shutdown().unwrap();
+
+    let repo = git2::Repository::open(work_dir.path()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let message = head.message().unwrap();
+    assert!(message.starts_with("Batch update: 3 changes"));
+    assert!(message.contains("- Add item task-0"));
+    assert!(message.contains("- Add item task-2"));
+    // 初期コミットの直後に1コミットだけ追加されている
+    assert_eq!(head.parent(0).unwrap().message().unwrap(), "Initial commit");
+}
+
+// 保存のたびに JSON ミラーが読み取り専用で書き出されることを確認
+#[test]
+fn test_storage_json_mirror_written_on_every_save() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let mirror_path = temp_dir.path().join("public").join("gtd.json");
+    let storage = Storage::new(&test_path, false).with_mirror(&mirror_path, MirrorFormat::Json);
+
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "first".to_string(),
+        title: "First".to_string(),
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+    assert!(fs::metadata(&mirror_path).unwrap().permissions().readonly());
+
+    // 読み取り専用でも次の保存で上書きされる
+    data.add(Nota {
+        id: "second".to_string(),
+        title: "Second".to_string(),
+        status: NotaStatus::next_action,
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&mirror_path).unwrap()).unwrap();
+    assert_eq!(json["format_version"], 3);
+    assert_eq!(json["inbox"][0]["id"], "first");
+    assert_eq!(json["next_action"][0]["title"], "Second");
+}
+
+#[test]
+fn test_storage_markdown_mirror_report() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("gtd.toml");
+    let mirror_path = temp_dir.path().join("gtd.md");
+    let storage = Storage::new(&test_path, false).with_mirror(&mirror_path, MirrorFormat::Markdown);
+
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "call-bob".to_string(),
+        title: "Call Bob".to_string(),
+        status: NotaStatus::next_action,
+        context: Some("Phone".to_string()),
+        notes: Some("About the contract".to_string()),
+        ..Default::default()
+    });
+    data.add(Nota {
+        id: "shipped".to_string(),
+        title: "Ship release".to_string(),
+        status: NotaStatus::done,
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+
+    let report = fs::read_to_string(&mirror_path).unwrap();
+    assert!(report.starts_with("# GTD Report"));
+    assert!(report.contains("## Next Actions (1)"));
+    assert!(report.contains("- [ ] **Call Bob** (`call-bob`) — context: Phone"));
+    assert!(report.contains("  > About the contract"));
+    assert!(report.contains("- [x] **Ship release** (`shipped`)"));
+    assert!(!report.contains("## Inbox"));
+}
+
+#[test]
+fn test_mirror_format_from_path_and_str() {
+    assert_eq!(
+        MirrorFormat::from_path("out/gtd.json"),
+        Some(MirrorFormat::Json)
+    );
+    assert_eq!(
+        MirrorFormat::from_path("README.MD"),
+        Some(MirrorFormat::Markdown)
+    );
+    assert_eq!(MirrorFormat::from_path("gtd.txt"), None);
+    assert_eq!(
+        "markdown".parse::<MirrorFormat>(),
+        Ok(MirrorFormat::Markdown)
+    );
+    assert!("yaml".parse::<MirrorFormat>().is_err());
+}
+
+// 両方のマシンで gtd.toml を変更して競合しても、nota 単位でマージされることを確認
+#[test]
+fn test_storage_sync_conflict_merges_at_nota_level() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let local = Storage::new(work_dir.path().join("gtd.toml"), true);
+    let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "shared".to_string(),
+        title: "Original".to_string(),
+        created_at: date("2024-01-01"),
+        updated_at: date("2024-01-01"),
+        ..Default::default()
+    });
+    local.save(&data).unwrap();
+
+    // 別マシン: shared を新しい日付で更新し、remote-only を追加
+    let other_dir = common::clone_remote(remote_dir.path());
+    let remote = Storage::new(other_dir.path().join("gtd.toml"), true);
+    let mut remote_data = remote.load().unwrap();
+    let mut shared = remote_data.find_by_id("shared").unwrap();
+    shared.title = "Remote title".to_string();
+    shared.updated_at = date("2024-02-01");
+    remote_data.update("shared", shared);
+    remote_data.add(Nota {
+        id: "remote-only".to_string(),
+        title: "Remote only".to_string(),
+        ..Default::default()
+    });
+    remote.save(&remote_data).unwrap();
+
+    // ローカル: shared を古い日付で更新し、local-only を追加（同じ行が競合する）
+    let mut shared = data.find_by_id("shared").unwrap();
+    shared.title = "Local title".to_string();
+    shared.updated_at = date("2024-01-15");
+    data.update("shared", shared);
+    data.add(Nota {
+        id: "local-only".to_string(),
+        title: "Local only".to_string(),
+        ..Default::default()
+    });
+    local.save(&data).unwrap();
+    assert!(local.take_remote_update());
+
+    let merged = local.load_local().unwrap();
+    assert_eq!(merged.find_by_id("shared").unwrap().title, "Remote title");
+    assert!(merged.find_by_id("local-only").is_some());
+    assert!(merged.find_by_id("remote-only").is_some());
+    let content = fs::read_to_string(local.file_path()).unwrap();
+    assert!(!content.contains("<<<<<<<"));
+
+    // マージ結果が remote にも push されている
+    let refreshed = remote.load().unwrap();
+    assert!(refreshed.find_by_id("local-only").is_some());
+}
+
+// gtd.toml とメモの Markdown が両方競合しても、保存と同じ形式 (チェックサム・外部メモ) でマージされることを確認
+#[test]
+fn test_storage_sync_conflict_in_data_and_notes_files() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let local = Storage::new(work_dir.path().join("gtd.toml"), true);
+    let notes = "# Plan\n\n- Collect feedback\n";
+
+    let mut data = GtdData::new();
+    data.external_notes = Some(20);
+    data.add(Nota {
+        id: "website".to_string(),
+        title: "Website".to_string(),
+        status: NotaStatus::project,
+        notes: Some(notes.to_string()),
+        created_at: common::date("2024-01-01"),
+        updated_at: common::date("2024-01-01"),
+        ..Default::default()
+    });
+    local.save(&data).unwrap();
+
+    // 別マシン: 同じ項目のタイトルとメモを新しい日付で変更
+    let other_dir = common::clone_remote(remote_dir.path());
+    let remote = Storage::new(other_dir.path().join("gtd.toml"), true);
+    let mut remote_data = remote.load().unwrap();
+    let mut website = remote_data.find_by_id("website").unwrap();
+    website.title = "Website (remote)".to_string();
+    website.notes = Some(notes.replace("Collect feedback", "Remote plan"));
+    website.updated_at = common::date("2024-02-01");
+    remote_data.update("website", website);
+    remote.save(&remote_data).unwrap();
+
+    // ローカル: 同じ行を古い日付で変更し、項目を追加
+    let mut website = data.find_by_id("website").unwrap();
+    website.title = "Website (local)".to_string();
+    website.notes = Some(notes.replace("Collect feedback", "Local plan"));
+    website.updated_at = common::date("2024-01-15");
+    data.update("website", website);
+    data.add(Nota {
+        id: "local-only".to_string(),
+        title: "Local only".to_string(),
+        ..Default::default()
+    });
+    local.save(&data).unwrap();
+    assert!(local.take_remote_update());
+
+    let content = fs::read_to_string(local.file_path()).unwrap();
+    assert!(!content.contains("<<<<<<<"), "{}", content);
+    assert!(
+        content.contains("notes_file = \"website.md\""),
+        "{}",
+        content
+    );
+    assert_eq!(local.verify_checksum().unwrap(), Verification::Valid);
+    let note = fs::read_to_string(work_dir.path().join("gtd.notes/website.md")).unwrap();
+    assert!(note.contains("Remote plan"), "{}", note);
+    assert!(!note.contains("<<<<<<<"), "{}", note);
+
+    let mut merged = local.load_local().unwrap();
+    assert_eq!(
+        merged.find_by_id("website").unwrap().title,
+        "Website (remote)"
+    );
+    assert!(merged.find_by_id("local-only").is_some());
+
+    // マージ結果の上に続けて保存できる
+    merged.add(Nota {
+        id: "after-merge".to_string(),
+        title: "After merge".to_string(),
+        ..Default::default()
+    });
+    local.save(&merged).unwrap();
+    let refreshed = remote.load().unwrap();
+    assert!(refreshed.find_by_id("after-merge").is_some());
+    assert!(
+        refreshed
+            .find_by_id("website")
+            .unwrap()
+            .notes
+            .unwrap()
+            .contains("Remote plan")
+    );
+}
+
+// バックグラウンド push 状態が条件を満たすまで待つ
+fn wait_for_push_status(
+    storage: &Storage,
+    condition: impl Fn(&gtd_mcp::push_worker::PushStatus) -> bool,
+) -> gtd_mcp::push_worker::PushStatus {
+    for _ in 0..200 {
+        let status = storage.push_status().unwrap();
+        if condition(&status) {
+            return status;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    panic!("push status did not reach the expected state");
+}
+
+// バックグラウンド push: 保存はコミットのみ行い、ワーカーが remote へ push することを確認
+#[test]
+fn test_storage_background_push_pushes_after_commit() {
+    let (work_dir, remote_dir) = common::setup_repo_with_remote();
+    let storage =
+        Storage::new(work_dir.path().join("gtd.toml"), true).with_background_push(Duration::ZERO);
+
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "pushed-later".to_string(),
+        title: "Pushed later".to_string(),
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+
+    let status = wait_for_push_status(&storage, |s| !s.pending);
+    assert!(status.last_success.is_some());
+    assert!(status.last_error.is_none());
+    assert_eq!(storage.unpushed_commits().unwrap(), Some(0));
+
+    let other_dir = common::clone_remote(remote_dir.path());
+    let other = Storage::new(other_dir.path().join("gtd.toml"), false);
+    assert!(other.load().unwrap().find_by_id("pushed-later").is_some());
+}
+
+// remote に到達できなくても保存は成功し、push は backoff 付きで再試行されることを確認
+#[test]
+fn test_storage_background_push_retries_when_remote_unreachable() {
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let repo = git2::Repository::open(work_dir.path()).unwrap();
+    let missing = work_dir.path().join("missing-remote");
+    repo.remote_set_url("origin", missing.to_str().unwrap())
+        .unwrap();
+
+    let storage =
+        Storage::new(work_dir.path().join("gtd.toml"), true).with_background_push(Duration::ZERO);
+    storage.save(&GtdData::new()).unwrap();
+
+    let status = wait_for_push_status(&storage, |s| s.consecutive_failures > 0);
+    assert!(status.pending);
+    assert!(status.last_error.is_some());
+    assert!(status.next_retry.is_some());
+    assert!(status.last_success.is_none());
+    assert_eq!(storage.unpushed_commits().unwrap(), Some(1));
+}
+
+// バックグラウンド push を無効にした場合は状態を持たないことを確認
+#[test]
+fn test_storage_push_status_none_without_background_push() {
+    let (work_dir, _remote_dir) = common::setup_repo_with_remote();
+    let storage = Storage::new(work_dir.path().join("gtd.toml"), true);
+    assert!(storage.push_status().is_none());
+
+    // Git 同期が無効なら worker は起動しない
+    let storage =
+        Storage::new(work_dir.path().join("gtd.toml"), false).with_background_push(Duration::ZERO);
+    assert!(storage.push_status().is_none());
+}
+
+// 繰り返し設定が保存・再読み込み後も失われず、プロジェクト・コンテキストの順序も保たれることを確認
+#[test]
+fn test_storage_round_trip_keeps_recurrence_and_order() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let storage = Storage::new(dir.path().join("gtd.toml"), false);
+    let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let mut data = GtdData::new();
+    for (id, status) in [
+        ("zeta", NotaStatus::project),
+        ("alpha", NotaStatus::project),
+        ("@work", NotaStatus::context),
+        ("@home", NotaStatus::context),
+    ] {
+        data.add(Nota {
+            id: id.to_string(),
+            title: id.to_string(),
+            status,
+            created_at: date,
+            updated_at: date,
+            ..Default::default()
+        });
+    }
+    data.add(Nota {
+        id: "weekly-review".to_string(),
+        title: "Weekly review".to_string(),
+        status: NotaStatus::calendar,
+        start_date: Some(date),
+        created_at: date,
+        updated_at: date,
+        recurrence_pattern: Some(gtd_mcp::gtd::RecurrencePattern::weekly),
+        recurrence_config: Some("Friday".to_string()),
+        ..Default::default()
+    });
+    storage.save(&data).unwrap();
+
+    let loaded = storage.load().unwrap();
+    let review = loaded.find_by_id("weekly-review").unwrap();
+    assert_eq!(
+        review.recurrence_pattern,
+        Some(gtd_mcp::gtd::RecurrencePattern::weekly)
+    );
+    assert_eq!(review.recurrence_config.as_deref(), Some("Friday"));
+    let ids: Vec<&str> = loaded
+        .iter()
+        .filter(|nota| nota.is_project() || nota.is_context())
+        .map(|nota| nota.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["zeta", "alpha", "@work", "@home"]);
+}
+
+// ベンチマーク用の合成データが保存・読み込みで失われないことを確認
+#[test]
+fn test_storage_roundtrips_benchmark_dataset() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let storage = Storage::new(temp_dir.path().join("gtd.toml"), false);
+    let data = common::dataset::generate(1_000);
+    assert_eq!(data.iter().count(), 1_000);
+    storage.save(&data).unwrap();
+
+    let loaded = storage.load().unwrap();
+    assert_eq!(loaded.iter().count(), 1_000);
+    for nota in data.iter() {
+        assert_eq!(loaded.find_by_id(&nota.id).as_ref(), Some(nota));
+    }
+    let project = common::dataset::project_id(1);
+    assert_eq!(
+        loaded.list_filtered(None, Some(&project), None).len(),
+        data.list_filtered(None, Some(&project), None).len()
+    );
+}
@@ -0,0 +1,206 @@
+//! Unit tests for next action suggestions
+//!
+//! These tests cover the constraints that leave next actions out, the order
+//! of the scores and the `suggest` tool output.
+
+mod common;
+
+use common::date;
+use gtd_mcp::gtd::{Energy, Priority};
+use gtd_mcp::suggest::{self, Constraints};
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn action(id: &str) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.to_string(),
+        status: NotaStatus::next_action,
+        created_at: date("2025-03-10"),
+        updated_at: date("2025-03-10"),
+        ..Default::default()
+    }
+}
+
+fn sample_data() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        context: Some("@office".to_string()),
+        ..action("print-slides")
+    });
+    data.add(Nota {
+        context: Some("@home".to_string()),
+        estimate: Some(90),
+        energy: Some(Energy::high),
+        priority: Some(Priority::high),
+        ..action("write-essay")
+    });
+    data.add(Nota {
+        due_date: Some(date("2025-03-09")),
+        estimate: Some(10),
+        energy: Some(Energy::low),
+        ..action("pay-bill")
+    });
+    data.add(Nota {
+        priority: Some(Priority::low),
+        created_at: date("2025-01-01"),
+        ..action("sort-photos")
+    });
+    data.add(Nota {
+        start_date: Some(date("2025-04-01")),
+        ..action("renew-passport")
+    });
+    data.add(Nota {
+        status: NotaStatus::someday,
+        ..action("learn-piano")
+    });
+    data
+}
+
+// 状況に合わない次の行動が除かれ、期限切れ・優先度・経過日数の順に並ぶことを確認
+#[test]
+fn test_rank_filters_and_orders() {
+    let data = sample_data();
+    let today = date("2025-03-12");
+
+    let ranking = suggest::rank(&data, &Constraints::default(), today, 10);
+    let ids: Vec<&str> = ranking
+        .suggestions
+        .iter()
+        .map(|s| s.nota.id.as_str())
+        .collect();
+    // 開始日が未来の項目とsomedayは対象外
+    assert_eq!(
+        ids,
+        vec!["pay-bill", "write-essay", "sort-photos", "print-slides"]
+    );
+    assert_eq!(ranking.skipped, 1);
+    assert!(
+        ranking.suggestions[0]
+            .reasons
+            .contains(&"overdue by 3 day(s) (2025-03-09)".to_string())
+    );
+    assert!(
+        ranking.suggestions[2]
+            .reasons
+            .contains(&"open for 70 days".to_string())
+    );
+
+    let constraints = Constraints {
+        context: Some("@home".to_string()),
+        minutes: Some(30),
+        energy: Some(Energy::low),
+        now: None,
+        escalate_after_days: None,
+    };
+    let ranking = suggest::rank(&data, &constraints, today, 2);
+    let ids: Vec<&str> = ranking
+        .suggestions
+        .iter()
+        .map(|s| s.nota.id.as_str())
+        .collect();
+    // 別のコンテキスト・時間や気力が足りない項目は除かれ、上位2件だけ返る
+    assert_eq!(ids, vec!["pay-bill", "sort-photos"]);
+    assert_eq!(ranking.fitting, 2);
+    assert_eq!(ranking.skipped, 3);
+    assert_eq!(constraints.describe(), "@home, 30 min, low energy");
+}
+
+// suggestツールがupdateで設定した情報から理由付きで提案することを確認
+#[tokio::test]
+async fn test_suggest_tool() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    for id in ["call-bob", "clean-garage"] {
+        handler
+            .inbox(
+                id.to_string(),
+                id.to_string(),
+                "next_action".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    handler
+        .update(
+            "call-bob".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("high".to_string()),
+            None,
+            Some("15".to_string()),
+            Some("low".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+    handler
+        .update(
+            "clean-garage".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("120".to_string()),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let output = handler
+        .suggest(None, Some(30), Some("low".to_string()), None)
+        .await
+        .unwrap();
+    assert!(output.starts_with("Top 1 of 1 next action(s) for 30 min, low energy:"));
+    assert!(output.contains("1. [call-bob] call-bob"));
+    assert!(output.contains("Why: takes 15 min; low energy; high priority"));
+    assert!(output.contains("1 next action(s) left out"));
+
+    // 不正な値はエラー
+    assert!(
+        handler
+            .suggest(None, None, Some("tired".to_string()), None)
+            .await
+            .is_err()
+    );
+    assert!(
+        handler
+            .suggest(Some("@moon".to_string()), None, None, None)
+            .await
+            .is_err()
+    );
+    assert!(
+        handler
+            .update(
+                "call-bob".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some("urgent".to_string()),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,148 @@
+//! Unit tests for the TaskPaper import
+//!
+//! These tests verify how projects, indented tasks, notes and tags of an
+//! outline map to items, and that re-importing an outline updates them.
+
+mod common;
+
+use common::{date, today};
+use gtd_mcp::import::{self, ImportFormat};
+use gtd_mcp::{GtdData, Nota, NotaStatus};
+
+fn import_text(data: &mut GtdData, text: &str) -> import::ImportSummary {
+    import::import(data, text, ImportFormat::TaskPaper, today()).unwrap()
+}
+
+const OUTLINE: &str = "Website:\n\
+\t- Draft the proposal @computer @flagged\n\
+\t\tAsk Anna for last year's numbers\n\
+\t\tand the budget\n\
+\t- Launch @due(2025-03-10 09:00)\n\
+\t\t- Book the domain @done(2025-03-02)\n\
+\t- Review @work @someone(Anna) @errands\n\
+Inbox:\n\
+\t- Call mom @phone\n\
+Someday:\n\
+\t- Learn piano\n";
+
+// プロジェクト・インデントされたタスク・メモ・タグが項目に対応付けられることを確認
+#[test]
+fn test_import_maps_outline() {
+    let mut data = GtdData::new();
+    let summary = import_text(&mut data, OUTLINE);
+    assert_eq!(
+        summary.added,
+        vec![
+            "website",
+            "@computer",
+            "draft-the-proposal",
+            "launch",
+            "book-the-domain",
+            "@errands",
+            "review-work-someone-anna",
+            "@phone",
+            "call-mom",
+            "learn-piano"
+        ]
+    );
+
+    assert_eq!(
+        data.find_by_id("website").unwrap().status,
+        NotaStatus::project
+    );
+    let draft = data.find_by_id("draft-the-proposal").unwrap();
+    assert_eq!(draft.status, NotaStatus::next_action);
+    assert_eq!(draft.project.as_deref(), Some("website"));
+    assert_eq!(draft.context.as_deref(), Some("@computer"));
+    assert_eq!(
+        draft.notes.as_deref(),
+        Some("Ask Anna for last year's numbers\nand the budget")
+    );
+
+    let launch = data.find_by_id("launch").unwrap();
+    assert_eq!(launch.status, NotaStatus::calendar);
+    assert_eq!(launch.start_date, Some(date("2025-03-10")));
+
+    // サブタスクも同じプロジェクトに属する
+    let domain = data.find_by_id("book-the-domain").unwrap();
+    assert_eq!(domain.status, NotaStatus::done);
+    assert_eq!(domain.project.as_deref(), Some("website"));
+    assert_eq!(domain.updated_at, date("2025-03-02"));
+
+    // 最後のタグだけがコンテキストになり、他はタイトルに残る
+    let review = data.find_by_id("review-work-someone-anna").unwrap();
+    assert_eq!(review.title, "Review @work @someone(Anna)");
+    assert_eq!(review.context.as_deref(), Some("@errands"));
+
+    // Inbox / Someday 見出しはプロジェクトにならない
+    let call = data.find_by_id("call-mom").unwrap();
+    assert_eq!(call.status, NotaStatus::inbox);
+    assert_eq!(call.project, None);
+    assert_eq!(
+        data.find_by_id("learn-piano").unwrap().status,
+        NotaStatus::someday
+    );
+    assert!(data.find_by_id("inbox").is_none());
+}
+
+// 同じアウトラインを再度読み込むと重複せず、変更は同じ項目に反映されることを確認
+#[test]
+fn test_reimport_updates_items() {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "home".to_string(),
+        title: "Home".to_string(),
+        status: NotaStatus::context,
+        ..Default::default()
+    });
+    import_text(&mut data, OUTLINE);
+    let count = data.iter().count();
+
+    let summary = import_text(&mut data, OUTLINE);
+    assert!(summary.added.is_empty() && summary.updated.is_empty());
+    assert_eq!(data.iter().count(), count);
+
+    let edited = OUTLINE
+        .replace(
+            "Draft the proposal @computer @flagged",
+            "Draft the proposal @done(2025-03-20)",
+        )
+        .replace("Call mom @phone", "Call mom @home @id(mom)");
+    let summary = import_text(&mut data, &edited);
+    assert_eq!(summary.updated, vec!["draft-the-proposal"]);
+    assert_eq!(summary.added, vec!["mom"]);
+    let draft = data.find_by_id("draft-the-proposal").unwrap();
+    assert_eq!(draft.status, NotaStatus::done);
+    // 書かれていないフィールドは保存済みの値を保つ
+    assert_eq!(draft.context.as_deref(), Some("@computer"));
+    assert_eq!(draft.updated_at, date("2025-03-20"));
+    // 既存のコンテキストは @ の有無にかかわらず再利用される
+    assert_eq!(
+        data.find_by_id("mom").unwrap().context.as_deref(),
+        Some("home")
+    );
+}
+
+// 不正な行は行番号付きのエラーになり、データは変更されないことを確認
+#[test]
+fn test_import_errors() {
+    for (text, expected) in [
+        ("A stray note\n", "line 1: note 'A stray note' is outside"),
+        (
+            "Work:\n\t- Ship @due(soon)\n",
+            "line 2: @due(soon) is not a YYYY-MM-DD date",
+        ),
+        ("Work:\n\t- Ship @start\n", "line 2: @start needs a date"),
+        ("Work:\n\t- @home\n", "line 2: the task has no text"),
+    ] {
+        let mut data = GtdData::new();
+        let error = import::import(&mut data, text, ImportFormat::TaskPaper, today()).unwrap_err();
+        assert!(error.to_string().contains(expected), "{}", error);
+        assert_eq!(data.iter().count(), 0);
+    }
+    assert_eq!(
+        ImportFormat::from_path("Things.taskpaper"),
+        Some(ImportFormat::TaskPaper)
+    );
+    assert_eq!("taskpaper".parse(), Ok(ImportFormat::TaskPaper));
+}
@@ -0,0 +1,251 @@
+//! Integration tests for checklist templates
+//!
+//! These tests cover parsing the checklist of a template, stamping out tasks
+//! with `instantiate_template`, and keeping templates out of task views.
+
+use gtd_mcp::templates;
+use gtd_mcp::{GtdServerHandler, Nota, NotaStatus, Storage};
+use mcp_attr::Result as McpResult;
+use std::collections::HashMap;
+use tempfile::NamedTempFile;
+
+const PACKING: &str = "Things to take on every trip.\n\
+                       - [ ] Passport\n\
+                       - Charger\n\
+                       * Book flights\n\
+                       2. Check in online\n\
+                       -not an item\n\
+                       - [x]   \n";
+
+// リスト行だけがチェックリスト項目になり、チェックボックスが取り除かれることを確認
+#[test]
+fn test_checklist() {
+    assert_eq!(
+        templates::checklist(PACKING),
+        vec!["Passport", "Charger", "Book flights", "Check in online"]
+    );
+    assert!(templates::checklist("Just a description").is_empty());
+}
+
+// テンプレートから次のアクションが作られ、ID が衝突しないことを確認
+#[tokio::test]
+async fn test_instantiate_template() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    {
+        let mut data = handler.data.write().await;
+        data.add(Nota {
+            id: "@errands".to_string(),
+            title: "Errands".to_string(),
+            status: NotaStatus::context,
+            ..Default::default()
+        });
+    }
+    handler
+        .inbox(
+            "travel-packing".to_string(),
+            "Travel packing".to_string(),
+            "template".to_string(),
+            None,
+            Some("@errands".to_string()),
+            Some(PACKING.to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    handler.data.write().await.add(Nota {
+        id: "trip-paris-passport".to_string(),
+        title: "Renew passport".to_string(),
+        ..Default::default()
+    });
+
+    let response = handler
+        .instantiate_template("travel-packing".to_string(), "trip-paris".to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        "Created 4 tasks from template 'travel-packing':\n\
+         - [trip-paris-passport-2] Passport\n\
+         - [trip-paris-charger] Charger\n\
+         - [trip-paris-book-flights] Book flights\n\
+         - [trip-paris-check-in-online] Check in online"
+    );
+
+    let data = handler.data.read().await;
+    let task = data.find_by_id("trip-paris-charger").unwrap();
+    assert_eq!(task.status, NotaStatus::next_action);
+    assert_eq!(task.context.as_deref(), Some("@errands"));
+    // テンプレート自体はタスクとして扱われない
+    let template = data.find_by_id("travel-packing").unwrap();
+    assert!(template.is_template());
+    assert!(!template.is_task());
+    drop(data);
+
+    // テンプレートは [[template]] 配列に保存される
+    let content = std::fs::read_to_string(temp_file.path()).unwrap();
+    assert!(content.contains("[[template]]"), "{}", content);
+    let reloaded = Storage::new(temp_file.path(), false).load().unwrap();
+    assert!(reloaded.find_by_id("travel-packing").unwrap().is_template());
+}
+
+// テンプレートでない項目・項目のないテンプレート・空の new_id は拒否されることを確認
+#[tokio::test]
+async fn test_instantiate_template_errors() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    {
+        let mut data = handler.data.write().await;
+        data.add(Nota {
+            id: "plain".to_string(),
+            title: "Plain task".to_string(),
+            notes: Some("- item".to_string()),
+            ..Default::default()
+        });
+        data.add(Nota {
+            id: "empty".to_string(),
+            title: "Empty template".to_string(),
+            status: NotaStatus::template,
+            ..Default::default()
+        });
+    }
+
+    let error = |result: McpResult<String>| result.unwrap_err().to_error_object(false).message;
+    let message = error(
+        handler
+            .instantiate_template("plain".to_string(), "x".to_string())
+            .await,
+    );
+    assert!(message.contains("'plain' is not a template"), "{}", message);
+    let message = error(
+        handler
+            .instantiate_template("empty".to_string(), "x".to_string())
+            .await,
+    );
+    assert!(message.contains("has no checklist items"), "{}", message);
+    let message = error(
+        handler
+            .instantiate_template("missing".to_string(), "x".to_string())
+            .await,
+    );
+    assert!(
+        message.contains("Template 'missing' not found"),
+        "{}",
+        message
+    );
+    let message = error(
+        handler
+            .instantiate_template("empty".to_string(), " ".to_string())
+            .await,
+    );
+    assert!(message.contains("new_id is empty"), "{}", message);
+}
+
+// プレースホルダーの抽出と置換を確認
+#[test]
+fn test_placeholders_and_fill() {
+    let text = "Onboard {client} ({client}, {region}) {not a name} {}";
+    assert_eq!(templates::placeholders(text), vec!["client", "region"]);
+    let params = HashMap::from([("client".to_string(), "Acme".to_string())]);
+    assert_eq!(
+        templates::fill(text, &params),
+        "Onboard Acme (Acme, {region}) {not a name} {}"
+    );
+    assert_eq!(
+        templates::description(PACKING),
+        "Things to take on every trip.\n-not an item"
+    );
+}
+
+const ONBOARDING: &str = "Onboarding steps for {client}.\n\
+                          - Send welcome mail to {client}\n\
+                          - Set up {client} workspace\n";
+
+// テンプレートからプロジェクトとタスクが一度のコミットで作られることを確認
+#[tokio::test]
+async fn test_new_project_from_template() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    handler.data.write().await.add(Nota {
+        id: "client-onboarding".to_string(),
+        title: "Onboard {client}".to_string(),
+        status: NotaStatus::template,
+        notes: Some(ONBOARDING.to_string()),
+        ..Default::default()
+    });
+
+    let response = handler
+        .new_project_from_template(
+            "client-onboarding".to_string(),
+            "onboarding-acme".to_string(),
+            Some(HashMap::from([("client".to_string(), "Acme".to_string())])),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        "Created project [onboarding-acme] Onboard Acme from template 'client-onboarding' with 2 tasks:\n\
+         - [onboarding-acme-send-welcome-mail-to-acme] Send welcome mail to Acme\n\
+         - [onboarding-acme-set-up-acme-workspace] Set up Acme workspace"
+    );
+
+    let data = handler.data.read().await;
+    let project = data.find_project_by_id("onboarding-acme").unwrap();
+    assert_eq!(project.notes.as_deref(), Some("Onboarding steps for Acme."));
+    let task = data
+        .find_by_id("onboarding-acme-set-up-acme-workspace")
+        .unwrap();
+    assert_eq!(task.status, NotaStatus::next_action);
+    assert_eq!(task.project.as_deref(), Some("onboarding-acme"));
+}
+
+// 値のないプレースホルダー・既存の project_id は拒否され、何も作られないことを確認
+#[tokio::test]
+async fn test_new_project_from_template_errors() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    handler.data.write().await.add(Nota {
+        id: "client-onboarding".to_string(),
+        title: "Onboard {client}".to_string(),
+        status: NotaStatus::template,
+        notes: Some(ONBOARDING.to_string()),
+        ..Default::default()
+    });
+
+    let error = |result: McpResult<String>| result.unwrap_err().to_error_object(false).message;
+    let message = error(
+        handler
+            .new_project_from_template(
+                "client-onboarding".to_string(),
+                "onboarding-acme".to_string(),
+                None,
+            )
+            .await,
+    );
+    assert!(
+        message.contains("needs a value for {client}"),
+        "{}",
+        message
+    );
+    assert!(
+        handler
+            .data
+            .read()
+            .await
+            .find_by_id("onboarding-acme")
+            .is_none()
+    );
+
+    let message = error(
+        handler
+            .new_project_from_template(
+                "client-onboarding".to_string(),
+                "client-onboarding".to_string(),
+                Some(HashMap::from([("client".to_string(), "Acme".to_string())])),
+            )
+            .await,
+    );
+    assert!(message.contains("already exists"), "{}", message);
+}
@@ -0,0 +1,153 @@
+//! Unit tests for the todo.txt export and import
+//!
+//! These tests verify the mapping of completion, priority, dates, projects and
+//! contexts in both directions.
+
+mod common;
+
+use common::{date, today};
+use gtd_mcp::import::{self, ImportFormat};
+use gtd_mcp::mirror::{self, MirrorFormat};
+use gtd_mcp::{GtdData, Nota, NotaStatus, todotxt};
+
+fn import_text(data: &mut GtdData, text: &str) -> import::ImportSummary {
+    import::import(data, text, ImportFormat::TodoTxt, today()).unwrap()
+}
+
+// todo.txt の各要素が項目のフィールドに対応付けられることを確認
+#[test]
+fn test_import_maps_fields() {
+    let mut data = GtdData::new();
+    let summary = import_text(
+        &mut data,
+        "x 2025-03-02 2025-03-01 Call mom +family @phone\n\
+         (B) 2025-03-01 Draft the +old proposal +website @computer\n\
+         Dentist due:2025-03-10\n\
+         Read a book status:someday id:book\n\
+         \n",
+    );
+    assert_eq!(
+        summary.added,
+        vec![
+            "family",
+            "@phone",
+            "call-mom",
+            "website",
+            "@computer",
+            "draft-the-old-proposal",
+            "dentist",
+            "book"
+        ]
+    );
+
+    let call = data.find_by_id("call-mom").unwrap();
+    assert_eq!(call.status, NotaStatus::done);
+    assert_eq!(call.updated_at, date("2025-03-02"));
+    assert_eq!(call.created_at, date("2025-03-01"));
+    assert_eq!(call.project.as_deref(), Some("family"));
+    assert_eq!(call.context.as_deref(), Some("@phone"));
+    assert_eq!(
+        data.find_by_id("family").unwrap().status,
+        NotaStatus::project
+    );
+    assert_eq!(
+        data.find_by_id("@phone").unwrap().status,
+        NotaStatus::context
+    );
+
+    // 優先度付きは next_action、最後の +project 以外はタイトルに残る
+    let draft = data.find_by_id("draft-the-old-proposal").unwrap();
+    assert_eq!(draft.status, NotaStatus::next_action);
+    assert_eq!(draft.title, "Draft the +old proposal");
+    assert_eq!(draft.project.as_deref(), Some("website"));
+
+    let dentist = data.find_by_id("dentist").unwrap();
+    assert_eq!(dentist.status, NotaStatus::calendar);
+    assert_eq!(dentist.start_date, Some(date("2025-03-10")));
+    assert_eq!(dentist.created_at, today());
+
+    assert_eq!(data.find_by_id("book").unwrap().status, NotaStatus::someday);
+}
+
+// 既存のコンテキストは @ の有無にかかわらず再利用されることを確認
+#[test]
+fn test_import_reuses_existing_contexts() {
+    let mut data = GtdData::new();
+    data.add(Nota {
+        id: "home".to_string(),
+        title: "Home".to_string(),
+        status: NotaStatus::context,
+        ..Default::default()
+    });
+    let summary = import_text(&mut data, "Water plants @home\n");
+    assert_eq!(summary.added, vec!["water-plants"]);
+    assert_eq!(
+        data.find_by_id("water-plants").unwrap().context.as_deref(),
+        Some("home")
+    );
+}
+
+// 書き出した内容を読み込み直すと変更がなく、編集は ID で反映されることを確認
+#[test]
+fn test_render_roundtrip_and_update() {
+    let mut data = GtdData::new();
+    import_text(
+        &mut data,
+        "x 2025-03-02 2025-03-01 Call mom +family @phone\n\
+         (A) 2025-03-01 Draft proposal +website\n\
+         2025-03-01 Dentist due:2025-03-10\n\
+         2025-03-01 Waiting on invoice status:waiting_for\n",
+    );
+    data.add(Nota {
+        id: "old".to_string(),
+        title: "Old".to_string(),
+        status: NotaStatus::trash,
+        ..Default::default()
+    });
+
+    assert_eq!(
+        MirrorFormat::from_path("sync/todo.txt"),
+        Some(MirrorFormat::TodoTxt)
+    );
+    assert_eq!(
+        ImportFormat::from_path("work.todo.txt"),
+        Some(ImportFormat::TodoTxt)
+    );
+    let rendered = mirror::render(&data, MirrorFormat::TodoTxt, today()).unwrap();
+    assert_eq!(rendered, todotxt::render(&data));
+    assert!(rendered.contains("x 2025-03-02 2025-03-01 Call mom +family @phone id:call-mom\n"));
+    assert!(rendered.contains("(A) 2025-03-01 Draft proposal +website id:draft-proposal\n"));
+    assert!(rendered.contains("Dentist due:2025-03-10 status:calendar id:dentist\n"));
+    assert!(rendered.contains("status:waiting_for id:waiting-on-invoice\n"));
+    // プロジェクト・コンテキスト・ゴミ箱は行にならない
+    assert_eq!(rendered.lines().count(), 4);
+
+    let summary = import_text(&mut data, &rendered);
+    assert_eq!(summary.unchanged, 4);
+
+    // スマートフォンで完了にした行は done になる
+    let edited = rendered.replace(
+        "(A) 2025-03-01 Draft proposal",
+        "x 2025-03-20 2025-03-01 Draft proposal",
+    );
+    let summary = import_text(&mut data, &edited);
+    assert_eq!(summary.updated, vec!["draft-proposal"]);
+    let draft = data.find_by_id("draft-proposal").unwrap();
+    assert_eq!(draft.status, NotaStatus::done);
+    assert_eq!(draft.updated_at, date("2025-03-20"));
+}
+
+// 不正な行は行番号付きのエラーになることを確認
+#[test]
+fn test_import_errors() {
+    for (text, expected) in [
+        ("Fine\nBad due:tomorrow\n", "line 2: due 'tomorrow'"),
+        ("Task status:started\n", "line 1: Invalid status 'started'"),
+        ("(A) +website\n", "line 1: the task has no text"),
+    ] {
+        let mut data = GtdData::new();
+        let error = import::import(&mut data, text, ImportFormat::TodoTxt, today()).unwrap_err();
+        assert!(error.to_string().contains(expected), "{}", error);
+        assert_eq!(data.iter().count(), 0);
+    }
+}
@@ -0,0 +1,89 @@
+//! Integration tests for the tool safety hints
+//!
+//! These tests list the tools of a plain handler, as stdio serves it, and
+//! through [`SharedServer`], as the daemon and the HTTP transports do, and check that each tool carries hints and that reading and
+//! deleting tools are told apart.
+
+use gtd_mcp::GtdServerHandler;
+use gtd_mcp::daemon::SharedServer;
+use mcp_attr::client::McpClient;
+use mcp_attr::schema::{Tool, ToolAnnotations};
+use std::sync::Arc;
+
+async fn list_tools(handler: GtdServerHandler) -> Vec<Tool> {
+    let client = McpClient::with_server(handler).await.unwrap();
+    client.tools_list(None).await.unwrap().tools
+}
+
+fn annotations<'a>(tools: &'a [Tool], name: &str) -> &'a ToolAnnotations {
+    let tool = tools.iter().find(|tool| tool.name == name).unwrap();
+    tool.annotations.as_ref().unwrap()
+}
+
+// 全てのツールにヒントが付き、読み取り専用・破壊的・冪等が区別されることを確認
+#[tokio::test]
+async fn test_every_tool_is_annotated() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let tools = list_tools(GtdServerHandler::new(path.to_str().unwrap(), false).unwrap()).await;
+
+    for tool in &tools {
+        assert!(tool.annotations.is_some(), "{} has no hints", tool.name);
+    }
+
+    for name in ["list", "get", "suggest"] {
+        let hints = annotations(&tools, name);
+        assert_eq!(hints.read_only_hint, Some(true), "{}", name);
+        assert_eq!(hints.destructive_hint, None, "{}", name);
+    }
+    for name in ["empty_trash", "restore_backup", "reload"] {
+        let hints = annotations(&tools, name);
+        assert_eq!(hints.read_only_hint, Some(false), "{}", name);
+        assert_eq!(hints.destructive_hint, Some(true), "{}", name);
+    }
+
+    let hints = annotations(&tools, "change_status");
+    assert_eq!(hints.destructive_hint, Some(false));
+    assert_eq!(hints.idempotent_hint, Some(true));
+    assert_eq!(annotations(&tools, "inbox").idempotent_hint, Some(false));
+    assert_eq!(annotations(&tools, "sync").open_world_hint, Some(true));
+    assert_eq!(annotations(&tools, "update").open_world_hint, Some(false));
+}
+
+// 待ち項目を次のアクションに戻す設定では suggest が書き込むツールになることを確認
+#[tokio::test]
+async fn test_suggest_writes_when_escalating() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false)
+        .unwrap()
+        .with_escalation(None, true);
+    let tools = list_tools(handler).await;
+
+    let hints = annotations(&tools, "suggest");
+    assert_eq!(hints.read_only_hint, Some(false));
+    assert_eq!(hints.destructive_hint, Some(false));
+}
+
+// SharedServer 経由でも素のハンドラと同じヒントが付くことを確認
+#[tokio::test]
+async fn test_shared_server_lists_the_same_hints() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let open = || GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    let plain = list_tools(open()).await;
+    let client = McpClient::with_server(SharedServer::new(Arc::new(open())))
+        .await
+        .unwrap();
+    let shared = client.tools_list(None).await.unwrap().tools;
+
+    assert_eq!(plain.len(), shared.len());
+    for tool in &plain {
+        assert_eq!(
+            annotations(&shared, &tool.name),
+            tool.annotations.as_ref().unwrap(),
+            "{}",
+            tool.name
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Integration tests for the transaction tools
+//!
+//! These tests cover rolling back every change of an aborted transaction,
+//! saving a committed one at once, rolling back a transaction whose commit
+//! fails, and keeping a transaction to the MCP session that began it.
+
+use gtd_mcp::daemon::SharedServer;
+use gtd_mcp::{GtdServerHandler, NotaStatus, Storage};
+use mcp_attr::client::McpClient;
+use mcp_attr::schema::CallToolRequestParams;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+async fn capture(handler: &GtdServerHandler, id: &str) {
+    handler
+        .inbox(
+            id.to_string(),
+            id.replace('-', " "),
+            "next_action".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+fn inbox(id: &str) -> CallToolRequestParams {
+    CallToolRequestParams::new("inbox")
+        .with_argument("id", id)
+        .unwrap()
+        .with_argument("title", id.replace('-', " "))
+        .unwrap()
+        .with_argument("status", "next_action")
+        .unwrap()
+}
+
+async fn session(handler: &Arc<GtdServerHandler>) -> McpClient {
+    McpClient::with_server(SharedServer::new(handler.clone()))
+        .await
+        .unwrap()
+}
+
+// abort で開始時のデータに戻り、commit で全ての変更がまとめて保存されることを確認
+#[tokio::test]
+async fn test_abort_and_commit() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    capture(&handler, "buy-milk").await;
+
+    handler.begin().await.unwrap();
+    assert!(handler.begin().await.is_err());
+    capture(&handler, "draft-plan").await;
+    handler
+        .change_status(vec!["buy-milk".to_string()], "done".to_string(), None, None)
+        .await
+        .unwrap();
+    let response = handler.abort().await.unwrap();
+    assert!(response.contains("2 changes rolled back"), "{}", response);
+    let data = handler.data.read().await;
+    assert!(data.find_by_id("draft-plan").is_none());
+    assert_eq!(
+        data.find_by_id("buy-milk").unwrap().status,
+        NotaStatus::next_action
+    );
+    assert!(!data.is_dirty(&Storage::serialize(&data).unwrap()));
+    drop(data);
+    assert!(handler.abort().await.is_err());
+
+    handler.begin().await.unwrap();
+    capture(&handler, "draft-plan").await;
+    capture(&handler, "send-plan").await;
+    assert!(!fs::read_to_string(&path).unwrap().contains("draft-plan"));
+    let response = handler.commit().await.unwrap();
+    assert!(response.contains("2 changes saved"), "{}", response);
+    let saved = Storage::new(&path, false).load().unwrap();
+    assert!(saved.find_by_id("draft-plan").is_some());
+    assert!(saved.find_by_id("send-plan").is_some());
+    assert!(handler.commit().await.is_err());
+
+    let client = McpClient::with_server(handler).await.unwrap();
+    let tools = client.tools_list(None).await.unwrap();
+    for name in ["begin_transaction", "commit", "abort"] {
+        assert!(tools.tools.iter().any(|tool| tool.name == name), "{}", name);
+    }
+}
+
+// commit の保存に失敗すると全ての変更が取り消されることを確認
+#[tokio::test]
+async fn test_failed_commit_rolls_back() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    capture(&handler, "buy-milk").await;
+
+    handler.begin().await.unwrap();
+    capture(&handler, "draft-plan").await;
+    // トランザクション中に別のプロセスがファイルを書き換える
+    fs::write(&path, "format_version = 3\n").unwrap();
+
+    let error = format!("{:?}", handler.commit().await.unwrap_err());
+    assert!(error.contains("rolled back"), "{}", error);
+    let data = handler.data.read().await;
+    assert!(data.find_by_id("draft-plan").is_none());
+    assert!(data.find_by_id("buy-milk").is_some());
+    drop(data);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "format_version = 3\n");
+}
+
+// トランザクションは開始したセッションのもので、他のセッションは読めるが変更・コミットできないことを確認
+#[tokio::test]
+async fn test_transaction_belongs_to_its_session() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = Arc::new(GtdServerHandler::new(path.to_str().unwrap(), false).unwrap());
+    let owner = session(&handler).await;
+    let other = session(&handler).await;
+
+    owner
+        .tools_call(CallToolRequestParams::new("begin_transaction"))
+        .await
+        .unwrap();
+    owner.tools_call(inbox("draft-plan")).await.unwrap();
+
+    let error = format!(
+        "{:?}",
+        other.tools_call(inbox("buy-milk")).await.unwrap_err()
+    );
+    assert!(
+        error.contains("Another client has a transaction open"),
+        "{}",
+        error
+    );
+    let error = format!(
+        "{:?}",
+        other
+            .tools_call(CallToolRequestParams::new("commit"))
+            .await
+            .unwrap_err()
+    );
+    assert!(
+        error.contains("Another client has a transaction open"),
+        "{}",
+        error
+    );
+    other
+        .tools_call(CallToolRequestParams::new("list"))
+        .await
+        .unwrap();
+    assert!(handler.data.read().await.find_by_id("buy-milk").is_none());
+    assert!(!path.exists() || !fs::read_to_string(&path).unwrap().contains("draft-plan"));
+
+    owner
+        .tools_call(CallToolRequestParams::new("commit"))
+        .await
+        .unwrap();
+    other.tools_call(inbox("buy-milk")).await.unwrap();
+    let saved = Storage::new(&path, false).load().unwrap();
+    assert!(saved.find_by_id("draft-plan").is_some());
+    assert!(saved.find_by_id("buy-milk").is_some());
+}
+
+// セッションが終わると、そのトランザクションは取り消されることを確認
+#[tokio::test]
+async fn test_ended_session_aborts_its_transaction() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = Arc::new(GtdServerHandler::new(path.to_str().unwrap(), false).unwrap());
+    let owner = session(&handler).await;
+    owner
+        .tools_call(CallToolRequestParams::new("begin_transaction"))
+        .await
+        .unwrap();
+    owner.tools_call(inbox("draft-plan")).await.unwrap();
+    drop(owner);
+
+    for _ in 0..100 {
+        if !handler.in_transaction() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(!handler.in_transaction());
+    assert!(handler.data.read().await.find_by_id("draft-plan").is_none());
+    let other = session(&handler).await;
+    other.tools_call(inbox("buy-milk")).await.unwrap();
+    let saved = Storage::new(&path, false).load().unwrap();
+    assert!(saved.find_by_id("draft-plan").is_none());
+    assert!(saved.find_by_id("buy-milk").is_some());
+}
+
+// タイムアウトを過ぎて放置されたトランザクションは、他のセッションの変更で取り消されることを確認
+#[tokio::test]
+async fn test_idle_transaction_is_aborted_by_another_session() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = Arc::new(
+        GtdServerHandler::new(path.to_str().unwrap(), false)
+            .unwrap()
+            .with_transaction_timeout(Duration::ZERO),
+    );
+    let owner = session(&handler).await;
+    let other = session(&handler).await;
+    owner
+        .tools_call(CallToolRequestParams::new("begin_transaction"))
+        .await
+        .unwrap();
+    owner.tools_call(inbox("draft-plan")).await.unwrap();
+
+    other.tools_call(inbox("buy-milk")).await.unwrap();
+    let saved = Storage::new(&path, false).load().unwrap();
+    assert!(saved.find_by_id("draft-plan").is_none());
+    assert!(saved.find_by_id("buy-milk").is_some());
+    let error = format!(
+        "{:?}",
+        owner
+            .tools_call(CallToolRequestParams::new("commit"))
+            .await
+            .unwrap_err()
+    );
+    assert!(error.contains("No transaction is open"), "{}", error);
+}
+
+// トランザクション中の sync は拒否され、トランザクションの変更が残ることを確認
+#[tokio::test]
+async fn test_sync_is_refused_during_a_transaction() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("gtd.toml");
+    let handler = GtdServerHandler::new(path.to_str().unwrap(), false).unwrap();
+    handler.begin().await.unwrap();
+    capture(&handler, "draft-plan").await;
+
+    let error = format!("{:?}", handler.sync().await.unwrap_err());
+    assert!(
+        error.contains("Cannot sync while a transaction is open"),
+        "{}",
+        error
+    );
+    assert!(handler.data.read().await.find_by_id("draft-plan").is_some());
+    handler.commit().await.unwrap();
+    let saved = Storage::new(&path, false).load().unwrap();
+    assert!(saved.find_by_id("draft-plan").is_some());
+}
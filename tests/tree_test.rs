@@ -0,0 +1,104 @@
+//! Unit tests for the tree view
+//!
+//! These tests check the nesting of projects, sub-projects and tasks, the
+//! status markers, the optional done tasks, and that a cycle of sub-projects
+//! does not loop.
+
+use gtd_mcp::tree;
+use gtd_mcp::{GtdData, GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn item(id: &str, status: NotaStatus, project: Option<&str>) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.replace('-', " "),
+        status,
+        project: project.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn sample() -> GtdData {
+    let mut data = GtdData::new();
+    data.add(item("website", NotaStatus::project, None));
+    data.add(item("launch", NotaStatus::project, Some("website")));
+    data.add(item("draft-copy", NotaStatus::next_action, Some("website")));
+    data.add(item("ask-legal", NotaStatus::waiting_for, Some("launch")));
+    data.add(item("pick-domain", NotaStatus::done, Some("website")));
+    data.add(item("old-logo", NotaStatus::trash, Some("website")));
+    data.add(Nota {
+        context: Some("@phone".to_string()),
+        ..item("call-bank", NotaStatus::inbox, None)
+    });
+    data.add(item("@phone", NotaStatus::context, None));
+    data
+}
+
+// プロジェクト・サブプロジェクト・タスクが入れ子になり、状態が印で表されることを確認
+#[test]
+fn test_render_nests_projects_and_tasks() {
+    let output = tree::render(&sample(), false);
+    assert_eq!(
+        output,
+        "- [website] website (1/2 done (50%))\n\
+         \x20 - [launch] launch (0/1 done (0%))\n\
+         \x20   - [w] [ask-legal] ask legal\n\
+         \x20 - [ ] [draft-copy] draft copy\n\
+         \n\
+         No project:\n\
+         - [i] [call-bank] call bank (@phone)\n\
+         \n\
+         Legend: [i] inbox, [ ] next_action, [w] waiting_for, [c] calendar, [l] later, [s] someday, [r] reference\n"
+    );
+}
+
+// include_done を指定すると完了タスクも表示されることを確認
+#[test]
+fn test_render_include_done() {
+    let output = tree::render(&sample(), true);
+    assert!(
+        output.contains("  - [x] [pick-domain] pick domain\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("[x] done"), "{}", output);
+    assert!(!output.contains("old-logo"), "{}", output);
+}
+
+// サブプロジェクトが循環していても各プロジェクトが一度だけ表示されることを確認
+#[test]
+fn test_render_project_cycle() {
+    let mut data = GtdData::new();
+    data.add(item("alpha", NotaStatus::project, Some("beta")));
+    data.add(item("beta", NotaStatus::project, Some("alpha")));
+    data.add(item("self-ref", NotaStatus::project, Some("self-ref")));
+
+    let output = tree::render(&data, false);
+    assert_eq!(output.matches("[alpha]").count(), 1, "{}", output);
+    assert_eq!(output.matches("[beta]").count(), 1, "{}", output);
+    assert!(output.starts_with("- [self-ref]"), "{}", output);
+}
+
+// 項目がなければその旨を表示することを確認
+#[test]
+fn test_render_empty() {
+    let output = tree::render(&GtdData::new(), false);
+    assert!(output.starts_with("No projects yet\n"), "{}", output);
+}
+
+// tree ツールが木構造を返すことを確認
+#[tokio::test]
+async fn test_tree_tool() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false).unwrap();
+    {
+        let mut data = handler.data.write().await;
+        data.add(item("website", NotaStatus::project, None));
+        data.add(item("draft-copy", NotaStatus::done, Some("website")));
+    }
+
+    let output = handler.handle_tree(None).await.unwrap();
+    assert!(!output.contains("draft-copy"), "{}", output);
+    let output = handler.handle_tree(Some(true)).await.unwrap();
+    assert!(output.contains("- [x] [draft-copy]"), "{}", output);
+}
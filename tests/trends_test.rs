@@ -0,0 +1,100 @@
+//! Unit tests for weekly productivity trends
+//!
+//! These tests verify how items are counted per week and the rendered table.
+
+mod common;
+
+use common::date;
+use gtd_mcp::trends::{self, WeekStats};
+use gtd_mcp::{GtdData, Nota, NotaStatus};
+
+fn item(id: &str, status: NotaStatus, created: &str, updated: &str) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: id.to_string(),
+        status,
+        created_at: date(created),
+        updated_at: date(updated),
+        ..Default::default()
+    }
+}
+
+// 週ごとの取り込み・整理・完了の件数と完了までの平均日数を確認
+#[test]
+fn test_compute_weekly_stats() {
+    let mut data = GtdData::new();
+    data.add(item("a", NotaStatus::done, "2025-02-24", "2025-02-28"));
+    data.add(item("b", NotaStatus::inbox, "2025-02-25", "2025-02-25"));
+    data.add(item("c", NotaStatus::done, "2025-03-01", "2025-03-11"));
+    data.add(item("d", NotaStatus::done, "2025-03-10", "2025-03-10"));
+    data.add(item(
+        "e",
+        NotaStatus::next_action,
+        "2025-03-11",
+        "2025-03-11",
+    ));
+    // 期間外と、プロジェクト・コンテキストは数えない
+    data.add(item("old", NotaStatus::done, "2025-01-01", "2025-01-02"));
+    data.add(item(
+        "proj",
+        NotaStatus::project,
+        "2025-03-10",
+        "2025-03-10",
+    ));
+    data.add(item(
+        "@home",
+        NotaStatus::context,
+        "2025-03-10",
+        "2025-03-10",
+    ));
+
+    let stats = trends::compute(&data, date("2025-03-12"), 3);
+    assert_eq!(
+        stats,
+        vec![
+            WeekStats {
+                start: date("2025-02-24"),
+                captured: 3,
+                clarified: 2,
+                completed: 1,
+                days_to_done: Some(4.0),
+            },
+            WeekStats {
+                start: date("2025-03-03"),
+                ..Default::default()
+            },
+            WeekStats {
+                start: date("2025-03-10"),
+                captured: 2,
+                clarified: 2,
+                completed: 2,
+                days_to_done: Some(5.0),
+            },
+        ]
+    );
+}
+
+// 表と合計の行が出力されることを確認
+#[test]
+fn test_render_trends() {
+    let stats = vec![
+        WeekStats {
+            start: date("2025-03-03"),
+            captured: 4,
+            clarified: 3,
+            completed: 1,
+            days_to_done: Some(2.0),
+        },
+        WeekStats {
+            start: date("2025-03-10"),
+            captured: 2,
+            ..Default::default()
+        },
+    ];
+    let out = trends::render(&stats, 1);
+    assert!(out.starts_with("# Trends: last 2 weeks\n"));
+    assert!(out.contains("| 2025-03-03 | 4 | 3 | 1 | 2.0 |\n"));
+    assert!(out.contains("| 2025-03-10 | 2 | 0 | 0 | - |\n"));
+    assert!(out.contains("Captured 6, clarified 3 of them, completed 1. Average 2.0 days"));
+    assert!(out.contains("Per week: 3.0 captured, 0.5 completed. 1 item in the inbox now."));
+}
@@ -0,0 +1,145 @@
+//! Unit tests for webhook notifications
+//!
+//! These tests verify how changes between two versions of the data are
+//! detected, the JSON payload sent for each, and the delivery after a save
+//! to a local HTTP endpoint.
+
+mod common;
+
+use common::date;
+use gtd_mcp::storage::Storage;
+use gtd_mcp::webhook::{self, ChangeKind};
+use gtd_mcp::{GtdData, Nota, NotaStatus};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn nota(id: &str, title: &str, status: NotaStatus) -> Nota {
+    Nota {
+        id: id.to_string(),
+        title: title.to_string(),
+        status,
+        created_at: date("2025-03-01"),
+        updated_at: date("2025-03-01"),
+        ..Default::default()
+    }
+}
+
+/// Accept one POST on a local port and hand its body to the returned channel
+fn listen_once() -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        sender.send(String::from_utf8(body).unwrap()).unwrap();
+    });
+    (url, receiver)
+}
+
+// 追加・更新・ステータス変更・削除がそれぞれ検出されることを確認
+#[test]
+fn test_changes_detects_each_kind() {
+    let mut before = GtdData::new();
+    before.add(nota("call-bob", "Call Bob", NotaStatus::next_action));
+    before.add(nota("buy-milk", "Buy milk", NotaStatus::inbox));
+    before.add(nota("old-idea", "Old idea", NotaStatus::someday));
+    before.add(nota("unchanged", "Unchanged", NotaStatus::inbox));
+
+    let mut after = before.clone();
+    let mut done = nota("call-bob", "Call Bob", NotaStatus::done);
+    done.updated_at = date("2025-03-02");
+    after.update("call-bob", done);
+    let mut renamed = nota("buy-milk", "Buy oat milk", NotaStatus::inbox);
+    renamed.updated_at = date("2025-03-02");
+    after.update("buy-milk", renamed);
+    after.remove_nota("old-idea");
+    after.add(nota("new-task", "New task", NotaStatus::inbox));
+
+    let changes = webhook::changes(&before, &after);
+    let kinds: Vec<(&str, ChangeKind)> = changes
+        .iter()
+        .map(|c| (c.nota.id.as_str(), c.kind))
+        .collect();
+    assert_eq!(kinds.len(), 4);
+    assert!(kinds.contains(&("call-bob", ChangeKind::StatusChanged)));
+    assert!(kinds.contains(&("buy-milk", ChangeKind::Updated)));
+    assert!(kinds.contains(&("new-task", ChangeKind::Added)));
+    // 削除は最後に並ぶ
+    assert_eq!(kinds.last(), Some(&("old-idea", ChangeKind::Deleted)));
+
+    let completed = changes.iter().find(|c| c.nota.id == "call-bob").unwrap();
+    assert_eq!(completed.old_status, Some(NotaStatus::next_action));
+    assert!(webhook::changes(&after, &after).is_empty());
+}
+
+// ペイロードに変更種別・新旧ステータス・項目全体・Slack用テキストが含まれることを確認
+#[test]
+fn test_payload_fields() {
+    let mut before = GtdData::new();
+    before.add(nota("call-bob", "Call Bob", NotaStatus::next_action));
+    let mut after = before.clone();
+    after.update("call-bob", nota("call-bob", "Call Bob", NotaStatus::done));
+
+    let changes = webhook::changes(&before, &after);
+    let payload = webhook::payload(&changes[0], "Complete call-bob");
+    assert_eq!(payload["change"], "status_changed");
+    assert_eq!(payload["id"], "call-bob");
+    assert_eq!(payload["old_status"], "next_action");
+    assert_eq!(payload["new_status"], "done");
+    assert_eq!(payload["item"]["title"], "Call Bob");
+    assert_eq!(payload["message"], "Complete call-bob");
+    assert_eq!(payload["text"], "Done: Call Bob");
+
+    // 削除された項目には新しいステータスがない
+    let deleted = webhook::changes(&after, &GtdData::new());
+    let payload = webhook::payload(&deleted[0], "Delete call-bob");
+    assert_eq!(payload["change"], "deleted");
+    assert!(payload["new_status"].is_null());
+    assert_eq!(payload["text"], "Deleted: Call Bob");
+}
+
+// 保存後に変更された項目がWebhook URLへPOSTされることを確認
+#[test]
+fn test_save_posts_changed_items() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("gtd.toml");
+    let mut data = GtdData::new();
+    data.add(nota("call-bob", "Call Bob", NotaStatus::next_action));
+    Storage::new(&file, false).save(&data).unwrap();
+
+    let (url, received) = listen_once();
+    let storage = Storage::new(&file, false).with_webhook(url);
+    data.update("call-bob", nota("call-bob", "Call Bob", NotaStatus::done));
+    storage
+        .save_with_message(&data, "Complete call-bob")
+        .unwrap();
+
+    let body = received.recv_timeout(Duration::from_secs(10)).unwrap();
+    let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(payload["id"], "call-bob");
+    assert_eq!(payload["new_status"], "done");
+    assert_eq!(payload["text"], "Done: Call Bob");
+}
@@ -0,0 +1,156 @@
+//! Integration tests for work-in-progress limits
+//!
+//! These tests cover parsing limits, warning about a status over its limit,
+//! and refusing moves into a full status when the limit is enforced.
+
+use gtd_mcp::wip::{WipLimit, WipMode};
+use gtd_mcp::{GtdServerHandler, Nota, NotaStatus};
+use tempfile::NamedTempFile;
+
+fn limit(s: &str) -> WipLimit {
+    s.parse().unwrap()
+}
+
+async fn handler_with(temp_file: &NamedTempFile, mode: WipMode) -> GtdServerHandler {
+    let handler = GtdServerHandler::new(temp_file.path().to_str().unwrap(), false)
+        .unwrap()
+        .with_wip_limits(vec![limit("next_action=2")], mode);
+    {
+        let mut data = handler.data.write().await;
+        for (id, status) in [
+            ("call-bank", NotaStatus::next_action),
+            ("file-taxes", NotaStatus::next_action),
+            ("fix-bike", NotaStatus::inbox),
+            ("buy-milk", NotaStatus::inbox),
+        ] {
+            data.add(Nota {
+                id: id.to_string(),
+                title: id.replace('-', " "),
+                status,
+                ..Default::default()
+            });
+        }
+    }
+    handler
+}
+
+async fn next_action(handler: &GtdServerHandler, ids: &[&str]) -> mcp_attr::Result<String> {
+    handler
+        .change_status(
+            ids.iter().map(|id| id.to_string()).collect(),
+            "next_action".to_string(),
+            None,
+            None,
+        )
+        .await
+}
+
+// STATUS=MAX の書式が解析され、不正な値や完了済みステータスは拒否されることを確認
+#[test]
+fn test_parse_limits() {
+    let parsed = limit(" next_action = 10");
+    assert_eq!(parsed.status, NotaStatus::next_action);
+    assert_eq!(parsed.max, 10);
+    assert_eq!(parsed.to_string(), "next_action=10");
+
+    for (input, expected) in [
+        ("next_action", "Use STATUS=MAX"),
+        ("someday=many", "Use STATUS=MAX"),
+        ("doing=3", "Use STATUS=MAX"),
+        ("done=5", "cannot be limited"),
+    ] {
+        let error = input.parse::<WipLimit>().unwrap_err();
+        assert!(error.contains(expected), "{}: {}", input, error);
+    }
+    assert_eq!("enforce".parse::<WipMode>(), Ok(WipMode::Enforce));
+    assert!("strict".parse::<WipMode>().is_err());
+}
+
+// warn モードでは移動は行われ、上限超過の警告が付くことを確認
+#[tokio::test]
+async fn test_warn_over_limit() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = handler_with(&temp_file, WipMode::Warn).await;
+
+    let response = next_action(&handler, &["fix-bike"]).await.unwrap();
+    assert!(
+        response.contains(
+            "Warning: next_action holds 3 items, over its WIP limit of 2. Consider finishing or deferring some"
+        ),
+        "{}",
+        response
+    );
+    let data = handler.data.read().await;
+    assert_eq!(
+        data.find_by_id("fix-bike").unwrap().status,
+        NotaStatus::next_action
+    );
+}
+
+// enforce モードでは満杯のステータスへの移動が拒否され、空きがあれば通ることを確認
+#[tokio::test]
+async fn test_enforce_limit() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let handler = handler_with(&temp_file, WipMode::Enforce).await;
+
+    let message = next_action(&handler, &["fix-bike"])
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("fix-bike: next_action is at its WIP limit (2/2)"),
+        "{}",
+        message
+    );
+
+    // すでに next_action の項目は数え直さない
+    next_action(&handler, &["call-bank"]).await.unwrap();
+
+    // 1 件空けると 1 件だけ移動できる
+    handler
+        .change_status(
+            vec!["call-bank".to_string()],
+            "done".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let response = next_action(&handler, &["fix-bike", "buy-milk"])
+        .await
+        .unwrap();
+    assert!(
+        response.contains("- fix-bike: inbox → next_action"),
+        "{}",
+        response
+    );
+    assert!(
+        response.contains("- buy-milk: next_action is at its WIP limit (2/2)"),
+        "{}",
+        response
+    );
+
+    // inbox で直接 next_action として登録する場合も同じ上限がかかる
+    let message = handler
+        .inbox(
+            "water-plants".to_string(),
+            "Water plants".to_string(),
+            "next_action".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err()
+        .to_error_object(false)
+        .message;
+    assert!(
+        message.contains("WIP limit error: next_action is at its WIP limit (2/2)"),
+        "{}",
+        message
+    );
+}